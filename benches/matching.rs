@@ -0,0 +1,57 @@
+//! Benchmarks comparing the fuzzy matching algorithms.
+//!
+//! Each algorithm matches a misspelled name and a misspelled email address
+//! against a corpus of 1000 synthetic profiles. Run with `cargo bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use git_setup_rs::config::types::Profile;
+use git_setup_rs::matching::{available_algorithms, FuzzyMatcher, ProfileFuzzyMatcher};
+
+const TEAMS: &[&str] = &["work", "personal", "oss", "client", "research", "infra", "mobile", "web"];
+const ORGS: &[&str] = &["acme", "globex", "initech", "umbrella", "hooli", "stark", "wayne", "wonka"];
+const PEOPLE: &[&str] = &["jane.doe", "john.smith", "maria.garcia", "wei.chen", "amara.okafor"];
+
+/// 1000 profiles with names like `work-acme-17` and matching email addresses.
+fn corpus() -> Vec<Profile> {
+    (0..1000)
+        .map(|i| {
+            let team = TEAMS[i % TEAMS.len()];
+            let org = ORGS[(i / TEAMS.len()) % ORGS.len()];
+            let person = PEOPLE[i % PEOPLE.len()];
+            Profile {
+                name: format!("{}-{}-{}", team, org, i),
+                git_user_email: format!("{}{}@{}.com", person, i, org),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn bench_algorithms(c: &mut Criterion) {
+    let profiles = corpus();
+    let names: Vec<&'static str> = available_algorithms().iter().map(|algorithm| algorithm.name()).collect();
+
+    let mut group = c.benchmark_group("match_name");
+    for name in &names {
+        let matcher = ProfileFuzzyMatcher::with_algorithm(name).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &profiles, |b, profiles| {
+            b.iter(|| matcher.find_matches(black_box("wrok-globex-42"), profiles))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("match_email");
+    for name in &names {
+        let matcher = ProfileFuzzyMatcher::with_algorithm(name).unwrap().with_updated_config(|config| {
+            config.match_name = false;
+            config.match_email = true;
+        });
+        group.bench_with_input(BenchmarkId::from_parameter(name), &profiles, |b, profiles| {
+            b.iter(|| matcher.find_matches(black_box("jnae.doe42@globex.com"), profiles))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_algorithms);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+//! Benchmarks of loading and listing a large profile set.
+//!
+//! Profiles are stored together in the config file, so start-up cost is one
+//! file read and TOML parse followed by in-memory listing. Both are measured
+//! for 200 synthetic profiles. Run with `cargo bench --bench profiles`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use git_setup_rs::config::loader::{ConfigLoader, ConfigLoaderTrait};
+use git_setup_rs::config::types::Profile;
+use git_setup_rs::profile::{manager::ProfileManagerImpl, ProfileManager};
+
+const PROFILE_COUNT: usize = 200;
+
+/// `PROFILE_COUNT` profiles with names like `profile-042` and a key and repo pattern each.
+fn profiles() -> Vec<Profile> {
+    (0..PROFILE_COUNT)
+        .map(|i| Profile {
+            name: format!("profile-{:03}", i),
+            git_user_name: Some(format!("User {}", i)),
+            git_user_email: format!("user{}@example{}.com", i, i % 10),
+            signing_key: Some(format!("~/.ssh/id_profile_{:03}.pub", i)),
+            repos: vec![format!("github.com/org-{}", i)],
+            tags: vec![format!("team-{}", i % 8)],
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn bench_profiles(c: &mut Criterion) {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("config.toml");
+    let loader = ConfigLoader::new(path.clone());
+    let mut config = loader.create_default();
+    config.profiles = profiles();
+    loader.save_to(&config, &path).unwrap();
+
+    c.bench_function("load_config_200_profiles", |b| {
+        b.iter(|| loader.load_from(black_box(&path)).unwrap())
+    });
+
+    let manager = ProfileManagerImpl::new();
+    manager.batch_create(profiles()).unwrap();
+    c.bench_function("list_200_profiles", |b| b.iter(|| black_box(manager.list().unwrap())));
+}
+
+criterion_group!(benches, bench_profiles);
+criterion_main!(benches);
@@ -32,6 +32,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ssh_key_path: None,
         allowed_signers: None,
         one_password: false,
+        require_passphrase: false,
+        is_archived: false,
+        description: None,
+        per_field_scope: HashMap::new(),
+        git_hooks_dir: None,
+        allowed_signers_content: None,
+        secret_store: None,
+        rotated_key_item: None,
+        tags: vec![],
     };
 
     let personal_profile = Profile {
@@ -54,6 +63,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ssh_key_path: None,
         allowed_signers: None,
         one_password: false,
+        require_passphrase: false,
+        is_archived: false,
+        description: None,
+        per_field_scope: HashMap::new(),
+        git_hooks_dir: None,
+        allowed_signers_content: None,
+        secret_store: None,
+        rotated_key_item: None,
+        tags: vec![],
     };
 
     let opensource_profile = Profile {
@@ -77,6 +95,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ssh_key_path: None,
         allowed_signers: None,
         one_password: false,
+        require_passphrase: false,
+        is_archived: false,
+        description: None,
+        per_field_scope: HashMap::new(),
+        git_hooks_dir: None,
+        allowed_signers_content: None,
+        secret_store: None,
+        rotated_key_item: None,
+        tags: vec![],
     };
 
     // Create a mock profile manager with our test profiles
@@ -91,7 +118,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     git_config.insert("remote.origin.url".to_string(), "git@github.com:company/awesome-project.git".to_string());
     let git = Arc::new(MockGitWrapper::new().with_config(git_config));
 
-    let detector = AutoDetector::new(profile_manager.clone(), git);
+    let detector = AutoDetector::new(profile_manager.clone(), git)?;
 
     // Simulate being in a company repository
     let temp_dir = tempfile::TempDir::new()?;
@@ -112,7 +139,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("-----------------------------------");
 
     let git2 = Arc::new(MockGitWrapper::new());
-    let detector2 = AutoDetector::new(profile_manager.clone(), git2);
+    let detector2 = AutoDetector::new(profile_manager.clone(), git2)?;
 
     let work_path = std::path::PathBuf::from("/home/user/work/my-project");
     match detector2.detect_in(&work_path)? {
@@ -149,7 +176,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     multiple_config.insert("user.email".to_string(), "johndoe@contributor.dev".to_string());
     let git3 = Arc::new(MockGitWrapper::new().with_config(multiple_config));
 
-    let detector3 = AutoDetector::new(profile_manager.clone(), git3.clone());
+    let detector3 = AutoDetector::new(profile_manager.clone(), git3.clone())?;
     let _opensource_path = std::path::PathBuf::from("/home/user/opensource/rust-project");
 
     let results = detector3.detect_all()?;
@@ -184,6 +211,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         check_include_if: true,
         check_hostname: false,   // Disable hostname matching
         check_git_config: true,
+        check_git_notes: false,
+        check_profile_hints: false,
+        custom_extractors: vec!["jira_project".to_string()],
         enable_cache: false,
     };
 
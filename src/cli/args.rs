@@ -1,10 +1,21 @@
-use clap::{Parser, ValueEnum};
+use crate::external::{git_print::ScriptFormat, gpg::DigestAlgorithm, ssh::SshKeyType};
+use crate::config::types::KeyType;
+use crate::profile::bulk::ProfileField;
+use clap::{ArgGroup, Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(name = "git-setup")]
 #[command(about = "Manage Git profiles with 1Password integration")]
 #[command(version)]
 #[command(disable_version_flag = true)]
+#[command(group(ArgGroup::new("field_target").args(["list", "bulk_update"]).multiple(true)))]
+#[command(group(ArgGroup::new("created_profile").args(["create_if_missing", "create_if_missing_then_delete"])))]
+#[command(group(ArgGroup::new("hooks_target").args(["install_hooks", "uninstall_hooks"])))]
+#[command(group(
+    ArgGroup::new("profile_source")
+        .args(["inherit_from", "create_if_missing", "create_if_missing_then_delete"])
+        .multiple(true)
+))]
 pub struct Args {
     /// Profile name to apply
     pub profile: Option<String>,
@@ -37,14 +48,410 @@ pub struct Args {
     #[arg(long, short = 'e')]
     pub edit: Option<String>,
 
+    /// Save profiles without running the validation hooks of the tool config
+    #[arg(long)]
+    pub skip_hooks: bool,
+
+    /// Archive a profile, hiding it from listings without deleting it
+    #[arg(long, value_name = "PROFILE", conflicts_with = "unarchive")]
+    pub archive: Option<String>,
+
+    /// Restore an archived profile
+    #[arg(long, value_name = "PROFILE")]
+    pub unarchive: Option<String>,
+
+    /// Copy the SOURCE profile to a new profile named DEST
+    #[arg(long, num_args = 2, value_names = ["SOURCE", "DEST"])]
+    pub duplicate: Option<Vec<String>>,
+
+    /// With --duplicate, overwrite an existing DEST profile
+    #[arg(long, requires = "duplicate")]
+    pub force: bool,
+
+    /// With --duplicate, keep the repos, match_patterns and include_if_dirs of SOURCE
+    #[arg(long, requires = "duplicate")]
+    pub keep_patterns: bool,
+
+    /// Store a NEW profile combining BASE and OVERLAY: fields set in OVERLAY win,
+    /// and repos, match_patterns, host_patterns and include_if_dirs are combined
+    #[arg(long, num_args = 3, value_names = ["BASE", "OVERLAY", "NEW"])]
+    pub merge_profiles: Option<Vec<String>>,
+
+    /// Rename the OLD profile to NEW, rewriting references to it in other profiles
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    pub rename: Option<Vec<String>>,
+
+    /// Show the fields that differ between two profiles (use --output json for scripts)
+    #[arg(long, num_args = 2, value_names = ["PROFILE_A", "PROFILE_B"])]
+    pub diff: Option<Vec<String>>,
+
+    /// List archived profiles
+    #[arg(long)]
+    pub list_archived: bool,
+
+    /// With --list, count the profiles by key type (e.g. `SSH: 12, GPG: 3, ..., Total: 21`)
+    #[arg(long, requires = "list")]
+    pub count_by_key_type: bool,
+
+    /// With --list, count the profiles by git config scope
+    #[arg(long, requires = "list")]
+    pub count_by_scope: bool,
+
+    /// With --list, count the profiles with and without 1Password
+    #[arg(long, requires = "list")]
+    pub count_by_one_password: bool,
+
+    /// Write a Markdown summary of the profile counts to PATH (default: PROFILES.md)
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "PROFILES.md")]
+    pub profiles_md: Option<String>,
+
     /// List all profiles
     #[arg(long, short = 'l')]
     pub list: bool,
 
+    /// With --list, rank profiles by fuzzy match against the profile name pattern
+    #[arg(long, requires_all = ["list", "profile"])]
+    pub fuzzy: bool,
+
+    /// With --fuzzy, explain how each match was scored
+    #[arg(long, requires = "fuzzy")]
+    pub explain: bool,
+
+    /// With --list, print the JSON Schema of a profile in the JSON output instead of listing profiles
+    #[arg(long, requires = "list")]
+    pub output_schema: bool,
+
+    /// Check a JSON file holding a profile, or an array of profiles, against the profile JSON Schema
+    #[arg(long, value_name = "FILE")]
+    pub validate_against_schema: Option<String>,
+
+    /// Check that a profile is consistent (signing key, vault, allowed signers, patterns) without applying it
+    #[arg(long, value_name = "PROFILE")]
+    pub validate: Option<String>,
+
+    /// With --validate, fail on warnings too
+    #[arg(long, requires = "validate")]
+    pub strict: bool,
+
+    /// Show which operations applying the profile depends on are available
+    #[arg(long, value_name = "PROFILE")]
+    pub list_capabilities: Option<String>,
+
+    /// With --list, show the path of each profile's generated gitconfig snippet
+    #[arg(long, requires = "list")]
+    pub output_profile_paths: bool,
+
+    /// List the generated gitconfig snippet files with their modification times
+    #[arg(long)]
+    pub list_snippets: bool,
+
     /// Import profiles from 1Password (agent.toml)
     #[arg(long, short = 'i')]
     pub import: bool,
 
+    /// Import all profiles or none: roll back if any profile fails to import
+    #[arg(long, requires = "import")]
+    pub atomic: bool,
+
+    /// Import the SSH keys GitHub Copilot created in ~/.ssh instead of 1Password profiles
+    #[arg(long, requires = "import")]
+    pub from_github_copilot: bool,
+
+    /// Apply the imported Copilot profile globally
+    #[arg(long, requires = "from_github_copilot")]
+    pub apply: bool,
+
+    /// Import the profiles of a bundle written by --export instead of 1Password profiles
+    #[arg(long, value_name = "FILE", requires = "import", conflicts_with = "from_github_copilot")]
+    pub from_bundle: Option<String>,
+
+    /// With --from-bundle, require the bundle to be signed by this GPG key
+    #[arg(long, value_name = "KEY_ID", requires = "from_bundle")]
+    pub trusted_signer: Option<String>,
+
+    /// Import the profiles of a TOML file written by --list --output toml instead of 1Password profiles
+    #[arg(long, value_name = "FILE", requires = "import", conflicts_with_all = ["from_github_copilot", "from_bundle"])]
+    pub from_toml: Option<String>,
+
+    /// Create profiles from the identities in a gitconfig FILE and the files its includeIf sections include
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "import",
+        conflicts_with_all = ["from_github_copilot", "from_bundle", "from_toml"]
+    )]
+    pub from_gitconfig: Option<String>,
+
+    /// Write profiles to a bundle FILE that --import --from-bundle reads
+    #[arg(long, value_name = "FILE")]
+    pub export: Option<String>,
+
+    /// With --export, only export these profiles (comma separated)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',', requires = "export")]
+    pub profiles: Option<Vec<String>>,
+
+    /// With --export, encrypt the bundle to the recipient's GPG key
+    #[arg(long, value_name = "KEY_ID", requires_all = ["export", "sign_with"])]
+    pub encrypt_for: Option<String>,
+
+    /// With --export, sign the bundle with your own GPG key
+    #[arg(long, value_name = "KEY_ID", requires = "export")]
+    pub sign_with: Option<String>,
+
+    /// Undo the last profile change
+    #[arg(long, conflicts_with = "redo")]
+    pub undo: bool,
+
+    /// Redo the last undone profile change
+    #[arg(long)]
+    pub redo: bool,
+
+    /// Report profiles that are not used by any git repository
+    #[arg(long)]
+    pub check_unused: bool,
+
+    /// Directory to search for git repositories (default: home directory)
+    #[arg(long, value_name = "DIR", requires = "check_unused")]
+    pub search_dir: Option<String>,
+
+    /// Search for git repositories recursively
+    #[arg(long, requires = "check_unused")]
+    pub recursive: bool,
+
+    /// Also report profiles not applied within this many days
+    #[arg(long, value_name = "DAYS", requires = "check_unused")]
+    pub unused_days: Option<u32>,
+
+    /// Delete the unused profiles after confirmation
+    #[arg(long, requires = "check_unused")]
+    pub delete_unused: bool,
+
+    /// Write the profile's git environment to a file sourced by the shell
+    #[arg(long)]
+    pub env_inject: bool,
+
+    /// Write only `unset` commands, clearing an injected environment
+    #[arg(long, requires = "env_inject", conflicts_with = "profile")]
+    pub clear: bool,
+
+    /// Print the environment to stdout instead of writing the file
+    #[arg(long, requires = "env_inject")]
+    pub stdout: bool,
+
+    /// Source the injected environment from this shell's startup file
+    #[arg(long, value_name = "SHELL")]
+    pub shell_install: Option<InstallShell>,
+
+    /// Show which profile is auto-detected for the current directory
+    #[arg(long)]
+    pub detect: bool,
+
+    /// Explain why no profile is detected, listing near-misses
+    #[arg(long, requires = "detect")]
+    pub explain_miss: bool,
+
+    /// Show the git identity and signing configuration in effect (with --verbose, where each value is set)
+    #[arg(long)]
+    pub status: bool,
+
+    /// Show profile usage statistics from the activation history
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Clear the activation history instead of showing statistics (requires --stats)
+    #[arg(long, requires = "stats")]
+    pub reset: bool,
+
+    /// Rate a profile's health from 0 to 100 (or every profile's, with --all)
+    #[arg(long, value_name = "NAME")]
+    pub health_score: Option<Option<String>>,
+
+    /// Show the health score of every profile, best first
+    #[arg(long, requires = "health_score")]
+    pub all: bool,
+
+    /// Share core.* and alias.* git config with your team as a note on a remote (with --global, the global config)
+    #[arg(long)]
+    pub push_config: bool,
+
+    /// Remote to push the shared config to (default: origin)
+    #[arg(long, value_name = "NAME", requires = "push_config")]
+    pub remote: Option<String>,
+
+    /// Config key pattern to share instead of core.* and alias.* (repeatable)
+    #[arg(long, value_name = "PATTERN", requires = "push_config")]
+    pub share_key: Vec<String>,
+
+    /// Report profiles sharing an email, signing key or 1Password key (use --output json for CI)
+    #[arg(long)]
+    pub duplicate_check: bool,
+
+    /// Check that the signing key of the given profile (or of every profile) can be used
+    #[arg(long)]
+    pub check_keys: bool,
+
+    /// Generate a new SSH key pair
+    #[arg(long)]
+    pub keygen: bool,
+
+    /// Store the generated key in this 1Password vault and delete the local private key
+    #[arg(long, value_name = "VAULT", requires = "keygen")]
+    pub store_in_op: Option<String>,
+
+    /// Algorithm of the generated key (default: ed25519)
+    #[arg(long, value_name = "TYPE", requires = "keygen")]
+    pub key_type: Option<SshKeyType>,
+
+    /// Title of the generated key (default: derived from the profile name)
+    #[arg(long, requires = "keygen")]
+    pub title: Option<String>,
+
+    /// Keep the private key on disk after storing it in 1Password
+    #[arg(long, requires = "store_in_op")]
+    pub keep_local_copy: bool,
+
+    /// Configure this profile to sign with the generated key
+    #[arg(long = "profile", value_name = "NAME", requires = "keygen")]
+    pub keygen_profile: Option<String>,
+
+    /// Start or stop the GPG agent
+    #[arg(long, value_name = "ACTION")]
+    pub gpg_agent: Option<GpgAgentAction>,
+
+    /// Archive the 1Password item of this profile's key and remove it from the profile
+    #[arg(long, value_name = "PROFILE")]
+    pub cleanup_key: Option<String>,
+
+    /// Permanently delete the item with --cleanup-key instead of archiving it
+    #[arg(long, requires = "cleanup_key")]
+    pub delete_item: bool,
+
+    /// Clean up the item with this title (e.g. a renamed key) and keep the profile's key
+    #[arg(long, value_name = "TITLE", requires = "cleanup_key")]
+    pub old_title: Option<String>,
+
+    /// Replace this profile's 1Password SSH key with a newly generated one in the same vault
+    #[arg(long, value_name = "PROFILE")]
+    pub rotate_key: Option<String>,
+
+    /// Title of the new key with --rotate-key (default: the old title with the rotation date)
+    #[arg(long, value_name = "TITLE", requires = "rotate_key", conflicts_with = "delete_old")]
+    pub new_title: Option<String>,
+
+    /// With --rotate-key, delete the 1Password item of the key replaced by the last rotation
+    #[arg(long, requires = "rotate_key")]
+    pub delete_old: bool,
+
+    /// Tag the PROFILE with TAG, adding it to the group of profiles applied with --tag TAG
+    #[arg(long, num_args = 2, value_names = ["PROFILE", "TAG"])]
+    pub tag_add: Option<Vec<String>>,
+
+    /// Remove TAG from the PROFILE
+    #[arg(long, num_args = 2, value_names = ["PROFILE", "TAG"])]
+    pub tag_remove: Option<Vec<String>>,
+
+    /// List the tags and the profiles tagged with each
+    #[arg(long)]
+    pub list_tags: bool,
+
+    /// Apply every profile tagged TAG, one after another in name order
+    #[arg(
+        long,
+        value_name = "TAG",
+        conflicts_with_all = ["profile", "inherit_from", "global_then_local", "watch", "print_only", "dry_run"]
+    )]
+    pub tag: Option<String>,
+
+    /// Show the effective tool configuration and the source of each value (use --output for json, yaml or toml)
+    #[arg(long)]
+    pub config_dump: bool,
+
+    /// Show what applying this profile would change in the git config (at the scope apply would use)
+    #[arg(long, value_name = "PROFILE")]
+    pub diff_from_applied: Option<String>,
+
+    /// Point git at this profile's git_hooks_dir by setting core.hooksPath
+    #[arg(long, value_name = "PROFILE")]
+    pub install_hooks: Option<String>,
+
+    /// Unset core.hooksPath so git uses the repository's own hooks again
+    #[arg(long)]
+    pub uninstall_hooks: bool,
+
+    /// Install or uninstall hooks in the repository's git config (.git/config)
+    #[arg(long, requires = "hooks_target", conflicts_with_all = ["global", "system"])]
+    pub local: bool,
+
+    /// Do not install the profile's git_hooks_dir when applying
+    #[arg(long)]
+    pub no_hooks: bool,
+
+    /// Import the GPG key stored in 1Password for this profile into the local keyring
+    #[arg(long, value_name = "PROFILE")]
+    pub import_op_gpg: Option<String>,
+
+    /// Show what would change without changing anything: the keys --import-op-gpg
+    /// would import, the profile --merge-profiles would store, or the git config changes
+    /// applying a profile would make
+    #[arg(long, conflicts_with_all = ["print_only", "write_gitconfig", "as_user", "global_then_local"])]
+    pub dry_run: bool,
+
+    /// Keep running and re-apply the detected profile whenever files in the working directory change
+    #[arg(
+        long,
+        conflicts_with_all = ["profile", "inherit_from", "global_then_local", "dry_run", "print_only", "write_gitconfig", "as_user"]
+    )]
+    pub watch: bool,
+
+    /// With --watch, milliseconds without changes to wait for before detecting again
+    #[arg(long, value_name = "MS", default_value_t = 500, requires = "watch")]
+    pub debounce_ms: u64,
+
+    /// Print a commented profile template for a key type (default: ssh) in the --output format
+    #[arg(long, value_name = "KEY_TYPE", num_args = 0..=1, default_missing_value = "ssh")]
+    pub generate_template: Option<KeyType>,
+
+    /// Add commented-out auto-detection examples to --generate-template
+    #[arg(long, requires = "generate_template")]
+    pub with_detection_examples: bool,
+
+    /// Change one field of many profiles at once (see --field, --value, --from)
+    #[arg(long, requires = "field")]
+    pub bulk_update: bool,
+
+    /// Field changed by --bulk-update, or the only profile field printed by --list
+    /// (e.g. `git_user_email`, for scripts)
+    #[arg(long, value_name = "FIELD", requires = "field_target")]
+    pub field: Option<ProfileField>,
+
+    /// Only update profiles whose current value of --field is VALUE (e.g. the old email domain)
+    #[arg(long, value_name = "VALUE", requires = "bulk_update")]
+    pub from: Option<String>,
+
+    /// New value of --field
+    #[arg(long, visible_alias = "to", value_name = "VALUE", requires = "bulk_update")]
+    pub value: Option<String>,
+
+    /// Only update profiles whose name matches this glob pattern
+    #[arg(long, value_name = "PATTERN", requires = "bulk_update")]
+    pub filter_name_glob: Option<String>,
+
+    /// Allow a bulk update that changes more than three profiles
+    #[arg(long, requires = "bulk_update")]
+    pub confirm: bool,
+
+    /// Print profiles sorted by this field (e.g. `git_user_email`)
+    #[arg(long, value_name = "FIELD")]
+    pub sort_by: Option<String>,
+
+    /// Direction of --sort-by
+    #[arg(long, value_enum, default_value_t = SortOrder::Asc, requires = "sort_by")]
+    pub direction: SortOrder,
+
+    /// Keep the --sort-by order: list profiles in it from now on
+    #[arg(long, requires = "sort_by")]
+    pub persist: bool,
+
     /// Output format
     #[arg(long, short = 'o', default_value = "tabular")]
     pub output: OutputFormat,
@@ -53,11 +460,253 @@ pub struct Args {
     #[arg(long, short = 'f')]
     pub file: Option<String>,
 
+    /// Override a table column width (e.g. `email=40`); may be repeated
+    #[arg(long = "column-width", value_name = "NAME=WIDTH")]
+    pub column_width: Vec<String>,
+
+    /// Colour scheme of the interactive interface: a TOML file mapping colour names
+    /// (background, primary, error, border, ...) to colour names or hex values
+    #[arg(long, value_name = "PATH")]
+    pub theme: Option<String>,
+
+    /// Render tables without borders
+    #[arg(long)]
+    pub no_border: bool,
+
+    /// Only list profiles last applied (with --stats, only count applies) on or after DATE (`2024-01-15`, `7 days ago`, `never`)
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<String>,
+
+    /// Only list profiles last applied (with --stats, only count applies) on or before DATE (`2024-01-31`, `yesterday`, `never`)
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<String>,
+
+    /// Apply a variant of this profile without saving it
+    #[arg(long, value_name = "PARENT", conflicts_with = "profile")]
+    pub inherit_from: Option<String>,
+
+    /// Override the user email of the inherited profile, or set it on a created one
+    #[arg(long, requires = "profile_source")]
+    pub email: Option<String>,
+
+    /// Override the user name of the inherited profile, or set it on a created one
+    #[arg(long, requires = "profile_source")]
+    pub name: Option<String>,
+
+    /// Override the signing key of the inherited profile, or set it on a created one
+    #[arg(long, requires = "profile_source")]
+    pub signing_key: Option<String>,
+
+    /// Save the combined inherited profile under a new name once it is applied
+    #[arg(long, value_name = "NEW_NAME", requires = "inherit_from")]
+    pub save_as: Option<String>,
+
+    /// Create the profile from --email (or $GIT_AUTHOR_EMAIL), --name and --signing-key if it does not exist
+    #[arg(long, requires = "profile")]
+    pub create_if_missing: bool,
+
+    /// Like --create-if-missing, but delete the created profile once it has been applied
+    #[arg(long, requires = "profile")]
+    pub create_if_missing_then_delete: bool,
+
+    /// Apply the profile created by --create-if-missing without saving it
+    #[arg(long, requires = "create_if_missing", conflicts_with = "create_if_missing_then_delete")]
+    pub no_persist: bool,
+
+    /// Key type of a profile created by --create-if-missing (default: ssh)
+    #[arg(long, value_name = "KEY_TYPE", requires = "created_profile")]
+    pub profile_key_type: Option<KeyType>,
+
+    /// Apply GLOBAL with global scope, then override it with the fields LOCAL sets in this repository
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["GLOBAL", "LOCAL"],
+        conflicts_with_all = ["profile", "inherit_from", "global", "system"]
+    )]
+    pub global_then_local: Option<Vec<String>>,
+
+    /// Read the key passphrase from this environment variable instead of prompting
+    #[arg(long, value_name = "VAR", conflicts_with = "passphrase_command")]
+    pub passphrase_env: Option<String>,
+
+    /// Read the key passphrase from the output of this command instead of prompting
+    #[arg(long, value_name = "CMD")]
+    pub passphrase_command: Option<String>,
+
+    /// Print which git config scope was resolved before applying
+    #[arg(long)]
+    pub show_scope: bool,
+
+    /// Make a signed test commit after applying to verify signing works
+    #[arg(long)]
+    pub test_signing: bool,
+
+    /// Restore the previous git config if the signing test fails
+    #[arg(long, requires = "test_signing")]
+    pub rollback_on_test_failure: bool,
+
+    /// Apply the keys listed in the profile's per_field_scope at their own scope instead of the profile's
+    #[arg(long, conflicts_with_all = ["write_gitconfig", "global_then_local"])]
+    pub scope_per_profile: bool,
+
+    /// Set the scope PROFILE applies GIT_KEY with when using --scope-per-profile (e.g. commit.gpgsign local)
+    #[arg(long, num_args = 3, value_names = ["PROFILE", "GIT_KEY", "SCOPE"])]
+    pub set_field_scope: Option<Vec<String>>,
+
+    /// Only set keys the target scope does not already inherit with the same value from a broader scope
+    #[arg(long, conflicts_with_all = ["write_gitconfig", "print_only"])]
+    pub scope_inherit: bool,
+
+    /// Print each key --scope-inherit skips
+    #[arg(long, requires = "scope_inherit")]
+    pub scope_inherit_verbose: bool,
+
+    /// Set every key at the target scope even if it is inherited (the default)
+    #[arg(long, conflicts_with = "scope_inherit")]
+    pub scope_override: bool,
+
+    /// Write the profile's git config to this gitconfig file instead of running git config
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["test_signing", "global_then_local"])]
+    pub write_gitconfig: Option<String>,
+
+    /// Print the git config commands applying the profile would run instead of running them
+    #[arg(long, conflicts_with_all = ["write_gitconfig", "global_then_local", "test_signing", "as_user"])]
+    pub print_only: bool,
+
+    /// Check that this host is in ~/.ssh/known_hosts before applying, offering to add it
+    #[arg(long, value_name = "HOSTNAME", conflicts_with = "print_only")]
+    pub verify_host: Option<String>,
+
+    /// Add the --verify-host host to known_hosts without asking
+    #[arg(long, requires = "verify_host")]
+    pub add_unknown_host: bool,
+
+    /// Abort applying if a capability the profile needs is unavailable (see --list-capabilities)
+    #[arg(long, conflicts_with = "print_only")]
+    pub check_capabilities: bool,
+
+    /// Warn when applying a profile whose GPG key expires within this many days
+    #[arg(long, value_name = "DAYS", default_value_t = 30)]
+    pub warn_expiry_days: i64,
+
+    /// Apply a profile even if its GPG key has expired
+    #[arg(long)]
+    pub allow_expired: bool,
+
+    /// Regenerate the profile's allowed signers file and point gpg.ssh.allowedSignersFile at it;
+    /// without a profile, rewrite the file of every profile with allowed_signers_content
+    #[arg(long, conflicts_with = "print_only")]
+    pub update_allowed_signers: bool,
+
+    /// With --update-allowed-signers, download the allowed signers entries from this URL
+    #[arg(long, value_name = "URL", requires_all = ["update_allowed_signers", "profile"], conflicts_with = "from_profile")]
+    pub from_file: Option<String>,
+
+    /// With --update-allowed-signers, use the allowed_signers_content of this profile
+    #[arg(long, value_name = "NAME", requires_all = ["update_allowed_signers", "profile"])]
+    pub from_profile: Option<String>,
+
+    /// Script format of --print-only
+    #[arg(long = "format", value_enum, default_value_t = ScriptFormat::Sh, requires = "print_only")]
+    pub print_format: ScriptFormat,
+
+    /// Record the applied profile in a note on HEAD (refs/notes/git-setup) for auto-detection
+    #[arg(long, conflicts_with = "write_gitconfig")]
+    pub annotate_commit: bool,
+
+    /// Run git config as this user via `sudo -n -H -u`, which needs a NOPASSWD sudo rule
+    #[arg(long, value_name = "USERNAME", conflicts_with_all = ["write_gitconfig", "global_then_local"])]
+    pub as_user: Option<String>,
+
+    /// Keep the existing content of the --write-gitconfig file instead of overwriting it
+    #[arg(long, requires = "write_gitconfig")]
+    pub merge: bool,
+
+    /// Also write the profile's environment variables (`GIT_AUTHOR_EMAIL=...`) to this file
+    #[arg(long, value_name = "PATH", conflicts_with = "global_then_local")]
+    pub output_env_file: Option<String>,
+
+    /// Format of --output-env-file
+    #[arg(long, value_enum, default_value_t = EnvFileFormat::Dotenv, requires = "output_env_file")]
+    pub env_file_format: EnvFileFormat,
+
+    /// Write secrets such as the key passphrase to --output-env-file
+    #[arg(long, requires = "output_env_file")]
+    pub include_secrets: bool,
+
+    /// After applying, sign and verify test data (GPG) or check the key is reachable (SSH)
+    #[arg(long)]
+    pub post_apply_verify: bool,
+
+    /// Wait for a lazily started GPG agent to answer before configuring signing
+    #[arg(long)]
+    pub wait_for_gpg_agent: bool,
+
+    /// Seconds to wait for the GPG agent with --wait-for-gpg-agent
+    #[arg(long = "timeout", value_name = "SECS", default_value_t = 30, requires = "wait_for_gpg_agent")]
+    pub gpg_agent_timeout: u64,
+
+    /// Digest algorithm for GPG signing checks (--post-apply-verify and --check-keys)
+    #[arg(long, value_name = "ALGORITHM")]
+    pub verify_algorithm: Option<DigestAlgorithm>,
+
+    /// Generate a shell completion script
+    #[arg(long, value_name = "SHELL")]
+    pub completions: Option<clap_complete::Shell>,
+
+    /// Complete profile names dynamically in the generated script (bash/zsh/fish)
+    #[arg(long, requires = "completions")]
+    pub dynamic: bool,
+
+    /// Only list profiles with this key type (for `__complete-profile-names`)
+    #[arg(long, hide = true, value_name = "TYPE")]
+    pub filter_by_key_type: Option<String>,
+
+    /// Sort listed profile names (for `__complete-profile-names`, default true)
+    #[arg(long, hide = true, num_args = 0..=1, default_missing_value = "true")]
+    pub sorted: Option<bool>,
+
     /// Show version information
     #[arg(long)]
     pub version: bool,
 }
 
+/// Shells supported by `--shell-install`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InstallShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Formats of `--output-env-file`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum EnvFileFormat {
+    /// `KEY="VALUE"` lines
+    #[default]
+    Dotenv,
+    /// `KEY=VALUE` lines for `docker run --env-file`
+    Docker,
+    /// `echo "KEY=VALUE" >> $GITHUB_ENV` commands
+    GithubActions,
+}
+
+/// Actions of `--gpg-agent`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GpgAgentAction {
+    Start,
+    Stop,
+}
+
+/// Direction of `--sort-by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum OutputFormat {
     Json,
@@ -153,6 +802,49 @@ mod tests {
         assert!(args.import);
     }
 
+    #[test]
+    fn test_atomic_flag_requires_import() {
+        let args = Args::try_parse_from(["git-setup", "--import", "--atomic"]).unwrap();
+        assert!(args.atomic);
+
+        assert!(Args::try_parse_from(["git-setup", "--atomic"]).is_err());
+    }
+
+    #[test]
+    fn test_detect_flags() {
+        let args = Args::try_parse_from(["git-setup", "--detect", "--explain-miss"]).unwrap();
+        assert!(args.detect);
+        assert!(args.explain_miss);
+
+        assert!(Args::try_parse_from(["git-setup", "--explain-miss"]).is_err());
+    }
+
+    #[test]
+    fn test_keygen_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "--keygen",
+            "--store-in-op",
+            "Work",
+            "--key-type",
+            "rsa",
+            "--title",
+            "Work key",
+            "--profile",
+            "work",
+        ])
+        .unwrap();
+        assert!(args.keygen);
+        assert_eq!(args.store_in_op, Some("Work".to_string()));
+        assert_eq!(args.key_type, Some(SshKeyType::Rsa));
+        assert_eq!(args.title, Some("Work key".to_string()));
+        assert_eq!(args.keygen_profile, Some("work".to_string()));
+        assert_eq!(args.profile, None);
+
+        assert!(Args::try_parse_from(["git-setup", "--store-in-op", "Work"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--keygen", "--keep-local-copy"]).is_err());
+    }
+
     #[test]
     fn test_output_format_options() {
         let args = Args::try_parse_from(&["git-setup", "--output", "json"]).unwrap();
@@ -237,4 +929,768 @@ mod tests {
         assert!(args.list);
         assert!(matches!(args.output, OutputFormat::Json));
     }
+
+    #[test]
+    fn test_table_layout_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "--list",
+            "--column-width",
+            "email=40",
+            "--column-width",
+            "name=12",
+            "--no-border",
+        ])
+        .unwrap();
+        assert_eq!(args.column_width, vec!["email=40".to_string(), "name=12".to_string()]);
+        assert!(args.no_border);
+
+        let args = Args::try_parse_from(["git-setup", "--list"]).unwrap();
+        assert!(args.column_width.is_empty());
+        assert!(!args.no_border);
+    }
+
+    #[test]
+    fn test_field_flag() {
+        let args = Args::try_parse_from(["git-setup", "--list", "work", "--field", "git_user_email"]).unwrap();
+        assert_eq!(args.field, Some(ProfileField::Email));
+        assert_eq!(args.profile.as_deref(), Some("work"));
+
+        assert!(Args::try_parse_from(["git-setup", "--field", "git_user_email"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--list", "--field", "email_address"]).is_err());
+    }
+
+    #[test]
+    fn test_stats_flag() {
+        let args = Args::try_parse_from(["git-setup", "--stats", "--since", "30 days ago", "-o", "json"]).unwrap();
+        assert!(args.stats);
+        assert_eq!(args.since.as_deref(), Some("30 days ago"));
+
+        let args = Args::try_parse_from(["git-setup", "--stats", "--reset"]).unwrap();
+        assert!(args.reset);
+        assert!(Args::try_parse_from(["git-setup", "--reset"]).is_err());
+    }
+
+    #[test]
+    fn test_health_score_flags() {
+        let args = Args::try_parse_from(["git-setup", "--health-score", "work"]).unwrap();
+        assert_eq!(args.health_score, Some(Some("work".to_string())));
+
+        let args = Args::try_parse_from(["git-setup", "--health-score", "--all"]).unwrap();
+        assert_eq!(args.health_score, Some(None));
+        assert!(args.all);
+
+        assert!(Args::try_parse_from(["git-setup", "--all"]).is_err());
+    }
+
+    #[test]
+    fn test_push_config_flags() {
+        let args = Args::try_parse_from([
+            "git-setup", "--push-config", "--remote", "team", "--share-key", "pull.*", "--share-key", "core.*",
+        ])
+        .unwrap();
+        assert!(args.push_config);
+        assert_eq!(args.remote.as_deref(), Some("team"));
+        assert_eq!(args.share_key, vec!["pull.*", "core.*"]);
+
+        assert!(Args::try_parse_from(["git-setup", "--remote", "team"]).is_err());
+    }
+
+    #[test]
+    fn test_since_until_flags() {
+        let args = Args::try_parse_from(["git-setup", "--list", "--since", "7 days ago", "--until", "2024-01-31"]).unwrap();
+        assert_eq!(args.since.as_deref(), Some("7 days ago"));
+        assert_eq!(args.until.as_deref(), Some("2024-01-31"));
+    }
+
+    #[test]
+    fn test_gpg_agent_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--wait-for-gpg-agent"]).unwrap();
+        assert!(args.wait_for_gpg_agent);
+        assert_eq!(args.gpg_agent_timeout, 30);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--wait-for-gpg-agent", "--timeout", "5"]).unwrap();
+        assert_eq!(args.gpg_agent_timeout, 5);
+
+        let args = Args::try_parse_from(["git-setup", "--gpg-agent", "stop"]).unwrap();
+        assert_eq!(args.gpg_agent, Some(GpgAgentAction::Stop));
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--timeout", "5"]).is_err());
+    }
+
+    #[test]
+    fn test_cleanup_key_flags() {
+        let args = Args::try_parse_from(["git-setup", "--cleanup-key", "work", "--delete-item", "--old-title", "Old Key"]).unwrap();
+        assert_eq!(args.cleanup_key.as_deref(), Some("work"));
+        assert!(args.delete_item);
+        assert_eq!(args.old_title.as_deref(), Some("Old Key"));
+
+        assert!(Args::try_parse_from(["git-setup", "--delete-item"]).is_err());
+    }
+
+    #[test]
+    fn test_field_scope_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--scope-per-profile"]).unwrap();
+        assert!(args.scope_per_profile);
+
+        let args = Args::try_parse_from(["git-setup", "--set-field-scope", "work", "commit.gpgsign", "local"]).unwrap();
+        assert_eq!(
+            args.set_field_scope,
+            Some(vec!["work".to_string(), "commit.gpgsign".to_string(), "local".to_string()])
+        );
+        assert!(Args::try_parse_from(["git-setup", "--set-field-scope", "work", "commit.gpgsign"]).is_err());
+    }
+
+    #[test]
+    fn test_config_dump_flag() {
+        let args = Args::try_parse_from(["git-setup", "--config-dump", "--output", "toml"]).unwrap();
+        assert!(args.config_dump);
+        assert!(matches!(args.output, OutputFormat::Toml));
+    }
+
+    #[test]
+    fn test_diff_from_applied_flag() {
+        let args = Args::try_parse_from(["git-setup", "--diff-from-applied", "work", "--global"]).unwrap();
+        assert_eq!(args.diff_from_applied.as_deref(), Some("work"));
+        assert!(args.global);
+    }
+
+    #[test]
+    fn test_hooks_flags() {
+        let args = Args::try_parse_from(["git-setup", "--install-hooks", "work", "--local"]).unwrap();
+        assert_eq!(args.install_hooks.as_deref(), Some("work"));
+        assert!(args.local);
+
+        let args = Args::try_parse_from(["git-setup", "--uninstall-hooks", "--global"]).unwrap();
+        assert!(args.uninstall_hooks);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--no-hooks"]).unwrap();
+        assert!(args.no_hooks);
+
+        assert!(Args::try_parse_from(["git-setup", "--local"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--uninstall-hooks", "--local", "--global"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--install-hooks", "work", "--uninstall-hooks"]).is_err());
+    }
+
+    #[test]
+    fn test_print_only_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--print-only"]).unwrap();
+        assert!(args.print_only);
+        assert_eq!(args.print_format, ScriptFormat::Sh);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--print-only", "--format", "make"]).unwrap();
+        assert_eq!(args.print_format, ScriptFormat::Make);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--format", "powershell"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "work", "--print-only", "--write-gitconfig", "out"]).is_err());
+    }
+
+    #[test]
+    fn test_verify_host_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--verify-host", "github.com", "--add-unknown-host"]).unwrap();
+        assert_eq!(args.verify_host.as_deref(), Some("github.com"));
+        assert!(args.add_unknown_host);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--add-unknown-host"]).is_err());
+    }
+
+    #[test]
+    fn test_scope_inherit_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--scope-inherit", "--scope-inherit-verbose"]).unwrap();
+        assert!(args.scope_inherit);
+        assert!(args.scope_inherit_verbose);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--scope-override"]).unwrap();
+        assert!(args.scope_override);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--scope-inherit", "--scope-override"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "work", "--scope-inherit-verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_schema_flags() {
+        let args = Args::try_parse_from(["git-setup", "--list", "--output-schema"]).unwrap();
+        assert!(args.output_schema);
+        assert!(Args::try_parse_from(["git-setup", "--output-schema"]).is_err());
+
+        let args = Args::try_parse_from(["git-setup", "--validate-against-schema", "profiles.json"]).unwrap();
+        assert_eq!(args.validate_against_schema, Some("profiles.json".to_string()));
+    }
+
+    #[test]
+    fn test_update_allowed_signers_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "work",
+            "--update-allowed-signers",
+            "--from-file",
+            "https://example.com/allowed_signers",
+        ])
+        .unwrap();
+        assert!(args.update_allowed_signers);
+        assert_eq!(args.from_file.as_deref(), Some("https://example.com/allowed_signers"));
+
+        let args = Args::try_parse_from(["git-setup", "--update-allowed-signers"]).unwrap();
+        assert!(args.update_allowed_signers);
+        assert!(args.profile.is_none());
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--from-profile", "team"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--update-allowed-signers", "--from-profile", "team"]).is_err());
+        assert!(Args::try_parse_from([
+            "git-setup", "work", "--update-allowed-signers", "--from-profile", "team", "--from-file", "https://x"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_key_expiry_flags() {
+        let args = Args::try_parse_from(["git-setup", "work"]).unwrap();
+        assert_eq!(args.warn_expiry_days, 30);
+        assert!(!args.allow_expired);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--warn-expiry-days", "7", "--allow-expired"]).unwrap();
+        assert_eq!(args.warn_expiry_days, 7);
+        assert!(args.allow_expired);
+    }
+
+    #[test]
+    fn test_capabilities_flags() {
+        let args = Args::try_parse_from(["git-setup", "--list-capabilities", "work"]).unwrap();
+        assert_eq!(args.list_capabilities.as_deref(), Some("work"));
+        assert!(args.profile.is_none());
+
+        let args = Args::try_parse_from(["git-setup", "work", "--check-capabilities"]).unwrap();
+        assert!(args.check_capabilities);
+        assert!(Args::try_parse_from(["git-setup", "work", "--check-capabilities", "--print-only"]).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_flags() {
+        let args = Args::try_parse_from(["git-setup", "--duplicate", "work", "client", "--force"]).unwrap();
+        assert_eq!(args.duplicate, Some(vec!["work".to_string(), "client".to_string()]));
+        assert!(args.force);
+        assert!(!args.keep_patterns);
+
+        assert!(Args::try_parse_from(["git-setup", "--duplicate", "work"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "work", "--keep-patterns"]).is_err());
+    }
+
+    #[test]
+    fn test_theme_flag() {
+        let args = Args::try_parse_from(["git-setup", "--theme", "~/.config/git-setup/theme.toml"]).unwrap();
+        assert_eq!(args.theme.as_deref(), Some("~/.config/git-setup/theme.toml"));
+        assert!(Args::try_parse_from(["git-setup"]).unwrap().theme.is_none());
+    }
+
+    #[test]
+    fn test_merge_profiles_flags() {
+        let args =
+            Args::try_parse_from(["git-setup", "--merge-profiles", "work", "client", "work-client", "--dry-run"]).unwrap();
+        assert_eq!(
+            args.merge_profiles,
+            Some(vec!["work".to_string(), "client".to_string(), "work-client".to_string()])
+        );
+        assert!(args.dry_run);
+
+        assert!(Args::try_parse_from(["git-setup", "--merge-profiles", "work", "client"]).is_err());
+    }
+
+    #[test]
+    fn test_rename_flags() {
+        let args = Args::try_parse_from(["git-setup", "--rename", "work", "acme"]).unwrap();
+        assert_eq!(args.rename, Some(vec!["work".to_string(), "acme".to_string()]));
+
+        assert!(Args::try_parse_from(["git-setup", "--rename", "work"]).is_err());
+    }
+
+    #[test]
+    fn test_diff_flags() {
+        let args = Args::try_parse_from(["git-setup", "--diff", "work", "work-eu", "--output", "json"]).unwrap();
+        assert_eq!(args.diff, Some(vec!["work".to_string(), "work-eu".to_string()]));
+        assert!(matches!(args.output, OutputFormat::Json));
+
+        assert!(Args::try_parse_from(["git-setup", "--diff", "work"]).is_err());
+    }
+
+    #[test]
+    fn test_validate_flags() {
+        let args = Args::try_parse_from(["git-setup", "--validate", "work", "--strict"]).unwrap();
+        assert_eq!(args.validate.as_deref(), Some("work"));
+        assert!(args.strict);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--strict"]).is_err());
+    }
+
+    #[test]
+    fn test_rotate_key_flags() {
+        let args = Args::try_parse_from(["git-setup", "--rotate-key", "work", "--new-title", "Work Key 2"]).unwrap();
+        assert_eq!(args.rotate_key.as_deref(), Some("work"));
+        assert_eq!(args.new_title.as_deref(), Some("Work Key 2"));
+        assert!(!args.delete_old);
+
+        let args = Args::try_parse_from(["git-setup", "--rotate-key", "work", "--delete-old"]).unwrap();
+        assert!(args.delete_old);
+
+        assert!(Args::try_parse_from(["git-setup", "--delete-old"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--rotate-key", "work", "--delete-old", "--new-title", "X"]).is_err());
+    }
+
+    #[test]
+    fn test_tag_flags() {
+        let args = Args::try_parse_from(["git-setup", "--tag-add", "work", "client-a"]).unwrap();
+        assert_eq!(args.tag_add, Some(vec!["work".to_string(), "client-a".to_string()]));
+
+        let args = Args::try_parse_from(["git-setup", "--tag-remove", "work", "client-a"]).unwrap();
+        assert_eq!(args.tag_remove, Some(vec!["work".to_string(), "client-a".to_string()]));
+
+        let args = Args::try_parse_from(["git-setup", "--tag", "client-a", "--global"]).unwrap();
+        assert_eq!(args.tag.as_deref(), Some("client-a"));
+        assert!(Args::try_parse_from(["git-setup", "--list-tags"]).unwrap().list_tags);
+
+        assert!(Args::try_parse_from(["git-setup", "--tag-add", "work"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "work", "--tag", "client-a"]).is_err());
+    }
+
+    #[test]
+    fn test_watch_flags() {
+        let args = Args::try_parse_from(["git-setup", "--watch"]).unwrap();
+        assert!(args.watch);
+        assert_eq!(args.debounce_ms, 500);
+
+        let args = Args::try_parse_from(["git-setup", "--watch", "--debounce-ms", "50", "--global"]).unwrap();
+        assert_eq!(args.debounce_ms, 50);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--watch"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--debounce-ms", "50"]).is_err());
+    }
+
+    #[test]
+    fn test_export_flags() {
+        let args = Args::try_parse_from([
+            "git-setup", "--export", "profiles.json", "--profiles", "work,personal", "--encrypt-for", "ABCD1234",
+            "--sign-with", "EF561234",
+        ])
+        .unwrap();
+        assert_eq!(args.export.as_deref(), Some("profiles.json"));
+        assert_eq!(args.profiles, Some(vec!["work".to_string(), "personal".to_string()]));
+        assert_eq!(args.encrypt_for.as_deref(), Some("ABCD1234"));
+        assert_eq!(args.sign_with.as_deref(), Some("EF561234"));
+
+        let args = Args::try_parse_from([
+            "git-setup", "--import", "--from-bundle", "profiles.json", "--trusted-signer", "EF561234",
+        ])
+        .unwrap();
+        assert_eq!(args.from_bundle.as_deref(), Some("profiles.json"));
+        assert_eq!(args.trusted_signer.as_deref(), Some("EF561234"));
+
+        assert!(Args::try_parse_from(["git-setup", "--encrypt-for", "ABCD1234"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--export", "profiles.json", "--encrypt-for", "ABCD1234"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--from-bundle", "profiles.json"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--import", "--trusted-signer", "EF561234"]).is_err());
+    }
+
+    #[test]
+    fn test_from_toml_flags() {
+        let args = Args::try_parse_from(["git-setup", "--import", "--from-toml", "profiles.toml"]).unwrap();
+        assert_eq!(args.from_toml.as_deref(), Some("profiles.toml"));
+
+        assert!(Args::try_parse_from(["git-setup", "--from-toml", "profiles.toml"]).is_err());
+        assert!(Args::try_parse_from([
+            "git-setup", "--import", "--from-toml", "profiles.toml", "--from-bundle", "profiles.json",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_from_gitconfig_flags() {
+        let args = Args::try_parse_from(["git-setup", "--import", "--from-gitconfig", "~/.gitconfig"]).unwrap();
+        assert_eq!(args.from_gitconfig.as_deref(), Some("~/.gitconfig"));
+
+        assert!(Args::try_parse_from(["git-setup", "--from-gitconfig", "~/.gitconfig"]).is_err());
+        assert!(Args::try_parse_from([
+            "git-setup", "--import", "--from-gitconfig", "~/.gitconfig", "--from-toml", "profiles.toml",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_import_op_gpg_flags() {
+        let args = Args::try_parse_from(["git-setup", "--import-op-gpg", "work", "--dry-run"]).unwrap();
+        assert_eq!(args.import_op_gpg.as_deref(), Some("work"));
+        assert!(args.dry_run);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--dry-run"]).unwrap();
+        assert!(args.dry_run);
+        assert!(Args::try_parse_from(["git-setup", "work", "--dry-run", "--print-only"]).is_err());
+    }
+
+    #[test]
+    fn test_from_github_copilot_flags() {
+        let args = Args::try_parse_from(["git-setup", "--import", "--from-github-copilot", "--apply"]).unwrap();
+        assert!(args.import);
+        assert!(args.from_github_copilot);
+        assert!(args.apply);
+
+        assert!(Args::try_parse_from(["git-setup", "--from-github-copilot"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--import", "--apply"]).is_err());
+    }
+
+    #[test]
+    fn test_archive_flags() {
+        let args = Args::try_parse_from(["git-setup", "--archive", "old"]).unwrap();
+        assert_eq!(args.archive.as_deref(), Some("old"));
+
+        let args = Args::try_parse_from(["git-setup", "--unarchive", "old"]).unwrap();
+        assert_eq!(args.unarchive.as_deref(), Some("old"));
+
+        let args = Args::try_parse_from(["git-setup", "--list-archived"]).unwrap();
+        assert!(args.list_archived);
+
+        assert!(Args::try_parse_from(["git-setup", "--archive", "a", "--unarchive", "b"]).is_err());
+    }
+
+    #[test]
+    fn test_generate_template_flags() {
+        let args = Args::try_parse_from(["git-setup", "--generate-template"]).unwrap();
+        assert_eq!(args.generate_template, Some(KeyType::Ssh));
+
+        let args = Args::try_parse_from([
+            "git-setup",
+            "--generate-template",
+            "gpg",
+            "--with-detection-examples",
+            "-o",
+            "yaml",
+        ])
+        .unwrap();
+        assert_eq!(args.generate_template, Some(KeyType::Gpg));
+        assert!(args.with_detection_examples);
+
+        assert!(Args::try_parse_from(["git-setup", "--generate-template", "rsa"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--with-detection-examples"]).is_err());
+    }
+
+    #[test]
+    fn test_sort_by_flags() {
+        let args = Args::try_parse_from(["git-setup", "--sort-by", "git_user_email", "--direction", "desc", "--persist"])
+            .unwrap();
+        assert_eq!(args.sort_by.as_deref(), Some("git_user_email"));
+        assert_eq!(args.direction, SortOrder::Desc);
+        assert!(args.persist);
+
+        let args = Args::try_parse_from(["git-setup", "--sort-by", "name"]).unwrap();
+        assert_eq!(args.direction, SortOrder::Asc);
+        assert!(!args.persist);
+
+        assert!(Args::try_parse_from(["git-setup", "--persist"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--direction", "desc"]).is_err());
+    }
+
+    #[test]
+    fn test_count_flags() {
+        let args = Args::try_parse_from(["git-setup", "--list", "--count-by-key-type", "--count-by-scope", "-o", "json"])
+            .unwrap();
+        assert!(args.count_by_key_type);
+        assert!(args.count_by_scope);
+        assert!(!args.count_by_one_password);
+        assert!(Args::try_parse_from(["git-setup", "--count-by-one-password"]).is_err());
+
+        let args = Args::try_parse_from(["git-setup", "--profiles-md"]).unwrap();
+        assert_eq!(args.profiles_md.as_deref(), Some("PROFILES.md"));
+        let args = Args::try_parse_from(["git-setup", "--profiles-md", "docs/team.md"]).unwrap();
+        assert_eq!(args.profiles_md.as_deref(), Some("docs/team.md"));
+    }
+
+    #[test]
+    fn test_fuzzy_explain_flags() {
+        let args = Args::try_parse_from(["git-setup", "--list", "--fuzzy", "--explain", "pers"]).unwrap();
+        assert!(args.fuzzy);
+        assert!(args.explain);
+        assert_eq!(args.profile.as_deref(), Some("pers"));
+
+        assert!(Args::try_parse_from(["git-setup", "--list", "--fuzzy"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--list", "--explain", "pers"]).is_err());
+    }
+
+    #[test]
+    fn test_snippet_path_flags() {
+        let args = Args::try_parse_from(["git-setup", "--list", "--output-profile-paths"]).unwrap();
+        assert!(args.output_profile_paths);
+        assert!(Args::try_parse_from(["git-setup", "--output-profile-paths"]).is_err());
+
+        let args = Args::try_parse_from(["git-setup", "--list-snippets"]).unwrap();
+        assert!(args.list_snippets);
+    }
+
+    #[test]
+    fn test_bulk_update_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "--bulk-update",
+            "--field",
+            "email-domain",
+            "--from",
+            "old.com",
+            "--to",
+            "new.com",
+            "--filter-name-glob",
+            "work-*",
+        ])
+        .unwrap();
+        assert!(args.bulk_update);
+        assert_eq!(args.field, Some(ProfileField::EmailDomain));
+        assert_eq!(args.from.as_deref(), Some("old.com"));
+        assert_eq!(args.value.as_deref(), Some("new.com"));
+        assert_eq!(args.filter_name_glob.as_deref(), Some("work-*"));
+        assert!(!args.confirm);
+
+        assert!(Args::try_parse_from(["git-setup", "--bulk-update"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--field", "vault"]).is_err());
+    }
+
+    #[test]
+    fn test_show_scope_flag() {
+        let args = Args::try_parse_from(["git-setup", "work", "--show-scope"]).unwrap();
+        assert!(args.show_scope);
+    }
+
+    #[test]
+    fn test_verify_algorithm_values() {
+        let args = Args::try_parse_from(["git-setup", "work", "--post-apply-verify", "--verify-algorithm", "sha512"])
+            .unwrap();
+        assert!(args.post_apply_verify);
+        assert_eq!(args.verify_algorithm, Some(DigestAlgorithm::Sha512));
+        assert!(Args::try_parse_from(["git-setup", "--check-keys", "--verify-algorithm", "md5"]).is_err());
+    }
+
+    #[test]
+    fn test_write_gitconfig_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--write-gitconfig", "/root/.gitconfig", "--merge"]).unwrap();
+        assert_eq!(args.write_gitconfig.as_deref(), Some("/root/.gitconfig"));
+        assert!(args.merge);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--merge"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "work", "--write-gitconfig", "out", "--test-signing"]).is_err());
+    }
+
+    #[test]
+    fn test_annotate_commit_flag() {
+        let args = Args::try_parse_from(["git-setup", "work", "--annotate-commit"]).unwrap();
+        assert!(args.annotate_commit);
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--annotate-commit", "--write-gitconfig", "out"]).is_err());
+    }
+
+    #[test]
+    fn test_as_user_flag() {
+        let args = Args::try_parse_from(["git-setup", "work", "--as-user", "deploy"]).unwrap();
+        assert_eq!(args.as_user.as_deref(), Some("deploy"));
+
+        assert!(Args::try_parse_from(["git-setup", "work", "--as-user", "deploy", "--write-gitconfig", "out"]).is_err());
+    }
+
+    #[test]
+    fn test_output_env_file_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "work",
+            "--write-gitconfig",
+            "out/.gitconfig",
+            "--output-env-file",
+            "out/.env",
+            "--env-file-format",
+            "github-actions",
+        ])
+        .unwrap();
+        assert_eq!(args.output_env_file.as_deref(), Some("out/.env"));
+        assert_eq!(args.env_file_format, EnvFileFormat::GithubActions);
+        assert!(!args.include_secrets);
+
+        let args = Args::try_parse_from(["git-setup", "work", "--output-env-file", ".env"]).unwrap();
+        assert_eq!(args.env_file_format, EnvFileFormat::Dotenv);
+        assert!(Args::try_parse_from(["git-setup", "work", "--include-secrets"]).is_err());
+    }
+
+    #[test]
+    fn test_test_signing_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--test-signing"]).unwrap();
+        assert!(args.test_signing);
+        assert!(!args.rollback_on_test_failure);
+
+        let args = Args::try_parse_from([
+            "git-setup",
+            "work",
+            "--test-signing",
+            "--rollback-on-test-failure",
+        ])
+        .unwrap();
+        assert!(args.rollback_on_test_failure);
+
+        // Rollback only makes sense together with the signing test
+        let result = Args::try_parse_from(["git-setup", "work", "--rollback-on-test-failure"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_undo_redo_flags() {
+        let args = Args::try_parse_from(["git-setup", "--undo"]).unwrap();
+        assert!(args.undo);
+        assert!(!args.redo);
+
+        let args = Args::try_parse_from(["git-setup", "--redo"]).unwrap();
+        assert!(args.redo);
+
+        let result = Args::try_parse_from(["git-setup", "--undo", "--redo"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_completions_flags() {
+        let args = Args::try_parse_from(["git-setup", "--completions", "bash", "--dynamic"]).unwrap();
+        assert!(matches!(args.completions, Some(clap_complete::Shell::Bash)));
+        assert!(args.dynamic);
+
+        // --dynamic only applies when generating completions
+        assert!(Args::try_parse_from(["git-setup", "--dynamic"]).is_err());
+    }
+
+    #[test]
+    fn test_complete_profile_names_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "__complete-profile-names",
+            "--filter-by-key-type",
+            "ssh",
+        ])
+        .unwrap();
+        assert_eq!(args.profile, Some("__complete-profile-names".to_string()));
+        assert_eq!(args.filter_by_key_type, Some("ssh".to_string()));
+        assert_eq!(args.sorted, None);
+
+        let args = Args::try_parse_from(["git-setup", "__complete-profile-names", "--sorted"]).unwrap();
+        assert_eq!(args.sorted, Some(true));
+
+        let args =
+            Args::try_parse_from(["git-setup", "__complete-profile-names", "--sorted", "false"]).unwrap();
+        assert_eq!(args.sorted, Some(false));
+    }
+
+    #[test]
+    fn test_inherit_from_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "--inherit-from",
+            "work",
+            "--email",
+            "client@example.com",
+            "--save-as",
+            "client",
+        ])
+        .unwrap();
+        assert_eq!(args.inherit_from, Some("work".to_string()));
+        assert_eq!(args.email, Some("client@example.com".to_string()));
+        assert_eq!(args.name, None);
+        assert_eq!(args.save_as, Some("client".to_string()));
+
+        // Overrides only make sense on top of an inherited profile
+        assert!(Args::try_parse_from(["git-setup", "--email", "a@b.com"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "work", "--inherit-from", "personal"]).is_err());
+    }
+
+    #[test]
+    fn test_create_if_missing_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "ci",
+            "--create-if-missing",
+            "--email",
+            "ci@example.com",
+            "--profile-key-type",
+            "gpg",
+            "--no-persist",
+        ])
+        .unwrap();
+        assert!(args.create_if_missing);
+        assert!(args.no_persist);
+        assert_eq!(args.email.as_deref(), Some("ci@example.com"));
+        assert_eq!(args.profile_key_type, Some(KeyType::Gpg));
+
+        let args = Args::try_parse_from(["git-setup", "ci", "--create-if-missing-then-delete"]).unwrap();
+        assert!(args.create_if_missing_then_delete);
+
+        assert!(Args::try_parse_from(["git-setup", "--create-if-missing"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "ci", "--create-if-missing", "--create-if-missing-then-delete"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "ci", "--create-if-missing-then-delete", "--no-persist"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "ci", "--profile-key-type", "gpg"]).is_err());
+    }
+
+    #[test]
+    fn test_global_then_local_flag() {
+        let args = Args::try_parse_from(["git-setup", "--global-then-local", "personal", "work"]).unwrap();
+        assert_eq!(
+            args.global_then_local,
+            Some(vec!["personal".to_string(), "work".to_string()])
+        );
+
+        assert!(Args::try_parse_from(["git-setup", "--global-then-local", "personal"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--global-then-local", "a", "b", "--global"]).is_err());
+    }
+
+    #[test]
+    fn test_passphrase_source_flags() {
+        let args = Args::try_parse_from(["git-setup", "work", "--passphrase-env", "KEY_PASS"]).unwrap();
+        assert_eq!(args.passphrase_env, Some("KEY_PASS".to_string()));
+
+        let args =
+            Args::try_parse_from(["git-setup", "work", "--passphrase-command", "pass show key"]).unwrap();
+        assert_eq!(args.passphrase_command, Some("pass show key".to_string()));
+
+        assert!(Args::try_parse_from([
+            "git-setup",
+            "work",
+            "--passphrase-env",
+            "KEY_PASS",
+            "--passphrase-command",
+            "pass show key",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_unused_flags() {
+        let args = Args::try_parse_from([
+            "git-setup",
+            "--check-unused",
+            "--search-dir",
+            "~/src",
+            "--recursive",
+            "--unused-days",
+            "90",
+            "--delete-unused",
+        ])
+        .unwrap();
+        assert!(args.check_unused);
+        assert_eq!(args.search_dir, Some("~/src".to_string()));
+        assert!(args.recursive);
+        assert_eq!(args.unused_days, Some(90));
+        assert!(args.delete_unused);
+
+        assert!(Args::try_parse_from(["git-setup", "--recursive"]).is_err());
+    }
+
+    #[test]
+    fn test_env_inject_flags() {
+        let args = Args::try_parse_from(["git-setup", "--env-inject", "work"]).unwrap();
+        assert!(args.env_inject);
+        assert_eq!(args.profile, Some("work".to_string()));
+
+        let args = Args::try_parse_from(["git-setup", "--env-inject", "--clear"]).unwrap();
+        assert!(args.clear);
+        assert!(Args::try_parse_from(["git-setup", "--env-inject", "--clear", "work"]).is_err());
+        assert!(Args::try_parse_from(["git-setup", "--clear"]).is_err());
+
+        let args = Args::try_parse_from(["git-setup", "--shell-install", "fish"]).unwrap();
+        assert_eq!(args.shell_install, Some(InstallShell::Fish));
+        assert!(Args::try_parse_from(["git-setup", "--shell-install", "powershell"]).is_err());
+    }
 }
@@ -1,3 +1,3 @@
 pub mod args;
 
-pub use args::{Args, OutputFormat};
+pub use args::{Args, EnvFileFormat, GpgAgentAction, InstallShell, OutputFormat, SortOrder};
@@ -5,8 +5,8 @@
 use super::{Command, CommandContext};
 use crate::{
     cli::Args,
-    config::types::{Profile, KeyType, Scope},
-    error::{GitSetupError, Result},
+    config::types::{Profile, KeyType, Scope, SecretStoreKind},
+    error::{GitSetupError, Result, ResultExt},
 };
 use async_trait::async_trait;
 
@@ -42,6 +42,15 @@ impl AddCommand {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         Ok(profile)
@@ -64,7 +73,7 @@ impl AddCommand {
         }
 
         // Validate 1Password configuration
-        if profile.one_password {
+        if profile.key_store() == Some(SecretStoreKind::OnePassword) {
             if profile.vault_name.is_none() {
                 return Err(GitSetupError::Git("Vault name is required for 1Password integration".to_string()));
             }
@@ -96,7 +105,7 @@ impl AddCommand {
         println!("  Key type: {:?}", profile.key_type);
         println!("  Scope: {:?}", profile.scope.as_ref().unwrap_or(&Scope::Local));
         
-        if profile.one_password {
+        if profile.key_store() == Some(SecretStoreKind::OnePassword) {
             println!("  1Password: Enabled");
             if let Some(vault) = &profile.vault_name {
                 println!("  Vault: {}", vault);
@@ -129,7 +138,8 @@ impl Command for AddCommand {
         self.validate_profile(&profile)?;
 
         // Save the profile
-        context.profile_manager.write(&profile)?;
+        context.profile_manager.write(&profile)
+            .with_context(format!("while adding profile '{}'", profile_name))?;
 
         // Print success message
         if !args.quiet {
@@ -177,15 +187,16 @@ mod tests {
     /// Test adding a new profile successfully.
     #[tokio::test]
     async fn test_add_profile_success() {
-        let profile_manager = Arc::new(MockProfileManager::new());
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let args = create_add_args("new-profile");
         let cmd = AddCommand::new();
         let result = cmd.execute(&args, &context).await;
 
         assert!(result.is_ok());
+        assert!(profile_manager.was_called_with_create("new-profile"));
     }
 
     /// Test adding a profile that already exists.
@@ -208,11 +219,20 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let args = create_add_args("existing");
         let cmd = AddCommand::new();
@@ -220,6 +240,7 @@ mod tests {
 
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("already exists"));
+        assert_eq!(profile_manager.create_call_count(), 0);
     }
 
     /// Test validation of profile data.
@@ -262,6 +283,9 @@ mod tests {
             name: "test".to_string(),
             git_user_email: "test@example.com".to_string(),
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
             vault_name: None,
             ..Default::default()
         };
@@ -274,6 +298,9 @@ mod tests {
             name: "test".to_string(),
             git_user_email: "test@example.com".to_string(),
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
             vault_name: Some("vault".to_string()),
             ssh_key_title: None,
             ..Default::default()
@@ -0,0 +1,97 @@
+//! Allowed signers update command implementation for git-setup-rs.
+//!
+//! `--update-allowed-signers` without a profile rewrites the allowed signers
+//! file of every profile that has `allowed_signers_content`, e.g. after the
+//! entries were edited. With a profile, the apply command updates that
+//! profile's file instead.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{Result, ResultExt},
+    profile::allowed_signers::AllowedSignersUpdater,
+};
+use async_trait::async_trait;
+
+/// Command implementation for refreshing the allowed signers files of all profiles.
+pub struct AllowedSignersCommand;
+
+impl AllowedSignersCommand {
+    /// Create a new AllowedSignersCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AllowedSignersCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for AllowedSignersCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let updated = AllowedSignersUpdater::new(context.profile_manager.clone(), context.git_wrapper.clone())
+            .update_all()
+            .with_context("while updating allowed signers files")?;
+
+        if !args.quiet {
+            if updated.is_empty() {
+                println!("No profile has allowed_signers_content");
+            }
+            for (name, path) in &updated {
+                println!("✓ Updated allowed signers of '{}': {}", name, path.display());
+            }
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "update-allowed-signers"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rewrite the allowed signers file of every profile"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::config::types::Profile;
+    use crate::external::git::MockGitWrapper;
+    use crate::profile::mock::MockProfileManager;
+    use std::sync::Arc;
+
+    /// Test that every profile with allowed signers entries gets its file rewritten.
+    #[tokio::test]
+    async fn test_update_allowed_signers_of_all_profiles() {
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                allowed_signers_content: Some("jane@work.com ssh-ed25519 AAAAC3Nza".to_string()),
+                ..Default::default()
+            },
+            Profile {
+                name: "personal".to_string(),
+                git_user_email: "jane@home.net".to_string(),
+                ..Default::default()
+            },
+        ]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+        let args = Args {
+            update_allowed_signers: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        AllowedSignersCommand::new().execute(&args, &context).await.unwrap();
+        assert!(git_wrapper.allowed_signers("work").is_some());
+        assert!(git_wrapper.allowed_signers("personal").is_none());
+    }
+}
@@ -1,28 +1,250 @@
 //! Apply command implementation for git-setup-rs.
 //!
 //! This command applies a git profile, configuring git user settings and signing keys.
-
-use super::{Command, CommandContext};
+//! With `--write-gitconfig <PATH>`, the settings are written to a gitconfig file
+//! (e.g. for a container image) instead of the git config of this machine.
+//! With `--create-if-missing`, a profile that does not exist yet is created
+//! from `--email`, `--name` and `--signing-key`, e.g. in CI.
+//! `--output-env-file` also writes the applied identity as environment
+//! variables, e.g. for a container next to a `--write-gitconfig` file.
+//! If a step fails part way, the git config keys already changed are restored
+//! so the config is never left half-applied.
+//! With `--scope-per-profile`, keys listed in the profile's `per_field_scope`
+//! are written at their own scope (e.g. `commit.gpgsign` locally only).
+//! A profile's `git_hooks_dir` is installed as `core.hooksPath` unless
+//! `--no-hooks` is given.
+//! `--print-only` prints the `git config` commands instead of running them,
+//! as a shell, PowerShell or Makefile script (`--format`).
+//! `--dry-run` prints a table of the git config keys applying would add,
+//! change or remove, next to their current values, without changing them.
+//! With `--scope-inherit`, keys the target scope already inherits with the
+//! same value from a broader scope (e.g. global config when applying locally)
+//! are not set again.
+//! `--verify-host <HOST>` first checks that the host is in `~/.ssh/known_hosts`
+//! and offers to add it (without asking with `--add-unknown-host`).
+//! `--update-allowed-signers` regenerates the profile's allowed signers file,
+//! from another profile (`--from-profile`) or a URL (`--from-file`), and
+//! points `gpg.ssh.allowedSignersFile` at it.
+//! Profiles with `secret_store = "bitwarden"` load their SSH key from
+//! Bitwarden instead of 1Password.
+//! Capabilities the profile needs that are unavailable (see
+//! `--list-capabilities`) are warned about, or abort the apply with
+//! `--check-capabilities`.
+//! `--watch` keeps running and re-applies the detected profile whenever files
+//! in the working directory change, once they settled for `--debounce-ms`.
+//! A GPG key expiring within `--warn-expiry-days` is warned about; an expired
+//! one aborts the apply unless `--allow-expired` is given.
+
+use super::{
+    capabilities::{CapabilityChecker, CapabilityStatus},
+    check_keys::verify_signing,
+    env_inject::{env_file_vars, render_env_file, write_env_file},
+    gpg_agent::wait_for_agent,
+    Command, CommandContext,
+};
 use crate::{
     cli::Args,
-    config::types::{Profile, KeyType, Scope},
-    error::{GitSetupError, Result},
-    external::git::GitConfigScope,
-    detection::RepositoryContext,
+    config::types::{Profile, ProfileOverrides, KeyType, SecretStoreKind},
+    error::{ErrorContext, GitSetupError, Result, ResultExt},
+    external::{
+        git::{GitConfigScope, GitWrapper},
+        git_dry_run::DryRunGitWrapper,
+        git_file::FilesystemGitWrapper,
+        git_inherit::InheritingGitWrapper,
+        git_print::PrintOnlyGitWrapper,
+        git_sudo::SudoGitWrapper,
+        gpg::days_until_expiry,
+        hooks::{HooksManager, HOOKS_PATH_KEY},
+        ssh::{KnownHostsChecker, SshKnownHostsChecker, SystemSshKeyGenerator},
+    },
+    detection::{RepositoryContext, PROFILE_NOTES_REF},
+    output::TableFormatter,
+    platform::{PlatformPaths, SystemPlatform},
+    profile::{
+        activation::ActivationRecord,
+        allowed_signers::{AllowedSignersUpdater, SignersSource, ALLOWED_SIGNERS_KEY},
+        ProfileManager,
+    },
 };
 use async_trait::async_trait;
+use is_terminal::IsTerminal;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+/// Git config keys that applying a profile may modify.
+pub(crate) const APPLIED_CONFIG_KEYS: &[&str] = &[
+    "user.email",
+    "user.name",
+    "user.signingkey",
+    "gpg.format",
+    "gpg.ssh.allowedSignersFile",
+    "gpg.x509.program",
+    "commit.gpgsign",
+    "core.hooksPath",
+];
+
+/// Colour of warnings about expiring keys on a terminal.
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// Deletes an ephemeral profile when dropped.
+///
+/// Holds the profile created by `--create-if-missing-then-delete` so it is
+/// removed again once the apply has finished, whether or not it succeeded.
+pub struct ProfileGuard {
+    profile_manager: Arc<dyn ProfileManager>,
+    name: String,
+}
+
+impl ProfileGuard {
+    /// Guard the profile `name`, deleting it from `profile_manager` on drop.
+    pub fn new(profile_manager: Arc<dyn ProfileManager>, name: impl Into<String>) -> Self {
+        Self {
+            profile_manager,
+            name: name.into(),
+        }
+    }
+}
+
+impl Drop for ProfileGuard {
+    fn drop(&mut self) {
+        // Nothing sensible can be done about a failure while unwinding
+        let _ = self.profile_manager.delete(&self.name);
+    }
+}
+
+/// Git config changes of an apply that can be undone.
+///
+/// Records the values of the keys applying may modify before anything is
+/// changed, so a failed apply can put them back.
+pub struct ApplyTransaction {
+    /// Value of each key at the scope it is applied with, before the apply
+    snapshot: HashMap<String, (GitConfigScope, Option<String>)>,
+    git: Arc<dyn GitWrapper>,
+}
+
+impl ApplyTransaction {
+    /// Snapshot the keys in [`APPLIED_CONFIG_KEYS`] at `scope`.
+    pub fn begin(git: Arc<dyn GitWrapper>, scope: GitConfigScope) -> Result<Self> {
+        Self::begin_per_key(git, |_| scope.clone())
+    }
+
+    /// Snapshot the keys in [`APPLIED_CONFIG_KEYS`], each at the scope `scope_of` returns for it.
+    pub fn begin_per_key(git: Arc<dyn GitWrapper>, scope_of: impl Fn(&str) -> GitConfigScope) -> Result<Self> {
+        let snapshot = APPLIED_CONFIG_KEYS
+            .iter()
+            .map(|key| {
+                let scope = scope_of(key);
+                let value = git.get_config(key, Some(scope.clone()))?;
+                Ok((key.to_string(), (scope, value)))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { snapshot, git })
+    }
+
+    /// Keep the changes made since [`ApplyTransaction::begin`].
+    pub fn commit(self) {}
+
+    /// Restore every snapshotted key: set the keys that had a value and unset the others.
+    ///
+    /// A key that cannot be restored does not stop the others from being restored.
+    ///
+    /// # Errors
+    /// Returns an error naming every key that could not be restored
+    pub fn rollback(self) -> Result<()> {
+        let mut failures: Vec<String> = self
+            .snapshot
+            .iter()
+            .filter_map(|(key, (scope, value))| {
+                let restored = match value {
+                    Some(value) => self.git.set_config(key, value, scope.clone()),
+                    None => self.git.unset_config(key, scope.clone()),
+                };
+                restored.err().map(|e| format!("{} ({})", key, e))
+            })
+            .collect();
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        failures.sort();
+        Err(GitSetupError::Git(format!("could not restore {}", failures.join(", "))))
+    }
+}
 
 /// Command implementation for applying git profiles.
-pub struct ApplyCommand;
+pub struct ApplyCommand {
+    known_hosts: Arc<dyn KnownHostsChecker>,
+    /// Environment variables that take precedence over the process environment
+    env: HashMap<String, String>,
+}
 
 impl ApplyCommand {
     /// Create a new ApplyCommand instance.
     pub fn new() -> Self {
-        Self
+        Self {
+            known_hosts: Arc::new(SshKnownHostsChecker::new()),
+            env: HashMap::new(),
+        }
+    }
+
+    /// Check `--verify-host` hosts with `known_hosts` instead of `~/.ssh/known_hosts`.
+    pub fn with_known_hosts_checker(mut self, known_hosts: Arc<dyn KnownHostsChecker>) -> Self {
+        self.known_hosts = known_hosts;
+        self
+    }
+
+    /// Read the environment variable `name` as `value` instead of from the process environment.
+    pub fn with_env_var(mut self, name: &str, value: &str) -> Self {
+        self.env.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Value of the environment variable `name`, if set.
+    fn env_var(&self, name: &str) -> Option<String> {
+        self.env.get(name).cloned().or_else(|| std::env::var(name).ok())
+    }
+
+    /// Make sure `hostname` is a known SSH host, adding it if the user agrees on `input`
+    /// or `--add-unknown-host` is given.
+    fn verify_host(&self, hostname: &str, args: &Args, input: &mut impl BufRead) -> Result<()> {
+        if self.known_hosts.is_known(hostname)? {
+            if !args.quiet {
+                println!("✓ Host '{}' is in known_hosts", hostname);
+            }
+            return Ok(());
+        }
+
+        eprintln!("Host '{}' is not in ~/.ssh/known_hosts. It can be added with:", hostname);
+        eprintln!("  {}", SshKnownHostsChecker::add_command(hostname));
+        if !args.add_unknown_host {
+            eprint!("Add it now? (y/N) ");
+            std::io::stderr().flush()?;
+
+            let mut answer = String::new();
+            input.read_line(&mut answer)?;
+            if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                return Err(GitSetupError::Git(format!("Host '{}' is not a known SSH host", hostname)));
+            }
+        }
+
+        self.known_hosts
+            .add_host(hostname)
+            .with_context(format!("while adding '{}' to known_hosts", hostname))?;
+        if !args.quiet {
+            println!("✓ Added host '{}' to known_hosts", hostname);
+        }
+        Ok(())
     }
 
     /// Determine the git configuration scope based on arguments and profile.
-    fn determine_scope(&self, args: &Args, profile: &Profile) -> Result<GitConfigScope> {
+    fn determine_scope(&self, args: &Args, profile: &Profile, context: &CommandContext) -> Result<GitConfigScope> {
         // Check for conflicting flags
         if args.global && args.system {
             return Err(GitSetupError::Git(
@@ -38,11 +260,30 @@ impl ApplyCommand {
             return Ok(GitConfigScope::System);
         }
 
-        // Use profile's default scope
-        match profile.scope {
-            Some(Scope::Global) => Ok(GitConfigScope::Global),
-            Some(Scope::System) => Ok(GitConfigScope::System),
-            Some(Scope::Local) | None => Ok(GitConfigScope::Local),
+        // Use the profile's scope, inferring it from the working directory if unset
+        let in_repo = context.git_wrapper.is_inside_work_tree()?;
+        Ok(profile.effective_scope(in_repo).into())
+    }
+
+    /// Where the scope chosen by `determine_scope` comes from, for `--show-scope`.
+    fn scope_source(args: &Args, profile: &Profile) -> &'static str {
+        if args.global {
+            "--global flag"
+        } else if args.system {
+            "--system flag"
+        } else if profile.scope.is_some() {
+            "profile setting"
+        } else {
+            "inferred from the working directory"
+        }
+    }
+
+    /// Lowercase name of a scope, as used on the command line.
+    fn scope_label(scope: &GitConfigScope) -> &'static str {
+        match scope {
+            GitConfigScope::Local => "local",
+            GitConfigScope::Global => "global",
+            GitConfigScope::System => "system",
         }
     }
 
@@ -65,28 +306,32 @@ impl ApplyCommand {
     }
 
     /// Configure signing based on profile key type.
+    ///
+    /// Returns the signing key that was configured, if the key type uses one.
     async fn configure_signing(
         &self,
         profile: &Profile,
         scope: GitConfigScope,
         context: &CommandContext,
-    ) -> Result<()> {
+    ) -> Result<Option<String>> {
         match profile.key_type {
             KeyType::Ssh => {
-                self.configure_ssh_signing(profile, scope, context).await?;
+                let signing_key = self.configure_ssh_signing(profile, scope, context).await?;
+                Ok(Some(signing_key))
             }
             KeyType::Gpg => {
-                self.configure_gpg_signing(profile, scope, context).await?;
+                let signing_key = self.configure_gpg_signing(profile, scope, context).await?;
+                Ok(Some(signing_key))
             }
             KeyType::X509 => {
                 context.git_wrapper.configure_x509_signing(scope)?;
+                Ok(None)
             }
             KeyType::Gitsign => {
                 context.git_wrapper.configure_gitsign(scope)?;
+                Ok(None)
             }
         }
-
-        Ok(())
     }
 
     /// Configure SSH signing for the profile.
@@ -95,12 +340,12 @@ impl ApplyCommand {
         profile: &Profile,
         scope: GitConfigScope,
         context: &CommandContext,
-    ) -> Result<()> {
-        let signing_key = if profile.one_password {
-            self.get_ssh_key_from_1password(profile, context).await?
-        } else {
-            profile.signing_key.clone()
-                .ok_or_else(|| GitSetupError::Git("SSH signing key not configured".to_string()))?
+    ) -> Result<String> {
+        let signing_key = match profile.key_store() {
+            Some(SecretStoreKind::OnePassword) => self.get_ssh_key_from_1password(profile, context).await?,
+            Some(SecretStoreKind::Bitwarden) => self.get_ssh_key_from_bitwarden(profile, context)?,
+            None => profile.signing_key.clone()
+                .ok_or_else(|| GitSetupError::Git("SSH signing key not configured".to_string()))?,
         };
 
         context.git_wrapper.configure_ssh_signing(
@@ -109,7 +354,7 @@ impl ApplyCommand {
             scope,
         )?;
 
-        Ok(())
+        Ok(signing_key)
     }
 
     /// Configure GPG signing for the profile.
@@ -118,17 +363,121 @@ impl ApplyCommand {
         profile: &Profile,
         scope: GitConfigScope,
         context: &CommandContext,
-    ) -> Result<()> {
-        let signing_key = if profile.one_password {
-            self.get_gpg_key_from_1password(profile, context).await?
-        } else {
-            profile.signing_key.clone()
-                .ok_or_else(|| GitSetupError::Git("GPG signing key not configured".to_string()))?
+    ) -> Result<String> {
+        let signing_key = match profile.key_store() {
+            Some(SecretStoreKind::OnePassword) => self.get_gpg_key_from_1password(profile, context).await?,
+            Some(SecretStoreKind::Bitwarden) => return Err(Self::bitwarden_gpg_unsupported(profile)),
+            None => Self::local_gpg_key(profile, context)?,
         };
 
         context.git_wrapper.configure_gpg_signing(&signing_key, scope)?;
 
-        Ok(())
+        Ok(signing_key)
+    }
+
+    /// Apply the profile with each git config key at its own scope.
+    ///
+    /// Returns the signing key that was configured, if the key type uses one.
+    async fn apply_per_field_scopes(
+        &self,
+        profile: &Profile,
+        scope: &GitConfigScope,
+        context: &CommandContext,
+    ) -> Result<Option<String>> {
+        let signing_key = self.resolve_signing_key(profile, context).await?;
+        for (key, value, key_scope) in Self::scoped_config_entries(profile, signing_key.as_deref(), scope) {
+            context.git_wrapper.set_config(key, &value, key_scope)?;
+        }
+        Ok(signing_key)
+    }
+
+    /// The git config applying `profile` writes, as `(key, value, scope)` triples.
+    ///
+    /// Keys in the profile's `per_field_scope` use that scope; all others use `scope`.
+    fn scoped_config_entries(
+        profile: &Profile,
+        signing_key: Option<&str>,
+        scope: &GitConfigScope,
+    ) -> Vec<(&'static str, String, GitConfigScope)> {
+        let (format, program) = match profile.key_type {
+            KeyType::Ssh => ("ssh", None),
+            KeyType::Gpg => ("openpgp", None),
+            KeyType::X509 => ("x509", Some("smimesign")),
+            KeyType::Gitsign => ("x509", Some("gitsign")),
+        };
+
+        let mut entries = vec![("user.email", profile.git_user_email.clone())];
+        if let Some(name) = &profile.git_user_name {
+            entries.push(("user.name", name.clone()));
+        }
+        entries.push(("gpg.format", format.to_string()));
+        if let Some(signing_key) = signing_key {
+            entries.push(("user.signingkey", signing_key.to_string()));
+        }
+        if profile.key_type == KeyType::Ssh
+            && let Some(allowed_signers) = &profile.allowed_signers
+        {
+            entries.push(("gpg.ssh.allowedSignersFile", allowed_signers.clone()));
+        }
+        if let Some(program) = program {
+            entries.push(("gpg.x509.program", program.to_string()));
+        }
+        entries.push(("commit.gpgsign", "true".to_string()));
+
+        entries
+            .into_iter()
+            .map(|(key, value)| (key, value, Self::key_scope(profile, key, scope)))
+            .collect()
+    }
+
+    /// Scope `key` is applied with: its field-level scope, or else `scope`.
+    fn key_scope(profile: &Profile, key: &str, scope: &GitConfigScope) -> GitConfigScope {
+        profile
+            .field_scope(key)
+            .cloned()
+            .map(GitConfigScope::from)
+            .unwrap_or_else(|| scope.clone())
+    }
+
+    /// Signing key of the profile, fetched from its secret store if the profile uses one.
+    ///
+    /// X.509 and gitsign profiles have no signing key.
+    async fn resolve_signing_key(&self, profile: &Profile, context: &CommandContext) -> Result<Option<String>> {
+        let signing_key = match (&profile.key_type, profile.key_store()) {
+            (KeyType::Ssh, Some(SecretStoreKind::Bitwarden)) => self.get_ssh_key_from_bitwarden(profile, context)?,
+            (KeyType::Ssh, Some(SecretStoreKind::OnePassword)) => self.get_ssh_key_from_1password(profile, context).await?,
+            (KeyType::Gpg, Some(SecretStoreKind::OnePassword)) => self.get_gpg_key_from_1password(profile, context).await?,
+            (KeyType::Gpg, Some(SecretStoreKind::Bitwarden)) => return Err(Self::bitwarden_gpg_unsupported(profile)),
+            (KeyType::Ssh, None) => profile.signing_key.clone()
+                .ok_or_else(|| GitSetupError::Git("SSH signing key not configured".to_string()))?,
+            (KeyType::Gpg, None) => Self::local_gpg_key(profile, context)?,
+            (KeyType::X509 | KeyType::Gitsign, _) => return Ok(None),
+        };
+        Ok(Some(signing_key))
+    }
+
+    /// GPG signing key of the profile, looked up in the keyring by email when unset.
+    ///
+    /// Fails if the keyring holds no key, or more than one key, for the email.
+    fn local_gpg_key(profile: &Profile, context: &CommandContext) -> Result<String> {
+        if let Some(signing_key) = &profile.signing_key {
+            return Ok(signing_key.clone());
+        }
+
+        let keys = context.gpg_wrapper.list_keys_by_email(&profile.git_user_email)?;
+        match keys.as_slice() {
+            [] => Err(GitSetupError::Git(format!(
+                "GPG signing key not configured and no key found for {}",
+                profile.git_user_email
+            ))),
+            [key] => Ok(key.key_id.clone()),
+            keys => Err(GitSetupError::Git(format!(
+                "GPG signing key not configured and {} keys match {} ({}); set signing_key to choose one",
+                keys.len(),
+                profile.git_user_email,
+                keys.iter().map(|k| k.key_id.as_str()).collect::<Vec<_>>().join(", ")
+            ))),
+        }
     }
 
     /// Get SSH key from 1Password.
@@ -148,6 +497,27 @@ impl ApplyCommand {
         context.onepassword_wrapper.get_ssh_public_key(&ssh_key.id).await
     }
 
+    /// Get SSH key from Bitwarden.
+    fn get_ssh_key_from_bitwarden(&self, profile: &Profile, context: &CommandContext) -> Result<String> {
+        let ssh_key_title = profile.ssh_key_title.as_ref()
+            .ok_or_else(|| GitSetupError::Git("SSH key title not configured for Bitwarden".to_string()))?;
+
+        let ssh_key = context.bitwarden_wrapper.get_ssh_key(ssh_key_title)?
+            .ok_or_else(|| GitSetupError::Git(format!("SSH key '{}' not found in Bitwarden", ssh_key_title)))?;
+
+        Ok(ssh_key.public_key)
+    }
+
+    /// Error for GPG profiles whose key store is Bitwarden, which holds no GPG keys.
+    fn bitwarden_gpg_unsupported(profile: &Profile) -> GitSetupError {
+        GitSetupError::InvalidProfile {
+            reason: format!(
+                "Profile '{}' loads its GPG key from Bitwarden, which only stores SSH keys",
+                profile.name
+            ),
+        }
+    }
+
     /// Get GPG key from 1Password.
     async fn get_gpg_key_from_1password(
         &self,
@@ -170,8 +540,102 @@ impl ApplyCommand {
         Ok(gpg_key.fingerprint)
     }
 
+    /// Build a one-off profile from `--inherit-from` and the override flags.
+    ///
+    /// With `--save-as` the profile takes the new name; it is only stored once
+    /// applied, see [`ApplyCommand::save_inherited_profile`].
+    fn inherited_profile(&self, parent: &str, args: &Args, context: &CommandContext) -> Result<Profile> {
+        let parent_profile = context.profile_manager.read(parent)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: parent.to_string() })?;
+
+        let mut profile = parent_profile.with_overrides(ProfileOverrides {
+            git_user_email: args.email.clone(),
+            git_user_name: args.name.clone(),
+            signing_key: args.signing_key.clone(),
+        });
+
+        if let Some(new_name) = &args.save_as {
+            profile.name = new_name.clone();
+        }
+
+        Ok(profile)
+    }
+
+    /// Store the applied `--inherit-from` profile under its `--save-as` name.
+    fn save_inherited_profile(&self, profile: &Profile, args: &Args, context: &CommandContext) -> Result<()> {
+        if args.inherit_from.is_none() || args.save_as.is_none() {
+            return Ok(());
+        }
+        context
+            .profile_manager
+            .create(profile.clone())
+            .with_context(format!("while saving profile '{}'", profile.name))?;
+        if !args.quiet {
+            println!("✓ Saved profile '{}'", profile.name);
+        }
+        Ok(())
+    }
+
+    /// Minimal profile named `name` built from `--email`, `--name`, `--profile-key-type` and `--signing-key`.
+    ///
+    /// `env_email` (`$GIT_AUTHOR_EMAIL`) is used when `--email` is not given.
+    fn minimal_profile(name: &str, args: &Args, env_email: Option<String>) -> Result<Profile> {
+        let email = args.email.clone().or(env_email).ok_or_else(|| GitSetupError::InvalidProfile {
+            reason: format!("--email or GIT_AUTHOR_EMAIL is required to create profile '{}'", name),
+        })?;
+        Ok(Profile {
+            name: name.to_string(),
+            git_user_name: args.name.clone(),
+            git_user_email: email,
+            key_type: args.profile_key_type.clone().unwrap_or_default(),
+            signing_key: args.signing_key.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// Create the named profile if `--create-if-missing` or `--create-if-missing-then-delete` is
+    /// given and it does not exist.
+    ///
+    /// Returns the created profile, and a guard deleting it again for `--create-if-missing-then-delete`.
+    fn create_missing_profile(
+        &self,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<Option<(Profile, Option<ProfileGuard>)>> {
+        if !args.create_if_missing && !args.create_if_missing_then_delete {
+            return Ok(None);
+        }
+        let Some(name) = args.profile.as_deref() else {
+            return Ok(None);
+        };
+        if context.profile_manager.exists(name)? {
+            return Ok(None);
+        }
+
+        let profile = Self::minimal_profile(name, args, self.env_var("GIT_AUTHOR_EMAIL"))?;
+        if args.no_persist {
+            return Ok(Some((profile, None)));
+        }
+        context
+            .profile_manager
+            .create(profile.clone())
+            .with_context(format!("while creating profile '{}'", name))?;
+        if !args.quiet {
+            println!("✓ Created profile '{}'", name);
+        }
+
+        let guard = args
+            .create_if_missing_then_delete
+            .then(|| ProfileGuard::new(context.profile_manager.clone(), name));
+        Ok(Some((profile, guard)))
+    }
+
     /// Get profile to apply, either from args or auto-detection.
     async fn get_profile_to_apply(&self, args: &Args, context: &CommandContext) -> Result<Profile> {
+        if let Some(parent) = &args.inherit_from {
+            return self.inherited_profile(parent, args, context);
+        }
+
         if let Some(profile_name) = &args.profile {
             // Explicit profile specified
             return context.profile_manager.read(profile_name)?
@@ -205,351 +669,2618 @@ impl ApplyCommand {
         }
     }
 
-    /// Print success message with applied configuration details.
-    fn print_success_message(&self, profile: &Profile, scope: GitConfigScope) {
-        let scope_str = match scope {
-            GitConfigScope::Local => "local",
-            GitConfigScope::Global => "global",
-            GitConfigScope::System => "system",
-        };
+    /// Read a profile by name, failing if it does not exist.
+    fn read_profile(&self, name: &str, context: &CommandContext) -> Result<Profile> {
+        context.profile_manager.read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.to_string() })
+    }
 
-        println!("✓ Applied profile '{}' with {} scope", profile.name, scope_str);
-        println!("  Email: {}", profile.git_user_email);
-        
+    /// Apply the fields `profile` sets with local scope, leaving the rest to the global config.
+    ///
+    /// The email is required, so it is always overridden; the name and signing
+    /// settings are inherited from the global profile unless `profile` sets them.
+    /// Returns the names of the overridden settings.
+    async fn apply_local_overrides(
+        &self,
+        profile: &Profile,
+        context: &CommandContext,
+    ) -> Result<Vec<&'static str>> {
+        let mut overridden = vec!["email"];
+
+        context.git_wrapper.set_config("user.email", &profile.git_user_email, GitConfigScope::Local)?;
         if let Some(name) = &profile.git_user_name {
-            println!("  Name: {}", name);
+            context.git_wrapper.set_config("user.name", name, GitConfigScope::Local)?;
+            overridden.push("name");
         }
-        
-        println!("  Key type: {:?}", profile.key_type);
-        
-        if profile.one_password {
-            println!("  Key source: 1Password");
+        if profile.signing_key.is_some() || profile.key_store().is_some() {
+            self.configure_signing(profile, GitConfigScope::Local, context).await?;
+            overridden.push("signing");
         }
-    }
-}
 
-impl Default for ApplyCommand {
-    fn default() -> Self {
-        Self::new()
+        Ok(overridden)
     }
-}
 
-#[async_trait]
-impl Command for ApplyCommand {
-    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
-        // Get the profile to apply
-        let profile = self.get_profile_to_apply(args, context).await?;
+    /// Apply `global_name` globally, then the fields `local_name` sets locally.
+    async fn apply_global_then_local(
+        &self,
+        global_name: &str,
+        local_name: &str,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<()> {
+        if global_name == local_name {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!(
+                    "--global-then-local needs two different profiles, got '{}' twice",
+                    global_name
+                ),
+            });
+        }
 
-        // Determine configuration scope
-        let scope = self.determine_scope(args, &profile)?;
+        let global = self.read_profile(global_name, context)?;
+        let local = self.read_profile(local_name, context)?;
 
-        // Apply user configuration
-        self.apply_user_config(&profile, scope, context).await?;
+        for profile in [&global, &local] {
+            if profile.require_passphrase {
+                let passphrase = self.read_passphrase(profile, args)?;
+                self.unlock_key(profile, &passphrase, context)?;
+            }
+        }
 
-        // Configure signing
-        self.configure_signing(&profile, scope, context).await?;
+        self.apply_user_config(&global, GitConfigScope::Global, context).await?;
+        self.configure_signing(&global, GitConfigScope::Global, context).await?;
+        let overridden = self.apply_local_overrides(&local, context).await?;
+
+        for (profile, repo_path) in [(&global, None), (&local, std::env::current_dir().ok())] {
+            if let Err(e) = context
+                .profile_manager
+                .record_activation(ActivationRecord::now(&profile.name, repo_path))
+                && !args.quiet
+            {
+                eprintln!("Warning: could not record activation of '{}': {}", profile.name, e);
+            }
+        }
 
-        // Print success message
         if !args.quiet {
-            self.print_success_message(&profile, scope);
+            self.print_success_message(&global, GitConfigScope::Global);
+            println!(
+                "✓ Applied local overrides from profile '{}': {}",
+                local.name,
+                if overridden.is_empty() { "none".to_string() } else { overridden.join(", ") }
+            );
         }
 
         Ok(())
     }
 
-    fn name(&self) -> &'static str {
-        "apply"
-    }
+    /// Apply every profile tagged `tag` in name order, stopping at the first failure.
+    ///
+    /// Later profiles override the keys they share with earlier ones in the same scope.
+    async fn apply_tag(&self, tag: &str, args: &Args, context: &CommandContext) -> Result<()> {
+        let mut profiles = context.profile_manager.list_by_tag(tag)?;
+        if profiles.is_empty() {
+            return Err(GitSetupError::Git(format!("No profiles are tagged '{}'", tag)));
+        }
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
 
-    fn description(&self) -> &'static str {
-        "Apply a git profile configuration"
+        for profile in &profiles {
+            self.apply_profile(profile, args, context)
+                .await
+                .with_context(format!("while applying profile '{}' of tag '{}'", profile.name, tag))?;
+        }
+
+        if !args.quiet {
+            println!("✓ Applied {} profile(s) tagged '{}'", profiles.len(), tag);
+        }
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        config::types::{Profile, KeyType, Scope},
-        commands::tests::create_test_context,
-        profile::mock::MockProfileManager,
-        external::{
-            git::MockGitWrapper,
-            onepassword::{MockOnePasswordWrapper, SshKeyItem},
-        },
-        detection::{DetectionResult, MockProfileDetector},
-    };
-    use std::sync::Arc;
+    /// Obtain the key passphrase from `--passphrase-env`, `--passphrase-command`,
+    /// or an interactive prompt, in that order.
+    fn read_passphrase(&self, profile: &Profile, args: &Args) -> Result<SecretString> {
+        if let Some(var) = &args.passphrase_env {
+            return self.env_var(var).map(SecretString::from).ok_or_else(|| {
+                GitSetupError::Git(format!("Passphrase environment variable '{}' is not set", var))
+            });
+        }
 
-    /// Helper function to create a test profile.
-    fn create_test_profile(name: &str, email: &str) -> Profile {
-        Profile {
-            name: name.to_string(),
-            git_user_name: Some(format!("{} User", name)),
-            git_user_email: email.to_string(),
-            key_type: KeyType::Ssh,
-            signing_key: Some("ssh-ed25519 AAAAC3...".to_string()),
-            vault_name: Some("test-vault".to_string()),
-            ssh_key_title: Some("test-ssh-key".to_string()),
-            scope: Some(Scope::Local),
-            ssh_key_source: None,
-            ssh_key_path: None,
-            allowed_signers: None,
-            match_patterns: vec![],
-            repos: vec![],
-            include_if_dirs: vec![],
-            host_patterns: vec![],
-            one_password: false,
+        if let Some(command) = &args.passphrase_command {
+            let output = std::process::Command::new("sh")
+                .args(["-c", command])
+                .stderr(std::process::Stdio::inherit())
+                .output()
+                .map_err(|e| GitSetupError::ExternalCommand {
+                    command: command.clone(),
+                    error: e.to_string(),
+                })?;
+            if !output.status.success() {
+                return Err(GitSetupError::ExternalCommand {
+                    command: command.clone(),
+                    error: format!("exited with {}", output.status),
+                });
+            }
+            let passphrase = String::from_utf8_lossy(&output.stdout);
+            return Ok(SecretString::from(passphrase.trim_end_matches(['\r', '\n']).to_string()));
         }
+
+        let passphrase = rpassword::prompt_password(format!(
+            "Enter passphrase for profile '{}': ",
+            profile.name
+        ))?;
+        Ok(SecretString::from(passphrase))
     }
 
-    /// Test that ApplyCommand can be created.
-    #[test]
-    fn test_apply_command_creation() {
-        let cmd = ApplyCommand::new();
-        assert_eq!(cmd.name(), "apply");
-        assert_eq!(cmd.description(), "Apply a git profile configuration");
+    /// Unlock the profile's local key with `passphrase`.
+    ///
+    /// The SSH key at `ssh_key_path` is added to `ssh-agent`; the passphrase of
+    /// the GPG key `signing_key` is cached in gpg-agent.
+    fn unlock_key(
+        &self,
+        profile: &Profile,
+        passphrase: &SecretString,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let missing = |field: &str| GitSetupError::InvalidProfile {
+            reason: format!(
+                "Profile '{}' requires a passphrase but has no {}",
+                profile.name, field
+            ),
+        };
+
+        match profile.key_type {
+            KeyType::Ssh => {
+                let key_path = profile.ssh_key_path.as_deref().ok_or_else(|| missing("ssh_key_path"))?;
+                let path = SystemPlatform.expand_path(key_path);
+                SystemSshKeyGenerator::new().start_agent(Path::new(&path), passphrase)
+            }
+            KeyType::Gpg => {
+                let key_id = profile.signing_key.as_deref().ok_or_else(|| missing("signing_key"))?;
+                context
+                    .gpg_wrapper
+                    .preset_passphrase(key_id, passphrase.expose_secret())
+            }
+            KeyType::X509 | KeyType::Gitsign => Ok(()),
+        }
     }
 
-    /// Test applying a profile with explicit name.
-    #[tokio::test]
-    async fn test_apply_explicit_profile() {
-        let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
-        
-        let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+    /// Verify the applied signing configuration by making a signed test commit.
+    fn test_signing(
+        &self,
+        profile: &Profile,
+        signing_key: Option<&str>,
+        context: &CommandContext,
+    ) -> Result<()> {
+        // SSH signature verification needs a readable allowed signers file
+        if profile.key_type == KeyType::Ssh
+            && let Some(allowed_signers) = &profile.allowed_signers
+        {
+            let path = SystemPlatform.expand_path(allowed_signers);
+            if let Err(e) = std::fs::File::open(&path) {
+                return Err(GitSetupError::Git(format!(
+                    "Signing test failed: allowed signers file '{}' is not readable: {}",
+                    path, e
+                )));
+            }
+        }
 
-        let args = Args {
-            profile: Some("work".to_string()),
-            quiet: true,
-            ..Default::default()
-        };
+        let signed = context
+            .git_wrapper
+            .test_sign(&profile.key_type, signing_key.unwrap_or_default())?;
 
-        let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
+        if signed {
+            Ok(())
+        } else {
+            Err(GitSetupError::Git(format!(
+                "Signing test failed for profile '{}'. {}",
+                profile.name,
+                Self::signing_fix_suggestion(&profile.key_type)
+            )))
+        }
+    }
 
-        assert!(result.is_ok());
+    /// Suggest how to fix a failed signing test for the given key type.
+    fn signing_fix_suggestion(key_type: &KeyType) -> &'static str {
+        match key_type {
+            KeyType::Ssh => {
+                "Make sure the key is loaded in your SSH agent (ssh-add -l) or that the 1Password SSH agent is running."
+            }
+            KeyType::Gpg => {
+                "Make sure the key is in your keyring (gpg --list-secret-keys) and gpg-agent is running."
+            }
+            KeyType::X509 => "Make sure smimesign is installed and a signing certificate is available.",
+            KeyType::Gitsign => "Make sure gitsign is installed and you can authenticate with Sigstore.",
+        }
     }
 
-    /// Test applying a profile with global scope.
+    /// Undo the git config changes of `transaction` after a step failed with `error`.
+    ///
+    /// Returns `error`, with the keys that could not be restored attached as
+    /// context if the rollback failed too.
+    fn roll_back(
+        &self,
+        transaction: ApplyTransaction,
+        error: GitSetupError,
+        profile: &Profile,
+        args: &Args,
+    ) -> GitSetupError {
+        match transaction.rollback() {
+            Ok(()) => {
+                if !args.quiet {
+                    println!("Rolled back git config changes for profile '{}'", profile.name);
+                }
+                error
+            }
+            Err(rollback_error) => ErrorContext::new(
+                error,
+                format!("rolling back profile '{}' also failed: {}", profile.name, rollback_error),
+            )
+            .into(),
+        }
+    }
+
+    /// Apply `profile` to the git config, verifying and recording it as requested.
+    async fn apply_profile(&self, profile: &Profile, args: &Args, context: &CommandContext) -> Result<()> {
+        // Determine configuration scope
+        let scope = self.determine_scope(args, profile, context)?;
+        if args.show_scope {
+            println!(
+                "Resolved {} scope for profile '{}' ({})",
+                Self::scope_label(&scope),
+                profile.name,
+                Self::scope_source(args, profile)
+            );
+        }
+
+        if args.check_capabilities || !args.quiet {
+            self.check_capabilities(profile, &scope, args, context)?;
+        }
+
+        self.check_key_expiry(profile, args, context)?;
+
+        if args.wait_for_gpg_agent {
+            wait_for_agent(
+                context.gpg_wrapper.as_ref(),
+                Duration::from_secs(args.gpg_agent_timeout),
+                args.quiet,
+            )
+            .await
+            .with_context("while waiting for the GPG agent")?;
+        }
+
+        // Unlock passphrase-protected local keys before touching git config
+        let passphrase = if profile.require_passphrase {
+            let passphrase = self.read_passphrase(profile, args)?;
+            self.unlock_key(profile, &passphrase, context)
+                .with_context("while unlocking the signing key")?;
+            Some(passphrase)
+        } else {
+            None
+        };
+
+        // Remember the previous config so a failed step can be undone
+        let transaction = if args.scope_per_profile {
+            ApplyTransaction::begin_per_key(context.git_wrapper.clone(), |key| Self::key_scope(profile, key, &scope))
+        } else {
+            ApplyTransaction::begin(context.git_wrapper.clone(), scope.clone())
+        }
+        .with_context("while saving the current git config")?;
+
+        // Leave keys to broader scopes that already set them to the same value
+        let inheriting_context;
+        let context = if args.scope_inherit {
+            let inheriting = InheritingGitWrapper::new(scope.clone(), context.git_wrapper.clone())
+                .with_verbose(args.scope_inherit_verbose && !args.quiet);
+            inheriting_context = CommandContext {
+                git_wrapper: Arc::new(inheriting),
+                ..context.clone()
+            };
+            &inheriting_context
+        } else {
+            context
+        };
+
+        // Apply user configuration, then configure signing
+        let applied = if args.scope_per_profile {
+            self.apply_per_field_scopes(profile, &scope, context).await
+        } else {
+            match self.apply_user_config(profile, scope.clone(), context).await {
+                Ok(()) => self.configure_signing(profile, scope.clone(), context).await,
+                Err(e) => Err(e),
+            }
+        };
+        let applied = match (applied, &profile.git_hooks_dir) {
+            (Ok(signing_key), Some(hooks_dir)) if !args.no_hooks => {
+                let hooks_scope = if args.scope_per_profile {
+                    Self::key_scope(profile, HOOKS_PATH_KEY, &scope)
+                } else {
+                    scope.clone()
+                };
+                HooksManager::install(hooks_dir, hooks_scope, context.git_wrapper.as_ref()).map(|()| signing_key)
+            }
+            (applied, _) => applied,
+        };
+        let applied = match applied {
+            Ok(signing_key) if args.update_allowed_signers => {
+                let signers_scope = if args.scope_per_profile {
+                    Self::key_scope(profile, ALLOWED_SIGNERS_KEY, &scope)
+                } else {
+                    scope.clone()
+                };
+                self.update_allowed_signers(profile, signers_scope, args, context)
+                    .map(|()| signing_key)
+            }
+            applied => applied,
+        };
+        let signing_key = match applied {
+            Ok(signing_key) => signing_key,
+            Err(e) => return Err(self.roll_back(transaction, e, profile, args)),
+        };
+
+        // Verify signing actually works with the applied configuration
+        if args.test_signing
+            && let Err(e) = self.test_signing(profile, signing_key.as_deref(), context)
+        {
+            if args.rollback_on_test_failure {
+                return Err(self.roll_back(transaction, e, profile, args));
+            }
+            return Err(e);
+        }
+        transaction.commit();
+
+        // Check the applied key can actually sign, without undoing the apply
+        if args.post_apply_verify {
+            let digest = args.verify_algorithm.unwrap_or_default();
+            match verify_signing(profile, signing_key.as_deref(), digest, context) {
+                Ok(()) => {
+                    if !args.quiet {
+                        println!("✓ Signing verified");
+                    }
+                }
+                Err(e) => {
+                    println!("✗ Signing failed: {}", e);
+                    return Err(e);
+                }
+            }
+        }
+
+        // Hand the applied identity to containers or CI through an environment file
+        if let Some(path) = &args.output_env_file {
+            let passphrase = passphrase.as_ref().filter(|_| args.include_secrets);
+            let vars = env_file_vars(profile, signing_key.as_deref(), passphrase);
+            write_env_file(
+                Path::new(path),
+                &render_env_file(&vars, args.env_file_format),
+                passphrase.is_some(),
+            )
+            .with_context(format!("while writing the environment file '{}'", path))?;
+            if !args.quiet {
+                println!("✓ Wrote environment for profile '{}' to {}", profile.name, path);
+            }
+        }
+
+        // A written file configures another system, so it is not an activation here
+        if let Some(path) = &args.write_gitconfig {
+            if !args.quiet {
+                println!("✓ Wrote profile '{}' to {}", profile.name, path);
+            }
+            return Ok(());
+        }
+
+        // Remember the activation; failing to do so must not fail the apply
+        let repo_path = match scope {
+            GitConfigScope::Local => std::env::current_dir().ok(),
+            GitConfigScope::Global | GitConfigScope::System => None,
+        };
+        if let Err(e) = context
+            .profile_manager
+            .record_activation(ActivationRecord::now(&profile.name, repo_path))
+            && !args.quiet
+        {
+            eprintln!("Warning: could not record activation of '{}': {}", profile.name, e);
+        }
+
+        if args.annotate_commit {
+            context
+                .git_wrapper
+                .add_commit_note(&profile.name, PROFILE_NOTES_REF)
+                .with_context("while annotating HEAD with the profile name")?;
+        }
+
+        // Print success message
+        if !args.quiet {
+            let override_note = match scope {
+                GitConfigScope::Global => self.local_override_note(profile, context),
+                GitConfigScope::Local | GitConfigScope::System => None,
+            };
+            self.print_success_message(profile, scope);
+            if let Some(note) = override_note {
+                println!("  {}", note);
+            }
+            if args.scope_per_profile && !profile.per_field_scope.is_empty() {
+                let mut field_scopes: Vec<_> = profile.per_field_scope.iter().collect();
+                field_scopes.sort_by(|a, b| a.0.cmp(b.0));
+                println!("  Field-level scopes:");
+                for (key, field_scope) in field_scopes {
+                    println!("    {}: {:?}", key, field_scope);
+                }
+            }
+            if args.test_signing {
+                println!("  ✓ Signing test passed");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Warn about capabilities applying `profile` at `scope` needs that are unavailable.
+    ///
+    /// # Errors
+    /// With `--check-capabilities`, returns an error if any of them is unavailable
+    fn check_capabilities(
+        &self,
+        profile: &Profile,
+        scope: &GitConfigScope,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let unavailable: Vec<String> = CapabilityChecker::check(profile, context)?
+            .into_iter()
+            .filter(|(capability, status)| {
+                *status == CapabilityStatus::Unavailable && capability.is_required(scope)
+            })
+            .map(|(capability, _)| capability.to_string())
+            .collect();
+        if unavailable.is_empty() {
+            return Ok(());
+        }
+
+        if args.check_capabilities {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!(
+                    "profile '{}' cannot be applied, unavailable: {}",
+                    profile.name,
+                    unavailable.join(", ")
+                ),
+            });
+        }
+        eprintln!(
+            "Warning: capabilities of profile '{}' are unavailable: {}",
+            profile.name,
+            unavailable.join(", ")
+        );
+        Ok(())
+    }
+
+    /// Fail if the profile's GPG key has expired, unless `--allow-expired`, and
+    /// warn if it expires within `--warn-expiry-days`.
+    ///
+    /// The key is the one the profile signs with, looked up by email when
+    /// `signing_key` is unset. Keys that are not in the local keyring or never
+    /// expire are not checked.
+    fn check_key_expiry(&self, profile: &Profile, args: &Args, context: &CommandContext) -> Result<()> {
+        let key_id = match (&profile.key_type, profile.key_store()) {
+            // A failed lookup is reported when the signing key is resolved
+            (KeyType::Gpg, None) => Self::local_gpg_key(profile, context).ok(),
+            (KeyType::Gpg, Some(_)) => profile.signing_key.clone(),
+            _ => None,
+        };
+        let Some(key_id) = key_id else {
+            return Ok(());
+        };
+        let Some(days) = context
+            .gpg_wrapper
+            .get_key_info(&key_id)
+            .ok()
+            .flatten()
+            .and_then(|info| info.expiration_date)
+            .and_then(|date| days_until_expiry(&date))
+        else {
+            return Ok(());
+        };
+
+        if days < 0 && !args.allow_expired {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!(
+                    "GPG key {} of profile '{}' expired {} day(s) ago; renew it or pass --allow-expired",
+                    key_id,
+                    profile.name,
+                    -days
+                ),
+            });
+        }
+        let warning = if days < 0 {
+            format!("GPG key {} of profile '{}' expired {} day(s) ago", key_id, profile.name, -days)
+        } else if days <= args.warn_expiry_days {
+            format!("GPG key {} of profile '{}' expires in {} day(s)", key_id, profile.name, days)
+        } else {
+            return Ok(());
+        };
+        if std::io::stderr().is_terminal() {
+            eprintln!("{}Warning: {}{}", YELLOW, warning, RESET);
+        } else {
+            eprintln!("Warning: {}", warning);
+        }
+        Ok(())
+    }
+
+    /// Regenerate the allowed signers file of `profile` and point git at it at `scope`.
+    fn update_allowed_signers(
+        &self,
+        profile: &Profile,
+        scope: GitConfigScope,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let source = match (&args.from_file, &args.from_profile) {
+            (Some(url), _) => Some(SignersSource::Url(url)),
+            (None, Some(name)) => Some(SignersSource::Profile(name)),
+            (None, None) => None,
+        };
+        let path = AllowedSignersUpdater::new(context.profile_manager.clone(), context.git_wrapper.clone())
+            .update(profile, source)
+            .with_context("while updating the allowed signers file")?;
+        context
+            .git_wrapper
+            .set_config(ALLOWED_SIGNERS_KEY, &path.to_string_lossy(), scope)?;
+
+        if !args.quiet {
+            println!("✓ Updated allowed signers file {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// Print the `git config` commands applying `profile` would run, without running them.
+    ///
+    /// Nothing is validated or recorded; only the signing key is looked up when
+    /// it comes from 1Password.
+    async fn print_commands(&self, profile: &Profile, args: &Args, context: &CommandContext) -> Result<()> {
+        let print = Arc::new(PrintOnlyGitWrapper::new());
+        self.collect_config_writes(profile, args, print.clone(), context).await?;
+
+        print!("{}", print.render(args.print_format));
+        Ok(())
+    }
+
+    /// Print a table of the git config changes applying `profile` would make, without making them.
+    ///
+    /// Like `--print-only`, nothing is validated or recorded, but the current
+    /// values are read from git to show what each change replaces.
+    async fn print_dry_run(&self, profile: &Profile, args: &Args, context: &CommandContext) -> Result<()> {
+        let dry_run = Arc::new(DryRunGitWrapper::new(context.git_wrapper.clone()));
+        self.collect_config_writes(profile, args, dry_run.clone(), context).await?;
+
+        let changes = dry_run.changes();
+        if changes.is_empty() {
+            println!("Applying profile '{}' would not change the git config", profile.name);
+            return Ok(());
+        }
+        let rows: Vec<Vec<String>> = changes
+            .iter()
+            .map(|change| {
+                vec![
+                    change.marker().to_string(),
+                    Self::scope_label(&change.scope).to_string(),
+                    change.key.clone(),
+                    change.current.clone().unwrap_or_else(|| "(unset)".to_string()),
+                    change.proposed.clone().unwrap_or_else(|| "(unset)".to_string()),
+                ]
+            })
+            .collect();
+        println!("Applying profile '{}' would make these git config changes:", profile.name);
+        println!(
+            "{}",
+            TableFormatter::new().format_table(&["", "Scope", "Key", "Current", "Proposed"], &rows)
+        );
+        Ok(())
+    }
+
+    /// Issue the config writes of applying `profile` to `git` instead of the context's wrapper.
+    async fn collect_config_writes(
+        &self,
+        profile: &Profile,
+        args: &Args,
+        git: Arc<dyn GitWrapper>,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let scope = self.determine_scope(args, profile, context)?;
+        let collecting_context = CommandContext {
+            git_wrapper: git.clone(),
+            ..context.clone()
+        };
+
+        if args.scope_per_profile {
+            self.apply_per_field_scopes(profile, &scope, &collecting_context).await?;
+        } else {
+            self.apply_user_config(profile, scope.clone(), &collecting_context).await?;
+            self.configure_signing(profile, scope.clone(), &collecting_context).await?;
+        }
+        if let Some(hooks_dir) = &profile.git_hooks_dir
+            && !args.no_hooks
+        {
+            let hooks_scope = if args.scope_per_profile {
+                Self::key_scope(profile, HOOKS_PATH_KEY, &scope)
+            } else {
+                scope
+            };
+            git.set_config(HOOKS_PATH_KEY, &hooks_dir.to_string_lossy(), hooks_scope)?;
+        }
+        Ok(())
+    }
+
+    /// Print success message with applied configuration details.
+    fn print_success_message(&self, profile: &Profile, scope: GitConfigScope) {
+        println!("✓ Applied profile '{}' with {} scope", profile.name, Self::scope_label(&scope));
+        println!("  Email: {}", profile.git_user_email);
+        
+        if let Some(name) = &profile.git_user_name {
+            println!("  Name: {}", name);
+        }
+        
+        println!("  Key type: {:?}", profile.key_type);
+        
+        match profile.key_store() {
+            Some(SecretStoreKind::OnePassword) => println!("  Key source: 1Password"),
+            Some(SecretStoreKind::Bitwarden) => println!("  Key source: Bitwarden"),
+            None => {}
+        }
+
+        if let Some(hooks_dir) = &profile.git_hooks_dir {
+            println!("  Hooks: {}", hooks_dir.display());
+        }
+    }
+
+    /// Note that another profile still overrides `profile` in the current repository,
+    /// as left by `--global-then-local`, so a global apply does not take effect there.
+    fn local_override_note(&self, profile: &Profile, context: &CommandContext) -> Option<String> {
+        if !matches!(context.git_wrapper.is_inside_work_tree(), Ok(true)) {
+            return None;
+        }
+        match context.profile_detector.detect_two_phase() {
+            Ok(Some(setup)) if setup.global_profile == profile.name => Some(format!(
+                "Profile '{}' overrides {} in this repository",
+                setup.local_profile,
+                setup.local_keys.join(", ")
+            )),
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("Warning: could not check this repository for local overrides: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Apply `profile` to the gitconfig file at `path` for `--write-gitconfig`.
+    ///
+    /// The profile is applied to a copy next to the file, which replaces it
+    /// only once the apply succeeded, so a failure leaves the file as it was.
+    /// The copy starts out empty unless `--merge` is given.
+    async fn write_gitconfig(
+        &self,
+        profile: &Profile,
+        path: &Path,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let dir = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        std::fs::create_dir_all(dir)?;
+        let staging = tempfile::NamedTempFile::new_in(dir)?;
+        if path.exists() {
+            if args.merge {
+                std::fs::copy(path, staging.path())?;
+            }
+            std::fs::set_permissions(staging.path(), std::fs::metadata(path)?.permissions())?;
+        }
+
+        let file_context = CommandContext {
+            git_wrapper: Arc::new(FilesystemGitWrapper::new(staging.path())),
+            ..context.clone()
+        };
+        self.apply_profile(profile, args, &file_context).await?;
+        staging.persist(path).map_err(|e| GitSetupError::Io(e.error))?;
+        Ok(())
+    }
+
+    /// Apply the profile detected in `path` if it differs from `current`.
+    ///
+    /// Returns the name of the profile applied now. The detector's
+    /// `min_confidence` decides whether anything is detected; without a
+    /// detection the current profile stays.
+    async fn apply_detected(
+        &self,
+        path: &Path,
+        current: Option<String>,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<Option<String>> {
+        let Some(detected) = context.profile_detector.detect_in(path)? else {
+            return Ok(current);
+        };
+        if current.as_deref() == Some(detected.profile.name.as_str()) {
+            return Ok(current);
+        }
+
+        let profile = detected.profile;
+        if !args.quiet {
+            let from = current.map(|name| format!(" from '{}'", name)).unwrap_or_default();
+            println!(
+                "↻ Switching{} to profile '{}' ({:.0}% confidence)",
+                from,
+                profile.name,
+                detected.confidence * 100.0
+            );
+        }
+        self.apply_profile(&profile, args, context)
+            .await
+            .with_context(format!("while applying profile '{}'", profile.name))?;
+        Ok(Some(profile.name))
+    }
+
+    /// Re-apply the detected profile after each burst of `events` until they
+    /// end or the context's cancellation token is cancelled.
+    ///
+    /// A burst ends once no event arrived for `--debounce-ms`, so e.g. a
+    /// checkout touching many files detects the profile once. Failures are
+    /// reported and the watch goes on. Cancellation is only checked between
+    /// applies, so a profile is never left half written.
+    async fn watch(
+        &self,
+        path: &Path,
+        mut events: UnboundedReceiver<()>,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let debounce = Duration::from_millis(args.debounce_ms);
+        let mut current = None;
+        loop {
+            match self.apply_detected(path, current.clone(), args, context).await {
+                Ok(applied) => current = applied,
+                Err(e) => eprintln!("⚠ Could not apply the detected profile: {}", e),
+            }
+
+            tokio::select! {
+                _ = context.cancellation_token.cancelled() => return Ok(()),
+                event = events.recv() => {
+                    if event.is_none() {
+                        return Ok(());
+                    }
+                }
+            }
+            // Wait for the burst to settle
+            loop {
+                tokio::select! {
+                    _ = context.cancellation_token.cancelled() => return Ok(()),
+                    event = tokio::time::timeout(debounce, events.recv()) => {
+                        if !matches!(event, Ok(Some(()))) {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Watch `path` and everything below it, sending a message for every filesystem event.
+///
+/// Events are delivered until the returned watcher is dropped.
+fn watch_directory(path: &Path) -> Result<(RecommendedWatcher, UnboundedReceiver<()>)> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let watch_error = |e: notify::Error| GitSetupError::ExternalCommand {
+        command: "watch".to_string(),
+        error: format!("cannot watch '{}': {}", path.display(), e),
+    };
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if event.is_ok() {
+            let _ = sender.send(());
+        }
+    })
+    .map_err(watch_error)?;
+    watcher.watch(path, RecursiveMode::Recursive).map_err(watch_error)?;
+    Ok((watcher, receiver))
+}
+
+impl Default for ApplyCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ApplyCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        if let Some(hostname) = &args.verify_host {
+            self.verify_host(hostname, args, &mut std::io::stdin().lock())?;
+        }
+
+        if args.watch {
+            let path = std::env::current_dir()?;
+            let (_watcher, events) = watch_directory(&path)?;
+            if !args.quiet {
+                println!("Watching {} for changes (Ctrl-C to stop)", path.display());
+            }
+            return self.watch(&path, events, args, context).await;
+        }
+
+        if let Some(profiles) = &args.global_then_local
+            && let [global, local] = profiles.as_slice()
+        {
+            self.apply_global_then_local(global, local, args, context)
+                .await
+                .with_context(format!(
+                    "while applying profile '{}' globally and '{}' locally",
+                    global, local
+                ))?;
+            return Ok(());
+        }
+
+        if let Some(tag) = &args.tag {
+            return self.apply_tag(tag, args, context).await;
+        }
+
+        // Get the profile to apply, creating it first if allowed; the guard
+        // deletes an ephemeral profile when this function returns
+        let (profile, _guard) = match self.create_missing_profile(args, context)? {
+            Some(created) => created,
+            None => (self.get_profile_to_apply(args, context).await?, None),
+        };
+
+        if args.print_only {
+            self.print_commands(&profile, args, context)
+                .await
+                .with_context(format!("while printing the git config commands of profile '{}'", profile.name))?;
+            return Ok(());
+        }
+
+        if args.dry_run {
+            self.print_dry_run(&profile, args, context)
+                .await
+                .with_context(format!("while previewing profile '{}'", profile.name))?;
+            return Ok(());
+        }
+
+        if let Some(path) = &args.write_gitconfig {
+            self.write_gitconfig(&profile, Path::new(path), args, context)
+                .await
+                .with_context(format!("while writing profile '{}' to '{}'", profile.name, path))?;
+            return Ok(());
+        }
+
+        if let Some(username) = &args.as_user {
+            let sudo = SudoGitWrapper::new(username, context.git_wrapper.clone());
+            sudo.check_access()
+                .with_context(format!("while checking sudo access to '{}'", username))?;
+            let sudo_context = CommandContext {
+                git_wrapper: Arc::new(sudo),
+                ..context.clone()
+            };
+            self.apply_profile(&profile, args, &sudo_context)
+                .await
+                .with_context(format!("while applying profile '{}' as '{}'", profile.name, username))?;
+            return self.save_inherited_profile(&profile, args, context);
+        }
+
+        self.apply_profile(&profile, args, context)
+            .await
+            .with_context(format!("while applying profile '{}'", profile.name))?;
+        self.save_inherited_profile(&profile, args, context)
+    }
+
+    fn name(&self) -> &'static str {
+        "apply"
+    }
+
+    fn description(&self) -> &'static str {
+        "Apply a git profile configuration"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::types::{Profile, KeyType, Scope, SecretStoreKind},
+        commands::tests::create_test_context,
+        profile::{mock::MockProfileManager, ProfileManager},
+        external::{
+            bitwarden::{BitwardenSshKey, MockBitwardenWrapper},
+            git::{GitConfigCall, GitWrapper, MockGitWrapper},
+            gpg::{GpgKeyInfo, MockGpgWrapper},
+            onepassword::{MockOnePasswordWrapper, SshKeyItem},
+            ssh::MockSshKnownHostsChecker,
+        },
+        detection::{DetectionResult, MockProfileDetector, TwoPhaseSetup},
+    };
+    use std::sync::Arc;
+
+    /// Helper function to create a test profile.
+    fn create_test_profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_name: Some(format!("{} User", name)),
+            git_user_email: email.to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("ssh-ed25519 AAAAC3...".to_string()),
+            vault_name: Some("test-vault".to_string()),
+            ssh_key_title: Some("test-ssh-key".to_string()),
+            scope: Some(Scope::Local),
+            ssh_key_source: None,
+            ssh_key_path: None,
+            allowed_signers: None,
+            match_patterns: vec![],
+            repos: vec![],
+            include_if_dirs: vec![],
+            host_patterns: vec![],
+            one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
+        }
+    }
+
+    /// Test that ApplyCommand can be created.
+    #[test]
+    fn test_apply_command_creation() {
+        let cmd = ApplyCommand::new();
+        assert_eq!(cmd.name(), "apply");
+        assert_eq!(cmd.description(), "Apply a git profile configuration");
+    }
+
+    /// Test applying a profile with explicit name.
+    #[tokio::test]
+    async fn test_apply_explicit_profile() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_ok());
+    }
+
+    /// Test applying a profile with global scope.
+    #[tokio::test]
+    async fn test_apply_profile_global_scope() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new();
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_ok());
+        git_wrapper.assert_config_was_set("user.email", "work@example.com", GitConfigScope::Global);
+        git_wrapper.assert_signing_was_configured(KeyType::Ssh);
+    }
+
+    /// Test applying a profile with system scope.
+    #[tokio::test]
+    async fn test_apply_profile_system_scope() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new();
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            system: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_ok());
+        git_wrapper.assert_config_was_set("user.email", "work@example.com", GitConfigScope::System);
+        git_wrapper.assert_signing_was_configured(KeyType::Ssh);
+    }
+
+    /// Test applying nonexistent profile returns error.
+    #[tokio::test]
+    async fn test_apply_nonexistent_profile() {
+        let profile_manager = Arc::new(MockProfileManager::new());
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+
+        let args = Args {
+            profile: Some("nonexistent".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), GitSetupError::ProfileNotFound { .. }));
+    }
+
+    /// Test conflicting global and system flags.
+    #[tokio::test]
+    async fn test_apply_conflicting_scope_flags() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            system: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Cannot use --global and --system"));
+    }
+
+    /// Test scope determination from profile.
+    #[test]
+    fn test_scope_determination() {
+        let cmd = ApplyCommand::new();
+        let context = create_test_context();
+        
+        // Test global scope from profile
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.scope = Some(Scope::Global);
+        
+        let args = Args::default();
+        let scope = cmd.determine_scope(&args, &profile, &context).unwrap();
+        assert_eq!(scope, GitConfigScope::Global);
+
+        // Test local scope override from args
+        let args = Args {
+            global: false,
+            system: false,
+            ..Default::default()
+        };
+        let scope = cmd.determine_scope(&args, &profile, &context).unwrap();
+        assert_eq!(scope, GitConfigScope::Global); // Profile still wins when no flags
+
+        // Test global flag override
+        let args = Args {
+            global: true,
+            ..Default::default()
+        };
+        let scope = cmd.determine_scope(&args, &profile, &context).unwrap();
+        assert_eq!(scope, GitConfigScope::Global);
+    }
+
+    /// Test that a profile without a scope is applied locally only inside a repository.
+    #[test]
+    fn test_scope_inferred_from_work_tree() {
+        let cmd = ApplyCommand::new();
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.scope = None;
+        let args = Args::default();
+
+        let context = create_test_context();
+        assert_eq!(cmd.determine_scope(&args, &profile, &context).unwrap(), GitConfigScope::Local);
+        assert_eq!(
+            ApplyCommand::scope_source(&args, &profile),
+            "inferred from the working directory"
+        );
+
+        let mut context = create_test_context();
+        context.git_wrapper = Arc::new(MockGitWrapper::new().with_inside_work_tree(false));
+        assert_eq!(cmd.determine_scope(&args, &profile, &context).unwrap(), GitConfigScope::Global);
+    }
+
+    /// Test auto-detection with high confidence.
+    #[tokio::test]
+    async fn test_auto_detection_high_confidence() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
+        
+        let mut detector = MockProfileDetector::new();
+        detector.set_detection_result(DetectionResult {
+            profile: profile.clone(),
+            confidence: 0.9,
+            matched_rules: vec![],
+            reason: "Repository matches pattern".to_string(),
+            reasons: vec!["Repository matches pattern".to_string()],
+        });
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.profile_detector = Arc::new(detector);
+
+        let args = Args {
+            profile: None, // No explicit profile
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_ok());
+    }
+
+    /// Test auto-detection with low confidence.
+    #[tokio::test]
+    async fn test_auto_detection_low_confidence() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
+        
+        let mut detector = MockProfileDetector::new();
+        detector.set_detection_result(DetectionResult {
+            profile: profile.clone(),
+            confidence: 0.3,
+            matched_rules: vec![],
+            reason: "Low confidence match".to_string(),
+            reasons: vec!["Low confidence match".to_string()],
+        });
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.profile_detector = Arc::new(detector);
+
+        let args = Args {
+            profile: None, // No explicit profile
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("auto-detection failed"));
+    }
+
+    /// Test applying profile with 1Password SSH key.
+    #[tokio::test]
+    async fn test_apply_profile_with_1password_ssh() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.one_password = true;
+        profile.signing_key = None; // Will be retrieved from 1Password
+        
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        
+        let mut onepassword_wrapper = MockOnePasswordWrapper::new();
+        onepassword_wrapper.set_ssh_keys(vec![SshKeyItem {
+            id: "ssh-key-id".to_string(),
+            title: "test-ssh-key".to_string(),
+            vault: crate::external::onepassword::Vault {
+                id: "vault-id".to_string(),
+                name: "test-vault".to_string(),
+            },
+            category: "SSH_KEY".to_string(),
+            public_key: Some("ssh-ed25519 AAAAC3...".to_string()),
+            private_key: None,
+        }]);
+
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.onepassword_wrapper = Arc::new(onepassword_wrapper);
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_ok());
+        git_wrapper.assert_signing_was_configured(KeyType::Ssh);
+    }
+
+    /// Test applying a profile whose SSH key is stored in Bitwarden.
+    #[tokio::test]
+    async fn test_apply_profile_with_bitwarden_ssh() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.one_password = true;
+        profile.secret_store = Some(SecretStoreKind::Bitwarden);
+        profile.signing_key = None;
+
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.onepassword_wrapper = Arc::new(MockOnePasswordWrapper::new());
+        context.bitwarden_wrapper = Arc::new(MockBitwardenWrapper::new().with_ssh_keys(vec![BitwardenSshKey {
+            id: "bw-1".to_string(),
+            name: "test-ssh-key".to_string(),
+            public_key: "ssh-ed25519 AAAAC3...bitwarden".to_string(),
+            fingerprint: None,
+        }]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(
+            git_wrapper.signing_keys(),
+            vec![(KeyType::Ssh, "ssh-ed25519 AAAAC3...bitwarden".to_string())]
+        );
+    }
+
+    /// Test that --post-apply-verify signs test data with the applied GPG key.
+    #[tokio::test]
+    async fn test_apply_post_apply_verify_gpg() {
+        let profile = Profile {
+            key_type: KeyType::Gpg,
+            signing_key: Some("ABCD1234".to_string()),
+            ..create_test_profile("work", "work@example.com")
+        };
+        let gpg_wrapper = Arc::new(MockGpgWrapper::new());
+
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.gpg_wrapper = gpg_wrapper.clone();
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            post_apply_verify: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(gpg_wrapper.sign_data_calls(), vec!["ABCD1234".to_string()]);
+    }
+
+    /// Helper function to create a GPG key for `email`.
+    fn create_test_gpg_key(key_id: &str, email: &str) -> GpgKeyInfo {
+        GpgKeyInfo {
+            key_id: key_id.to_string(),
+            fingerprint: format!("{:0>40}", key_id),
+            user_id: format!("Test User <{}>", email),
+            key_type: "RSA".to_string(),
+            key_size: Some(4096),
+            creation_date: "2024-01-01".to_string(),
+            expiration_date: None,
+            trust_level: "ultimate".to_string(),
+        }
+    }
+
+    /// Test that a GPG profile without a signing key uses the key matching its email.
+    #[tokio::test]
+    async fn test_apply_gpg_key_looked_up_by_email() {
+        let profile = Profile {
+            key_type: KeyType::Gpg,
+            signing_key: None,
+            ..create_test_profile("work", "work@example.com")
+        };
+        let mut gpg_wrapper = MockGpgWrapper::new();
+        gpg_wrapper.add_key(create_test_gpg_key("ABCD1234", "work@example.com"));
+        gpg_wrapper.add_key(create_test_gpg_key("EF567890", "personal@example.com"));
+        let git_wrapper = MockGitWrapper::new();
+
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.gpg_wrapper = Arc::new(gpg_wrapper);
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(git_wrapper.signing_keys(), vec![(KeyType::Gpg, "ABCD1234".to_string())]);
+    }
+
+    /// Test that several keys for the profile's email are reported instead of guessed.
+    #[tokio::test]
+    async fn test_apply_gpg_multiple_keys_for_email() {
+        let profile = Profile {
+            key_type: KeyType::Gpg,
+            signing_key: None,
+            ..create_test_profile("work", "work@example.com")
+        };
+        let mut gpg_wrapper = MockGpgWrapper::new();
+        gpg_wrapper.add_key(create_test_gpg_key("ABCD1234", "work@example.com"));
+        gpg_wrapper.add_key(create_test_gpg_key("EF567890", "work@example.com"));
+
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
+        context.gpg_wrapper = Arc::new(gpg_wrapper);
+
+        let err = ApplyCommand::local_gpg_key(&profile, &context).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("2 keys match work@example.com"));
+        assert!(message.contains("ABCD1234, EF567890"));
+
+        // An explicit signing key wins over the lookup
+        let profile = Profile { signing_key: Some("EF567890".to_string()), ..profile };
+        assert_eq!(ApplyCommand::local_gpg_key(&profile, &context).unwrap(), "EF567890");
+    }
+
+    /// Context with a GPG profile whose key expires `expires_in_days` from now, if ever.
+    fn expiring_key_context(expires_in_days: Option<i64>) -> (CommandContext, Profile) {
+        let profile = Profile {
+            key_type: KeyType::Gpg,
+            signing_key: Some("ABCD1234".to_string()),
+            ..create_test_profile("work", "work@example.com")
+        };
+        let mut key = create_test_gpg_key("ABCD1234", "work@example.com");
+        key.expiration_date = expires_in_days.map(|days| {
+            (chrono::Utc::now() + chrono::Duration::days(days) + chrono::Duration::hours(1))
+                .timestamp()
+                .to_string()
+        });
+        let mut gpg_wrapper = MockGpgWrapper::new();
+        gpg_wrapper.add_key(key);
+
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
+        context.gpg_wrapper = Arc::new(gpg_wrapper);
+        (context, profile)
+    }
+
+    fn expiry_args(allow_expired: bool) -> Args {
+        Args {
+            profile: Some("work".to_string()),
+            warn_expiry_days: 30,
+            allow_expired,
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that an expired GPG key aborts the apply unless --allow-expired.
+    #[tokio::test]
+    async fn test_apply_expired_gpg_key() {
+        let (context, profile) = expiring_key_context(Some(-3));
+
+        let err = ApplyCommand::new().execute(&expiry_args(false), &context).await.unwrap_err();
+        assert!(err.to_string().contains("expired 3 day(s) ago"), "{}", err);
+
+        ApplyCommand::new().execute(&expiry_args(true), &context).await.unwrap();
+        assert!(ApplyCommand::new().check_key_expiry(&profile, &expiry_args(true), &context).is_ok());
+    }
+
+    /// Test that keys expiring soon, later or never do not abort the apply.
+    #[tokio::test]
+    async fn test_apply_expiring_and_non_expiring_gpg_keys() {
+        for expires_in_days in [Some(5), Some(0), Some(365), None] {
+            let (context, profile) = expiring_key_context(expires_in_days);
+            assert!(
+                ApplyCommand::new().check_key_expiry(&profile, &expiry_args(false), &context).is_ok(),
+                "key expiring in {:?} days",
+                expires_in_days
+            );
+            ApplyCommand::new().execute(&expiry_args(false), &context).await.unwrap();
+        }
+    }
+
+    /// Test that the key found by email is checked when the profile sets no signing key.
+    #[tokio::test]
+    async fn test_apply_expired_gpg_key_found_by_email() {
+        let (context, profile) = expiring_key_context(Some(-3));
+        let profile = Profile { signing_key: None, ..profile };
+        context.profile_manager.update(&profile.name, profile.clone()).unwrap();
+
+        let err = ApplyCommand::new().check_key_expiry(&profile, &expiry_args(false), &context).unwrap_err();
+        assert!(err.to_string().contains("GPG key ABCD1234"), "{}", err);
+        assert!(err.to_string().contains("expired 3 day(s) ago"), "{}", err);
+
+        let err = ApplyCommand::new().execute(&expiry_args(false), &context).await.unwrap_err();
+        assert!(err.to_string().contains("expired 3 day(s) ago"), "{}", err);
+        ApplyCommand::new().execute(&expiry_args(true), &context).await.unwrap();
+    }
+
+    /// Test that --test-signing runs a signing test with the applied key.
+    #[tokio::test]
+    async fn test_apply_with_test_signing() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new();
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            test_signing: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            git_wrapper.test_sign_calls(),
+            vec![(KeyType::Ssh, "ssh-ed25519 AAAAC3...".to_string())]
+        );
+    }
+
+    /// Test that a failed signing test rolls back config when requested.
+    #[tokio::test]
+    async fn test_apply_test_signing_failure_rolls_back() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new().with_sign_result(false);
+        git_wrapper.mock_set_config("user.email", "old@example.com");
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            test_signing: true,
+            rollback_on_test_failure: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Signing test failed"));
+        assert_eq!(
+            git_wrapper.get_config("user.email", None).unwrap(),
+            Some("old@example.com".to_string())
+        );
+        assert_eq!(git_wrapper.get_config("user.name", None).unwrap(), None);
+    }
+
+    /// Test that rolling back restores changed keys and unsets keys that were absent.
+    #[test]
+    fn test_apply_transaction_rollback() {
+        let git = MockGitWrapper::new();
+        git.mock_set_config("user.email", "old@example.com");
+
+        let transaction = ApplyTransaction::begin(Arc::new(git.clone()), GitConfigScope::Local).unwrap();
+        git.set_config("user.email", "new@example.com", GitConfigScope::Local).unwrap();
+        git.set_config("user.name", "New User", GitConfigScope::Local).unwrap();
+        transaction.rollback().unwrap();
+
+        git.assert_config_was_set("user.email", "old@example.com", GitConfigScope::Local);
+        git.assert_config_was_unset("user.name", GitConfigScope::Local);
+        assert_eq!(git.get_config("user.email", None).unwrap(), Some("old@example.com".to_string()));
+        assert_eq!(git.get_config("user.name", None).unwrap(), None);
+
+        // Committing leaves the changes in place
+        let transaction = ApplyTransaction::begin(Arc::new(git.clone()), GitConfigScope::Local).unwrap();
+        git.set_config("user.email", "new@example.com", GitConfigScope::Local).unwrap();
+        transaction.commit();
+        assert_eq!(git.get_config("user.email", None).unwrap(), Some("new@example.com".to_string()));
+    }
+
+    /// Test that a failure configuring signing rolls back the user config already set.
+    #[tokio::test]
+    async fn test_apply_signing_failure_rolls_back_user_config() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new().with_signing_failure();
+        git_wrapper.mock_set_config("user.email", "old@example.com");
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        assert!(ApplyCommand::new().execute(&args, &context).await.is_err());
+
+        // The email was applied first, then restored
+        let set_email = |value: &str| GitConfigCall::Set {
+            key: "user.email".to_string(),
+            value: value.to_string(),
+            scope: GitConfigScope::Local,
+        };
+        let calls = git_wrapper.call_log();
+        assert_eq!(calls[0], set_email("work@example.com"));
+        assert!(calls[1..].contains(&set_email("old@example.com")));
+        assert_eq!(
+            git_wrapper.get_config("user.email", None).unwrap(),
+            Some("old@example.com".to_string())
+        );
+        assert_eq!(git_wrapper.get_config("user.name", None).unwrap(), None);
+    }
+
+    /// Test that a key that cannot be restored does not stop the rollback of the others.
+    #[test]
+    fn test_apply_transaction_rollback_continues_after_failure() {
+        let git = MockGitWrapper::new().with_unwritable_key("user.name");
+        git.mock_set_config("user.email", "old@example.com");
+
+        let transaction = ApplyTransaction::begin(Arc::new(git.clone()), GitConfigScope::Local).unwrap();
+        git.set_config("user.email", "new@example.com", GitConfigScope::Local).unwrap();
+        let err = transaction.rollback().unwrap_err();
+
+        assert!(err.to_string().contains("could not restore user.name"));
+        assert_eq!(git.get_config("user.email", None).unwrap(), Some("old@example.com".to_string()));
+    }
+
+    /// Test that a failed rollback keeps the apply error and attaches the rollback failure.
+    #[tokio::test]
+    async fn test_apply_failed_rollback_keeps_apply_error() {
+        let profile = create_test_profile("work", "work@example.com");
+        let git_wrapper = MockGitWrapper::new()
+            .with_signing_failure()
+            .with_unwritable_key("core.hooksPath");
+        git_wrapper.mock_set_config("user.email", "old@example.com");
+
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        let err = ApplyCommand::new().execute(&args, &context).await.unwrap_err();
+
+        assert!(err.root_cause().to_string().contains("Mock SSH signing configuration failure"));
+        assert!(err.to_string().contains("could not restore core.hooksPath"));
+        assert_eq!(
+            git_wrapper.get_config("user.email", None).unwrap(),
+            Some("old@example.com".to_string())
+        );
+    }
+
+    /// Test that a failed signing test keeps the applied config without rollback.
+    #[tokio::test]
+    async fn test_apply_test_signing_failure_without_rollback() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new().with_sign_result(false);
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            test_signing: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_err());
+        assert_eq!(
+            git_wrapper.get_config("user.email", None).unwrap(),
+            Some("work@example.com".to_string())
+        );
+    }
+
+    /// Test that --inherit-from applies overrides without saving a profile.
+    #[tokio::test]
+    async fn test_apply_inherit_from_with_overrides() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
+        let git_wrapper = MockGitWrapper::new();
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            inherit_from: Some("work".to_string()),
+            email: Some("client@example.com".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_ok());
+
+        assert_eq!(
+            git_wrapper.get_config("user.email", None).unwrap(),
+            Some("client@example.com".to_string())
+        );
+        assert_eq!(
+            git_wrapper.get_config("user.name", None).unwrap(),
+            Some("work User".to_string())
+        );
+        // The parent is untouched and nothing new is saved
+        assert_eq!(profile_manager.create_call_count(), 0);
+        assert_eq!(profile_manager.update_call_count(), 0);
+    }
+
+    /// Test that --save-as persists the combined profile.
+    #[tokio::test]
+    async fn test_apply_inherit_from_save_as() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            inherit_from: Some("work".to_string()),
+            name: Some("Client User".to_string()),
+            save_as: Some("client".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_ok());
+
+        assert!(profile_manager.was_called_with_create("client"));
+        let saved = profile_manager.read("client").unwrap().unwrap();
+        assert_eq!(saved.git_user_name, Some("Client User".to_string()));
+        assert_eq!(saved.git_user_email, "work@example.com");
+    }
+
+    /// Test that --save-as keeps nothing when the apply fails.
+    #[tokio::test]
+    async fn test_apply_inherit_from_save_as_after_failed_apply() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        context.git_wrapper = Arc::new(MockGitWrapper::new().with_sign_result(false));
+
+        let args = Args {
+            inherit_from: Some("work".to_string()),
+            save_as: Some("client".to_string()),
+            test_signing: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_err());
+        assert_eq!(profile_manager.create_call_count(), 0);
+    }
+
+    /// Test that inheriting from a missing profile fails.
+    #[tokio::test]
+    async fn test_apply_inherit_from_missing_parent() {
+        let context = create_test_context();
+        let args = Args {
+            inherit_from: Some("missing".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+        assert!(matches!(result.unwrap_err(), GitSetupError::ProfileNotFound { .. }));
+    }
+
+    /// Test that --global-then-local only overrides the fields the local profile sets.
+    #[tokio::test]
+    async fn test_apply_global_then_local() {
+        let personal = create_test_profile("personal", "me@personal.com");
+        let work = Profile {
+            git_user_name: None,
+            ..create_test_profile("work", "me@work.com")
+        };
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![personal, work]));
+        let git_wrapper = MockGitWrapper::new().with_scoped_config(GitConfigScope::Local, HashMap::new());
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            global_then_local: Some(vec!["personal".to_string(), "work".to_string()]),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_ok());
+
+        assert_eq!(
+            git_wrapper.get_config("user.email", Some(GitConfigScope::Global)).unwrap(),
+            Some("me@personal.com".to_string())
+        );
+        assert_eq!(
+            git_wrapper.get_config("user.email", Some(GitConfigScope::Local)).unwrap(),
+            Some("me@work.com".to_string())
+        );
+        assert_eq!(git_wrapper.get_config("user.name", Some(GitConfigScope::Local)).unwrap(), None);
+
+        let activations = profile_manager.activation_history().unwrap();
+        assert_eq!(activations.len(), 2);
+        assert_eq!(activations[0].repo_path, None);
+        assert!(activations[1].repo_path.is_some());
+    }
+
+    /// Test that a global apply points out the local overrides of a global-then-local setup.
+    #[test]
+    fn test_local_override_note() {
+        let personal = create_test_profile("personal", "me@personal.com");
+        let mut detector = MockProfileDetector::new();
+        detector.set_two_phase_setup(TwoPhaseSetup {
+            global_profile: "personal".to_string(),
+            local_profile: "work".to_string(),
+            local_keys: vec!["user.email".to_string(), "user.signingkey".to_string()],
+        });
+        let mut context = create_test_context();
+        context.profile_detector = Arc::new(detector);
+
+        let cmd = ApplyCommand::new();
+        assert_eq!(
+            cmd.local_override_note(&personal, &context).as_deref(),
+            Some("Profile 'work' overrides user.email, user.signingkey in this repository")
+        );
+        assert_eq!(cmd.local_override_note(&create_test_profile("work", "me@work.com"), &context), None);
+
+        context.git_wrapper = Arc::new(MockGitWrapper::new().with_inside_work_tree(false));
+        assert_eq!(cmd.local_override_note(&personal, &context), None);
+    }
+
+    /// Test that --global-then-local rejects the same profile twice.
+    #[tokio::test]
+    async fn test_apply_global_then_local_same_profile() {
+        let context = create_test_context();
+        let args = Args {
+            global_then_local: Some(vec!["work".to_string(), "work".to_string()]),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        let result = cmd.execute(&args, &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::InvalidProfile { .. }));
+    }
+
+    /// Test that a successful apply is recorded in the activation history.
+    #[tokio::test]
+    async fn test_apply_records_activation() {
+        let profile = create_test_profile("work", "work@example.com");
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ApplyCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_ok());
+
+        let activations = profile_manager.activation_history().unwrap();
+        assert_eq!(activations.len(), 1);
+        assert_eq!(activations[0].profile_name, "work");
+        assert_eq!(activations[0].repo_path, None);
+    }
+
+    /// Test that --annotate-commit notes the applied profile on HEAD.
+    #[tokio::test]
+    async fn test_apply_annotate_commit() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![create_test_profile(
+            "work",
+            "work@example.com",
+        )]));
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            annotate_commit: true,
+            quiet: true,
+            ..Default::default()
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(
+            git_wrapper.get_last_commit_note(PROFILE_NOTES_REF).unwrap().as_deref(),
+            Some("work")
+        );
+    }
+
+    /// Test that --scope-per-profile writes keys with a field-level scope at that scope.
+    #[tokio::test]
+    async fn test_apply_scope_per_profile() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.per_field_scope.insert("commit.gpgsign".to_string(), Scope::Local);
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let git_wrapper = MockGitWrapper::new().with_call_tracking();
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            scope_per_profile: true,
+            quiet: true,
+            ..Default::default()
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        git_wrapper.assert_signing_was_configured(KeyType::Ssh);
+        let calls = git_wrapper.call_log();
+        let set = |key: &str, value: &str, scope: GitConfigScope| GitConfigCall::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+            scope,
+        };
+        assert!(calls.contains(&set("user.email", "work@example.com", GitConfigScope::Global)));
+        assert!(calls.contains(&set("gpg.format", "ssh", GitConfigScope::Global)));
+        assert!(calls.contains(&set("commit.gpgsign", "true", GitConfigScope::Local)));
+        assert!(!calls.contains(&set("commit.gpgsign", "true", GitConfigScope::Global)));
+    }
+
+    /// Test that the profile's hooks are installed unless --no-hooks is given.
+    #[tokio::test]
+    async fn test_apply_installs_hooks() {
+        let hooks_dir = tempfile::TempDir::new().unwrap();
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.git_hooks_dir = Some(hooks_dir.path().to_path_buf());
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+        let hooks_path = hooks_dir.path().to_string_lossy().to_string();
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            no_hooks: true,
+            quiet: true,
+            ..Default::default()
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert!(!git_wrapper
+            .call_log()
+            .iter()
+            .any(|call| matches!(call, GitConfigCall::Set { key, .. } if key == "core.hooksPath")));
+
+        let args = Args { no_hooks: false, ..args };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        git_wrapper.assert_config_was_set("core.hooksPath", &hooks_path, GitConfigScope::Global);
+    }
+
+    /// Test that --update-allowed-signers writes the entries of --from-profile and points git at them.
+    #[tokio::test]
+    async fn test_apply_updates_allowed_signers() {
+        let mut team = create_test_profile("team", "team@example.com");
+        team.allowed_signers_content = Some("jane@example.com ssh-ed25519 AAAAC3Nza".to_string());
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            create_test_profile("work", "work@example.com"),
+            team,
+        ]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            update_allowed_signers: true,
+            from_profile: Some("team".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(
+            git_wrapper.allowed_signers("work").as_deref(),
+            Some("jane@example.com ssh-ed25519 AAAAC3Nza")
+        );
+        git_wrapper.assert_config_was_set(
+            "gpg.ssh.allowedSignersFile",
+            "/mock/allowed_signers/work",
+            GitConfigScope::Global,
+        );
+    }
+
+    /// Test that --check-capabilities aborts before changing config when git is unavailable.
+    #[tokio::test]
+    async fn test_apply_check_capabilities() {
+        let git_wrapper = MockGitWrapper::new().with_git_available(false);
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![create_test_profile(
+            "work",
+            "work@example.com",
+        )]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            check_capabilities: true,
+            quiet: true,
+            ..Default::default()
+        };
+        let err = ApplyCommand::new().execute(&args, &context).await.unwrap_err();
+        assert!(matches!(err.root_cause(), GitSetupError::InvalidProfile { .. }));
+        assert!(git_wrapper.config_writes().is_empty());
+    }
+
+    /// Test that --verify-host adds unknown hosts only when confirmed.
+    #[test]
+    fn test_verify_host() {
+        let known_hosts = Arc::new(MockSshKnownHostsChecker::new().with_known_host("github.com"));
+        let cmd = ApplyCommand::new().with_known_hosts_checker(known_hosts.clone());
+        let args = Args {
+            quiet: true,
+            ..Default::default()
+        };
+
+        cmd.verify_host("github.com", &args, &mut "".as_bytes()).unwrap();
+        assert!(cmd.verify_host("gitlab.com", &args, &mut "n\n".as_bytes()).is_err());
+        assert!(known_hosts.added_hosts().is_empty());
+
+        cmd.verify_host("gitlab.com", &args, &mut "y\n".as_bytes()).unwrap();
+        let args = Args {
+            add_unknown_host: true,
+            ..args
+        };
+        cmd.verify_host("git.example.com", &args, &mut "".as_bytes()).unwrap();
+        assert_eq!(
+            known_hosts.added_hosts(),
+            vec!["gitlab.com".to_string(), "git.example.com".to_string()]
+        );
+    }
+
+    /// Test that --scope-inherit does not repeat globally set values in the local config.
     #[tokio::test]
-    async fn test_apply_profile_global_scope() {
-        let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
-        
+    async fn test_apply_scope_inherit() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.scope = Some(Scope::Local);
+        let git_wrapper = MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Global,
+            HashMap::from([
+                ("user.email".to_string(), "work@example.com".to_string()),
+                ("user.name".to_string(), "someone else".to_string()),
+            ]),
+        );
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.git_wrapper = Arc::new(git_wrapper.clone());
 
         let args = Args {
             profile: Some("work".to_string()),
-            global: true,
+            scope_inherit: true,
             quiet: true,
             ..Default::default()
         };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        git_wrapper.assert_config_was_set("user.name", "work User", GitConfigScope::Local);
+        assert!(!git_wrapper.call_log().contains(&GitConfigCall::Set {
+            key: "user.email".to_string(),
+            value: "work@example.com".to_string(),
+            scope: GitConfigScope::Local,
+        }));
+    }
 
-        let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
+    /// Test that --print-only leaves the git config alone.
+    #[tokio::test]
+    async fn test_apply_print_only() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![create_test_profile(
+            "work",
+            "work@example.com",
+        )]));
+        let git_wrapper = MockGitWrapper::new();
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.git_wrapper = Arc::new(git_wrapper.clone());
 
-        assert!(result.is_ok());
+        let args = Args {
+            profile: Some("work".to_string()),
+            global: true,
+            print_only: true,
+            ..Default::default()
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        git_wrapper.assert_no_calls_made();
     }
 
-    /// Test applying a profile with system scope.
+    /// Test that a dry run reads the current config but writes and records nothing.
     #[tokio::test]
-    async fn test_apply_profile_system_scope() {
-        let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
-        
+    async fn test_apply_dry_run() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![create_test_profile(
+            "work",
+            "work@example.com",
+        )]));
+        let git_wrapper = MockGitWrapper::new();
+        git_wrapper.mock_set_config("user.email", "old@example.com");
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
+        context.git_wrapper = Arc::new(git_wrapper.clone());
 
         let args = Args {
             profile: Some("work".to_string()),
-            system: true,
+            global: true,
+            dry_run: true,
             quiet: true,
             ..Default::default()
         };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        assert!(git_wrapper.config_writes().is_empty());
+        assert!(git_wrapper
+            .call_log()
+            .iter()
+            .all(|call| matches!(call, GitConfigCall::Get { .. })));
+        assert!(profile_manager.activation_history().unwrap().is_empty());
+    }
+
+    /// Test that the config triples follow the key type and the field-level scopes.
+    #[test]
+    fn test_scoped_config_entries() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.key_type = KeyType::Gitsign;
+        profile.per_field_scope.insert("User.Email".to_string(), Scope::System);
+
+        let entries = ApplyCommand::scoped_config_entries(&profile, None, &GitConfigScope::Global);
+        let entry = |key: &str| entries.iter().find(|(name, _, _)| *name == key).cloned();
+
+        assert_eq!(
+            entry("user.email"),
+            Some(("user.email", "work@example.com".to_string(), GitConfigScope::System))
+        );
+        assert_eq!(
+            entry("gpg.x509.program"),
+            Some(("gpg.x509.program", "gitsign".to_string(), GitConfigScope::Global))
+        );
+        assert_eq!(entry("user.signingkey"), None);
+    }
+
+    /// Test that the passphrase is read from --passphrase-env.
+    #[test]
+    fn test_read_passphrase_from_env() {
+        let profile = create_test_profile("work", "work@example.com");
+        let var = "GIT_SETUP_TEST_APPLY_PASSPHRASE";
 
+        let args = Args {
+            passphrase_env: Some(var.to_string()),
+            ..Default::default()
+        };
+        assert!(ApplyCommand::new().read_passphrase(&profile, &args).is_err());
+
+        let cmd = ApplyCommand::new().with_env_var(var, "hunter2");
+        let passphrase = cmd.read_passphrase(&profile, &args).unwrap();
+        assert_eq!(passphrase.expose_secret(), "hunter2");
+    }
+
+    /// Test that the passphrase is read from --passphrase-command output.
+    #[test]
+    fn test_read_passphrase_from_command() {
         let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
+        let profile = create_test_profile("work", "work@example.com");
 
-        assert!(result.is_ok());
+        let args = Args {
+            passphrase_command: Some("echo hunter2".to_string()),
+            ..Default::default()
+        };
+        let passphrase = cmd.read_passphrase(&profile, &args).unwrap();
+        assert_eq!(passphrase.expose_secret(), "hunter2");
+
+        let args = Args {
+            passphrase_command: Some("exit 1".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            cmd.read_passphrase(&profile, &args).unwrap_err(),
+            GitSetupError::ExternalCommand { .. }
+        ));
     }
 
-    /// Test applying nonexistent profile returns error.
+    /// Test that GPG profiles requiring a passphrase unlock their signing key in gpg-agent.
     #[tokio::test]
-    async fn test_apply_nonexistent_profile() {
-        let profile_manager = Arc::new(MockProfileManager::new());
+    async fn test_apply_gpg_profile_with_passphrase_unlocks_key() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.key_type = KeyType::Gpg;
+        profile.signing_key = Some("ABCD1234".to_string());
+        profile.require_passphrase = true;
+
+        let gpg_wrapper = Arc::new(MockGpgWrapper::new());
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        context.gpg_wrapper = gpg_wrapper.clone();
 
         let args = Args {
-            profile: Some("nonexistent".to_string()),
+            profile: Some("work".to_string()),
+            passphrase_command: Some("echo hunter2".to_string()),
             quiet: true,
             ..Default::default()
         };
 
         let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
-
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), GitSetupError::ProfileNotFound { .. }));
+        cmd.execute(&args, &context).await.unwrap();
+        assert_eq!(gpg_wrapper.preset_passphrase_calls(), vec!["ABCD1234".to_string()]);
+
+        let mut gpg_wrapper = MockGpgWrapper::new();
+        gpg_wrapper.set_should_fail("preset_passphrase", true);
+        context.gpg_wrapper = Arc::new(gpg_wrapper);
+
+        let error = cmd.execute(&args, &context).await.unwrap_err();
+        assert!(matches!(error.root_cause(), GitSetupError::ExternalCommand { .. }));
+        assert_eq!(
+            error.to_string(),
+            "while applying profile 'work': while unlocking the signing key: \
+             External command 'gpg --pinentry-mode loopback --sign' failed: Mock failure"
+        );
     }
 
-    /// Test conflicting global and system flags.
+    /// Test that a passphrase-protected profile without a key path is rejected.
     #[tokio::test]
-    async fn test_apply_conflicting_scope_flags() {
-        let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
-        
+    async fn test_apply_passphrase_profile_without_key_path() {
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.require_passphrase = true;
+
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
 
         let args = Args {
             profile: Some("work".to_string()),
-            global: true,
-            system: true,
+            passphrase_command: Some("echo hunter2".to_string()),
             quiet: true,
             ..Default::default()
         };
 
         let cmd = ApplyCommand::new();
         let result = cmd.execute(&args, &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::InvalidProfile { .. }));
+    }
 
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Cannot use --global and --system"));
+    /// Test that --write-gitconfig writes to the file instead of git, overwriting it unless --merge is given.
+    #[tokio::test]
+    async fn test_apply_write_gitconfig() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(".gitconfig");
+        std::fs::write(&path, "[core]\n\teditor = vim\n").unwrap();
+
+        let git_wrapper = MockGitWrapper::new();
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![create_test_profile(
+            "work",
+            "work@example.com",
+        )]));
+        let mut context = create_test_context();
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            profile: Some("work".to_string()),
+            write_gitconfig: Some(path.display().to_string()),
+            merge: true,
+            quiet: true,
+            ..Default::default()
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+
+        let file = FilesystemGitWrapper::new(&path);
+        assert_eq!(file.get_config("core.editor", None).unwrap().as_deref(), Some("vim"));
+        assert_eq!(file.get_config("user.email", None).unwrap().as_deref(), Some("work@example.com"));
+        assert_eq!(file.get_config("gpg.format", None).unwrap().as_deref(), Some("ssh"));
+        assert_eq!(git_wrapper.get_config("user.email", None).unwrap(), None);
+        assert!(profile_manager.activation_history().unwrap().is_empty());
+
+        let args = Args { merge: false, ..args };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(file.get_config("core.editor", None).unwrap(), None);
+        assert_eq!(file.get_config("user.email", None).unwrap().as_deref(), Some("work@example.com"));
     }
 
-    /// Test scope determination from profile.
-    #[test]
-    fn test_scope_determination() {
-        let cmd = ApplyCommand::new();
-        
-        // Test global scope from profile
+    /// Test that a failed --write-gitconfig apply leaves the file untouched.
+    #[tokio::test]
+    async fn test_apply_write_gitconfig_failure_keeps_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(".gitconfig");
+        std::fs::write(&path, "[core]\n\teditor = vim\n").unwrap();
+
         let mut profile = create_test_profile("work", "work@example.com");
-        profile.scope = Some(Scope::Global);
-        
-        let args = Args::default();
-        let scope = cmd.determine_scope(&args, &profile).unwrap();
-        assert_eq!(scope, GitConfigScope::Global);
+        profile.signing_key = None;
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
 
-        // Test local scope override from args
         let args = Args {
-            global: false,
-            system: false,
+            profile: Some("work".to_string()),
+            write_gitconfig: Some(path.display().to_string()),
+            quiet: true,
             ..Default::default()
         };
-        let scope = cmd.determine_scope(&args, &profile).unwrap();
-        assert_eq!(scope, GitConfigScope::Global); // Profile still wins when no flags
+        assert!(ApplyCommand::new().execute(&args, &context).await.is_err());
 
-        // Test global flag override
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[core]\n\teditor = vim\n");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    /// Test that --output-env-file writes the identity next to a --write-gitconfig file.
+    #[tokio::test]
+    async fn test_apply_output_env_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let gitconfig = dir.path().join(".gitconfig");
+        let env_file = dir.path().join("out").join(".env");
+
+        let mut profile = create_test_profile("work", "work@example.com");
+        profile.require_passphrase = true;
+        profile.key_type = KeyType::X509;
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+
+        let cmd = ApplyCommand::new().with_env_var("GIT_SETUP_TEST_ENV_FILE_PASSPHRASE", "s3cr3t");
         let args = Args {
-            global: true,
+            profile: Some("work".to_string()),
+            write_gitconfig: Some(gitconfig.display().to_string()),
+            output_env_file: Some(env_file.display().to_string()),
+            passphrase_env: Some("GIT_SETUP_TEST_ENV_FILE_PASSPHRASE".to_string()),
+            quiet: true,
             ..Default::default()
         };
-        let scope = cmd.determine_scope(&args, &profile).unwrap();
-        assert_eq!(scope, GitConfigScope::Global);
+        cmd.execute(&args, &context).await.unwrap();
+
+        let content = std::fs::read_to_string(&env_file).unwrap();
+        assert!(content.starts_with("GIT_SETUP_PROFILE_NAME=\"work\"\n"));
+        assert!(content.contains("GIT_AUTHOR_EMAIL=\"work@example.com\"\n"));
+        assert!(content.contains("GIT_SIGNING_KEY=\"ssh-ed25519 AAAAC3...\"\n"));
+        assert!(!content.contains("s3cr3t"));
+        assert!(gitconfig.exists());
+
+        let args = Args { include_secrets: true, ..args };
+        cmd.execute(&args, &context).await.unwrap();
+        let content = std::fs::read_to_string(&env_file).unwrap();
+        assert!(content.contains("GIT_SIGNING_KEY_PASSPHRASE=\"s3cr3t\"\n"));
     }
 
-    /// Test auto-detection with high confidence.
+    /// Test that a missing profile is created from flags, saved unless --no-persist.
     #[tokio::test]
-    async fn test_auto_detection_high_confidence() {
-        let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
-        
-        let mut detector = MockProfileDetector::new();
-        detector.set_detection_result(DetectionResult {
-            profile: profile.clone(),
-            confidence: 0.9,
-            matched_rules: vec![],
-            reason: "Repository matches pattern".to_string(),
-            reasons: vec!["Repository matches pattern".to_string()],
-        });
-
+    async fn test_apply_create_if_missing() {
+        let git_wrapper = MockGitWrapper::new();
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
-        context.profile_detector = Arc::new(detector);
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+        context.profile_manager = profile_manager.clone();
 
         let args = Args {
-            profile: None, // No explicit profile
+            profile: Some("ci".to_string()),
+            create_if_missing: true,
+            email: Some("ci@example.com".to_string()),
+            signing_key: Some("ssh-ed25519 AAAAC3...".to_string()),
+            no_persist: true,
             quiet: true,
             ..Default::default()
         };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(git_wrapper.get_config("user.email", None).unwrap().as_deref(), Some("ci@example.com"));
+        assert_eq!(profile_manager.create_call_count(), 0);
 
-        let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
+        let args = Args { no_persist: false, ..args };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert!(profile_manager.was_called_with_create("ci"));
 
-        assert!(result.is_ok());
+        // An existing profile is applied as it is
+        let args = Args {
+            email: Some("other@example.com".to_string()),
+            ..args
+        };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(profile_manager.create_call_count(), 1);
+        assert_eq!(git_wrapper.get_config("user.email", None).unwrap().as_deref(), Some("ci@example.com"));
     }
 
-    /// Test auto-detection with low confidence.
+    /// Test that an ephemeral profile is deleted once it has been applied.
     #[tokio::test]
-    async fn test_auto_detection_low_confidence() {
-        let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
-        
-        let mut detector = MockProfileDetector::new();
-        detector.set_detection_result(DetectionResult {
-            profile: profile.clone(),
-            confidence: 0.3,
-            matched_rules: vec![],
-            reason: "Low confidence match".to_string(),
-            reasons: vec!["Low confidence match".to_string()],
-        });
-
+    async fn test_apply_create_if_missing_then_delete() {
+        let git_wrapper = MockGitWrapper::new();
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
-        context.profile_detector = Arc::new(detector);
+        context.git_wrapper = Arc::new(git_wrapper.clone());
+        context.profile_manager = profile_manager.clone();
 
         let args = Args {
-            profile: None, // No explicit profile
+            profile: Some("build".to_string()),
+            create_if_missing_then_delete: true,
+            email: Some("build@example.com".to_string()),
+            profile_key_type: Some(KeyType::Gitsign),
             quiet: true,
             ..Default::default()
         };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
 
-        let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
+        assert_eq!(git_wrapper.get_config("user.email", None).unwrap().as_deref(), Some("build@example.com"));
+        assert!(profile_manager.was_called_with_create("build"));
+        assert!(profile_manager.was_called_with_delete("build"));
+        assert!(!profile_manager.exists("build").unwrap());
+    }
 
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("auto-detection failed"));
+    /// Test that the email of a created profile falls back to GIT_AUTHOR_EMAIL.
+    #[test]
+    fn test_minimal_profile_email() {
+        let args = Args {
+            name: Some("CI Bot".to_string()),
+            ..Default::default()
+        };
+        let profile = ApplyCommand::minimal_profile("ci", &args, Some("bot@example.com".to_string())).unwrap();
+        assert_eq!(profile.git_user_email, "bot@example.com");
+        assert_eq!(profile.git_user_name.as_deref(), Some("CI Bot"));
+        assert_eq!(profile.key_type, KeyType::Ssh);
+
+        let result = ApplyCommand::minimal_profile("ci", &args, None);
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
     }
 
-    /// Test applying profile with 1Password SSH key.
+    /// Test that --tag applies the tagged profiles in name order and skips the others.
     #[tokio::test]
-    async fn test_apply_profile_with_1password_ssh() {
-        let mut profile = create_test_profile("work", "work@example.com");
-        profile.one_password = true;
-        profile.signing_key = None; // Will be retrieved from 1Password
-        
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
-        
-        let mut onepassword_wrapper = MockOnePasswordWrapper::new();
-        onepassword_wrapper.set_ssh_keys(vec![SshKeyItem {
-            id: "ssh-key-id".to_string(),
-            title: "test-ssh-key".to_string(),
-            vault: crate::external::onepassword::Vault {
-                id: "vault-id".to_string(),
-                name: "test-vault".to_string(),
-            },
-            category: "SSH_KEY".to_string(),
-            public_key: Some("ssh-ed25519 AAAAC3...".to_string()),
-            private_key: None,
-        }]);
-
+    async fn test_apply_tag() {
+        let tagged = |name: &str, email: &str| Profile {
+            tags: vec!["client-a".to_string()],
+            ..create_test_profile(name, email)
+        };
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            tagged("work", "work@example.com"),
+            create_test_profile("personal", "me@example.com"),
+            tagged("client", "client@example.com"),
+        ]));
+        let git_wrapper = MockGitWrapper::new();
         let mut context = create_test_context();
         context.profile_manager = profile_manager;
-        context.onepassword_wrapper = Arc::new(onepassword_wrapper);
+        context.git_wrapper = Arc::new(git_wrapper.clone());
 
         let args = Args {
-            profile: Some("work".to_string()),
+            tag: Some("client-a".to_string()),
+            global: true,
             quiet: true,
             ..Default::default()
         };
+        ApplyCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(written_emails(&git_wrapper), vec!["client@example.com", "work@example.com"]);
 
-        let cmd = ApplyCommand::new();
-        let result = cmd.execute(&args, &context).await;
+        let args = Args {
+            tag: Some("client-b".to_string()),
+            ..args
+        };
+        let result = ApplyCommand::new().execute(&args, &context).await;
+        assert!(matches!(result, Err(GitSetupError::Git(_))));
+    }
 
-        assert!(result.is_ok());
+    /// Detector returning `profiles` in turn on each `detect_in`.
+    struct TogglingDetector {
+        profiles: Vec<Profile>,
+        calls: std::sync::Mutex<usize>,
+    }
+
+    impl TogglingDetector {
+        fn new(profiles: Vec<Profile>) -> Self {
+            Self {
+                profiles,
+                calls: std::sync::Mutex::new(0),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            *self.calls.lock().unwrap()
+        }
+
+        fn result(&self, profile: Profile) -> DetectionResult {
+            DetectionResult {
+                profile,
+                confidence: 0.9,
+                matched_rules: vec![],
+                reason: "toggled".to_string(),
+                reasons: vec![],
+            }
+        }
+    }
+
+    impl crate::detection::ProfileDetector for TogglingDetector {
+        fn detect(&self) -> Result<Option<DetectionResult>> {
+            self.detect_in(Path::new("."))
+        }
+
+        fn detect_in(&self, _path: &Path) -> Result<Option<DetectionResult>> {
+            let mut calls = self.calls.lock().unwrap();
+            let profile = self.profiles[*calls % self.profiles.len()].clone();
+            *calls += 1;
+            Ok(Some(self.result(profile)))
+        }
+
+        fn detect_all(&self) -> Result<Vec<DetectionResult>> {
+            Ok(self.detect()?.into_iter().collect())
+        }
+
+        fn detect_profile(&self, _context: &RepositoryContext) -> Result<DetectionResult> {
+            Ok(self.detect()?.unwrap())
+        }
+
+        fn explain_miss(&self, _path: &Path) -> Result<String> {
+            Ok(String::new())
+        }
+    }
+
+    fn watch_context(detector: Arc<TogglingDetector>) -> (CommandContext, Arc<MockGitWrapper>) {
+        let git_wrapper = Arc::new(MockGitWrapper::new());
+        let mut context = create_test_context();
+        context.git_wrapper = git_wrapper.clone();
+        context.profile_detector = detector;
+        (context, git_wrapper)
+    }
+
+    fn written_emails(git_wrapper: &MockGitWrapper) -> Vec<String> {
+        git_wrapper
+            .config_writes()
+            .into_iter()
+            .filter(|(key, _)| key == "user.email")
+            .filter_map(|(_, value)| value)
+            .collect()
+    }
+
+    fn watch_args() -> Args {
+        Args {
+            watch: true,
+            debounce_ms: 500,
+            global: true,
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that each settled change re-applies the profile the detector switched to.
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_switches_profiles() {
+        let detector = Arc::new(TogglingDetector::new(vec![
+            create_test_profile("work", "work@example.com"),
+            create_test_profile("personal", "me@example.com"),
+        ]));
+        let (context, git_wrapper) = watch_context(detector.clone());
+
+        let (sender, events) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for _ in 0..2 {
+                sender.send(()).unwrap();
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        });
+        ApplyCommand::new()
+            .watch(Path::new("."), events, &watch_args(), &context)
+            .await
+            .unwrap();
+
+        assert_eq!(detector.calls(), 3);
+        assert_eq!(
+            written_emails(&git_wrapper),
+            vec!["work@example.com", "me@example.com", "work@example.com"]
+        );
+        git_wrapper.assert_signing_was_configured(KeyType::Ssh);
+    }
+
+    /// Test that a burst of events detects once and an unchanged profile is not re-applied.
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_debounces_events() {
+        let detector = Arc::new(TogglingDetector::new(vec![
+            create_test_profile("work", "work@example.com"),
+            create_test_profile("work", "work@example.com"),
+            create_test_profile("personal", "me@example.com"),
+        ]));
+        let (context, git_wrapper) = watch_context(detector.clone());
+
+        let (sender, events) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for _ in 0..5 {
+                sender.send(()).unwrap();
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            sender.send(()).unwrap();
+        });
+        ApplyCommand::new()
+            .watch(Path::new("."), events, &watch_args(), &context)
+            .await
+            .unwrap();
+
+        // The initial detection, one for the burst and one for the last event
+        assert_eq!(detector.calls(), 3);
+        assert_eq!(written_emails(&git_wrapper), vec!["work@example.com", "me@example.com"]);
+    }
+
+    /// Test that cancelling the token ends a watch that still receives events.
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_stops_when_cancelled() {
+        let detector = Arc::new(TogglingDetector::new(vec![
+            create_test_profile("work", "work@example.com"),
+            create_test_profile("personal", "me@example.com"),
+        ]));
+        let (context, git_wrapper) = watch_context(detector.clone());
+        let token = context.cancellation_token.clone();
+
+        let (sender, events) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            sender.send(()).unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            token.cancel();
+            // The channel stays open, so only the cancellation can end the watch
+            loop {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                if sender.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+        let result = tokio::time::timeout(
+            Duration::from_secs(60),
+            ApplyCommand::new().watch(Path::new("."), events, &watch_args(), &context),
+        )
+        .await;
+
+        assert!(matches!(result, Ok(Ok(()))));
+        assert_eq!(detector.calls(), 2);
+        assert_eq!(written_emails(&git_wrapper), vec!["work@example.com", "me@example.com"]);
+    }
+
+    /// Test that the watch applies whatever the detector reports, leaving the
+    /// confidence threshold to the detection config.
+    #[tokio::test]
+    async fn test_watch_uses_detector_threshold() {
+        let mut detector = MockProfileDetector::new();
+        detector.set_detection_result(DetectionResult {
+            profile: create_test_profile("work", "work@example.com"),
+            confidence: 0.4,
+            matched_rules: vec![],
+            reason: "low threshold".to_string(),
+            reasons: vec![],
+        });
+        let git_wrapper = Arc::new(MockGitWrapper::new());
+        let mut context = create_test_context();
+        context.git_wrapper = git_wrapper.clone();
+        context.profile_detector = Arc::new(detector);
+
+        let (sender, events) = mpsc::unbounded_channel();
+        drop(sender);
+        ApplyCommand::new()
+            .watch(Path::new("."), events, &watch_args(), &context)
+            .await
+            .unwrap();
+
+        assert_eq!(written_emails(&git_wrapper), vec!["work@example.com"]);
+        git_wrapper.assert_signing_was_configured(KeyType::Ssh);
     }
 
     /// Test that ApplyCommand implements Send + Sync.
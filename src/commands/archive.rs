@@ -0,0 +1,152 @@
+//! Archive and unarchive command implementations for git-setup-rs.
+//!
+//! Archiving hides a profile from listings and auto-detection without deleting
+//! it. Archived profiles are shown with `--list-archived` and restored with
+//! `--unarchive`.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Command implementation for archiving a profile.
+pub struct ArchiveCommand;
+
+impl ArchiveCommand {
+    /// Create a new ArchiveCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ArchiveCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ArchiveCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.archive.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --archive".to_string())
+        })?;
+
+        context.profile_manager.archive(name)
+            .with_context(format!("while archiving profile '{}'", name))?;
+
+        if !args.quiet {
+            println!("✓ Archived profile '{}'", name);
+            println!("Use 'git-setup --unarchive {}' to restore it.", name);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "archive"
+    }
+
+    fn description(&self) -> &'static str {
+        "Archive a profile without deleting it"
+    }
+}
+
+/// Command implementation for restoring an archived profile.
+pub struct UnarchiveCommand;
+
+impl UnarchiveCommand {
+    /// Create a new UnarchiveCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UnarchiveCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for UnarchiveCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.unarchive.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --unarchive".to_string())
+        })?;
+
+        context.profile_manager.unarchive(name)
+            .with_context(format!("while restoring profile '{}'", name))?;
+
+        if !args.quiet {
+            println!("✓ Restored profile '{}'", name);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "unarchive"
+    }
+
+    fn description(&self) -> &'static str {
+        "Restore an archived profile"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        profile::{manager::ProfileManagerImpl, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    /// Test that archiving hides a profile and unarchiving brings it back.
+    #[tokio::test]
+    async fn test_archive_and_unarchive() {
+        let manager = Arc::new(ProfileManagerImpl::new());
+        manager
+            .create(Profile {
+                name: "work".to_string(),
+                git_user_email: "work@example.com".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let mut context = create_test_context();
+        context.profile_manager = manager.clone();
+
+        let args = Args {
+            archive: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        ArchiveCommand::new().execute(&args, &context).await.unwrap();
+        assert!(manager.list().unwrap().is_empty());
+        assert!(manager.exists("work").unwrap());
+
+        let args = Args {
+            unarchive: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        UnarchiveCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(manager.list().unwrap().len(), 1);
+    }
+
+    /// Test that archiving an unknown profile fails.
+    #[tokio::test]
+    async fn test_archive_missing_profile() {
+        let args = Args {
+            archive: Some("missing".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        assert!(ArchiveCommand::new().execute(&args, &create_test_context()).await.is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Bulk-update command implementation for git-setup-rs.
+//!
+//! `--bulk-update --field <field> --value <value>` changes one field of many
+//! profiles at once, e.g. `--field email-domain --from old.com --to new.com`.
+//! Profiles can be narrowed with `--filter-name-glob`; changing more than
+//! three profiles requires `--confirm`.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+    profile::bulk::{self, ProfileField, ProfileUpdate},
+};
+use async_trait::async_trait;
+use clap::ValueEnum;
+
+/// Number of profiles a bulk update may change without `--confirm`.
+const CONFIRM_THRESHOLD: usize = 3;
+
+/// Command implementation for changing a field of many profiles at once.
+pub struct BulkUpdateCommand;
+
+impl BulkUpdateCommand {
+    /// Create a new BulkUpdateCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Build the profile filter from `--filter-name-glob` and `--from`.
+    fn filter(args: &Args, field: ProfileField) -> Result<impl Fn(&Profile) -> bool> {
+        let pattern = args
+            .filter_name_glob
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| GitSetupError::Git(format!("Invalid --filter-name-glob pattern: {}", e)))?;
+        let from = args.from.clone();
+
+        Ok(move |profile: &Profile| {
+            pattern.as_ref().is_none_or(|p| p.matches(&profile.name))
+                && from
+                    .as_ref()
+                    .is_none_or(|from| field.value_of(profile).as_ref() == Some(from))
+        })
+    }
+
+    /// Names of the profiles `update` would change.
+    fn planned_changes<F>(profiles: Vec<Profile>, filter: &F, update: &ProfileUpdate) -> Result<Vec<String>>
+    where
+        F: Fn(&Profile) -> bool,
+    {
+        let mut names = Vec::new();
+        for mut profile in profiles.into_iter().filter(|p| filter(p)) {
+            if update.apply(&mut profile)? {
+                names.push(profile.name);
+            }
+        }
+        Ok(names)
+    }
+}
+
+impl Default for BulkUpdateCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for BulkUpdateCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let field = args
+            .field
+            .ok_or_else(|| GitSetupError::Git("--field is required for --bulk-update".to_string()))?;
+        if !ProfileField::UPDATABLE.contains(&field) {
+            let valid: Vec<String> = ProfileField::UPDATABLE
+                .iter()
+                .filter_map(ValueEnum::to_possible_value)
+                .map(|value| value.get_name().to_string())
+                .collect();
+            return Err(GitSetupError::Git(format!(
+                "--bulk-update cannot change {}; valid fields are: {}",
+                field.field_name(),
+                valid.join(", ")
+            )));
+        }
+        let value = args
+            .value
+            .as_ref()
+            .ok_or_else(|| GitSetupError::Git("--value is required for --bulk-update".to_string()))?;
+        let update = ProfileUpdate::new(field, value.clone());
+        let filter = Self::filter(args, field)?;
+
+        let planned = Self::planned_changes(context.profile_manager.list()?, &filter, &update)?;
+        if planned.is_empty() {
+            if !args.quiet {
+                println!("No profiles to update");
+            }
+            return Ok(());
+        }
+        if planned.len() > CONFIRM_THRESHOLD && !args.confirm {
+            return Err(GitSetupError::Git(format!(
+                "Bulk update would change {} profiles ({}); re-run with --confirm",
+                planned.len(),
+                planned.join(", ")
+            )));
+        }
+
+        let updated = bulk::bulk_update(&*context.profile_manager, filter, &update)
+            .with_context("while updating profiles")?;
+
+        if !args.quiet {
+            println!("✓ Updated {} profile(s)", updated);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "bulk-update"
+    }
+
+    fn description(&self) -> &'static str {
+        "Change one field of many profiles at once"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    fn profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: email.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn context_with(profiles: Vec<Profile>) -> (CommandContext, Arc<MockProfileManager>) {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        (context, profile_manager)
+    }
+
+    fn domain_args(from: &str, to: &str) -> Args {
+        Args {
+            bulk_update: true,
+            field: Some(ProfileField::EmailDomain),
+            from: Some(from.to_string()),
+            value: Some(to.to_string()),
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_update_email_domain_with_glob() {
+        let (context, profile_manager) = context_with(vec![
+            profile("work", "jane@old.com"),
+            profile("work-oss", "jane.oss@old.com"),
+            profile("client", "jane@old.com"),
+        ]);
+        let args = Args {
+            filter_name_glob: Some("work*".to_string()),
+            ..domain_args("old.com", "new.com")
+        };
+
+        BulkUpdateCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(profile_manager.read("work").unwrap().unwrap().git_user_email, "jane@new.com");
+        assert_eq!(profile_manager.read("work-oss").unwrap().unwrap().git_user_email, "jane.oss@new.com");
+        assert_eq!(profile_manager.read("client").unwrap().unwrap().git_user_email, "jane@old.com");
+        assert!(!profile_manager.was_called_with_update("client"));
+    }
+
+    #[tokio::test]
+    async fn test_more_than_three_profiles_requires_confirm() {
+        let profiles: Vec<Profile> = (1..=4).map(|i| profile(&format!("p{}", i), "me@old.com")).collect();
+        let (context, profile_manager) = context_with(profiles);
+
+        let result = BulkUpdateCommand::new().execute(&domain_args("old.com", "new.com"), &context).await;
+        assert!(result.unwrap_err().to_string().contains("--confirm"));
+        assert_eq!(profile_manager.read("p1").unwrap().unwrap().git_user_email, "me@old.com");
+        assert_eq!(profile_manager.update_call_count(), 0);
+
+        let args = Args {
+            confirm: true,
+            ..domain_args("old.com", "new.com")
+        };
+        BulkUpdateCommand::new().execute(&args, &context).await.unwrap();
+        assert!(profile_manager.list().unwrap().iter().all(|p| p.git_user_email == "me@new.com"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_glob_is_an_error() {
+        let (context, _) = context_with(vec![profile("work", "jane@old.com")]);
+        let args = Args {
+            filter_name_glob: Some("[".to_string()),
+            ..domain_args("old.com", "new.com")
+        };
+
+        assert!(BulkUpdateCommand::new().execute(&args, &context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_field_is_rejected() {
+        let (context, profile_manager) = context_with(vec![profile("work", "jane@old.com")]);
+        let args = Args {
+            field: Some(ProfileField::Tags),
+            ..domain_args("old.com", "oss")
+        };
+
+        let err = BulkUpdateCommand::new().execute(&args, &context).await.unwrap_err();
+        assert!(err.to_string().contains("email-domain"));
+        assert_eq!(profile_manager.update_call_count(), 0);
+    }
+}
@@ -0,0 +1,355 @@
+//! List-capabilities command implementation for git-setup-rs.
+//!
+//! `--list-capabilities <PROFILE>` shows whether a profile can be applied
+//! before trying: whether git can write the global and local config, the
+//! signing program is installed, the profile's secret store is signed in, the
+//! SSH key can be found and a GPG key still has to be imported. `apply` runs the same checks
+//! and warns about anything unavailable, or fails with `--check-capabilities`.
+
+use super::{check_keys::is_literal_ssh_key, Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::{KeyType, Profile, SecretStoreKind},
+    error::{GitSetupError, Result, ResultExt},
+    external::git::GitConfigScope,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use async_trait::async_trait;
+use std::fmt;
+use std::path::Path;
+
+/// An operation applying a profile may depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// Git can write the global config
+    CanApplyGlobal,
+    /// Git can write the config of the current repository
+    CanApplyLocal,
+    /// The program signing with the profile's key type is installed
+    CanSign,
+    /// 1Password is signed in
+    CanFetchFromOnePassword,
+    /// Bitwarden is unlocked
+    CanFetchFromBitwarden,
+    /// The SSH key file exists or its secret store item is accessible
+    CanFetchSshKey,
+    /// The GPG key is not in the local keyring yet, so it must be imported.
+    ///
+    /// Unlike the other capabilities, `Available` means the import is needed.
+    NeedsGpgImport,
+}
+
+impl Capability {
+    /// Whether applying at `scope` fails if this capability is unavailable.
+    pub fn is_required(&self, scope: &GitConfigScope) -> bool {
+        match self {
+            Capability::CanApplyGlobal => *scope != GitConfigScope::Local,
+            Capability::CanApplyLocal => *scope == GitConfigScope::Local,
+            Capability::NeedsGpgImport => false,
+            Capability::CanSign
+            | Capability::CanFetchFromOnePassword
+            | Capability::CanFetchFromBitwarden
+            | Capability::CanFetchSshKey => true,
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Result of checking a capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapabilityStatus {
+    Available,
+    Unavailable,
+    /// The check itself failed, e.g. a program could not be run
+    Unknown,
+}
+
+impl CapabilityStatus {
+    fn from_check(check: Result<bool>) -> Self {
+        match check {
+            Ok(true) => CapabilityStatus::Available,
+            Ok(false) => CapabilityStatus::Unavailable,
+            Err(_) => CapabilityStatus::Unknown,
+        }
+    }
+
+    /// Symbol shown in front of the capability.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CapabilityStatus::Available => "✓",
+            CapabilityStatus::Unavailable => "✗",
+            CapabilityStatus::Unknown => "?",
+        }
+    }
+}
+
+/// Checks the capabilities a profile depends on.
+pub struct CapabilityChecker;
+
+impl CapabilityChecker {
+    /// Check the capabilities relevant to `profile`.
+    ///
+    /// The apply capabilities are always checked; the others only for profiles
+    /// they apply to (e.g. `NeedsGpgImport` for GPG profiles). Checks that fail
+    /// to run are reported as `Unknown` rather than as an error.
+    pub fn check(profile: &Profile, ctx: &CommandContext) -> Result<Vec<(Capability, CapabilityStatus)>> {
+        let can_apply_global = CapabilityStatus::from_check(ctx.git_wrapper.is_git_available());
+        let can_apply_local = match can_apply_global {
+            CapabilityStatus::Available => CapabilityStatus::from_check(ctx.git_wrapper.is_inside_work_tree()),
+            status => status,
+        };
+        let mut capabilities = vec![
+            (Capability::CanApplyGlobal, can_apply_global),
+            (Capability::CanApplyLocal, can_apply_local),
+            (Capability::CanSign, CapabilityStatus::from_check(Self::can_sign(profile, ctx))),
+        ];
+
+        match profile.key_store() {
+            Some(SecretStoreKind::OnePassword) => capabilities.push((
+                Capability::CanFetchFromOnePassword,
+                CapabilityStatus::from_check(ctx.onepassword_wrapper.is_authenticated()),
+            )),
+            Some(SecretStoreKind::Bitwarden) => capabilities.push((
+                Capability::CanFetchFromBitwarden,
+                CapabilityStatus::from_check(ctx.bitwarden_wrapper.is_authenticated()),
+            )),
+            None => {}
+        }
+        if profile.key_type == KeyType::Ssh {
+            capabilities.push((
+                Capability::CanFetchSshKey,
+                CapabilityStatus::from_check(Self::can_fetch_ssh_key(profile, ctx)),
+            ));
+        }
+        if profile.key_type == KeyType::Gpg
+            && let Some(key_id) = &profile.signing_key
+        {
+            let missing = ctx.gpg_wrapper.get_key_info(key_id).map(|key| key.is_none());
+            capabilities.push((Capability::NeedsGpgImport, CapabilityStatus::from_check(missing)));
+        }
+
+        Ok(capabilities)
+    }
+
+    /// Whether the program signing with the profile's key type is installed.
+    fn can_sign(profile: &Profile, ctx: &CommandContext) -> Result<bool> {
+        match profile.key_type {
+            KeyType::Gpg => ctx.gpg_wrapper.list_secret_keys().map(|_| true),
+            KeyType::Ssh if profile.key_store() == Some(SecretStoreKind::OnePassword) => {
+                Ok(Path::new(SystemPlatform.default_ssh_program()).exists())
+            }
+            KeyType::Ssh => Ok(is_on_path("ssh-keygen")),
+            KeyType::X509 => Ok(is_on_path("gpgsm")),
+            KeyType::Gitsign => Ok(is_on_path("gitsign")),
+        }
+    }
+
+    /// Whether the SSH key file exists or its secret store item is accessible.
+    fn can_fetch_ssh_key(profile: &Profile, ctx: &CommandContext) -> Result<bool> {
+        if let Some(store) = profile.key_store() {
+            let Some(title) = &profile.ssh_key_title else {
+                return Ok(false);
+            };
+            return match store {
+                SecretStoreKind::OnePassword => {
+                    if !ctx.onepassword_wrapper.is_authenticated()? {
+                        return Ok(false);
+                    }
+                    let keys = ctx.onepassword_wrapper.list_ssh_keys(profile.vault_name.as_deref())?;
+                    Ok(keys.iter().any(|key| &key.title == title))
+                }
+                SecretStoreKind::Bitwarden => {
+                    if !ctx.bitwarden_wrapper.is_authenticated()? {
+                        return Ok(false);
+                    }
+                    Ok(ctx.bitwarden_wrapper.get_ssh_key(title)?.is_some())
+                }
+            };
+        }
+
+        let key_path = profile
+            .ssh_key_path
+            .as_deref()
+            .or(profile.signing_key.as_deref().filter(|key| !is_literal_ssh_key(key)));
+        match key_path {
+            Some(key_path) => Ok(Path::new(&SystemPlatform.expand_path(key_path)).is_file()),
+            // A literal public key is served by the SSH agent
+            None => Ok(profile.signing_key.is_some()),
+        }
+    }
+}
+
+/// Whether `program` is found in a directory of `PATH`.
+fn is_on_path(program: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        dir.join(program).is_file() || (cfg!(windows) && dir.join(format!("{}.exe", program)).is_file())
+    })
+}
+
+/// Command implementation for listing the capabilities of a profile.
+pub struct ListCapabilitiesCommand;
+
+impl ListCapabilitiesCommand {
+    /// Create a new ListCapabilitiesCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ListCapabilitiesCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ListCapabilitiesCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.list_capabilities.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --list-capabilities".to_string())
+        })?;
+        let profile = context
+            .profile_manager
+            .read(name)
+            .with_context(format!("while loading profile '{}'", name))?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+
+        println!("Capabilities of profile '{}':", profile.name);
+        for (capability, status) in CapabilityChecker::check(&profile, context)? {
+            println!("  {} {} ({:?})", status.symbol(), capability, status);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "list-capabilities"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show which operations applying a profile depends on are available"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::external::{
+        bitwarden::MockBitwardenWrapper,
+        git::MockGitWrapper,
+        gpg::{GpgKeyInfo, MockGpgWrapper},
+        onepassword::MockOnePasswordWrapper,
+    };
+    use crate::profile::mock::MockProfileManager;
+    use std::sync::Arc;
+
+    fn gpg_profile(key_id: &str) -> Profile {
+        Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Gpg,
+            signing_key: Some(key_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_gpg_profile() {
+        let mut gpg = MockGpgWrapper::new();
+        gpg.add_key(GpgKeyInfo {
+            key_id: "ABCD1234".to_string(),
+            fingerprint: "0123456789ABCDEF0123456789ABCDEFABCD1234".to_string(),
+            user_id: "Jane <jane@work.com>".to_string(),
+            key_type: "RSA".to_string(),
+            key_size: Some(4096),
+            creation_date: "2024-01-01".to_string(),
+            expiration_date: None,
+            trust_level: "ultimate".to_string(),
+        });
+        let mut context = create_test_context();
+        context.gpg_wrapper = Arc::new(gpg);
+
+        let capabilities = CapabilityChecker::check(&gpg_profile("ABCD1234"), &context).unwrap();
+        assert_eq!(
+            capabilities,
+            vec![
+                (Capability::CanApplyGlobal, CapabilityStatus::Available),
+                (Capability::CanApplyLocal, CapabilityStatus::Available),
+                (Capability::CanSign, CapabilityStatus::Available),
+                (Capability::NeedsGpgImport, CapabilityStatus::Unavailable),
+            ]
+        );
+
+        let capabilities = CapabilityChecker::check(&gpg_profile("FFFF0000"), &context).unwrap();
+        assert!(capabilities.contains(&(Capability::NeedsGpgImport, CapabilityStatus::Available)));
+    }
+
+    #[test]
+    fn test_check_reports_failed_checks_as_unknown() {
+        let mut context = create_test_context();
+        context.git_wrapper = Arc::new(MockGitWrapper::new().with_failure());
+        context.onepassword_wrapper = Arc::new(MockOnePasswordWrapper::new().with_authenticated(false));
+        let profile = Profile {
+            name: "personal".to_string(),
+            git_user_email: "jane@home.net".to_string(),
+            one_password: true,
+            ssh_key_title: Some("Personal Key".to_string()),
+            ..Default::default()
+        };
+
+        let capabilities = CapabilityChecker::check(&profile, &context).unwrap();
+        assert!(capabilities.contains(&(Capability::CanApplyGlobal, CapabilityStatus::Unknown)));
+        assert!(capabilities.contains(&(Capability::CanApplyLocal, CapabilityStatus::Unknown)));
+        assert!(capabilities.contains(&(Capability::CanFetchFromOnePassword, CapabilityStatus::Unavailable)));
+        assert!(capabilities.contains(&(Capability::CanFetchSshKey, CapabilityStatus::Unavailable)));
+    }
+
+    #[test]
+    fn test_check_bitwarden_profile() {
+        let mut context = create_test_context();
+        context.onepassword_wrapper = Arc::new(MockOnePasswordWrapper::new().with_authenticated(false));
+        context.bitwarden_wrapper = Arc::new(MockBitwardenWrapper::new().with_authenticated(false));
+        let profile = Profile {
+            name: "personal".to_string(),
+            git_user_email: "jane@home.net".to_string(),
+            one_password: true,
+            secret_store: Some(SecretStoreKind::Bitwarden),
+            ssh_key_title: Some("Personal Key".to_string()),
+            ..Default::default()
+        };
+
+        let capabilities = CapabilityChecker::check(&profile, &context).unwrap();
+        assert!(capabilities.contains(&(Capability::CanFetchFromBitwarden, CapabilityStatus::Unavailable)));
+        assert!(capabilities.contains(&(Capability::CanFetchSshKey, CapabilityStatus::Unavailable)));
+        assert!(!capabilities.iter().any(|(capability, _)| *capability == Capability::CanFetchFromOnePassword));
+    }
+
+    #[test]
+    fn test_required_capabilities() {
+        assert!(Capability::CanApplyLocal.is_required(&GitConfigScope::Local));
+        assert!(!Capability::CanApplyGlobal.is_required(&GitConfigScope::Local));
+        assert!(Capability::CanApplyGlobal.is_required(&GitConfigScope::System));
+        assert!(!Capability::NeedsGpgImport.is_required(&GitConfigScope::Global));
+    }
+
+    #[tokio::test]
+    async fn test_list_capabilities_of_missing_profile() {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::new());
+        let args = Args {
+            list_capabilities: Some("missing".to_string()),
+            ..Default::default()
+        };
+
+        let result = ListCapabilitiesCommand::new().execute(&args, &context).await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+    }
+}
@@ -0,0 +1,256 @@
+//! Check-keys command implementation for git-setup-rs.
+//!
+//! `--check-keys` checks that each profile's signing key can actually be used:
+//! GPG keys must produce a signature that verifies, and SSH keys must be
+//! readable on disk or available in the profile's secret store. `apply --post-apply-verify`
+//! runs the same check after applying a profile.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::{KeyType, Profile, SecretStoreKind},
+    error::{GitSetupError, Result, ResultExt},
+    external::gpg::DigestAlgorithm,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Check that `profile` can sign, using `signing_key` in place of the profile's key if given.
+pub fn verify_signing(
+    profile: &Profile,
+    signing_key: Option<&str>,
+    digest: DigestAlgorithm,
+    context: &CommandContext,
+) -> Result<()> {
+    let signing_key = signing_key.or(profile.signing_key.as_deref());
+
+    match profile.key_type {
+        KeyType::Gpg => {
+            let key_id = signing_key.ok_or_else(|| GitSetupError::InvalidProfile {
+                reason: "GPG profile has no signing key".to_string(),
+            })?;
+            let content = format!("git-setup signing verification for profile '{}'", profile.name);
+            let signature = context
+                .gpg_wrapper
+                .sign_data_with_digest(&content, key_id, None, digest)?;
+            if context.gpg_wrapper.verify_signature(&content, &signature)? {
+                Ok(())
+            } else {
+                Err(GitSetupError::Git(format!(
+                    "signature made with key '{}' could not be verified",
+                    key_id
+                )))
+            }
+        }
+        KeyType::Ssh if profile.key_store() == Some(SecretStoreKind::OnePassword) => {
+            let title = profile.ssh_key_title.as_deref().ok_or_else(|| GitSetupError::InvalidProfile {
+                reason: "SSH key title not configured for 1Password".to_string(),
+            })?;
+            let keys = context.onepassword_wrapper.list_ssh_keys(profile.vault_name.as_deref())?;
+            if keys.iter().any(|key| key.title == title) {
+                Ok(())
+            } else {
+                Err(GitSetupError::OnePassword(format!("SSH key '{}' not found in 1Password", title)))
+            }
+        }
+        KeyType::Ssh if profile.key_store() == Some(SecretStoreKind::Bitwarden) => {
+            let title = profile.ssh_key_title.as_deref().ok_or_else(|| GitSetupError::InvalidProfile {
+                reason: "SSH key title not configured for Bitwarden".to_string(),
+            })?;
+            match context.bitwarden_wrapper.get_ssh_key(title)? {
+                Some(_) => Ok(()),
+                None => Err(GitSetupError::Bitwarden(format!("SSH key '{}' not found in Bitwarden", title))),
+            }
+        }
+        KeyType::Ssh => {
+            // A literal public key is served by the SSH agent, so only paths can be checked
+            let key_path = profile
+                .ssh_key_path
+                .as_deref()
+                .or(signing_key.filter(|key| !is_literal_ssh_key(key)));
+            match key_path {
+                Some(key_path) => {
+                    let path = SystemPlatform.expand_path(key_path);
+                    std::fs::File::open(Path::new(&path))
+                        .map(|_| ())
+                        .map_err(|e| GitSetupError::Git(format!("SSH key '{}' is not readable: {}", path, e)))
+                }
+                None if signing_key.is_some() => Ok(()),
+                None => Err(GitSetupError::InvalidProfile {
+                    reason: "SSH profile has no signing key".to_string(),
+                }),
+            }
+        }
+        // x509 and gitsign keys are managed by their signing programs
+        KeyType::X509 | KeyType::Gitsign => Ok(()),
+    }
+}
+
+/// Check if `key` is an SSH public key rather than a path to one.
+pub(crate) fn is_literal_ssh_key(key: &str) -> bool {
+    ["ssh-", "ecdsa-", "sk-", "key::"].iter().any(|prefix| key.starts_with(prefix))
+}
+
+/// Command implementation for checking that profiles can sign.
+pub struct CheckKeysCommand;
+
+impl CheckKeysCommand {
+    /// Create a new CheckKeysCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Profiles to check: the named profile, or all of them.
+    fn profiles(&self, args: &Args, context: &CommandContext) -> Result<Vec<Profile>> {
+        match &args.profile {
+            Some(name) => Ok(vec![context.profile_manager.read(name)?.ok_or_else(|| {
+                GitSetupError::ProfileNotFound { name: name.clone() }
+            })?]),
+            None => context.profile_manager.list(),
+        }
+    }
+}
+
+impl Default for CheckKeysCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for CheckKeysCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let profiles = self
+            .profiles(args, context)
+            .with_context("while loading profiles to check")?;
+        let digest = args.verify_algorithm.unwrap_or_default();
+
+        let mut failed = 0;
+        for profile in &profiles {
+            match verify_signing(profile, None, digest, context) {
+                Ok(()) => {
+                    if !args.quiet {
+                        println!("✓ {}: Signing verified", profile.name);
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("✗ {}: Signing failed: {}", profile.name, e);
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(GitSetupError::Git(format!(
+                "Signing failed for {} of {} profile(s)",
+                failed,
+                profiles.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "check-keys"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check that each profile's signing key can be used"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        external::{
+            bitwarden::{BitwardenSshKey, MockBitwardenWrapper},
+            gpg::MockGpgWrapper,
+        },
+        profile::mock::MockProfileManager,
+    };
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
+
+    fn profile(name: &str, key_type: KeyType, signing_key: Option<&str>) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            key_type,
+            signing_key: signing_key.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_gpg_signs_with_key() {
+        let gpg = Arc::new(MockGpgWrapper::new());
+        let mut context = create_test_context();
+        context.gpg_wrapper = gpg.clone();
+
+        let work = profile("work", KeyType::Gpg, Some("ABCD1234"));
+        verify_signing(&work, None, DigestAlgorithm::Sha512, &context).unwrap();
+        assert_eq!(gpg.sign_data_calls(), vec!["ABCD1234".to_string()]);
+
+        let mut failing = MockGpgWrapper::new();
+        failing.set_should_fail("verify_signature", true);
+        context.gpg_wrapper = Arc::new(failing);
+        assert!(verify_signing(&work, None, DigestAlgorithm::Sha256, &context).is_err());
+    }
+
+    #[test]
+    fn test_verify_ssh_key_path() {
+        let context = create_test_context();
+        let key = NamedTempFile::new().unwrap();
+        let key_path = key.path().display().to_string();
+
+        let readable = profile("work", KeyType::Ssh, Some(&key_path));
+        assert!(verify_signing(&readable, None, DigestAlgorithm::Sha256, &context).is_ok());
+
+        let missing = profile("work", KeyType::Ssh, Some("/nonexistent/id_ed25519"));
+        assert!(verify_signing(&missing, None, DigestAlgorithm::Sha256, &context).is_err());
+
+        let literal = profile("work", KeyType::Ssh, Some("ssh-ed25519 AAAAC3Nza"));
+        assert!(verify_signing(&literal, None, DigestAlgorithm::Sha256, &context).is_ok());
+    }
+
+    #[test]
+    fn test_verify_ssh_key_in_bitwarden() {
+        let mut context = create_test_context();
+        context.bitwarden_wrapper = Arc::new(MockBitwardenWrapper::new().with_ssh_keys(vec![BitwardenSshKey {
+            id: "bw-1".to_string(),
+            name: "Work Key".to_string(),
+            public_key: "ssh-ed25519 AAAAC3Nza".to_string(),
+            fingerprint: None,
+        }]));
+
+        let mut work = profile("work", KeyType::Ssh, None);
+        work.one_password = true;
+        work.secret_store = Some(SecretStoreKind::Bitwarden);
+        work.ssh_key_title = Some("Work Key".to_string());
+        assert!(verify_signing(&work, None, DigestAlgorithm::Sha256, &context).is_ok());
+
+        work.ssh_key_title = Some("Missing Key".to_string());
+        let err = verify_signing(&work, None, DigestAlgorithm::Sha256, &context).unwrap_err();
+        assert!(matches!(err, GitSetupError::Bitwarden(_)));
+    }
+
+    #[tokio::test]
+    async fn test_check_keys_fails_when_any_profile_fails() {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            profile("gpg", KeyType::Gpg, Some("ABCD1234")),
+            profile("broken", KeyType::Ssh, Some("/nonexistent/id_ed25519")),
+        ]));
+        let args = Args {
+            check_keys: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let result = CheckKeysCommand::new().execute(&args, &context).await;
+        assert!(matches!(result.unwrap_err(), GitSetupError::Git(msg) if msg.contains("1 of 2")));
+    }
+}
@@ -0,0 +1,330 @@
+//! Check-unused command implementation for git-setup-rs.
+//!
+//! This command scans git repositories for auto-detected profiles and reports
+//! profiles that no repository resolves to, optionally deleting them.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    config::types::Profile,
+    error::{Result, ResultExt},
+    platform::{PlatformPaths, SystemPlatform},
+    profile::activation::ActivationRecord,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// Result of scanning for unused profiles.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnusedProfilesReport {
+    /// Number of git repositories that were scanned.
+    pub repositories_scanned: usize,
+    /// Names of profiles that were not detected in any repository, sorted.
+    pub unused_profiles: Vec<String>,
+}
+
+/// Command implementation for finding stale profiles.
+pub struct CheckUnusedCommand;
+
+impl CheckUnusedCommand {
+    /// Create a new CheckUnusedCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find git repositories in `dir`.
+    ///
+    /// Without `recursive`, only `dir` itself and its direct children are checked.
+    /// With `recursive`, hidden directories and the insides of found repositories
+    /// are skipped to keep the walk fast.
+    pub fn find_git_repos(dir: &Path, recursive: bool) -> Vec<PathBuf> {
+        let mut repos = Vec::new();
+        let mut pending = vec![(dir.to_path_buf(), 0usize)];
+
+        while let Some((current, depth)) = pending.pop() {
+            if current.join(".git").exists() {
+                repos.push(current);
+                continue;
+            }
+            if !recursive && depth >= 1 {
+                continue;
+            }
+
+            let Ok(entries) = std::fs::read_dir(&current) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if file_type.is_dir() && !hidden {
+                    pending.push((entry.path(), depth + 1));
+                }
+            }
+        }
+
+        repos.sort();
+        repos
+    }
+
+    /// Names of profiles with no activation within the last `days` days.
+    fn inactive_profiles(
+        profiles: &[Profile],
+        activations: &[ActivationRecord],
+        days: u32,
+        now: DateTime<Utc>,
+    ) -> HashSet<String> {
+        let cutoff = now - Duration::days(i64::from(days));
+        profiles
+            .iter()
+            .filter(|profile| {
+                !activations.iter().any(|record| {
+                    record.profile_name == profile.name && record.activated_at >= cutoff
+                })
+            })
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+
+    /// Scan `search_dir` and report profiles that are never detected.
+    ///
+    /// With `unused_days`, profiles that have not been applied within that many
+    /// days are reported as well.
+    pub fn check(
+        &self,
+        search_dir: &Path,
+        recursive: bool,
+        unused_days: Option<u32>,
+        context: &CommandContext,
+    ) -> Result<UnusedProfilesReport> {
+        let repos = Self::find_git_repos(search_dir, recursive);
+
+        // A repository that fails detection simply contributes no profile
+        let detected: HashSet<String> = repos
+            .iter()
+            .filter_map(|repo| context.profile_detector.detect_in(repo).ok().flatten())
+            .map(|result| result.profile.name)
+            .collect();
+
+        let profiles = context.profile_manager.list()?;
+        let inactive = match unused_days {
+            Some(days) => Self::inactive_profiles(
+                &profiles,
+                &context.profile_manager.activation_history()?,
+                days,
+                Utc::now(),
+            ),
+            None => HashSet::new(),
+        };
+
+        let unused_profiles = profiles
+            .into_iter()
+            .map(|profile| profile.name)
+            .filter(|name| !detected.contains(name) || inactive.contains(name))
+            .collect();
+
+        Ok(UnusedProfilesReport {
+            repositories_scanned: repos.len(),
+            unused_profiles,
+        })
+    }
+
+    /// Ask on stderr whether the unused profiles should be deleted.
+    fn confirm_delete(&self, names: &[String], input: &mut impl BufRead) -> Result<bool> {
+        eprint!("Delete {} unused profile(s) [{}]? (y/N) ", names.len(), names.join(", "));
+        std::io::stderr().flush()?;
+
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+
+    /// Delete the unused profiles after confirmation read from `input`.
+    fn delete_unused(
+        &self,
+        names: &[String],
+        args: &Args,
+        context: &CommandContext,
+        input: &mut impl BufRead,
+    ) -> Result<()> {
+        if !self.confirm_delete(names, input)? {
+            eprintln!("Deletion cancelled.");
+            return Ok(());
+        }
+
+        for name in names {
+            context.profile_manager.delete(name)?;
+            if !args.quiet {
+                eprintln!("✓ Profile '{}' deleted successfully", name);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for CheckUnusedCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for CheckUnusedCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let search_dir = match &args.search_dir {
+            Some(dir) => PathBuf::from(SystemPlatform.expand_path(dir)),
+            None => SystemPlatform.home_dir()?,
+        };
+
+        let report = self
+            .check(&search_dir, args.recursive, args.unused_days, context)
+            .with_context(format!("while scanning '{}' for unused profiles", search_dir.display()))?;
+
+        match args.output {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            _ => {
+                if !args.quiet {
+                    println!("Unused profiles: [{}]", report.unused_profiles.join(", "));
+                }
+            }
+        }
+
+        if args.delete_unused && !report.unused_profiles.is_empty() {
+            let stdin = std::io::stdin();
+            self.delete_unused(&report.unused_profiles, args, context, &mut stdin.lock())?;
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "check-unused"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find profiles that are not used by any repository"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        detection::{DetectionResult, MockProfileDetector},
+        profile::mock::MockProfileManager,
+    };
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn profile(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            ..Default::default()
+        }
+    }
+
+    fn make_repo(path: &Path) {
+        std::fs::create_dir_all(path.join(".git")).unwrap();
+    }
+
+    fn context_detecting(name: &str, profile_manager: Arc<MockProfileManager>) -> CommandContext {
+        let mut detector = MockProfileDetector::new();
+        detector.set_detection_result(DetectionResult {
+            profile: profile(name),
+            confidence: 0.9,
+            matched_rules: vec![],
+            reason: "test".to_string(),
+            reasons: vec![],
+        });
+
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+        context.profile_detector = Arc::new(detector);
+        context
+    }
+
+    #[test]
+    fn test_find_git_repos() {
+        let dir = TempDir::new().unwrap();
+        make_repo(&dir.path().join("a"));
+        make_repo(&dir.path().join("nested").join("b"));
+        make_repo(&dir.path().join(".hidden").join("c"));
+        std::fs::create_dir_all(dir.path().join("not-a-repo")).unwrap();
+
+        let repos = CheckUnusedCommand::find_git_repos(dir.path(), false);
+        assert_eq!(repos, vec![dir.path().join("a")]);
+
+        let repos = CheckUnusedCommand::find_git_repos(dir.path(), true);
+        assert_eq!(repos, vec![dir.path().join("a"), dir.path().join("nested").join("b")]);
+    }
+
+    #[test]
+    fn test_check_reports_undetected_profiles() {
+        let dir = TempDir::new().unwrap();
+        make_repo(&dir.path().join("project"));
+
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            profile("work"),
+            profile("old-client"),
+            profile("archived"),
+        ]));
+        let context = context_detecting("work", profile_manager);
+
+        let report = CheckUnusedCommand::new()
+            .check(dir.path(), false, None, &context)
+            .unwrap();
+        assert_eq!(report.repositories_scanned, 1);
+        assert_eq!(report.unused_profiles, vec!["archived", "old-client"]);
+    }
+
+    #[test]
+    fn test_check_reports_inactive_profiles() {
+        let dir = TempDir::new().unwrap();
+        make_repo(&dir.path().join("project"));
+
+        let stale = ActivationRecord {
+            profile_name: "work".to_string(),
+            repo_path: None,
+            activated_at: Utc::now() - Duration::days(90),
+        };
+        let profile_manager = Arc::new(
+            MockProfileManager::with_profiles(vec![profile("work")]).with_activations(vec![stale]),
+        );
+        let context = context_detecting("work", profile_manager);
+
+        let cmd = CheckUnusedCommand::new();
+        let report = cmd.check(dir.path(), false, Some(120), &context).unwrap();
+        assert!(report.unused_profiles.is_empty());
+
+        let report = cmd.check(dir.path(), false, Some(30), &context).unwrap();
+        assert_eq!(report.unused_profiles, vec!["work"]);
+    }
+
+    #[test]
+    fn test_delete_unused_requires_confirmation() {
+        let profile_manager = Arc::new(
+            MockProfileManager::with_profiles(vec![profile("work"), profile("old-client")]).track_calls(true),
+        );
+        let context = context_detecting("work", profile_manager.clone());
+        let cmd = CheckUnusedCommand::new();
+        let names = vec!["old-client".to_string()];
+        let args = Args {
+            quiet: true,
+            ..Default::default()
+        };
+
+        cmd.delete_unused(&names, &args, &context, &mut "n\n".as_bytes()).unwrap();
+        assert_eq!(profile_manager.delete_call_count(), 0);
+
+        cmd.delete_unused(&names, &args, &context, &mut "y\n".as_bytes()).unwrap();
+        assert!(profile_manager.was_called_with_delete("old-client"));
+        assert_eq!(profile_manager.delete_call_count(), 1);
+    }
+}
@@ -0,0 +1,395 @@
+//! Shell completion commands for git-setup-rs.
+//!
+//! `--completions <shell>` prints a completion script. With `--dynamic` the script
+//! completes profile names by calling back into `git-setup __complete-profile-names`.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::{KeyType, Profile},
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+/// Hidden command name used by completion scripts to list profile names.
+pub const COMPLETE_PROFILE_NAMES: &str = "__complete-profile-names";
+
+/// Flags whose value is a profile name.
+const PROFILE_NAME_FLAGS: &[&str] = &["-e", "--edit", "-d", "--delete"];
+
+/// Command implementation for generating shell completion scripts.
+pub struct CompletionsCommand;
+
+impl CompletionsCommand {
+    /// Create a new CompletionsCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate the static clap completion script for a shell.
+    pub fn generate_static(shell: Shell) -> String {
+        let mut command = Args::command();
+        let mut buffer = Vec::new();
+        clap_complete::generate(shell, &mut command, "git-setup", &mut buffer);
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    /// Generate a completion script that completes profile names dynamically.
+    pub fn generate_dynamic(shell: Shell) -> Result<String> {
+        let flags = Self::visible_flags().join(" ");
+        let profile_flags = PROFILE_NAME_FLAGS.join("|");
+        let output_formats = "json yaml toml csv tabular";
+
+        match shell {
+            Shell::Bash => Ok(format!(
+                r#"# git-setup bash completion with dynamic profile names
+_git_setup_profile_names() {{ git-setup {complete} 2>/dev/null; }}
+
+_git_setup() {{
+    local cur prev
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+        {profile_flags})
+            COMPREPLY=( $(compgen -W "$(_git_setup_profile_names)" -- "$cur") )
+            return 0
+            ;;
+        -o|--output)
+            COMPREPLY=( $(compgen -W "{output_formats}" -- "$cur") )
+            return 0
+            ;;
+    esac
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "{flags}" -- "$cur") )
+    else
+        # A bare argument is the profile to apply
+        COMPREPLY=( $(compgen -W "$(_git_setup_profile_names)" -- "$cur") )
+    fi
+}}
+
+complete -F _git_setup git-setup
+"#,
+                complete = COMPLETE_PROFILE_NAMES,
+            )),
+            Shell::Zsh => Ok(format!(
+                r#"#compdef git-setup
+# git-setup zsh completion with dynamic profile names
+_git_setup_profile_names() {{ git-setup {complete} 2>/dev/null }}
+
+_git_setup() {{
+    local -a profiles flags
+    profiles=(${{(f)"$(_git_setup_profile_names)"}})
+
+    case "$words[CURRENT-1]" in
+        {profile_flags})
+            compadd -a profiles
+            return
+            ;;
+        -o|--output)
+            compadd {output_formats}
+            return
+            ;;
+    esac
+
+    if [[ "$words[CURRENT]" == -* ]]; then
+        flags=({flags})
+        compadd -a flags
+    else
+        # A bare argument is the profile to apply
+        compadd -a profiles
+    fi
+}}
+
+compdef _git_setup git-setup
+"#,
+                complete = COMPLETE_PROFILE_NAMES,
+            )),
+            Shell::Fish => Ok(format!(
+                r#"# git-setup fish completion with dynamic profile names
+function __git_setup_profile_names
+    git-setup {complete} 2>/dev/null
+end
+
+# A bare argument is the profile to apply
+complete -c git-setup -f -a '(__git_setup_profile_names)'
+{flags}
+"#,
+                complete = COMPLETE_PROFILE_NAMES,
+                flags = Self::fish_flags(output_formats).join("\n"),
+            )),
+            other => Err(GitSetupError::Git(format!(
+                "Dynamic completions are only supported for bash, zsh and fish, not {}",
+                other
+            ))),
+        }
+    }
+
+    /// One fish `complete` line per non-hidden long flag, completing the values
+    /// of profile name flags and `--output`.
+    fn fish_flags(output_formats: &str) -> Vec<String> {
+        let command = Args::command();
+        command
+            .get_arguments()
+            .filter(|a| !a.is_hide_set())
+            .filter_map(|arg| {
+                let long = arg.get_long()?;
+                let mut line = String::from("complete -c git-setup");
+                if let Some(short) = arg.get_short() {
+                    line.push_str(&format!(" -s {}", short));
+                }
+                line.push_str(&format!(" -l {}", long));
+                if let Some(help) = arg.get_help() {
+                    line.push_str(&format!(" -d '{}'", help.to_string().replace('\'', "\\'")));
+                }
+                if PROFILE_NAME_FLAGS.contains(&format!("--{}", long).as_str()) {
+                    line.push_str(" -x -a '(__git_setup_profile_names)'");
+                } else if long == "output" {
+                    line.push_str(&format!(" -x -a '{}'", output_formats));
+                }
+                Some(line)
+            })
+            .collect()
+    }
+
+    /// All non-hidden flags, long and short.
+    fn visible_flags() -> Vec<String> {
+        let command = Args::command();
+        let mut flags = Vec::new();
+        for arg in command.get_arguments().filter(|a| !a.is_hide_set()) {
+            if let Some(long) = arg.get_long() {
+                flags.push(format!("--{}", long));
+            }
+            if let Some(short) = arg.get_short() {
+                flags.push(format!("-{}", short));
+            }
+        }
+        flags
+    }
+}
+
+impl Default for CompletionsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for CompletionsCommand {
+    async fn execute(&self, args: &Args, _context: &CommandContext) -> Result<()> {
+        let shell = args.completions.ok_or_else(|| {
+            GitSetupError::Git("Shell is required for completions command".to_string())
+        })?;
+
+        let script = if args.dynamic {
+            Self::generate_dynamic(shell)
+                .with_context(format!("while generating {} completions", shell))?
+        } else {
+            Self::generate_static(shell)
+        };
+
+        print!("{}", script);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "completions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate a shell completion script"
+    }
+}
+
+/// Hidden command that prints profile names, one per line, for completion scripts.
+pub struct CompleteProfileNamesCommand;
+
+impl CompleteProfileNamesCommand {
+    /// Create a new CompleteProfileNamesCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Select the profile names to complete.
+    pub fn profile_names(
+        profiles: &[Profile],
+        key_type: Option<&KeyType>,
+        sorted: bool,
+    ) -> Vec<String> {
+        let mut names: Vec<String> = profiles
+            .iter()
+            .filter(|p| key_type.is_none_or(|kt| p.key_type == *kt))
+            .map(|p| p.name.clone())
+            .collect();
+        if sorted {
+            names.sort();
+        }
+        names
+    }
+
+    /// Parse a `--filter-by-key-type` value.
+    fn parse_key_type(value: &str) -> Result<KeyType> {
+        value.parse()
+    }
+}
+
+impl Default for CompleteProfileNamesCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for CompleteProfileNamesCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let key_type = args
+            .filter_by_key_type
+            .as_deref()
+            .map(Self::parse_key_type)
+            .transpose()?;
+
+        let profiles = match context.profile_manager.list() {
+            // No profiles configured yet means nothing to complete, not an error
+            Err(GitSetupError::ConfigNotFound { .. }) => Vec::new(),
+            profiles => profiles.with_context("while loading profiles")?,
+        };
+
+        for name in Self::profile_names(&profiles, key_type.as_ref(), args.sorted.unwrap_or(true)) {
+            println!("{}", name);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        COMPLETE_PROFILE_NAMES
+    }
+
+    fn description(&self) -> &'static str {
+        "List profile names for shell completion"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::tests::create_test_context, profile::mock::MockProfileManager};
+    use std::sync::Arc;
+
+    fn create_test_profile(name: &str, key_type: KeyType) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            key_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_profile_names_sorted_and_filtered() {
+        let profiles = vec![
+            create_test_profile("work", KeyType::Ssh),
+            create_test_profile("personal", KeyType::Gpg),
+            create_test_profile("client", KeyType::Ssh),
+        ];
+
+        let names = CompleteProfileNamesCommand::profile_names(&profiles, None, true);
+        assert_eq!(names, vec!["client", "personal", "work"]);
+
+        let names = CompleteProfileNamesCommand::profile_names(&profiles, None, false);
+        assert_eq!(names, vec!["work", "personal", "client"]);
+
+        let names =
+            CompleteProfileNamesCommand::profile_names(&profiles, Some(&KeyType::Ssh), true);
+        assert_eq!(names, vec!["client", "work"]);
+    }
+
+    #[test]
+    fn test_parse_key_type() {
+        assert_eq!(CompleteProfileNamesCommand::parse_key_type("SSH").unwrap(), KeyType::Ssh);
+        assert_eq!(CompleteProfileNamesCommand::parse_key_type("gitsign").unwrap(), KeyType::Gitsign);
+        assert!(CompleteProfileNamesCommand::parse_key_type("rsa").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_complete_profile_names_without_profiles() {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::new());
+
+        let args = Args {
+            profile: Some(COMPLETE_PROFILE_NAMES.to_string()),
+            ..Default::default()
+        };
+
+        let result = CompleteProfileNamesCommand::new().execute(&args, &context).await;
+        assert!(result.is_ok());
+
+        context.profile_manager = Arc::new(MockProfileManager::new().with_list_error(GitSetupError::ConfigNotFound {
+            path: "/missing/config.toml".to_string(),
+        }));
+        let result = CompleteProfileNamesCommand::new().execute(&args, &context).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_complete_profile_names_reports_list_errors() {
+        let mut context = create_test_context();
+        context.profile_manager =
+            Arc::new(MockProfileManager::new().with_list_error(GitSetupError::Git("corrupt config".to_string())));
+
+        let args = Args {
+            profile: Some(COMPLETE_PROFILE_NAMES.to_string()),
+            ..Default::default()
+        };
+
+        let result = CompleteProfileNamesCommand::new().execute(&args, &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::Git(_)));
+    }
+
+    #[test]
+    fn test_dynamic_bash_script_calls_back() {
+        let script = CompletionsCommand::generate_dynamic(Shell::Bash).unwrap();
+        assert!(script.contains(
+            "_git_setup_profile_names() { git-setup __complete-profile-names 2>/dev/null; }"
+        ));
+        assert!(script.contains("-e|--edit|-d|--delete)"));
+        assert!(script.contains("complete -F _git_setup git-setup"));
+        // Hidden completion helpers are not offered as flags
+        assert!(!script.contains("--filter-by-key-type"));
+    }
+
+    #[test]
+    fn test_dynamic_zsh_script_calls_back() {
+        let script = CompletionsCommand::generate_dynamic(Shell::Zsh).unwrap();
+        assert!(script.starts_with("#compdef git-setup"));
+        assert!(script.contains("git-setup __complete-profile-names"));
+    }
+
+    #[test]
+    fn test_dynamic_fish_script_calls_back() {
+        let script = CompletionsCommand::generate_dynamic(Shell::Fish).unwrap();
+        assert!(script.contains("    git-setup __complete-profile-names 2>/dev/null"));
+        assert!(script.contains("complete -c git-setup -f -a '(__git_setup_profile_names)'"));
+        assert!(script.contains("-s e -l edit"));
+        assert!(script
+            .lines()
+            .any(|line| line.contains("-l delete") && line.ends_with("-x -a '(__git_setup_profile_names)'")));
+        assert!(script.contains("-x -a 'json yaml toml csv tabular'"));
+        assert!(!script.contains("filter-by-key-type"));
+    }
+
+    #[test]
+    fn test_dynamic_unsupported_shell() {
+        assert!(CompletionsCommand::generate_dynamic(Shell::PowerShell).is_err());
+    }
+
+    #[test]
+    fn test_static_script_generation() {
+        let script = CompletionsCommand::generate_static(Shell::Bash);
+        assert!(script.contains("git-setup"));
+        assert!(script.contains("--list"));
+    }
+}
@@ -0,0 +1,107 @@
+//! Config dump command implementation for git-setup-rs.
+//!
+//! `--config-dump` prints the effective tool configuration: every setting
+//! with defaults filled in and `GIT_SETUP_*` environment overrides applied,
+//! along with where its value comes from. With `--output json|yaml|toml` the
+//! values and sources are serialized instead.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    config::{
+        loader::{ConfigLoader, ConfigLoaderTrait},
+        types::ToolConfig,
+    },
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Command implementation for dumping the effective tool configuration.
+pub struct ConfigDumpCommand;
+
+impl ConfigDumpCommand {
+    /// Create a new ConfigDumpCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ConfigDumpCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ConfigDumpCommand {
+    async fn execute(&self, args: &Args, _context: &CommandContext) -> Result<()> {
+        let loader = match &args.file {
+            Some(path) => ConfigLoader::new(PathBuf::from(path)),
+            None => ConfigLoader::from_platform_default()?,
+        };
+        let config = loader.load().with_context("while loading the config file")?;
+        let file = loader.exists().then(|| loader.config_path());
+        let resolved = config.tool.resolve(file, &ToolConfig::from_env_overrides())?;
+
+        let output = match args.output {
+            OutputFormat::Tabular => resolved.render(),
+            OutputFormat::Json => serde_json::to_string_pretty(&resolved)?,
+            OutputFormat::Yaml => serde_yaml::to_string(&resolved)?,
+            OutputFormat::Toml => toml::to_string_pretty(&resolved)?,
+            OutputFormat::Csv => {
+                return Err(GitSetupError::Git(
+                    "The configuration can be dumped as tabular, json, yaml or toml output".to_string(),
+                ));
+            }
+        };
+        println!("{}", output);
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "config-dump"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the effective tool configuration and where each value comes from"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::tests::create_test_context, config::types::Config};
+    use tempfile::TempDir;
+
+    /// Test that the config file is dumped in every supported format.
+    #[tokio::test]
+    async fn test_config_dump() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let loader = ConfigLoader::new(path.clone());
+        let mut config: Config = loader.create_default();
+        config.tool.op_rate_limit_calls_per_minute = Some(60);
+        loader.save(&config).unwrap();
+        let context = create_test_context();
+
+        for output in [OutputFormat::Tabular, OutputFormat::Json, OutputFormat::Yaml, OutputFormat::Toml] {
+            let args = Args {
+                config_dump: true,
+                file: Some(path.display().to_string()),
+                output,
+                ..Default::default()
+            };
+            ConfigDumpCommand::new().execute(&args, &context).await.unwrap();
+        }
+
+        let args = Args {
+            config_dump: true,
+            file: Some(path.display().to_string()),
+            output: OutputFormat::Csv,
+            ..Default::default()
+        };
+        assert!(ConfigDumpCommand::new().execute(&args, &context).await.is_err());
+    }
+}
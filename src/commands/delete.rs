@@ -5,7 +5,7 @@
 use super::{Command, CommandContext};
 use crate::{
     cli::Args,
-    error::{GitSetupError, Result},
+    error::{GitSetupError, Result, ResultExt},
 };
 use async_trait::async_trait;
 
@@ -73,7 +73,8 @@ impl Command for DeleteCommand {
         }
 
         // Delete the profile
-        self.delete_profile(profile_name, context).await?;
+        self.delete_profile(profile_name, context).await
+            .with_context(format!("while deleting profile '{}'", profile_name))?;
 
         // Print success message
         if !args.quiet {
@@ -130,6 +131,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -145,23 +155,24 @@ mod tests {
     #[tokio::test]
     async fn test_delete_profile_success() {
         let profile = create_test_profile("test-profile", "test@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let args = create_delete_args("test-profile");
         let cmd = DeleteCommand::new();
         let result = cmd.execute(&args, &context).await;
 
         assert!(result.is_ok());
+        assert!(profile_manager.was_called_with_delete("test-profile"));
     }
 
     /// Test deleting a non-existent profile.
     #[tokio::test]
     async fn test_delete_nonexistent_profile() {
-        let profile_manager = Arc::new(MockProfileManager::new());
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let args = create_delete_args("nonexistent");
         let cmd = DeleteCommand::new();
@@ -169,6 +180,7 @@ mod tests {
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), GitSetupError::ProfileNotFound { .. }));
+        assert_eq!(profile_manager.delete_call_count(), 0);
     }
 
     /// Test that profile existence check works correctly.
@@ -257,9 +269,9 @@ mod tests {
             create_test_profile("profile3", "profile3@example.com"),
         ];
         
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let cmd = DeleteCommand::new();
         
@@ -282,5 +294,7 @@ mod tests {
         assert!(!exists);
         let exists = cmd.check_profile_exists("profile2", &context).await.unwrap();
         assert!(!exists);
+        assert_eq!(profile_manager.delete_call_count(), 2);
+        assert!(!profile_manager.was_called_with_delete("profile3"));
     }
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+//! Detect command implementation for git-setup-rs.
+//!
+//! This command shows which profile auto-detection selects for the current
+//! directory and, with `--explain-miss`, why no profile was selected.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{Result, ResultExt},
+};
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Command implementation for showing the auto-detected profile.
+pub struct DetectCommand;
+
+impl DetectCommand {
+    /// Create a new DetectCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Describe the detection result for `path`.
+    pub fn report(&self, path: &Path, explain_miss: bool, context: &CommandContext) -> Result<String> {
+        match context.profile_detector.detect_in(path)? {
+            Some(result) => Ok(format!(
+                "Detected profile '{}' ({:.0}% confidence): {}",
+                result.profile.name,
+                result.confidence * 100.0,
+                result.reason
+            )),
+            None if explain_miss => context.profile_detector.explain_miss(path),
+            None => Ok("No profile detected (use --explain-miss to see why)".to_string()),
+        }
+    }
+}
+
+impl Default for DetectCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for DetectCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let current_dir = std::env::current_dir()?;
+        let report = self
+            .report(&current_dir, args.explain_miss, context)
+            .with_context(format!("while detecting a profile for '{}'", current_dir.display()))?;
+
+        if !args.quiet {
+            println!("{}", report);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "detect"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the auto-detected profile for the current directory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        detection::{DetectionResult, MockProfileDetector},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_report_detected_profile() {
+        let mut detector = MockProfileDetector::new();
+        detector.set_detection_result(DetectionResult {
+            profile: Profile {
+                name: "work".to_string(),
+                ..Default::default()
+            },
+            confidence: 0.9,
+            matched_rules: vec![],
+            reason: "Profile 'work' detected: directory pattern matches".to_string(),
+            reasons: vec![],
+        });
+        let mut context = create_test_context();
+        context.profile_detector = Arc::new(detector);
+
+        let report = DetectCommand::new().report(Path::new("/repo"), true, &context).unwrap();
+        assert_eq!(
+            report,
+            "Detected profile 'work' (90% confidence): Profile 'work' detected: directory pattern matches"
+        );
+    }
+
+    #[test]
+    fn test_report_explains_miss() {
+        let context = create_test_context();
+        let cmd = DetectCommand::new();
+
+        let report = cmd.report(Path::new("/repo"), false, &context).unwrap();
+        assert!(report.contains("--explain-miss"));
+
+        let report = cmd.report(Path::new("/repo"), true, &context).unwrap();
+        assert!(report.starts_with("No profile detected because:"));
+    }
+}
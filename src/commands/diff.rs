@@ -0,0 +1,171 @@
+//! Profile comparison command implementation for git-setup-rs.
+//!
+//! `--diff <PROFILE_A> <PROFILE_B>` shows the fields whose values differ
+//! between two profiles side by side, e.g. to find why two near-identical
+//! work profiles behave differently. Fields with the same value are left
+//! out. `--output json` prints the differences as `[{field, old, new}]`.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    config::types::Profile,
+    error::{GitSetupError, Result},
+    output::DiffFormatter,
+    profile::diff::{FieldDiff, ProfileDiff},
+};
+use async_trait::async_trait;
+use is_terminal::IsTerminal;
+
+/// Command implementation for comparing two profiles.
+pub struct DiffCommand;
+
+impl DiffCommand {
+    /// Create a new DiffCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn read_profile(name: &str, context: &CommandContext) -> Result<Profile> {
+        context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.to_string() })
+    }
+
+    /// Render `diffs` between the profiles `old` and `new` in `format`.
+    ///
+    /// Returns `None` for tabular output when the profiles do not differ.
+    fn render(diffs: &[FieldDiff], old: &str, new: &str, format: &OutputFormat, color: bool) -> Result<Option<String>> {
+        let formatter = DiffFormatter::new();
+        match format {
+            OutputFormat::Json => Ok(Some(formatter.format_json(diffs)?)),
+            OutputFormat::Tabular if diffs.is_empty() => Ok(None),
+            OutputFormat::Tabular => Ok(Some(formatter.format_table(diffs, old, new, color))),
+            OutputFormat::Yaml | OutputFormat::Toml | OutputFormat::Csv => Err(GitSetupError::Git(
+                "Profile differences can be shown as tabular or json output".to_string(),
+            )),
+        }
+    }
+}
+
+impl Default for DiffCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for DiffCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let (old, new) = match args.diff.as_deref() {
+            Some([old, new]) => (old, new),
+            _ => return Err(GitSetupError::Git("Two profile names are required for --diff".to_string())),
+        };
+        let diffs = ProfileDiff::compare(&Self::read_profile(old, context)?, &Self::read_profile(new, context)?);
+
+        match Self::render(&diffs, old, new, &args.output, std::io::stdout().is_terminal())? {
+            Some(output) => println!("{}", output),
+            None if !args.quiet => println!("Profiles '{}' and '{}' have the same settings", old, new),
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "diff"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the fields that differ between two profiles"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::config::types::{KeyType, Scope};
+    use crate::profile::mock::MockProfileManager;
+    use std::sync::Arc;
+
+    fn work() -> Profile {
+        Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Jane Doe".to_string()),
+            git_user_email: "jane@acme.com".to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("~/.ssh/id_acme".to_string()),
+            scope: Some(Scope::Local),
+            ..Default::default()
+        }
+    }
+
+    fn work_eu() -> Profile {
+        Profile {
+            name: "work-eu".to_string(),
+            git_user_email: "jane@acme.eu".to_string(),
+            scope: Some(Scope::Global),
+            ..work()
+        }
+    }
+
+    /// Test the exact side-by-side and JSON output for two hand-crafted profiles.
+    #[test]
+    fn test_render_diff() {
+        let diffs = ProfileDiff::compare(&work(), &work_eu());
+
+        let table = DiffCommand::render(&diffs, "work", "work-eu", &OutputFormat::Tabular, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            table,
+            "\
+┌────────────────┬───────────────┬──────────────┐
+│ Field          ┆ work          ┆ work-eu      │
+╞════════════════╪═══════════════╪══════════════╡
+│ git_user_email ┆ jane@acme.com ┆ jane@acme.eu │
+├╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+│ scope          ┆ local         ┆ global       │
+└────────────────┴───────────────┴──────────────┘"
+        );
+
+        let json = DiffCommand::render(&diffs, "work", "work-eu", &OutputFormat::Json, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            json,
+            r#"[
+  {
+    "field": "git_user_email",
+    "old": "jane@acme.com",
+    "new": "jane@acme.eu"
+  },
+  {
+    "field": "scope",
+    "old": "local",
+    "new": "global"
+  }
+]"#
+        );
+
+        assert_eq!(DiffCommand::render(&[], "work", "copy", &OutputFormat::Tabular, false).unwrap(), None);
+        assert!(DiffCommand::render(&diffs, "work", "work-eu", &OutputFormat::Csv, false).is_err());
+    }
+
+    /// Test that both profiles must exist.
+    #[tokio::test]
+    async fn test_diff_command() {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![work(), work_eu()]));
+        let args = |a: &str, b: &str| Args {
+            diff: Some(vec![a.to_string(), b.to_string()]),
+            quiet: true,
+            ..Default::default()
+        };
+
+        DiffCommand::new().execute(&args("work", "work-eu"), &context).await.unwrap();
+
+        let result = DiffCommand::new().execute(&args("work", "missing"), &context).await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { name }) if name == "missing"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Profile diff command implementation for git-setup-rs.
+//!
+//! `--diff-from-applied <PROFILE>` shows what applying the profile would change
+//! in the git config: `-` lines for values that would go away, `+` lines for
+//! values the profile sets and unchanged values in grey. The scope compared is
+//! the one apply would use.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{GitSetupError, Result, ResultExt},
+    external::git::GitConfigScope,
+    profile::diff::{DiffStatus, ProfileDiff},
+};
+use async_trait::async_trait;
+use is_terminal::IsTerminal;
+
+/// Command implementation for diffing a profile against the applied git config.
+pub struct DiffAppliedCommand;
+
+impl DiffAppliedCommand {
+    /// Create a new DiffAppliedCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DiffAppliedCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for DiffAppliedCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.diff_from_applied.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --diff-from-applied".to_string())
+        })?;
+        let profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+
+        let scope = if args.global {
+            GitConfigScope::Global
+        } else if args.system {
+            GitConfigScope::System
+        } else {
+            let in_repo = context.git_wrapper.is_inside_work_tree()?;
+            profile.effective_scope(in_repo).into()
+        };
+
+        let lines = ProfileDiff::compute(&profile, context.git_wrapper.as_ref(), scope)
+            .with_context(format!("while comparing profile '{}' with the git config", name))?;
+
+        print!("{}", ProfileDiff::render(&lines, std::io::stdout().is_terminal()));
+        if !args.quiet && lines.iter().all(|line| line.status == DiffStatus::Unchanged) {
+            println!("Profile '{}' is already applied", name);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "diff-from-applied"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show what applying a profile would change in the git config"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        external::git::{GitWrapper, MockGitWrapper},
+        profile::mock::MockProfileManager,
+    };
+    use std::sync::Arc;
+
+    /// Test that the diff compares the scope given on the command line.
+    #[tokio::test]
+    async fn test_diff_from_applied() {
+        let git = Arc::new(MockGitWrapper::new());
+        git.set_config("user.email", "jane@work.com", GitConfigScope::Global).unwrap();
+        let mut context = create_test_context();
+        context.git_wrapper = git;
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            ..Default::default()
+        }]));
+
+        let args = Args {
+            diff_from_applied: Some("work".to_string()),
+            global: true,
+            quiet: true,
+            ..Default::default()
+        };
+        DiffAppliedCommand::new().execute(&args, &context).await.unwrap();
+
+        let args = Args {
+            diff_from_applied: Some("missing".to_string()),
+            ..args
+        };
+        let result = DiffAppliedCommand::new().execute(&args, &context).await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+    }
+}
@@ -0,0 +1,221 @@
+//! Duplicate command implementation for git-setup-rs.
+//!
+//! `--duplicate <SOURCE> <DEST>` copies a profile under a new name, e.g. to
+//! start a client profile from the work one. The repositories and patterns
+//! selecting SOURCE (`repos`, `match_patterns`, `include_if_dirs`) are left
+//! out of the copy unless `--keep-patterns` is given, so both profiles do not
+//! claim the same repositories. An existing DEST is only replaced with `--force`.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Command implementation for copying a profile under a new name.
+pub struct DuplicateCommand;
+
+impl DuplicateCommand {
+    /// Create a new DuplicateCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `source` renamed to `dest`, without the fields selecting `source` unless `keep_patterns`.
+    ///
+    /// The copy is never archived, even if `source` is.
+    fn copy(source: &Profile, dest: &str, keep_patterns: bool) -> Profile {
+        let mut copy = source.clone();
+        copy.name = dest.to_string();
+        copy.is_archived = false;
+        if !keep_patterns {
+            copy.repos.clear();
+            copy.match_patterns.clear();
+            copy.include_if_dirs.clear();
+        }
+        copy
+    }
+}
+
+impl Default for DuplicateCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for DuplicateCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let (source, dest) = match args.duplicate.as_deref() {
+            Some([source, dest]) => (source, dest),
+            _ => {
+                return Err(GitSetupError::Git(
+                    "A source and a destination profile are required for --duplicate".to_string(),
+                ))
+            }
+        };
+        if source == dest {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("cannot duplicate profile '{}' onto itself", source),
+            });
+        }
+
+        let profile = context
+            .profile_manager
+            .read(source)
+            .with_context(format!("while loading profile '{}'", source))?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: source.clone() })?;
+        let copy = Self::copy(&profile, dest, args.keep_patterns);
+
+        if context.profile_manager.exists(dest)? {
+            if !args.force {
+                return Err(GitSetupError::DuplicateProfile { name: dest.clone() });
+            }
+            context
+                .profile_manager
+                .update(dest, copy)
+                .with_context(format!("while overwriting profile '{}'", dest))?;
+        } else {
+            context
+                .profile_manager
+                .create(copy)
+                .with_context(format!("while creating profile '{}'", dest))?;
+        }
+
+        if !args.quiet {
+            println!("✓ Duplicated profile '{}' as '{}'", source, dest);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Copy a profile under a new name"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::profile::{mock::MockProfileManager, ProfileManager};
+    use std::sync::Arc;
+
+    fn work_profile() -> Profile {
+        Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            signing_key: Some("~/.ssh/id_work".to_string()),
+            repos: vec!["~/work/api".to_string()],
+            match_patterns: vec!["github.com/acme/*".to_string()],
+            include_if_dirs: vec!["~/work/".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn context_with(profiles: Vec<Profile>) -> (CommandContext, Arc<MockProfileManager>) {
+        let manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = manager.clone();
+        (context, manager)
+    }
+
+    fn duplicate_args(source: &str, dest: &str) -> Args {
+        Args {
+            duplicate: Some(vec![source.to_string(), dest.to_string()]),
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that the copy keeps the identity but drops the source's patterns.
+    #[tokio::test]
+    async fn test_duplicate_strips_patterns() {
+        let (context, manager) = context_with(vec![work_profile()]);
+
+        DuplicateCommand::new()
+            .execute(&duplicate_args("work", "client"), &context)
+            .await
+            .unwrap();
+
+        let copy = manager.read("client").unwrap().unwrap();
+        assert_eq!(copy.git_user_email, "jane@work.com");
+        assert_eq!(copy.signing_key.as_deref(), Some("~/.ssh/id_work"));
+        assert!(copy.repos.is_empty());
+        assert!(copy.match_patterns.is_empty());
+        assert!(copy.include_if_dirs.is_empty());
+        assert!(manager.was_called_with_create("client"));
+        assert!(!manager.was_called_with_update("work"));
+    }
+
+    /// Test that --keep-patterns copies the patterns too.
+    #[tokio::test]
+    async fn test_duplicate_keep_patterns() {
+        let (context, manager) = context_with(vec![work_profile()]);
+        let args = Args {
+            keep_patterns: true,
+            ..duplicate_args("work", "client")
+        };
+
+        DuplicateCommand::new().execute(&args, &context).await.unwrap();
+
+        let copy = manager.read("client").unwrap().unwrap();
+        assert_eq!(copy, Profile { name: "client".to_string(), ..work_profile() });
+    }
+
+    /// Test that a missing source and an existing destination are rejected.
+    #[tokio::test]
+    async fn test_duplicate_errors() {
+        let personal = Profile {
+            name: "personal".to_string(),
+            git_user_email: "jane@home.net".to_string(),
+            ..Default::default()
+        };
+        let (context, manager) = context_with(vec![work_profile(), personal]);
+
+        let result = DuplicateCommand::new()
+            .execute(&duplicate_args("missing", "client"), &context)
+            .await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+
+        let result = DuplicateCommand::new()
+            .execute(&duplicate_args("work", "personal"), &context)
+            .await;
+        assert!(matches!(result, Err(GitSetupError::DuplicateProfile { .. })));
+        assert_eq!(manager.create_call_count(), 0);
+        assert_eq!(manager.update_call_count(), 0);
+
+        let result = DuplicateCommand::new()
+            .execute(&duplicate_args("work", "work"), &context)
+            .await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+    }
+
+    /// Test that --force overwrites an existing destination.
+    #[tokio::test]
+    async fn test_duplicate_force_overwrites() {
+        let personal = Profile {
+            name: "personal".to_string(),
+            git_user_email: "jane@home.net".to_string(),
+            ..Default::default()
+        };
+        let (context, manager) = context_with(vec![work_profile(), personal]);
+        let args = Args {
+            force: true,
+            ..duplicate_args("work", "personal")
+        };
+
+        DuplicateCommand::new().execute(&args, &context).await.unwrap();
+
+        let copy = manager.read("personal").unwrap().unwrap();
+        assert_eq!(copy.git_user_email, "jane@work.com");
+        assert!(copy.repos.is_empty());
+        assert!(manager.was_called_with_update("personal"));
+        assert_eq!(manager.create_call_count(), 0);
+    }
+}
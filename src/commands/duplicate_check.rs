@@ -0,0 +1,116 @@
+//! Duplicate-check command implementation for git-setup-rs.
+//!
+//! This command reports groups of profiles that share an email, a signing key
+//! or a 1Password SSH key, which usually means one is an accidental copy.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    error::{Result, ResultExt},
+    profile::duplicates::{find_duplicates, DuplicateGroup},
+};
+use async_trait::async_trait;
+
+/// Command implementation for finding duplicate profiles.
+pub struct DuplicateCheckCommand;
+
+impl DuplicateCheckCommand {
+    /// Create a new DuplicateCheckCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find the duplicate groups among all stored profiles.
+    pub fn check(&self, context: &CommandContext) -> Result<Vec<DuplicateGroup>> {
+        Ok(find_duplicates(&context.profile_manager.list()?))
+    }
+
+    /// Human-readable line for `group`.
+    fn describe(group: &DuplicateGroup) -> String {
+        format!(
+            "Potential duplicate ({} '{}'): {}",
+            group.reason,
+            group.field_value,
+            group.profiles.join(", ")
+        )
+    }
+}
+
+impl Default for DuplicateCheckCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for DuplicateCheckCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let groups = self
+            .check(context)
+            .with_context("while checking profiles for duplicates")?;
+
+        match args.output {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&groups)?),
+            _ => {
+                if !args.quiet {
+                    if groups.is_empty() {
+                        println!("No duplicate profiles found");
+                    }
+                    for group in &groups {
+                        println!("{}", Self::describe(group));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate-check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find profiles with the same email, signing key or 1Password key"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        profile::{duplicates::DuplicateReason, mock::MockProfileManager},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_check_reports_shared_email() {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                ..Default::default()
+            },
+            Profile {
+                name: "work-old".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                ..Default::default()
+            },
+        ]));
+
+        let groups = DuplicateCheckCommand::new().check(&context).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].reason, DuplicateReason::Email);
+        assert_eq!(
+            DuplicateCheckCommand::describe(&groups[0]),
+            "Potential duplicate (email 'jane@work.com'): work, work-old"
+        );
+
+        let json = serde_json::to_value(&groups).unwrap();
+        assert_eq!(json[0]["reason"], "email");
+        assert_eq!(json[0]["profiles"][1], "work-old");
+    }
+}
@@ -5,8 +5,8 @@
 use super::{Command, CommandContext};
 use crate::{
     cli::Args,
-    config::types::Profile,
-    error::{GitSetupError, Result},
+    config::types::{Profile, SecretStoreKind},
+    error::{GitSetupError, Result, ResultExt},
 };
 use async_trait::async_trait;
 
@@ -53,7 +53,7 @@ impl EditCommand {
         }
 
         // Validate 1Password configuration
-        if profile.one_password {
+        if profile.key_store() == Some(SecretStoreKind::OnePassword) {
             if profile.vault_name.is_none() {
                 return Err(GitSetupError::Git("Vault name is required for 1Password integration".to_string()));
             }
@@ -76,7 +76,7 @@ impl EditCommand {
         
         println!("  Key type: {:?}", profile.key_type);
         
-        if profile.one_password {
+        if profile.key_store() == Some(SecretStoreKind::OnePassword) {
             println!("  1Password: Enabled");
             if let Some(vault) = &profile.vault_name {
                 println!("  Vault: {}", vault);
@@ -107,7 +107,8 @@ impl Command for EditCommand {
         self.validate_profile(&edited_profile)?;
 
         // Save the updated profile
-        context.profile_manager.write(&edited_profile)?;
+        context.profile_manager.write(&edited_profile)
+            .with_context(format!("while updating profile '{}'", profile_name))?;
 
         // Print success message
         if !args.quiet {
@@ -164,6 +165,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -193,9 +203,9 @@ mod tests {
     /// Test editing a non-existent profile.
     #[tokio::test]
     async fn test_edit_nonexistent_profile() {
-        let profile_manager = Arc::new(MockProfileManager::new());
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let args = create_edit_args("nonexistent");
         let cmd = EditCommand::new();
@@ -203,21 +213,23 @@ mod tests {
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), GitSetupError::ProfileNotFound { .. }));
+        assert_eq!(profile_manager.update_call_count(), 0);
     }
 
     /// Test loading a profile for editing.
     #[tokio::test]
     async fn test_load_profile() {
         let profile = create_test_profile("test-profile", "test@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
         let cmd = EditCommand::new();
         let loaded_profile = cmd.load_profile("test-profile", &context).await.unwrap();
 
         assert_eq!(loaded_profile.name, profile.name);
         assert_eq!(loaded_profile.git_user_email, profile.git_user_email);
+        assert!(profile_manager.was_called_with_read("test-profile"));
     }
 
     /// Test loading a non-existent profile.
@@ -0,0 +1,596 @@
+//! Environment injection commands for git-setup-rs.
+//!
+//! `--env-inject` writes a profile's identity and signing settings as environment
+//! variables to a file the shell sources, so a profile can be activated for a
+//! shell session without touching any git config. `--shell-install` adds the
+//! sourcing line to the user's shell startup file. `apply --output-env-file`
+//! writes the same identity as a `.env` style file for containers and CI.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, EnvFileFormat, InstallShell},
+    config::types::{KeyType, Profile},
+    error::{GitSetupError, Result, ResultExt},
+    platform::{PlatformPaths, SystemPlatform},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use secrecy::{ExposeSecret, SecretString};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File name of the generated POSIX shell environment file.
+pub const SH_ENV_FILE: &str = "current-env.sh";
+
+/// File name of the generated fish environment file.
+pub const FISH_ENV_FILE: &str = "current-env.fish";
+
+/// Variables that are always cleared by `--env-inject --clear`.
+const IDENTITY_VARS: &[&str] = &[
+    "GIT_SETUP_PROFILE",
+    "GIT_AUTHOR_NAME",
+    "GIT_AUTHOR_EMAIL",
+    "GIT_COMMITTER_NAME",
+    "GIT_COMMITTER_EMAIL",
+    "GIT_CONFIG_COUNT",
+];
+
+/// Maximum number of `GIT_CONFIG_KEY_<n>`/`GIT_CONFIG_VALUE_<n>` pairs generated.
+const MAX_CONFIG_ENTRIES: usize = 4;
+
+/// Environment variables that activate `profile` for git.
+///
+/// Signing settings are passed through git's `GIT_CONFIG_COUNT` mechanism.
+pub fn env_vars(profile: &Profile) -> Vec<(String, String)> {
+    let mut vars = vec![("GIT_SETUP_PROFILE".to_string(), profile.name.clone())];
+
+    if let Some(name) = &profile.git_user_name {
+        vars.push(("GIT_AUTHOR_NAME".to_string(), name.clone()));
+        vars.push(("GIT_COMMITTER_NAME".to_string(), name.clone()));
+    }
+    vars.push(("GIT_AUTHOR_EMAIL".to_string(), profile.git_user_email.clone()));
+    vars.push(("GIT_COMMITTER_EMAIL".to_string(), profile.git_user_email.clone()));
+
+    let mut config = vec![];
+    match profile.key_type {
+        KeyType::Ssh => config.push(("gpg.format", "ssh".to_string())),
+        KeyType::Gpg => config.push(("gpg.format", "openpgp".to_string())),
+        KeyType::X509 => config.push(("gpg.format", "x509".to_string())),
+        KeyType::Gitsign => {
+            config.push(("gpg.format", "x509".to_string()));
+            config.push(("gpg.x509.program", "gitsign".to_string()));
+        }
+    }
+    if let Some(signing_key) = &profile.signing_key {
+        config.push(("user.signingkey", signing_key.clone()));
+    }
+    if profile.signing_key.is_some() || matches!(profile.key_type, KeyType::X509 | KeyType::Gitsign) {
+        config.push(("commit.gpgsign", "true".to_string()));
+    }
+
+    vars.push(("GIT_CONFIG_COUNT".to_string(), config.len().to_string()));
+    for (i, (key, value)) in config.into_iter().enumerate() {
+        vars.push((format!("GIT_CONFIG_KEY_{}", i), key.to_string()));
+        vars.push((format!("GIT_CONFIG_VALUE_{}", i), value));
+    }
+
+    vars
+}
+
+/// Names of every variable `env_vars` may set.
+fn all_var_names() -> Vec<String> {
+    let mut names: Vec<String> = IDENTITY_VARS.iter().map(|name| name.to_string()).collect();
+    for i in 0..MAX_CONFIG_ENTRIES {
+        names.push(format!("GIT_CONFIG_KEY_{}", i));
+        names.push(format!("GIT_CONFIG_VALUE_{}", i));
+    }
+    names
+}
+
+/// Quote a value for POSIX shells.
+fn sh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Quote a value for fish.
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Render a POSIX shell environment file; `None` renders only `unset` commands.
+pub fn render_sh(profile: Option<&Profile>, generated_at: DateTime<Utc>) -> String {
+    let mut out = format!("# Generated by git-setup at {}\n", generated_at.to_rfc3339());
+    for name in all_var_names() {
+        out.push_str(&format!("unset {}\n", name));
+    }
+    if let Some(profile) = profile {
+        for (name, value) in env_vars(profile) {
+            out.push_str(&format!("export {}={}\n", name, sh_quote(&value)));
+        }
+    }
+    out.push_str(&format!("# Profile: {}\n", profile.map_or("none", |p| p.name.as_str())));
+    out
+}
+
+/// Render a fish environment file; `None` renders only erase commands.
+pub fn render_fish(profile: Option<&Profile>, generated_at: DateTime<Utc>) -> String {
+    let mut out = format!("# Generated by git-setup at {}\n", generated_at.to_rfc3339());
+    for name in all_var_names() {
+        out.push_str(&format!("set -e {}\n", name));
+    }
+    if let Some(profile) = profile {
+        for (name, value) in env_vars(profile) {
+            out.push_str(&format!("set -gx {} {}\n", name, fish_quote(&value)));
+        }
+    }
+    out.push_str(&format!("# Profile: {}\n", profile.map_or("none", |p| p.name.as_str())));
+    out
+}
+
+/// Variables written by `apply --output-env-file`.
+///
+/// `signing_key` is the key the apply resolved, e.g. from 1Password. The
+/// passphrase is only written when given, which `--include-secrets` controls.
+pub fn env_file_vars(
+    profile: &Profile,
+    signing_key: Option<&str>,
+    passphrase: Option<&SecretString>,
+) -> Vec<(String, String)> {
+    let mut vars = vec![("GIT_SETUP_PROFILE_NAME".to_string(), profile.name.clone())];
+
+    if let Some(name) = &profile.git_user_name {
+        vars.push(("GIT_AUTHOR_NAME".to_string(), name.clone()));
+    }
+    vars.push(("GIT_AUTHOR_EMAIL".to_string(), profile.git_user_email.clone()));
+
+    if let Some(signing_key) = signing_key.or(profile.signing_key.as_deref()) {
+        vars.push(("GIT_SIGNING_KEY".to_string(), signing_key.to_string()));
+        if profile.key_type == KeyType::Gpg {
+            vars.push(("GPG_KEY_ID".to_string(), signing_key.to_string()));
+        }
+    }
+
+    if let Some(passphrase) = passphrase {
+        vars.push((
+            "GIT_SIGNING_KEY_PASSPHRASE".to_string(),
+            passphrase.expose_secret().to_string(),
+        ));
+    }
+
+    vars
+}
+
+/// Quote a value inside a double-quoted shell string.
+fn double_quote_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`")
+}
+
+/// Quote a value for a `.env` file.
+fn dotenv_quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+    )
+}
+
+/// Render `vars` as an environment file in `format`.
+///
+/// `.env` files double-quote each value; Docker `--env-file` files have no
+/// quoting and take values verbatim, one `KEY=VALUE` per line; GitHub Actions
+/// files append each line to `$GITHUB_ENV`.
+pub fn render_env_file(vars: &[(String, String)], format: EnvFileFormat) -> String {
+    vars.iter()
+        .map(|(name, value)| match format {
+            EnvFileFormat::Dotenv => format!("{}={}\n", name, dotenv_quote(value)),
+            EnvFileFormat::Docker => format!("{}={}\n", name, value),
+            EnvFileFormat::GithubActions => format!(
+                "echo \"{}={}\" >> \"$GITHUB_ENV\"\n",
+                name,
+                double_quote_escape(value)
+            ),
+        })
+        .collect()
+}
+
+/// Write an environment file to `path`, readable only by the user when it holds secrets.
+///
+/// A secret file is created with mode 0600, and an existing one is narrowed to
+/// 0600 before any content is written to it.
+pub fn write_env_file(path: &Path, content: &str, secret: bool) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    if secret {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+    }
+    let mut file = options.open(path)?;
+
+    if secret {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Write both environment files into `dir`, returning their paths.
+pub fn write_env_files(dir: &Path, profile: Option<&Profile>) -> Result<(PathBuf, PathBuf)> {
+    std::fs::create_dir_all(dir)?;
+    let now = Utc::now();
+
+    let sh_path = dir.join(SH_ENV_FILE);
+    std::fs::write(&sh_path, render_sh(profile, now))?;
+    let fish_path = dir.join(FISH_ENV_FILE);
+    std::fs::write(&fish_path, render_fish(profile, now))?;
+
+    Ok((sh_path, fish_path))
+}
+
+/// Command implementation for writing a profile's environment file.
+pub struct EnvInjectCommand;
+
+impl EnvInjectCommand {
+    /// Create a new EnvInjectCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Look up the profile named on the command line, unless clearing.
+    fn profile_to_inject(&self, args: &Args, context: &CommandContext) -> Result<Option<Profile>> {
+        if args.clear {
+            return Ok(None);
+        }
+
+        let name = args.profile.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --env-inject".to_string())
+        })?;
+        let profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+        Ok(Some(profile))
+    }
+}
+
+impl Default for EnvInjectCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for EnvInjectCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let profile = self.profile_to_inject(args, context)?;
+
+        // For `source <(git-setup --env-inject work --stdout)`
+        if args.stdout {
+            print!("{}", render_sh(profile.as_ref(), Utc::now()));
+            return Ok(());
+        }
+
+        let (sh_path, _) = write_env_files(&SystemPlatform.data_dir()?, profile.as_ref())
+            .with_context("while writing the shell environment files")?;
+
+        if !args.quiet {
+            match &profile {
+                Some(profile) => println!(
+                    "✓ Wrote environment for profile '{}' to {}",
+                    profile.name,
+                    sh_path.display()
+                ),
+                None => println!("✓ Cleared git-setup environment in {}", sh_path.display()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "env-inject"
+    }
+
+    fn description(&self) -> &'static str {
+        "Write a profile's git environment to a file sourced by the shell"
+    }
+}
+
+/// Command implementation for hooking the environment file into a shell.
+pub struct ShellInstallCommand;
+
+impl ShellInstallCommand {
+    /// Create a new ShellInstallCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Startup file of `shell` under `home`.
+    pub fn rc_file(shell: InstallShell, home: &Path) -> PathBuf {
+        match shell {
+            InstallShell::Bash => home.join(".bashrc"),
+            InstallShell::Zsh => match std::env::var("ZDOTDIR") {
+                Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join(".zshrc"),
+                _ => home.join(".zshrc"),
+            },
+            InstallShell::Fish => home.join(".config").join("fish").join("config.fish"),
+        }
+    }
+
+    /// Line that sources the environment file in `env_dir` for `shell`.
+    pub fn source_line(shell: InstallShell, env_dir: &Path) -> String {
+        match shell {
+            InstallShell::Bash | InstallShell::Zsh => {
+                let path = env_dir.join(SH_ENV_FILE);
+                let quoted = sh_quote(&path.display().to_string());
+                format!("[ -f {} ] && . {}", quoted, quoted)
+            }
+            InstallShell::Fish => {
+                let path = env_dir.join(FISH_ENV_FILE);
+                let quoted = fish_quote(&path.display().to_string());
+                format!("test -f {}; and source {}", quoted, quoted)
+            }
+        }
+    }
+
+    /// Append the source line to `rc_file` unless it is already there.
+    ///
+    /// Returns `false` if the file already contained the line.
+    pub fn install(&self, rc_file: &Path, line: &str) -> Result<bool> {
+        if let Ok(content) = std::fs::read_to_string(rc_file)
+            && content.lines().any(|existing| existing.trim() == line)
+        {
+            return Ok(false);
+        }
+
+        if let Some(parent) = rc_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(rc_file)?;
+        writeln!(file, "\n# Added by git-setup")?;
+        writeln!(file, "{}", line)?;
+        Ok(true)
+    }
+}
+
+impl Default for ShellInstallCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ShellInstallCommand {
+    async fn execute(&self, args: &Args, _context: &CommandContext) -> Result<()> {
+        let shell = args.shell_install.ok_or_else(|| {
+            GitSetupError::Git("Shell is required for --shell-install".to_string())
+        })?;
+
+        let rc_file = Self::rc_file(shell, &SystemPlatform.home_dir()?);
+        let line = Self::source_line(shell, &SystemPlatform.data_dir()?);
+        let installed = self
+            .install(&rc_file, &line)
+            .with_context(format!("while updating '{}'", rc_file.display()))?;
+
+        if !args.quiet {
+            if installed {
+                println!("✓ Added git-setup environment to {}", rc_file.display());
+            } else {
+                println!("git-setup environment is already sourced in {}", rc_file.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "shell-install"
+    }
+
+    fn description(&self) -> &'static str {
+        "Source the git-setup environment file from the shell startup file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_profile() -> Profile {
+        Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Pat O'Brien".to_string()),
+            git_user_email: "pat@example.com".to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("ssh-ed25519 AAAAC3 pat@example.com".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Source `file` in `sh` and print the named variables, one per line.
+    fn source_and_print(file: &Path, vars: &[&str]) -> String {
+        let prints: Vec<String> = vars
+            .iter()
+            .map(|var| format!("printf '%s\\n' \"${{{}:-<unset>}}\"", var))
+            .collect();
+        let script = format!(". '{}'; {}", file.display(), prints.join("; "));
+        let output = std::process::Command::new("sh").args(["-c", &script]).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_env_vars() {
+        let vars = env_vars(&test_profile());
+        let get = |name: &str| vars.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+
+        assert_eq!(get("GIT_AUTHOR_EMAIL"), Some("pat@example.com"));
+        assert_eq!(get("GIT_COMMITTER_NAME"), Some("Pat O'Brien"));
+        assert_eq!(get("GIT_CONFIG_COUNT"), Some("3"));
+        assert_eq!(get("GIT_CONFIG_KEY_0"), Some("gpg.format"));
+        assert_eq!(get("GIT_CONFIG_VALUE_0"), Some("ssh"));
+        assert_eq!(get("GIT_CONFIG_KEY_1"), Some("user.signingkey"));
+        assert_eq!(get("GIT_CONFIG_VALUE_2"), Some("true"));
+    }
+
+    #[test]
+    fn test_generated_sh_file_is_valid_shell() {
+        let dir = TempDir::new().unwrap();
+        let (sh_path, _) = write_env_files(dir.path(), Some(&test_profile())).unwrap();
+
+        let content = std::fs::read_to_string(&sh_path).unwrap();
+        assert!(content.starts_with("# Generated by git-setup at "));
+        assert!(content.ends_with("# Profile: work\n"));
+
+        let status = std::process::Command::new("sh").arg("-n").arg(&sh_path).status().unwrap();
+        assert!(status.success());
+
+        let printed = source_and_print(&sh_path, &["GIT_AUTHOR_NAME", "GIT_AUTHOR_EMAIL", "GIT_CONFIG_VALUE_1"]);
+        assert_eq!(
+            printed,
+            "Pat O'Brien\npat@example.com\nssh-ed25519 AAAAC3 pat@example.com\n"
+        );
+    }
+
+    #[test]
+    fn test_clear_writes_only_unset_commands() {
+        let dir = TempDir::new().unwrap();
+        write_env_files(dir.path(), Some(&test_profile())).unwrap();
+        let (sh_path, fish_path) = write_env_files(dir.path(), None).unwrap();
+
+        let content = std::fs::read_to_string(&sh_path).unwrap();
+        assert!(content.lines().all(|line| line.starts_with('#') || line.starts_with("unset ")));
+        assert!(content.ends_with("# Profile: none\n"));
+
+        let fish = std::fs::read_to_string(&fish_path).unwrap();
+        assert!(fish.lines().all(|line| line.starts_with('#') || line.starts_with("set -e ")));
+
+        let status = std::process::Command::new("sh").arg("-n").arg(&sh_path).status().unwrap();
+        assert!(status.success());
+        assert_eq!(source_and_print(&sh_path, &["GIT_AUTHOR_EMAIL"]), "<unset>\n");
+    }
+
+    #[test]
+    fn test_render_fish_quotes_values() {
+        let content = render_fish(Some(&test_profile()), Utc::now());
+        assert!(content.contains("set -gx GIT_AUTHOR_NAME 'Pat O\\'Brien'\n"));
+        assert!(content.ends_with("# Profile: work\n"));
+    }
+
+    #[test]
+    fn test_env_file_vars() {
+        let vars = env_file_vars(&test_profile(), None, None);
+        assert_eq!(
+            render_env_file(&vars, EnvFileFormat::Dotenv),
+            "GIT_SETUP_PROFILE_NAME=\"work\"\n\
+             GIT_AUTHOR_NAME=\"Pat O'Brien\"\n\
+             GIT_AUTHOR_EMAIL=\"pat@example.com\"\n\
+             GIT_SIGNING_KEY=\"ssh-ed25519 AAAAC3 pat@example.com\"\n"
+        );
+        assert_eq!(
+            render_env_file(&vars, EnvFileFormat::Docker),
+            "GIT_SETUP_PROFILE_NAME=work\n\
+             GIT_AUTHOR_NAME=Pat O'Brien\n\
+             GIT_AUTHOR_EMAIL=pat@example.com\n\
+             GIT_SIGNING_KEY=ssh-ed25519 AAAAC3 pat@example.com\n"
+        );
+        assert_eq!(
+            dotenv_quote("a \"b\" \\ c\nd"),
+            "\"a \\\"b\\\" \\\\ c\\nd\""
+        );
+
+        let profile = Profile {
+            key_type: KeyType::Gpg,
+            signing_key: Some("ABCD1234".to_string()),
+            ..test_profile()
+        };
+        let passphrase = SecretString::from("s3cr3t".to_string());
+        let vars = env_file_vars(&profile, None, Some(&passphrase));
+        let get = |name: &str| vars.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+        assert_eq!(get("GPG_KEY_ID"), Some("ABCD1234"));
+        assert_eq!(get("GIT_SIGNING_KEY_PASSPHRASE"), Some("s3cr3t"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_env_file_secret_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".env");
+        write_env_file(&path, "A=\"1\"\n", true).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        write_env_file(&path, "B=\"2\"\n", true).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "B=\"2\"\n");
+    }
+
+    #[test]
+    fn test_github_actions_env_file_is_valid_shell() {
+        let dir = TempDir::new().unwrap();
+        let github_env = dir.path().join("github_env");
+        let profile = Profile {
+            git_user_name: Some("Pat \"$HOME\" O'Brien".to_string()),
+            ..test_profile()
+        };
+        let script = render_env_file(&env_file_vars(&profile, None, None), EnvFileFormat::GithubActions);
+        assert!(script.starts_with("echo \"GIT_SETUP_PROFILE_NAME=work\" >> \"$GITHUB_ENV\"\n"));
+
+        let output = std::process::Command::new("sh")
+            .args(["-c", &script])
+            .env("GITHUB_ENV", &github_env)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let written = std::fs::read_to_string(&github_env).unwrap();
+        assert!(written.contains("GIT_AUTHOR_NAME=Pat \"$HOME\" O'Brien\n"));
+    }
+
+    #[test]
+    fn test_shell_install_is_idempotent() {
+        let dir = TempDir::new().unwrap();
+        let rc_file = ShellInstallCommand::rc_file(InstallShell::Bash, dir.path());
+        let line = ShellInstallCommand::source_line(InstallShell::Bash, Path::new("/data/git-setup"));
+        assert_eq!(line, "[ -f '/data/git-setup/current-env.sh' ] && . '/data/git-setup/current-env.sh'");
+
+        let cmd = ShellInstallCommand::new();
+        assert!(cmd.install(&rc_file, &line).unwrap());
+        assert!(!cmd.install(&rc_file, &line).unwrap());
+
+        let content = std::fs::read_to_string(&rc_file).unwrap();
+        assert_eq!(content.matches(&line).count(), 1);
+    }
+
+    #[test]
+    fn test_fish_rc_file() {
+        let home = Path::new("/home/pat");
+        assert_eq!(
+            ShellInstallCommand::rc_file(InstallShell::Fish, home),
+            home.join(".config/fish/config.fish")
+        );
+        assert!(ShellInstallCommand::source_line(InstallShell::Fish, Path::new("/d"))
+            .starts_with("test -f '/d/current-env.fish'; and source"));
+    }
+}
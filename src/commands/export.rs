@@ -0,0 +1,218 @@
+//! Export command implementation for git-setup-rs.
+//!
+//! `--export <FILE>` writes all profiles, or those named with `--profiles`,
+//! to a profile bundle that `--import --from-bundle` reads on another
+//! machine. With `--sign-with <KEY_ID>` the bundle is signed with your own
+//! GPG key, and with `--encrypt-for <KEY_ID>` the profiles are also
+//! encrypted to the recipient's key.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+    profile::bundle::ProfileBundle,
+};
+use async_trait::async_trait;
+
+/// Command implementation for exporting profiles to a bundle.
+pub struct ExportCommand;
+
+impl ExportCommand {
+    /// Create a new ExportCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Profiles to export: those named in `--profiles`, or all of them.
+    fn profiles(&self, args: &Args, context: &CommandContext) -> Result<Vec<Profile>> {
+        match &args.profiles {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    context
+                        .profile_manager
+                        .read(name)?
+                        .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })
+                })
+                .collect(),
+            None => context.profile_manager.list(),
+        }
+    }
+}
+
+impl Default for ExportCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ExportCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let path = args
+            .export
+            .as_ref()
+            .ok_or_else(|| GitSetupError::Git("A file is required for --export".to_string()))?;
+        let profiles = self
+            .profiles(args, context)
+            .with_context("while loading profiles to export")?;
+
+        let gpg = context.gpg_wrapper.as_ref();
+        let bundle = match (&args.encrypt_for, &args.sign_with) {
+            (Some(recipient), Some(signer)) => ProfileBundle::encrypted(&profiles, recipient, signer, gpg)
+                .with_context(format!("while encrypting the profiles for '{}'", recipient))?,
+            (None, Some(signer)) => ProfileBundle::new(&profiles)?
+                .signed(signer, gpg)
+                .with_context(format!("while signing the profiles with '{}'", signer))?,
+            (Some(_), None) => {
+                return Err(GitSetupError::Git("--encrypt-for requires --sign-with".to_string()));
+            }
+            (None, None) => ProfileBundle::new(&profiles)?,
+        };
+        std::fs::write(path, bundle.to_json()?).with_context(format!("while writing '{}'", path))?;
+
+        if !args.quiet {
+            println!("✓ Exported {} profile(s) to {}", profiles.len(), path);
+            if let Some(recipient) = &args.encrypt_for {
+                println!("  Encrypted for {}", recipient);
+            }
+            if let Some(signer) = &args.sign_with {
+                println!("  Signed with {}", signer);
+            }
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn description(&self) -> &'static str {
+        "Export profiles to a bundle file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::import::ImportCommand;
+    use crate::commands::tests::create_test_context;
+    use crate::external::gpg::MockGpgWrapper;
+    use crate::profile::{bundle::BUNDLE_VERSION, mock::MockProfileManager, ProfileManager};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn profile(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            ..Default::default()
+        }
+    }
+
+    /// Test that a named subset is exported unencrypted with a version header.
+    #[tokio::test]
+    async fn test_export_subset() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("profiles.json");
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            profile("work"),
+            profile("personal"),
+        ]));
+        context.gpg_wrapper = Arc::new(MockGpgWrapper::new());
+        let args = Args {
+            export: Some(path.display().to_string()),
+            profiles: Some(vec!["work".to_string()]),
+            quiet: true,
+            ..Default::default()
+        };
+
+        ExportCommand::new().execute(&args, &context).await.unwrap();
+
+        let bundle = ProfileBundle::from_json(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.encrypted_for, None);
+        assert_eq!(bundle.profiles(context.gpg_wrapper.as_ref(), None).unwrap(), vec![profile("work")]);
+
+        let args = Args {
+            profiles: Some(vec!["missing".to_string()]),
+            ..args
+        };
+        let err = ExportCommand::new().execute(&args, &context).await.unwrap_err();
+        assert!(matches!(err.root_cause(), GitSetupError::ProfileNotFound { .. }));
+    }
+
+    /// Test that an exported bundle imports on another machine, skipping existing profiles.
+    #[tokio::test]
+    async fn test_export_then_import() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("profiles.json");
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            profile("work"),
+            profile("personal"),
+        ]));
+        let args = Args {
+            export: Some(path.display().to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        ExportCommand::new().execute(&args, &context).await.unwrap();
+
+        let target = Arc::new(MockProfileManager::with_profiles(vec![profile("personal")]).track_calls(true));
+        context.profile_manager = target.clone();
+        let args = Args {
+            import: true,
+            from_bundle: Some(path.display().to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        ImportCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(target.read("work").unwrap(), Some(profile("work")));
+        assert_eq!(target.create_call_count(), 1);
+        assert!(!target.was_called_with_create("personal"));
+    }
+
+    /// Test that an encrypted bundle is signed with the exporter's key and only imports from that signer.
+    #[tokio::test]
+    async fn test_export_encrypted_then_import() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("profiles.json");
+        let gpg = Arc::new(MockGpgWrapper::new());
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile("work")]));
+        context.gpg_wrapper = gpg.clone();
+        let args = Args {
+            export: Some(path.display().to_string()),
+            encrypt_for: Some("RECIPIENT".to_string()),
+            sign_with: Some("EXPORTER".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        ExportCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(gpg.sign_data_calls(), vec!["EXPORTER".to_string()]);
+
+        let target = Arc::new(MockProfileManager::new().track_calls(true));
+        context.profile_manager = target.clone();
+        let args = Args {
+            import: true,
+            from_bundle: Some(path.display().to_string()),
+            trusted_signer: Some("RECIPIENT".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        assert!(ImportCommand::new().execute(&args, &context).await.is_err());
+        assert_eq!(target.create_call_count(), 0);
+
+        let args = Args {
+            trusted_signer: Some("EXPORTER".to_string()),
+            ..args
+        };
+        ImportCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(target.read("work").unwrap(), Some(profile("work")));
+        assert!(target.was_called_with_create("work"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Field scope command implementation for git-setup-rs.
+//!
+//! `--set-field-scope <PROFILE> <GIT_KEY> <SCOPE>` records in the profile's
+//! `per_field_scope` that `apply --scope-per-profile` writes GIT_KEY at SCOPE
+//! instead of the profile's scope, e.g. `commit.gpgsign` only locally.
+
+use super::{apply::APPLIED_CONFIG_KEYS, Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::Scope,
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Command implementation for setting the scope of a single git config key.
+pub struct FieldScopeCommand;
+
+impl FieldScopeCommand {
+    /// Create a new FieldScopeCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for FieldScopeCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for FieldScopeCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let Some([name, git_key, scope]) = args.set_field_scope.as_deref() else {
+            return Err(GitSetupError::Git(
+                "Profile, git key and scope are required for --set-field-scope".to_string(),
+            ));
+        };
+        let scope: Scope = scope.parse()?;
+
+        // Only the keys apply writes can be scoped; store them in their usual spelling
+        let key = APPLIED_CONFIG_KEYS
+            .iter()
+            .find(|key| key.eq_ignore_ascii_case(git_key))
+            .ok_or_else(|| {
+                GitSetupError::Git(format!(
+                    "Unknown git key '{}'. Valid keys: {}",
+                    git_key,
+                    APPLIED_CONFIG_KEYS.join(", ")
+                ))
+            })?;
+
+        let mut profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+        profile.per_field_scope.retain(|existing, _| !existing.eq_ignore_ascii_case(key));
+        profile.per_field_scope.insert(key.to_string(), scope.clone());
+        context
+            .profile_manager
+            .update(name, profile)
+            .with_context(format!("while updating profile '{}'", name))?;
+
+        if !args.quiet {
+            println!("✓ Profile '{}' applies {} with {:?} scope", name, key, scope);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "set-field-scope"
+    }
+
+    fn description(&self) -> &'static str {
+        "Set the scope a profile applies a git config key with"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    /// Test that the scope is stored under the canonical key name.
+    #[tokio::test]
+    async fn test_set_field_scope() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            ..Default::default()
+        }]).track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            set_field_scope: Some(vec!["work".to_string(), "commit.gpgSign".to_string(), "Local".to_string()]),
+            quiet: true,
+            ..Default::default()
+        };
+        FieldScopeCommand::new().execute(&args, &context).await.unwrap();
+
+        let profile = profile_manager.read("work").unwrap().unwrap();
+        assert_eq!(profile.field_scope("commit.gpgsign"), Some(&Scope::Local));
+        assert!(profile.per_field_scope.contains_key("commit.gpgsign"));
+        assert!(profile_manager.was_called_with_update("work"));
+
+        let args = Args {
+            set_field_scope: Some(vec!["work".to_string(), "core.editor".to_string(), "local".to_string()]),
+            ..args
+        };
+        assert!(FieldScopeCommand::new().execute(&args, &context).await.is_err());
+        assert_eq!(profile_manager.update_call_count(), 1);
+    }
+}
@@ -0,0 +1,265 @@
+//! Generate-template command implementation for git-setup-rs.
+//!
+//! `--generate-template [KEY_TYPE]` prints a profile with every field relevant
+//! to the key type filled in with an example value. TOML and YAML templates
+//! annotate each field as `(required)` or `(optional)`; the TOML template is a
+//! `[[profiles]]` entry that can be pasted into the config file as is.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    config::types::KeyType,
+    error::{GitSetupError, Result},
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+/// One field of a profile template.
+struct TemplateField {
+    key: &'static str,
+    value: Value,
+    required: bool,
+    comment: &'static str,
+}
+
+impl TemplateField {
+    fn required(key: &'static str, value: Value, comment: &'static str) -> Self {
+        Self { key, value, required: true, comment }
+    }
+
+    fn optional(key: &'static str, value: Value, comment: &'static str) -> Self {
+        Self { key, value, required: false, comment }
+    }
+
+    /// `(required)` or `(optional)` annotation followed by the comment.
+    fn annotation(&self) -> String {
+        let kind = if self.required { "required" } else { "optional" };
+        format!("({}) {}", kind, self.comment)
+    }
+}
+
+/// Command implementation for printing a profile template.
+pub struct GenerateTemplateCommand;
+
+impl GenerateTemplateCommand {
+    /// Create a new GenerateTemplateCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Fields of a profile signing with `key_type`.
+    fn fields(key_type: &KeyType) -> Vec<TemplateField> {
+        let type_name = format!("{:?}", key_type).to_lowercase();
+        let mut fields = vec![
+            TemplateField::required("name", json!("work"), "Unique name of the profile"),
+            TemplateField::optional("description", json!("Repositories at Acme"), "What the profile is for"),
+            TemplateField::optional("git_user_name", json!("Jane Doe"), "Name recorded in commits"),
+            TemplateField::required("git_user_email", json!("jane@example.com"), "Email recorded in commits"),
+            TemplateField::required("key_type", json!(type_name), "Signing key type: ssh, gpg, x509 or gitsign"),
+            TemplateField::optional("scope", json!("local"), "Git config to write: local, global or system"),
+        ];
+
+        match key_type {
+            KeyType::Ssh => fields.extend([
+                TemplateField::optional(
+                    "signing_key",
+                    json!("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA... jane@example.com"),
+                    "Public key used to sign commits",
+                ),
+                TemplateField::optional(
+                    "ssh_key_source",
+                    json!("onepassword"),
+                    "Where the key comes from: onepassword, authorizedkeys or file",
+                ),
+                TemplateField::optional("ssh_key_path", json!("~/.ssh/id_ed25519"), "Path to your private key"),
+                TemplateField::optional("one_password", json!(true), "Load the key from 1Password"),
+                TemplateField::optional("vault_name", json!("Personal"), "1Password vault holding the key"),
+                TemplateField::optional("ssh_key_title", json!("Git Signing Key"), "Title of the 1Password item"),
+                TemplateField::optional(
+                    "allowed_signers",
+                    json!("~/.ssh/allowed_signers"),
+                    "Allowed signers file used to verify signatures",
+                ),
+                TemplateField::optional(
+                    "require_passphrase",
+                    json!(false),
+                    "Prompt for the private key's passphrase when applying",
+                ),
+            ]),
+            KeyType::Gpg => fields.extend([
+                TemplateField::optional(
+                    "signing_key",
+                    json!("0123456789ABCDEF0123456789ABCDEF01234567"),
+                    "Fingerprint or ID of the GPG key used to sign commits",
+                ),
+                TemplateField::optional("one_password", json!(true), "The GPG key is stored in 1Password"),
+                TemplateField::optional("vault_name", json!("Personal"), "1Password vault holding the key"),
+                TemplateField::optional("ssh_key_title", json!("Git GPG Key"), "Title of the 1Password item"),
+            ]),
+            KeyType::X509 => fields.push(TemplateField::optional(
+                "signing_key",
+                json!("jane@example.com"),
+                "Certificate ID used by gpgsm or smimesign",
+            )),
+            // gitsign signs keylessly through Sigstore and needs no key
+            KeyType::Gitsign => {}
+        }
+
+        fields
+    }
+
+    /// Commented-out examples of the auto-detection fields.
+    fn detection_examples() -> Vec<TemplateField> {
+        vec![
+            TemplateField::optional(
+                "match_patterns",
+                json!(["*/acme/*"]),
+                "Path patterns of repositories that use this profile",
+            ),
+            TemplateField::optional("repos", json!(["github.com/acme/*"]), "Remote URLs that use this profile"),
+            TemplateField::optional(
+                "include_if_dirs",
+                json!(["~/work/"]),
+                "Directories to configure with git's includeIf",
+            ),
+        ]
+    }
+
+    /// Render `fields` as `key<separator>value`, with aligned annotations.
+    ///
+    /// Values are written as JSON scalars and arrays, which TOML and YAML both accept.
+    fn render_lines(fields: &[TemplateField], commented: &[TemplateField], prefix: &str, separator: &str) -> Vec<String> {
+        let entries: Vec<(String, String)> = fields
+            .iter()
+            .map(|f| (String::new(), f))
+            .chain(commented.iter().map(|f| ("# ".to_string(), f)))
+            .map(|(comment, f)| (format!("{}{}{}{}", comment, f.key, separator, f.value), f.annotation()))
+            .collect();
+        let width = entries.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+
+        entries
+            .into_iter()
+            .map(|(line, annotation)| format!("{}{:<width$}  # {}", prefix, line, annotation, width = width))
+            .collect()
+    }
+
+    /// Render a template in `format`.
+    pub fn render(key_type: &KeyType, format: &OutputFormat, with_detection_examples: bool) -> Result<String> {
+        let fields = Self::fields(key_type);
+        let examples = if with_detection_examples {
+            Self::detection_examples()
+        } else {
+            Vec::new()
+        };
+        let header = format!("# git-setup profile template ({})", format!("{:?}", key_type).to_lowercase());
+
+        match format {
+            OutputFormat::Toml | OutputFormat::Tabular => {
+                let mut lines = vec![header, "[[profiles]]".to_string()];
+                lines.extend(Self::render_lines(&fields, &examples, "", " = "));
+                Ok(lines.join("\n"))
+            }
+            OutputFormat::Yaml => {
+                let mut lines = vec![header, "profiles:".to_string()];
+                let mut entries = Self::render_lines(&fields, &examples, "    ", ": ");
+                // The first field starts the list entry
+                if let Some(first) = entries.first_mut() {
+                    first.replace_range(..4, "  - ");
+                }
+                lines.extend(entries);
+                Ok(lines.join("\n"))
+            }
+            OutputFormat::Json => {
+                // JSON has no comments, so the detection examples become real values
+                let profile: serde_json::Map<String, Value> = fields
+                    .into_iter()
+                    .chain(examples)
+                    .map(|f| (f.key.to_string(), f.value))
+                    .collect();
+                Ok(serde_json::to_string_pretty(&profile)?)
+            }
+            OutputFormat::Csv => Err(GitSetupError::Git(
+                "Templates can be generated as toml, yaml or json, not csv".to_string(),
+            )),
+        }
+    }
+}
+
+impl Default for GenerateTemplateCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for GenerateTemplateCommand {
+    async fn execute(&self, args: &Args, _context: &CommandContext) -> Result<()> {
+        let key_type = args.generate_template.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Key type is required for --generate-template".to_string())
+        })?;
+
+        println!("{}", Self::render(key_type, &args.output, args.with_detection_examples)?);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "generate-template"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print a commented profile template"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::Profile;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Templates {
+        profiles: Vec<Profile>,
+    }
+
+    #[test]
+    fn test_toml_template_is_a_valid_profile() {
+        let toml = GenerateTemplateCommand::render(&KeyType::Ssh, &OutputFormat::Toml, true).unwrap();
+        assert!(toml.contains("# (optional) Path to your private key"));
+        assert!(toml.contains("# match_patterns = [\"*/acme/*\"]"));
+
+        let parsed: Templates = toml::from_str(&toml).unwrap();
+        let profile = &parsed.profiles[0];
+        assert_eq!(profile.name, "work");
+        assert_eq!(profile.key_type, KeyType::Ssh);
+        assert_eq!(profile.ssh_key_path.as_deref(), Some("~/.ssh/id_ed25519"));
+        // The detection examples are commented out
+        assert!(profile.match_patterns.is_empty());
+    }
+
+    #[test]
+    fn test_yaml_template_is_annotated() {
+        let yaml = GenerateTemplateCommand::render(&KeyType::Gpg, &OutputFormat::Yaml, false).unwrap();
+        assert!(yaml.contains("  - name: \"work\""));
+        assert!(yaml.contains("# (required) Email recorded in commits"));
+        assert!(!yaml.contains("ssh_key_path"));
+
+        let parsed: Templates = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.profiles[0].key_type, KeyType::Gpg);
+        assert!(parsed.profiles[0].one_password);
+    }
+
+    #[test]
+    fn test_json_template_includes_detection_examples() {
+        let json = GenerateTemplateCommand::render(&KeyType::Gitsign, &OutputFormat::Json, true).unwrap();
+        let profile: Profile = serde_json::from_str(&json).unwrap();
+        assert_eq!(profile.key_type, KeyType::Gitsign);
+        assert_eq!(profile.repos, vec!["github.com/acme/*"]);
+        assert!(profile.signing_key.is_none());
+    }
+
+    #[test]
+    fn test_csv_is_rejected() {
+        assert!(GenerateTemplateCommand::render(&KeyType::Ssh, &OutputFormat::Csv, false).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! GPG agent command implementation for git-setup-rs.
+//!
+//! `--gpg-agent start|stop` manages the GPG agent explicitly. Where the agent
+//! starts lazily, the first signing attempt fails with "cannot connect to
+//! agent"; `apply --wait-for-gpg-agent` uses [`wait_for_agent`] to poll the
+//! agent until it answers before configuring signing.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, GpgAgentAction},
+    error::{GitSetupError, Result, ResultExt},
+    external::gpg::GpgWrapper,
+};
+use async_trait::async_trait;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// How often the agent is pinged while waiting for it.
+const AGENT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Wait until the GPG agent answers, for at most `timeout`.
+///
+/// Unless `quiet`, `Waiting for GPG agent...` is shown with the elapsed time.
+pub async fn wait_for_agent(gpg: &dyn GpgWrapper, timeout: Duration, quiet: bool) -> Result<()> {
+    let start = Instant::now();
+    let poll = async {
+        loop {
+            if gpg.ping_agent()? {
+                return Ok(());
+            }
+            if !quiet {
+                eprint!("\rWaiting for GPG agent... {:.1}s", start.elapsed().as_secs_f64());
+                let _ = std::io::stderr().flush();
+            }
+            tokio::time::sleep(AGENT_POLL_INTERVAL).await;
+        }
+    };
+
+    let result = tokio::time::timeout(timeout, poll).await;
+    if !quiet && start.elapsed() >= AGENT_POLL_INTERVAL {
+        eprintln!();
+    }
+    result.map_err(|_| GitSetupError::ExternalCommand {
+        command: "gpg-connect-agent".to_string(),
+        error: format!("GPG agent did not respond within {}s", timeout.as_secs()),
+    })?
+}
+
+/// Command implementation for starting and stopping the GPG agent.
+pub struct GpgAgentCommand;
+
+impl GpgAgentCommand {
+    /// Create a new GpgAgentCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for GpgAgentCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for GpgAgentCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let action = args.gpg_agent.ok_or_else(|| {
+            GitSetupError::Git("Action is required for --gpg-agent".to_string())
+        })?;
+
+        match action {
+            GpgAgentAction::Start => {
+                context.gpg_wrapper.start_agent().with_context("while starting the GPG agent")?;
+                if !args.quiet {
+                    println!("✓ GPG agent started");
+                }
+            }
+            GpgAgentAction::Stop => {
+                context.gpg_wrapper.stop_agent().with_context("while stopping the GPG agent")?;
+                if !args.quiet {
+                    println!("✓ GPG agent stopped");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "gpg-agent"
+    }
+
+    fn description(&self) -> &'static str {
+        "Start or stop the GPG agent"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::tests::create_test_context, external::gpg::MockGpgWrapper};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_wait_for_agent_after_startup_delay() {
+        let gpg = MockGpgWrapper::new().with_agent_startup_delay(600);
+        wait_for_agent(&gpg, Duration::from_secs(5), true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_agent_timeout() {
+        let gpg = MockGpgWrapper::new().with_agent_startup_delay(60_000);
+        let result = wait_for_agent(&gpg, Duration::from_millis(700), true).await;
+        assert!(matches!(result, Err(GitSetupError::ExternalCommand { .. })));
+    }
+
+    /// Test that --gpg-agent stop and start control the agent.
+    #[tokio::test]
+    async fn test_gpg_agent_start_and_stop() {
+        let gpg = Arc::new(MockGpgWrapper::new());
+        let mut context = create_test_context();
+        context.gpg_wrapper = gpg.clone();
+
+        let args = Args {
+            gpg_agent: Some(GpgAgentAction::Stop),
+            quiet: true,
+            ..Default::default()
+        };
+        GpgAgentCommand::new().execute(&args, &context).await.unwrap();
+        assert!(!gpg.ping_agent().unwrap());
+
+        let args = Args {
+            gpg_agent: Some(GpgAgentAction::Start),
+            ..args
+        };
+        GpgAgentCommand::new().execute(&args, &context).await.unwrap();
+        assert!(gpg.ping_agent().unwrap());
+    }
+}
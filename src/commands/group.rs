@@ -0,0 +1,228 @@
+//! Profile group command implementation for git-setup-rs.
+//!
+//! Profiles are grouped by the tags in their `tags` field. `--tag-add
+//! <PROFILE> <TAG>` and `--tag-remove <PROFILE> <TAG>` edit the tags of a
+//! profile and `--list-tags` shows each tag with its profiles. Applying a
+//! whole group with `--tag <TAG>` is handled by the apply command.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+
+/// Command implementation for tagging profiles and listing the groups.
+pub struct GroupCommand;
+
+impl GroupCommand {
+    /// Create a new GroupCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn read_profile(name: &str, context: &CommandContext) -> Result<Profile> {
+        context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.to_string() })
+    }
+
+    /// Tags are listed comma separated, so they cannot contain commas or whitespace.
+    fn check_tag(tag: &str) -> Result<()> {
+        if tag.is_empty() || tag.contains(|c: char| c == ',' || c.is_whitespace()) {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("invalid tag '{}': tags must be non-empty without commas or whitespace", tag),
+            });
+        }
+        Ok(())
+    }
+
+    /// Add `tag` to the profile `name`, returning false if it already had it.
+    fn add_tag(&self, name: &str, tag: &str, context: &CommandContext) -> Result<bool> {
+        Self::check_tag(tag)?;
+        let mut profile = Self::read_profile(name, context)?;
+        if profile.tags.iter().any(|t| t == tag) {
+            return Ok(false);
+        }
+        profile.tags.push(tag.to_string());
+        context
+            .profile_manager
+            .update(name, profile)
+            .with_context(format!("while updating profile '{}'", name))?;
+        Ok(true)
+    }
+
+    /// Remove `tag` from the profile `name`.
+    fn remove_tag(&self, name: &str, tag: &str, context: &CommandContext) -> Result<()> {
+        let mut profile = Self::read_profile(name, context)?;
+        let count = profile.tags.len();
+        profile.tags.retain(|t| t != tag);
+        if profile.tags.len() == count {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("profile '{}' is not tagged '{}'", name, tag),
+            });
+        }
+        context
+            .profile_manager
+            .update(name, profile)
+            .with_context(format!("while updating profile '{}'", name))?;
+        Ok(())
+    }
+
+    /// The names of the profiles tagged with each tag, both sorted.
+    fn groups(context: &CommandContext) -> Result<BTreeMap<String, Vec<String>>> {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for profile in context.profile_manager.list()? {
+            for tag in &profile.tags {
+                groups.entry(tag.clone()).or_default().push(profile.name.clone());
+            }
+        }
+        for names in groups.values_mut() {
+            names.sort();
+        }
+        Ok(groups)
+    }
+}
+
+impl Default for GroupCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for GroupCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        if let Some([name, tag]) = args.tag_add.as_deref() {
+            let added = self.add_tag(name, tag, context)?;
+            if !args.quiet {
+                if added {
+                    println!("✓ Tagged profile '{}' with '{}'", name, tag);
+                } else {
+                    println!("Profile '{}' is already tagged '{}'", name, tag);
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some([name, tag]) = args.tag_remove.as_deref() {
+            self.remove_tag(name, tag, context)?;
+            if !args.quiet {
+                println!("✓ Removed tag '{}' from profile '{}'", tag, name);
+            }
+            return Ok(());
+        }
+
+        let groups = Self::groups(context)?;
+        if groups.is_empty() {
+            if !args.quiet {
+                println!("No profiles are tagged; add a tag with --tag-add <PROFILE> <TAG>");
+            }
+            return Ok(());
+        }
+        for (tag, names) in groups {
+            println!("{}: {}", tag, names.join(", "));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "group"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tag profiles and list the profile groups"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    fn test_context() -> (CommandContext, Arc<MockProfileManager>) {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                ..Default::default()
+            },
+            Profile {
+                name: "client".to_string(),
+                git_user_email: "jane@client.com".to_string(),
+                tags: vec!["client-a".to_string()],
+                ..Default::default()
+            },
+        ])
+        .track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        (context, profile_manager)
+    }
+
+    fn args(tag_add: Option<[&str; 2]>, tag_remove: Option<[&str; 2]>) -> Args {
+        let pair = |pair: [&str; 2]| pair.iter().map(|s| s.to_string()).collect();
+        Args {
+            tag_add: tag_add.map(pair),
+            tag_remove: tag_remove.map(pair),
+            list_tags: tag_add.is_none() && tag_remove.is_none(),
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tag_add_and_remove() {
+        let (context, profile_manager) = test_context();
+        let command = GroupCommand::new();
+
+        command.execute(&args(Some(["work", "client-a"]), None), &context).await.unwrap();
+        // Adding a tag twice keeps a single copy
+        command.execute(&args(Some(["work", "client-a"]), None), &context).await.unwrap();
+        assert_eq!(profile_manager.read("work").unwrap().unwrap().tags, vec!["client-a".to_string()]);
+        assert_eq!(profile_manager.update_call_count(), 1);
+
+        let tagged: Vec<String> = profile_manager
+            .list_by_tag("client-a")
+            .unwrap()
+            .into_iter()
+            .map(|profile| profile.name)
+            .collect();
+        assert_eq!(tagged.len(), 2);
+        assert!(tagged.contains(&"work".to_string()) && tagged.contains(&"client".to_string()));
+        assert_eq!(
+            GroupCommand::groups(&context).unwrap(),
+            BTreeMap::from([("client-a".to_string(), vec!["client".to_string(), "work".to_string()])])
+        );
+
+        command.execute(&args(None, Some(["work", "client-a"])), &context).await.unwrap();
+        assert!(profile_manager.read("work").unwrap().unwrap().tags.is_empty());
+        assert_eq!(profile_manager.list_by_tag("client-a").unwrap().len(), 1);
+        assert_eq!(profile_manager.update_call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tag_errors() {
+        let (context, profile_manager) = test_context();
+        let command = GroupCommand::new();
+
+        let result = command.execute(&args(None, Some(["work", "client-a"])), &context).await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+
+        let result = command.execute(&args(Some(["work", "client a"]), None), &context).await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+
+        let result = command.execute(&args(Some(["missing", "client-a"]), None), &context).await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+
+        assert_eq!(profile_manager.update_call_count(), 0);
+        command.execute(&args(None, None), &context).await.unwrap();
+    }
+}
@@ -10,6 +10,43 @@ use super::{
     delete::DeleteCommand,
     edit::EditCommand,
     import::ImportCommand,
+    export::ExportCommand,
+    import_copilot::ImportCopilotCommand,
+    undo::{RedoCommand, UndoCommand},
+    check_unused::CheckUnusedCommand,
+    env_inject::{EnvInjectCommand, ShellInstallCommand},
+    keygen::KeygenCommand,
+    key_cleanup::KeyCleanupCommand,
+    gpg_agent::GpgAgentCommand,
+    detect::DetectCommand,
+    config_dump::ConfigDumpCommand,
+    diff_applied::DiffAppliedCommand,
+    diff::DiffCommand,
+    validate::ValidateCommand,
+    rotate_key::RotateKeyCommand,
+    group::GroupCommand,
+    field_scope::FieldScopeCommand,
+    hooks::{HooksInstallCommand, HooksUninstallCommand},
+    validate_schema::ValidateSchemaCommand,
+    allowed_signers::AllowedSignersCommand,
+    capabilities::ListCapabilitiesCommand,
+    duplicate::DuplicateCommand,
+    merge::MergeCommand,
+    rename::RenameCommand,
+    status::StatusCommand,
+    duplicate_check::DuplicateCheckCommand,
+    check_keys::CheckKeysCommand,
+    import_op_gpg::ImportOpGpgCommand,
+    bulk_update::BulkUpdateCommand,
+    archive::{ArchiveCommand, UnarchiveCommand},
+    generate_template::GenerateTemplateCommand,
+    stats::StatsCommand,
+    health_score::HealthScoreCommand,
+    push_config::PushConfigCommand,
+    sort_by::SortByCommand,
+    profiles_md::ProfilesMdCommand,
+    snippets::SnippetsListCommand,
+    completions::{CompleteProfileNamesCommand, CompletionsCommand, COMPLETE_PROFILE_NAMES},
 };
 use crate::{
     cli::Args,
@@ -37,7 +74,11 @@ impl CommandHandler {
         }
 
         // Route to appropriate command based on args
-        let command: Arc<dyn Command> = if args.list {
+        let command: Arc<dyn Command> = if args.profile.as_deref() == Some(COMPLETE_PROFILE_NAMES) {
+            Arc::new(CompleteProfileNamesCommand::new())
+        } else if args.completions.is_some() {
+            Arc::new(CompletionsCommand::new())
+        } else if args.list || args.list_archived {
             Arc::new(ListCommand::new())
         } else if args.add.is_some() {
             Arc::new(AddCommand::new())
@@ -45,9 +86,94 @@ impl CommandHandler {
             Arc::new(DeleteCommand::new())
         } else if args.edit.is_some() {
             Arc::new(EditCommand::new())
+        } else if args.archive.is_some() {
+            Arc::new(ArchiveCommand::new())
+        } else if args.unarchive.is_some() {
+            Arc::new(UnarchiveCommand::new())
+        } else if args.from_github_copilot {
+            Arc::new(ImportCopilotCommand::new())
         } else if args.import {
             Arc::new(ImportCommand::new())
-        } else if args.profile.is_some() {
+        } else if args.export.is_some() {
+            Arc::new(ExportCommand::new())
+        } else if args.env_inject {
+            Arc::new(EnvInjectCommand::new())
+        } else if args.shell_install.is_some() {
+            Arc::new(ShellInstallCommand::new())
+        } else if args.detect {
+            Arc::new(DetectCommand::new())
+        } else if args.keygen {
+            Arc::new(KeygenCommand::new())
+        } else if args.cleanup_key.is_some() {
+            Arc::new(KeyCleanupCommand::new())
+        } else if args.gpg_agent.is_some() {
+            Arc::new(GpgAgentCommand::new())
+        } else if args.config_dump {
+            Arc::new(ConfigDumpCommand::new())
+        } else if args.diff_from_applied.is_some() {
+            Arc::new(DiffAppliedCommand::new())
+        } else if args.set_field_scope.is_some() {
+            Arc::new(FieldScopeCommand::new())
+        } else if args.install_hooks.is_some() {
+            Arc::new(HooksInstallCommand::new())
+        } else if args.uninstall_hooks {
+            Arc::new(HooksUninstallCommand::new())
+        } else if args.validate_against_schema.is_some() {
+            Arc::new(ValidateSchemaCommand::new())
+        } else if args.update_allowed_signers && args.profile.is_none() {
+            Arc::new(AllowedSignersCommand::new())
+        } else if args.list_capabilities.is_some() {
+            Arc::new(ListCapabilitiesCommand::new())
+        } else if args.duplicate.is_some() {
+            Arc::new(DuplicateCommand::new())
+        } else if args.merge_profiles.is_some() {
+            Arc::new(MergeCommand::new())
+        } else if args.rename.is_some() {
+            Arc::new(RenameCommand::new())
+        } else if args.diff.is_some() {
+            Arc::new(DiffCommand::new())
+        } else if args.validate.is_some() {
+            Arc::new(ValidateCommand::new())
+        } else if args.rotate_key.is_some() {
+            Arc::new(RotateKeyCommand::new())
+        } else if args.tag_add.is_some() || args.tag_remove.is_some() || args.list_tags {
+            Arc::new(GroupCommand::new())
+        } else if args.import_op_gpg.is_some() {
+            Arc::new(ImportOpGpgCommand::new())
+        } else if args.generate_template.is_some() {
+            Arc::new(GenerateTemplateCommand::new())
+        } else if args.bulk_update {
+            Arc::new(BulkUpdateCommand::new())
+        } else if args.sort_by.is_some() {
+            Arc::new(SortByCommand::new())
+        } else if args.profiles_md.is_some() {
+            Arc::new(ProfilesMdCommand::new())
+        } else if args.list_snippets {
+            Arc::new(SnippetsListCommand::new())
+        } else if args.status {
+            Arc::new(StatusCommand::new())
+        } else if args.stats {
+            Arc::new(StatsCommand::new())
+        } else if args.health_score.is_some() {
+            Arc::new(HealthScoreCommand::new())
+        } else if args.push_config {
+            Arc::new(PushConfigCommand::new())
+        } else if args.duplicate_check {
+            Arc::new(DuplicateCheckCommand::new())
+        } else if args.check_keys {
+            Arc::new(CheckKeysCommand::new())
+        } else if args.check_unused {
+            Arc::new(CheckUnusedCommand::new())
+        } else if args.undo {
+            Arc::new(UndoCommand::new())
+        } else if args.redo {
+            Arc::new(RedoCommand::new())
+        } else if args.profile.is_some()
+            || args.inherit_from.is_some()
+            || args.global_then_local.is_some()
+            || args.watch
+            || args.tag.is_some()
+        {
             // If a profile name is provided without other flags, apply it
             Arc::new(ApplyCommand::new())
         } else {
@@ -97,6 +223,12 @@ impl CommandHandlerBuilder {
         self
     }
 
+    /// Set the Bitwarden wrapper dependency.
+    pub fn with_bitwarden_wrapper(mut self, bitwarden_wrapper: Arc<dyn crate::external::bitwarden::BitwardenWrapper>) -> Self {
+        self.context_builder = self.context_builder.with_bitwarden_wrapper(bitwarden_wrapper);
+        self
+    }
+
     /// Set the GPG wrapper dependency.
     pub fn with_gpg_wrapper(mut self, gpg_wrapper: Arc<dyn crate::external::gpg::GpgWrapper>) -> Self {
         self.context_builder = self.context_builder.with_gpg_wrapper(gpg_wrapper);
@@ -115,6 +247,12 @@ impl CommandHandlerBuilder {
         self
     }
 
+    /// Set the token cancelling long-running commands.
+    pub fn with_cancellation_token(mut self, cancellation_token: tokio_util::sync::CancellationToken) -> Self {
+        self.context_builder = self.context_builder.with_cancellation_token(cancellation_token);
+        self
+    }
+
     /// Build the CommandHandler.
     pub fn build(self) -> Result<CommandHandler> {
         let context = self.context_builder.build()?;
@@ -158,6 +296,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -193,9 +340,9 @@ mod tests {
             create_test_profile("personal", "personal@example.com"),
         ];
         
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         
         let handler = CommandHandler::new(context);
         
@@ -208,6 +355,7 @@ mod tests {
         
         let result = handler.execute(&args).await;
         assert!(result.is_ok());
+        assert_eq!(profile_manager.list_call_count(), 1);
     }
 
     /// Test add command routing.
@@ -230,9 +378,9 @@ mod tests {
     #[tokio::test]
     async fn test_delete_command_routing() {
         let profile = create_test_profile("test-profile", "test@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         
         let handler = CommandHandler::new(context);
         
@@ -244,15 +392,16 @@ mod tests {
         
         let result = handler.execute(&args).await;
         assert!(result.is_ok());
+        assert!(profile_manager.was_called_with_delete("test-profile"));
     }
 
     /// Test edit command routing.
     #[tokio::test]
     async fn test_edit_command_routing() {
         let profile = create_test_profile("test-profile", "test@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         
         let handler = CommandHandler::new(context);
         
@@ -264,6 +413,7 @@ mod tests {
         
         let result = handler.execute(&args).await;
         assert!(result.is_ok());
+        assert!(profile_manager.was_called_with_read("test-profile"));
     }
 
     /// Test import command routing.
@@ -286,9 +436,9 @@ mod tests {
     #[tokio::test]
     async fn test_apply_command_routing() {
         let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         
         let handler = CommandHandler::new(context);
         
@@ -300,6 +450,7 @@ mod tests {
         
         let result = handler.execute(&args).await;
         assert!(result.is_ok());
+        assert!(profile_manager.was_called_with_read("work"));
     }
 
     /// Test default command routing (should list profiles).
@@ -320,14 +471,15 @@ mod tests {
     /// Test CommandHandlerBuilder.
     #[test]
     fn test_command_handler_builder() {
-        let profile_manager = Arc::new(MockProfileManager::new());
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         
         let result = CommandHandlerBuilder::new()
-            .with_profile_manager(profile_manager)
+            .with_profile_manager(profile_manager.clone())
             .build();
         
         // Should fail because other dependencies are missing
         assert!(result.is_err());
+        assert!(profile_manager.calls().is_empty());
     }
 
     /// Test CommandHandlerBuilder with all dependencies.
@@ -339,6 +491,7 @@ mod tests {
             .with_profile_manager(context.profile_manager)
             .with_git_wrapper(context.git_wrapper)
             .with_onepassword_wrapper(context.onepassword_wrapper)
+            .with_bitwarden_wrapper(context.bitwarden_wrapper)
             .with_gpg_wrapper(context.gpg_wrapper)
             .with_profile_detector(context.profile_detector)
             .with_fuzzy_matcher(context.fuzzy_matcher)
@@ -355,9 +508,9 @@ mod tests {
             create_test_profile("personal", "personal@example.com"),
         ];
         
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         
         let handler = CommandHandler::new(context);
         
@@ -378,6 +531,7 @@ mod tests {
         };
         let result = handler.execute(&args).await;
         assert!(result.is_ok());
+        assert!(profile_manager.was_called_with_create("new-profile"));
         
         // Test apply command
         let args = Args {
@@ -393,9 +547,9 @@ mod tests {
     #[tokio::test]
     async fn test_command_priority() {
         let profile = create_test_profile("work", "work@example.com");
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         
         let handler = CommandHandler::new(context);
         
@@ -409,5 +563,7 @@ mod tests {
         
         let result = handler.execute(&args).await;
         assert!(result.is_ok());
+        assert_eq!(profile_manager.list_call_count(), 1);
+        assert!(!profile_manager.was_called_with_read("work"));
     }
 }
\ No newline at end of file
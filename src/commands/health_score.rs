@@ -0,0 +1,213 @@
+//! Health-score command implementation for git-setup-rs.
+//!
+//! `--health-score <NAME>` rates a profile from 0 to 100 and grades it A to F
+//! based on whether its key is available, it can sign, its key is not about
+//! to expire and it is in use. `--health-score --all` rates every profile,
+//! best first.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+    profile::health::{HealthScore, HealthScoreCalculator},
+};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::cmp::Reverse;
+
+/// Health score of a named profile.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileHealth {
+    pub name: String,
+    #[serde(flatten)]
+    pub health: HealthScore,
+}
+
+/// Command implementation for profile health scores.
+pub struct HealthScoreCommand;
+
+impl HealthScoreCommand {
+    /// Create a new HealthScoreCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Score `selected` against all `profiles`, best score first.
+    fn score(selected: &[Profile], profiles: &[Profile], context: &CommandContext) -> Result<Vec<ProfileHealth>> {
+        let activations = context
+            .profile_manager
+            .activation_history()
+            .with_context("while loading the activation history")?;
+        let calculator = HealthScoreCalculator::new(&activations, profiles);
+
+        let mut results = selected
+            .iter()
+            .map(|profile| {
+                Ok(ProfileHealth {
+                    name: profile.name.clone(),
+                    health: calculator.calculate(
+                        profile,
+                        context.git_wrapper.as_ref(),
+                        context.gpg_wrapper.as_ref(),
+                        context.onepassword_wrapper.as_ref(),
+                    )?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Stable sort keeps list order between profiles with the same score
+        results.sort_by_key(|result| Reverse(result.health.score));
+        Ok(results)
+    }
+
+    /// Render a single profile's score with its grade and every check.
+    pub fn format_detail(result: &ProfileHealth) -> String {
+        let mut lines = vec![
+            format!("Profile: {}", result.name),
+            format!("Grade:   {}  ({}/100)", result.health.grade, result.health.score),
+            String::new(),
+        ];
+        for (check, points) in &result.health.components {
+            let mark = if *points > 0 { "✓" } else { "✗" };
+            lines.push(format!("  {} {:<24} {:>3}", mark, check, points));
+        }
+        lines.join("\n")
+    }
+
+    /// Render the scores of several profiles as a table.
+    pub fn format_summary(results: &[ProfileHealth]) -> String {
+        let mut lines = vec![format!("{:<5} {:>5}  {}", "Grade", "Score", "Profile")];
+        for result in results {
+            lines.push(format!(
+                "{:<5} {:>5}  {}",
+                result.health.grade, result.health.score, result.name
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Default for HealthScoreCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for HealthScoreCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let profiles = context.profile_manager.list().with_context("while loading profiles")?;
+        let selected = match (args.health_score.as_ref().and_then(|name| name.as_deref()), args.all) {
+            (Some(_), true) => {
+                return Err(GitSetupError::Git(
+                    "Give either a profile name or --all to --health-score, not both".to_string(),
+                ));
+            }
+            (Some(name), false) => vec![
+                context
+                    .profile_manager
+                    .read(name)?
+                    .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.to_string() })?,
+            ],
+            (None, true) => profiles.clone(),
+            (None, false) => {
+                return Err(GitSetupError::Git(
+                    "Profile name or --all is required for --health-score".to_string(),
+                ));
+            }
+        };
+
+        let results = Self::score(&selected, &profiles, context)?;
+        let output = match args.output {
+            OutputFormat::Tabular if args.all => Self::format_summary(&results),
+            OutputFormat::Tabular => results.iter().map(Self::format_detail).collect::<Vec<_>>().join("\n"),
+            OutputFormat::Json => serde_json::to_string_pretty(&results)?,
+            OutputFormat::Yaml => serde_yaml::to_string(&results)?,
+            OutputFormat::Toml | OutputFormat::Csv => {
+                return Err(GitSetupError::Git(
+                    "Health scores can be shown as tabular, json or yaml output".to_string(),
+                ));
+            }
+        };
+        println!("{}", output);
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "health-score"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rate the health of profiles from 0 to 100"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::KeyType,
+        profile::mock::MockProfileManager,
+    };
+    use std::sync::Arc;
+
+    fn context() -> CommandContext {
+        let manager = MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "described".to_string(),
+                git_user_email: "a@example.com".to_string(),
+                key_type: KeyType::Gitsign,
+                description: Some("Open source work".to_string()),
+                ..Default::default()
+            },
+            Profile {
+                name: "plain".to_string(),
+                git_user_email: "b@example.com".to_string(),
+                key_type: KeyType::Gitsign,
+                ..Default::default()
+            },
+        ]);
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(manager);
+        context
+    }
+
+    #[test]
+    fn test_scores_sorted_best_first() {
+        let context = context();
+        let profiles = context.profile_manager.list().unwrap();
+
+        let results = HealthScoreCommand::score(&profiles, &profiles, &context).unwrap();
+        assert_eq!(results[0].name, "described");
+        assert_eq!(results[0].health.score, results[1].health.score + 5);
+
+        let summary = HealthScoreCommand::format_summary(&results);
+        assert!(summary.lines().nth(1).unwrap().ends_with("described"));
+    }
+
+    #[tokio::test]
+    async fn test_health_score_requires_name_or_all() {
+        let context = context();
+        let args = Args {
+            health_score: Some(None),
+            quiet: true,
+            ..Default::default()
+        };
+        assert!(HealthScoreCommand::new().execute(&args, &context).await.is_err());
+
+        let args = Args {
+            health_score: Some(Some("missing".to_string())),
+            ..args
+        };
+        assert!(HealthScoreCommand::new().execute(&args, &context).await.is_err());
+
+        let args = Args {
+            health_score: Some(None),
+            all: true,
+            ..args
+        };
+        assert!(HealthScoreCommand::new().execute(&args, &context).await.is_ok());
+    }
+}
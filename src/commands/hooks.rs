@@ -0,0 +1,200 @@
+//! Hooks install and uninstall command implementations for git-setup-rs.
+//!
+//! `--install-hooks <PROFILE>` points git at the profile's `git_hooks_dir`
+//! by setting `core.hooksPath`; `--uninstall-hooks` unsets it again. Both
+//! honor `--global`, `--system` and `--local`.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{GitSetupError, Result, ResultExt},
+    external::{git::GitConfigScope, hooks::HooksManager},
+};
+use async_trait::async_trait;
+
+/// Scope chosen with `--global`, `--system` or `--local`, if any.
+fn flag_scope(args: &Args) -> Option<GitConfigScope> {
+    if args.global {
+        Some(GitConfigScope::Global)
+    } else if args.system {
+        Some(GitConfigScope::System)
+    } else if args.local {
+        Some(GitConfigScope::Local)
+    } else {
+        None
+    }
+}
+
+/// Command implementation for installing a profile's git hooks.
+pub struct HooksInstallCommand;
+
+impl HooksInstallCommand {
+    /// Create a new HooksInstallCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for HooksInstallCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for HooksInstallCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.install_hooks.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --install-hooks".to_string())
+        })?;
+        let profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+        let hooks_dir = profile.git_hooks_dir.as_ref().ok_or_else(|| GitSetupError::InvalidProfile {
+            reason: format!("profile '{}' has no git_hooks_dir", name),
+        })?;
+
+        // Without a scope flag, install at the scope applying the profile would use
+        let scope = match flag_scope(args) {
+            Some(scope) => scope,
+            None => {
+                let in_repo = context.git_wrapper.is_inside_work_tree()?;
+                profile.effective_scope(in_repo).into()
+            }
+        };
+
+        HooksManager::install(hooks_dir, scope.clone(), context.git_wrapper.as_ref())
+            .with_context(format!("while installing the git hooks of profile '{}'", name))?;
+
+        if !args.quiet {
+            println!("✓ Installed hooks from {} ({:?} scope)", hooks_dir.display(), scope);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "install-hooks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Point git at a profile's hooks directory"
+    }
+}
+
+/// Command implementation for uninstalling git hooks.
+pub struct HooksUninstallCommand;
+
+impl HooksUninstallCommand {
+    /// Create a new HooksUninstallCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for HooksUninstallCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for HooksUninstallCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        // Without a scope flag, uninstall from the repository when inside one
+        let scope = match flag_scope(args) {
+            Some(scope) => scope,
+            None if context.git_wrapper.is_inside_work_tree()? => GitConfigScope::Local,
+            None => GitConfigScope::Global,
+        };
+
+        HooksManager::uninstall(scope.clone(), context.git_wrapper.as_ref())
+            .with_context("while uninstalling git hooks")?;
+
+        if !args.quiet {
+            println!("✓ Uninstalled hooks ({:?} scope)", scope);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "uninstall-hooks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unset core.hooksPath so git uses the repository's own hooks"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        external::{
+            git::{GitWrapper, MockGitWrapper},
+            hooks::HOOKS_PATH_KEY,
+        },
+        profile::mock::MockProfileManager,
+    };
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Test that hooks are installed and uninstalled at the requested scope.
+    #[tokio::test]
+    async fn test_install_and_uninstall_hooks() {
+        let hooks_dir = TempDir::new().unwrap();
+        let git = Arc::new(MockGitWrapper::new());
+        let mut context = create_test_context();
+        context.git_wrapper = git.clone();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                git_hooks_dir: Some(hooks_dir.path().to_path_buf()),
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
+                ..Default::default()
+            },
+            Profile {
+                name: "personal".to_string(),
+                git_user_email: "jane@home.net".to_string(),
+                ..Default::default()
+            },
+        ]));
+
+        let args = Args {
+            install_hooks: Some("work".to_string()),
+            local: true,
+            quiet: true,
+            ..Default::default()
+        };
+        HooksInstallCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(
+            git.get_config(HOOKS_PATH_KEY, Some(GitConfigScope::Local)).unwrap(),
+            Some(hooks_dir.path().to_string_lossy().to_string())
+        );
+
+        let args = Args {
+            uninstall_hooks: true,
+            local: true,
+            quiet: true,
+            ..Default::default()
+        };
+        HooksUninstallCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(git.get_config(HOOKS_PATH_KEY, Some(GitConfigScope::Local)).unwrap(), None);
+
+        // A profile without a hooks directory cannot be installed
+        let args = Args {
+            install_hooks: Some("personal".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        assert!(HooksInstallCommand::new().execute(&args, &context).await.is_err());
+    }
+}
@@ -1,14 +1,26 @@
 //! Import command implementation for git-setup-rs.
 //!
-//! This command imports profiles from 1Password agent.toml configuration.
+//! This command imports profiles from 1Password agent.toml configuration,
+//! with `--from-bundle` from a bundle written by `--export`, with
+//! `--from-toml` from the output of `--list --output toml`, or with
+//! `--from-gitconfig` from the identities of an existing gitconfig file.
 
 use super::{Command, CommandContext};
 use crate::{
     cli::Args,
     config::types::{Profile, KeyType, Scope},
-    error::{GitSetupError, Result},
+    error::{GitSetupError, Result, ResultExt},
+    external::{FilesystemGitWrapper, GitWrapper},
+    output::TomlFormatter,
+    platform::{PlatformPaths, SystemPlatform},
+    profile::{bundle::ProfileBundle, transaction::TransactionalProfileManager, ProfileManager},
 };
 use async_trait::async_trait;
+use std::path::Path;
+use tokio_util::sync::CancellationToken;
+
+/// Name of the profile created from the identity set outside any includeIf file.
+const GITCONFIG_PROFILE_NAME: &str = "global";
 
 /// Command implementation for importing profiles from 1Password.
 pub struct ImportCommand;
@@ -45,6 +57,15 @@ impl ImportCommand {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             };
             
             profiles.push(profile);
@@ -53,33 +74,205 @@ impl ImportCommand {
         Ok(profiles)
     }
 
+    /// Read the profiles of a bundle, verifying its signature and signer before they are parsed.
+    fn import_from_bundle(
+        &self,
+        path: &str,
+        trusted_signer: Option<&str>,
+        context: &CommandContext,
+    ) -> Result<Vec<Profile>> {
+        let json = std::fs::read_to_string(path).with_context(format!("while reading '{}'", path))?;
+        ProfileBundle::from_json(&json)?.profiles(context.gpg_wrapper.as_ref(), trusted_signer)
+    }
+
+    /// Read the `[[profile]]` tables of a TOML file.
+    fn import_from_toml(&self, path: &str) -> Result<Vec<Profile>> {
+        let content = std::fs::read_to_string(path).with_context(format!("while reading '{}'", path))?;
+        TomlFormatter::parse_profiles(&content)
+    }
+
+    /// Create a profile for each distinct identity of the gitconfig at `path`:
+    /// its own `[user]` section and those of the files its `[includeIf "gitdir:..."]`
+    /// sections include, which become the profile's `include_if_dirs`.
+    ///
+    /// Returns the profiles and warnings about settings that could not be imported.
+    fn import_from_gitconfig(&self, path: &str) -> Result<(Vec<Profile>, Vec<String>)> {
+        let path = Path::new(path);
+        std::fs::metadata(path).with_context(format!("while reading '{}'", path.display()))?;
+        let git = FilesystemGitWrapper::new(path);
+        let mut profiles: Vec<Profile> = Vec::new();
+        let mut warnings = Vec::new();
+
+        let mut sources = vec![(path.to_path_buf(), None)];
+        // Visit the includeIf sections in file order
+        let mut include_keys: Vec<(u32, String)> = git
+            .get_all_config_with_origin()?
+            .into_iter()
+            .filter(|(key, _)| key.starts_with("includeif.") && key.ends_with(".path"))
+            .map(|(key, (_, origin))| (origin.line, key))
+            .collect();
+        include_keys.sort();
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        for (_, key) in include_keys {
+            let condition = &key["includeif.".len()..key.len() - ".path".len()];
+            let dir = match condition.split_once(':') {
+                Some(("gitdir" | "gitdir/i", dir)) => Some(dir.to_string()),
+                _ => {
+                    warnings.push(format!(
+                        "includeIf condition '{}' is not a gitdir; its identity is imported without directories",
+                        condition
+                    ));
+                    None
+                }
+            };
+            for include in git.get_all_values(&key)? {
+                let include = SystemPlatform.expand_path(&include);
+                sources.push((base_dir.join(include), dir.clone()));
+            }
+        }
+
+        for (source, dir) in sources {
+            if !source.exists() {
+                warnings.push(format!("included file '{}' does not exist", source.display()));
+                continue;
+            }
+            let source_git = FilesystemGitWrapper::new(&source);
+            let Some(email) = source_git.get_config("user.email", None)? else {
+                continue;
+            };
+            let name = source_git.get_config("user.name", None)?;
+
+            if let Some(existing) = profiles
+                .iter_mut()
+                .find(|profile| profile.git_user_email == email && profile.git_user_name == name)
+            {
+                existing.include_if_dirs.extend(dir);
+                continue;
+            }
+
+            let base_name = dir.as_deref().map_or(GITCONFIG_PROFILE_NAME.to_string(), Self::name_for_dir);
+            let mut profile = Profile {
+                name: Self::unique_name(&base_name, &profiles),
+                git_user_name: name,
+                git_user_email: email,
+                include_if_dirs: dir.into_iter().collect(),
+                ..Default::default()
+            };
+            if let Some(warning) = Self::apply_signing_info(&mut profile, &source_git)? {
+                warnings.push(format!("{}: {}", source.display(), warning));
+            }
+            profiles.push(profile);
+        }
+
+        Ok((profiles, warnings))
+    }
+
+    /// Set the key type and signing settings of `profile` from the signing
+    /// config of `git`, returning a warning if they cannot be mapped.
+    fn apply_signing_info(profile: &mut Profile, git: &FilesystemGitWrapper) -> Result<Option<String>> {
+        let info = git.get_signing_info(None)?;
+        profile.key_type = match info.format.as_deref().map(str::to_lowercase).as_deref() {
+            Some("ssh") => KeyType::Ssh,
+            None | Some("openpgp") if info.signing_key.is_some() => KeyType::Gpg,
+            None | Some("openpgp") => {
+                return Ok(Some(format!(
+                    "identity '{}' has no signing key; add one with --edit",
+                    profile.git_user_email
+                )));
+            }
+            Some("x509") if info.x509_program.as_deref() == Some("gitsign") => KeyType::Gitsign,
+            Some("x509") => KeyType::X509,
+            Some(format) => {
+                return Ok(Some(format!(
+                    "unknown signing format '{}' for '{}'; imported without signing",
+                    format, profile.git_user_email
+                )));
+            }
+        };
+        profile.signing_key = info.signing_key;
+        profile.allowed_signers = info.allowed_signers_file;
+        Ok(None)
+    }
+
+    /// Profile name for the identity used in `dir`, from its last path component.
+    fn name_for_dir(dir: &str) -> String {
+        let component = dir
+            .trim_end_matches(['/', '*'])
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default();
+        let name: String = component
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+            .collect();
+        let name = name.trim_matches('-');
+        if name.is_empty() {
+            GITCONFIG_PROFILE_NAME.to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// `base`, suffixed with a number if one of `profiles` already has that name.
+    fn unique_name(base: &str, profiles: &[Profile]) -> String {
+        let taken = |name: &str| profiles.iter().any(|profile| profile.name == name);
+        if !taken(base) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{}-{}", base, n))
+            .find(|name| !taken(name))
+            .unwrap_or_default()
+    }
+
     /// Check if a profile with the same name already exists.
-    async fn profile_exists(&self, name: &str, context: &CommandContext) -> Result<bool> {
-        match context.profile_manager.read(name)? {
+    fn profile_exists(manager: &dyn ProfileManager, name: &str) -> Result<bool> {
+        match manager.read(name)? {
             Some(_) => Ok(true),
             None => Ok(false),
         }
     }
 
-    /// Save imported profiles, handling conflicts.
-    async fn save_profiles(&self, profiles: Vec<Profile>, context: &CommandContext) -> Result<(usize, usize)> {
+    /// Save imported profiles into `manager`, skipping names that already exist.
+    ///
+    /// Stops before the next profile once `cancellation_token` is cancelled.
+    fn save_profiles(
+        manager: &dyn ProfileManager,
+        profiles: &[Profile],
+        cancellation_token: &CancellationToken,
+    ) -> Result<(usize, usize)> {
         let mut created = 0;
         let mut skipped = 0;
-        
+
         for profile in profiles {
-            if self.profile_exists(&profile.name, context).await? {
+            if cancellation_token.is_cancelled() {
+                break;
+            }
+            if Self::profile_exists(manager, &profile.name)? {
                 println!("Skipping existing profile: {}", profile.name);
                 skipped += 1;
             } else {
-                context.profile_manager.write(&profile)?;
+                manager.create(profile.clone())?;
                 println!("Imported profile: {}", profile.name);
                 created += 1;
             }
         }
-        
+
         Ok((created, skipped))
     }
 
+    /// Save imported profiles as one transaction, so a failure keeps none of them.
+    ///
+    /// The transaction is not interrupted by cancellation: it stores every profile or none.
+    fn save_profiles_atomic(&self, profiles: &[Profile], context: &CommandContext) -> Result<(usize, usize)> {
+        let mut counts = (0, 0);
+        TransactionalProfileManager::new(context.profile_manager.clone()).transaction(|manager| {
+            counts = Self::save_profiles(manager, profiles, &CancellationToken::new())?;
+            Ok(())
+        })?;
+        Ok(counts)
+    }
+
     /// Print import summary.
     fn print_import_summary(&self, created: usize, skipped: usize) {
         println!("\n✓ Import completed");
@@ -103,20 +296,61 @@ impl Command for ImportCommand {
             return Err(GitSetupError::Git("Import flag is required for import command".to_string()));
         }
 
-        println!("Importing profiles from 1Password...");
+        let profiles = if let Some(path) = &args.from_bundle {
+            self.import_from_bundle(path, args.trusted_signer.as_deref(), context)
+                .with_context(format!("while importing profiles from '{}'", path))?
+        } else if let Some(path) = &args.from_toml {
+            self.import_from_toml(path)
+                .with_context(format!("while importing profiles from '{}'", path))?
+        } else if let Some(path) = &args.from_gitconfig {
+            let (profiles, warnings) = self
+                .import_from_gitconfig(path)
+                .with_context(format!("while importing profiles from '{}'", path))?;
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            if profiles.is_empty() {
+                println!("No user.email found in '{}'.", path);
+                return Ok(());
+            }
+            profiles
+        } else {
+            println!("Importing profiles from 1Password...");
+
+            // Import profiles from 1Password
+            let profiles = tokio::select! {
+                _ = context.cancellation_token.cancelled() => {
+                    println!("Import cancelled.");
+                    return Ok(());
+                }
+                profiles = self.import_from_1password(context) => {
+                    profiles.with_context("while importing profiles from 1Password")?
+                }
+            };
+
+            if profiles.is_empty() {
+                println!("No SSH keys found in 1Password.");
+                return Ok(());
+            }
+            profiles
+        };
 
-        // Import profiles from 1Password
-        let profiles = self.import_from_1password(context).await?;
-        
-        if profiles.is_empty() {
-            println!("No SSH keys found in 1Password.");
+        // Save imported profiles
+        if args.atomic && context.cancellation_token.is_cancelled() {
+            println!("Import cancelled.");
             return Ok(());
         }
-
-        // Save imported profiles
-        let (created, skipped) = self.save_profiles(profiles, context).await?;
+        let saved = if args.atomic {
+            self.save_profiles_atomic(&profiles, context)
+        } else {
+            Self::save_profiles(context.profile_manager.as_ref(), &profiles, &context.cancellation_token)
+        };
+        let (created, skipped) = saved.with_context("while saving imported profiles")?;
 
         // Print summary
+        if context.cancellation_token.is_cancelled() {
+            println!("Import cancelled after {} of {} profiles.", created + skipped, profiles.len());
+        }
         if !args.quiet {
             self.print_import_summary(created, skipped);
         }
@@ -191,9 +425,9 @@ mod tests {
         let mut onepassword_wrapper = MockOnePasswordWrapper::new();
         onepassword_wrapper.set_ssh_keys(ssh_keys);
 
-        let profile_manager = Arc::new(MockProfileManager::new());
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         context.onepassword_wrapper = Arc::new(onepassword_wrapper);
 
         let args = create_import_args();
@@ -201,16 +435,18 @@ mod tests {
         let result = cmd.execute(&args, &context).await;
 
         assert!(result.is_ok());
+        assert_eq!(profile_manager.create_call_count(), 2);
+        assert!(profile_manager.was_called_with_create("Work SSH Key"));
     }
 
     /// Test importing with no SSH keys available.
     #[tokio::test]
     async fn test_import_no_ssh_keys() {
         let onepassword_wrapper = MockOnePasswordWrapper::new(); // No SSH keys
-        let profile_manager = Arc::new(MockProfileManager::new());
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
         
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         context.onepassword_wrapper = Arc::new(onepassword_wrapper);
 
         let args = create_import_args();
@@ -218,6 +454,7 @@ mod tests {
         let result = cmd.execute(&args, &context).await;
 
         assert!(result.is_ok());
+        assert_eq!(profile_manager.create_call_count(), 0);
     }
 
     /// Test importing with existing profiles (should skip).
@@ -247,9 +484,9 @@ mod tests {
             ..Default::default()
         };
 
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
         context.onepassword_wrapper = Arc::new(onepassword_wrapper);
 
         let args = create_import_args();
@@ -257,6 +494,7 @@ mod tests {
         let result = cmd.execute(&args, &context).await;
 
         assert!(result.is_ok());
+        assert_eq!(profile_manager.create_call_count(), 0);
     }
 
     /// Test that ImportCommand implements Send + Sync.
@@ -292,19 +530,19 @@ mod tests {
             ..Default::default()
         };
 
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
-        let cmd = ImportCommand::new();
-        
         // Test existing profile
-        let exists = cmd.profile_exists("existing", &context).await.unwrap();
+        let exists = ImportCommand::profile_exists(context.profile_manager.as_ref(), "existing").unwrap();
         assert!(exists);
 
         // Test non-existing profile
-        let exists = cmd.profile_exists("nonexistent", &context).await.unwrap();
+        let exists = ImportCommand::profile_exists(context.profile_manager.as_ref(), "nonexistent").unwrap();
         assert!(!exists);
+        assert!(profile_manager.was_called_with_read("existing"));
+        assert!(profile_manager.was_called_with_read("nonexistent"));
     }
 
     /// Test saving imported profiles.
@@ -330,15 +568,16 @@ mod tests {
             ..Default::default()
         };
 
-        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]));
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]).track_calls(true));
         let mut context = create_test_context();
-        context.profile_manager = profile_manager;
+        context.profile_manager = profile_manager.clone();
 
-        let cmd = ImportCommand::new();
-        let (created, skipped) = cmd.save_profiles(profiles, &context).await.unwrap();
+        let (created, skipped) = ImportCommand::save_profiles(context.profile_manager.as_ref(), &profiles, &CancellationToken::new()).unwrap();
 
         assert_eq!(created, 1); // profile2 created
         assert_eq!(skipped, 1); // profile1 skipped
+        assert!(profile_manager.was_called_with_create("profile2"));
+        assert!(!profile_manager.was_called_with_create("profile1"));
     }
 
     /// Test import from 1Password with profile creation.
@@ -376,4 +615,323 @@ mod tests {
         assert_eq!(profile.ssh_key_title, Some("Test SSH Key".to_string()));
         assert_eq!(profile.vault_name, Some("Default".to_string()));
     }
-}
\ No newline at end of file
+
+    /// Test that an atomic import keeps none of the profiles when one fails.
+    #[tokio::test]
+    async fn test_atomic_import_rolls_back_on_failure() {
+        let ssh_keys = (1..=5)
+            .map(|i| SshKeyItem {
+                id: format!("key{}", i),
+                title: format!("Key {}", i),
+                vault: crate::external::onepassword::Vault {
+                    id: "vault-id".to_string(),
+                    name: "test-vault".to_string(),
+                },
+                category: "SSH_KEY".to_string(),
+                public_key: Some("ssh-ed25519 AAAAC3...".to_string()),
+                private_key: None,
+            })
+            .collect();
+
+        let mut onepassword_wrapper = MockOnePasswordWrapper::new();
+        onepassword_wrapper.set_ssh_keys(ssh_keys);
+
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        profile_manager.fail_on_create_after(2);
+
+        let mut context = create_test_context();
+        context.onepassword_wrapper = Arc::new(onepassword_wrapper);
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            import: true,
+            atomic: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let result = ImportCommand::new().execute(&args, &context).await;
+        assert!(result.unwrap_err().root_cause().to_string().contains("Key 3"));
+        assert!(profile_manager.list().unwrap().is_empty());
+        assert!(profile_manager.was_called_with_delete("Key 1"));
+        assert!(profile_manager.was_called_with_delete("Key 2"));
+    }
+
+    /// Test that a bundle whose signature does not verify imports nothing.
+    #[tokio::test]
+    async fn test_import_bundle_with_bad_signature() {
+        use crate::external::gpg::MockGpgWrapper;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("profiles.json");
+        let profiles = vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            ..Default::default()
+        }];
+        let bundle = ProfileBundle {
+            signature: Some("forged".to_string()),
+            ..ProfileBundle::new(&profiles).unwrap()
+        };
+        std::fs::write(&path, bundle.to_json().unwrap()).unwrap();
+
+        let mut gpg = MockGpgWrapper::new();
+        gpg.set_should_fail("verify_signature", true);
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut context = create_test_context();
+        context.gpg_wrapper = Arc::new(gpg);
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            from_bundle: Some(path.display().to_string()),
+            ..create_import_args()
+        };
+        let result = ImportCommand::new().execute(&args, &context).await;
+        assert!(result.is_err());
+        assert_eq!(profile_manager.create_call_count(), 0);
+    }
+
+    /// Profile manager cancelling `token` once it has created a profile.
+    struct CancellingProfileManager {
+        inner: MockProfileManager,
+        token: CancellationToken,
+    }
+
+    impl ProfileManager for CancellingProfileManager {
+        fn create(&self, profile: Profile) -> Result<()> {
+            self.inner.create(profile)?;
+            self.token.cancel();
+            Ok(())
+        }
+
+        fn read(&self, name: &str) -> Result<Option<Profile>> {
+            self.inner.read(name)
+        }
+
+        fn update(&self, name: &str, profile: Profile) -> Result<()> {
+            self.inner.update(name, profile)
+        }
+
+        fn delete(&self, name: &str) -> Result<()> {
+            self.inner.delete(name)
+        }
+
+        fn list(&self) -> Result<Vec<Profile>> {
+            self.inner.list()
+        }
+
+        fn exists(&self, name: &str) -> Result<bool> {
+            self.inner.exists(name)
+        }
+    }
+
+    /// Test that a cancelled import stops between profiles and still succeeds.
+    #[tokio::test]
+    async fn test_import_cancelled_mid_loop() {
+        use crate::output::OutputFormatter;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("profiles.toml");
+        let profiles: Vec<Profile> = ["work", "personal", "oss"]
+            .iter()
+            .map(|name| Profile {
+                name: name.to_string(),
+                git_user_email: format!("jane@{}.com", name),
+                ..Default::default()
+            })
+            .collect();
+        std::fs::write(&path, TomlFormatter::new().format_profiles(&profiles).unwrap()).unwrap();
+
+        let mut context = create_test_context();
+        let profile_manager = Arc::new(CancellingProfileManager {
+            inner: MockProfileManager::new().track_calls(true),
+            token: context.cancellation_token.clone(),
+        });
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            from_toml: Some(path.display().to_string()),
+            ..create_import_args()
+        };
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            ImportCommand::new().execute(&args, &context),
+        )
+        .await;
+
+        assert!(matches!(result, Ok(Ok(()))));
+        assert_eq!(profile_manager.list().unwrap(), vec![profiles[0].clone()]);
+        assert_eq!(profile_manager.inner.create_call_count(), 1);
+    }
+
+    /// Test that a cancelled 1Password import returns without saving anything.
+    #[tokio::test]
+    async fn test_import_cancelled_before_1password() {
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        context.cancellation_token.cancel();
+
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            ImportCommand::new().execute(&create_import_args(), &context),
+        )
+        .await;
+
+        assert!(matches!(result, Ok(Ok(()))));
+        assert_eq!(profile_manager.create_call_count(), 0);
+    }
+
+    /// Test that profiles listed as TOML import again.
+    #[tokio::test]
+    async fn test_import_from_toml() {
+        use crate::output::OutputFormatter;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("profiles.toml");
+        let profiles = vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                key_type: KeyType::Gpg,
+                scope: Some(Scope::Global),
+                ..Default::default()
+            },
+            Profile {
+                name: "personal".to_string(),
+                git_user_email: "jane@example.com".to_string(),
+                ..Default::default()
+            },
+        ];
+        std::fs::write(&path, TomlFormatter::new().format_profiles(&profiles).unwrap()).unwrap();
+
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            from_toml: Some(path.display().to_string()),
+            ..create_import_args()
+        };
+        ImportCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(profile_manager.read("work").unwrap(), Some(profiles[0].clone()));
+        assert_eq!(profile_manager.create_call_count(), 2);
+    }
+
+    /// Write a `.gitconfig` including a GPG, an unsigned and an x509 work identity.
+    fn write_gitconfig_fixture(dir: &Path) -> std::path::PathBuf {
+        std::fs::write(
+            dir.join(".gitconfig-work"),
+            "[user]\n\tname = Jane Doe\n\temail = jane@work.com\n\tsigningkey = ABCD1234\n[commit]\n\tgpgsign = true\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(".gitconfig-oss"), "[user]\n\temail = jane@oss.dev\n").unwrap();
+        std::fs::write(
+            dir.join(".gitconfig-client"),
+            "[user]\n\temail = jane@client.com\n[gpg]\n\tformat = pkcs11\n",
+        )
+        .unwrap();
+
+        let path = dir.join(".gitconfig");
+        std::fs::write(
+            &path,
+            "[user]\n\
+             \tname = Jane Doe\n\
+             \temail = jane@example.com\n\
+             \tsigningkey = ~/.ssh/id_ed25519.pub\n\
+             [gpg]\n\
+             \tformat = ssh\n\
+             [gpg \"ssh\"]\n\
+             \tallowedSignersFile = ~/.ssh/allowed_signers\n\
+             [includeIf \"gitdir:~/work/\"]\n\
+             \tpath = .gitconfig-work\n\
+             [includeIf \"gitdir/i:~/Work-Archive/\"]\n\
+             \tpath = .gitconfig-work\n\
+             [includeIf \"gitdir:~/oss/\"]\n\
+             \tpath = .gitconfig-oss\n\
+             [includeIf \"onbranch:client/*\"]\n\
+             \tpath = .gitconfig-client\n",
+        )
+        .unwrap();
+        path
+    }
+
+    /// Test that each identity of a gitconfig becomes a profile with its signing settings.
+    #[test]
+    fn test_profiles_from_gitconfig() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_gitconfig_fixture(dir.path());
+
+        let (profiles, warnings) = ImportCommand::new()
+            .import_from_gitconfig(&path.display().to_string())
+            .unwrap();
+        let names: Vec<&str> = profiles.iter().map(|profile| profile.name.as_str()).collect();
+        assert_eq!(names, vec!["global", "work", "oss", "global-2"]);
+
+        // SSH signing from the top-level [user] section
+        assert_eq!(profiles[0].key_type, KeyType::Ssh);
+        assert_eq!(profiles[0].signing_key.as_deref(), Some("~/.ssh/id_ed25519.pub"));
+        assert_eq!(profiles[0].allowed_signers.as_deref(), Some("~/.ssh/allowed_signers"));
+        assert!(profiles[0].include_if_dirs.is_empty());
+
+        // GPG signing, included for two directories but imported once
+        assert_eq!(profiles[1].git_user_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(profiles[1].key_type, KeyType::Gpg);
+        assert_eq!(profiles[1].signing_key.as_deref(), Some("ABCD1234"));
+        assert_eq!(profiles[1].include_if_dirs, vec!["~/work/", "~/Work-Archive/"]);
+
+        // Unsigned
+        assert_eq!(profiles[2].git_user_email, "jane@oss.dev");
+        assert_eq!(profiles[2].signing_key, None);
+
+        // Unknown signing format, included on a branch condition
+        assert_eq!(profiles[3].git_user_email, "jane@client.com");
+        assert_eq!(profiles[3].signing_key, None);
+        assert!(profiles[3].include_if_dirs.is_empty());
+
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings.iter().any(|warning| warning.contains("'onbranch:client/*' is not a gitdir")));
+        assert!(warnings.iter().any(|warning| warning.contains("jane@oss.dev") && warning.contains("no signing key")));
+        assert!(warnings.iter().any(|warning| warning.contains("unknown signing format 'pkcs11'")));
+    }
+
+    /// Test that gitconfig profiles are saved and a missing file is an error.
+    #[tokio::test]
+    async fn test_import_from_gitconfig() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_gitconfig_fixture(dir.path());
+
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            from_gitconfig: Some(path.display().to_string()),
+            ..create_import_args()
+        };
+        ImportCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(profile_manager.create_call_count(), 4);
+        assert_eq!(profile_manager.read("work").unwrap().unwrap().git_user_email, "jane@work.com");
+
+        let args = Args {
+            from_gitconfig: Some(dir.path().join("missing").display().to_string()),
+            ..create_import_args()
+        };
+        assert!(ImportCommand::new().execute(&args, &context).await.is_err());
+    }
+
+    #[test]
+    fn test_gitconfig_profile_names() {
+        assert_eq!(ImportCommand::name_for_dir("~/work/"), "work");
+        assert_eq!(ImportCommand::name_for_dir("~/code/client.io/**"), "client-io");
+        assert_eq!(ImportCommand::name_for_dir("/"), "global");
+
+        let profiles = vec![Profile {
+            name: "work".to_string(),
+            ..Default::default()
+        }];
+        assert_eq!(ImportCommand::unique_name("work", &profiles), "work-2");
+        assert_eq!(ImportCommand::unique_name("oss", &profiles), "oss");
+    }
+}
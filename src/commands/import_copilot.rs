@@ -0,0 +1,336 @@
+//! Import-from-GitHub-Copilot command implementation for git-setup-rs.
+//!
+//! `--import --from-github-copilot` finds the SSH keys GitHub Copilot leaves in
+//! `~/.ssh/copilot_github_*` and creates a signing profile for each of them.
+//! The GitHub account is taken from `github.user` in the global git config or
+//! from the Copilot editor extension's `~/.config/github-copilot/apps.json`.
+//! With `--apply`, the new profile is applied globally.
+
+use super::{apply::ApplyCommand, Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::{KeyType, Profile, Scope, SshKeySource},
+    error::{GitSetupError, Result, ResultExt},
+    external::git::GitConfigScope,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clap::Parser;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// File name prefix of the keys GitHub Copilot generates.
+const COPILOT_KEY_PREFIX: &str = "copilot_github_";
+
+/// An SSH key pair generated by GitHub Copilot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopilotKey {
+    /// Path of the private key, if it is present.
+    pub private_key_path: Option<PathBuf>,
+    /// Contents of the public key.
+    pub public_key: String,
+}
+
+/// Command implementation for importing GitHub Copilot SSH keys.
+pub struct ImportCopilotCommand {
+    home_dir: Option<PathBuf>,
+}
+
+impl ImportCopilotCommand {
+    /// Create a new ImportCopilotCommand looking in the user's home directory.
+    pub fn new() -> Self {
+        Self { home_dir: None }
+    }
+
+    /// Look for keys and Copilot settings under `home_dir` instead of the user's home directory.
+    pub fn with_home_dir(mut self, home_dir: PathBuf) -> Self {
+        self.home_dir = Some(home_dir);
+        self
+    }
+
+    fn home_dir(&self) -> Result<PathBuf> {
+        match &self.home_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(SystemPlatform.home_dir()?),
+        }
+    }
+
+    /// Find the Copilot key pairs in `ssh_dir`, sorted by file name.
+    ///
+    /// Keys without a readable `.pub` file are skipped, since the public key
+    /// cannot be derived without the private key's passphrase.
+    pub fn find_keys(ssh_dir: &Path) -> Vec<CopilotKey> {
+        let Ok(entries) = std::fs::read_dir(ssh_dir) else {
+            return Vec::new();
+        };
+        let mut public_keys: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(COPILOT_KEY_PREFIX) && name.ends_with(".pub"))
+            })
+            .collect();
+        public_keys.sort();
+
+        public_keys
+            .into_iter()
+            .filter_map(|public_path| {
+                let public_key = std::fs::read_to_string(&public_path).ok()?.trim().to_string();
+                let private_path = public_path.with_extension("");
+                Some(CopilotKey {
+                    private_key_path: private_path.exists().then_some(private_path),
+                    public_key,
+                })
+            })
+            .collect()
+    }
+
+    /// GitHub user name stored by the Copilot editor extension in `apps.json`.
+    fn apps_json_user(path: &Path) -> Option<String> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let apps: serde_json::Value = serde_json::from_str(&content).ok()?;
+        apps.as_object()?
+            .values()
+            .find_map(|app| app.get("user")?.as_str().map(str::to_string))
+    }
+
+    /// GitHub account of the user: `github.user` from the global git config, then `apps.json`.
+    fn github_account(&self, context: &CommandContext) -> Result<Option<String>> {
+        if let Some(user) = context.git_wrapper.get_config("github.user", Some(GitConfigScope::Global))? {
+            return Ok(Some(user));
+        }
+        let apps_json = self.home_dir()?.join(".config").join("github-copilot").join("apps.json");
+        Ok(Self::apps_json_user(&apps_json))
+    }
+
+    /// Profiles to create for `keys`, named `github-copilot-<timestamp>`.
+    fn build_profiles(keys: &[CopilotKey], account: &str, timestamp: &str) -> Vec<Profile> {
+        keys.iter()
+            .enumerate()
+            .map(|(i, key)| Profile {
+                name: match i {
+                    0 => format!("github-copilot-{}", timestamp),
+                    _ => format!("github-copilot-{}-{}", timestamp, i + 1),
+                },
+                git_user_name: Some(account.to_string()),
+                git_user_email: format!("{}@users.noreply.github.com", account),
+                key_type: KeyType::Ssh,
+                signing_key: Some(key.public_key.clone()),
+                scope: Some(Scope::Global),
+                ssh_key_source: Some(SshKeySource::File),
+                ssh_key_path: key.private_key_path.as_ref().map(|path| path.display().to_string()),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Show the profiles that would be created and ask for confirmation on `input`.
+    fn confirm(profiles: &[Profile], input: &mut impl BufRead) -> Result<bool> {
+        eprintln!("The following profile(s) will be created:");
+        for profile in profiles {
+            eprintln!(
+                "  {} <{}> signing with {}",
+                profile.name,
+                profile.git_user_email,
+                profile.ssh_key_path.as_deref().unwrap_or("the Copilot public key")
+            );
+        }
+        eprint!("Create {} profile(s)? (y/N) ", profiles.len());
+        std::io::stderr().flush()?;
+
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+
+    /// Import the Copilot keys, reading the confirmation from `input`.
+    ///
+    /// Returns the names of the created profiles.
+    pub fn import(&self, args: &Args, context: &CommandContext, input: &mut impl BufRead) -> Result<Vec<String>> {
+        let ssh_dir = self.home_dir()?.join(".ssh");
+        let keys = Self::find_keys(&ssh_dir);
+        if keys.is_empty() {
+            if !args.quiet {
+                println!("No GitHub Copilot SSH keys found in {}", ssh_dir.display());
+            }
+            return Ok(Vec::new());
+        }
+
+        let account = self.github_account(context)?.ok_or_else(|| {
+            GitSetupError::Git(
+                "Could not determine your GitHub account; set it with 'git config --global github.user <name>'"
+                    .to_string(),
+            )
+        })?;
+        let profiles = Self::build_profiles(&keys, &account, &Utc::now().format("%Y%m%d%H%M%S").to_string());
+
+        if !Self::confirm(&profiles, input)? {
+            eprintln!("Import cancelled.");
+            return Ok(Vec::new());
+        }
+
+        let mut created = Vec::new();
+        for profile in profiles {
+            let name = profile.name.clone();
+            context
+                .profile_manager
+                .create(profile)
+                .with_context(format!("while creating profile '{}'", name))?;
+            if !args.quiet {
+                println!("Imported profile: {}", name);
+            }
+            created.push(name);
+        }
+        Ok(created)
+    }
+}
+
+impl Default for ImportCopilotCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ImportCopilotCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let created = {
+            let stdin = std::io::stdin();
+            self.import(args, context, &mut stdin.lock())
+                .with_context("while importing GitHub Copilot keys")?
+        };
+
+        if args.apply
+            && let Some(name) = created.first()
+        {
+            let mut apply_argv = vec!["git-setup", name.as_str(), "--global"];
+            if args.quiet {
+                apply_argv.push("--quiet");
+            }
+            let apply_args = Args::try_parse_from(apply_argv)
+                .map_err(|e| GitSetupError::Git(e.to_string()))?;
+            ApplyCommand::new()
+                .execute(&apply_args, context)
+                .await
+                .with_context(format!("while applying profile '{}'", name))?;
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "import-github-copilot"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create profiles from the SSH keys generated by GitHub Copilot"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        external::git::MockGitWrapper,
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const PUBLIC_KEY: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIcopilot copilot";
+
+    fn home_with_key() -> TempDir {
+        let home = TempDir::new().unwrap();
+        let ssh = home.path().join(".ssh");
+        std::fs::create_dir_all(&ssh).unwrap();
+        std::fs::write(ssh.join("copilot_github_ed25519"), "PRIVATE").unwrap();
+        std::fs::write(ssh.join("copilot_github_ed25519.pub"), format!("{}\n", PUBLIC_KEY)).unwrap();
+        std::fs::write(ssh.join("id_ed25519.pub"), "ssh-ed25519 AAAAother").unwrap();
+        home
+    }
+
+    fn args() -> Args {
+        Args {
+            import: true,
+            from_github_copilot: true,
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_keys() {
+        let home = home_with_key();
+        std::fs::write(home.path().join(".ssh").join("copilot_github_rsa.pub"), "ssh-rsa AAAArsa").unwrap();
+
+        let keys = ImportCopilotCommand::find_keys(&home.path().join(".ssh"));
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].public_key, PUBLIC_KEY);
+        assert_eq!(keys[0].private_key_path, Some(home.path().join(".ssh").join("copilot_github_ed25519")));
+        // A public key without its private key is still imported
+        assert_eq!(keys[1].private_key_path, None);
+        assert!(ImportCopilotCommand::find_keys(&home.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_import_uses_github_user() {
+        let home = home_with_key();
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+        context.git_wrapper = Arc::new(MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Global,
+            HashMap::from([("github.user".to_string(), "octocat".to_string())]),
+        ));
+
+        let cmd = ImportCopilotCommand::new().with_home_dir(home.path().to_path_buf());
+        let created = cmd.import(&args(), &context, &mut "y\n".as_bytes()).unwrap();
+
+        assert_eq!(created.len(), 1);
+        assert!(created[0].starts_with("github-copilot-"));
+        assert!(profile_manager.was_called_with_create(&created[0]));
+        let profile = profile_manager.read(&created[0]).unwrap().unwrap();
+        assert_eq!(profile.signing_key.as_deref(), Some(PUBLIC_KEY));
+        assert_eq!(profile.git_user_email, "octocat@users.noreply.github.com");
+        assert_eq!(profile.scope, Some(Scope::Global));
+    }
+
+    #[test]
+    fn test_import_reads_apps_json_and_can_be_cancelled() {
+        let home = home_with_key();
+        let copilot_dir = home.path().join(".config").join("github-copilot");
+        std::fs::create_dir_all(&copilot_dir).unwrap();
+        std::fs::write(
+            copilot_dir.join("apps.json"),
+            r#"{"github.com:Iv1.b507a08c87ecfe98": {"user": "hubot", "oauth_token": "secret"}}"#,
+        )
+        .unwrap();
+        let profile_manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let cmd = ImportCopilotCommand::new().with_home_dir(home.path().to_path_buf());
+        assert!(cmd.import(&args(), &context, &mut "n\n".as_bytes()).unwrap().is_empty());
+        assert_eq!(profile_manager.create_call_count(), 0);
+
+        let created = cmd.import(&args(), &context, &mut "yes\n".as_bytes()).unwrap();
+        assert_eq!(profile_manager.create_call_count(), 1);
+        let profile = profile_manager.read(&created[0]).unwrap().unwrap();
+        assert_eq!(profile.git_user_name.as_deref(), Some("hubot"));
+    }
+
+    #[test]
+    fn test_import_without_account_fails() {
+        let home = home_with_key();
+        let cmd = ImportCopilotCommand::new().with_home_dir(home.path().to_path_buf());
+
+        let result = cmd.import(&args(), &create_test_context(), &mut "y\n".as_bytes());
+        assert!(result.is_err());
+    }
+}
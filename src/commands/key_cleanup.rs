@@ -0,0 +1,198 @@
+//! Key cleanup command implementation for git-setup-rs.
+//!
+//! `--cleanup-key <PROFILE>` archives (or with `--delete-item`, deletes) the
+//! 1Password item holding the profile's key, found by `ssh_key_title` and
+//! `vault_name`, and removes both fields from the profile. With `--old-title`,
+//! the item left behind by a renamed key is cleaned up instead and the profile
+//! keeps its current key.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Archive or delete the 1Password item titled `title` in the profile's vault.
+///
+/// Returns the ID of the removed item.
+pub fn cleanup_key_item(profile: &Profile, title: &str, delete: bool, context: &CommandContext) -> Result<String> {
+    let vault = profile.vault_name.as_deref().ok_or_else(|| GitSetupError::InvalidProfile {
+        reason: format!("profile '{}' has no 1Password vault", profile.name),
+    })?;
+
+    let item_id = match context.onepassword_wrapper.get_ssh_key(title, vault)? {
+        Some(item) => item.id,
+        None => context
+            .onepassword_wrapper
+            .get_gpg_key(title, vault)?
+            .map(|item| item.id)
+            .ok_or_else(|| {
+                GitSetupError::OnePassword(format!("item '{}' not found in vault '{}'", title, vault))
+            })?,
+    };
+
+    if delete {
+        context.onepassword_wrapper.delete_item(&item_id)?;
+    } else {
+        context.onepassword_wrapper.archive_item(&item_id)?;
+    }
+    Ok(item_id)
+}
+
+/// Command implementation for cleaning up a profile's 1Password key item.
+pub struct KeyCleanupCommand;
+
+impl KeyCleanupCommand {
+    /// Create a new KeyCleanupCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for KeyCleanupCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for KeyCleanupCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.cleanup_key.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --cleanup-key".to_string())
+        })?;
+        let mut profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+
+        let title = match (&args.old_title, &profile.ssh_key_title) {
+            (Some(title), _) | (None, Some(title)) => title.clone(),
+            (None, None) => {
+                return Err(GitSetupError::InvalidProfile {
+                    reason: format!("profile '{}' has no 1Password key item", name),
+                });
+            }
+        };
+
+        cleanup_key_item(&profile, &title, args.delete_item, context)
+            .with_context(format!("while cleaning up 1Password item '{}'", title))?;
+
+        // A renamed item's profile still uses its current key
+        if args.old_title.is_none() {
+            profile.ssh_key_title = None;
+            profile.vault_name = None;
+            context
+                .profile_manager
+                .update(name, profile)
+                .with_context(format!("while updating profile '{}'", name))?;
+        }
+
+        if !args.quiet {
+            let action = if args.delete_item { "Deleted" } else { "Archived" };
+            println!("✓ {} 1Password item '{}' of profile '{}'", action, title, name);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "cleanup-key"
+    }
+
+    fn description(&self) -> &'static str {
+        "Archive or delete a profile's 1Password key item"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        external::onepassword::{MockOnePasswordWrapper, SshKeyItem, Vault},
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    fn ssh_key_item(id: &str, title: &str) -> SshKeyItem {
+        SshKeyItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            vault: Vault {
+                id: "vault2".to_string(),
+                name: "Work".to_string(),
+            },
+            category: "SSH_KEY".to_string(),
+            public_key: Some("ssh-ed25519 AAAAC3...".to_string()),
+            private_key: None,
+        }
+    }
+
+    fn test_context(onepassword: Arc<MockOnePasswordWrapper>) -> (CommandContext, Arc<MockProfileManager>) {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            one_password: true,
+            ssh_key_title: Some("Work Key".to_string()),
+            vault_name: Some("Work".to_string()),
+            ..Default::default()
+        }]).track_calls(true));
+        let mut context = create_test_context();
+        context.onepassword_wrapper = onepassword;
+        context.profile_manager = profile_manager.clone();
+        (context, profile_manager)
+    }
+
+    /// Test that the current key item is archived and removed from the profile.
+    #[tokio::test]
+    async fn test_cleanup_key_archives_current_item() {
+        let onepassword = Arc::new(MockOnePasswordWrapper::new().with_ssh_keys(vec![ssh_key_item("item1", "Work Key")]));
+        let (context, profile_manager) = test_context(onepassword.clone());
+
+        let args = Args {
+            cleanup_key: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        KeyCleanupCommand::new().execute(&args, &context).await.unwrap();
+
+        assert!(onepassword.was_item_archived("item1"));
+        assert!(!onepassword.was_item_deleted("item1"));
+        let profile = profile_manager.read("work").unwrap().unwrap();
+        assert_eq!(profile.ssh_key_title, None);
+        assert_eq!(profile.vault_name, None);
+        assert!(profile_manager.was_called_with_update("work"));
+    }
+
+    /// Test that --old-title deletes the renamed item and keeps the profile's key.
+    #[tokio::test]
+    async fn test_cleanup_key_old_title() {
+        let onepassword = Arc::new(MockOnePasswordWrapper::new().with_ssh_keys(vec![
+            ssh_key_item("item1", "Work Key"),
+            ssh_key_item("item0", "Old Work Key"),
+        ]));
+        let (context, profile_manager) = test_context(onepassword.clone());
+
+        let args = Args {
+            cleanup_key: Some("work".to_string()),
+            old_title: Some("Old Work Key".to_string()),
+            delete_item: true,
+            quiet: true,
+            ..Default::default()
+        };
+        KeyCleanupCommand::new().execute(&args, &context).await.unwrap();
+
+        assert!(onepassword.was_item_deleted("item0"));
+        assert!(!onepassword.was_item_deleted("item1"));
+        assert_eq!(profile_manager.update_call_count(), 0);
+
+        let args = Args {
+            old_title: Some("Missing".to_string()),
+            ..args
+        };
+        assert!(KeyCleanupCommand::new().execute(&args, &context).await.is_err());
+    }
+}
@@ -0,0 +1,333 @@
+//! Keygen command implementation for git-setup-rs.
+//!
+//! `--keygen` generates an SSH key pair and, with `--store-in-op`, stores it in
+//! 1Password and removes the private key from disk. With `--profile`, the
+//! profile is updated to sign with the new key.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::KeyType,
+    error::{GitSetupError, Result, ResultExt},
+    external::ssh::{SshKeyGenParams, SshKeyGenerator, SshKeyPair, SystemSshKeyGenerator},
+    platform::{PlatformPaths, SystemPlatform},
+};
+use async_trait::async_trait;
+use secrecy::ExposeSecret;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Command implementation for generating SSH keys.
+pub struct KeygenCommand {
+    generator: Arc<dyn SshKeyGenerator>,
+    key_dir: Option<PathBuf>,
+}
+
+impl KeygenCommand {
+    /// Create a new KeygenCommand writing keys to `~/.ssh`.
+    pub fn new() -> Self {
+        Self {
+            generator: Arc::new(SystemSshKeyGenerator::new()),
+            key_dir: None,
+        }
+    }
+
+    /// Write generated keys to `key_dir` instead of `~/.ssh`.
+    pub fn with_key_dir(mut self, key_dir: PathBuf) -> Self {
+        self.key_dir = Some(key_dir);
+        self
+    }
+
+    /// Generate keys with `generator` instead of the system `ssh-keygen`.
+    pub fn with_generator(mut self, generator: Arc<dyn SshKeyGenerator>) -> Self {
+        self.generator = generator;
+        self
+    }
+
+    /// Title of the key: `--title`, or one derived from the profile name.
+    fn title(args: &Args) -> String {
+        match (&args.title, &args.keygen_profile) {
+            (Some(title), _) => title.clone(),
+            (None, Some(profile)) => format!("git-setup {}", profile),
+            (None, None) => "git-setup".to_string(),
+        }
+    }
+
+    /// File name of the private key, e.g. `id_ed25519_git-setup_work`.
+    fn key_file_name(key_type: &str, title: &str) -> String {
+        let slug: String = title
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        format!("id_{}_{}", key_type, slug)
+    }
+
+    /// Directory the key pair is written to.
+    fn key_dir(&self) -> Result<PathBuf> {
+        match &self.key_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(SystemPlatform.home_dir()?.join(".ssh")),
+        }
+    }
+
+    /// Store `key` as a new SSH key item in `vault`, returning the item ID.
+    fn store_in_op(&self, key: &SshKeyPair, title: &str, vault: &str, context: &CommandContext) -> Result<String> {
+        context
+            .onepassword_wrapper
+            .import_ssh_key(title, vault, &key.public_key, key.private_key.expose_secret())
+    }
+
+    /// Configure the named profile to sign with `key`.
+    fn update_profile(
+        &self,
+        name: &str,
+        key: &SshKeyPair,
+        title: &str,
+        args: &Args,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let mut profile = context.profile_manager.read(name)?.ok_or_else(|| GitSetupError::ProfileNotFound {
+            name: name.to_string(),
+        })?;
+
+        profile.key_type = KeyType::Ssh;
+        profile.signing_key = Some(key.public_key.clone());
+        if let Some(vault) = &args.store_in_op {
+            profile.one_password = true;
+            profile.vault_name = Some(vault.clone());
+            profile.ssh_key_title = Some(title.to_string());
+        }
+        // The private key only stays on disk without 1Password or with --keep-local-copy
+        profile.ssh_key_path = if args.store_in_op.is_none() || args.keep_local_copy {
+            Some(key.private_key_path.display().to_string())
+        } else {
+            None
+        };
+
+        context.profile_manager.update(name, profile)
+    }
+}
+
+impl Default for KeygenCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for KeygenCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        // Fail before generating anything if the profile does not exist
+        let profile = match &args.keygen_profile {
+            Some(name) => Some(context.profile_manager.read(name)?.ok_or_else(|| {
+                GitSetupError::ProfileNotFound { name: name.clone() }
+            })?),
+            None => None,
+        };
+
+        let key_type = args.key_type.unwrap_or_default();
+        let title = Self::title(args);
+        let path = self.key_dir()?.join(Self::key_file_name(key_type.as_str(), &title));
+        let comment = profile.as_ref().map_or(title.as_str(), |p| p.git_user_email.as_str());
+
+        let key = self
+            .generator
+            .generate(SshKeyGenParams {
+                algorithm: key_type,
+                bits: None,
+                comment: comment.to_string(),
+                output_path: path,
+                passphrase: None,
+            })
+            .with_context(format!("while generating an {} key", key_type))?;
+        if !args.quiet {
+            println!(
+                "Generated {} key: {} ({})",
+                key_type,
+                key.private_key_path.display(),
+                key.fingerprint
+            );
+        }
+
+        if let Some(vault) = &args.store_in_op {
+            let item_id = self
+                .store_in_op(&key, &title, vault, context)
+                .with_context(format!("while storing the key in 1Password vault '{}'", vault))?;
+            if !args.quiet {
+                println!("Stored key '{}' in vault '{}' ({})", title, vault, item_id);
+            }
+        }
+
+        if let Some(name) = &args.keygen_profile {
+            self.update_profile(name, &key, &title, args, context)
+                .with_context(format!("while updating profile '{}'", name))?;
+            if !args.quiet {
+                println!("Profile '{}' now signs with the new key", name);
+            }
+        }
+
+        if args.store_in_op.is_some() && !args.keep_local_copy {
+            key.remove_private_key()
+                .with_context("while deleting the local private key")?;
+            if !args.quiet {
+                println!("Deleted local private key");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "keygen"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate an SSH key and optionally store it in 1Password"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        external::{onepassword::MockOnePasswordWrapper, ssh::MockSshKeyGenerator},
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use tempfile::TempDir;
+
+    fn ssh_keygen_available() -> bool {
+        std::process::Command::new("ssh-keygen").arg("-?").output().is_ok()
+    }
+
+    #[test]
+    fn test_key_file_name() {
+        assert_eq!(KeygenCommand::key_file_name("ed25519", "git-setup Work"), "id_ed25519_git-setup_work");
+    }
+
+    #[test]
+    fn test_title() {
+        let args = Args {
+            keygen_profile: Some("work".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(KeygenCommand::title(&args), "git-setup work");
+
+        let args = Args {
+            title: Some("Laptop".to_string()),
+            ..args
+        };
+        assert_eq!(KeygenCommand::title(&args), "Laptop");
+    }
+
+    #[tokio::test]
+    async fn test_keygen_stores_key_and_updates_profile() {
+        if !ssh_keygen_available() {
+            return;
+        }
+        let dir = TempDir::new().unwrap();
+        let onepassword = Arc::new(MockOnePasswordWrapper::new());
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            ..Default::default()
+        }]).track_calls(true));
+        let mut context = create_test_context();
+        context.onepassword_wrapper = onepassword.clone();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            keygen: true,
+            store_in_op: Some("Work".to_string()),
+            keygen_profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        let cmd = KeygenCommand::new().with_key_dir(dir.path().to_path_buf());
+        cmd.execute(&args, &context).await.unwrap();
+
+        let private_key = dir.path().join("id_ed25519_git-setup_work");
+        assert!(!private_key.exists());
+        assert!(dir.path().join("id_ed25519_git-setup_work.pub").exists());
+
+        let (public_key, private_key) = onepassword.imported_ssh_key("ssh-key-git-setup-work").unwrap();
+        assert!(private_key.contains("PRIVATE KEY"));
+        assert_eq!(
+            public_key,
+            std::fs::read_to_string(dir.path().join("id_ed25519_git-setup_work.pub")).unwrap().trim()
+        );
+
+        let profile = profile_manager.read("work").unwrap().unwrap();
+        assert!(profile.one_password);
+        assert_eq!(profile.vault_name, Some("Work".to_string()));
+        assert_eq!(profile.ssh_key_title, Some("git-setup work".to_string()));
+        assert!(profile.signing_key.unwrap().ends_with("jane@work.com"));
+        assert!(profile_manager.was_called_with_update("work"));
+    }
+
+    #[tokio::test]
+    async fn test_keygen_keep_local_copy() {
+        if !ssh_keygen_available() {
+            return;
+        }
+        let dir = TempDir::new().unwrap();
+        let args = Args {
+            keygen: true,
+            store_in_op: Some("Personal".to_string()),
+            title: Some("laptop".to_string()),
+            keep_local_copy: true,
+            quiet: true,
+            ..Default::default()
+        };
+        let cmd = KeygenCommand::new().with_key_dir(dir.path().to_path_buf());
+        cmd.execute(&args, &create_test_context()).await.unwrap();
+
+        assert!(dir.path().join("id_ed25519_laptop").exists());
+    }
+
+    #[tokio::test]
+    async fn test_keygen_with_generator_updates_profile() {
+        let generator = Arc::new(MockSshKeyGenerator::new());
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            ..Default::default()
+        }]).track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            keygen: true,
+            keygen_profile: Some("work".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        let cmd = KeygenCommand::new()
+            .with_key_dir(PathBuf::from("/keys"))
+            .with_generator(generator.clone());
+        cmd.execute(&args, &context).await.unwrap();
+
+        assert_eq!(generator.generated_keys(), vec![PathBuf::from("/keys/id_ed25519_git-setup_work")]);
+        let profile = profile_manager.read("work").unwrap().unwrap();
+        assert_eq!(profile.signing_key.as_deref(), Some("ssh-ed25519 AAAAMOCKKEY jane@work.com"));
+        assert_eq!(profile.ssh_key_path.as_deref(), Some("/keys/id_ed25519_git-setup_work"));
+        assert_eq!(profile_manager.update_call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keygen_unknown_profile_generates_nothing() {
+        let dir = TempDir::new().unwrap();
+        let args = Args {
+            keygen: true,
+            keygen_profile: Some("missing".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        let cmd = KeygenCommand::new().with_key_dir(dir.path().to_path_buf());
+        let result = cmd.execute(&args, &create_test_context()).await;
+
+        assert!(matches!(result.unwrap_err(), GitSetupError::ProfileNotFound { .. }));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
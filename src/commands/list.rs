@@ -5,10 +5,30 @@
 use super::{Command, CommandContext};
 use crate::{
     cli::{Args, OutputFormat},
-    error::Result,
-    output::{OutputFormatter, JsonFormatter, YamlFormatter, CsvFormatter},
+    config::{snippets::SnippetManager, types::Profile},
+    error::{GitSetupError, Result, ResultExt},
+    matching::MatchResult,
+    output::{OutputFormatter, JsonFormatter, YamlFormatter, TomlFormatter, CsvFormatter, TableFormatOptions, TableFormatter},
+    profile::{
+        activation::ActivationRecord,
+        counts::{ProfileCountsByKeyType, ProfileCountsByOnePassword, ProfileCountsByScope},
+        schema::ProfileJsonSchema,
+    },
+    util::date_filter::ActivationDateFilter,
 };
 use async_trait::async_trait;
+use serde::Serialize;
+
+/// Profile counts requested by the `--count-by-*` flags.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProfileCounts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_type: Option<ProfileCountsByKeyType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<ProfileCountsByScope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub one_password: Option<ProfileCountsByOnePassword>,
+}
 
 /// Command implementation for listing profiles.
 pub struct ListCommand;
@@ -18,6 +38,171 @@ impl ListCommand {
     pub fn new() -> Self {
         Self
     }
+
+    /// Keep the profiles matching the name/email pattern and activation date range.
+    fn filter_profiles(
+        rows: Vec<(Profile, Option<ActivationRecord>)>,
+        pattern: Option<&str>,
+        date_filter: Option<&ActivationDateFilter>,
+    ) -> Vec<(Profile, Option<ActivationRecord>)> {
+        rows.into_iter()
+            .filter(|(p, _)| {
+                pattern.is_none_or(|pattern| {
+                    p.name.contains(pattern) ||
+                        p.git_user_email.contains(pattern) ||
+                        p.git_user_name.as_ref().is_some_and(|n| n.contains(pattern))
+                })
+            })
+            .filter(|(_, activation)| {
+                date_filter.is_none_or(|filter| filter.matches(activation.as_ref().map(|r| &r.activated_at)))
+            })
+            .collect()
+    }
+
+    /// Keep the profiles fuzzy matching `pattern`, best match first.
+    fn fuzzy_filter(
+        rows: Vec<(Profile, Option<ActivationRecord>)>,
+        matches: &[MatchResult],
+    ) -> Vec<(Profile, Option<ActivationRecord>)> {
+        let mut rows: Vec<_> = rows.into_iter().map(Some).collect();
+        matches
+            .iter()
+            .filter_map(|result| {
+                rows.iter_mut()
+                    .find(|row| row.as_ref().is_some_and(|(p, _)| p.name == result.profile.name))
+                    .and_then(Option::take)
+            })
+            .collect()
+    }
+
+    /// Count the profiles as requested by the `--count-by-*` flags.
+    fn counts(args: &Args, context: &CommandContext) -> Result<ProfileCounts> {
+        let manager = &context.profile_manager;
+        Ok(ProfileCounts {
+            key_type: args.count_by_key_type.then(|| manager.count_by_key_type()).transpose()?,
+            scope: args.count_by_scope.then(|| manager.count_by_scope()).transpose()?,
+            one_password: args.count_by_one_password.then(|| manager.count_by_one_password()).transpose()?,
+        })
+    }
+
+    /// Render `counts` with one `Label: n, ..., Total: n` line per grouping.
+    pub(crate) fn format_counts(counts: &ProfileCounts, format: &OutputFormat) -> Result<String> {
+        match format {
+            OutputFormat::Tabular => {
+                let lines: Vec<String> = [
+                    counts.key_type.map(|c| c.to_string()),
+                    counts.scope.map(|c| c.to_string()),
+                    counts.one_password.map(|c| c.to_string()),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                Ok(lines.join("\n"))
+            }
+            OutputFormat::Json => Ok(serde_json::to_string_pretty(counts)?),
+            OutputFormat::Yaml => Ok(serde_yaml::to_string(counts)?),
+            OutputFormat::Toml | OutputFormat::Csv => Err(GitSetupError::Git(
+                "Profile counts can be shown as tabular, json or yaml output".to_string(),
+            )),
+        }
+    }
+
+    /// Render a two column table of profile names and the paths of their
+    /// gitconfig snippets, marking the snippets not generated yet.
+    pub(crate) fn format_profile_paths(
+        rows: &[(Profile, Option<ActivationRecord>)],
+        snippets: &SnippetManager,
+    ) -> Result<String> {
+        let width = rows.iter().map(|(p, _)| p.name.len()).chain([4]).max().unwrap_or_default();
+        let mut output = format!("{:<width$}  {}\n", "name", "path");
+        for (profile, _) in rows {
+            let snippet = snippets.snippet(&profile.name)?;
+            let marker = if snippet.exists { "" } else { " (not generated)" };
+            output.push_str(&format!(
+                "{:<width$}  {}{}\n",
+                profile.name,
+                snippet.path.display(),
+                marker
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Render the listed profiles in the `--output` format, with the date each
+    /// was last applied if `show_activations` is set.
+    fn format_rows(
+        rows: &[(Profile, Option<ActivationRecord>)],
+        args: &Args,
+        show_activations: bool,
+    ) -> Result<String> {
+        let format = |formatter: &dyn OutputFormatter| {
+            if show_activations {
+                formatter.format_profiles_with_activations(rows)
+            } else {
+                let profiles: Vec<Profile> = rows.iter().map(|(p, _)| p.clone()).collect();
+                formatter.format_profiles(&profiles)
+            }
+        };
+
+        Ok(match args.output {
+            OutputFormat::Json => format(&JsonFormatter::new())?,
+            OutputFormat::Yaml => format(&YamlFormatter::new())?,
+            OutputFormat::Toml => format(&TomlFormatter::new())?,
+            OutputFormat::Csv => format(&CsvFormatter::new())?,
+            OutputFormat::Tabular => {
+                let mut options = TableFormatOptions::default();
+                if show_activations {
+                    options = options.with_last_activated();
+                }
+                for spec in &args.column_width {
+                    options.apply_column_width(spec)?;
+                }
+                options.border = !args.no_border;
+
+                let formatter = TableFormatter::new();
+                let mut output = formatter.format_with_activations(rows, &options)?;
+                output.push('\n');
+                output
+            }
+        })
+    }
+
+    /// Reject `field` unless [`Profile::get_field`] can read it.
+    pub(crate) fn check_field(field: &str) -> Result<()> {
+        if Profile::FIELD_NAMES.contains(&field) {
+            return Ok(());
+        }
+        Err(GitSetupError::Git(format!(
+            "Unknown field '{}'; valid fields are: {}",
+            field,
+            Profile::FIELD_NAMES.join(", ")
+        )))
+    }
+
+    /// Render `field` of the listed profiles: just the value when `name` is one
+    /// of them, otherwise a two column table of profile names and values.
+    pub(crate) fn format_field(
+        rows: &[(Profile, Option<ActivationRecord>)],
+        field: &str,
+        name: Option<&str>,
+    ) -> Result<String> {
+        Self::check_field(field)?;
+
+        if let Some((profile, _)) = name.and_then(|name| rows.iter().find(|(p, _)| p.name == name)) {
+            return Ok(format!("{}\n", profile.get_field(field).unwrap_or_default()));
+        }
+
+        let width = rows.iter().map(|(p, _)| p.name.len()).chain([4]).max().unwrap_or_default();
+        let mut output = format!("{:<width$}  {}\n", "name", field);
+        for (profile, _) in rows {
+            output.push_str(&format!(
+                "{:<width$}  {}\n",
+                profile.name,
+                profile.get_field(field).unwrap_or_default()
+            ));
+        }
+        Ok(output)
+    }
 }
 
 impl Default for ListCommand {
@@ -29,25 +214,93 @@ impl Default for ListCommand {
 #[async_trait]
 impl Command for ListCommand {
     async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        // --output-schema describes the JSON output instead of listing profiles
+        if args.output_schema {
+            println!("{}", serde_json::to_string_pretty(&ProfileJsonSchema::generate())?);
+            return Ok(());
+        }
+
+        // --since/--until filter on, and show, the date each profile was last applied
+        let date_filter = if args.since.is_some() || args.until.is_some() {
+            Some(ActivationDateFilter::parse(args.since.as_deref(), args.until.as_deref())?)
+        } else {
+            None
+        };
+        let show_activations = date_filter.is_some();
+
+        // --count-by-* summarize every active profile instead of listing them
+        if args.count_by_key_type || args.count_by_scope || args.count_by_one_password {
+            let output = Self::format_counts(&Self::counts(args, context)?, &args.output)?;
+            if !args.quiet {
+                println!("{}", output);
+            }
+            return Ok(());
+        }
+
         // Load all profiles from the profile manager
-        let profiles = context.profile_manager.list()?;
-
-        // Apply filtering if needed
-        let filtered_profiles = if let Some(pattern) = args.profile.as_ref() {
-            // Filter profiles by pattern
-            profiles.into_iter()
-                .filter(|p| p.name.contains(pattern) || 
-                           p.git_user_email.contains(pattern) ||
-                           p.git_user_name.as_ref().map_or(false, |n| n.contains(pattern)))
-                .collect()
+        let rows = if args.list_archived {
+            context
+                .profile_manager
+                .list_archived()
+                .map(|profiles| profiles.into_iter().map(|p| (p, None)).collect())
+        } else if show_activations {
+            context.profile_manager.list_with_activation_dates()
         } else {
-            profiles
+            context
+                .profile_manager
+                .list()
+                .map(|profiles| profiles.into_iter().map(|p| (p, None)).collect())
+        }
+        .with_context("while loading profiles")?;
+
+        let filtered = match args.profile.as_deref().filter(|_| args.fuzzy) {
+            Some(pattern) => {
+                let profiles: Vec<Profile> = rows.iter().map(|(p, _)| p.clone()).collect();
+                let matches = context.fuzzy_matcher.find_matches(pattern, &profiles);
+
+                // --explain shows why profiles ranked as they did instead of listing them
+                if args.explain {
+                    if !args.quiet {
+                        for result in &matches {
+                            println!("{}\n{}\n", result.profile.name, context.fuzzy_matcher.explain(result));
+                        }
+                    }
+                    return Ok(());
+                }
+
+                Self::filter_profiles(Self::fuzzy_filter(rows, &matches), None, date_filter.as_ref())
+            }
+            None => Self::filter_profiles(rows, args.profile.as_deref(), date_filter.as_ref()),
         };
 
+        // --field prints bare values for scripts, so skip the usual messages
+        if let Some(field) = args.field {
+            let output = Self::format_field(&filtered, field.field_name(), args.profile.as_deref())?;
+            if !args.quiet {
+                print!("{}", output);
+            }
+            return Ok(());
+        }
+
+        // --output-profile-paths shows where each profile's [includeIf] snippet lives
+        if args.output_profile_paths {
+            let snippets = SnippetManager::from_platform_default()?;
+            let output = Self::format_profile_paths(&filtered, &snippets)
+                .with_context("while locating profile snippets")?;
+            if !args.quiet {
+                print!("{}", output);
+            }
+            return Ok(());
+        }
+
         // Check if no profiles found
-        if filtered_profiles.is_empty() {
+        if filtered.is_empty() {
             if args.profile.is_some() {
                 println!("No profiles found matching pattern: {}", args.profile.as_ref().unwrap());
+            } else if args.list_archived {
+                println!("No archived profiles.");
+            } else if show_activations {
+                println!("No profiles were applied in the given date range.");
             } else {
                 println!("No profiles found.");
                 println!("Use 'git-setup add <name>' to create a profile.");
@@ -55,52 +308,7 @@ impl Command for ListCommand {
             return Ok(());
         }
 
-        // Format output based on requested format
-        let output = match args.output {
-            OutputFormat::Json => {
-                let formatter = JsonFormatter::new();
-                formatter.format_profiles(&filtered_profiles)?
-            }
-            OutputFormat::Yaml => {
-                let formatter = YamlFormatter::new();
-                formatter.format_profiles(&filtered_profiles)?
-            }
-            OutputFormat::Csv => {
-                let formatter = CsvFormatter::new();
-                formatter.format_profiles(&filtered_profiles)?
-            }
-            OutputFormat::Tabular => {
-                // Use simple table format for now
-                let mut output = String::new();
-                output.push_str("NAME\tEMAIL\tKEY_TYPE\tSCOPE\n");
-                for profile in &filtered_profiles {
-                    output.push_str(&format!(
-                        "{}\t{}\t{:?}\t{:?}\n",
-                        profile.name,
-                        profile.git_user_email,
-                        profile.key_type,
-                        profile.scope.as_ref().unwrap_or(&crate::config::types::Scope::Local)
-                    ));
-                }
-                output
-            }
-            OutputFormat::Toml => {
-                // Simple TOML-like output
-                let mut output = String::new();
-                for profile in &filtered_profiles {
-                    output.push_str(&format!("[[profiles]]\n"));
-                    output.push_str(&format!("name = \"{}\"\n", profile.name));
-                    output.push_str(&format!("git_user_email = \"{}\"\n", profile.git_user_email));
-                    if let Some(name) = &profile.git_user_name {
-                        output.push_str(&format!("git_user_name = \"{}\"\n", name));
-                    }
-                    output.push_str(&format!("key_type = \"{:?}\"\n", profile.key_type));
-                    output.push_str(&format!("one_password = {}\n", profile.one_password));
-                    output.push('\n');
-                }
-                output
-            }
-        };
+        let output = Self::format_rows(&filtered, args, show_activations)?;
 
         // Print the formatted output
         if !args.quiet {
@@ -148,6 +356,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -252,6 +469,25 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    /// Test extracting a single field for scripts.
+    #[test]
+    fn test_format_field() {
+        let rows = vec![
+            (create_test_profile("work", "work@example.com"), None),
+            (create_test_profile("workshop", "shop@example.com"), None),
+        ];
+
+        let value = ListCommand::format_field(&rows, "git_user_email", Some("work")).unwrap();
+        assert_eq!(value, "work@example.com\n");
+
+        // A pattern that is not a profile name lists every match
+        let table = ListCommand::format_field(&rows, "one_password", Some("wor")).unwrap();
+        assert_eq!(table, "name      one_password\nwork      false\nworkshop  false\n");
+
+        let err = ListCommand::format_field(&rows, "email", None).unwrap_err();
+        assert!(err.to_string().contains("git_user_email"));
+    }
+
     /// Test filtering profiles by name pattern.
     #[tokio::test]
     async fn test_list_profiles_with_filter() {
@@ -378,7 +614,179 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    /// Test listing profiles with table layout overrides.
+    #[tokio::test]
+    async fn test_list_profiles_table_layout_options() {
+        let profiles = vec![
+            create_test_profile("work", "work@example.com"),
+        ];
+
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager;
+
+        let args = Args {
+            list: true,
+            output: OutputFormat::Tabular,
+            column_width: vec!["email=12".to_string()],
+            no_border: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let cmd = ListCommand::new();
+        assert!(cmd.execute(&args, &context).await.is_ok());
+
+        let args = Args {
+            column_width: vec!["email".to_string()],
+            ..args
+        };
+        assert!(cmd.execute(&args, &context).await.is_err());
+    }
+
+    /// Test filtering profiles by activation date.
+    #[test]
+    fn test_filter_profiles_by_activation_date() {
+        let recent = ActivationRecord::now("work", None);
+        let mut old = ActivationRecord::now("personal", None);
+        old.activated_at = chrono::Utc::now() - chrono::Duration::days(30);
+        let rows = vec![
+            (create_test_profile("work", "work@example.com"), Some(recent)),
+            (create_test_profile("personal", "personal@example.com"), Some(old)),
+            (create_test_profile("unused", "unused@example.com"), None),
+        ];
+        let names = |rows: Vec<(Profile, Option<ActivationRecord>)>| -> Vec<String> {
+            rows.into_iter().map(|(p, _)| p.name).collect()
+        };
+
+        let filter = ActivationDateFilter::parse(Some("7 days ago"), None).unwrap();
+        assert_eq!(names(ListCommand::filter_profiles(rows.clone(), None, Some(&filter))), vec!["work"]);
+
+        let filter = ActivationDateFilter::parse(None, Some("last week")).unwrap();
+        assert_eq!(names(ListCommand::filter_profiles(rows.clone(), None, Some(&filter))), vec!["personal"]);
+
+        let filter = ActivationDateFilter::parse(Some("never"), None).unwrap();
+        assert_eq!(names(ListCommand::filter_profiles(rows.clone(), None, Some(&filter))), vec!["unused"]);
+
+        assert_eq!(names(ListCommand::filter_profiles(rows, Some("example"), None)).len(), 3);
+    }
+
+    /// Test listing with --since in every output format.
+    #[tokio::test]
+    async fn test_list_profiles_since_all_formats() {
+        let mut old = ActivationRecord::now("personal", None);
+        old.activated_at = chrono::Utc::now() - chrono::Duration::days(30);
+        let profile_manager = Arc::new(
+            MockProfileManager::with_profiles(vec![
+                create_test_profile("work", "work@example.com"),
+                create_test_profile("personal", "personal@example.com"),
+            ])
+            .with_activations(vec![ActivationRecord::now("work", None), old]),
+        );
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let cmd = ListCommand::new();
+        let filter = ActivationDateFilter::parse(Some("yesterday"), None).unwrap();
+        let rows = ListCommand::filter_profiles(profile_manager.list_with_activation_dates().unwrap(), None, Some(&filter));
+        for output in [OutputFormat::Tabular, OutputFormat::Json, OutputFormat::Yaml, OutputFormat::Csv, OutputFormat::Toml] {
+            let args = Args {
+                list: true,
+                output,
+                since: Some("yesterday".to_string()),
+                quiet: true,
+                ..Default::default()
+            };
+            assert!(cmd.execute(&args, &context).await.is_ok());
+
+            let rendered = ListCommand::format_rows(&rows, &args, true).unwrap();
+            assert!(rendered.contains("work@example.com"), "{:?}: {}", args.output, rendered);
+            assert!(!rendered.contains("personal@example.com"), "{:?}: {}", args.output, rendered);
+        }
+
+        let args = Args {
+            list: true,
+            since: Some("sometime".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        assert!(cmd.execute(&args, &context).await.is_err());
+
+        let args = Args {
+            since: Some("never".to_string()),
+            until: Some("yesterday".to_string()),
+            ..args
+        };
+        assert!(cmd.execute(&args, &context).await.is_err());
+    }
+
+    /// Test the --count-by-* output in tabular and json formats.
+    #[test]
+    fn test_format_counts() {
+        let profiles = vec![
+            create_test_profile("work", "work@example.com"),
+            Profile {
+                key_type: KeyType::Gpg,
+                one_password: true,
+                ..create_test_profile("oss", "oss@example.com")
+            },
+        ];
+        let counts = ProfileCounts {
+            key_type: Some(ProfileCountsByKeyType::from_profiles(&profiles)),
+            one_password: Some(ProfileCountsByOnePassword::from_profiles(&profiles)),
+            ..Default::default()
+        };
+
+        let output = ListCommand::format_counts(&counts, &OutputFormat::Tabular).unwrap();
+        assert_eq!(output, "SSH: 1, GPG: 1, Gitsign: 0, X509: 0, Total: 2\nYes: 1, No: 1, Total: 2");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&ListCommand::format_counts(&counts, &OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["key_type"]["gpg"], 1);
+        assert_eq!(json["one_password"]["yes"], 1);
+        assert!(json.get("scope").is_none());
+
+        assert!(ListCommand::format_counts(&counts, &OutputFormat::Csv).is_err());
+    }
+
     /// Test that ListCommand implements Send + Sync.
+    /// Test that --fuzzy keeps the matched profiles in match order.
+    #[test]
+    fn test_fuzzy_filter_orders_by_match() {
+        let rows = vec![
+            (create_test_profile("personal-work", "pw@example.com"), None),
+            (create_test_profile("work", "work@example.com"), None),
+            (create_test_profile("personal", "personal@example.com"), None),
+        ];
+        let matches: Vec<MatchResult> = ["personal", "personal-work"]
+            .iter()
+            .map(|name| MatchResult::new(create_test_profile(name, "x@example.com"), 0.9, "test".to_string(), vec![]))
+            .collect();
+
+        let filtered = ListCommand::fuzzy_filter(rows, &matches);
+        let names: Vec<&str> = filtered.iter().map(|(p, _)| p.name.as_str()).collect();
+        assert_eq!(names, ["personal", "personal-work"]);
+    }
+
+    /// Test that profile paths mark the snippets that were not generated.
+    #[test]
+    fn test_format_profile_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("profile-work.gitconfig"), "[user]\n").unwrap();
+        let rows = vec![
+            (create_test_profile("work", "work@example.com"), None),
+            (create_test_profile("personal", "personal@example.com"), None),
+        ];
+
+        let output = ListCommand::format_profile_paths(&rows, &SnippetManager::new(dir.path())).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0].split_whitespace().collect::<Vec<_>>(), ["name", "path"]);
+        assert!(lines[1].starts_with("work "));
+        assert!(lines[1].ends_with("profile-work.gitconfig"));
+        assert!(lines[2].starts_with("personal "));
+        assert!(lines[2].ends_with("profile-personal.gitconfig (not generated)"));
+    }
+
     #[test]
     fn test_list_command_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
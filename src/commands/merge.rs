@@ -0,0 +1,190 @@
+//! Merge command implementation for git-setup-rs.
+//!
+//! `--merge-profiles <BASE> <OVERLAY> <NEW>` stores a new profile combining two
+//! existing ones: fields set in OVERLAY replace those of BASE, and the
+//! repositories and patterns selecting either profile are combined (see
+//! [`Profile::merge`]). With `--dry-run` the merged profile is printed as
+//! TOML, or in the `--output` format if that is json or yaml, instead.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    config::types::Profile,
+    error::{GitSetupError, Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Command implementation for combining two profiles into a new one.
+pub struct MergeCommand;
+
+impl MergeCommand {
+    /// Create a new MergeCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Load the profile named `name`.
+    fn load(name: &str, context: &CommandContext) -> Result<Profile> {
+        context
+            .profile_manager
+            .read(name)
+            .with_context(format!("while loading profile '{}'", name))?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.to_string() })
+    }
+
+    /// `profile` in the `--output` format, defaulting to TOML as in the config file.
+    fn render(profile: &Profile, format: &OutputFormat) -> Result<String> {
+        Ok(match format {
+            OutputFormat::Json => serde_json::to_string_pretty(profile)?,
+            OutputFormat::Yaml => serde_yaml::to_string(profile)?,
+            _ => toml::to_string_pretty(profile)?,
+        })
+    }
+}
+
+impl Default for MergeCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for MergeCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let (base, overlay, new) = match args.merge_profiles.as_deref() {
+            Some([base, overlay, new]) => (base, overlay, new),
+            _ => {
+                return Err(GitSetupError::Git(
+                    "A base, an overlay and a new profile name are required for --merge-profiles".to_string(),
+                ))
+            }
+        };
+
+        let mut merged = Profile::merge(&Self::load(base, context)?, &Self::load(overlay, context)?);
+        merged.name = new.clone();
+
+        if args.dry_run {
+            println!("{}", Self::render(&merged, &args.output)?);
+            return Ok(());
+        }
+
+        if context.profile_manager.exists(new)? {
+            return Err(GitSetupError::DuplicateProfile { name: new.clone() });
+        }
+        context
+            .profile_manager
+            .create(merged)
+            .with_context(format!("while creating profile '{}'", new))?;
+
+        if !args.quiet {
+            println!("✓ Merged profile '{}' into '{}' as '{}'", overlay, base, new);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "merge"
+    }
+
+    fn description(&self) -> &'static str {
+        "Combine two profiles into a new profile"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::config::types::KeyType;
+    use crate::profile::{mock::MockProfileManager, ProfileManager};
+    use std::sync::Arc;
+
+    fn profiles() -> Vec<Profile> {
+        vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                key_type: KeyType::Gpg,
+                signing_key: Some("ABCD1234".to_string()),
+                repos: vec!["~/work/api".to_string()],
+                ..Default::default()
+            },
+            Profile {
+                name: "client".to_string(),
+                git_user_email: "jane@client.com".to_string(),
+                repos: vec!["~/client/app".to_string(), "~/work/api".to_string()],
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn context_with(profiles: Vec<Profile>) -> (CommandContext, Arc<MockProfileManager>) {
+        let manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = manager.clone();
+        (context, manager)
+    }
+
+    fn merge_args(base: &str, overlay: &str, new: &str) -> Args {
+        Args {
+            merge_profiles: Some(vec![base.to_string(), overlay.to_string(), new.to_string()]),
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that the merged profile is stored under the new name.
+    #[tokio::test]
+    async fn test_merge_creates_profile() {
+        let (context, manager) = context_with(profiles());
+
+        MergeCommand::new()
+            .execute(&merge_args("work", "client", "work-client"), &context)
+            .await
+            .unwrap();
+
+        let merged = manager.read("work-client").unwrap().unwrap();
+        assert_eq!(merged.git_user_email, "jane@client.com");
+        assert_eq!(merged.key_type, KeyType::Gpg);
+        assert_eq!(merged.signing_key.as_deref(), Some("ABCD1234"));
+        assert_eq!(merged.repos, vec!["~/work/api", "~/client/app"]);
+        assert_eq!(manager.create_call_count(), 1);
+        assert!(manager.was_called_with_create("work-client"));
+    }
+
+    /// Test that --dry-run stores nothing.
+    #[tokio::test]
+    async fn test_merge_dry_run() {
+        let (context, manager) = context_with(profiles());
+        let args = Args {
+            dry_run: true,
+            ..merge_args("work", "client", "work-client")
+        };
+
+        MergeCommand::new().execute(&args, &context).await.unwrap();
+
+        assert_eq!(manager.create_call_count(), 0);
+        let mut merged = Profile::merge(&profiles()[0], &profiles()[1]);
+        merged.name = "work-client".to_string();
+        let rendered = MergeCommand::render(&merged, &OutputFormat::Tabular).unwrap();
+        assert!(rendered.contains("name = \"work-client\""));
+    }
+
+    /// Test that missing profiles and an existing new name are rejected.
+    #[tokio::test]
+    async fn test_merge_errors() {
+        let (context, manager) = context_with(profiles());
+
+        let result = MergeCommand::new()
+            .execute(&merge_args("work", "missing", "new"), &context)
+            .await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+
+        let result = MergeCommand::new()
+            .execute(&merge_args("work", "client", "client"), &context)
+            .await;
+        assert!(matches!(result, Err(GitSetupError::DuplicateProfile { .. })));
+        assert_eq!(manager.create_call_count(), 0);
+        assert_eq!(manager.update_call_count(), 0);
+    }
+}
@@ -10,6 +10,43 @@ pub mod add;
 pub mod delete;
 pub mod edit;
 pub mod import;
+pub mod completions;
+pub mod config_dump;
+pub mod undo;
+pub mod check_unused;
+pub mod env_inject;
+pub mod detect;
+pub mod diff_applied;
+pub mod field_scope;
+pub mod keygen;
+pub mod gpg_agent;
+pub mod hooks;
+pub mod key_cleanup;
+pub mod status;
+pub mod duplicate_check;
+pub mod check_keys;
+pub mod import_op_gpg;
+pub mod bulk_update;
+pub mod archive;
+pub mod generate_template;
+pub mod stats;
+pub mod import_copilot;
+pub mod health_score;
+pub mod push_config;
+pub mod sort_by;
+pub mod profiles_md;
+pub mod snippets;
+pub mod validate_schema;
+pub mod allowed_signers;
+pub mod capabilities;
+pub mod duplicate;
+pub mod merge;
+pub mod export;
+pub mod rename;
+pub mod diff;
+pub mod validate;
+pub mod rotate_key;
+pub mod group;
 
 use crate::{
     cli::Args,
@@ -19,6 +56,7 @@ use crate::{
     external::{
         git::GitWrapper,
         onepassword::OnePasswordWrapper,
+        bitwarden::BitwardenWrapper,
         gpg::GpgWrapper,
     },
     detection::ProfileDetector,
@@ -26,6 +64,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 /// Command context containing all dependencies needed by commands.
 ///
@@ -36,9 +75,12 @@ pub struct CommandContext {
     pub profile_manager: Arc<dyn ProfileManager>,
     pub git_wrapper: Arc<dyn GitWrapper>,
     pub onepassword_wrapper: Arc<dyn OnePasswordWrapper>,
+    pub bitwarden_wrapper: Arc<dyn BitwardenWrapper>,
     pub gpg_wrapper: Arc<dyn GpgWrapper>,
     pub profile_detector: Arc<dyn ProfileDetector>,
     pub fuzzy_matcher: Arc<dyn FuzzyMatcher>,
+    /// Cancelled on Ctrl-C; long-running commands stop at the next safe point
+    pub cancellation_token: CancellationToken,
 }
 
 /// Builder for creating CommandContext instances.
@@ -49,9 +91,11 @@ pub struct CommandContextBuilder {
     profile_manager: Option<Arc<dyn ProfileManager>>,
     git_wrapper: Option<Arc<dyn GitWrapper>>,
     onepassword_wrapper: Option<Arc<dyn OnePasswordWrapper>>,
+    bitwarden_wrapper: Option<Arc<dyn BitwardenWrapper>>,
     gpg_wrapper: Option<Arc<dyn GpgWrapper>>,
     profile_detector: Option<Arc<dyn ProfileDetector>>,
     fuzzy_matcher: Option<Arc<dyn FuzzyMatcher>>,
+    cancellation_token: Option<CancellationToken>,
 }
 
 impl CommandContextBuilder {
@@ -61,9 +105,11 @@ impl CommandContextBuilder {
             profile_manager: None,
             git_wrapper: None,
             onepassword_wrapper: None,
+            bitwarden_wrapper: None,
             gpg_wrapper: None,
             profile_detector: None,
             fuzzy_matcher: None,
+            cancellation_token: None,
         }
     }
 
@@ -85,6 +131,12 @@ impl CommandContextBuilder {
         self
     }
 
+    /// Set the Bitwarden wrapper dependency.
+    pub fn with_bitwarden_wrapper(mut self, bitwarden_wrapper: Arc<dyn BitwardenWrapper>) -> Self {
+        self.bitwarden_wrapper = Some(bitwarden_wrapper);
+        self
+    }
+
     /// Set the GPG wrapper dependency.
     pub fn with_gpg_wrapper(mut self, gpg_wrapper: Arc<dyn GpgWrapper>) -> Self {
         self.gpg_wrapper = Some(gpg_wrapper);
@@ -103,6 +155,12 @@ impl CommandContextBuilder {
         self
     }
 
+    /// Set the token cancelling long-running commands, instead of one that is never cancelled.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
     /// Build the CommandContext.
     ///
     /// # Errors
@@ -115,12 +173,15 @@ impl CommandContextBuilder {
                 .ok_or_else(|| crate::error::GitSetupError::Git("GitWrapper not provided".to_string()))?,
             onepassword_wrapper: self.onepassword_wrapper
                 .ok_or_else(|| crate::error::GitSetupError::Git("OnePasswordWrapper not provided".to_string()))?,
+            bitwarden_wrapper: self.bitwarden_wrapper
+                .ok_or_else(|| crate::error::GitSetupError::Git("BitwardenWrapper not provided".to_string()))?,
             gpg_wrapper: self.gpg_wrapper
                 .ok_or_else(|| crate::error::GitSetupError::Git("GpgWrapper not provided".to_string()))?,
             profile_detector: self.profile_detector
                 .ok_or_else(|| crate::error::GitSetupError::Git("ProfileDetector not provided".to_string()))?,
             fuzzy_matcher: self.fuzzy_matcher
                 .ok_or_else(|| crate::error::GitSetupError::Git("FuzzyMatcher not provided".to_string()))?,
+            cancellation_token: self.cancellation_token.unwrap_or_default(),
         })
     }
 }
@@ -162,6 +223,7 @@ mod tests {
         external::{
             git::MockGitWrapper,
             onepassword::MockOnePasswordWrapper,
+            bitwarden::MockBitwardenWrapper,
             gpg::MockGpgWrapper,
         },
         detection::{DetectionResult, MockProfileDetector},
@@ -185,6 +247,7 @@ mod tests {
             .with_profile_manager(Arc::new(MockProfileManager::new()))
             .with_git_wrapper(Arc::new(MockGitWrapper::new()))
             .with_onepassword_wrapper(Arc::new(MockOnePasswordWrapper::new()))
+            .with_bitwarden_wrapper(Arc::new(MockBitwardenWrapper::new()))
             .with_gpg_wrapper(Arc::new(MockGpgWrapper::new()))
             .with_profile_detector(Arc::new(MockProfileDetector::new()))
             .with_fuzzy_matcher(Arc::new(MockFuzzyMatcher::new()))
@@ -211,6 +274,7 @@ mod tests {
             .with_profile_manager(Arc::new(MockProfileManager::new()))
             .with_git_wrapper(Arc::new(MockGitWrapper::new()))
             .with_onepassword_wrapper(Arc::new(MockOnePasswordWrapper::new()))
+            .with_bitwarden_wrapper(Arc::new(MockBitwardenWrapper::new()))
             .with_gpg_wrapper(Arc::new(MockGpgWrapper::new()))
             .with_profile_detector(Arc::new(MockProfileDetector::new()))
             .with_fuzzy_matcher(Arc::new(MockFuzzyMatcher::new()))
@@ -226,6 +290,7 @@ mod tests {
             .with_profile_manager(Arc::new(MockProfileManager::new()))
             .with_git_wrapper(Arc::new(MockGitWrapper::new()))
             .with_onepassword_wrapper(Arc::new(MockOnePasswordWrapper::new()))
+            .with_bitwarden_wrapper(Arc::new(MockBitwardenWrapper::new()))
             .with_gpg_wrapper(Arc::new(MockGpgWrapper::new()))
             .with_profile_detector(Arc::new(MockProfileDetector::new()))
             .with_fuzzy_matcher(Arc::new(MockFuzzyMatcher::new()))
@@ -0,0 +1,128 @@
+//! Profiles-md command implementation for git-setup-rs.
+//!
+//! `--profiles-md [PATH]` writes a Markdown summary of the active profiles to
+//! PATH (default `PROFILES.md`): how many use each key type, each git config
+//! scope and 1Password, as tables ready to commit next to a team's setup docs.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Command implementation for the Markdown profile summary.
+pub struct ProfilesMdCommand;
+
+impl ProfilesMdCommand {
+    /// Create a new ProfilesMdCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render one count table headed by `heading`, ending with the total.
+    fn table(heading: &str, rows: Vec<(&str, usize)>, total: usize) -> String {
+        let mut lines = vec![
+            format!("| {} | Profiles |", heading),
+            "|---|---:|".to_string(),
+        ];
+        for (label, count) in rows {
+            lines.push(format!("| {} | {} |", label, count));
+        }
+        lines.push(format!("| **Total** | **{}** |", total));
+        lines.join("\n")
+    }
+
+    /// Render the Markdown summary from the profile store's counts.
+    pub fn render(context: &CommandContext) -> Result<String> {
+        let manager = &context.profile_manager;
+        let by_key_type = manager.count_by_key_type()?;
+        let by_scope = manager.count_by_scope()?;
+        let by_one_password = manager.count_by_one_password()?;
+
+        let sections = [
+            "# Profiles".to_string(),
+            format!("{} active profiles.", by_key_type.total()),
+            "## By key type".to_string(),
+            Self::table("Key type", by_key_type.rows(), by_key_type.total()),
+            "## By scope".to_string(),
+            Self::table("Scope", by_scope.rows(), by_scope.total()),
+            "## 1Password".to_string(),
+            Self::table("Uses 1Password", by_one_password.rows(), by_one_password.total()),
+        ];
+        Ok(format!("{}\n", sections.join("\n\n")))
+    }
+}
+
+impl Default for ProfilesMdCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ProfilesMdCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let path = args.profiles_md.as_deref().unwrap_or("PROFILES.md");
+        let markdown = Self::render(context).with_context("while counting profiles")?;
+        std::fs::write(path, markdown).with_context(format!("while writing '{}'", path))?;
+
+        if !args.quiet {
+            println!("✓ Wrote profile summary to {}", path);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "profiles-md"
+    }
+
+    fn description(&self) -> &'static str {
+        "Write a Markdown summary of the profile counts"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::{KeyType, Profile},
+        profile::mock::MockProfileManager,
+    };
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_writes_profiles_md() {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "work".to_string(),
+                key_type: KeyType::Gpg,
+                one_password: true,
+                ..Default::default()
+            },
+            Profile {
+                name: "personal".to_string(),
+                ..Default::default()
+            },
+        ]));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("PROFILES.md");
+        let args = Args {
+            profiles_md: Some(path.display().to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        ProfilesMdCommand::new().execute(&args, &context).await.unwrap();
+
+        let markdown = std::fs::read_to_string(&path).unwrap();
+        assert!(markdown.starts_with("# Profiles\n\n2 active profiles."));
+        assert!(markdown.contains("| GPG | 1 |"));
+        assert!(markdown.contains("| Yes | 1 |"));
+        assert!(markdown.contains("| **Total** | **2** |"));
+    }
+}
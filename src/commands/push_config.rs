@@ -0,0 +1,115 @@
+//! Push-config command implementation for git-setup-rs.
+//!
+//! `--push-config` shares git config settings with a team for onboarding: the
+//! `core.*` and `alias.*` keys of the repository (or of `--global`/`--system`
+//! config) are stored as a note under `refs/notes/gitconfig` and pushed to
+//! `--remote`. Identities, credentials and signing keys are never shared.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{Result, ResultExt},
+    external::git::{GitConfigScope, DEFAULT_SHARED_CONFIG_KEYS},
+};
+use async_trait::async_trait;
+
+/// Remote the config is pushed to when `--remote` is not given.
+const DEFAULT_REMOTE: &str = "origin";
+
+/// Command implementation for sharing git config on a remote.
+pub struct PushConfigCommand;
+
+impl PushConfigCommand {
+    /// Create a new PushConfigCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Config scope to share, from `--global`/`--system`.
+    fn scope(args: &Args) -> GitConfigScope {
+        if args.global {
+            GitConfigScope::Global
+        } else if args.system {
+            GitConfigScope::System
+        } else {
+            GitConfigScope::Local
+        }
+    }
+}
+
+impl Default for PushConfigCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for PushConfigCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let remote = args.remote.as_deref().unwrap_or(DEFAULT_REMOTE);
+        let whitelist: Vec<&str> = if args.share_key.is_empty() {
+            DEFAULT_SHARED_CONFIG_KEYS.to_vec()
+        } else {
+            args.share_key.iter().map(String::as_str).collect()
+        };
+
+        context
+            .git_wrapper
+            .push_config_to_remote(Self::scope(args), remote, &whitelist)
+            .with_context(format!("while pushing config to '{}'", remote))?;
+
+        if !args.quiet {
+            println!("✓ Pushed {} config to '{}'", whitelist.join(", "), remote);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "push-config"
+    }
+
+    fn description(&self) -> &'static str {
+        "Share git config settings with a team through a remote"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::tests::create_test_context, external::git::MockGitWrapper};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// Test that the whitelisted keys of the chosen scope are pushed to the remote.
+    #[tokio::test]
+    async fn test_push_config() {
+        let git = Arc::new(MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Global,
+            HashMap::from([
+                ("core.editor".to_string(), "vim".to_string()),
+                ("pull.rebase".to_string(), "true".to_string()),
+                ("user.email".to_string(), "jane@example.com".to_string()),
+            ]),
+        ));
+        let mut context = create_test_context();
+        context.git_wrapper = git.clone();
+
+        let args = Args {
+            push_config: true,
+            global: true,
+            quiet: true,
+            ..Default::default()
+        };
+        PushConfigCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(git.remote_config_note("origin").as_deref(), Some("core.editor=vim"));
+
+        let args = Args {
+            remote: Some("team".to_string()),
+            share_key: vec!["pull.*".to_string(), "user.*".to_string()],
+            ..args
+        };
+        PushConfigCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(git.remote_config_note("team").as_deref(), Some("pull.rebase=true"));
+    }
+}
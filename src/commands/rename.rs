@@ -0,0 +1,264 @@
+//! Rename command implementation for git-setup-rs.
+//!
+//! `--rename <OLD> <NEW>` renames a profile. Unlike an edit, it also rewrites
+//! the `repos`, `match_patterns` and `include_if_dirs` entries of the other
+//! profiles that have OLD as a path segment, in the same transaction, so
+//! either the rename and all rewrites are stored or nothing is. `[includeIf]`
+//! sections of the git config still pointing at OLD are only warned about.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::{snippets::SnippetManager, types::Profile},
+    error::{GitSetupError, Result, ResultExt},
+    profile::{transaction::TransactionalProfileManager, ProfileManager},
+};
+use async_trait::async_trait;
+
+/// Command implementation for renaming a profile.
+pub struct RenameCommand;
+
+impl RenameCommand {
+    /// Create a new RenameCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Rename `old` to `new` in `manager`, returning the number of references rewritten.
+    fn rename(manager: &dyn ProfileManager, old: &str, new: &str) -> Result<usize> {
+        let mut profile = manager
+            .read(old)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: old.to_string() })?;
+        if manager.exists(new)? {
+            return Err(GitSetupError::DuplicateProfile { name: new.to_string() });
+        }
+
+        profile.name = new.to_string();
+        manager.create(profile)?;
+        manager.delete(old)?;
+
+        let mut rewritten = 0;
+        for mut other in manager.list_all()?.into_iter().filter(|p| p.name != new) {
+            let count = rewrite_references(&mut other, old, new);
+            if count > 0 {
+                let name = other.name.clone();
+                manager.update(&name, other)?;
+                rewritten += count;
+            }
+        }
+        Ok(rewritten)
+    }
+
+    /// Git config `[includeIf]` entries that still reference `old`.
+    ///
+    /// Reading the git config is best effort: if it fails, nothing is reported.
+    fn stale_include_ifs(context: &CommandContext, old: &str) -> Vec<(String, String)> {
+        let snippet = SnippetManager::from_platform_default()
+            .ok()
+            .map(|snippets| snippets.snippet_path(old).display().to_string());
+        let Ok(config) = context.git_wrapper.get_all_config(None) else {
+            return Vec::new();
+        };
+
+        let mut stale: Vec<(String, String)> = config
+            .into_iter()
+            .filter(|(key, value)| {
+                key.to_lowercase().starts_with("includeif.")
+                    && (rewrite_segments(key, old, "").is_some()
+                        || rewrite_segments(value, old, "").is_some()
+                        || snippet.as_deref() == Some(value.as_str()))
+            })
+            .collect();
+        stale.sort();
+        stale
+    }
+}
+
+/// Replace the path segments of `value` equal to `old` with `new`.
+///
+/// Returns `None` if no segment matches, so `~/work-old/` is not touched when
+/// renaming `work`.
+fn rewrite_segments(value: &str, old: &str, new: &str) -> Option<String> {
+    let mut changed = false;
+    let rewritten: Vec<&str> = value
+        .split('/')
+        .map(|segment| {
+            if segment == old {
+                changed = true;
+                new
+            } else {
+                segment
+            }
+        })
+        .collect();
+    changed.then(|| rewritten.join("/"))
+}
+
+/// Rewrite the references to `old` in `profile`, returning how many changed.
+fn rewrite_references(profile: &mut Profile, old: &str, new: &str) -> usize {
+    let mut count = 0;
+    for entry in profile
+        .repos
+        .iter_mut()
+        .chain(profile.match_patterns.iter_mut())
+        .chain(profile.include_if_dirs.iter_mut())
+    {
+        if let Some(rewritten) = rewrite_segments(entry, old, new) {
+            *entry = rewritten;
+            count += 1;
+        }
+    }
+    count
+}
+
+impl Default for RenameCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for RenameCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let (old, new) = match args.rename.as_deref() {
+            Some([old, new]) => (old, new),
+            _ => {
+                return Err(GitSetupError::Git(
+                    "The current and the new profile name are required for --rename".to_string(),
+                ))
+            }
+        };
+        if old == new {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("profile '{}' already has that name", old),
+            });
+        }
+
+        let mut rewritten = 0;
+        TransactionalProfileManager::new(context.profile_manager.clone())
+            .transaction(|manager| {
+                rewritten = Self::rename(manager, old, new)?;
+                Ok(())
+            })
+            .with_context(format!("while renaming profile '{}' to '{}'", old, new))?;
+
+        if !args.quiet {
+            println!("✓ Renamed profile '{}' to '{}'", old, new);
+            if rewritten > 0 {
+                println!("  Rewrote {} reference(s) in other profiles", rewritten);
+            }
+        }
+        for (key, value) in Self::stale_include_ifs(context, old) {
+            eprintln!("⚠ Git config {} = {} still refers to profile '{}'; update it by hand", key, value, old);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "rename"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rename a profile and the references to it in other profiles"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::external::git::MockGitWrapper;
+    use crate::profile::mock::MockProfileManager;
+    use std::sync::Arc;
+
+    fn profile(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            ..Default::default()
+        }
+    }
+
+    fn context_with(profiles: Vec<Profile>) -> (CommandContext, Arc<MockProfileManager>) {
+        let manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
+        let mut context = create_test_context();
+        context.profile_manager = manager.clone();
+        (context, manager)
+    }
+
+    fn rename_args(old: &str, new: &str) -> Args {
+        Args {
+            rename: Some(vec![old.to_string(), new.to_string()]),
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that references in other profiles are rewritten along with the name.
+    #[tokio::test]
+    async fn test_rename_rewrites_cross_profile_references() {
+        let oss = Profile {
+            repos: vec!["~/code/work/api".to_string(), "~/code/work-old/web".to_string()],
+            match_patterns: vec!["github.com/work/*".to_string()],
+            include_if_dirs: vec!["~/src/work/".to_string(), "~/src/oss/".to_string()],
+            ..profile("oss")
+        };
+        let work = Profile {
+            signing_key: Some("~/.ssh/id_work".to_string()),
+            ..profile("work")
+        };
+        let (context, manager) = context_with(vec![work, oss, profile("personal")]);
+
+        RenameCommand::new()
+            .execute(&rename_args("work", "acme"), &context)
+            .await
+            .unwrap();
+
+        assert!(manager.was_called_with_create("acme"));
+        assert!(manager.was_called_with_delete("work"));
+        let renamed = manager.read("acme").unwrap().unwrap();
+        assert_eq!(renamed.signing_key.as_deref(), Some("~/.ssh/id_work"));
+
+        let oss = manager.read("oss").unwrap().unwrap();
+        assert_eq!(oss.repos, vec!["~/code/acme/api", "~/code/work-old/web"]);
+        assert_eq!(oss.match_patterns, vec!["github.com/acme/*"]);
+        assert_eq!(oss.include_if_dirs, vec!["~/src/acme/", "~/src/oss/"]);
+        assert!(!manager.was_called_with_update("personal"));
+    }
+
+    /// Test that missing, existing and unchanged names are rejected.
+    #[tokio::test]
+    async fn test_rename_errors() {
+        let (context, manager) = context_with(vec![profile("work"), profile("personal")]);
+
+        let result = RenameCommand::new().execute(&rename_args("missing", "acme"), &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::ProfileNotFound { .. }));
+
+        let result = RenameCommand::new().execute(&rename_args("work", "personal"), &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::DuplicateProfile { .. }));
+
+        let result = RenameCommand::new().execute(&rename_args("work", "work"), &context).await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        assert_eq!(manager.create_call_count(), 0);
+        assert_eq!(manager.delete_call_count(), 0);
+    }
+
+    /// Test that includeIf entries naming the old profile are found, but not lookalikes.
+    #[test]
+    fn test_stale_include_ifs() {
+        let git = MockGitWrapper::new();
+        git.mock_set_config("includeif.gitdir:~/work/.path", "~/.gitconfig-acme");
+        git.mock_set_config("includeif.gitdir:~/oss/.path", "~/.config/work/gitconfig");
+        git.mock_set_config("includeif.gitdir:~/work-old/.path", "~/.gitconfig-old");
+        git.mock_set_config("user.name", "work");
+        let mut context = create_test_context();
+        context.git_wrapper = Arc::new(git);
+
+        let stale = RenameCommand::stale_include_ifs(&context, "work");
+        let keys: Vec<&str> = stale.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["includeif.gitdir:~/oss/.path", "includeif.gitdir:~/work/.path"]);
+
+        context.git_wrapper = Arc::new(MockGitWrapper::new().with_failure());
+        assert!(RenameCommand::stale_include_ifs(&context, "work").is_empty());
+    }
+}
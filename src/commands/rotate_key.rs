@@ -0,0 +1,332 @@
+//! Key rotation command implementation for git-setup-rs.
+//!
+//! `--rotate-key <PROFILE>` generates a new SSH key in the vault of the
+//! profile's 1Password key, switches the profile and the git config to it and
+//! records the replaced item in `rotated_key_item`. The old item is kept, so
+//! commits can still be verified while the new public key is rolled out;
+//! `--rotate-key <PROFILE> --delete-old` deletes it afterward. Profiles whose
+//! key store is not 1Password are rejected.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::types::{KeyType, Profile, SecretStoreKind},
+    error::{GitSetupError, Result, ResultExt},
+    external::git::GitConfigScope,
+};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+
+/// Title of the key replacing `title` on `date`, e.g. `Work Key (rotated 2024-05-01)`.
+///
+/// The date of an earlier rotation is replaced rather than appended to.
+fn rotated_title(title: &str, date: NaiveDate) -> String {
+    let base = match title.rsplit_once(" (rotated ") {
+        Some((base, rest)) if rest.ends_with(')') => base,
+        _ => title,
+    };
+    format!("{} (rotated {})", base, date.format("%Y-%m-%d"))
+}
+
+/// Command implementation for rotating a profile's 1Password SSH key.
+pub struct RotateKeyCommand;
+
+impl RotateKeyCommand {
+    /// Create a new RotateKeyCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Replace the profile's key with a new one titled `new_title`, returning the old item ID.
+    fn rotate(&self, profile: &mut Profile, new_title: &str, context: &CommandContext) -> Result<String> {
+        let (title, vault) = match (&profile.ssh_key_title, &profile.vault_name) {
+            (Some(title), Some(vault)) => (title.clone(), vault.clone()),
+            _ => {
+                return Err(GitSetupError::InvalidProfile {
+                    reason: format!("profile '{}' has no 1Password SSH key to rotate", profile.name),
+                });
+            }
+        };
+        if let Some(item_id) = &profile.rotated_key_item {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!(
+                    "profile '{}' still has the replaced key item '{}'; remove it with --delete-old first",
+                    profile.name, item_id
+                ),
+            });
+        }
+
+        let op = &context.onepassword_wrapper;
+        let old = op.get_ssh_key(&title, &vault)?.ok_or_else(|| {
+            GitSetupError::OnePassword(format!("SSH key '{}' not found in vault '{}'", title, vault))
+        })?;
+        if op.get_ssh_key(new_title, &vault)?.is_some() {
+            return Err(GitSetupError::OnePassword(format!(
+                "an SSH key titled '{}' already exists in vault '{}'; choose another with --new-title",
+                new_title, vault
+            )));
+        }
+
+        let new_id = op
+            .create_ssh_key(new_title, &vault)
+            .with_context(format!("while creating SSH key '{}' in vault '{}'", new_title, vault))?;
+        let public_key = op.get_ssh_public_key(&new_id)?;
+
+        profile.key_type = KeyType::Ssh;
+        profile.signing_key = Some(public_key);
+        profile.ssh_key_title = Some(new_title.to_string());
+        profile.rotated_key_item = Some(old.id.clone());
+        Ok(old.id)
+    }
+
+    /// Delete the item recorded by the last rotation, returning its ID.
+    fn delete_old(&self, profile: &mut Profile, context: &CommandContext) -> Result<String> {
+        let item_id = profile.rotated_key_item.take().ok_or_else(|| GitSetupError::InvalidProfile {
+            reason: format!("profile '{}' has no key replaced by --rotate-key", profile.name),
+        })?;
+        context.onepassword_wrapper.delete_item(&item_id)?;
+        Ok(item_id)
+    }
+}
+
+impl Default for RotateKeyCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for RotateKeyCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args.rotate_key.as_ref().ok_or_else(|| {
+            GitSetupError::Git("Profile name is required for --rotate-key".to_string())
+        })?;
+        let mut profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+        if profile.key_store() != Some(SecretStoreKind::OnePassword) {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!(
+                    "profile '{}' does not keep its key in 1Password; --rotate-key only rotates 1Password keys",
+                    name
+                ),
+            });
+        }
+
+        if args.delete_old {
+            let item_id = self
+                .delete_old(&mut profile, context)
+                .with_context(format!("while deleting the replaced key of profile '{}'", name))?;
+            context
+                .profile_manager
+                .update(name, profile)
+                .with_context(format!("while updating profile '{}'", name))?;
+            if !args.quiet {
+                println!("✓ Deleted replaced 1Password item '{}' of profile '{}'", item_id, name);
+            }
+            return Ok(());
+        }
+
+        let new_title = match (&args.new_title, &profile.ssh_key_title) {
+            (Some(title), _) => title.clone(),
+            (None, Some(title)) => rotated_title(title, Utc::now().date_naive()),
+            (None, None) => String::new(),
+        };
+        let old_id = self
+            .rotate(&mut profile, &new_title, context)
+            .with_context(format!("while rotating the key of profile '{}'", name))?;
+        context
+            .profile_manager
+            .update(name, profile.clone())
+            .with_context(format!("while updating profile '{}'", name))?;
+
+        let scope = if args.global {
+            GitConfigScope::Global
+        } else if args.system {
+            GitConfigScope::System
+        } else {
+            let in_repo = context.git_wrapper.is_inside_work_tree()?;
+            profile.effective_scope(in_repo).into()
+        };
+        let signing_key = profile.signing_key.as_deref().unwrap_or_default();
+        context
+            .git_wrapper
+            .configure_ssh_signing(signing_key, profile.allowed_signers.as_deref(), scope)
+            .with_context("while configuring git to sign with the new key")?;
+
+        if !args.quiet {
+            println!("✓ Profile '{}' now signs with the new key '{}'", name, new_title);
+            println!(
+                "  The old item '{}' was kept; delete it with --rotate-key {} --delete-old",
+                old_id, name
+            );
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "rotate-key"
+    }
+
+    fn description(&self) -> &'static str {
+        "Replace a profile's 1Password SSH key with a new one"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        external::{
+            git::MockGitWrapper,
+            onepassword::{MockOnePasswordWrapper, SshKeyItem, Vault},
+        },
+        profile::{mock::MockProfileManager, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    fn ssh_key_item(id: &str, title: &str, public_key: &str) -> SshKeyItem {
+        SshKeyItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            vault: Vault {
+                id: "vault2".to_string(),
+                name: "Work".to_string(),
+            },
+            category: "SSH_KEY".to_string(),
+            public_key: Some(public_key.to_string()),
+            private_key: None,
+        }
+    }
+
+    fn test_context(
+        onepassword: Arc<MockOnePasswordWrapper>,
+    ) -> (CommandContext, Arc<MockProfileManager>, Arc<MockGitWrapper>) {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            one_password: true,
+            signing_key: Some("ssh-ed25519 AAAAC3...old".to_string()),
+            ssh_key_title: Some("Work Key".to_string()),
+            vault_name: Some("Work".to_string()),
+            ..Default::default()
+        }]).track_calls(true));
+        let git = Arc::new(MockGitWrapper::new());
+        let mut context = create_test_context();
+        context.onepassword_wrapper = onepassword;
+        context.profile_manager = profile_manager.clone();
+        context.git_wrapper = git.clone();
+        (context, profile_manager, git)
+    }
+
+    fn rotate_args(new_title: Option<&str>, delete_old: bool) -> Args {
+        Args {
+            rotate_key: Some("work".to_string()),
+            new_title: new_title.map(str::to_string),
+            delete_old,
+            global: true,
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that the profile and git config switch to the new key and the old item is kept until --delete-old.
+    #[tokio::test]
+    async fn test_rotate_key_then_delete_old() {
+        let onepassword = Arc::new(
+            MockOnePasswordWrapper::new().with_ssh_keys(vec![ssh_key_item("item1", "Work Key", "ssh-ed25519 AAAAC3...old")]),
+        );
+        let (context, profile_manager, git) = test_context(onepassword.clone());
+
+        RotateKeyCommand::new()
+            .execute(&rotate_args(Some("Work Key 2"), false), &context)
+            .await
+            .unwrap();
+
+        let profile = profile_manager.read("work").unwrap().unwrap();
+        assert_eq!(profile.signing_key.as_deref(), Some("ssh-ed25519 AAAAC3...ssh-key-work-key-2"));
+        assert_eq!(profile.ssh_key_title.as_deref(), Some("Work Key 2"));
+        assert_eq!(profile.rotated_key_item.as_deref(), Some("item1"));
+        git.assert_signing_was_configured(KeyType::Ssh);
+        assert!(!onepassword.was_item_deleted("item1"));
+        assert_eq!(profile_manager.update_call_count(), 1);
+
+        // A second rotation has to wait until the replaced item is gone
+        let result = RotateKeyCommand::new()
+            .execute(&rotate_args(Some("Work Key 3"), false), &context)
+            .await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::InvalidProfile { .. }));
+        assert_eq!(profile_manager.update_call_count(), 1);
+
+        RotateKeyCommand::new()
+            .execute(&rotate_args(None, true), &context)
+            .await
+            .unwrap();
+
+        assert!(onepassword.was_item_deleted("item1"));
+        let profile = profile_manager.read("work").unwrap().unwrap();
+        assert_eq!(profile.rotated_key_item, None);
+        assert_eq!(profile.ssh_key_title.as_deref(), Some("Work Key 2"));
+
+        let result = RotateKeyCommand::new().execute(&rotate_args(None, true), &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::InvalidProfile { .. }));
+        assert_eq!(profile_manager.update_call_count(), 2);
+    }
+
+    /// Test that a missing key or a taken title leaves the profile unchanged.
+    #[tokio::test]
+    async fn test_rotate_key_errors() {
+        let onepassword = Arc::new(MockOnePasswordWrapper::new().with_ssh_keys(vec![
+            ssh_key_item("item1", "Work Key", "ssh-ed25519 AAAAC3...old"),
+            ssh_key_item("item2", "Work Key 2", "ssh-ed25519 AAAAC3...other"),
+        ]));
+        let (context, profile_manager, git) = test_context(onepassword);
+
+        let result = RotateKeyCommand::new()
+            .execute(&rotate_args(Some("Work Key 2"), false), &context)
+            .await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::OnePassword(_)));
+
+        let (context, _, _) = test_context(Arc::new(MockOnePasswordWrapper::new()));
+        let result = RotateKeyCommand::new().execute(&rotate_args(None, false), &context).await;
+        assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::OnePassword(_)));
+
+        assert_eq!(profile_manager.update_call_count(), 0);
+        git.assert_no_calls_made();
+    }
+
+    /// Test that a profile keeping its key in Bitwarden is rejected before 1Password is touched.
+    #[tokio::test]
+    async fn test_rotate_key_rejects_bitwarden_profile() {
+        let onepassword = Arc::new(
+            MockOnePasswordWrapper::new().with_ssh_keys(vec![ssh_key_item("item1", "Work Key", "ssh-ed25519 AAAAC3...old")]),
+        );
+        let (context, profile_manager, git) = test_context(onepassword.clone());
+        let mut profile = profile_manager.read("work").unwrap().unwrap();
+        profile.secret_store = Some(SecretStoreKind::Bitwarden);
+        profile.rotated_key_item = Some("item1".to_string());
+        profile_manager.update("work", profile).unwrap();
+
+        for delete_old in [false, true] {
+            let result = RotateKeyCommand::new()
+                .execute(&rotate_args(Some("Work Key 2"), delete_old), &context)
+                .await;
+            assert!(matches!(result.unwrap_err().root_cause(), GitSetupError::InvalidProfile { .. }));
+        }
+
+        // Only the update made by the test itself
+        assert_eq!(profile_manager.update_call_count(), 1);
+        assert!(!onepassword.was_item_deleted("item1"));
+        git.assert_no_calls_made();
+    }
+
+    #[test]
+    fn test_rotated_title() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert_eq!(rotated_title("Work Key", date), "Work Key (rotated 2024-05-01)");
+        assert_eq!(rotated_title("Work Key (rotated 2023-01-15)", date), "Work Key (rotated 2024-05-01)");
+        assert_eq!(rotated_title("Key (rotated soon", date), "Key (rotated soon (rotated 2024-05-01)");
+    }
+}
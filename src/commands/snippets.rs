@@ -0,0 +1,113 @@
+//! Snippets command implementation for git-setup-rs.
+//!
+//! `--list-snippets` shows the gitconfig snippet of every profile, with the
+//! time it was last generated, to troubleshoot `[includeIf]` setups that
+//! point at a missing or stale file.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    config::snippets::{SnippetInfo, SnippetManager},
+    error::{Result, ResultExt},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+
+/// Command implementation for listing profile snippets.
+pub struct SnippetsListCommand;
+
+impl SnippetsListCommand {
+    /// Create a new SnippetsListCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render one line per snippet: profile name, modification time and path.
+    pub fn format(snippets: &[SnippetInfo]) -> String {
+        let width = snippets.iter().map(|s| s.profile_name.len()).chain([7]).max().unwrap_or_default();
+        let mut output = format!("{:<width$}  {:<16}  {}\n", "profile", "modified", "path");
+        for snippet in snippets {
+            let modified = match snippet.modified {
+                Some(time) => DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M").to_string(),
+                None => "(not generated)".to_string(),
+            };
+            output.push_str(&format!(
+                "{:<width$}  {:<16}  {}\n",
+                snippet.profile_name,
+                modified,
+                snippet.path.display()
+            ));
+        }
+        output
+    }
+}
+
+impl Default for SnippetsListCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for SnippetsListCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let profiles = context.profile_manager.list().with_context("while loading profiles")?;
+        let manager = SnippetManager::from_platform_default()?
+            .with_profiles(profiles.into_iter().map(|p| p.name));
+        let snippets = manager.list_snippets().with_context(format!(
+            "while listing snippets in '{}'",
+            manager.dir().display()
+        ))?;
+
+        if !args.quiet {
+            if snippets.is_empty() {
+                println!("No profile snippets in {}", manager.dir().display());
+            } else {
+                print!("{}", Self::format(&snippets));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "snippets-list"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the generated gitconfig snippets of the profiles"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_snippets() {
+        let snippets = vec![
+            SnippetInfo {
+                profile_name: "work".to_string(),
+                path: PathBuf::from("/cfg/profile-work.gitconfig"),
+                exists: true,
+                modified: Some(std::time::SystemTime::now()),
+            },
+            SnippetInfo {
+                profile_name: "personal".to_string(),
+                path: PathBuf::from("/cfg/profile-personal.gitconfig"),
+                exists: false,
+                modified: None,
+            },
+        ];
+
+        let output = SnippetsListCommand::format(&snippets);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("profile "));
+        assert!(lines[1].starts_with("work "));
+        assert!(lines[1].ends_with("/cfg/profile-work.gitconfig"));
+        assert!(lines[2].contains("(not generated)"));
+        assert!(lines[2].ends_with("/cfg/profile-personal.gitconfig"));
+    }
+}
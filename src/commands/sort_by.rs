@@ -0,0 +1,196 @@
+//! Sort-by command implementation for git-setup-rs.
+//!
+//! `--sort-by <FIELD> [--direction asc|desc]` prints the profiles ordered by
+//! one of their fields. With `--persist` the order is kept: the profile store
+//! lists profiles in it from now on and the configuration file (`--file` or
+//! the default `config.toml`) is rewritten with its profiles in that order.
+
+use super::{list::ListCommand, Command, CommandContext};
+use crate::{
+    cli::{Args, SortOrder},
+    config::{
+        loader::{ConfigLoader, ConfigLoaderTrait},
+        types::{Config, Profile},
+    },
+    error::{Result, ResultExt},
+    profile::sort_by_order,
+};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Command implementation for sorting profiles by a field.
+pub struct SortByCommand;
+
+impl SortByCommand {
+    /// Create a new SortByCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Stable-sort `profiles` by the value of `field`.
+    fn sort(profiles: &mut [Profile], field: &str, direction: SortOrder) {
+        profiles.sort_by(|a, b| {
+            let ordering = a.get_field(field).cmp(&b.get_field(field));
+            match direction {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+    }
+
+    /// Rewrite the profiles of the configuration file in `order`.
+    ///
+    /// The file is read without expanding paths so only the order changes.
+    /// Nothing is written if there is no configuration file yet.
+    fn persist_config(args: &Args, order: &[String]) -> Result<()> {
+        let loader = match &args.file {
+            Some(path) => ConfigLoader::new(PathBuf::from(path)),
+            None => ConfigLoader::from_platform_default()?,
+        };
+        let path = loader.default_path()?;
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let mut config: Config = toml::from_str(&std::fs::read_to_string(&path)?)?;
+        sort_by_order(&mut config.profiles, order);
+        loader.save_to(&config, &path)
+    }
+}
+
+impl Default for SortByCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for SortByCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let field = args.sort_by.as_deref().unwrap_or("name");
+        ListCommand::check_field(field)?;
+
+        let mut profiles = context.profile_manager.list().with_context("while loading profiles")?;
+        Self::sort(&mut profiles, field, args.direction);
+        let order: Vec<String> = profiles.iter().map(|profile| profile.name.clone()).collect();
+
+        if args.persist {
+            context
+                .profile_manager
+                .reorder(order.clone())
+                .with_context("while saving the profile order")?;
+            Self::persist_config(args, &order).with_context("while rewriting the configuration file")?;
+        }
+
+        if !args.quiet {
+            let rows: Vec<_> = profiles.into_iter().map(|profile| (profile, None)).collect();
+            print!("{}", ListCommand::format_field(&rows, field, None)?);
+            if args.persist {
+                println!("✓ Saved profile order by {}", field);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "sort-by"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sort profiles by a field, optionally keeping the order"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        profile::{
+            mock::{MockProfileManager, ProfileManagerCall},
+            ProfileManager,
+        },
+    };
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: email.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn names(profiles: &[Profile]) -> Vec<&str> {
+        profiles.iter().map(|profile| profile.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_by_field_and_direction() {
+        let mut profiles = vec![
+            profile("a", "zed@example.com"),
+            profile("b", "amy@example.com"),
+            profile("c", "amy@example.com"),
+        ];
+
+        SortByCommand::sort(&mut profiles, "git_user_email", SortOrder::Asc);
+        assert_eq!(names(&profiles), ["b", "c", "a"]);
+
+        // Equal values keep their relative order in either direction
+        SortByCommand::sort(&mut profiles, "git_user_email", SortOrder::Desc);
+        assert_eq!(names(&profiles), ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_persist_reorders_store_and_config_file() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let mut config = ConfigLoader::new(config_path.clone()).create_default();
+        config.profiles = vec![profile("a", "zed@example.com"), profile("b", "amy@example.com")];
+        std::fs::write(&config_path, toml::to_string_pretty(&config).unwrap()).unwrap();
+
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(config.profiles.clone()).track_calls(true));
+        let reorders = |manager: &MockProfileManager| -> Vec<Vec<String>> {
+            manager
+                .calls()
+                .into_iter()
+                .filter_map(|call| match call {
+                    ProfileManagerCall::Reorder(order) => Some(order),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut context = create_test_context();
+        context.profile_manager = profile_manager.clone();
+
+        let args = Args {
+            sort_by: Some("git_user_email".to_string()),
+            quiet: true,
+            file: Some(config_path.display().to_string()),
+            ..Default::default()
+        };
+        SortByCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(names(&profile_manager.list().unwrap()), ["a", "b"]);
+        assert!(reorders(&profile_manager).is_empty());
+
+        let args = Args { persist: true, ..args };
+        SortByCommand::new().execute(&args, &context).await.unwrap();
+        assert_eq!(names(&profile_manager.list().unwrap()), ["b", "a"]);
+        assert_eq!(reorders(&profile_manager), vec![vec!["b".to_string(), "a".to_string()]]);
+
+        let saved: Config = toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(names(&saved.profiles), ["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_field_fails() {
+        let args = Args {
+            sort_by: Some("email".to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+        assert!(SortByCommand::new().execute(&args, &create_test_context()).await.is_err());
+    }
+}
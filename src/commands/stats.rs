@@ -0,0 +1,344 @@
+//! Stats command implementation for git-setup-rs.
+//!
+//! `--stats` analyzes the activation history and reports the most and least
+//! used profiles, profiles that were not applied in the last 30, 60 or 90
+//! days, the average number of applies per day and when during the day
+//! profiles are applied. Each profile is listed with its apply count, when it
+//! was last used and the repository it is applied in most often.
+//! `--since`/`--until` restrict the analyzed history and `--reset` clears it.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::{Args, OutputFormat},
+    error::{GitSetupError, Result, ResultExt},
+    output::TableFormatter,
+    profile::analytics::ActivationAnalyzer,
+    util::date_filter::ActivationDateFilter,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Inactivity periods reported, in days.
+const INACTIVITY_PERIODS: [u32; 3] = [30, 60, 90];
+
+/// Width of the longest histogram bar, in characters.
+const HISTOGRAM_WIDTH: usize = 30;
+
+/// Partial block characters for a bar's last character, by eighths.
+const PARTIAL_BLOCKS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Usage of a single profile.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileUsage {
+    pub name: String,
+    pub activations: usize,
+    pub average_per_day: f64,
+    pub last_used: DateTime<Utc>,
+    pub most_common_repo: Option<PathBuf>,
+}
+
+/// Profiles not applied within a number of days.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InactiveProfiles {
+    pub days: u32,
+    pub profiles: Vec<String>,
+}
+
+/// Summary of the activation history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageStats {
+    pub total_activations: usize,
+    pub most_used: Option<String>,
+    pub least_used: Option<String>,
+    pub profiles: Vec<ProfileUsage>,
+    pub inactive: Vec<InactiveProfiles>,
+    /// Activations per hour of the day (UTC)
+    pub hourly_histogram: [usize; 24],
+}
+
+/// Command implementation for profile usage analytics.
+pub struct StatsCommand;
+
+impl StatsCommand {
+    /// Create a new StatsCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Summarize `analyzer`; profiles in `profile_names` that were never applied count as inactive.
+    pub fn summarize(analyzer: &ActivationAnalyzer, profile_names: &[String]) -> UsageStats {
+        let counts = analyzer.counts();
+        let averages = analyzer.daily_averages();
+        let profile_stats = analyzer.profile_stats();
+        let never_applied: Vec<String> = profile_names
+            .iter()
+            .filter(|name| !counts.contains_key(name.as_str()))
+            .cloned()
+            .collect();
+
+        UsageStats {
+            total_activations: analyzer.total(),
+            most_used: analyzer.most_frequent().map(str::to_string),
+            least_used: analyzer.least_frequent().map(str::to_string),
+            profiles: profile_stats
+                .into_iter()
+                .map(|stats| ProfileUsage {
+                    average_per_day: averages.get(stats.name.as_str()).copied().unwrap_or_default(),
+                    name: stats.name,
+                    activations: stats.apply_count,
+                    last_used: stats.last_used,
+                    most_common_repo: stats.most_common_repo,
+                })
+                .collect(),
+            inactive: INACTIVITY_PERIODS
+                .iter()
+                .map(|&days| {
+                    let mut profiles = analyzer.inactive_since(days);
+                    profiles.extend(never_applied.iter().cloned());
+                    profiles.sort();
+                    InactiveProfiles { days, profiles }
+                })
+                .collect(),
+            hourly_histogram: analyzer.hourly_histogram(),
+        }
+    }
+
+    /// Bar of `value` scaled so that `max` fills the histogram width.
+    fn bar(value: usize, max: usize) -> String {
+        if max == 0 {
+            return String::new();
+        }
+        let eighths = value * HISTOGRAM_WIDTH * 8 / max;
+        format!("{}{}", "█".repeat(eighths / 8), PARTIAL_BLOCKS[eighths % 8])
+    }
+
+    /// Render `stats` as a human-readable report.
+    pub fn format_table(stats: &UsageStats) -> String {
+        let mut lines = vec![format!("Total activations: {}", stats.total_activations)];
+        if let Some(name) = &stats.most_used {
+            lines.push(format!("Most used:         {}", name));
+        }
+        if let Some(name) = &stats.least_used {
+            lines.push(format!("Least used:        {}", name));
+        }
+
+        if !stats.profiles.is_empty() {
+            let rows: Vec<Vec<String>> = stats
+                .profiles
+                .iter()
+                .map(|usage| {
+                    vec![
+                        usage.name.clone(),
+                        usage.activations.to_string(),
+                        format!("{:.2}", usage.average_per_day),
+                        usage.last_used.format("%Y-%m-%d %H:%M").to_string(),
+                        usage
+                            .most_common_repo
+                            .as_ref()
+                            .map_or_else(|| "none".to_string(), |repo| repo.display().to_string()),
+                    ]
+                })
+                .collect();
+            lines.push(String::new());
+            lines.push(TableFormatter::new().format_table(
+                &["Profile", "Applies", "Per day", "Last used", "Most common repo"],
+                &rows,
+            ));
+        }
+
+        lines.push(String::new());
+        for inactive in &stats.inactive {
+            let names = if inactive.profiles.is_empty() {
+                "none".to_string()
+            } else {
+                inactive.profiles.join(", ")
+            };
+            lines.push(format!("Not used in {} days: {}", inactive.days, names));
+        }
+
+        lines.push(String::new());
+        lines.push("Applies by hour (UTC):".to_string());
+        let max = stats.hourly_histogram.iter().copied().max().unwrap_or(0);
+        for (hour, count) in stats.hourly_histogram.iter().enumerate() {
+            lines.push(format!("{:02} {:>4} {}", hour, count, Self::bar(*count, max)));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Default for StatsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for StatsCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        if args.reset {
+            context
+                .profile_manager
+                .clear_activation_history()
+                .with_context("while clearing the activation history")?;
+            if !args.quiet {
+                println!("✓ Cleared the activation history");
+            }
+            return Ok(());
+        }
+
+        let mut records = context
+            .profile_manager
+            .activation_history()
+            .with_context("while loading the activation history")?;
+        if args.since.is_some() || args.until.is_some() {
+            let filter = ActivationDateFilter::parse(args.since.as_deref(), args.until.as_deref())?;
+            records.retain(|record| filter.matches(Some(&record.activated_at)));
+        }
+
+        let profile_names: Vec<String> = context
+            .profile_manager
+            .list()
+            .with_context("while loading profiles")?
+            .into_iter()
+            .map(|profile| profile.name)
+            .collect();
+        let stats = Self::summarize(&ActivationAnalyzer::new(records), &profile_names);
+
+        let output = match args.output {
+            OutputFormat::Tabular => Self::format_table(&stats),
+            OutputFormat::Json => serde_json::to_string_pretty(&stats)?,
+            OutputFormat::Yaml => serde_yaml::to_string(&stats)?,
+            OutputFormat::Toml | OutputFormat::Csv => {
+                return Err(GitSetupError::Git(
+                    "Stats can be shown as tabular, json or yaml output".to_string(),
+                ));
+            }
+        };
+        println!("{}", output);
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "stats"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show profile usage statistics from the activation history"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        profile::{
+            activation::ActivationRecord,
+            mock::{MockProfileManager, ProfileManagerCall},
+            ProfileManager,
+        },
+    };
+    use chrono::{DateTime, Utc};
+    use std::sync::Arc;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    fn record(name: &str, timestamp: &str) -> ActivationRecord {
+        ActivationRecord {
+            profile_name: name.to_string(),
+            repo_path: None,
+            activated_at: at(timestamp),
+        }
+    }
+
+    #[test]
+    fn test_summarize_includes_never_applied_profiles() {
+        let analyzer = ActivationAnalyzer::new(vec![
+            record("work", "2024-03-19T09:00:00Z"),
+            record("work", "2024-03-19T10:00:00Z"),
+            record("old", "2024-01-01T09:00:00Z"),
+        ])
+        .at(at("2024-03-20T12:00:00Z"));
+        let names = vec!["old".to_string(), "unused".to_string(), "work".to_string()];
+
+        let stats = StatsCommand::summarize(&analyzer, &names);
+
+        assert_eq!(stats.total_activations, 3);
+        assert_eq!(stats.profiles[1].name, "work");
+        assert_eq!(stats.profiles[1].activations, 2);
+        assert_eq!(stats.profiles[1].last_used, at("2024-03-19T10:00:00Z"));
+        assert!(StatsCommand::format_table(&stats).contains("2024-03-19 10:00"));
+        assert_eq!(stats.most_used.as_deref(), Some("work"));
+        assert_eq!(stats.least_used.as_deref(), Some("old"));
+        assert_eq!(stats.inactive[0], InactiveProfiles {
+            days: 30,
+            profiles: vec!["old".to_string(), "unused".to_string()],
+        });
+        assert_eq!(stats.inactive[2].profiles, vec!["unused"]);
+        assert_eq!(stats.hourly_histogram[9], 2);
+    }
+
+    #[test]
+    fn test_histogram_bars() {
+        assert_eq!(StatsCommand::bar(4, 4), "█".repeat(HISTOGRAM_WIDTH));
+        assert_eq!(StatsCommand::bar(0, 4), "");
+        assert_eq!(StatsCommand::bar(1, 16), format!("{}{}", "█", PARTIAL_BLOCKS[7]));
+        assert_eq!(StatsCommand::bar(0, 0), "");
+    }
+
+    #[tokio::test]
+    async fn test_stats_rejects_csv() {
+        let manager = MockProfileManager::with_profiles(vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "work@example.com".to_string(),
+            ..Default::default()
+        }]);
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(manager);
+
+        let args = Args {
+            stats: true,
+            output: OutputFormat::Csv,
+            ..Default::default()
+        };
+        assert!(StatsCommand::new().execute(&args, &context).await.is_err());
+
+        let args = Args {
+            stats: true,
+            output: OutputFormat::Json,
+            ..args
+        };
+        assert!(StatsCommand::new().execute(&args, &context).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stats_reset() {
+        let manager = Arc::new(
+            MockProfileManager::new()
+                .with_activations(vec![record("work", "2024-03-19T09:00:00Z")])
+                .track_calls(true),
+        );
+        let mut context = create_test_context();
+        context.profile_manager = manager.clone();
+
+        let args = Args {
+            stats: true,
+            reset: true,
+            quiet: true,
+            ..Default::default()
+        };
+        StatsCommand::new().execute(&args, &context).await.unwrap();
+        assert!(manager.activation_history().unwrap().is_empty());
+        assert!(manager
+            .calls()
+            .iter()
+            .any(|call| matches!(call, ProfileManagerCall::ClearActivationHistory)));
+    }
+}
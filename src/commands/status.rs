@@ -0,0 +1,155 @@
+//! Status command implementation for git-setup-rs.
+//!
+//! `--status` prints the git identity and signing configuration currently in
+//! effect. With `--verbose`, each value is followed by the file and line that
+//! set it.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{Result, ResultExt},
+    external::git::ConfigOrigin,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Configuration keys shown by the status command.
+const STATUS_KEYS: &[&str] = &[
+    "user.name",
+    "user.email",
+    "user.signingkey",
+    "gpg.format",
+    "commit.gpgsign",
+];
+
+/// Command implementation for showing the effective git configuration.
+pub struct StatusCommand;
+
+impl StatusCommand {
+    /// Create a new StatusCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format `origin`, abbreviating `home` to `~`.
+    fn format_origin(origin: &ConfigOrigin, home: Option<&Path>) -> String {
+        let file = match home.and_then(|home| origin.file.strip_prefix(home).ok()) {
+            Some(relative) => format!("~/{}", relative.display()),
+            None => origin.file.display().to_string(),
+        };
+        if origin.line == 0 {
+            format!("from {}", file)
+        } else {
+            format!("from {} line {}", file, origin.line)
+        }
+    }
+
+    /// Status lines for the keys that are set.
+    pub fn report(&self, verbose: bool, context: &CommandContext) -> Result<Vec<String>> {
+        let home = SystemPlatform.home_dir().ok();
+        let mut lines = Vec::new();
+
+        for key in STATUS_KEYS {
+            let Some(value) = context.git_wrapper.get_config(key, None)? else {
+                continue;
+            };
+            let origin = if verbose {
+                context.git_wrapper.get_config_with_origin(key)?.map(|(_, origin)| origin)
+            } else {
+                None
+            };
+
+            match origin {
+                Some(origin) => lines.push(format!(
+                    "{} = {} ({})",
+                    key,
+                    value,
+                    Self::format_origin(&origin, home.as_deref())
+                )),
+                None => lines.push(format!("{} = {}", key, value)),
+            }
+        }
+
+        Ok(lines)
+    }
+}
+
+impl Default for StatusCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for StatusCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let lines = self
+            .report(args.verbose, context)
+            .with_context("while reading the current git configuration")?;
+
+        if lines.is_empty() {
+            println!("No git identity configured");
+        }
+        for line in lines {
+            println!("{}", line);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "status"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the git identity and signing configuration in effect"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::tests::create_test_context, external::git::MockGitWrapper};
+    use std::sync::Arc;
+
+    fn context_with(git: MockGitWrapper) -> CommandContext {
+        let mut context = create_test_context();
+        context.git_wrapper = Arc::new(git);
+        context
+    }
+
+    #[test]
+    fn test_report_verbose_shows_origin() {
+        let git = MockGitWrapper::new();
+        git.mock_set_config_with_origin("user.email", "work@example.com", "/etc/gitconfig", 12);
+        git.mock_set_config("user.name", "Jane");
+        let context = context_with(git);
+
+        let lines = StatusCommand::new().report(true, &context).unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "user.name = Jane".to_string(),
+                "user.email = work@example.com (from /etc/gitconfig line 12)".to_string(),
+            ]
+        );
+
+        let lines = StatusCommand::new().report(false, &context).unwrap();
+        assert_eq!(lines[1], "user.email = work@example.com");
+    }
+
+    #[test]
+    fn test_format_origin_abbreviates_home() {
+        let origin = ConfigOrigin::new("/home/jane/.gitconfig", 12);
+        assert_eq!(
+            StatusCommand::format_origin(&origin, Some(Path::new("/home/jane"))),
+            "from ~/.gitconfig line 12"
+        );
+
+        let origin = ConfigOrigin::new("/repo/.git/config", 0);
+        assert_eq!(
+            StatusCommand::format_origin(&origin, Some(Path::new("/home/jane"))),
+            "from /repo/.git/config"
+        );
+    }
+}
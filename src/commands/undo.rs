@@ -0,0 +1,158 @@
+//! Undo and redo command implementations for git-setup-rs.
+//!
+//! These commands reverse or re-apply the most recent profile changes.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{Result, ResultExt},
+};
+use async_trait::async_trait;
+
+/// Command implementation for undoing the last profile change.
+pub struct UndoCommand;
+
+impl UndoCommand {
+    /// Create a new UndoCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UndoCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for UndoCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let undone = context.profile_manager.undo()
+            .with_context("while undoing the last profile change")?;
+
+        if !args.quiet {
+            match undone {
+                Some(name) => println!("✓ Undid last change to profile '{}'", name),
+                None => println!("Nothing to undo."),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "undo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Undo the last profile change"
+    }
+}
+
+/// Command implementation for redoing the last undone profile change.
+pub struct RedoCommand;
+
+impl RedoCommand {
+    /// Create a new RedoCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RedoCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for RedoCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let redone = context.profile_manager.redo()
+            .with_context("while redoing the last undone profile change")?;
+
+        if !args.quiet {
+            match redone {
+                Some(name) => println!("✓ Redid last undone change to profile '{}'", name),
+                None => println!("Nothing to redo."),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "redo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Redo the last undone profile change"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::tests::create_test_context,
+        config::types::Profile,
+        profile::{manager::ProfileManagerImpl, ProfileManager},
+    };
+    use std::sync::Arc;
+
+    fn create_test_profile(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            ..Default::default()
+        }
+    }
+
+    /// Test that undo and redo reverse a deletion through the profile manager.
+    #[tokio::test]
+    async fn test_undo_redo_delete() {
+        let manager = Arc::new(ProfileManagerImpl::new());
+        manager.create(create_test_profile("work")).unwrap();
+        manager.delete("work").unwrap();
+
+        let mut context = create_test_context();
+        context.profile_manager = manager.clone();
+
+        let args = Args {
+            undo: true,
+            quiet: true,
+            ..Default::default()
+        };
+        UndoCommand::new().execute(&args, &context).await.unwrap();
+        assert!(manager.exists("work").unwrap());
+
+        let args = Args {
+            redo: true,
+            quiet: true,
+            ..Default::default()
+        };
+        RedoCommand::new().execute(&args, &context).await.unwrap();
+        assert!(!manager.exists("work").unwrap());
+    }
+
+    /// Test that undo with no history succeeds without changes.
+    #[tokio::test]
+    async fn test_undo_nothing_to_undo() {
+        let context = create_test_context();
+        let args = Args {
+            undo: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        assert!(UndoCommand::new().execute(&args, &context).await.is_ok());
+    }
+
+    /// Test command metadata.
+    #[test]
+    fn test_command_names() {
+        assert_eq!(UndoCommand::new().name(), "undo");
+        assert_eq!(RedoCommand::new().name(), "redo");
+    }
+}
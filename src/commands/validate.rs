@@ -0,0 +1,161 @@
+//! Validate command implementation for git-setup-rs.
+//!
+//! `--validate <PROFILE>` checks that a profile is consistent before it is
+//! applied: that it has the signing key its key type needs, that GPG keys are
+//! key IDs, that 1Password profiles name their vault, that the allowed
+//! signers path is usable and that its match patterns are valid globs. Every
+//! check is printed as a table; the command fails if a check failed, or with
+//! `--strict` if one warned.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{GitSetupError, Result},
+    output::TableFormatter,
+    profile::validator::{CheckStatus, ProfileValidator, ValidationReport},
+};
+use async_trait::async_trait;
+
+/// Command implementation for validating a profile without applying it.
+pub struct ValidateCommand;
+
+impl ValidateCommand {
+    /// Create a new ValidateCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render `report` as a table of checks with their status.
+    pub fn format_report(report: &ValidationReport) -> String {
+        let rows: Vec<Vec<String>> = report
+            .checks
+            .iter()
+            .map(|check| vec![check.name.clone(), check.status.to_string(), check.message.clone()])
+            .collect();
+        TableFormatter::new().format_table(&["Check", "Status", "Message"], &rows)
+    }
+}
+
+impl Default for ValidateCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ValidateCommand {
+    async fn execute(&self, args: &Args, context: &CommandContext) -> Result<()> {
+        let name = args
+            .validate
+            .as_ref()
+            .ok_or_else(|| GitSetupError::Git("Profile name is required for --validate".to_string()))?;
+        let profile = context
+            .profile_manager
+            .read(name)?
+            .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.clone() })?;
+
+        let report = ProfileValidator::report(&profile);
+        if !args.quiet {
+            println!("{}", Self::format_report(&report));
+        }
+
+        let (failed, warned) = (report.count(CheckStatus::Fail), report.count(CheckStatus::Warn));
+        if !report.passed(args.strict) {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("profile '{}' has {} failed and {} warning check(s)", name, failed, warned),
+            });
+        }
+        if !args.quiet {
+            println!("✓ Profile '{}' is valid ({} warning(s))", name, warned);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "validate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check that a profile is consistent without applying it"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use crate::config::types::{KeyType, Profile};
+    use crate::profile::mock::MockProfileManager;
+    use std::sync::Arc;
+
+    fn context() -> CommandContext {
+        let mut context = create_test_context();
+        context.profile_manager = Arc::new(MockProfileManager::with_profiles(vec![
+            Profile {
+                name: "work".to_string(),
+                git_user_email: "jane@work.com".to_string(),
+                key_type: KeyType::Ssh,
+                signing_key: Some("~/.ssh/id_work.pub".to_string()),
+                ..Default::default()
+            },
+            Profile {
+                name: "oss".to_string(),
+                git_user_email: "jane@example.com".to_string(),
+                key_type: KeyType::Gpg,
+                signing_key: Some("jane@example.com".to_string()),
+                ..Default::default()
+            },
+            Profile {
+                name: "broken".to_string(),
+                git_user_email: "jane@example.com".to_string(),
+                key_type: KeyType::Ssh,
+                ..Default::default()
+            },
+        ]));
+        context
+    }
+
+    fn validate_args(name: &str, strict: bool) -> Args {
+        Args {
+            validate: Some(name.to_string()),
+            strict,
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    /// Test that failures always fail and warnings only fail with --strict.
+    #[tokio::test]
+    async fn test_validate_strict() {
+        let context = context();
+        let cmd = ValidateCommand::new();
+
+        assert!(cmd.execute(&validate_args("work", true), &context).await.is_ok());
+        assert!(cmd.execute(&validate_args("oss", false), &context).await.is_ok());
+
+        let result = cmd.execute(&validate_args("oss", true), &context).await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        let result = cmd.execute(&validate_args("broken", false), &context).await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        let result = cmd.execute(&validate_args("missing", false), &context).await;
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+    }
+
+    /// Test that every check is listed with its status.
+    #[test]
+    fn test_format_report() {
+        let profile = Profile {
+            name: "oss".to_string(),
+            git_user_email: "jane@example.com".to_string(),
+            key_type: KeyType::Gpg,
+            signing_key: Some("jane@example.com".to_string()),
+            ..Default::default()
+        };
+        let output = ValidateCommand::format_report(&ProfileValidator::report(&profile));
+
+        assert!(output.contains("Check"));
+        assert!(output.contains("git_user_email ┆ pass"));
+        assert!(output.contains("signing_key    ┆ warn"));
+        assert!(output.contains("match_patterns ┆ pass"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Validate-against-schema command implementation for git-setup-rs.
+//!
+//! `--validate-against-schema <FILE>` checks a JSON file holding a profile, or
+//! an array of profiles such as the `--list --output json` output, against the
+//! profile JSON Schema printed by `--list --output-schema`. Every violation is
+//! reported and the command fails if there is any.
+
+use super::{Command, CommandContext};
+use crate::{
+    cli::Args,
+    error::{GitSetupError, Result, ResultExt},
+    profile::schema::{ProfileJsonSchema, PROFILE_SCHEMA_ID},
+};
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// Command implementation for validating profile JSON files.
+pub struct ValidateSchemaCommand;
+
+impl ValidateSchemaCommand {
+    /// Create a new ValidateSchemaCommand instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Schema violations of a profile or an array of profiles.
+    fn violations(value: &Value) -> Vec<String> {
+        match value {
+            Value::Array(profiles) => profiles
+                .iter()
+                .enumerate()
+                .flat_map(|(index, profile)| {
+                    ProfileJsonSchema::validate(profile)
+                        .into_iter()
+                        .map(move |error| error.replacen('$', &format!("$[{}]", index), 1))
+                })
+                .collect(),
+            profile => ProfileJsonSchema::validate(profile),
+        }
+    }
+}
+
+impl Default for ValidateSchemaCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Command for ValidateSchemaCommand {
+    async fn execute(&self, args: &Args, _context: &CommandContext) -> Result<()> {
+        let path = args.validate_against_schema.as_ref().ok_or_else(|| {
+            GitSetupError::Git("A file is required for --validate-against-schema".to_string())
+        })?;
+        let content = std::fs::read_to_string(path).with_context(format!("while reading '{}'", path))?;
+        let value: Value = serde_json::from_str(&content).with_context(format!("while parsing '{}'", path))?;
+
+        let violations = Self::violations(&value);
+        if violations.is_empty() {
+            if !args.quiet {
+                println!("✓ {} matches {}", path, PROFILE_SCHEMA_ID);
+            }
+            return Ok(());
+        }
+
+        for violation in &violations {
+            eprintln!("  {}", violation);
+        }
+        Err(GitSetupError::InvalidProfile {
+            reason: format!("{} has {} schema violation(s)", path, violations.len()),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "validate-against-schema"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check a profile JSON file against the profile JSON Schema"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tests::create_test_context;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// Test that valid files pass and invalid profiles in an array are reported by index.
+    #[tokio::test]
+    async fn test_validate_against_schema() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("profiles.json");
+        let context = create_test_context();
+        let args = Args {
+            validate_against_schema: Some(path.display().to_string()),
+            quiet: true,
+            ..Default::default()
+        };
+
+        let valid = json!({ "name": "work", "git_user_email": "jane@work.com", "key_type": "ssh" });
+        std::fs::write(&path, valid.to_string()).unwrap();
+        ValidateSchemaCommand::new().execute(&args, &context).await.unwrap();
+
+        let profiles = json!([valid, { "name": "home", "git_user_email": "jane@home.net", "key_type": "pgp" }]);
+        assert_eq!(
+            ValidateSchemaCommand::violations(&profiles),
+            vec!["$[1].key_type: \"pgp\" is not one of \"ssh\", \"gpg\", \"x509\", \"gitsign\"".to_string()]
+        );
+        std::fs::write(&path, profiles.to_string()).unwrap();
+        let result = ValidateSchemaCommand::new().execute(&args, &context).await;
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+    }
+}
@@ -0,0 +1,265 @@
+//! Effective tool configuration and where each value comes from.
+//!
+//! Tool settings can be overridden with `GIT_SETUP_<SETTING>` environment
+//! variables (e.g. `GIT_SETUP_OP_RATE_LIMIT_CALLS_PER_MINUTE=30`), which win
+//! over the `[tool]` table of the config file. [`ToolConfig::resolve`] records
+//! the source of every value so `--config-dump` can show why a setting has
+//! the value it has.
+
+use super::types::{ToolConfig, ValidationHookConfig};
+use crate::{
+    error::{GitSetupError, Result},
+    tui::animation::DEFAULT_ANIMATION_DURATION,
+};
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the environment variables overriding tool settings.
+pub const ENV_PREFIX: &str = "GIT_SETUP_";
+
+/// Tool settings that can be overridden from the environment.
+const ENV_SETTINGS: &[&str] = &["op_rate_limit_calls_per_minute", "tui_animation_duration_ms"];
+
+/// Where a configuration value comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in default
+    Default,
+    /// Config file, with the 1-based line the value is set on (0 if unknown)
+    File { path: PathBuf, line: u32 },
+    /// Environment variable
+    Env(String),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File { path, line: 0 } => write!(f, "from {}", path.display()),
+            ConfigSource::File { path, line } => write!(f, "from {}:{}", path.display(), line),
+            ConfigSource::Env(var) => write!(f, "from ${}", var),
+        }
+    }
+}
+
+impl Serialize for ConfigSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A configuration value together with its source.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigValue<T> {
+    pub value: T,
+    pub source: ConfigSource,
+}
+
+impl<T> ConfigValue<T> {
+    /// A built-in default value.
+    pub fn default_value(value: T) -> Self {
+        Self { value, source: ConfigSource::Default }
+    }
+}
+
+/// The tool configuration with defaults filled in and the source of every value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResolvedToolConfig {
+    pub op_rate_limit_calls_per_minute: ConfigValue<Option<u32>>,
+    pub tui_animation_duration_ms: ConfigValue<u64>,
+    pub validation_hooks: ConfigValue<Vec<ValidationHookConfig>>,
+}
+
+impl ResolvedToolConfig {
+    /// One `setting: value (source)` line per setting; unset values are shown as `unset`.
+    pub fn render(&self) -> String {
+        let hooks = &self.validation_hooks.value;
+        let hooks = if hooks.is_empty() {
+            "none".to_string()
+        } else {
+            hooks.iter().map(|hook| hook.command.as_str()).collect::<Vec<_>>().join(", ")
+        };
+        let rate_limit = self
+            .op_rate_limit_calls_per_minute
+            .value
+            .map_or_else(|| "unset".to_string(), |calls| calls.to_string());
+
+        format!(
+            "op_rate_limit_calls_per_minute: {} ({})\ntui_animation_duration_ms: {} ({})\nvalidation_hooks: {} ({})",
+            rate_limit,
+            self.op_rate_limit_calls_per_minute.source,
+            self.tui_animation_duration_ms.value,
+            self.tui_animation_duration_ms.source,
+            hooks,
+            self.validation_hooks.source,
+        )
+    }
+}
+
+impl ToolConfig {
+    /// Tool settings overridden by `GIT_SETUP_*` environment variables, keyed by setting name.
+    pub fn from_env_overrides() -> HashMap<String, String> {
+        Self::env_overrides_from(std::env::vars())
+    }
+
+    /// The overrides among `vars`; variables not naming a tool setting are ignored.
+    pub fn env_overrides_from(vars: impl IntoIterator<Item = (String, String)>) -> HashMap<String, String> {
+        vars.into_iter()
+            .filter_map(|(var, value)| {
+                let setting = var.strip_prefix(ENV_PREFIX)?.to_lowercase();
+                ENV_SETTINGS.contains(&setting.as_str()).then_some((setting, value))
+            })
+            .collect()
+    }
+
+    /// This configuration with the environment overrides applied.
+    ///
+    /// # Errors
+    /// Returns an error if an override is not a valid value for its setting
+    pub fn effective(&self) -> Result<ToolConfig> {
+        self.with_overrides(&Self::from_env_overrides())
+    }
+
+    /// This configuration with `overrides` (as returned by [`ToolConfig::from_env_overrides`]) applied.
+    pub fn with_overrides(&self, overrides: &HashMap<String, String>) -> Result<ToolConfig> {
+        let mut config = self.clone();
+        if let Some(value) = overrides.get("op_rate_limit_calls_per_minute") {
+            config.op_rate_limit_calls_per_minute = Some(parse_override("op_rate_limit_calls_per_minute", value)?);
+        }
+        if let Some(value) = overrides.get("tui_animation_duration_ms") {
+            config.tui_animation_duration_ms = Some(parse_override("tui_animation_duration_ms", value)?);
+        }
+        Ok(config)
+    }
+
+    /// Resolve every setting with its source: `overrides` first, then the file, then the default.
+    ///
+    /// `file` is the config file this configuration was loaded from, if it exists.
+    pub fn resolve(&self, file: Option<&Path>, overrides: &HashMap<String, String>) -> Result<ResolvedToolConfig> {
+        let content = file.and_then(|path| std::fs::read_to_string(path).ok());
+        let effective = self.with_overrides(overrides)?;
+        let source = |setting: &str, set_in_file: bool| {
+            if overrides.contains_key(setting) {
+                ConfigSource::Env(format!("{}{}", ENV_PREFIX, setting.to_uppercase()))
+            } else if let (true, Some(path)) = (set_in_file, file) {
+                let line = content.as_deref().map_or(0, |content| tool_setting_line(content, setting));
+                ConfigSource::File { path: path.to_path_buf(), line }
+            } else {
+                ConfigSource::Default
+            }
+        };
+
+        Ok(ResolvedToolConfig {
+            op_rate_limit_calls_per_minute: ConfigValue {
+                source: source("op_rate_limit_calls_per_minute", self.op_rate_limit_calls_per_minute.is_some()),
+                value: effective.op_rate_limit_calls_per_minute,
+            },
+            tui_animation_duration_ms: ConfigValue {
+                source: source("tui_animation_duration_ms", self.tui_animation_duration_ms.is_some()),
+                value: effective
+                    .tui_animation_duration_ms
+                    .unwrap_or(DEFAULT_ANIMATION_DURATION.as_millis() as u64),
+            },
+            validation_hooks: ConfigValue {
+                source: source("validation_hooks", !self.validation_hooks.is_empty()),
+                value: effective.validation_hooks,
+            },
+        })
+    }
+}
+
+/// Parse the override `value` of `setting`.
+fn parse_override<T: std::str::FromStr>(setting: &str, value: &str) -> Result<T> {
+    value.trim().parse().map_err(|_| {
+        GitSetupError::Git(format!(
+            "Invalid value '{}' for {}{}",
+            value,
+            ENV_PREFIX,
+            setting.to_uppercase()
+        ))
+    })
+}
+
+/// 1-based line of `setting` in the `[tool]` table of `content`, or 0 if it is not found.
+fn tool_setting_line(content: &str, setting: &str) -> u32 {
+    let mut in_tool = false;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            // Array entries such as validation hooks are set by their header
+            if line == format!("[[tool.{}]]", setting) || line == format!("[tool.{}]", setting) {
+                return index as u32 + 1;
+            }
+            in_tool = line == "[tool]";
+        } else if in_tool
+            && let Some((key, _)) = line.split_once('=')
+            && key.trim() == setting
+        {
+            return index as u32 + 1;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        ToolConfig::env_overrides_from(vars.iter().map(|(var, value)| (var.to_string(), value.to_string())))
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let overrides = env(&[
+            ("GIT_SETUP_OP_RATE_LIMIT_CALLS_PER_MINUTE", "30"),
+            ("GIT_SETUP_PROFILE", "work"),
+            ("HOME", "/home/jane"),
+        ]);
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides["op_rate_limit_calls_per_minute"], "30");
+
+        let config = ToolConfig::default().with_overrides(&overrides).unwrap();
+        assert_eq!(config.op_rate_limit_calls_per_minute, Some(30));
+
+        let invalid = env(&[("GIT_SETUP_TUI_ANIMATION_DURATION_MS", "fast")]);
+        assert!(ToolConfig::default().with_overrides(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_resolve_sources() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "version = 1\n\n[tool]\nop_rate_limit_calls_per_minute = 60\ntui_animation_duration_ms = 0\n\n[[tool.validation_hooks]]\ncommand = \"check.sh\"\n"
+        )
+        .unwrap();
+        let config = ToolConfig {
+            op_rate_limit_calls_per_minute: Some(60),
+            tui_animation_duration_ms: None,
+            validation_hooks: vec![],
+        };
+        let overrides = env(&[("GIT_SETUP_TUI_ANIMATION_DURATION_MS", "50")]);
+
+        let resolved = config.resolve(Some(file.path()), &overrides).unwrap();
+        assert_eq!(resolved.op_rate_limit_calls_per_minute.value, Some(60));
+        assert_eq!(
+            resolved.op_rate_limit_calls_per_minute.source,
+            ConfigSource::File { path: file.path().to_path_buf(), line: 4 }
+        );
+        assert_eq!(resolved.tui_animation_duration_ms.value, 50);
+        assert_eq!(
+            resolved.tui_animation_duration_ms.source.to_string(),
+            "from $GIT_SETUP_TUI_ANIMATION_DURATION_MS"
+        );
+        assert_eq!(resolved.validation_hooks.source, ConfigSource::Default);
+
+        let resolved = ToolConfig::default().resolve(None, &HashMap::new()).unwrap();
+        assert_eq!(resolved.tui_animation_duration_ms.value, 200);
+        assert!(resolved.render().contains("op_rate_limit_calls_per_minute: unset (default)"));
+    }
+}
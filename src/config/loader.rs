@@ -1,7 +1,7 @@
 //! Configuration loading and saving implementation.
 
 use crate::{
-    config::types::{Config, ConfigPaths, SshDefaults},
+    config::types::{Config, ConfigPaths, SshDefaults, ToolConfig},
     error::{GitSetupError, Result},
     platform::{PlatformPaths, SystemPlatform},
 };
@@ -59,6 +59,11 @@ impl ConfigLoader {
         Self { config_path, platform }
     }
 
+    /// Path of the config file this loader reads by default.
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
     /// Create a ConfigLoader using platform default paths.
     pub fn from_platform_default() -> Result<Self> {
         let platform = SystemPlatform;
@@ -140,6 +145,7 @@ impl ConfigLoaderTrait for ConfigLoader {
                 key_type: "ed25519".to_string(),
                 key_size: Some(256),
             }),
+            tool: ToolConfig::default(),
         }
     }
 
@@ -261,6 +267,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             }),
             profiles: vec![
                 Profile {
@@ -280,6 +295,15 @@ mod tests {
                     include_if_dirs: vec![],
                     host_patterns: vec![],
                     one_password: true,
+                    require_passphrase: false,
+                    is_archived: false,
+                    description: None,
+                    per_field_scope: Default::default(),
+                    git_hooks_dir: None,
+                    allowed_signers_content: None,
+                    secret_store: None,
+                    rotated_key_item: None,
+                    tags: vec![],
                 }
             ],
             config_paths: ConfigPaths {
@@ -291,6 +315,7 @@ mod tests {
                 key_type: "ed25519".to_string(),
                 key_size: Some(256),
             }),
+            tool: ToolConfig::default(),
         }
     }
 
@@ -717,6 +742,7 @@ pub mod mock {
                         key_type: "ed25519".to_string(),
                         key_size: Some(256),
                     }),
+                    tool: ToolConfig::default(),
                 })),
                 should_fail_load: false,
                 should_fail_save: false,
@@ -792,6 +818,7 @@ pub mod mock {
                     key_type: "ed25519".to_string(),
                     key_size: Some(256),
                 }),
+                tool: ToolConfig::default(),
             }
         }
 
@@ -1,5 +1,7 @@
 pub mod types;
 pub mod loader;
+pub mod effective;
+pub mod snippets;
 
 pub use types::*;
 pub use loader::*;
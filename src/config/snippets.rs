@@ -0,0 +1,164 @@
+//! Locating the gitconfig snippets generated for profiles.
+//!
+//! Each profile's snippet lives at `<config dir>/profile-<name>.gitconfig`,
+//! the file an `[includeIf]` section points at. [`SnippetManager`] reports
+//! where those files are and whether they have been generated, which helps
+//! when an `[includeIf]` setup does not pick up the expected identity.
+
+use crate::{
+    error::Result,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// File name prefix of generated snippets.
+const SNIPPET_PREFIX: &str = "profile-";
+
+/// File name extension of generated snippets.
+const SNIPPET_EXTENSION: &str = ".gitconfig";
+
+/// Location and state of one profile's snippet file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetInfo {
+    pub profile_name: String,
+    pub path: PathBuf,
+    pub exists: bool,
+    pub modified: Option<SystemTime>,
+}
+
+/// Finds the gitconfig snippets of profiles in a directory.
+#[derive(Debug, Clone)]
+pub struct SnippetManager {
+    dir: PathBuf,
+    profile_names: Vec<String>,
+}
+
+impl SnippetManager {
+    /// Create a manager for the snippets in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            profile_names: Vec::new(),
+        }
+    }
+
+    /// Create a manager for the snippets in the platform config directory.
+    pub fn from_platform_default() -> Result<Self> {
+        Ok(Self::new(SystemPlatform.config_dir()?))
+    }
+
+    /// Also report these profiles when their snippet has not been generated.
+    pub fn with_profiles<I, S>(mut self, profile_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.profile_names = profile_names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The directory holding the snippets.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the snippet generated for `profile_name`.
+    pub fn snippet_path(&self, profile_name: &str) -> PathBuf {
+        self.dir.join(format!("{}{}{}", SNIPPET_PREFIX, profile_name, SNIPPET_EXTENSION))
+    }
+
+    /// Look up the snippet of `profile_name`.
+    pub fn snippet(&self, profile_name: &str) -> Result<SnippetInfo> {
+        let path = self.snippet_path(profile_name);
+        let (exists, modified) = match std::fs::metadata(&path) {
+            Ok(metadata) => (true, Some(metadata.modified()?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (false, None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(SnippetInfo {
+            profile_name: profile_name.to_string(),
+            path,
+            exists,
+            modified,
+        })
+    }
+
+    /// List the snippets generated in the directory and those of the known
+    /// profiles, sorted by profile name.
+    pub fn list_snippets(&self) -> Result<Vec<SnippetInfo>> {
+        let mut names: BTreeSet<String> = self.profile_names.iter().cloned().collect();
+
+        match std::fs::read_dir(&self.dir) {
+            Ok(entries) => {
+                for entry in entries {
+                    let file_name = entry?.file_name();
+                    if let Some(name) = file_name
+                        .to_str()
+                        .and_then(|name| name.strip_prefix(SNIPPET_PREFIX))
+                        .and_then(|name| name.strip_suffix(SNIPPET_EXTENSION))
+                        .filter(|name| !name.is_empty())
+                    {
+                        names.insert(name.to_string());
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        names.iter().map(|name| self.snippet(name)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snippet_path() {
+        let manager = SnippetManager::new("/home/jane/.config/git/setup");
+        assert_eq!(
+            manager.snippet_path("work"),
+            PathBuf::from("/home/jane/.config/git/setup/profile-work.gitconfig")
+        );
+    }
+
+    #[test]
+    fn test_list_snippets() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("profile-work.gitconfig"), "[user]\n").unwrap();
+        std::fs::write(dir.path().join("profile-old.gitconfig"), "[user]\n").unwrap();
+        std::fs::write(dir.path().join("config.toml"), "").unwrap();
+
+        let snippets = SnippetManager::new(dir.path())
+            .with_profiles(["work", "personal"])
+            .list_snippets()
+            .unwrap();
+
+        let names: Vec<&str> = snippets.iter().map(|s| s.profile_name.as_str()).collect();
+        assert_eq!(names, ["old", "personal", "work"]);
+        assert!(snippets[0].exists);
+        assert!(!snippets[1].exists);
+        assert_eq!(snippets[1].modified, None);
+        assert_eq!(snippets[1].path, dir.path().join("profile-personal.gitconfig"));
+        assert!(snippets[2].exists);
+        assert!(snippets[2].modified.is_some());
+    }
+
+    #[test]
+    fn test_list_snippets_missing_dir() {
+        let dir = TempDir::new().unwrap();
+        let snippets = SnippetManager::new(dir.path().join("missing"))
+            .with_profiles(["work"])
+            .list_snippets()
+            .unwrap();
+
+        assert_eq!(snippets.len(), 1);
+        assert!(!snippets[0].exists);
+    }
+}
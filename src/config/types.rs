@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -8,13 +10,15 @@ pub struct Config {
     pub profiles: Vec<Profile>,
     pub config_paths: ConfigPaths,
     pub ssh_defaults: Option<SshDefaults>,
+    #[serde(default)]
+    pub tool: ToolConfig,
 }
 
 fn default_version() -> u32 {
     1
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Profile {
     pub name: String,
     pub git_user_name: Option<String>,
@@ -37,6 +41,250 @@ pub struct Profile {
     pub host_patterns: Vec<String>,
     #[serde(default)]
     pub one_password: bool,
+    /// Prompt for the local key's passphrase when the profile is applied.
+    #[serde(default)]
+    pub require_passphrase: bool,
+    /// Archived profiles are kept but hidden from listings and detection.
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Free-form note describing what the profile is for.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Scopes overriding `scope` for individual git config keys (e.g. `commit.gpgsign = "local"`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub per_field_scope: HashMap<String, Scope>,
+    /// Directory of git hooks installed as `core.hooksPath` when the profile is applied.
+    #[serde(default)]
+    pub git_hooks_dir: Option<PathBuf>,
+    /// Allowed signers entries (`<email> <public key>` lines) written to the
+    /// allowed signers file by `--update-allowed-signers`.
+    #[serde(default)]
+    pub allowed_signers_content: Option<String>,
+    /// Secret manager the key is loaded from; `one_password` alone means 1Password.
+    #[serde(default)]
+    pub secret_store: Option<SecretStoreKind>,
+    /// 1Password item ID of the key replaced by `--rotate-key`, removed with `--delete-old`.
+    #[serde(default)]
+    pub rotated_key_item: Option<String>,
+    /// Groups the profile belongs to, applied together with `--tag <TAG>`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// One-off values that replace fields of an existing profile when applying it.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileOverrides {
+    pub git_user_email: Option<String>,
+    pub git_user_name: Option<String>,
+    pub signing_key: Option<String>,
+}
+
+impl Profile {
+    /// Return a copy of this profile with every provided override applied.
+    ///
+    /// An overriding signing key replaces the key of the profile's secret
+    /// store, so the store is dropped from the copy.
+    pub fn with_overrides(mut self, overrides: ProfileOverrides) -> Self {
+        if let Some(email) = overrides.git_user_email {
+            self.git_user_email = email;
+        }
+        if let Some(name) = overrides.git_user_name {
+            self.git_user_name = Some(name);
+        }
+        if let Some(signing_key) = overrides.signing_key {
+            self.signing_key = Some(signing_key);
+            self.one_password = false;
+            self.secret_store = None;
+        }
+        self
+    }
+
+    /// Combine two profiles, with the fields set in `overlay` replacing those of `base`.
+    ///
+    /// `repos`, `match_patterns`, `host_patterns` and `include_if_dirs` are the
+    /// union of both, without duplicates. The key type and signing key are taken
+    /// together: from `overlay` if it sets a signing key or a non-default key
+    /// type, otherwise from `base`. The result keeps the name of `base` and is
+    /// never archived.
+    pub fn merge(base: &Profile, overlay: &Profile) -> Profile {
+        let overlays_key = overlay.signing_key.is_some() || overlay.key_type != KeyType::default();
+        let mut per_field_scope = base.per_field_scope.clone();
+        per_field_scope.extend(overlay.per_field_scope.clone());
+
+        Profile {
+            name: base.name.clone(),
+            git_user_name: merge_option(&base.git_user_name, &overlay.git_user_name),
+            git_user_email: if overlay.git_user_email.is_empty() {
+                base.git_user_email.clone()
+            } else {
+                overlay.git_user_email.clone()
+            },
+            key_type: if overlays_key { overlay.key_type.clone() } else { base.key_type.clone() },
+            signing_key: if overlays_key { overlay.signing_key.clone() } else { base.signing_key.clone() },
+            vault_name: merge_option(&base.vault_name, &overlay.vault_name),
+            ssh_key_title: merge_option(&base.ssh_key_title, &overlay.ssh_key_title),
+            scope: merge_option(&base.scope, &overlay.scope),
+            ssh_key_source: merge_option(&base.ssh_key_source, &overlay.ssh_key_source),
+            ssh_key_path: merge_option(&base.ssh_key_path, &overlay.ssh_key_path),
+            allowed_signers: merge_option(&base.allowed_signers, &overlay.allowed_signers),
+            match_patterns: merge_list(&base.match_patterns, &overlay.match_patterns),
+            repos: merge_list(&base.repos, &overlay.repos),
+            include_if_dirs: merge_list(&base.include_if_dirs, &overlay.include_if_dirs),
+            host_patterns: merge_list(&base.host_patterns, &overlay.host_patterns),
+            one_password: base.one_password || overlay.one_password,
+            require_passphrase: base.require_passphrase || overlay.require_passphrase,
+            is_archived: false,
+            description: merge_option(&base.description, &overlay.description),
+            per_field_scope,
+            git_hooks_dir: merge_option(&base.git_hooks_dir, &overlay.git_hooks_dir),
+            allowed_signers_content: merge_option(&base.allowed_signers_content, &overlay.allowed_signers_content),
+            secret_store: merge_option(&base.secret_store, &overlay.secret_store),
+            rotated_key_item: merge_option(&base.rotated_key_item, &overlay.rotated_key_item),
+            tags: if overlay.tags.is_empty() { base.tags.clone() } else { overlay.tags.clone() },
+        }
+    }
+
+    /// Scope the profile is applied with.
+    ///
+    /// An explicit scope always wins; without one, the profile is applied locally
+    /// inside a git work tree and globally elsewhere.
+    pub fn effective_scope(&self, in_repo: bool) -> Scope {
+        match &self.scope {
+            Some(scope) => scope.clone(),
+            None if in_repo => Scope::Local,
+            None => Scope::Global,
+        }
+    }
+
+    /// Secret manager the profile's key is loaded from, if any.
+    ///
+    /// `secret_store` names the manager; profiles that only set `one_password`
+    /// use 1Password, and profiles with neither use a local key.
+    pub fn key_store(&self) -> Option<SecretStoreKind> {
+        self.secret_store
+            .or_else(|| self.one_password.then_some(SecretStoreKind::OnePassword))
+    }
+
+    /// Scope set for the git config `key` in `per_field_scope`, if any.
+    ///
+    /// Git config keys are case-insensitive, so `commit.gpgSign` matches `commit.gpgsign`.
+    pub fn field_scope(&self, key: &str) -> Option<&Scope> {
+        self.per_field_scope
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, scope)| scope)
+    }
+
+    /// Names of the fields [`Profile::get_field`] can read.
+    pub const FIELD_NAMES: &'static [&'static str] = &[
+        "name",
+        "git_user_name",
+        "git_user_email",
+        "email_domain",
+        "key_type",
+        "signing_key",
+        "vault_name",
+        "ssh_key_title",
+        "scope",
+        "ssh_key_source",
+        "ssh_key_path",
+        "allowed_signers",
+        "match_patterns",
+        "repos",
+        "include_if_dirs",
+        "host_patterns",
+        "one_password",
+        "require_passphrase",
+        "is_archived",
+        "description",
+        "per_field_scope",
+        "git_hooks_dir",
+        "allowed_signers_content",
+        "secret_store",
+        "rotated_key_item",
+        "tags",
+    ];
+
+    /// Value of the field named `field`, formatted for scripts.
+    ///
+    /// Unset optional fields are empty, lists are comma separated and flags
+    /// are `true`/`false`. Returns `None` if there is no such field.
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let value = match field {
+            "name" => self.name.clone(),
+            "git_user_name" => optional(&self.git_user_name),
+            "git_user_email" => self.git_user_email.clone(),
+            "email_domain" => self
+                .git_user_email
+                .rsplit_once('@')
+                .map(|(_, domain)| domain.to_string())
+                .unwrap_or_default(),
+            "key_type" => format!("{:?}", self.key_type).to_lowercase(),
+            "signing_key" => optional(&self.signing_key),
+            "vault_name" => optional(&self.vault_name),
+            "ssh_key_title" => optional(&self.ssh_key_title),
+            "scope" => self
+                .scope
+                .as_ref()
+                .map(|scope| format!("{:?}", scope).to_lowercase())
+                .unwrap_or_default(),
+            "ssh_key_source" => self
+                .ssh_key_source
+                .as_ref()
+                .map(|source| format!("{:?}", source).to_lowercase())
+                .unwrap_or_default(),
+            "ssh_key_path" => optional(&self.ssh_key_path),
+            "allowed_signers" => optional(&self.allowed_signers),
+            "match_patterns" => self.match_patterns.join(","),
+            "repos" => self.repos.join(","),
+            "include_if_dirs" => self.include_if_dirs.join(","),
+            "host_patterns" => self.host_patterns.join(","),
+            "one_password" => self.one_password.to_string(),
+            "require_passphrase" => self.require_passphrase.to_string(),
+            "is_archived" => self.is_archived.to_string(),
+            "description" => optional(&self.description),
+            "per_field_scope" => {
+                let mut scopes: Vec<String> = self
+                    .per_field_scope
+                    .iter()
+                    .map(|(key, scope)| format!("{}={}", key, format!("{:?}", scope).to_lowercase()))
+                    .collect();
+                scopes.sort();
+                scopes.join(",")
+            }
+            "git_hooks_dir" => self
+                .git_hooks_dir
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            "allowed_signers_content" => optional(&self.allowed_signers_content),
+            "secret_store" => self
+                .secret_store
+                .map(|store| format!("{:?}", store).to_lowercase())
+                .unwrap_or_default(),
+            "rotated_key_item" => optional(&self.rotated_key_item),
+            "tags" => self.tags.join(","),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// `overlay` if it is set, otherwise `base`.
+fn merge_option<T: Clone>(base: &Option<T>, overlay: &Option<T>) -> Option<T> {
+    overlay.clone().or_else(|| base.clone())
+}
+
+/// The entries of `base` followed by those of `overlay` it lacks.
+fn merge_list(base: &[String], overlay: &[String]) -> Vec<String> {
+    let mut merged = base.to_vec();
+    for entry in overlay {
+        if !merged.contains(entry) {
+            merged.push(entry.clone());
+        }
+    }
+    merged
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -49,7 +297,41 @@ pub enum KeyType {
     Gitsign,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl std::str::FromStr for KeyType {
+    type Err = crate::error::GitSetupError;
+
+    /// Parse a key type name case-insensitively (`ssh`, `gpg`, `x509`, `gitsign`).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "ssh" => Ok(KeyType::Ssh),
+            "gpg" => Ok(KeyType::Gpg),
+            "x509" => Ok(KeyType::X509),
+            "gitsign" => Ok(KeyType::Gitsign),
+            _ => Err(crate::error::GitSetupError::Git(format!("Unknown key type: {}", value))),
+        }
+    }
+}
+
+/// Prefixes of SSH public keys, including `sk-` hardware keys.
+const SSH_KEY_PREFIXES: &[&str] = &["ssh-", "ecdsa-sha2-", "sk-ssh-", "sk-ecdsa-sha2-"];
+
+/// Deduce the key type from the shape of a signing key.
+///
+/// SSH public keys (also with git's `key::` prefix) are [`KeyType::Ssh`]; GPG key
+/// IDs and fingerprints of 8, 16 or 40 hex digits, optionally with a `0x` prefix
+/// and spaces, are [`KeyType::Gpg`]. Key file paths and anything else give `None`.
+pub fn infer_key_type(signing_key: &str) -> Option<KeyType> {
+    let key = signing_key.trim();
+    let literal = key.strip_prefix("key::").unwrap_or(key);
+    if SSH_KEY_PREFIXES.iter().any(|prefix| literal.starts_with(prefix)) {
+        return Some(KeyType::Ssh);
+    }
+
+    let hex = key.strip_prefix("0x").unwrap_or(key).replace(' ', "");
+    (matches!(hex.len(), 8 | 16 | 40) && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(KeyType::Gpg)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Scope {
     #[default]
@@ -58,7 +340,21 @@ pub enum Scope {
     System,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl std::str::FromStr for Scope {
+    type Err = crate::error::GitSetupError;
+
+    /// Parse a scope name case-insensitively (`local`, `global`, `system`).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "local" => Ok(Scope::Local),
+            "global" => Ok(Scope::Global),
+            "system" => Ok(Scope::System),
+            _ => Err(crate::error::GitSetupError::Git(format!("Unknown scope: {}", value))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum SshKeySource {
     #[default]
@@ -67,6 +363,15 @@ pub enum SshKeySource {
     File,
 }
 
+/// Secret manager holding a profile's keys.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretStoreKind {
+    #[default]
+    OnePassword,
+    Bitwarden,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigPaths {
     pub global: ConfigPath,
@@ -85,10 +390,78 @@ pub struct SshDefaults {
     pub key_size: Option<u32>,
 }
 
+/// Settings for how git-setup drives external tools.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ToolConfig {
+    /// Maximum number of 1Password CLI calls per minute; unlimited when unset.
+    pub op_rate_limit_calls_per_minute: Option<u32>,
+    /// Length of TUI screen transitions in milliseconds; 0 disables them.
+    pub tui_animation_duration_ms: Option<u64>,
+    /// Scripts enforcing custom policies on profiles before they are saved.
+    #[serde(default)]
+    pub validation_hooks: Vec<ValidationHookConfig>,
+}
+
+/// A script run with the profile as JSON on stdin before it is saved.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ValidationHookConfig {
+    /// Shell command of the hook
+    pub command: String,
+    /// Glob matching the names of the profiles the hook applies to
+    #[serde(default = "default_hook_profiles")]
+    pub applies_to_profiles: String,
+    /// Reject the profile when the hook exits non-zero; otherwise only warn
+    #[serde(default = "default_fail_on_nonzero")]
+    pub fail_on_nonzero: bool,
+}
+
+fn default_hook_profiles() -> String {
+    "*".to_string()
+}
+
+fn default_fail_on_nonzero() -> bool {
+    true
+}
+
+impl ToolConfig {
+    /// Configured TUI transition length, if set.
+    pub fn tui_animation_duration(&self) -> Option<std::time::Duration> {
+        self.tui_animation_duration_ms.map(std::time::Duration::from_millis)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_infer_key_type() {
+        for key in [
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA jane@work.com",
+            "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAABAQ",
+            "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTY",
+            "sk-ssh-ed25519@openssh.com AAAAGnNrLXNzaC1lZDI1NTE5",
+            "sk-ecdsa-sha2-nistp256@openssh.com AAAAInNrLWVjZHNh",
+            "key::ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA",
+        ] {
+            assert_eq!(infer_key_type(key), Some(KeyType::Ssh), "{}", key);
+        }
+
+        for key in [
+            "0123456789ABCDEF0123456789abcdef01234567",
+            "0123 4567 89AB CDEF 0123  4567 89AB CDEF 0123 4567",
+            "1234567890ABCDEF",
+            "0x1234567890ABCDEF",
+            "ABCD1234",
+        ] {
+            assert_eq!(infer_key_type(key), Some(KeyType::Gpg), "{}", key);
+        }
+
+        for key in ["", "   ", "~/.ssh/id_ed25519.pub", "jane@work.com", "1234567890ABCDEG", "ABCDEF12345"] {
+            assert_eq!(infer_key_type(key), None, "{}", key);
+        }
+    }
+
     #[test]
     fn test_profile_toml_roundtrip() {
         let profile = Profile {
@@ -108,6 +481,15 @@ mod tests {
             include_if_dirs: vec!["dir1".to_string()],
             host_patterns: vec!["*.example.com".to_string()],
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let toml_str = toml::to_string(&profile).unwrap();
@@ -119,6 +501,128 @@ mod tests {
         assert_eq!(profile.one_password, parsed.one_password);
     }
 
+    #[test]
+    fn test_profile_merge() {
+        let base = Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Jane Doe".to_string()),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Gpg,
+            signing_key: Some("ABCD1234".to_string()),
+            repos: vec!["~/work/api".to_string(), "~/work/web".to_string()],
+            match_patterns: vec!["github.com/acme/*".to_string()],
+            include_if_dirs: vec!["~/work/".to_string()],
+            description: Some("Work".to_string()),
+            is_archived: true,
+            tags: vec!["work".to_string()],
+            ..Default::default()
+        };
+        let overlay = Profile {
+            name: "client".to_string(),
+            git_user_email: "jane@client.com".to_string(),
+            repos: vec!["~/work/web".to_string(), "~/client/app".to_string()],
+            host_patterns: vec!["git.client.com".to_string()],
+            include_if_dirs: vec!["~/work/".to_string(), "~/client/".to_string()],
+            scope: Some(Scope::Local),
+            ..Default::default()
+        };
+
+        let merged = Profile::merge(&base, &overlay);
+        assert_eq!(merged.name, "work");
+        assert_eq!(merged.git_user_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(merged.git_user_email, "jane@client.com");
+        // The overlay sets no key, so the GPG key of the base is kept
+        assert_eq!(merged.key_type, KeyType::Gpg);
+        assert_eq!(merged.signing_key.as_deref(), Some("ABCD1234"));
+        assert_eq!(merged.scope, Some(Scope::Local));
+        assert_eq!(merged.repos, vec!["~/work/api", "~/work/web", "~/client/app"]);
+        assert_eq!(merged.match_patterns, vec!["github.com/acme/*"]);
+        assert_eq!(merged.host_patterns, vec!["git.client.com"]);
+        assert_eq!(merged.include_if_dirs, vec!["~/work/", "~/client/"]);
+        assert_eq!(merged.description.as_deref(), Some("Work"));
+        assert_eq!(merged.tags, vec!["work"]);
+        assert!(!merged.is_archived);
+
+        let overlay = Profile {
+            key_type: KeyType::Ssh,
+            signing_key: Some("ssh-ed25519 AAAA".to_string()),
+            ..Default::default()
+        };
+        let merged = Profile::merge(&base, &overlay);
+        assert_eq!(merged.key_type, KeyType::Ssh);
+        assert_eq!(merged.signing_key.as_deref(), Some("ssh-ed25519 AAAA"));
+        assert_eq!(merged.git_user_email, "jane@work.com");
+
+        // A key type without a key does not keep the key of the other type
+        let ssh_base = Profile {
+            key_type: KeyType::Ssh,
+            signing_key: Some("ssh-ed25519 AAAA".to_string()),
+            ..base.clone()
+        };
+        let overlay = Profile {
+            key_type: KeyType::Gpg,
+            ..Default::default()
+        };
+        let merged = Profile::merge(&ssh_base, &overlay);
+        assert_eq!(merged.key_type, KeyType::Gpg);
+        assert_eq!(merged.signing_key, None);
+    }
+
+    #[test]
+    fn test_effective_scope() {
+        let mut profile = Profile::default();
+        assert_eq!(profile.effective_scope(true), Scope::Local);
+        assert_eq!(profile.effective_scope(false), Scope::Global);
+
+        for scope in [Scope::Local, Scope::Global, Scope::System] {
+            profile.scope = Some(scope.clone());
+            assert_eq!(profile.effective_scope(true), scope);
+            assert_eq!(profile.effective_scope(false), scope);
+        }
+    }
+
+    #[test]
+    fn test_per_field_scope() {
+        let toml_str = r#"
+            name = "work"
+            git_user_email = "jane@work.com"
+            key_type = "ssh"
+            scope = "global"
+
+            [per_field_scope]
+            "commit.gpgsign" = "local"
+        "#;
+        let profile: Profile = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(profile.field_scope("commit.gpgSign"), Some(&Scope::Local));
+        assert_eq!(profile.field_scope("user.email"), None);
+        assert_eq!(profile.get_field("per_field_scope").as_deref(), Some("commit.gpgsign=local"));
+        assert_eq!("System".parse::<Scope>().unwrap(), Scope::System);
+        assert!("repo".parse::<Scope>().is_err());
+    }
+
+    #[test]
+    fn test_get_field() {
+        let profile = Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Gpg,
+            scope: Some(Scope::Global),
+            repos: vec!["~/work/*".to_string(), "~/oss/*".to_string()],
+            one_password: true,
+            ..Default::default()
+        };
+
+        assert_eq!(profile.get_field("git_user_email").as_deref(), Some("jane@work.com"));
+        assert_eq!(profile.get_field("key_type").as_deref(), Some("gpg"));
+        assert_eq!(profile.get_field("scope").as_deref(), Some("global"));
+        assert_eq!(profile.get_field("repos").as_deref(), Some("~/work/*,~/oss/*"));
+        assert_eq!(profile.get_field("one_password").as_deref(), Some("true"));
+        assert_eq!(profile.get_field("signing_key").as_deref(), Some(""));
+        assert_eq!(profile.get_field("email"), None);
+        assert!(Profile::FIELD_NAMES.iter().all(|field| profile.get_field(field).is_some()));
+    }
+
     #[test]
     fn test_profile_default_vecs() {
         let toml_str = r#"
@@ -226,6 +730,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_secret_store_deserialization() {
+        let toml_str = r#"
+            name = "test"
+            git_user_email = "test@example.com"
+            key_type = "ssh"
+            one_password = true
+            secret_store = "bitwarden"
+        "#;
+        let profile: Profile = toml::from_str(toml_str).unwrap();
+        assert_eq!(profile.secret_store, Some(SecretStoreKind::Bitwarden));
+        assert_eq!(profile.key_store(), Some(SecretStoreKind::Bitwarden));
+
+        // secret_store alone is enough to load the key from the store
+        let profile = Profile {
+            one_password: false,
+            ..profile
+        };
+        assert_eq!(profile.key_store(), Some(SecretStoreKind::Bitwarden));
+
+        // Profiles written before secret_store existed keep using 1Password
+        let profile = Profile {
+            one_password: true,
+            ..Profile::default()
+        };
+        assert_eq!(profile.secret_store, None);
+        assert_eq!(profile.key_store(), Some(SecretStoreKind::OnePassword));
+        assert_eq!(Profile::default().key_store(), None);
+    }
+
     #[test]
     fn test_config_toml_roundtrip() {
         let config = Config {
@@ -247,6 +781,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: HashMap::new(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             }),
             profiles: vec![],
             config_paths: ConfigPaths {
@@ -264,6 +807,7 @@ mod tests {
                 key_type: "ed25519".to_string(),
                 key_size: Some(256),
             }),
+            tool: ToolConfig::default(),
         };
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -274,6 +818,28 @@ mod tests {
         assert!(parsed.ssh_defaults.is_some());
     }
 
+    #[test]
+    fn test_validation_hooks_toml() {
+        let toml_str = r#"
+            [[validation_hooks]]
+            command = "check-gpg-4096"
+            applies_to_profiles = "work-*"
+
+            [[validation_hooks]]
+            command = "warn-only"
+            fail_on_nonzero = false
+        "#;
+
+        let tool: ToolConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(tool.validation_hooks.len(), 2);
+        assert_eq!(tool.validation_hooks[0].applies_to_profiles, "work-*");
+        assert!(tool.validation_hooks[0].fail_on_nonzero);
+        assert_eq!(tool.validation_hooks[1].applies_to_profiles, "*");
+        assert!(!tool.validation_hooks[1].fail_on_nonzero);
+
+        assert!(toml::from_str::<ToolConfig>("").unwrap().validation_hooks.is_empty());
+    }
+
     #[test]
     fn test_invalid_enum_handling() {
         let invalid_key_type = r#"
@@ -337,4 +903,36 @@ mod tests {
         assert_eq!(config_paths.default.path, "/default");
         assert_eq!(config_paths.system.path, "/system");
     }
+
+    #[test]
+    fn test_profile_with_overrides() {
+        let profile = Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Work User".to_string()),
+            git_user_email: "work@example.com".to_string(),
+            signing_key: Some("key123".to_string()),
+            one_password: true,
+            ..Default::default()
+        };
+
+        let overridden = profile.clone().with_overrides(ProfileOverrides {
+            git_user_email: Some("client@example.com".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(overridden.name, "work");
+        assert_eq!(overridden.git_user_email, "client@example.com");
+        assert_eq!(overridden.git_user_name, profile.git_user_name);
+        assert_eq!(overridden.signing_key, profile.signing_key);
+        assert_eq!(overridden.key_store(), Some(SecretStoreKind::OnePassword));
+
+        let overridden = profile.with_overrides(ProfileOverrides {
+            git_user_name: Some("Client User".to_string()),
+            signing_key: Some("key456".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(overridden.git_user_email, "work@example.com");
+        assert_eq!(overridden.git_user_name, Some("Client User".to_string()));
+        assert_eq!(overridden.signing_key, Some("key456".to_string()));
+        assert_eq!(overridden.key_store(), None);
+    }
 }
@@ -3,13 +3,101 @@
 //! This module provides functionality to extract relevant information from
 //! the current repository and environment for use in profile detection.
 
+use super::extractors::CustomExtractor;
 use crate::{
-    error::Result,
+    error::{Result, ResultExt},
     external::git::{GitConfigScope, GitWrapper},
     platform::{PlatformPaths, SystemPlatform},
 };
+use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Git config key listing the profiles a repository suggests, comma-separated
+pub const PROFILE_HINTS_KEY: &str = "git-setup.profile-hints";
+
+/// Profile names in a `git-setup.profile-hints` value such as `"work, acme"`.
+pub fn parse_profile_hints(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|hint| !hint.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Host of a git remote URL, for `https://`, `ssh://` and scp-like `git@host:path` URLs.
+///
+/// Local paths and `file://` URLs have no host.
+pub fn remote_hostname(url: &str) -> Option<String> {
+    let authority = match url.split_once("://") {
+        Some(("file", _)) => return None,
+        Some((_, rest)) => rest.split('/').next()?,
+        // scp-like syntax needs a colon before the first slash
+        None => url.split_once(':').map(|(host, _)| host).filter(|host| !host.contains('/'))?,
+    };
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// Repository path of a git remote URL on its host, e.g. `group/repo` for
+/// `git@gitlab.com:group/repo.git`, without a trailing `.git`.
+///
+/// URLs without a host (see [`remote_hostname`]) have no path.
+pub fn remote_path(url: &str) -> Option<String> {
+    remote_hostname(url)?;
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+        None => url.split_once(':').map(|(_, path)| path)?,
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    Some(path.to_string())
+}
+
+/// Source of the machine's hostname, so detection can be tested with a fixed name.
+pub trait HostnameProvider: Send + Sync {
+    /// The hostname, or `None` if it cannot be determined
+    fn hostname(&self) -> Option<String>;
+}
+
+/// Hostname of this machine, as returned by `gethostname()`
+pub struct SystemHostname;
+
+impl HostnameProvider for SystemHostname {
+    fn hostname(&self) -> Option<String> {
+        hostname::get().ok().and_then(|h| h.to_str().map(String::from))
+    }
+}
+
+/// Hostname provider returning a fixed name, for testing
+pub struct MockHostnameProvider {
+    hostname: Option<String>,
+}
+
+impl MockHostnameProvider {
+    pub fn new(hostname: &str) -> Self {
+        Self {
+            hostname: Some(hostname.to_string()),
+        }
+    }
+
+    /// A provider that cannot determine the hostname.
+    pub fn unknown() -> Self {
+        Self { hostname: None }
+    }
+}
+
+impl HostnameProvider for MockHostnameProvider {
+    fn hostname(&self) -> Option<String> {
+        self.hostname.clone()
+    }
+}
 
 /// Repository context information
 #[derive(Debug, Clone)]
@@ -34,6 +122,15 @@ pub struct RepositoryContext {
 
     /// Parent directories up to home
     pub parent_dirs: Vec<PathBuf>,
+
+    /// Fields added by custom extractors, matched as `custom.<field>`
+    pub custom_fields: HashMap<String, String>,
+
+    /// Profile names suggested by the repository's `git-setup.profile-hints` config
+    pub profile_hints: Vec<String>,
+
+    /// Host of the `origin` remote (or the first remote), e.g. `github.com`
+    pub remote_hostname: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,18 +143,34 @@ pub struct RemoteInfo {
 pub struct ContextExtractor<G: GitWrapper> {
     git: G,
     platform: Box<dyn PlatformPaths>,
+    hostname_provider: Arc<dyn HostnameProvider>,
+    custom_extractors: Vec<Box<dyn CustomExtractor>>,
 }
 
 impl<G: GitWrapper> ContextExtractor<G> {
     pub fn new(git: G) -> Self {
+        Self::with_platform(git, Box::new(SystemPlatform))
+    }
+
+    pub fn with_platform(git: G, platform: Box<dyn PlatformPaths>) -> Self {
         Self {
             git,
-            platform: Box::new(SystemPlatform),
+            platform,
+            hostname_provider: Arc::new(SystemHostname),
+            custom_extractors: Vec::new(),
         }
     }
 
-    pub fn with_platform(git: G, platform: Box<dyn PlatformPaths>) -> Self {
-        Self { git, platform }
+    /// Read the hostname from `provider` instead of the system.
+    pub fn with_hostname_provider(mut self, provider: Arc<dyn HostnameProvider>) -> Self {
+        self.hostname_provider = provider;
+        self
+    }
+
+    /// Run `extractors` after the built-in fields are extracted.
+    pub fn with_custom_extractors(mut self, extractors: Vec<Box<dyn CustomExtractor>>) -> Self {
+        self.custom_extractors = extractors;
+        self
     }
 
     pub fn extract(&self) -> Result<RepositoryContext> {
@@ -86,16 +199,34 @@ impl<G: GitWrapper> ContextExtractor<G> {
             (Vec::new(), None, None)
         };
 
+        // Hints committed by the team, e.g. through a tracked `.gitconfig` include.
+        // Scoped reads skip `include.path`, so read from every config file.
+        let profile_hints = if repo_root.is_some() {
+            self.git
+                .get_config(PROFILE_HINTS_KEY, None)
+                .unwrap_or(None)
+                .map(|value| parse_profile_hints(&value))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let remote_hostname = remotes
+            .iter()
+            .find(|remote| remote.name == "origin")
+            .or_else(|| remotes.first())
+            .and_then(|remote| remote_hostname(&remote.url));
+
         // Get hostname
-        let hostname = hostname::get()
-            .ok()
-            .and_then(|h| h.to_str().map(String::from))
+        let hostname = self
+            .hostname_provider
+            .hostname()
             .unwrap_or_else(|| "unknown".to_string());
 
         // Build parent directory list
         let parent_dirs = self.build_parent_dirs(&working_dir)?;
 
-        Ok(RepositoryContext {
+        let mut context = RepositoryContext {
             working_dir,
             repo_root,
             remotes,
@@ -103,7 +234,18 @@ impl<G: GitWrapper> ContextExtractor<G> {
             current_name,
             hostname,
             parent_dirs,
-        })
+            custom_fields: HashMap::new(),
+            profile_hints,
+            remote_hostname,
+        };
+
+        for extractor in &self.custom_extractors {
+            extractor
+                .extract(&mut context)
+                .with_context(format!("while running custom extractor '{}'", extractor.name()))?;
+        }
+
+        Ok(context)
     }
 
     fn find_repo_root(&self, start: &Path) -> Result<Option<PathBuf>> {
@@ -168,7 +310,7 @@ impl<G: GitWrapper> ContextExtractor<G> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::external::git::MockGitWrapper;
+    use crate::external::git::{MockGitWrapper, SystemGitWrapper};
     use std::collections::HashMap;
     use tempfile::TempDir;
 
@@ -237,6 +379,76 @@ mod tests {
         assert_eq!(context.remotes.len(), 1);
         assert_eq!(context.remotes[0].url, "git@github.com:user/repo.git");
         assert_eq!(context.remotes[0].name, "origin");
+        assert_eq!(context.remote_hostname.as_deref(), Some("github.com"));
+    }
+
+    #[test]
+    fn test_remote_hostname() {
+        assert_eq!(remote_hostname("git@github.com:user/repo.git").as_deref(), Some("github.com"));
+        assert_eq!(remote_hostname("https://gitlab.com/group/repo.git").as_deref(), Some("gitlab.com"));
+        assert_eq!(
+            remote_hostname("ssh://git@git.example.com:2222/repo.git").as_deref(),
+            Some("git.example.com")
+        );
+        assert_eq!(remote_hostname("https://user:token@[::1]:8443/repo").as_deref(), Some("::1"));
+        assert_eq!(remote_hostname("/srv/git/repo.git"), None);
+        assert_eq!(remote_hostname("../repo"), None);
+        assert_eq!(remote_hostname("file:///srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn test_remote_path() {
+        assert_eq!(remote_path("git@github.com:user/repo.git").as_deref(), Some("user/repo"));
+        assert_eq!(
+            remote_path("https://gitlab.com/group/subgroup/repo.git").as_deref(),
+            Some("group/subgroup/repo")
+        );
+        assert_eq!(
+            remote_path("ssh://git@gitea.example.com:2222/team/app.git").as_deref(),
+            Some("team/app")
+        );
+        assert_eq!(remote_path("https://github.com").as_deref(), Some(""));
+        assert_eq!(remote_path("/srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn test_context_extraction_profile_hints() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+
+        let config = HashMap::from([(PROFILE_HINTS_KEY.to_string(), " work, acme ,,".to_string())]);
+        let mock_git = MockGitWrapper::new().with_config(config);
+        let mock_platform = Box::new(MockPlatformPaths::new(PathBuf::from("/home/test")));
+
+        let extractor = ContextExtractor::with_platform(mock_git, mock_platform);
+        let context = extractor.extract_in(temp_dir.path()).unwrap();
+
+        assert_eq!(context.profile_hints, vec!["work".to_string(), "acme".to_string()]);
+    }
+
+    #[test]
+    fn test_context_extraction_profile_hints_from_include() {
+        let temp_dir = TempDir::new().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "--quiet"])
+            .current_dir(temp_dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        std::fs::write(
+            temp_dir.path().join(".gitconfig"),
+            "[git-setup]\n\tprofile-hints = work, acme\n",
+        )
+        .unwrap();
+        let git = SystemGitWrapper::in_dir(temp_dir.path());
+        git.set_config("include.path", "../.gitconfig", GitConfigScope::Local)
+            .unwrap();
+
+        let mock_platform = Box::new(MockPlatformPaths::new(PathBuf::from("/home/test")));
+        let extractor = ContextExtractor::with_platform(git, mock_platform);
+        let context = extractor.extract_in(temp_dir.path()).unwrap();
+
+        assert_eq!(context.profile_hints, vec!["work".to_string(), "acme".to_string()]);
     }
 
     #[test]
@@ -365,6 +577,22 @@ mod tests {
         assert!(!context.hostname.is_empty());
     }
 
+    #[test]
+    fn test_hostname_provider() {
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = |provider: MockHostnameProvider| {
+            let mock_platform = Box::new(MockPlatformPaths::new(PathBuf::from("/home/test")));
+            ContextExtractor::with_platform(MockGitWrapper::new(), mock_platform)
+                .with_hostname_provider(Arc::new(provider))
+        };
+
+        let context = extractor(MockHostnameProvider::new("ci-runner-42")).extract_in(temp_dir.path()).unwrap();
+        assert_eq!(context.hostname, "ci-runner-42");
+
+        let context = extractor(MockHostnameProvider::unknown()).extract_in(temp_dir.path()).unwrap();
+        assert_eq!(context.hostname, "unknown");
+    }
+
     #[test]
     fn test_context_debug_display() {
         let context = RepositoryContext {
@@ -375,6 +603,9 @@ mod tests {
             current_name: None,
             hostname: "test-host".to_string(),
             parent_dirs: vec![],
+            custom_fields: HashMap::new(),
+            profile_hints: vec![],
+            remote_hostname: None,
         };
 
         let debug_str = format!("{:?}", context);
@@ -436,4 +667,30 @@ mod tests {
         assert!(context.remotes.is_empty());
         assert!(context.current_email.is_none());
     }
+
+    struct ConstantExtractor;
+
+    impl CustomExtractor for ConstantExtractor {
+        fn extract(&self, context: &mut RepositoryContext) -> Result<()> {
+            let dir = context.working_dir.display().to_string();
+            context.custom_fields.insert("team".to_string(), format!("infra:{}", dir.len()));
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "constant"
+        }
+    }
+
+    #[test]
+    fn test_custom_extractors_run_after_builtin_fields() {
+        let mock_platform = Box::new(MockPlatformPaths::new(PathBuf::from("/home/test")));
+        let extractor = ContextExtractor::with_platform(MockGitWrapper::new(), mock_platform)
+            .with_custom_extractors(vec![Box::new(ConstantExtractor)]);
+        let temp_dir = TempDir::new().unwrap();
+
+        let context = extractor.extract_in(temp_dir.path()).unwrap();
+        let expected = format!("infra:{}", temp_dir.path().display().to_string().len());
+        assert_eq!(context.custom_fields.get("team"), Some(&expected));
+    }
 }
@@ -4,28 +4,44 @@
 //! rules to automatically select the best matching profile for the current context.
 
 use super::{
-    context::{ContextExtractor, RepositoryContext},
+    context::{ContextExtractor, HostnameProvider, RepositoryContext, SystemHostname},
+    extractors::custom_extractor,
     rules::*,
-    DetectionConfig, DetectionResult, MatchedRule, ProfileDetector,
+    DetectionConfig, DetectionResult, MatchedRule, ProfileDetector, TwoPhaseSetup,
 };
 use crate::{
-    config::types::Profile, error::Result, external::git::GitWrapper, profile::ProfileManager,
+    config::types::Profile,
+    error::Result,
+    external::git::{GitConfigScope, GitWrapper},
+    profile::ProfileManager,
 };
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Git config keys that identify which profile is applied.
+pub const IDENTITY_CONFIG_KEYS: &[&str] = &["user.email", "user.name", "user.signingkey"];
+
+/// Notes ref whose note on a commit names the profile it was made with.
+pub const PROFILE_NOTES_REF: &str = "refs/notes/git-setup";
+
+/// Confidence of a profile named by a commit note.
+const GIT_NOTES_CONFIDENCE: f64 = 0.9;
+
 pub struct AutoDetector<P: ProfileManager, G: GitWrapper> {
     profile_manager: Arc<P>,
     git: Arc<G>,
     config: DetectionConfig,
     rules: Vec<Box<dyn DetectionRule>>,
+    hostname_provider: Arc<dyn HostnameProvider>,
     cache: HashMap<String, DetectionResult>,
 }
 
 impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> AutoDetector<P, G> {
-    pub fn new(profile_manager: Arc<P>, git: Arc<G>) -> Self {
-        Self::with_config(profile_manager, git, DetectionConfig::default())
+    /// Create a detector using the settings saved by the TUI settings screen, or the defaults.
+    pub fn new(profile_manager: Arc<P>, git: Arc<G>) -> Result<Self> {
+        let config = DetectionConfig::load(&DetectionConfig::default_path()?)?;
+        Ok(Self::with_config(profile_manager, git, config))
     }
 
     pub fn with_config(
@@ -40,10 +56,17 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> AutoDetector<P, G>
             git,
             config,
             rules,
+            hostname_provider: Arc::new(SystemHostname),
             cache: HashMap::new(),
         }
     }
 
+    /// Match `host_patterns` against the hostname from `provider` instead of the system's.
+    pub fn with_hostname_provider(mut self, provider: Arc<dyn HostnameProvider>) -> Self {
+        self.hostname_provider = provider;
+        self
+    }
+
     fn build_rules(config: &DetectionConfig) -> Vec<Box<dyn DetectionRule>> {
         let mut rules: Vec<Box<dyn DetectionRule>> = Vec::new();
 
@@ -62,15 +85,37 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> AutoDetector<P, G>
         if config.check_git_config {
             rules.push(Box::new(GitConfigRule::new()));
         }
+        if config.check_profile_hints {
+            rules.push(Box::new(CommittedHintRule::new()));
+        }
+        if !config.custom_extractors.is_empty() {
+            rules.push(Box::new(CustomFieldRule::new()));
+        }
 
         rules
     }
 
-    fn score_profile(
+    /// Context extractor running the custom extractors enabled in the config.
+    ///
+    /// Names without a built-in extractor are skipped.
+    fn context_extractor(&self) -> ContextExtractor<G> {
+        let extractors = self
+            .config
+            .custom_extractors
+            .iter()
+            .filter_map(|name| custom_extractor(name))
+            .collect();
+        ContextExtractor::new((*self.git).clone())
+            .with_hostname_provider(self.hostname_provider.clone())
+            .with_custom_extractors(extractors)
+    }
+
+    /// Weighted confidence of `profile` and the rules that matched, before the threshold is applied
+    fn weighted_score(
         &self,
         profile: &Profile,
         context: &RepositoryContext,
-    ) -> Option<DetectionResult> {
+    ) -> Option<(f64, Vec<MatchedRule>)> {
         let mut matched_rules = Vec::new();
         let mut total_score = 0.0;
         let mut total_weight = 0.0;
@@ -97,7 +142,15 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> AutoDetector<P, G>
         }
 
         // Calculate weighted average
-        let confidence = total_score / total_weight;
+        Some((total_score / total_weight, matched_rules))
+    }
+
+    fn score_profile(
+        &self,
+        profile: &Profile,
+        context: &RepositoryContext,
+    ) -> Option<DetectionResult> {
+        let (confidence, matched_rules) = self.weighted_score(profile, context)?;
 
         if confidence < self.config.min_confidence {
             return None;
@@ -115,6 +168,148 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> AutoDetector<P, G>
         })
     }
 
+    /// Explain why none of `profiles` was detected in `context`
+    fn explain(&self, profiles: &[Profile], context: &RepositoryContext) -> String {
+        if profiles.is_empty() {
+            return "No profile detected because: no profiles are configured.".to_string();
+        }
+
+        let mut scored: Vec<(f64, &Profile, Vec<MatchedRule>)> = profiles
+            .iter()
+            .filter_map(|profile| {
+                self.weighted_score(profile, context)
+                    .map(|(confidence, rules)| (confidence, profile, rules))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        if let Some((confidence, profile, _)) = scored.first()
+            && *confidence >= self.config.min_confidence
+        {
+            return format!(
+                "Profile '{}' was detected with {:.0}% confidence.",
+                profile.name,
+                confidence * 100.0
+            );
+        }
+
+        let dir = context.working_dir.display();
+        let mut reasons = Vec::new();
+        for rule in &self.rules {
+            if profiles.iter().any(|profile| rule.matches(profile, context).is_some()) {
+                continue;
+            }
+            let reason = match rule.name() {
+                "remote_url" => match context.remotes.first() {
+                    Some(remote) => format!("Remote URL '{}' matched no profile repos.", remote.url),
+                    None if context.repo_root.is_none() => format!("Directory '{}' is not a git repository.", dir),
+                    None => "The repository has no remotes.".to_string(),
+                },
+                "directory_path" => format!("Directory '{}' matched no match_patterns.", dir),
+                "include_if_dir" => format!("Directory '{}' is not inside any include_if_dirs.", dir),
+                "hostname" => format!("Hostname '{}' matched no host_patterns.", context.hostname),
+                "git_config" => match &context.current_email {
+                    Some(email) => format!("Current user.email '{}' belongs to no profile.", email),
+                    None => "No user.email is set.".to_string(),
+                },
+                _ => continue,
+            };
+            reasons.push(reason);
+        }
+        if Self::find_envrc(context).is_none() {
+            reasons.push("No .envrc found.".to_string());
+        }
+        if reasons.is_empty() {
+            reasons.push(format!(
+                "no profile reached the {:.0}% confidence threshold.",
+                self.config.min_confidence * 100.0
+            ));
+        }
+
+        // Profiles that matched some rules but stayed below the threshold are the closest misses
+        let mut near_misses: Vec<String> = scored
+            .iter()
+            .map(|(confidence, profile, rules)| {
+                let names: Vec<&str> = rules.iter().map(|rule| rule.rule_name.as_str()).collect();
+                format!(
+                    "Profile '{}' scored {:.0}% from {}, below the {:.0}% threshold",
+                    profile.name,
+                    confidence * 100.0,
+                    names.join(", "),
+                    self.config.min_confidence * 100.0
+                )
+            })
+            .collect();
+        for profile in profiles {
+            for rule in &self.rules {
+                if rule.matches(profile, context).is_none() {
+                    near_misses.extend(Self::pattern_miss(rule.name(), profile, context));
+                }
+            }
+        }
+
+        let mut explanation = format!("No profile detected because: {}", reasons.join(" "));
+        if !near_misses.is_empty() {
+            explanation.push_str("\nNear misses:");
+            for miss in near_misses {
+                explanation.push_str(&format!("\n  - {}", miss));
+            }
+        }
+        explanation
+    }
+
+    /// The `.envrc` direnv would load in the working directory, looked up through its parents
+    fn find_envrc(context: &RepositoryContext) -> Option<PathBuf> {
+        context
+            .working_dir
+            .ancestors()
+            .map(|dir| dir.join(".envrc"))
+            .find(|path| path.is_file())
+    }
+
+    /// Describe the patterns of `profile` checked by the named rule that did not match
+    fn pattern_miss(rule_name: &str, profile: &Profile, context: &RepositoryContext) -> Option<String> {
+        let quote = |patterns: &[String]| {
+            patterns.iter().map(|p| format!("'{}'", p)).collect::<Vec<_>>().join(", ")
+        };
+        let dir = context.working_dir.display();
+
+        match rule_name {
+            "remote_url" if !profile.repos.is_empty() => Some(match context.remotes.first() {
+                Some(remote) => format!(
+                    "Profile '{}' had repos {} but the remote URL is '{}'",
+                    profile.name,
+                    quote(&profile.repos),
+                    remote.url
+                ),
+                None => format!(
+                    "Profile '{}' had repos {} but the repository has no remotes",
+                    profile.name,
+                    quote(&profile.repos)
+                ),
+            }),
+            "directory_path" if !profile.match_patterns.is_empty() => Some(format!(
+                "Profile '{}' had match_patterns {} but the current directory is '{}'",
+                profile.name,
+                quote(&profile.match_patterns),
+                dir
+            )),
+            "include_if_dir" if !profile.include_if_dirs.is_empty() => Some(format!(
+                "Profile '{}' had include_if_dirs {} but the current directory is '{}'",
+                profile.name,
+                quote(&profile.include_if_dirs),
+                dir
+            )),
+            "hostname" if !profile.host_patterns.is_empty() => Some(format!(
+                "Profile '{}' had host_patterns {} but the hostname is '{}'",
+                profile.name,
+                quote(&profile.host_patterns),
+                context.hostname
+            )),
+            _ => None,
+        }
+    }
+
     fn build_reason(&self, rules: &[MatchedRule], profile: &Profile) -> String {
         let mut parts = Vec::new();
 
@@ -143,10 +338,51 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> AutoDetector<P, G>
             format!("Profile '{}' detected: {}", profile.name, parts.join(", "))
         }
     }
+
+    /// Detect the profile named in the note of `HEAD` under [`PROFILE_NOTES_REF`].
+    ///
+    /// Teams annotating commits with `apply --annotate-commit` record the
+    /// profile used, which is a very strong signal for the next commit.
+    pub fn detect_from_git_notes(&self) -> Result<Option<DetectionResult>> {
+        let Some(note) = self.git.get_last_commit_note(PROFILE_NOTES_REF)? else {
+            return Ok(None);
+        };
+        let name = note.trim();
+        let Some(profile) = self.profile_manager.list()?.into_iter().find(|p| p.name == name) else {
+            return Ok(None);
+        };
+
+        let reason = format!("Profile '{}' detected: commit note names it", profile.name);
+        Ok(Some(DetectionResult {
+            profile,
+            confidence: GIT_NOTES_CONFIDENCE,
+            matched_rules: vec![MatchedRule {
+                rule_name: "git_notes".to_string(),
+                priority: RulePriority::Exact,
+                confidence: GIT_NOTES_CONFIDENCE,
+            }],
+            reason: reason.clone(),
+            reasons: vec![reason],
+        }))
+    }
+
+    /// Identity config values set in `scope`
+    fn identity_config(&self, scope: GitConfigScope) -> Result<HashMap<String, String>> {
+        let mut config = self.git.get_all_config(Some(scope))?;
+        config.retain(|key, _| IDENTITY_CONFIG_KEYS.contains(&key.as_str()));
+        Ok(config)
+    }
 }
 
 impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> ProfileDetector for AutoDetector<P, G> {
     fn detect(&self) -> Result<Option<DetectionResult>> {
+        // Outside a repository or before the first commit there is no note to read
+        if self.config.check_git_notes
+            && let Ok(Some(result)) = self.detect_from_git_notes()
+        {
+            return Ok(Some(result));
+        }
+
         let current_dir = std::env::current_dir()?;
         self.detect_in(&current_dir)
     }
@@ -161,7 +397,7 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> ProfileDetector for
         }
 
         // Extract repository context
-        let extractor = ContextExtractor::new((*self.git).clone());
+        let extractor = self.context_extractor();
         let context = extractor.extract_in(path)?;
 
         // Get all profiles
@@ -182,11 +418,19 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> ProfileDetector for
         Ok(results.into_iter().next())
     }
 
+    fn explain_miss(&self, path: &Path) -> Result<String> {
+        let extractor = self.context_extractor();
+        let context = extractor.extract_in(path)?;
+        let profiles = self.profile_manager.list()?;
+
+        Ok(self.explain(&profiles, &context))
+    }
+
     fn detect_all(&self) -> Result<Vec<DetectionResult>> {
         let current_dir = std::env::current_dir()?;
 
         // Extract repository context
-        let extractor = ContextExtractor::new((*self.git).clone());
+        let extractor = self.context_extractor();
         let context = extractor.extract_in(&current_dir)?;
 
         // Get all profiles
@@ -203,6 +447,47 @@ impl<P: ProfileManager, G: GitWrapper + Clone + Send + Sync> ProfileDetector for
 
         Ok(results)
     }
+
+    /// Detect a profile applied globally and partly overridden by another profile locally.
+    ///
+    /// This is the layout produced by `--global-then-local`: the local config sets
+    /// some identity keys while others still come from the global config.
+    fn detect_two_phase(&self) -> Result<Option<TwoPhaseSetup>> {
+        let local = self.identity_config(GitConfigScope::Local)?;
+        let global = self.identity_config(GitConfigScope::Global)?;
+
+        let inherits_global = global.keys().any(|key| !local.contains_key(key));
+        if local.is_empty() || !inherits_global {
+            return Ok(None);
+        }
+
+        let Some(email) = global.get("user.email") else {
+            return Ok(None);
+        };
+        let Some(global_profile) = self.profile_manager.search_by_email(email)?.into_iter().next() else {
+            return Ok(None);
+        };
+
+        let profiles = self.profile_manager.list()?;
+
+        let local_profile = profiles
+            .iter()
+            .filter(|p| p.name != global_profile.name)
+            .find(|p| {
+                local.get("user.signingkey").is_some_and(|key| p.signing_key.as_ref() == Some(key))
+                    || local.get("user.email") == Some(&p.git_user_email)
+                    || local.get("user.name").is_some_and(|name| p.git_user_name.as_ref() == Some(name))
+            });
+
+        let mut local_keys: Vec<String> = local.into_keys().collect();
+        local_keys.sort();
+
+        Ok(local_profile.map(|local_profile| TwoPhaseSetup {
+            global_profile: global_profile.name.clone(),
+            local_profile: local_profile.name.clone(),
+            local_keys,
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -210,6 +495,7 @@ mod tests {
     use super::*;
     use crate::{
         config::types::{KeyType, Scope},
+        detection::context::MockHostnameProvider,
         external::git::MockGitWrapper,
         profile::mock::MockProfileManager,
     };
@@ -236,6 +522,15 @@ mod tests {
                 ssh_key_path: None,
                 allowed_signers: None,
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: HashMap::new(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "personal".to_string(),
@@ -254,6 +549,15 @@ mod tests {
                 ssh_key_path: None,
                 allowed_signers: None,
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: HashMap::new(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "hostname-test".to_string(),
@@ -272,6 +576,15 @@ mod tests {
                 ssh_key_path: None,
                 allowed_signers: None,
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: HashMap::new(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ]
     }
@@ -288,7 +601,7 @@ mod tests {
         );
         let git = Arc::new(MockGitWrapper::new().with_config(config));
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
@@ -308,7 +621,7 @@ mod tests {
         let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
 
         let git = Arc::new(MockGitWrapper::new());
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let work_dir = PathBuf::from("/home/user/work/project");
         let result = detector.detect_in(&work_dir).unwrap();
@@ -323,7 +636,7 @@ mod tests {
         let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
 
         let git = Arc::new(MockGitWrapper::new());
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let personal_dir = PathBuf::from("/home/user/personal/project");
         let result = detector.detect_in(&personal_dir).unwrap();
@@ -338,7 +651,8 @@ mod tests {
         let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
 
         let git = Arc::new(MockGitWrapper::new());
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default())
+            .with_hostname_provider(Arc::new(MockHostnameProvider::new("dev-box")));
 
         let unrelated_dir = PathBuf::from("/tmp/random");
         let result = detector.detect_in(&unrelated_dir).unwrap();
@@ -346,6 +660,19 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_detection_by_hostname() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(test_profiles()));
+        let git = Arc::new(MockGitWrapper::new());
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default())
+            .with_hostname_provider(Arc::new(MockHostnameProvider::new("test-box")));
+
+        let result = detector.detect_in(Path::new("/tmp/random")).unwrap().unwrap();
+        assert_eq!(result.profile.name, "hostname-test");
+        assert_eq!(result.matched_rules[0].rule_name, "hostname");
+        assert_eq!(result.matched_rules[0].priority, RulePriority::Medium);
+    }
+
     #[test]
     fn test_confidence_threshold() {
         let profiles = test_profiles();
@@ -378,7 +705,7 @@ mod tests {
         );
         let git = Arc::new(MockGitWrapper::new().with_config(config));
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
@@ -415,7 +742,7 @@ mod tests {
         config.insert("user.email".to_string(), "work@company.com".to_string());
         let git = Arc::new(MockGitWrapper::new().with_config(config));
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
@@ -442,6 +769,7 @@ mod tests {
             check_include_if: true,
             check_hostname: false,
             check_git_config: true,
+            check_profile_hints: false,
             ..Default::default()
         };
 
@@ -457,6 +785,28 @@ mod tests {
         assert!(!rule_names.contains(&"hostname"));
     }
 
+    #[test]
+    fn test_build_rules_with_profile_hints() {
+        let config = DetectionConfig {
+            check_remote_url: false,
+            check_directory: false,
+            check_include_if: false,
+            check_hostname: false,
+            check_git_config: false,
+            ..Default::default()
+        };
+
+        let rules = AutoDetector::<MockProfileManager, MockGitWrapper>::build_rules(&config);
+        let rule_names: Vec<&str> = rules.iter().map(|r| r.name()).collect();
+        assert_eq!(rule_names, vec!["committed_hint"]);
+
+        let config = DetectionConfig {
+            check_profile_hints: false,
+            ..config
+        };
+        assert!(AutoDetector::<MockProfileManager, MockGitWrapper>::build_rules(&config).is_empty());
+    }
+
     #[test]
     fn test_detector_with_custom_config() {
         let profiles = test_profiles();
@@ -470,6 +820,9 @@ mod tests {
             check_include_if: false,
             check_hostname: false,
             check_git_config: false,
+            check_git_notes: false,
+            check_profile_hints: false,
+            custom_extractors: vec![],
             enable_cache: false,
         };
 
@@ -499,7 +852,7 @@ mod tests {
         config.insert("user.email".to_string(), "work@company.com".to_string());
         let git = Arc::new(MockGitWrapper::new().with_config(config));
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
@@ -521,7 +874,7 @@ mod tests {
         let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![]));
         let git = Arc::new(MockGitWrapper::new());
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         let result = detector.detect_in(temp_dir.path()).unwrap();
@@ -559,7 +912,7 @@ mod tests {
         let profile_manager = Arc::new(FailingProfileManager);
         let git = Arc::new(MockGitWrapper::new());
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         let result = detector.detect_in(temp_dir.path());
@@ -573,7 +926,7 @@ mod tests {
         let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
         let git = Arc::new(MockGitWrapper::new().with_failure());
 
-        let detector = AutoDetector::new(profile_manager, git);
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
 
         let temp_dir = TempDir::new().unwrap();
         let result = detector.detect_in(temp_dir.path());
@@ -582,4 +935,140 @@ mod tests {
         // based on non-git rules (like directory patterns)
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_detection_by_custom_field() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".slack_channel"), "#platform").unwrap();
+
+        let team = Profile {
+            name: "platform".to_string(),
+            git_user_email: "jane@platform.example.com".to_string(),
+            match_patterns: vec!["custom.slack_channel=#platform".to_string()],
+            ..Default::default()
+        };
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(vec![team]));
+        let git = Arc::new(MockGitWrapper::new());
+        let config = DetectionConfig {
+            custom_extractors: vec!["team_slack_channel".to_string(), "not_a_plugin".to_string()],
+            ..Default::default()
+        };
+        let detector = AutoDetector::with_config(profile_manager, git, config);
+
+        let result = detector.detect_in(dir.path()).unwrap().unwrap();
+        assert_eq!(result.profile.name, "platform");
+        assert_eq!(result.matched_rules[0].rule_name, "custom_field");
+    }
+
+    #[test]
+    fn test_explain_miss() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(test_profiles()));
+        let git = Arc::new(MockGitWrapper::new());
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default())
+            .with_hostname_provider(Arc::new(MockHostnameProvider::new("dev-box")));
+
+        let explanation = detector.explain_miss(Path::new("/tmp/random")).unwrap();
+        assert!(explanation.starts_with("No profile detected because:"));
+        assert!(explanation.contains("Directory '/tmp/random' matched no match_patterns."));
+        assert!(explanation.contains(
+            "Profile 'work' had match_patterns '*/work/*' but the current directory is '/tmp/random'"
+        ));
+        assert!(explanation.contains("Profile 'hostname-test' had host_patterns 'test-*'"));
+        assert!(!explanation.ends_with("because: "));
+    }
+
+    #[test]
+    fn test_explain_miss_reports_envrc() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(test_profiles()));
+        let git = Arc::new(MockGitWrapper::new());
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
+
+        let explanation = detector.explain_miss(&project).unwrap();
+        assert!(explanation.contains("No .envrc found."));
+
+        // An .envrc in a parent directory is found too
+        std::fs::write(dir.path().join(".envrc"), "export GIT_PROFILE=work\n").unwrap();
+        let explanation = detector.explain_miss(&project).unwrap();
+        assert!(!explanation.contains("No .envrc found."));
+    }
+
+    #[test]
+    fn test_explain_miss_below_threshold() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(test_profiles()));
+        let git = Arc::new(MockGitWrapper::new());
+        let config = DetectionConfig {
+            min_confidence: 0.9,
+            ..Default::default()
+        };
+        let detector = AutoDetector::with_config(profile_manager, git, config);
+
+        let explanation = detector.explain_miss(Path::new("/home/user/work/project")).unwrap();
+        assert!(explanation.contains("Profile 'work' scored 80% from directory_path, below the 90% threshold"));
+        // The directory rule matched a profile, so it is not a reason for the miss
+        assert!(!explanation.contains("matched no match_patterns"));
+    }
+
+    #[test]
+    fn test_explain_miss_when_detected() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(test_profiles()));
+        let git = Arc::new(MockGitWrapper::new());
+        let detector = AutoDetector::with_config(profile_manager, git, DetectionConfig::default());
+
+        let explanation = detector.explain_miss(Path::new("/home/user/work/project")).unwrap();
+        assert_eq!(explanation, "Profile 'work' was detected with 80% confidence.");
+    }
+
+    #[test]
+    fn test_detect_from_git_notes() {
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(test_profiles()));
+        let git = Arc::new(MockGitWrapper::new().with_commit_note(PROFILE_NOTES_REF, "personal\n"));
+        let detector = AutoDetector::with_config(profile_manager.clone(), git, DetectionConfig::default());
+
+        let result = detector.detect_from_git_notes().unwrap().unwrap();
+        assert_eq!(result.profile.name, "personal");
+        assert_eq!(result.confidence, 0.9);
+        assert_eq!(result.matched_rules[0].rule_name, "git_notes");
+        assert_eq!(detector.detect().unwrap().unwrap().profile.name, "personal");
+
+        // A note naming an unknown profile is ignored
+        let git = Arc::new(MockGitWrapper::new().with_commit_note(PROFILE_NOTES_REF, "deleted"));
+        assert!(AutoDetector::with_config(profile_manager.clone(), git, DetectionConfig::default()).detect_from_git_notes().unwrap().is_none());
+
+        let git = Arc::new(MockGitWrapper::new());
+        assert!(AutoDetector::with_config(profile_manager, git, DetectionConfig::default()).detect_from_git_notes().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_detect_two_phase_setup() {
+        let mut profiles = test_profiles();
+        profiles[0].signing_key = Some("work-key".to_string());
+        let profile_manager = Arc::new(MockProfileManager::with_profiles(profiles));
+
+        let global = HashMap::from([
+            ("user.email".to_string(), "me@personal.com".to_string()),
+            ("user.name".to_string(), "Personal User".to_string()),
+        ]);
+        let local = HashMap::from([("user.signingkey".to_string(), "work-key".to_string())]);
+        let git = Arc::new(
+            MockGitWrapper::new()
+                .with_scoped_config(GitConfigScope::Global, global.clone())
+                .with_scoped_config(GitConfigScope::Local, local),
+        );
+
+        let setup = AutoDetector::with_config(profile_manager.clone(), git, DetectionConfig::default())
+            .detect_two_phase()
+            .unwrap()
+            .unwrap();
+        assert_eq!(setup.global_profile, "personal");
+        assert_eq!(setup.local_profile, "work");
+        assert_eq!(setup.local_keys, vec!["user.signingkey"]);
+        assert!(setup.involves("work") && !setup.involves("hostname-test"));
+
+        // Without local overrides there is nothing two-phase about the setup
+        let git = Arc::new(MockGitWrapper::new().with_scoped_config(GitConfigScope::Global, global));
+        assert!(AutoDetector::with_config(profile_manager, git, DetectionConfig::default()).detect_two_phase().unwrap().is_none());
+    }
 }
@@ -0,0 +1,157 @@
+//! Custom context extractors for auto-detection.
+//!
+//! Custom extractors add organization-specific values to
+//! `RepositoryContext::custom_fields`, which profiles can match with
+//! `custom.<field>=<pattern>` entries in their match patterns. Built-in
+//! extractors are enabled by name through `DetectionConfig::custom_extractors`.
+
+use super::context::RepositoryContext;
+use crate::error::Result;
+use std::path::Path;
+
+/// Extractor that adds fields to the repository context.
+pub trait CustomExtractor: Send + Sync {
+    /// Add this extractor's fields to `context.custom_fields`.
+    fn extract(&self, context: &mut RepositoryContext) -> Result<()>;
+
+    /// Name the extractor is enabled by in configuration.
+    fn name(&self) -> &'static str;
+}
+
+/// Create the built-in extractor called `name`, if there is one.
+pub fn custom_extractor(name: &str) -> Option<Box<dyn CustomExtractor>> {
+    match name {
+        "jira_project" => Some(Box::new(JiraProjectExtractor::new())),
+        "team_slack_channel" => Some(Box::new(TeamSlackChannelExtractor::new())),
+        _ => None,
+    }
+}
+
+/// Trimmed contents of the first non-empty `file_name` in the working directory or repository root.
+fn read_marker_file(context: &RepositoryContext, file_name: &str) -> Option<String> {
+    let dirs = std::iter::once(context.working_dir.as_path()).chain(context.repo_root.as_deref());
+    dirs.map(|dir: &Path| dir.join(file_name))
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| contents.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+/// Sets `jira_project` from the `JIRA_PROJECT` environment variable or a `JIRA_PROJECT` file.
+pub struct JiraProjectExtractor {
+    env_var: String,
+}
+
+impl JiraProjectExtractor {
+    pub fn new() -> Self {
+        Self {
+            env_var: "JIRA_PROJECT".to_string(),
+        }
+    }
+
+    /// Read the project from `env_var` instead of `JIRA_PROJECT`.
+    pub fn with_env_var(mut self, env_var: &str) -> Self {
+        self.env_var = env_var.to_string();
+        self
+    }
+}
+
+impl Default for JiraProjectExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CustomExtractor for JiraProjectExtractor {
+    fn extract(&self, context: &mut RepositoryContext) -> Result<()> {
+        let project = std::env::var(&self.env_var)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| read_marker_file(context, "JIRA_PROJECT"));
+
+        if let Some(project) = project {
+            context.custom_fields.insert("jira_project".to_string(), project);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "jira_project"
+    }
+}
+
+/// Sets `slack_channel` from a `.slack_channel` file.
+pub struct TeamSlackChannelExtractor;
+
+impl TeamSlackChannelExtractor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TeamSlackChannelExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CustomExtractor for TeamSlackChannelExtractor {
+    fn extract(&self, context: &mut RepositoryContext) -> Result<()> {
+        if let Some(channel) = read_marker_file(context, ".slack_channel") {
+            context.custom_fields.insert("slack_channel".to_string(), channel);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "team_slack_channel"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn context_in(dir: PathBuf, repo_root: Option<PathBuf>) -> RepositoryContext {
+        RepositoryContext {
+            working_dir: dir,
+            repo_root,
+            remotes: vec![],
+            current_email: None,
+            current_name: None,
+            hostname: "test-host".to_string(),
+            parent_dirs: vec![],
+            custom_fields: HashMap::new(),
+            profile_hints: vec![],
+            remote_hostname: None,
+        }
+    }
+
+    #[test]
+    fn test_marker_files() {
+        let repo = TempDir::new().unwrap();
+        let sub_dir = repo.path().join("src");
+        std::fs::create_dir(&sub_dir).unwrap();
+        std::fs::write(repo.path().join("JIRA_PROJECT"), "PLAT\n").unwrap();
+        std::fs::write(sub_dir.join(".slack_channel"), "#platform-team").unwrap();
+
+        let mut context = context_in(sub_dir, Some(repo.path().to_path_buf()));
+        JiraProjectExtractor::new()
+            .with_env_var("GIT_SETUP_TEST_UNSET_JIRA_PROJECT")
+            .extract(&mut context)
+            .unwrap();
+        TeamSlackChannelExtractor::new().extract(&mut context).unwrap();
+
+        assert_eq!(context.custom_fields.get("jira_project"), Some(&"PLAT".to_string()));
+        assert_eq!(context.custom_fields.get("slack_channel"), Some(&"#platform-team".to_string()));
+    }
+
+    #[test]
+    fn test_custom_extractor_by_name() {
+        assert_eq!(custom_extractor("jira_project").unwrap().name(), "jira_project");
+        assert_eq!(custom_extractor("team_slack_channel").unwrap().name(), "team_slack_channel");
+        assert!(custom_extractor("unknown").is_none());
+    }
+}
@@ -6,14 +6,28 @@
 
 pub mod context;
 pub mod detector;
+pub mod extractors;
 pub mod rules;
 
-pub use context::{ContextExtractor, RemoteInfo, RepositoryContext};
-pub use detector::AutoDetector;
+pub use context::{
+    remote_hostname, remote_path, ContextExtractor, HostnameProvider, MockHostnameProvider, RemoteInfo,
+    RepositoryContext, SystemHostname, PROFILE_HINTS_KEY,
+};
+pub use detector::{AutoDetector, IDENTITY_CONFIG_KEYS, PROFILE_NOTES_REF};
+pub use extractors::{custom_extractor, CustomExtractor, JiraProjectExtractor, TeamSlackChannelExtractor};
 pub use rules::{DetectionRule, RulePriority};
 
-use crate::{config::types::Profile, error::Result};
-use std::path::Path;
+use crate::{
+    config::types::Profile,
+    error::Result,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the saved detection settings in the config directory
+pub const DETECTION_CONFIG_FILE: &str = "detection.toml";
 
 /// Result of auto-detection
 #[derive(Debug, Clone)]
@@ -30,6 +44,24 @@ pub struct DetectionResult {
     pub reasons: Vec<String>,
 }
 
+/// A profile applied globally whose fields are partly overridden locally by another profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoPhaseSetup {
+    /// Profile applied with global scope
+    pub global_profile: String,
+    /// Profile whose fields override the global profile in the repository
+    pub local_profile: String,
+    /// Identity config keys set in the local config
+    pub local_keys: Vec<String>,
+}
+
+impl TwoPhaseSetup {
+    /// Check if the named profile is part of this setup
+    pub fn involves(&self, profile_name: &str) -> bool {
+        self.global_profile == profile_name || self.local_profile == profile_name
+    }
+}
+
 /// Information about a matched rule
 #[derive(Debug, Clone)]
 pub struct MatchedRule {
@@ -51,10 +83,21 @@ pub trait ProfileDetector: Send + Sync {
 
     /// Detect profile for a given repository context
     fn detect_profile(&self, context: &RepositoryContext) -> Result<DetectionResult>;
+
+    /// Explain why no profile is detected in `path`, listing the closest near-misses
+    fn explain_miss(&self, path: &Path) -> Result<String>;
+
+    /// Detect a profile applied globally whose fields another profile overrides locally
+    fn detect_two_phase(&self) -> Result<Option<TwoPhaseSetup>> {
+        Ok(None)
+    }
 }
 
 /// Configuration for auto-detection
-#[derive(Debug, Clone)]
+///
+/// Settings missing from a saved file keep their default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DetectionConfig {
     /// Minimum confidence threshold (0.0 - 1.0)
     pub min_confidence: f64,
@@ -74,6 +117,16 @@ pub struct DetectionConfig {
     /// Enable existing git config matching
     pub check_git_config: bool,
 
+    /// Enable matching the profile named in the note of `HEAD` under `refs/notes/git-setup`
+    pub check_git_notes: bool,
+
+    /// Enable matching the profiles listed in the repository's `git-setup.profile-hints` config
+    pub check_profile_hints: bool,
+
+    /// Names of the built-in custom extractors to run, e.g. `jira_project`.
+    /// Profiles match their fields with `custom.<field>=<pattern>` match patterns.
+    pub custom_extractors: Vec<String>,
+
     /// Cache detection results
     pub enable_cache: bool,
 }
@@ -87,15 +140,49 @@ impl Default for DetectionConfig {
             check_include_if: true,
             check_hostname: true,
             check_git_config: true,
+            check_git_notes: true,
+            check_profile_hints: true,
+            custom_extractors: Vec::new(),
             enable_cache: true,
         }
     }
 }
 
+impl DetectionConfig {
+    /// Path of the saved settings in the platform config directory
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(SystemPlatform.config_dir()?.join(DETECTION_CONFIG_FILE))
+    }
+
+    /// Load the settings saved at `path`, or the defaults if there are none
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save the settings to `path`, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write atomically using a temporary file
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+}
+
 /// Mock implementation of ProfileDetector for testing
 #[cfg(test)]
 pub struct MockProfileDetector {
     detection_result: Option<DetectionResult>,
+    two_phase: Option<TwoPhaseSetup>,
 }
 
 #[cfg(test)]
@@ -103,12 +190,17 @@ impl MockProfileDetector {
     pub fn new() -> Self {
         Self {
             detection_result: None,
+            two_phase: None,
         }
     }
 
     pub fn set_detection_result(&mut self, result: DetectionResult) {
         self.detection_result = Some(result);
     }
+
+    pub fn set_two_phase_setup(&mut self, setup: TwoPhaseSetup) {
+        self.two_phase = Some(setup);
+    }
 }
 
 #[cfg(test)]
@@ -134,6 +226,17 @@ impl ProfileDetector for MockProfileDetector {
             crate::error::GitSetupError::Git("No detection result set".to_string())
         })
     }
+
+    fn explain_miss(&self, _path: &Path) -> Result<String> {
+        Ok(match &self.detection_result {
+            Some(result) => format!("Profile '{}' was detected.", result.profile.name),
+            None => "No profile detected because: no detection result set.".to_string(),
+        })
+    }
+
+    fn detect_two_phase(&self) -> Result<Option<TwoPhaseSetup>> {
+        Ok(self.two_phase.clone())
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +255,28 @@ mod tests {
         assert!(config.enable_cache);
     }
 
+    #[test]
+    fn test_detection_config_save_and_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nested").join(DETECTION_CONFIG_FILE);
+        assert_eq!(DetectionConfig::load(&path).unwrap(), DetectionConfig::default());
+
+        let config = DetectionConfig {
+            min_confidence: 0.75,
+            check_hostname: false,
+            custom_extractors: vec!["jira_project".to_string()],
+            ..Default::default()
+        };
+        config.save(&path).unwrap();
+        assert_eq!(DetectionConfig::load(&path).unwrap(), config);
+
+        // Settings missing from the file keep their default
+        std::fs::write(&path, "check_git_notes = false\n").unwrap();
+        let loaded = DetectionConfig::load(&path).unwrap();
+        assert!(!loaded.check_git_notes);
+        assert_eq!(loaded.min_confidence, 0.6);
+    }
+
     #[test]
     fn test_detection_result_debug() {
         use crate::config::types::{KeyType, Scope};
@@ -173,6 +298,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = DetectionResult {
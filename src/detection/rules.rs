@@ -3,8 +3,9 @@
 //! This module implements various rules for detecting which profile should be
 //! used based on repository context, directory patterns, hostname, and other factors.
 
-use super::context::RepositoryContext;
+use super::context::{remote_hostname, remote_path, RepositoryContext};
 use crate::config::types::Profile;
+use glob::MatchOptions;
 use regex::Regex;
 use std::path::Path;
 
@@ -33,7 +34,20 @@ pub trait DetectionRule: Send + Sync {
     fn matches(&self, profile: &Profile, context: &RepositoryContext) -> Option<f64>;
 }
 
+/// Score of a `host/org/repo` repo pattern naming the remote's full path
+const FULL_PATH_SCORE: f64 = 0.95;
+
+/// Score of a `host/org` repo pattern naming a prefix of the remote's path
+const ORG_SCORE: f64 = 0.85;
+
+/// Score of a bare `host` repo pattern
+const HOST_SCORE: f64 = 0.75;
+
 /// Rule: Match by remote URL
+///
+/// Repo patterns are whole URLs (optionally with `*`/`?` wildcards), `host`,
+/// `host/org` or `host/org/repo` locations, or substrings of the URL. More
+/// specific locations score higher, whatever the remote's URL syntax.
 pub struct RemoteUrlRule {
     name: String,
 }
@@ -45,12 +59,50 @@ impl RemoteUrlRule {
         }
     }
 
+    /// Score a `host[/org[/repo]]` pattern against the host and path of `remote_url`.
+    ///
+    /// Returns `None` if the pattern is not a location or names another host,
+    /// so it can still match as a glob or substring.
+    fn score_location_match(&self, profile_pattern: &str, remote_url: &str) -> Option<f64> {
+        if profile_pattern.contains([':', '@', '*', '?']) {
+            return None;
+        }
+        let host = remote_hostname(remote_url)?;
+        let path = remote_path(remote_url)?;
+
+        let mut pattern = profile_pattern.trim_matches('/').split('/');
+        if !pattern.next().is_some_and(|pattern_host| pattern_host.eq_ignore_ascii_case(&host)) {
+            return None;
+        }
+        let pattern_segments: Vec<&str> = pattern.map(|segment| segment.trim_end_matches(".git")).collect();
+        let path_segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        // On the right host, the segments decide; `github.com/acme` must not match `acme-corp` as a substring
+        if pattern_segments.len() > path_segments.len()
+            || !pattern_segments
+                .iter()
+                .zip(&path_segments)
+                .all(|(pattern, segment)| pattern.eq_ignore_ascii_case(segment))
+        {
+            return Some(0.0);
+        }
+        Some(match pattern_segments.len() {
+            0 => HOST_SCORE,
+            n if n == path_segments.len() => FULL_PATH_SCORE,
+            _ => ORG_SCORE,
+        })
+    }
+
     fn score_url_match(&self, profile_pattern: &str, remote_url: &str) -> f64 {
         // Exact match gets highest score
         if profile_pattern == remote_url {
             return 1.0;
         }
 
+        if let Some(location_score) = self.score_location_match(profile_pattern, remote_url) {
+            return location_score;
+        }
+
         // Convert pattern to regex
         let pattern = profile_pattern
             .replace("*", ".*")
@@ -178,7 +230,11 @@ impl DetectionRule for DirectoryPathRule {
         }
 
         // Check working directory against patterns
-        for pattern in &profile.match_patterns {
+        let path_patterns = profile
+            .match_patterns
+            .iter()
+            .filter(|pattern| !pattern.starts_with(CUSTOM_FIELD_PREFIX));
+        for pattern in path_patterns {
             if self.matches_pattern(pattern, &context.working_dir) {
                 return Some(0.8);
             }
@@ -282,25 +338,88 @@ impl DetectionRule for HostnameRule {
             return None;
         }
 
+        // Hostnames are case-insensitive
+        let options = MatchOptions {
+            case_sensitive: false,
+            ..MatchOptions::new()
+        };
         for pattern in &profile.host_patterns {
             // Exact match
-            if pattern == &context.hostname {
+            if pattern.eq_ignore_ascii_case(&context.hostname) {
                 return Some(0.9);
             }
 
-            // Pattern match
+            // Glob match, e.g. `build-*` or `laptop-??.corp.example.com`
+            if glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches_with(&context.hostname, options)) {
+                return Some(0.7);
+            }
+        }
+
+        None
+    }
+}
+
+/// Prefix of match patterns that refer to custom context fields
+pub const CUSTOM_FIELD_PREFIX: &str = "custom.";
+
+/// Rule: Match custom context fields with `custom.<field>=<pattern>` match patterns
+pub struct CustomFieldRule {
+    name: String,
+}
+
+impl CustomFieldRule {
+    pub fn new() -> Self {
+        Self {
+            name: "custom_field".to_string(),
+        }
+    }
+
+    /// Split `custom.<field>=<pattern>` into the field name and pattern
+    fn parse(pattern: &str) -> Option<(&str, &str)> {
+        pattern.strip_prefix(CUSTOM_FIELD_PREFIX)?.split_once('=')
+    }
+}
+
+impl Default for CustomFieldRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DetectionRule for CustomFieldRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> RulePriority {
+        RulePriority::High
+    }
+
+    fn matches(&self, profile: &Profile, context: &RepositoryContext) -> Option<f64> {
+        let mut best_score: f64 = 0.0;
+
+        for (field, pattern) in profile.match_patterns.iter().filter_map(|p| Self::parse(p)) {
+            let Some(value) = context.custom_fields.get(field) else {
+                continue;
+            };
+
+            if value == pattern {
+                return Some(0.95);
+            }
+
             let regex_pattern = pattern
                 .replace("*", ".*")
                 .replace("?", ".");
-
-            if let Ok(regex) = Regex::new(&format!("^{}$", regex_pattern)) {
-                if regex.is_match(&context.hostname) {
-                    return Some(0.7);
-                }
+            if Regex::new(&format!("^{}$", regex_pattern)).is_ok_and(|regex| regex.is_match(value)) {
+                best_score = best_score.max(0.8);
             }
         }
 
-        None
+        if best_score > 0.0 {
+            Some(best_score)
+        } else {
+            None
+        }
     }
 }
 
@@ -359,10 +478,52 @@ impl DetectionRule for GitConfigRule {
     }
 }
 
+/// Rule: Match profiles suggested by the repository's `git-setup.profile-hints` config
+///
+/// Teams can commit the hints in a `.gitconfig` at the repository root and
+/// include it with `git config include.path ../.gitconfig`, e.g.
+/// `git-setup.profile-hints = "work,acme"` in a monorepo shared by two teams.
+pub struct CommittedHintRule {
+    name: String,
+}
+
+impl CommittedHintRule {
+    pub fn new() -> Self {
+        Self {
+            name: "committed_hint".to_string(),
+        }
+    }
+}
+
+impl Default for CommittedHintRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DetectionRule for CommittedHintRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> RulePriority {
+        RulePriority::Medium
+    }
+
+    fn matches(&self, profile: &Profile, context: &RepositoryContext) -> Option<f64> {
+        context
+            .profile_hints
+            .iter()
+            .any(|hint| hint == &profile.name)
+            .then_some(0.75)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::types::{KeyType, Scope};
+    use std::collections::HashMap;
     use std::path::PathBuf;
 
     fn test_profile() -> Profile {
@@ -383,6 +544,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -404,6 +574,9 @@ mod tests {
                 PathBuf::from("/home/user/projects"),
                 PathBuf::from("/home/user"),
             ],
+            custom_fields: HashMap::from([("jira_project".to_string(), "PLAT".to_string())]),
+            profile_hints: vec![],
+            remote_hostname: None,
         }
     }
 
@@ -458,6 +631,92 @@ mod tests {
         assert_eq!(rule.matches(&profile, &context), None);
     }
 
+    #[test]
+    fn test_remote_url_rule_location_specificity() {
+        let rule = RemoteUrlRule::new();
+        let context = test_context();
+        let score = |pattern: &str| {
+            let mut profile = test_profile();
+            profile.repos = vec![pattern.to_string()];
+            rule.matches(&profile, &context)
+        };
+
+        assert_eq!(score("github.com/company/project"), Some(FULL_PATH_SCORE));
+        assert_eq!(score("GitHub.com/Company/project.git"), Some(FULL_PATH_SCORE));
+        assert_eq!(score("github.com/company"), Some(ORG_SCORE));
+        assert_eq!(score("github.com"), Some(HOST_SCORE));
+        assert_eq!(score("github.com/other"), None);
+        assert_eq!(score("gitlab.com/company"), None);
+    }
+
+    #[test]
+    fn test_remote_url_rule_location_is_not_a_substring() {
+        use super::super::context::RemoteInfo;
+
+        let rule = RemoteUrlRule::new();
+        let context = RepositoryContext {
+            remotes: vec![RemoteInfo {
+                name: "origin".to_string(),
+                url: "https://github.com/acme-corp/website.git".to_string(),
+                push_url: None,
+            }],
+            ..test_context()
+        };
+        let mut profile = test_profile();
+
+        // `github.com/acme` is contained in the URL, but names another org
+        profile.repos = vec!["github.com/acme".to_string()];
+        assert_eq!(rule.matches(&profile, &context), None);
+        profile.repos = vec!["github.com/acme-corp".to_string()];
+        assert_eq!(rule.matches(&profile, &context), Some(ORG_SCORE));
+        // Patterns that are not locations still match as substrings
+        profile.repos = vec!["acme".to_string()];
+        assert_eq!(rule.matches(&profile, &context), Some(0.7));
+    }
+
+    #[test]
+    fn test_remote_url_rule_selects_most_specific_profile() {
+        use super::super::context::RemoteInfo;
+
+        let rule = RemoteUrlRule::new();
+        let profile = |name: &str, repos: &[&str]| Profile {
+            name: name.to_string(),
+            repos: repos.iter().map(|repo| repo.to_string()).collect(),
+            ..test_profile()
+        };
+        let profiles = [
+            profile("personal", &["github.com"]),
+            profile("work", &["github.com/acme", "gitlab.com/acme-corp"]),
+            profile("platform", &["gitlab.com/acme-corp/platform/api"]),
+            profile("homelab", &["gitea.home.example.com"]),
+        ];
+        let best = |url: &str| {
+            let context = RepositoryContext {
+                remotes: vec![RemoteInfo {
+                    name: "origin".to_string(),
+                    url: url.to_string(),
+                    push_url: None,
+                }],
+                ..test_context()
+            };
+            profiles
+                .iter()
+                .filter_map(|profile| Some((rule.matches(profile, &context)?, profile.name.as_str())))
+                .max_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, name)| name)
+        };
+
+        // GitHub: org beats host
+        assert_eq!(best("git@github.com:acme/website.git"), Some("work"));
+        assert_eq!(best("https://github.com/jane/dotfiles.git"), Some("personal"));
+        // GitLab subgroups: full path beats org
+        assert_eq!(best("https://gitlab.com/acme-corp/platform/api.git"), Some("platform"));
+        assert_eq!(best("git@gitlab.com:acme-corp/platform/web.git"), Some("work"));
+        // Self-hosted Gitea on a custom SSH port
+        assert_eq!(best("ssh://git@gitea.home.example.com:2222/jane/nas.git"), Some("homelab"));
+        assert_eq!(best("https://bitbucket.org/acme/website.git"), None);
+    }
+
     #[test]
     fn test_remote_url_rule_empty_repos() {
         let rule = RemoteUrlRule::new();
@@ -546,6 +805,22 @@ mod tests {
         assert_eq!(rule.matches(&profile, &context), Some(0.7));
     }
 
+    #[test]
+    fn test_hostname_rule_glob_match() {
+        let rule = HostnameRule::new();
+        let mut context = test_context();
+        context.hostname = "Laptop-07.corp.example.com".to_string();
+
+        let mut profile = test_profile();
+        profile.host_patterns = vec!["laptop-??.corp.example.com".to_string()];
+        assert_eq!(rule.matches(&profile, &context), Some(0.7));
+
+        // `.` is literal, unlike in a regex
+        context.hostname = "laptop-07xcorp.example.com".to_string();
+        profile.host_patterns = vec!["laptop-07.corp.*".to_string()];
+        assert_eq!(rule.matches(&profile, &context), None);
+    }
+
     #[test]
     fn test_hostname_rule_no_match() {
         let rule = HostnameRule::new();
@@ -556,6 +831,38 @@ mod tests {
         assert_eq!(rule.matches(&profile, &context), None);
     }
 
+    #[test]
+    fn test_custom_field_rule() {
+        let rule = CustomFieldRule::new();
+        let context = test_context();
+
+        let mut profile = test_profile();
+        profile.match_patterns = vec!["custom.jira_project=PLAT".to_string()];
+        assert_eq!(rule.matches(&profile, &context), Some(0.95));
+
+        profile.match_patterns = vec!["custom.jira_project=PL*".to_string()];
+        assert_eq!(rule.matches(&profile, &context), Some(0.8));
+
+        profile.match_patterns = vec!["custom.slack_channel=#plat".to_string(), "work".to_string()];
+        assert_eq!(rule.matches(&profile, &context), None);
+
+        // Custom field patterns are not treated as directory patterns
+        profile.match_patterns = vec!["custom.jira_project=*".to_string()];
+        assert_eq!(DirectoryPathRule::new().matches(&profile, &context), None);
+    }
+
+    #[test]
+    fn test_committed_hint_rule() {
+        let rule = CommittedHintRule::new();
+        let mut context = test_context();
+        let profile = test_profile();
+        assert_eq!(rule.matches(&profile, &context), None);
+
+        context.profile_hints = vec!["acme".to_string(), "test".to_string()];
+        assert_eq!(rule.matches(&profile, &context), Some(0.75));
+        assert_eq!(rule.name(), "committed_hint");
+    }
+
     #[test]
     fn test_git_config_rule_email_match() {
         let rule = GitConfigRule::new();
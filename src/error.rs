@@ -39,6 +39,10 @@ pub enum GitSetupError {
     #[error("1Password error: {0}")]
     OnePassword(String),
 
+    /// Bitwarden CLI operation failed.
+    #[error("Bitwarden error: {0}")]
+    Bitwarden(String),
+
     /// Git operation failed.
     #[error("Git error: {0}")]
     Git(String),
@@ -67,6 +71,20 @@ pub enum GitSetupError {
     /// CSV parsing failed.
     #[error(transparent)]
     Csv(#[from] csv::Error),
+
+    /// An error annotated with the operation that was being performed.
+    #[error(transparent)]
+    Context(Box<ErrorContext>),
+}
+
+impl GitSetupError {
+    /// The underlying error, with any operation context removed.
+    pub fn root_cause(&self) -> &GitSetupError {
+        match self {
+            GitSetupError::Context(context) => context.root_cause(),
+            error => error,
+        }
+    }
 }
 
 /// A type alias for `Result<T, GitSetupError>`.
@@ -75,6 +93,85 @@ pub enum GitSetupError {
 /// git-setup-rs specific errors.
 pub type Result<T> = std::result::Result<T, GitSetupError>;
 
+/// An error together with a description of the operation it interrupted.
+///
+/// Contexts nest: wrapping an error that already has a context keeps the
+/// inner one, so the full chain can be shown to the user.
+#[derive(Debug)]
+pub struct ErrorContext {
+    context: String,
+    error: GitSetupError,
+}
+
+impl ErrorContext {
+    /// Wrap `error` with a description of the operation, e.g. "while applying profile 'work'".
+    pub fn new(error: GitSetupError, context: impl Into<String>) -> Self {
+        Self {
+            context: context.into(),
+            error,
+        }
+    }
+
+    /// The outermost context.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// The wrapped error.
+    pub fn error(&self) -> &GitSetupError {
+        &self.error
+    }
+
+    /// All nested contexts, outermost first.
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = vec![self.context.clone()];
+        let mut error = &self.error;
+        while let GitSetupError::Context(inner) = error {
+            chain.push(inner.context.clone());
+            error = &inner.error;
+        }
+        chain
+    }
+
+    /// The underlying error without any context.
+    pub fn root_cause(&self) -> &GitSetupError {
+        self.error.root_cause()
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for context in self.chain() {
+            write!(f, "{}: ", context)?;
+        }
+        write!(f, "{}", self.root_cause())
+    }
+}
+
+impl std::error::Error for ErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<ErrorContext> for GitSetupError {
+    fn from(context: ErrorContext) -> Self {
+        GitSetupError::Context(Box::new(context))
+    }
+}
+
+/// Extension trait for attaching operation context to errors.
+pub trait ResultExt<T> {
+    /// Wrap the error, if any, with `ctx`.
+    fn with_context<S: Into<String>>(self, ctx: S) -> std::result::Result<T, ErrorContext>;
+}
+
+impl<T, E: Into<GitSetupError>> ResultExt<T> for std::result::Result<T, E> {
+    fn with_context<S: Into<String>>(self, ctx: S) -> std::result::Result<T, ErrorContext> {
+        self.map_err(|error| ErrorContext::new(error.into(), ctx))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,4 +342,40 @@ mod tests {
         let error_string = git_err.to_string();
         assert!(error_string.contains("config.toml"));
     }
+
+    #[test]
+    fn test_error_context_chain() {
+        let result: Result<()> = Err(GitSetupError::ExternalCommand {
+            command: "gpg --import".to_string(),
+            error: "bad passphrase".to_string(),
+        });
+        let error = result
+            .with_context("while unlocking the key")
+            .with_context("while applying profile 'work'")
+            .unwrap_err();
+
+        assert_eq!(error.context(), "while applying profile 'work'");
+        assert_eq!(
+            error.chain(),
+            vec!["while applying profile 'work'", "while unlocking the key"]
+        );
+        assert!(matches!(error.root_cause(), GitSetupError::ExternalCommand { .. }));
+        assert_eq!(
+            error.to_string(),
+            "while applying profile 'work': while unlocking the key: \
+             External command 'gpg --import' failed: bad passphrase"
+        );
+    }
+
+    #[test]
+    fn test_error_context_converts_to_git_setup_error() {
+        let result: std::result::Result<(), io::Error> =
+            Err(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        let error: GitSetupError = result.with_context("while reading config").unwrap_err().into();
+
+        assert!(matches!(error, GitSetupError::Context(_)));
+        assert!(matches!(error.root_cause(), GitSetupError::Io(_)));
+        assert_eq!(error.to_string(), "while reading config: missing");
+        assert!(std::error::Error::source(&error).is_some());
+    }
 }
@@ -6,11 +6,27 @@
 
 use crate::config::types::{KeyType, Profile, Scope};
 use crate::error::{GitSetupError, Result};
+use crate::platform::{PlatformPaths, SystemPlatform};
 use std::collections::HashMap;
-use std::process::Command;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Config keys shared with `push_config_to_remote` unless others are given.
+pub const DEFAULT_SHARED_CONFIG_KEYS: &[&str] = &["core.*", "alias.*"];
+
+/// Notes ref holding the shared config on the remote.
+const CONFIG_NOTES_REF: &str = "refs/notes/gitconfig";
+
+/// Directory, under the data directory, holding the allowed signers file of each profile.
+pub(crate) const ALLOWED_SIGNERS_DIR: &str = "allowed_signers";
+
+/// Sections and key fragments that may hold identities, credentials or signing keys.
+const SENSITIVE_CONFIG_SECTIONS: &[&str] = &["user.", "credential.", "gpg.", "sendemail."];
+const SENSITIVE_CONFIG_FRAGMENTS: &[&str] = &["signingkey", "token", "password", "secret", "extraheader"];
 
 /// Git configuration scope for operations.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GitConfigScope {
     /// Local repository configuration
     Local,
@@ -22,7 +38,7 @@ pub enum GitConfigScope {
 
 impl GitConfigScope {
     /// Convert scope to git command line argument
-    fn to_git_arg(&self) -> &'static str {
+    pub(super) fn to_git_arg(&self) -> &'static str {
         match self {
             GitConfigScope::Local => "--local",
             GitConfigScope::Global => "--global",
@@ -41,10 +57,146 @@ impl From<Scope> for GitConfigScope {
     }
 }
 
+/// Commit signing configuration as read back from git config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigningInfo {
+    /// `gpg.format` (`openpgp`, `ssh` or `x509`)
+    pub format: Option<String>,
+    /// `user.signingkey`
+    pub signing_key: Option<String>,
+    /// Whether `commit.gpgsign` is enabled
+    pub commit_gpgsign: bool,
+    /// `gpg.ssh.allowedSignersFile`
+    pub allowed_signers_file: Option<String>,
+    /// `gpg.x509.program`
+    pub x509_program: Option<String>,
+}
+
+/// Interpret a git boolean config value; anything unrecognized is false.
+fn parse_git_bool(value: &str) -> bool {
+    matches!(value.trim().to_lowercase().as_str(), "true" | "yes" | "on" | "1")
+}
+
+/// Where a git configuration value was set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigOrigin {
+    /// Configuration file the value was read from
+    pub file: PathBuf,
+    /// 1-based line of the value in `file`, or 0 if it could not be located
+    pub line: u32,
+}
+
+impl ConfigOrigin {
+    /// Create an origin for a value set at `line` of `file`.
+    pub fn new(file: impl Into<PathBuf>, line: u32) -> Self {
+        Self {
+            file: file.into(),
+            line,
+        }
+    }
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.file.display())
+        } else {
+            write!(f, "{} line {}", self.file.display(), self.line)
+        }
+    }
+}
+
+/// Split a `--show-origin` line into its origin and the rest of the line.
+///
+/// Git prints the origin as `file:<path>`, quoting paths with unusual characters.
+pub(super) fn split_origin(line: &str) -> Option<(PathBuf, &str)> {
+    let (origin, rest) = line.split_once('\t')?;
+    let origin = origin.strip_prefix("file:").unwrap_or(origin);
+    let origin = origin
+        .strip_prefix('"')
+        .and_then(|o| o.strip_suffix('"'))
+        .unwrap_or(origin);
+    Some((PathBuf::from(origin), rest))
+}
+
+/// Find the last line of `contents` that sets `key`, as git uses the last value.
+fn find_key_line(contents: &str, key: &str) -> Option<u32> {
+    let (section, name) = key.rsplit_once('.')?;
+    let mut current_section = String::new();
+    let mut found = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header.split(']').next().unwrap_or_default().trim();
+            // `[section "sub"]` and the older `[section.sub]` both name `section.sub`
+            current_section = match header.split_once(char::is_whitespace) {
+                Some((base, sub)) => format!("{}.{}", base, sub.trim().trim_matches('"')),
+                None => header.to_string(),
+            };
+            continue;
+        }
+
+        let entry = line.split(['=', ';', '#']).next().unwrap_or_default().trim();
+        if !entry.is_empty()
+            && entry.eq_ignore_ascii_case(name)
+            && current_section.eq_ignore_ascii_case(section)
+        {
+            found = Some(index as u32 + 1);
+        }
+    }
+
+    found
+}
+
+/// Line of `key` in `file`, or 0 if the file cannot be read or the key is not found.
+pub(super) fn origin_line(file: &Path, key: &str, cache: &mut HashMap<PathBuf, String>) -> u32 {
+    if !cache.contains_key(file) {
+        let contents = std::fs::read_to_string(file).unwrap_or_default();
+        cache.insert(file.to_path_buf(), contents);
+    }
+    find_key_line(&cache[file], key).unwrap_or(0)
+}
+
+/// Check if a config key may hold identities, credentials or signing keys.
+fn is_sensitive_config_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_CONFIG_SECTIONS.iter().any(|section| key.starts_with(section))
+        || SENSITIVE_CONFIG_FRAGMENTS.iter().any(|fragment| key.contains(fragment))
+}
+
+/// Check if `key` matches a `whitelist` pattern (e.g. `core.*`) and is safe to share.
+///
+/// Sensitive keys are never shared, even if whitelisted.
+pub fn is_shareable_config_key(key: &str, whitelist: &[&str]) -> bool {
+    let key = key.to_lowercase();
+    !is_sensitive_config_key(&key)
+        && whitelist.iter().any(|pattern| {
+            glob::Pattern::new(&pattern.to_lowercase()).is_ok_and(|pattern| pattern.matches(&key))
+        })
+}
+
+/// Render shared config as the note stored on the remote: sorted `key=value` lines.
+fn format_config_note(config: &HashMap<String, String>) -> String {
+    let mut lines: Vec<String> = config.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+/// Parse a note written by `format_config_note`, dropping sensitive keys.
+fn parse_config_note(note: &str) -> HashMap<String, String> {
+    note.lines()
+        .filter_map(|line| line.split_once('='))
+        .filter(|(key, _)| !is_sensitive_config_key(key))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
 /// Trait for git configuration operations.
 ///
 /// This trait allows for easy testing by providing a mock implementation
 /// while keeping the real implementation using system git commands.
+/// The signing helpers are implemented on top of `set_config` and `unset_config`.
 pub trait GitWrapper {
     /// Get a git configuration value.
     fn get_config(&self, key: &str, scope: Option<GitConfigScope>) -> Result<Option<String>>;
@@ -58,11 +210,50 @@ pub trait GitWrapper {
     /// Get all configuration values as a HashMap.
     fn get_all_config(&self, scope: Option<GitConfigScope>) -> Result<HashMap<String, String>>;
 
+    /// Get a git configuration value along with the file and line that set it.
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>>;
+
+    /// Get all configuration values along with the file and line that set each of them.
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>>;
+
     /// Check if git is available on the system.
     fn is_git_available(&self) -> Result<bool>;
 
+    /// Check if the current directory is inside a git work tree.
+    fn is_inside_work_tree(&self) -> Result<bool>;
+
     /// Configure signing for a profile based on its key type.
-    fn configure_signing(&self, profile: &Profile, scope: GitConfigScope) -> Result<()>;
+    fn configure_signing(&self, profile: &Profile, scope: GitConfigScope) -> Result<()> {
+        // First clear any existing signing configuration
+        self.clear_signing_config(scope.clone())?;
+
+        match profile.key_type {
+            KeyType::Ssh => {
+                if let Some(signing_key) = &profile.signing_key {
+                    self.configure_ssh_signing(
+                        signing_key,
+                        profile.allowed_signers.as_deref(),
+                        scope,
+                    )
+                } else {
+                    Err(GitSetupError::InvalidProfile {
+                        reason: "SSH key type requires a signing key".to_string(),
+                    })
+                }
+            }
+            KeyType::Gpg => {
+                if let Some(signing_key) = &profile.signing_key {
+                    self.configure_gpg_signing(signing_key, scope)
+                } else {
+                    Err(GitSetupError::InvalidProfile {
+                        reason: "GPG key type requires a signing key".to_string(),
+                    })
+                }
+            }
+            KeyType::Gitsign => self.configure_gitsign(scope),
+            KeyType::X509 => self.configure_x509_signing(scope),
+        }
+    }
 
     /// Configure SSH signing specifically.
     fn configure_ssh_signing(
@@ -70,27 +261,196 @@ pub trait GitWrapper {
         signing_key: &str,
         allowed_signers: Option<&str>,
         scope: GitConfigScope,
-    ) -> Result<()>;
+    ) -> Result<()> {
+        // Set gpg.format to ssh
+        self.set_config("gpg.format", "ssh", scope.clone())?;
+
+        // Set user.signingkey to the SSH public key or path
+        self.set_config("user.signingkey", signing_key, scope.clone())?;
+
+        // Set gpg.ssh.allowedSignersFile if provided
+        if let Some(allowed_signers_path) = allowed_signers {
+            self.set_config(
+                "gpg.ssh.allowedSignersFile",
+                allowed_signers_path,
+                scope.clone(),
+            )?;
+        }
+
+        // Enable commit signing
+        self.set_config("commit.gpgsign", "true", scope)?;
+
+        Ok(())
+    }
 
     /// Configure GPG signing specifically.
-    fn configure_gpg_signing(&self, signing_key: &str, scope: GitConfigScope) -> Result<()>;
+    fn configure_gpg_signing(&self, signing_key: &str, scope: GitConfigScope) -> Result<()> {
+        // Set gpg.format to openpgp (default)
+        self.set_config("gpg.format", "openpgp", scope.clone())?;
+
+        // Set user.signingkey to the GPG key ID
+        self.set_config("user.signingkey", signing_key, scope.clone())?;
+
+        // Enable commit signing
+        self.set_config("commit.gpgsign", "true", scope)?;
+
+        Ok(())
+    }
 
     /// Configure gitsign (keyless) signing.
-    fn configure_gitsign(&self, scope: GitConfigScope) -> Result<()>;
+    fn configure_gitsign(&self, scope: GitConfigScope) -> Result<()> {
+        // Set gpg.format to x509
+        self.set_config("gpg.format", "x509", scope.clone())?;
+
+        // Set gpg.x509.program to gitsign
+        self.set_config("gpg.x509.program", "gitsign", scope.clone())?;
+
+        // Enable commit signing
+        self.set_config("commit.gpgsign", "true", scope)?;
+
+        Ok(())
+    }
 
     /// Configure x509 signing with smimesign.
-    fn configure_x509_signing(&self, scope: GitConfigScope) -> Result<()>;
+    fn configure_x509_signing(&self, scope: GitConfigScope) -> Result<()> {
+        // Set gpg.format to x509
+        self.set_config("gpg.format", "x509", scope.clone())?;
+
+        // Set gpg.x509.program to smimesign
+        self.set_config("gpg.x509.program", "smimesign", scope.clone())?;
+
+        // Enable commit signing
+        self.set_config("commit.gpgsign", "true", scope)?;
+
+        Ok(())
+    }
 
     /// Remove all signing configuration.
-    fn clear_signing_config(&self, scope: GitConfigScope) -> Result<()>;
+    fn clear_signing_config(&self, scope: GitConfigScope) -> Result<()> {
+        // Clear all signing-related configurations
+        let _ = self.unset_config("commit.gpgsign", scope.clone());
+        let _ = self.unset_config("user.signingkey", scope.clone());
+        let _ = self.unset_config("gpg.format", scope.clone());
+        let _ = self.unset_config("gpg.ssh.allowedSignersFile", scope.clone());
+        let _ = self.unset_config("gpg.x509.program", scope);
+
+        Ok(())
+    }
+
+    /// Read the signing configuration of `scope`, or the effective one if `scope` is `None`.
+    fn get_signing_info(&self, scope: Option<GitConfigScope>) -> Result<SigningInfo> {
+        Ok(SigningInfo {
+            format: self.get_config("gpg.format", scope.clone())?,
+            signing_key: self.get_config("user.signingkey", scope.clone())?,
+            commit_gpgsign: self
+                .get_config("commit.gpgsign", scope.clone())?
+                .is_some_and(|value| parse_git_bool(&value)),
+            allowed_signers_file: self.get_config("gpg.ssh.allowedSignersFile", scope.clone())?,
+            x509_program: self.get_config("gpg.x509.program", scope)?,
+        })
+    }
+
+    /// Make a signed test commit in a throwaway repository using the given key.
+    ///
+    /// Returns `Ok(false)` if git ran but the signed commit failed.
+    fn test_sign(&self, key_type: &KeyType, signing_key: &str) -> Result<bool>;
+
+    /// Share the config keys of `scope` matching `keys_whitelist` with a team.
+    ///
+    /// The keys are stored as a note under `refs/notes/gitconfig`, which is then
+    /// pushed to `remote`. Identities, credentials and signing keys are never shared.
+    fn push_config_to_remote(&self, scope: GitConfigScope, remote: &str, keys_whitelist: &[&str]) -> Result<()>;
+
+    /// Fetch the config shared on `remote` by `push_config_to_remote`.
+    fn pull_config_from_remote(&self, remote: &str) -> Result<HashMap<String, String>>;
+
+    /// Get the note attached to `HEAD` under the notes ref `ref_name`, if any.
+    fn get_last_commit_note(&self, ref_name: &str) -> Result<Option<String>>;
+
+    /// Attach `message` as a note to `HEAD` under the notes ref `ref_name`,
+    /// replacing an existing note.
+    fn add_commit_note(&self, message: &str, ref_name: &str) -> Result<()>;
+
+    /// Write `content` as the allowed signers file of the profile `profile_name`,
+    /// replacing an existing one, and return its path.
+    ///
+    /// The path is what `gpg.ssh.allowedSignersFile` should be set to.
+    fn set_allowed_signers(&self, profile_name: &str, content: &str) -> Result<PathBuf>;
+}
+
+/// Write `content` to the allowed signers file of `profile_name` in `dir`, creating `dir`.
+pub(crate) fn write_allowed_signers_file(dir: &Path, profile_name: &str, content: &str) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(profile_name);
+    let mut content = content.to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    std::fs::write(&path, content)?;
+    Ok(path)
 }
 
 /// Real implementation of GitWrapper using std::process::Command.
-pub struct SystemGitWrapper;
+#[derive(Clone)]
+pub struct SystemGitWrapper {
+    /// Directory git runs in, the current directory when unset
+    working_dir: Option<PathBuf>,
+}
 
 impl SystemGitWrapper {
     pub fn new() -> Self {
-        Self
+        Self { working_dir: None }
+    }
+
+    /// Run git in `dir` instead of the current directory.
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            working_dir: Some(dir.into()),
+        }
+    }
+}
+
+impl SystemGitWrapper {
+    /// A git command running in the wrapper's directory.
+    fn git(&self) -> Command {
+        let mut cmd = Command::new("git");
+        if let Some(dir) = &self.working_dir {
+            cmd.current_dir(dir);
+        }
+        cmd
+    }
+
+    /// Run git with `args` and return its trimmed output.
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let output = self.git()
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("git {}", args.join(" ")),
+                error: e.to_string(),
+            })?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(GitSetupError::Git(format!(
+                "'git {}' failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    /// Fetch the shared config notes of `remote` into the local notes ref.
+    fn fetch_config_notes(&self, remote: &str) -> Result<()> {
+        let refspec = format!("+{}:{}", CONFIG_NOTES_REF, CONFIG_NOTES_REF);
+        self.run(&["fetch", "--quiet", remote, &refspec]).map(|_| ())
+    }
+
+    /// Object the shared config note is attached to: the empty tree, which every repository can create.
+    fn config_note_object(&self) -> Result<String> {
+        self.run(&["mktree"])
     }
 }
 
@@ -102,7 +462,7 @@ impl Default for SystemGitWrapper {
 
 impl GitWrapper for SystemGitWrapper {
     fn get_config(&self, key: &str, scope: Option<GitConfigScope>) -> Result<Option<String>> {
-        let mut cmd = Command::new("git");
+        let mut cmd = self.git();
         cmd.arg("config");
 
         if let Some(scope) = scope {
@@ -142,7 +502,7 @@ impl GitWrapper for SystemGitWrapper {
     }
 
     fn set_config(&self, key: &str, value: &str, scope: GitConfigScope) -> Result<()> {
-        let mut cmd = Command::new("git");
+        let mut cmd = self.git();
         cmd.arg("config")
             .arg(scope.to_git_arg())
             .arg(key)
@@ -170,7 +530,7 @@ impl GitWrapper for SystemGitWrapper {
     }
 
     fn unset_config(&self, key: &str, scope: GitConfigScope) -> Result<()> {
-        let mut cmd = Command::new("git");
+        let mut cmd = self.git();
         cmd.arg("config")
             .arg(scope.to_git_arg())
             .arg("--unset")
@@ -203,7 +563,7 @@ impl GitWrapper for SystemGitWrapper {
     }
 
     fn get_all_config(&self, scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
-        let mut cmd = Command::new("git");
+        let mut cmd = self.git();
         cmd.arg("config").arg("--list");
 
         if let Some(scope) = scope {
@@ -238,6 +598,67 @@ impl GitWrapper for SystemGitWrapper {
         }
     }
 
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        let output = self.git()
+            .args(["config", "--show-origin", "--get", key])
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("git config --show-origin --get {}", key),
+                error: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            // git config returns exit code 1 when key is not found
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.is_empty() || output.status.code() == Some(1) {
+                return Ok(None);
+            }
+            return Err(GitSetupError::Git(format!(
+                "Failed to get config '{}': {}",
+                key,
+                stderr.trim()
+            )));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some((file, value)) = stdout.lines().next().and_then(split_origin) else {
+            return Ok(None);
+        };
+        let line = origin_line(&file, key, &mut HashMap::new());
+        Ok(Some((value.to_string(), ConfigOrigin { file, line })))
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        let output = self.git()
+            .args(["config", "--show-origin", "--list"])
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: "git config --show-origin --list".to_string(),
+                error: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GitSetupError::Git(format!(
+                "Failed to list git config: {}",
+                stderr.trim()
+            )));
+        }
+
+        let mut config = HashMap::new();
+        let mut files = HashMap::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some((file, entry)) = split_origin(line)
+                && let Some((key, value)) = entry.split_once('=')
+            {
+                let line = origin_line(&file, key, &mut files);
+                config.insert(key.to_string(), (value.to_string(), ConfigOrigin { file, line }));
+            }
+        }
+
+        Ok(config)
+    }
+
     fn is_git_available(&self) -> Result<bool> {
         match Command::new("git").arg("--version").output() {
             Ok(output) => Ok(output.status.success()),
@@ -254,132 +675,218 @@ impl GitWrapper for SystemGitWrapper {
         }
     }
 
-    fn configure_signing(&self, profile: &Profile, scope: GitConfigScope) -> Result<()> {
-        // First clear any existing signing configuration
-        self.clear_signing_config(scope.clone())?;
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        let output = self.git()
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: "git rev-parse --is-inside-work-tree".to_string(),
+                error: e.to_string(),
+            })?;
 
-        match profile.key_type {
-            KeyType::Ssh => {
-                if let Some(signing_key) = &profile.signing_key {
-                    self.configure_ssh_signing(
-                        signing_key,
-                        profile.allowed_signers.as_deref(),
-                        scope,
-                    )
-                } else {
-                    Err(GitSetupError::InvalidProfile {
-                        reason: "SSH key type requires a signing key".to_string(),
-                    })
-                }
-            }
-            KeyType::Gpg => {
-                if let Some(signing_key) = &profile.signing_key {
-                    self.configure_gpg_signing(signing_key, scope)
-                } else {
-                    Err(GitSetupError::InvalidProfile {
-                        reason: "GPG key type requires a signing key".to_string(),
-                    })
-                }
-            }
-            KeyType::Gitsign => self.configure_gitsign(scope),
-            KeyType::X509 => self.configure_x509_signing(scope),
-        }
+        // git exits with an error outside of a repository
+        Ok(output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true")
     }
 
-    fn configure_ssh_signing(
-        &self,
-        signing_key: &str,
-        allowed_signers: Option<&str>,
-        scope: GitConfigScope,
-    ) -> Result<()> {
-        // Set gpg.format to ssh
-        self.set_config("gpg.format", "ssh", scope.clone())?;
-
-        // Set user.signingkey to the SSH public key or path
-        self.set_config("user.signingkey", signing_key, scope.clone())?;
+    fn test_sign(&self, key_type: &KeyType, signing_key: &str) -> Result<bool> {
+        let repo_dir = tempfile::tempdir().map_err(|e| {
+            GitSetupError::Git(format!("Failed to create temporary repository: {}", e))
+        })?;
+        let repo_path = repo_dir.path();
 
-        // Set gpg.ssh.allowedSignersFile if provided
-        if let Some(allowed_signers_path) = allowed_signers {
-            self.set_config(
-                "gpg.ssh.allowedSignersFile",
-                allowed_signers_path,
-                scope.clone(),
-            )?;
+        let init = Command::new("git")
+            .arg("init")
+            .arg("--quiet")
+            .arg(repo_path)
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: "git init".to_string(),
+                error: e.to_string(),
+            })?;
+        if !init.status.success() {
+            return Err(GitSetupError::Git(format!(
+                "Failed to initialize temporary repository: {}",
+                String::from_utf8_lossy(&init.stderr).trim()
+            )));
         }
 
-        // Enable commit signing
-        self.set_config("commit.gpgsign", "true", scope)?;
-
-        Ok(())
-    }
-
-    fn configure_gpg_signing(&self, signing_key: &str, scope: GitConfigScope) -> Result<()> {
-        // Set gpg.format to openpgp (default)
-        self.set_config("gpg.format", "openpgp", scope.clone())?;
+        std::fs::write(repo_path.join(".git-setup-test-signing"), "").map_err(|e| {
+            GitSetupError::Git(format!("Failed to create test file: {}", e))
+        })?;
 
-        // Set user.signingkey to the GPG key ID
-        self.set_config("user.signingkey", signing_key, scope.clone())?;
+        let (format, program) = match key_type {
+            KeyType::Ssh => ("ssh", None),
+            KeyType::Gpg => ("openpgp", None),
+            KeyType::X509 => ("x509", Some("smimesign")),
+            KeyType::Gitsign => ("x509", Some("gitsign")),
+        };
 
-        // Enable commit signing
-        self.set_config("commit.gpgsign", "true", scope)?;
+        let mut cmd = Command::new("git");
+        cmd.arg("-C")
+            .arg(repo_path)
+            .arg("-c")
+            .arg(format!("gpg.format={}", format))
+            .arg("-c")
+            .arg("user.name=git-setup")
+            .arg("-c")
+            .arg("user.email=git-setup@localhost");
+        if let Some(program) = program {
+            cmd.arg("-c").arg(format!("gpg.x509.program={}", program));
+        }
+        if !signing_key.is_empty() {
+            cmd.arg("-c").arg(format!("user.signingkey={}", signing_key));
+        }
+        cmd.arg("commit")
+            .arg("--allow-empty")
+            .arg("--no-edit")
+            .arg("--quiet")
+            .arg("-S")
+            .arg("-m")
+            .arg("git-setup signing test");
 
-        Ok(())
+        match cmd.output() {
+            Ok(output) => Ok(output.status.success()),
+            Err(e) => Err(GitSetupError::ExternalCommand {
+                command: "git commit --allow-empty --no-edit -S".to_string(),
+                error: e.to_string(),
+            }),
+        }
     }
 
-    fn configure_gitsign(&self, scope: GitConfigScope) -> Result<()> {
-        // Set gpg.format to x509
-        self.set_config("gpg.format", "x509", scope.clone())?;
-
-        // Set gpg.x509.program to gitsign
-        self.set_config("gpg.x509.program", "gitsign", scope.clone())?;
+    fn push_config_to_remote(&self, scope: GitConfigScope, remote: &str, keys_whitelist: &[&str]) -> Result<()> {
+        let shared: HashMap<String, String> = self
+            .get_all_config(Some(scope))?
+            .into_iter()
+            .filter(|(key, _)| is_shareable_config_key(key, keys_whitelist))
+            .collect();
+        if shared.is_empty() {
+            return Err(GitSetupError::Git(format!(
+                "No config keys match {}",
+                keys_whitelist.join(", ")
+            )));
+        }
 
-        // Enable commit signing
-        self.set_config("commit.gpgsign", "true", scope)?;
+        // Build on the remote's notes so the push fast-forwards; the ref may not exist yet
+        let _ = self.fetch_config_notes(remote);
+        let object = self.config_note_object()?;
+        let note = format_config_note(&shared);
+        let notes_ref = format!("--ref={}", CONFIG_NOTES_REF);
+        self.run(&["notes", &notes_ref, "add", "--force", "-m", &note, &object])?;
+        self.run(&["push", "--quiet", remote, CONFIG_NOTES_REF])?;
 
         Ok(())
     }
 
-    fn configure_x509_signing(&self, scope: GitConfigScope) -> Result<()> {
-        // Set gpg.format to x509
-        self.set_config("gpg.format", "x509", scope.clone())?;
+    fn pull_config_from_remote(&self, remote: &str) -> Result<HashMap<String, String>> {
+        self.fetch_config_notes(remote)?;
+        let object = self.config_note_object()?;
+        let notes_ref = format!("--ref={}", CONFIG_NOTES_REF);
+        let note = self.run(&["notes", &notes_ref, "show", &object])?;
+        Ok(parse_config_note(&note))
+    }
 
-        // Set gpg.x509.program to smimesign
-        self.set_config("gpg.x509.program", "smimesign", scope.clone())?;
+    fn get_last_commit_note(&self, ref_name: &str) -> Result<Option<String>> {
+        let notes_ref = format!("--ref={}", ref_name);
+        let output = self.git()
+            .args(["notes", &notes_ref, "show", "HEAD"])
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("git notes --ref={} show HEAD", ref_name),
+                error: e.to_string(),
+            })?;
 
-        // Enable commit signing
-        self.set_config("commit.gpgsign", "true", scope)?;
+        if output.status.success() {
+            let note = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            return Ok((!note.is_empty()).then_some(note));
+        }
 
-        Ok(())
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no note found") {
+            Ok(None)
+        } else {
+            Err(GitSetupError::Git(format!(
+                "Failed to read note '{}' of HEAD: {}",
+                ref_name,
+                stderr.trim()
+            )))
+        }
     }
 
-    fn clear_signing_config(&self, scope: GitConfigScope) -> Result<()> {
-        // Clear all signing-related configurations
-        let _ = self.unset_config("commit.gpgsign", scope.clone());
-        let _ = self.unset_config("user.signingkey", scope.clone());
-        let _ = self.unset_config("gpg.format", scope.clone());
-        let _ = self.unset_config("gpg.ssh.allowedSignersFile", scope.clone());
-        let _ = self.unset_config("gpg.x509.program", scope);
+    fn add_commit_note(&self, message: &str, ref_name: &str) -> Result<()> {
+        let notes_ref = format!("--ref={}", ref_name);
+        self.run(&["notes", &notes_ref, "add", "--force", "-m", message, "HEAD"])
+            .map(|_| ())
+    }
 
-        Ok(())
+    fn set_allowed_signers(&self, profile_name: &str, content: &str) -> Result<PathBuf> {
+        let dir = SystemPlatform.data_dir()?.join(ALLOWED_SIGNERS_DIR);
+        write_allowed_signers_file(&dir, profile_name, content)
     }
 }
 
 /// Mock implementation of GitWrapper for testing.
 use std::sync::{Arc, Mutex};
 
+/// A config write recorded by a [`MockGitWrapper`]: `(key, Some(value))` for
+/// `set_config` and `(key, None)` for `unset_config`.
+pub type ConfigWrite = (String, Option<String>);
+
+/// A config operation recorded in the call log of a [`MockGitWrapper`].
+///
+/// Reads are only recorded with call tracking enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitConfigCall {
+    Set { key: String, value: String, scope: GitConfigScope },
+    Unset { key: String, scope: GitConfigScope },
+    Get { key: String },
+}
+
 #[derive(Clone)]
 pub struct MockGitWrapper {
     config: Arc<Mutex<HashMap<String, String>>>,
+    scoped_config: Arc<Mutex<HashMap<GitConfigScope, HashMap<String, String>>>>,
+    origins: Arc<Mutex<HashMap<String, ConfigOrigin>>>,
     should_fail: bool,
     git_available: bool,
+    inside_work_tree: bool,
+    sign_succeeds: bool,
+    signing_fails: bool,
+    unwritable_keys: Vec<String>,
+    test_sign_calls: Arc<Mutex<Vec<(KeyType, String)>>>,
+    config_writes: Arc<Mutex<Vec<ConfigWrite>>>,
+    sudo_user: Option<String>,
+    call_log: Arc<Mutex<Vec<GitConfigCall>>>,
+    track_calls: bool,
+    signing_configured: Arc<Mutex<Vec<KeyType>>>,
+    signing_keys: Arc<Mutex<Vec<(KeyType, String)>>>,
+    commit_notes: Arc<Mutex<HashMap<String, String>>>,
+    remote_notes: Arc<Mutex<HashMap<String, String>>>,
+    allowed_signers: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl MockGitWrapper {
     pub fn new() -> Self {
         Self {
             config: Arc::new(Mutex::new(HashMap::new())),
+            scoped_config: Arc::new(Mutex::new(HashMap::new())),
+            origins: Arc::new(Mutex::new(HashMap::new())),
             should_fail: false,
             git_available: true,
+            inside_work_tree: true,
+            sign_succeeds: true,
+            signing_fails: false,
+            unwritable_keys: Vec::new(),
+            test_sign_calls: Arc::new(Mutex::new(Vec::new())),
+            config_writes: Arc::new(Mutex::new(Vec::new())),
+            sudo_user: None,
+            call_log: Arc::new(Mutex::new(Vec::new())),
+            track_calls: false,
+            signing_configured: Arc::new(Mutex::new(Vec::new())),
+            signing_keys: Arc::new(Mutex::new(Vec::new())),
+            commit_notes: Arc::new(Mutex::new(HashMap::new())),
+            remote_notes: Arc::new(Mutex::new(HashMap::new())),
+            allowed_signers: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -395,16 +902,230 @@ impl MockGitWrapper {
         self
     }
 
+    /// Configure whether the current directory is inside a git work tree.
+    pub fn with_inside_work_tree(mut self, inside: bool) -> Self {
+        self.inside_work_tree = inside;
+        self
+    }
+
     /// Pre-populate the mock with configuration values.
     pub fn with_config(self, config: HashMap<String, String>) -> Self {
         *self.config.lock().unwrap() = config;
         self
     }
 
+    /// Pre-populate the mock with configuration values for a single scope.
+    ///
+    /// Scoped reads of a key that is set in any scope only see that scope's value.
+    /// From then on writes are kept per scope too; mocks without scoped config
+    /// ignore the scope of reads and writes.
+    pub fn with_scoped_config(self, scope: GitConfigScope, config: HashMap<String, String>) -> Self {
+        self.config.lock().unwrap().extend(config.clone());
+        self.scoped_config.lock().unwrap().insert(scope, config);
+        self
+    }
+
+    /// Pre-populate the mock with the config keys of a signing configuration.
+    pub fn with_signing_info(self, info: SigningInfo) -> Self {
+        {
+            let mut config = self.config.lock().unwrap();
+            let values = [
+                ("gpg.format", info.format),
+                ("user.signingkey", info.signing_key),
+                ("commit.gpgsign", info.commit_gpgsign.then(|| "true".to_string())),
+                ("gpg.ssh.allowedSignersFile", info.allowed_signers_file),
+                ("gpg.x509.program", info.x509_program),
+            ];
+            for (key, value) in values {
+                match value {
+                    Some(value) => config.insert(key.to_string(), value),
+                    None => config.remove(key),
+                };
+            }
+        }
+        self
+    }
+
     /// Set a config value in the mock (for testing)
     pub fn mock_set_config(&self, key: &str, value: &str) {
         self.config.lock().unwrap().insert(key.to_string(), value.to_string());
     }
+
+    /// Set a config value in the mock along with the file and line it came from (for testing)
+    pub fn mock_set_config_with_origin(&self, key: &str, value: &str, file: &str, line: u32) {
+        self.mock_set_config(key, value);
+        self.origins
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), ConfigOrigin::new(file, line));
+    }
+
+    /// Configure whether test signing commits succeed.
+    pub fn with_sign_result(mut self, succeeds: bool) -> Self {
+        self.sign_succeeds = succeeds;
+        self
+    }
+
+    /// Configure signing setup to fail while plain config writes succeed.
+    pub fn with_signing_failure(mut self) -> Self {
+        self.signing_fails = true;
+        self
+    }
+
+    /// Make `set_config` and `unset_config` fail for `key` while other keys succeed.
+    pub fn with_unwritable_key(mut self, key: &str) -> Self {
+        self.unwritable_keys.push(key.to_string());
+        self
+    }
+
+    /// Get the config writes in order: `(key, Some(value))` for `set_config`
+    /// and `(key, None)` for `unset_config`.
+    pub fn config_writes(&self) -> Vec<ConfigWrite> {
+        self.config_writes.lock().unwrap().clone()
+    }
+
+    /// Record config writes as if run through `sudo -u <username>`, like `SudoGitWrapper`.
+    pub fn with_sudo_mode(mut self, username: &str) -> Self {
+        self.sudo_user = Some(username.to_string());
+        self
+    }
+
+    /// Get the config calls in order: every `set_config` and `unset_config`,
+    /// and with call tracking every `get_config`.
+    pub fn call_log(&self) -> Vec<GitConfigCall> {
+        self.call_log.lock().unwrap().clone()
+    }
+
+    /// Get the git commands the config writes of the call log would have run, e.g.
+    /// `sudo -u deploy git config --global user.email deploy@example.com`.
+    pub fn call_commands(&self) -> Vec<String> {
+        self.call_log()
+            .into_iter()
+            .filter_map(|call| {
+                let args = match call {
+                    GitConfigCall::Set { key, value, scope } => format!("{} {} {}", scope.to_git_arg(), key, value),
+                    GitConfigCall::Unset { key, scope } => format!("{} --unset {}", scope.to_git_arg(), key),
+                    GitConfigCall::Get { .. } => return None,
+                };
+                Some(match &self.sudo_user {
+                    Some(user) => format!("sudo -u {} git config {}", user, args),
+                    None => format!("git config {}", args),
+                })
+            })
+            .collect()
+    }
+
+    /// Record `get_config` calls in the call log too.
+    pub fn with_call_tracking(mut self) -> Self {
+        self.track_calls = true;
+        self
+    }
+
+    /// Append `call` to the call log.
+    fn log_call(&self, call: GitConfigCall) {
+        self.call_log.lock().unwrap().push(call);
+    }
+
+    /// Panic unless `key` was set to `value` at `scope`.
+    pub fn assert_config_was_set(&self, key: &str, value: &str, scope: GitConfigScope) {
+        let calls = self.call_log();
+        let expected = GitConfigCall::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+            scope: scope.clone(),
+        };
+        assert!(
+            calls.contains(&expected),
+            "expected {} to be set to '{}' at {:?} scope, config calls were: {:?}",
+            key,
+            value,
+            scope,
+            calls
+        );
+    }
+
+    /// Panic unless `key` was unset at `scope`.
+    pub fn assert_config_was_unset(&self, key: &str, scope: GitConfigScope) {
+        let calls = self.call_log();
+        let expected = GitConfigCall::Unset {
+            key: key.to_string(),
+            scope: scope.clone(),
+        };
+        assert!(
+            calls.contains(&expected),
+            "expected {} to be unset at {:?} scope, config calls were: {:?}",
+            key,
+            scope,
+            calls
+        );
+    }
+
+    /// Panic unless signing was configured for `key_type`, either through the
+    /// signing helpers or by setting `gpg.format` and `commit.gpgsign` directly.
+    pub fn assert_signing_was_configured(&self, key_type: KeyType) {
+        let format = match key_type {
+            KeyType::Ssh => "ssh",
+            KeyType::Gpg => "openpgp",
+            KeyType::X509 | KeyType::Gitsign => "x509",
+        };
+        let writes = self.config_writes();
+        let written = |key: &str, value: &str| writes.contains(&(key.to_string(), Some(value.to_string())));
+        let configured = self.signing_configured.lock().unwrap().contains(&key_type)
+            || (written("gpg.format", format) && written("commit.gpgsign", "true"));
+        assert!(
+            configured,
+            "expected {:?} signing to be configured, config writes were: {:?}",
+            key_type,
+            writes
+        );
+    }
+
+    /// Panic if any config was written, signing was configured or (with call tracking) config was read.
+    pub fn assert_no_calls_made(&self) {
+        let calls = self.call_log();
+        let signing = self.signing_configured.lock().unwrap().clone();
+        assert!(
+            calls.is_empty() && signing.is_empty(),
+            "expected no git calls, got config calls {:?} and signing setups {:?}",
+            calls,
+            signing
+        );
+    }
+
+    /// Record that signing was configured for `key_type`.
+    fn signing_configured(&self, key_type: KeyType) -> Result<()> {
+        self.signing_configured.lock().unwrap().push(key_type);
+        Ok(())
+    }
+
+    /// Get the `(key_type, signing_key)` pairs signing was configured with.
+    pub fn signing_keys(&self) -> Vec<(KeyType, String)> {
+        self.signing_keys.lock().unwrap().clone()
+    }
+
+    /// Get the `(key_type, signing_key)` pairs passed to `test_sign`.
+    pub fn test_sign_calls(&self) -> Vec<(KeyType, String)> {
+        self.test_sign_calls.lock().unwrap().clone()
+    }
+
+    /// Attach a note to `HEAD` under `ref_name`.
+    pub fn with_commit_note(self, ref_name: &str, note: &str) -> Self {
+        self.commit_notes
+            .lock()
+            .unwrap()
+            .insert(ref_name.to_string(), note.to_string());
+        self
+    }
+
+    /// Get the config note pushed to `remote`, if any.
+    pub fn remote_config_note(&self, remote: &str) -> Option<String> {
+        self.remote_notes.lock().unwrap().get(remote).cloned()
+    }
+
+    /// Get the allowed signers content written for `profile_name`, if any.
+    pub fn allowed_signers(&self, profile_name: &str) -> Option<String> {
+        self.allowed_signers.lock().unwrap().get(profile_name).cloned()
+    }
 }
 
 impl Default for MockGitWrapper {
@@ -414,36 +1135,91 @@ impl Default for MockGitWrapper {
 }
 
 impl GitWrapper for MockGitWrapper {
-    fn get_config(&self, key: &str, _scope: Option<GitConfigScope>) -> Result<Option<String>> {
+    fn get_config(&self, key: &str, scope: Option<GitConfigScope>) -> Result<Option<String>> {
         if self.should_fail {
             return Err(GitSetupError::Git("Mock git failure".to_string()));
         }
+        if self.track_calls {
+            self.log_call(GitConfigCall::Get { key: key.to_string() });
+        }
+        if let Some(scope) = scope {
+            let scoped = self.scoped_config.lock().unwrap();
+            if scoped.values().any(|config| config.contains_key(key)) {
+                return Ok(scoped.get(&scope).and_then(|config| config.get(key)).cloned());
+            }
+        }
         Ok(self.config.lock().unwrap().get(key).cloned())
     }
 
-    fn set_config(&self, key: &str, value: &str, _scope: GitConfigScope) -> Result<()> {
-        if self.should_fail {
+    fn set_config(&self, key: &str, value: &str, scope: GitConfigScope) -> Result<()> {
+        if self.should_fail || self.unwritable_keys.iter().any(|unwritable| unwritable == key) {
             return Err(GitSetupError::Git("Mock git failure".to_string()));
         }
+        self.config_writes.lock().unwrap().push((key.to_string(), Some(value.to_string())));
+        self.log_call(GitConfigCall::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+            scope: scope.clone(),
+        });
         self.config.lock().unwrap().insert(key.to_string(), value.to_string());
+        let mut scoped = self.scoped_config.lock().unwrap();
+        if !scoped.is_empty() {
+            scoped.entry(scope).or_default().insert(key.to_string(), value.to_string());
+        }
         Ok(())
     }
 
-    fn unset_config(&self, key: &str, _scope: GitConfigScope) -> Result<()> {
-        if self.should_fail {
+    fn unset_config(&self, key: &str, scope: GitConfigScope) -> Result<()> {
+        if self.should_fail || self.unwritable_keys.iter().any(|unwritable| unwritable == key) {
             return Err(GitSetupError::Git("Mock git failure".to_string()));
         }
+        self.config_writes.lock().unwrap().push((key.to_string(), None));
+        self.log_call(GitConfigCall::Unset {
+            key: key.to_string(),
+            scope: scope.clone(),
+        });
         self.config.lock().unwrap().remove(key);
+        if let Some(config) = self.scoped_config.lock().unwrap().get_mut(&scope) {
+            config.remove(key);
+        }
         Ok(())
     }
 
-    fn get_all_config(&self, _scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
+    fn get_all_config(&self, scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
         if self.should_fail {
             return Err(GitSetupError::Git("Mock git failure".to_string()));
         }
+        if let Some(scope) = scope {
+            let scoped = self.scoped_config.lock().unwrap();
+            if !scoped.is_empty() {
+                return Ok(scoped.get(&scope).cloned().unwrap_or_default());
+            }
+        }
         Ok(self.config.lock().unwrap().clone())
     }
 
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        Ok(self.get_all_config_with_origin()?.remove(key))
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock git failure".to_string()));
+        }
+        let origins = self.origins.lock().unwrap();
+        Ok(self
+            .config
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(key, value)| {
+                origins
+                    .get(key)
+                    .map(|origin| (key.clone(), (value.clone(), origin.clone())))
+            })
+            .collect())
+    }
+
     fn is_git_available(&self) -> Result<bool> {
         if self.should_fail {
             return Err(GitSetupError::Git("Mock git failure".to_string()));
@@ -451,6 +1227,13 @@ impl GitWrapper for MockGitWrapper {
         Ok(self.git_available)
     }
 
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock git failure".to_string()));
+        }
+        Ok(self.inside_work_tree)
+    }
+
     fn configure_signing(&self, profile: &Profile, _scope: GitConfigScope) -> Result<()> {
         if self.should_fail {
             return Err(GitSetupError::Git(
@@ -477,43 +1260,45 @@ impl GitWrapper for MockGitWrapper {
 
     fn configure_ssh_signing(
         &self,
-        _signing_key: &str,
+        signing_key: &str,
         _allowed_signers: Option<&str>,
         _scope: GitConfigScope,
     ) -> Result<()> {
-        if self.should_fail {
+        if self.should_fail || self.signing_fails {
             return Err(GitSetupError::Git(
                 "Mock SSH signing configuration failure".to_string(),
             ));
         }
-        Ok(())
+        self.signing_keys.lock().unwrap().push((KeyType::Ssh, signing_key.to_string()));
+        self.signing_configured(KeyType::Ssh)
     }
 
-    fn configure_gpg_signing(&self, _signing_key: &str, _scope: GitConfigScope) -> Result<()> {
-        if self.should_fail {
+    fn configure_gpg_signing(&self, signing_key: &str, _scope: GitConfigScope) -> Result<()> {
+        if self.should_fail || self.signing_fails {
             return Err(GitSetupError::Git(
                 "Mock GPG signing configuration failure".to_string(),
             ));
         }
-        Ok(())
+        self.signing_keys.lock().unwrap().push((KeyType::Gpg, signing_key.to_string()));
+        self.signing_configured(KeyType::Gpg)
     }
 
     fn configure_gitsign(&self, _scope: GitConfigScope) -> Result<()> {
-        if self.should_fail {
+        if self.should_fail || self.signing_fails {
             return Err(GitSetupError::Git(
                 "Mock gitsign configuration failure".to_string(),
             ));
         }
-        Ok(())
+        self.signing_configured(KeyType::Gitsign)
     }
 
     fn configure_x509_signing(&self, _scope: GitConfigScope) -> Result<()> {
-        if self.should_fail {
+        if self.should_fail || self.signing_fails {
             return Err(GitSetupError::Git(
                 "Mock x509 signing configuration failure".to_string(),
             ));
         }
-        Ok(())
+        self.signing_configured(KeyType::X509)
     }
 
     fn clear_signing_config(&self, _scope: GitConfigScope) -> Result<()> {
@@ -524,6 +1309,76 @@ impl GitWrapper for MockGitWrapper {
         }
         Ok(())
     }
+
+    fn test_sign(&self, key_type: &KeyType, signing_key: &str) -> Result<bool> {
+        self.test_sign_calls
+            .lock()
+            .unwrap()
+            .push((key_type.clone(), signing_key.to_string()));
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock test signing failure".to_string()));
+        }
+        Ok(self.sign_succeeds)
+    }
+
+    fn push_config_to_remote(&self, scope: GitConfigScope, remote: &str, keys_whitelist: &[&str]) -> Result<()> {
+        let shared: HashMap<String, String> = self
+            .get_all_config(Some(scope))?
+            .into_iter()
+            .filter(|(key, _)| is_shareable_config_key(key, keys_whitelist))
+            .collect();
+        if shared.is_empty() {
+            return Err(GitSetupError::Git(format!(
+                "No config keys match {}",
+                keys_whitelist.join(", ")
+            )));
+        }
+        self.remote_notes
+            .lock()
+            .unwrap()
+            .insert(remote.to_string(), format_config_note(&shared));
+        Ok(())
+    }
+
+    fn pull_config_from_remote(&self, remote: &str) -> Result<HashMap<String, String>> {
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock git failure".to_string()));
+        }
+        let notes = self.remote_notes.lock().unwrap();
+        let note = notes.get(remote).ok_or_else(|| {
+            GitSetupError::Git(format!("No shared config on remote '{}'", remote))
+        })?;
+        Ok(parse_config_note(note))
+    }
+
+    fn get_last_commit_note(&self, ref_name: &str) -> Result<Option<String>> {
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock git failure".to_string()));
+        }
+        Ok(self.commit_notes.lock().unwrap().get(ref_name).cloned())
+    }
+
+    fn add_commit_note(&self, message: &str, ref_name: &str) -> Result<()> {
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock git failure".to_string()));
+        }
+        self.commit_notes
+            .lock()
+            .unwrap()
+            .insert(ref_name.to_string(), message.to_string());
+        Ok(())
+    }
+
+    fn set_allowed_signers(&self, profile_name: &str, content: &str) -> Result<PathBuf> {
+        if self.should_fail {
+            return Err(GitSetupError::Git("Mock git failure".to_string()));
+        }
+        self.allowed_signers
+            .lock()
+            .unwrap()
+            .insert(profile_name.to_string(), content.to_string());
+        Ok(PathBuf::from("/mock/allowed_signers").join(profile_name))
+    }
 }
 
 #[cfg(test)]
@@ -545,6 +1400,52 @@ mod tests {
         assert_eq!(scope, cloned);
     }
 
+    // Shared config tests
+    #[test]
+    fn test_is_shareable_config_key() {
+        assert!(is_shareable_config_key("core.autocrlf", DEFAULT_SHARED_CONFIG_KEYS));
+        assert!(is_shareable_config_key("alias.co", DEFAULT_SHARED_CONFIG_KEYS));
+        assert!(!is_shareable_config_key("pull.rebase", DEFAULT_SHARED_CONFIG_KEYS));
+        assert!(is_shareable_config_key("pull.rebase", &["pull.*"]));
+
+        // Sensitive keys are never shared, even when whitelisted
+        assert!(!is_shareable_config_key("user.signingkey", &["*"]));
+        assert!(!is_shareable_config_key("user.email", &["user.*"]));
+        assert!(!is_shareable_config_key("credential.helper", &["*"]));
+        assert!(!is_shareable_config_key("http.https://example.com.extraheader", &["http.*"]));
+    }
+
+    #[test]
+    fn test_push_and_pull_config_with_mock() {
+        let mock = MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Local,
+            HashMap::from([
+                ("core.autocrlf".to_string(), "input".to_string()),
+                ("alias.co".to_string(), "checkout".to_string()),
+                ("user.signingkey".to_string(), "ABCD1234".to_string()),
+            ]),
+        );
+
+        mock.push_config_to_remote(GitConfigScope::Local, "origin", DEFAULT_SHARED_CONFIG_KEYS)
+            .unwrap();
+        assert_eq!(
+            mock.remote_config_note("origin").as_deref(),
+            Some("alias.co=checkout\ncore.autocrlf=input")
+        );
+
+        let pulled = mock.pull_config_from_remote("origin").unwrap();
+        assert_eq!(pulled.len(), 2);
+        assert_eq!(pulled.get("core.autocrlf").map(String::as_str), Some("input"));
+        assert!(mock.pull_config_from_remote("upstream").is_err());
+        assert!(mock.push_config_to_remote(GitConfigScope::Local, "origin", &["pull.*"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_config_note_drops_sensitive_keys() {
+        let config = parse_config_note("core.editor=vim\nuser.signingkey=ABCD\nnot a setting");
+        assert_eq!(config, HashMap::from([("core.editor".to_string(), "vim".to_string())]));
+    }
+
     // MockGitWrapper tests
     #[test]
     fn test_mock_git_wrapper_new() {
@@ -594,6 +1495,28 @@ mod tests {
         assert_eq!(all_config, config);
     }
 
+    #[test]
+    fn test_get_signing_info() {
+        let info = SigningInfo {
+            format: Some("ssh".to_string()),
+            signing_key: Some("~/.ssh/id_ed25519.pub".to_string()),
+            commit_gpgsign: true,
+            allowed_signers_file: Some("~/.ssh/allowed_signers".to_string()),
+            x509_program: None,
+        };
+        let mock = MockGitWrapper::new().with_signing_info(info.clone());
+        assert_eq!(mock.get_signing_info(None).unwrap(), info);
+
+        // Scoped reads only see the signing config of that scope
+        let local = HashMap::from([("commit.gpgsign".to_string(), "Yes".to_string())]);
+        let mock = MockGitWrapper::new().with_scoped_config(GitConfigScope::Local, local);
+        assert!(mock.get_signing_info(Some(GitConfigScope::Local)).unwrap().commit_gpgsign);
+        assert!(!mock.get_signing_info(Some(GitConfigScope::Global)).unwrap().commit_gpgsign);
+
+        assert_eq!(MockGitWrapper::new().get_signing_info(None).unwrap(), SigningInfo::default());
+        assert!(MockGitWrapper::new().with_failure().get_signing_info(None).is_err());
+    }
+
     #[test]
     fn test_mock_git_wrapper_set_config_success() {
         let mock = MockGitWrapper::new();
@@ -616,6 +1539,84 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_mock_git_wrapper_test_sign_tracks_calls() {
+        let mock = MockGitWrapper::new();
+        assert!(mock.test_sign(&KeyType::Ssh, "ssh-ed25519 AAAA").unwrap());
+
+        let failing = MockGitWrapper::new().with_sign_result(false);
+        assert!(!failing.test_sign(&KeyType::Gpg, "ABCD1234").unwrap());
+
+        assert_eq!(
+            mock.test_sign_calls(),
+            vec![(KeyType::Ssh, "ssh-ed25519 AAAA".to_string())]
+        );
+        assert_eq!(
+            failing.test_sign_calls(),
+            vec![(KeyType::Gpg, "ABCD1234".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_mock_git_wrapper_sudo_mode_call_log() {
+        let mock = MockGitWrapper::new().with_sudo_mode("deploy");
+        mock.set_config("user.name", "Deploy", GitConfigScope::Global).unwrap();
+        mock.unset_config("commit.gpgsign", GitConfigScope::Local).unwrap();
+
+        assert_eq!(
+            mock.call_commands(),
+            vec![
+                "sudo -u deploy git config --global user.name Deploy".to_string(),
+                "sudo -u deploy git config --local --unset commit.gpgsign".to_string(),
+            ]
+        );
+
+        let plain = MockGitWrapper::new();
+        plain.set_config("user.name", "Jane", GitConfigScope::Global).unwrap();
+        assert_eq!(plain.call_commands(), vec!["git config --global user.name Jane".to_string()]);
+    }
+
+    #[test]
+    fn test_mock_git_wrapper_call_tracking() {
+        let mock = MockGitWrapper::new().with_call_tracking();
+        mock.assert_no_calls_made();
+
+        mock.get_config("user.email", None).unwrap();
+        mock.set_config("user.email", "jane@work.com", GitConfigScope::Local).unwrap();
+        mock.unset_config("user.name", GitConfigScope::Global).unwrap();
+        mock.configure_gpg_signing("ABCD1234", GitConfigScope::Local).unwrap();
+
+        assert_eq!(
+            mock.call_log(),
+            vec![
+                GitConfigCall::Get { key: "user.email".to_string() },
+                GitConfigCall::Set {
+                    key: "user.email".to_string(),
+                    value: "jane@work.com".to_string(),
+                    scope: GitConfigScope::Local,
+                },
+                GitConfigCall::Unset { key: "user.name".to_string(), scope: GitConfigScope::Global },
+            ]
+        );
+        mock.assert_config_was_set("user.email", "jane@work.com", GitConfigScope::Local);
+        mock.assert_config_was_unset("user.name", GitConfigScope::Global);
+        mock.assert_signing_was_configured(KeyType::Gpg);
+
+        // Reads are only recorded with call tracking
+        let untracked = MockGitWrapper::new();
+        untracked.get_config("user.email", None).unwrap();
+        untracked.assert_no_calls_made();
+    }
+
+    #[test]
+    #[should_panic(expected = "expected user.email to be set to 'jane@work.com' at Global scope")]
+    fn test_mock_git_wrapper_assert_config_was_set_panics() {
+        let mock = MockGitWrapper::new();
+        mock.set_config("user.email", "jane@home.net", GitConfigScope::Local).unwrap();
+        mock.set_config("user.email", "jane@work.com", GitConfigScope::Local).unwrap();
+        mock.assert_config_was_set("user.email", "jane@work.com", GitConfigScope::Global);
+    }
+
     #[test]
     fn test_mock_git_wrapper_unset_config_failure() {
         let mock = MockGitWrapper::new().with_failure();
@@ -624,6 +1625,50 @@ mod tests {
         assert!(matches!(result.unwrap_err(), GitSetupError::Git(_)));
     }
 
+    #[test]
+    fn test_mock_git_wrapper_config_with_origin() {
+        let mock = MockGitWrapper::new();
+        mock.mock_set_config_with_origin("user.email", "work@example.com", "/home/jane/.gitconfig", 12);
+        mock.mock_set_config("user.name", "Jane");
+
+        let (value, origin) = mock.get_config_with_origin("user.email").unwrap().unwrap();
+        assert_eq!(value, "work@example.com");
+        assert_eq!(origin, ConfigOrigin::new("/home/jane/.gitconfig", 12));
+        assert_eq!(origin.to_string(), "/home/jane/.gitconfig line 12");
+
+        let all = mock.get_all_config_with_origin().unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(mock.get_config_with_origin("user.name").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_split_origin() {
+        let (file, rest) = split_origin("file:/home/jane/.gitconfig\tuser.email=jane@example.com").unwrap();
+        assert_eq!(file, PathBuf::from("/home/jane/.gitconfig"));
+        assert_eq!(rest, "user.email=jane@example.com");
+
+        let (file, _) = split_origin("file:\"/tmp/my config\"\tvalue").unwrap();
+        assert_eq!(file, PathBuf::from("/tmp/my config"));
+        assert!(split_origin("no tab here").is_none());
+    }
+
+    #[test]
+    fn test_find_key_line() {
+        let contents = "[user]\n\tname = Jane\n\temail = old@example.com\n[core]\n\teditor = vim\n\
+                        [user]\n\temail = new@example.com ; work\n[url \"git@github.com:\"]\n\tinsteadOf = gh:\n";
+        assert_eq!(find_key_line(contents, "user.name"), Some(2));
+        assert_eq!(find_key_line(contents, "user.email"), Some(7));
+        assert_eq!(find_key_line(contents, "url.git@github.com:.insteadof"), Some(9));
+        assert_eq!(find_key_line(contents, "core.pager"), None);
+    }
+
+    #[test]
+    fn test_system_git_wrapper_get_config_with_origin() {
+        let wrapper = SystemGitWrapper::new();
+        let result = wrapper.get_config_with_origin("git-setup-rs.test.nonexistent");
+        assert!(matches!(result, Ok(None) | Err(GitSetupError::ExternalCommand { .. })));
+    }
+
     // SystemGitWrapper tests - these should fail until we implement them
     #[test]
     fn test_system_git_wrapper_get_config() {
@@ -803,6 +1848,15 @@ mod tests {
             include_if_dirs: Vec::new(),
             host_patterns: Vec::new(),
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = mock.configure_signing(&profile, GitConfigScope::Global);
@@ -831,6 +1885,15 @@ mod tests {
             include_if_dirs: Vec::new(),
             host_patterns: Vec::new(),
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = mock.configure_signing(&profile, GitConfigScope::Global);
@@ -859,6 +1922,15 @@ mod tests {
             include_if_dirs: Vec::new(),
             host_patterns: Vec::new(),
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = mock.configure_signing(&profile, GitConfigScope::Global);
@@ -891,6 +1963,15 @@ mod tests {
             include_if_dirs: Vec::new(),
             host_patterns: Vec::new(),
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = mock.configure_signing(&profile, GitConfigScope::Global);
@@ -923,6 +2004,15 @@ mod tests {
             include_if_dirs: Vec::new(),
             host_patterns: Vec::new(),
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = mock.configure_signing(&profile, GitConfigScope::Global);
@@ -951,6 +2041,15 @@ mod tests {
             include_if_dirs: Vec::new(),
             host_patterns: Vec::new(),
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let result = mock.configure_signing(&profile, GitConfigScope::Global);
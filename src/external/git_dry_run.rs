@@ -0,0 +1,183 @@
+//! Dry-run git wrapper for git-setup-rs.
+//!
+//! [`DryRunGitWrapper`] implements [`GitWrapper`] by recording the config
+//! writes an apply would make, together with the value each key has now,
+//! instead of making them. Reads are passed to the wrapped [`GitWrapper`], so
+//! the recorded changes show the difference to the real configuration.
+
+use super::git::{ConfigOrigin, GitConfigScope, GitWrapper};
+use crate::config::types::KeyType;
+use crate::error::{GitSetupError, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// A change to one git config key that an apply would make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub scope: GitConfigScope,
+    /// Value of the key at `scope` now
+    pub current: Option<String>,
+    /// Value after the apply; `None` if the key would be unset
+    pub proposed: Option<String>,
+}
+
+impl ConfigChange {
+    /// Diff marker of the change: `+` adds, `-` removes and `~` changes a key.
+    pub fn marker(&self) -> &'static str {
+        match (&self.current, &self.proposed) {
+            (None, _) => "+",
+            (Some(_), None) => "-",
+            (Some(_), Some(_)) => "~",
+        }
+    }
+}
+
+/// Git wrapper recording config writes as [`ConfigChange`]s instead of making them.
+pub struct DryRunGitWrapper {
+    inner: Arc<dyn GitWrapper>,
+    changes: Mutex<Vec<ConfigChange>>,
+}
+
+impl DryRunGitWrapper {
+    /// Create a wrapper reading the current configuration through `inner`.
+    pub fn new(inner: Arc<dyn GitWrapper>) -> Self {
+        Self {
+            inner,
+            changes: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The recorded changes in the order they were first made.
+    ///
+    /// Writes leaving a key at its current value are not included, and a key
+    /// written several times shows its last value.
+    pub fn changes(&self) -> Vec<ConfigChange> {
+        self.changes
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|change| change.current != change.proposed)
+            .cloned()
+            .collect()
+    }
+
+    fn record(&self, key: &str, proposed: Option<&str>, scope: GitConfigScope) -> Result<()> {
+        let mut changes = self.changes.lock().unwrap();
+        let proposed = proposed.map(str::to_string);
+        if let Some(change) = changes.iter_mut().find(|c| c.key == key && c.scope == scope) {
+            change.proposed = proposed;
+            return Ok(());
+        }
+
+        let current = self.inner.get_config(key, Some(scope.clone()))?;
+        changes.push(ConfigChange {
+            key: key.to_string(),
+            scope,
+            current,
+            proposed,
+        });
+        Ok(())
+    }
+
+    fn unsupported(operation: &str) -> GitSetupError {
+        GitSetupError::Git(format!("{} is not possible in a dry run", operation))
+    }
+}
+
+impl GitWrapper for DryRunGitWrapper {
+    fn get_config(&self, key: &str, scope: Option<GitConfigScope>) -> Result<Option<String>> {
+        self.inner.get_config(key, scope)
+    }
+
+    fn set_config(&self, key: &str, value: &str, scope: GitConfigScope) -> Result<()> {
+        self.record(key, Some(value), scope)
+    }
+
+    fn unset_config(&self, key: &str, scope: GitConfigScope) -> Result<()> {
+        self.record(key, None, scope)
+    }
+
+    fn get_all_config(&self, scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
+        self.inner.get_all_config(scope)
+    }
+
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        self.inner.get_config_with_origin(key)
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        self.inner.get_all_config_with_origin()
+    }
+
+    fn is_git_available(&self) -> Result<bool> {
+        self.inner.is_git_available()
+    }
+
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        self.inner.is_inside_work_tree()
+    }
+
+    fn test_sign(&self, _key_type: &KeyType, _signing_key: &str) -> Result<bool> {
+        Err(Self::unsupported("Test signing"))
+    }
+
+    fn push_config_to_remote(&self, _scope: GitConfigScope, _remote: &str, _keys_whitelist: &[&str]) -> Result<()> {
+        Err(Self::unsupported("Pushing config to a remote"))
+    }
+
+    fn pull_config_from_remote(&self, _remote: &str) -> Result<HashMap<String, String>> {
+        Err(Self::unsupported("Pulling config from a remote"))
+    }
+
+    fn get_last_commit_note(&self, ref_name: &str) -> Result<Option<String>> {
+        self.inner.get_last_commit_note(ref_name)
+    }
+
+    fn add_commit_note(&self, _message: &str, _ref_name: &str) -> Result<()> {
+        Err(Self::unsupported("Adding commit notes"))
+    }
+
+    fn set_allowed_signers(&self, _profile_name: &str, _content: &str) -> Result<PathBuf> {
+        Err(Self::unsupported("Writing an allowed signers file"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::git::MockGitWrapper;
+
+    #[test]
+    fn test_records_changes_without_writing() {
+        let inner = Arc::new(MockGitWrapper::new());
+        inner.mock_set_config("user.email", "old@example.com");
+        inner.mock_set_config("gpg.format", "openpgp");
+        inner.mock_set_config("user.name", "Jane");
+        let git = DryRunGitWrapper::new(inner.clone());
+
+        git.set_config("user.email", "new@example.com", GitConfigScope::Global).unwrap();
+        git.set_config("user.signingkey", "ABCD", GitConfigScope::Global).unwrap();
+        git.set_config("user.signingkey", "EF01", GitConfigScope::Global).unwrap();
+        git.unset_config("gpg.format", GitConfigScope::Global).unwrap();
+        git.unset_config("gpg.program", GitConfigScope::Global).unwrap();
+        git.set_config("user.name", "Jane", GitConfigScope::Global).unwrap();
+
+        let changes = git.changes();
+        let summary: Vec<(&str, &str, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.marker(), c.key.as_str(), c.proposed.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("~", "user.email", Some("new@example.com")),
+                ("+", "user.signingkey", Some("EF01")),
+                ("-", "gpg.format", None),
+            ]
+        );
+        assert!(inner.config_writes().is_empty());
+        assert_eq!(git.get_config("user.email", None).unwrap().as_deref(), Some("old@example.com"));
+    }
+}
@@ -0,0 +1,563 @@
+//! Gitconfig file wrapper for git-setup-rs.
+//!
+//! [`FilesystemGitWrapper`] implements [`GitWrapper`] by editing a gitconfig
+//! file directly instead of running `git config`, so configuration can be
+//! generated for a container or another machine. Sections, subsections
+//! (`[includeIf "gitdir:~/work/"]`), quoted values and multi-value keys are
+//! handled; comments and unrelated entries of an existing file are kept.
+
+use super::git::{write_allowed_signers_file, ConfigOrigin, GitConfigScope, GitWrapper, ALLOWED_SIGNERS_DIR};
+use crate::config::types::KeyType;
+use crate::error::{GitSetupError, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A line of a gitconfig file.
+#[derive(Debug, Clone, PartialEq)]
+enum ConfigLine {
+    /// `[section]` or `[section "subsection"]`, with its normalized name
+    Section { name: String, raw: String },
+    /// `name = value`, with its normalized full key and 1-based line number
+    Entry { key: String, value: String, line: u32, raw: String },
+    /// Comment, blank line or anything else, kept verbatim
+    Other(String),
+}
+
+impl ConfigLine {
+    fn raw(&self) -> &str {
+        match self {
+            ConfigLine::Section { raw, .. } | ConfigLine::Entry { raw, .. } | ConfigLine::Other(raw) => raw,
+        }
+    }
+}
+
+/// Split `key` into its section (`section` or `section.subsection`) and variable name.
+///
+/// Section and variable names are case-insensitive and lowercased; subsections keep their case.
+fn split_key(key: &str) -> Result<(String, &str)> {
+    let (section, name) = key
+        .rsplit_once('.')
+        .filter(|(section, name)| !section.is_empty() && !name.is_empty())
+        .ok_or_else(|| GitSetupError::Git(format!("Config key '{}' does not contain a section", key)))?;
+    let section = match section.split_once('.') {
+        Some((section, subsection)) => format!("{}.{}", section.to_lowercase(), subsection),
+        None => section.to_lowercase(),
+    };
+    Ok((section, name))
+}
+
+/// Normalized form of `key`, for comparing keys.
+fn normalize_key(key: &str) -> Result<String> {
+    let (section, name) = split_key(key)?;
+    Ok(format!("{}.{}", section, name.to_lowercase()))
+}
+
+/// Parse a `[section]`, `[section "subsection"]` or legacy `[section.subsection]` header.
+fn parse_section_header(line: &str) -> Option<String> {
+    let inner = line.trim().strip_prefix('[')?;
+    let end = inner.rfind(']')?;
+    let inner = &inner[..end];
+
+    match inner.split_once(char::is_whitespace) {
+        Some((section, subsection)) => {
+            let quoted = subsection.trim().strip_prefix('"')?.strip_suffix('"')?;
+            let mut name = String::new();
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => name.extend(chars.next()),
+                    c => name.push(c),
+                }
+            }
+            Some(format!("{}.{}", section.to_lowercase(), name))
+        }
+        // Legacy subsections are case-insensitive
+        None => Some(inner.to_lowercase()),
+    }
+}
+
+/// Parse a value: strip comments and unquoted surrounding whitespace, resolve quotes and escapes.
+fn parse_value(raw: &str) -> String {
+    let mut value = String::new();
+    let mut pending_whitespace = String::new();
+    let mut in_quotes = false;
+    let mut chars = raw.trim_start().chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '#' | ';' if !in_quotes => break,
+            c if c.is_whitespace() && !in_quotes => {
+                pending_whitespace.push(c);
+                continue;
+            }
+            '\\' => {
+                value.push_str(&pending_whitespace);
+                match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('b') => value.push('\u{8}'),
+                    Some(escaped) => value.push(escaped),
+                    None => {}
+                }
+            }
+            c => {
+                value.push_str(&pending_whitespace);
+                value.push(c);
+            }
+        }
+        pending_whitespace.clear();
+    }
+    value
+}
+
+/// Format a value so that `parse_value` reads it back unchanged.
+fn format_value(value: &str) -> String {
+    let needs_quotes = value.starts_with(char::is_whitespace)
+        || value.ends_with(char::is_whitespace)
+        || value.contains(['#', ';']);
+    let escaped: String = value
+        .chars()
+        .map(|c| match c {
+            '\\' => "\\\\".to_string(),
+            '"' => "\\\"".to_string(),
+            '\n' => "\\n".to_string(),
+            '\t' => "\\t".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    if needs_quotes {
+        format!("\"{}\"", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// Format the header of a `section` or `section.subsection` name.
+fn format_section_header(section: &str) -> String {
+    match section.split_once('.') {
+        Some((section, subsection)) => format!(
+            "[{} \"{}\"]",
+            section,
+            subsection.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+        None => format!("[{}]", section),
+    }
+}
+
+/// Whether a value line continues on the next line (ends in an unescaped backslash).
+fn continues(line: &str) -> bool {
+    let trailing = line.len() - line.trim_end_matches('\\').len();
+    trailing % 2 == 1
+}
+
+/// Parsed contents of a gitconfig file.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ConfigDocument {
+    lines: Vec<ConfigLine>,
+}
+
+impl ConfigDocument {
+    fn parse(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut section: Option<String> = None;
+        let mut source = content.lines().enumerate();
+
+        while let Some((index, line)) = source.next() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with(['#', ';']) {
+                lines.push(ConfigLine::Other(line.to_string()));
+            } else if trimmed.starts_with('[') {
+                match parse_section_header(trimmed) {
+                    Some(name) => {
+                        section = Some(name.clone());
+                        lines.push(ConfigLine::Section { name, raw: line.to_string() });
+                    }
+                    None => lines.push(ConfigLine::Other(line.to_string())),
+                }
+            } else if let Some(section) = &section {
+                let mut raw = line.to_string();
+                let mut logical = line.to_string();
+                while continues(&logical) {
+                    let Some((_, next)) = source.next() else {
+                        break;
+                    };
+                    logical.pop();
+                    logical.push_str(next);
+                    raw.push('\n');
+                    raw.push_str(next);
+                }
+
+                let name_len = trimmed
+                    .find(|c: char| !(c.is_alphanumeric() || c == '-'))
+                    .unwrap_or(trimmed.len());
+                let name = &trimmed[..name_len];
+                let rest = logical.trim_start()[name_len..].trim_start();
+                let value = match rest.strip_prefix('=') {
+                    Some(value) => parse_value(value),
+                    // A name without a value is a boolean set to true
+                    None => "true".to_string(),
+                };
+                lines.push(ConfigLine::Entry {
+                    key: format!("{}.{}", section, name.to_lowercase()),
+                    value,
+                    line: index as u32 + 1,
+                    raw,
+                });
+            } else {
+                lines.push(ConfigLine::Other(line.to_string()));
+            }
+        }
+
+        Self { lines }
+    }
+
+    fn render(&self) -> String {
+        let mut content: String = self.lines.iter().map(|line| format!("{}\n", line.raw())).collect();
+        if content.trim().is_empty() {
+            content.clear();
+        }
+        content
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&str, &str, u32)> {
+        self.lines.iter().filter_map(|line| match line {
+            ConfigLine::Entry { key, value, line, .. } => Some((key.as_str(), value.as_str(), *line)),
+            _ => None,
+        })
+    }
+
+    fn values(&self, key: &str) -> Result<Vec<String>> {
+        let key = normalize_key(key)?;
+        Ok(self
+            .entries()
+            .filter(|(entry_key, _, _)| *entry_key == key)
+            .map(|(_, value, _)| value.to_string())
+            .collect())
+    }
+
+    fn entry_line(key: &str, value: &str) -> Result<ConfigLine> {
+        let (section, name) = split_key(key)?;
+        Ok(ConfigLine::Entry {
+            key: format!("{}.{}", section, name.to_lowercase()),
+            value: value.to_string(),
+            line: 0,
+            raw: format!("\t{} = {}", name, format_value(value)),
+        })
+    }
+
+    /// Add a value for `key` after the last entry of its section, creating the section if needed.
+    fn add(&mut self, key: &str, value: &str) -> Result<()> {
+        let (section, _) = split_key(key)?;
+        let entry = Self::entry_line(key, value)?;
+
+        let section_start = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, ConfigLine::Section { name, .. } if *name == section));
+        match section_start {
+            Some(start) => {
+                let end = self.lines[start + 1..]
+                    .iter()
+                    .position(|line| matches!(line, ConfigLine::Section { .. }))
+                    .map_or(self.lines.len(), |offset| start + 1 + offset);
+                // Keep blank lines and comments before the next section after the new entry
+                let insert_at = self.lines[start + 1..end]
+                    .iter()
+                    .rposition(|line| matches!(line, ConfigLine::Entry { .. }))
+                    .map_or(start + 1, |offset| start + 2 + offset);
+                self.lines.insert(insert_at, entry);
+            }
+            None => {
+                // Write the section as it was given, like git does
+                let (given_section, _) = key.rsplit_once('.').unwrap_or_default();
+                self.lines.push(ConfigLine::Section {
+                    raw: format_section_header(given_section),
+                    name: section,
+                });
+                self.lines.push(entry);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace all values of `key` with `value`.
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let normalized = normalize_key(key)?;
+        let is_key = |line: &ConfigLine| matches!(line, ConfigLine::Entry { key, .. } if *key == normalized);
+
+        match self.lines.iter().position(is_key) {
+            Some(first) => {
+                self.lines[first] = Self::entry_line(key, value)?;
+                let mut index = 0;
+                self.lines.retain(|line| {
+                    index += 1;
+                    index - 1 == first || !is_key(line)
+                });
+                Ok(())
+            }
+            None => self.add(key, value),
+        }
+    }
+
+    /// Remove all values of `key`, and its section if that leaves it empty.
+    fn unset(&mut self, key: &str) -> Result<()> {
+        let normalized = normalize_key(key)?;
+        self.lines
+            .retain(|line| !matches!(line, ConfigLine::Entry { key, .. } if *key == normalized));
+
+        let mut index = 0;
+        while index < self.lines.len() {
+            let empty_section = matches!(self.lines[index], ConfigLine::Section { .. })
+                && matches!(self.lines.get(index + 1), None | Some(ConfigLine::Section { .. }));
+            if empty_section {
+                self.lines.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// GitWrapper that reads and writes a gitconfig file directly.
+///
+/// The scope passed to each operation is ignored: every value is read from
+/// and written to the wrapped file.
+pub struct FilesystemGitWrapper {
+    path: PathBuf,
+}
+
+impl FilesystemGitWrapper {
+    /// Create a wrapper editing the gitconfig file at `path`, which need not exist yet.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the wrapped file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn load(&self) -> Result<ConfigDocument> {
+        if !self.path.exists() {
+            return Ok(ConfigDocument::default());
+        }
+        Ok(ConfigDocument::parse(&std::fs::read_to_string(&self.path)?))
+    }
+
+    fn save(&self, document: &ConfigDocument) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, document.render())?;
+        Ok(())
+    }
+
+    fn modify(&self, change: impl FnOnce(&mut ConfigDocument) -> Result<()>) -> Result<()> {
+        let mut document = self.load()?;
+        change(&mut document)?;
+        self.save(&document)
+    }
+
+    /// Empty the file, discarding its previous content.
+    pub fn clear(&self) -> Result<()> {
+        self.save(&ConfigDocument::default())
+    }
+
+    /// Get every value of a multi-value key, in file order.
+    pub fn get_all_values(&self, key: &str) -> Result<Vec<String>> {
+        self.load()?.values(key)
+    }
+
+    /// Add a value to `key` without replacing its existing values (`git config --add`).
+    pub fn add_config(&self, key: &str, value: &str) -> Result<()> {
+        self.modify(|document| document.add(key, value))
+    }
+
+    fn unsupported(operation: &str) -> GitSetupError {
+        GitSetupError::Git(format!("{} is not possible when writing to a gitconfig file", operation))
+    }
+}
+
+impl GitWrapper for FilesystemGitWrapper {
+    fn get_config(&self, key: &str, _scope: Option<GitConfigScope>) -> Result<Option<String>> {
+        // Like git, the last of several values wins
+        Ok(self.get_all_values(key)?.pop())
+    }
+
+    fn set_config(&self, key: &str, value: &str, _scope: GitConfigScope) -> Result<()> {
+        self.modify(|document| document.set(key, value))
+    }
+
+    fn unset_config(&self, key: &str, _scope: GitConfigScope) -> Result<()> {
+        self.modify(|document| document.unset(key))
+    }
+
+    fn get_all_config(&self, _scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
+        Ok(self
+            .load()?
+            .entries()
+            .map(|(key, value, _)| (key.to_string(), value.to_string()))
+            .collect())
+    }
+
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        let key = normalize_key(key)?;
+        Ok(self
+            .load()?
+            .entries()
+            .filter(|(entry_key, _, _)| *entry_key == key)
+            .last()
+            .map(|(_, value, line)| (value.to_string(), ConfigOrigin::new(&self.path, line))))
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        Ok(self
+            .load()?
+            .entries()
+            .map(|(key, value, line)| (key.to_string(), (value.to_string(), ConfigOrigin::new(&self.path, line))))
+            .collect())
+    }
+
+    fn is_git_available(&self) -> Result<bool> {
+        // The file is edited without running git
+        Ok(true)
+    }
+
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        // The file does not belong to the repository in the current directory
+        Ok(false)
+    }
+
+    fn test_sign(&self, _key_type: &KeyType, _signing_key: &str) -> Result<bool> {
+        Err(Self::unsupported("Test signing"))
+    }
+
+    fn push_config_to_remote(&self, _scope: GitConfigScope, _remote: &str, _keys_whitelist: &[&str]) -> Result<()> {
+        Err(Self::unsupported("Pushing config to a remote"))
+    }
+
+    fn pull_config_from_remote(&self, _remote: &str) -> Result<HashMap<String, String>> {
+        Err(Self::unsupported("Pulling config from a remote"))
+    }
+
+    fn get_last_commit_note(&self, _ref_name: &str) -> Result<Option<String>> {
+        Err(Self::unsupported("Reading commit notes"))
+    }
+
+    fn add_commit_note(&self, _message: &str, _ref_name: &str) -> Result<()> {
+        Err(Self::unsupported("Adding commit notes"))
+    }
+
+    fn set_allowed_signers(&self, profile_name: &str, content: &str) -> Result<PathBuf> {
+        // Kept next to the gitconfig file, so both can be copied to the target together
+        let dir = self.path.parent().unwrap_or_else(|| Path::new(".")).join(ALLOWED_SIGNERS_DIR);
+        write_allowed_signers_file(&dir, profile_name, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::Profile;
+    use tempfile::TempDir;
+
+    const EXISTING: &str = r#"# Shared settings
+[core]
+	editor = vim  # inline comment
+	pager = "less -R"
+[includeIf "gitdir:~/Work/"]
+	path = ~/.gitconfig-work
+[remote "origin"]
+	fetch = +refs/heads/*:refs/remotes/origin/*
+	fetch = +refs/tags/*:refs/tags/*
+[alias]
+	lg = log --graph \
+		--oneline
+	ro
+"#;
+
+    fn wrapper_with(content: &str) -> (TempDir, FilesystemGitWrapper) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("gitconfig");
+        std::fs::write(&path, content).unwrap();
+        (dir, FilesystemGitWrapper::new(path))
+    }
+
+    #[test]
+    fn test_reads_sections_subsections_and_values() {
+        let (_dir, git) = wrapper_with(EXISTING);
+
+        assert_eq!(git.get_config("core.editor", None).unwrap().as_deref(), Some("vim"));
+        assert_eq!(git.get_config("Core.Pager", None).unwrap().as_deref(), Some("less -R"));
+        assert_eq!(
+            git.get_config("includeIf.gitdir:~/Work/.path", None).unwrap().as_deref(),
+            Some("~/.gitconfig-work")
+        );
+        assert_eq!(
+            git.get_all_values("remote.origin.fetch").unwrap(),
+            vec!["+refs/heads/*:refs/remotes/origin/*", "+refs/tags/*:refs/tags/*"]
+        );
+        assert_eq!(git.get_config("alias.lg", None).unwrap().as_deref(), Some("log --graph \t\t--oneline"));
+        assert_eq!(git.get_config("alias.ro", None).unwrap().as_deref(), Some("true"));
+        assert_eq!(git.get_config("user.email", None).unwrap(), None);
+
+        let (_, origin) = git.get_config_with_origin("core.pager").unwrap().unwrap();
+        assert_eq!(origin.line, 4);
+    }
+
+    #[test]
+    fn test_set_and_unset_keep_other_content() {
+        let (_dir, git) = wrapper_with(EXISTING);
+
+        git.set_config("core.editor", "code --wait", GitConfigScope::Global).unwrap();
+        git.set_config("user.email", "jane@example.com", GitConfigScope::Global).unwrap();
+        git.set_config("remote.origin.fetch", "+refs/heads/main", GitConfigScope::Global).unwrap();
+        git.set_config("alias.hash", "!echo '#'", GitConfigScope::Global).unwrap();
+        git.unset_config("includeIf.gitdir:~/Work/.path", GitConfigScope::Global).unwrap();
+
+        let content = std::fs::read_to_string(git.path()).unwrap();
+        assert!(content.starts_with("# Shared settings\n[core]\n\teditor = code --wait\n"));
+        assert!(content.ends_with("[user]\n\temail = jane@example.com\n"));
+        assert!(!content.contains("includeIf"));
+        assert_eq!(git.get_all_values("remote.origin.fetch").unwrap(), vec!["+refs/heads/main"]);
+        assert_eq!(git.get_config("alias.hash", None).unwrap().as_deref(), Some("!echo '#'"));
+        assert_eq!(git.get_config("alias.lg", None).unwrap().as_deref(), Some("log --graph \t\t--oneline"));
+    }
+
+    #[test]
+    fn test_configure_signing_writes_new_file() {
+        let dir = TempDir::new().unwrap();
+        let git = FilesystemGitWrapper::new(dir.path().join("out").join(".gitconfig"));
+        let profile = Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("ssh-ed25519 AAAA jane@work.com".to_string()),
+            ..Default::default()
+        };
+
+        git.set_config("user.email", &profile.git_user_email, GitConfigScope::Global).unwrap();
+        git.configure_signing(&profile, GitConfigScope::Global).unwrap();
+        git.add_config("includeIf.gitdir:~/work/.path", "~/.gitconfig-work").unwrap();
+
+        let content = std::fs::read_to_string(git.path()).unwrap();
+        assert_eq!(
+            content,
+            "[user]\n\temail = jane@work.com\n\tsigningkey = ssh-ed25519 AAAA jane@work.com\n\
+             [gpg]\n\tformat = ssh\n[commit]\n\tgpgsign = true\n\
+             [includeIf \"gitdir:~/work/\"]\n\tpath = ~/.gitconfig-work\n"
+        );
+
+        git.clear().unwrap();
+        assert!(git.get_all_config(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        for value in ["plain", " padded ", "has \"quotes\"", "back\\slash", "line\nbreak", "semi;colon"] {
+            assert_eq!(parse_value(&format_value(value)), value);
+        }
+    }
+}
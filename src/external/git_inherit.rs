@@ -0,0 +1,174 @@
+//! Git configuration wrapper that relies on inherited config.
+//!
+//! Git reads system, then global, then local configuration, so a repository
+//! inherits every key set at a broader scope. [`InheritingGitWrapper`] skips
+//! writes at its scope whose value is already inherited from a broader one,
+//! e.g. a `user.email` the global config already sets to the same address,
+//! which keeps monorepo setups free of duplicated config.
+
+use super::git::{ConfigOrigin, GitConfigScope, GitWrapper};
+use crate::config::types::KeyType;
+use crate::error::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Skips config writes at `scope` that a broader scope already provides.
+///
+/// Writes at other scopes, such as field-level scopes, and all reads are
+/// passed to the wrapped [`GitWrapper`] unchanged.
+pub struct InheritingGitWrapper {
+    scope: GitConfigScope,
+    inner: Arc<dyn GitWrapper>,
+    verbose: bool,
+    skipped: Mutex<Vec<String>>,
+}
+
+impl InheritingGitWrapper {
+    /// Create a wrapper skipping inherited writes at `scope`, delegating to `inner`.
+    pub fn new(scope: GitConfigScope, inner: Arc<dyn GitWrapper>) -> Self {
+        Self {
+            scope,
+            inner,
+            verbose: false,
+            skipped: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Print each skipped key.
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Keys whose write was skipped, in order.
+    pub fn skipped(&self) -> Vec<String> {
+        self.skipped.lock().unwrap().clone()
+    }
+
+    /// Scopes `scope` inherits from, most specific first.
+    fn broader_scopes(scope: &GitConfigScope) -> &'static [GitConfigScope] {
+        match scope {
+            GitConfigScope::Local => &[GitConfigScope::Global, GitConfigScope::System],
+            GitConfigScope::Global => &[GitConfigScope::System],
+            GitConfigScope::System => &[],
+        }
+    }
+
+    /// The value of `key` inherited at `scope` and the scope it comes from, if any.
+    fn inherited(&self, key: &str, scope: &GitConfigScope) -> Result<Option<(String, GitConfigScope)>> {
+        for broader in Self::broader_scopes(scope) {
+            if let Some(value) = self.inner.get_config(key, Some(broader.clone()))? {
+                return Ok(Some((value, broader.clone())));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl GitWrapper for InheritingGitWrapper {
+    fn get_config(&self, key: &str, scope: Option<GitConfigScope>) -> Result<Option<String>> {
+        self.inner.get_config(key, scope)
+    }
+
+    fn set_config(&self, key: &str, value: &str, scope: GitConfigScope) -> Result<()> {
+        if scope == self.scope
+            && self.inner.get_config(key, Some(scope.clone()))?.is_none()
+            && let Some((inherited, from)) = self.inherited(key, &scope)?
+            && inherited == value
+        {
+            if self.verbose {
+                println!("  Skipped {} (inherited from {:?} config)", key, from);
+            }
+            self.skipped.lock().unwrap().push(key.to_string());
+            return Ok(());
+        }
+        self.inner.set_config(key, value, scope)
+    }
+
+    fn unset_config(&self, key: &str, scope: GitConfigScope) -> Result<()> {
+        self.inner.unset_config(key, scope)
+    }
+
+    fn get_all_config(&self, scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
+        self.inner.get_all_config(scope)
+    }
+
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        self.inner.get_config_with_origin(key)
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        self.inner.get_all_config_with_origin()
+    }
+
+    fn is_git_available(&self) -> Result<bool> {
+        self.inner.is_git_available()
+    }
+
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        self.inner.is_inside_work_tree()
+    }
+
+    fn test_sign(&self, key_type: &KeyType, signing_key: &str) -> Result<bool> {
+        self.inner.test_sign(key_type, signing_key)
+    }
+
+    fn push_config_to_remote(&self, scope: GitConfigScope, remote: &str, keys_whitelist: &[&str]) -> Result<()> {
+        self.inner.push_config_to_remote(scope, remote, keys_whitelist)
+    }
+
+    fn pull_config_from_remote(&self, remote: &str) -> Result<HashMap<String, String>> {
+        self.inner.pull_config_from_remote(remote)
+    }
+
+    fn get_last_commit_note(&self, ref_name: &str) -> Result<Option<String>> {
+        self.inner.get_last_commit_note(ref_name)
+    }
+
+    fn add_commit_note(&self, message: &str, ref_name: &str) -> Result<()> {
+        self.inner.add_commit_note(message, ref_name)
+    }
+
+    fn set_allowed_signers(&self, profile_name: &str, content: &str) -> Result<PathBuf> {
+        self.inner.set_allowed_signers(profile_name, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::git::MockGitWrapper;
+
+    #[test]
+    fn test_skips_inherited_values() {
+        let inner = MockGitWrapper::new()
+            .with_scoped_config(
+                GitConfigScope::Global,
+                HashMap::from([
+                    ("user.email".to_string(), "jane@work.com".to_string()),
+                    ("user.name".to_string(), "Jane".to_string()),
+                ]),
+            )
+            .with_scoped_config(
+                GitConfigScope::System,
+                HashMap::from([("commit.gpgsign".to_string(), "true".to_string())]),
+            );
+        let git = InheritingGitWrapper::new(GitConfigScope::Local, Arc::new(inner.clone()));
+
+        git.set_config("user.email", "jane@work.com", GitConfigScope::Local).unwrap();
+        git.set_config("commit.gpgsign", "true", GitConfigScope::Local).unwrap();
+        git.set_config("user.name", "Jane Doe", GitConfigScope::Local).unwrap();
+        // Writes at other scopes are not affected
+        git.set_config("user.email", "jane@work.com", GitConfigScope::Global).unwrap();
+
+        assert_eq!(git.skipped(), vec!["user.email".to_string(), "commit.gpgsign".to_string()]);
+        assert_eq!(
+            inner.config_writes(),
+            vec![
+                ("user.name".to_string(), Some("Jane Doe".to_string())),
+                ("user.email".to_string(), Some("jane@work.com".to_string())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Print-only git wrapper for git-setup-rs.
+//!
+//! [`PrintOnlyGitWrapper`] implements [`GitWrapper`] by collecting the
+//! `git config` commands an apply would run instead of running them, so CI
+//! scripts that cannot run git-setup themselves can run the commands. Reads
+//! see an empty configuration and nothing is validated.
+
+use super::git::{ConfigOrigin, GitConfigScope, GitWrapper};
+use crate::config::types::KeyType;
+use crate::error::{GitSetupError, Result};
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Script formats the collected commands can be printed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ScriptFormat {
+    /// POSIX shell commands
+    #[default]
+    Sh,
+    /// PowerShell commands
+    Powershell,
+    /// A Makefile target running the commands
+    Make,
+}
+
+/// A `git config` command setting a single key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitConfigCommand {
+    pub key: String,
+    pub value: String,
+    pub scope: GitConfigScope,
+}
+
+impl GitConfigCommand {
+    /// The command line in `format`, with the value quoted for that format.
+    pub fn render(&self, format: ScriptFormat) -> String {
+        let value = match format {
+            ScriptFormat::Sh => sh_quote(&self.value),
+            ScriptFormat::Powershell => format!("'{}'", self.value.replace('\'', "''")),
+            // Make expands `$` before the shell sees the command
+            ScriptFormat::Make => sh_quote(&self.value).replace('$', "$$"),
+        };
+        format!("git config {} {} {}", self.scope.to_git_arg(), self.key, value)
+    }
+}
+
+/// `value` in double quotes, with the characters the shell expands there escaped.
+fn sh_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Git wrapper collecting the config writes as commands instead of running git.
+#[derive(Debug, Default)]
+pub struct PrintOnlyGitWrapper {
+    commands: Mutex<Vec<GitConfigCommand>>,
+}
+
+impl PrintOnlyGitWrapper {
+    /// Create an empty PrintOnlyGitWrapper.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The collected commands, in the order they were issued.
+    pub fn commands(&self) -> Vec<GitConfigCommand> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    /// The collected commands as a script in `format`, one command per line.
+    pub fn render(&self, format: ScriptFormat) -> String {
+        let lines = self.commands().iter().map(|command| command.render(format)).collect::<Vec<_>>();
+        match format {
+            ScriptFormat::Sh | ScriptFormat::Powershell => {
+                lines.iter().map(|line| format!("{}\n", line)).collect()
+            }
+            ScriptFormat::Make => {
+                let mut script = String::from(".PHONY: git-config\ngit-config:\n");
+                for line in lines {
+                    script.push_str(&format!("\t{}\n", line));
+                }
+                script
+            }
+        }
+    }
+
+    fn unsupported(operation: &str) -> GitSetupError {
+        GitSetupError::Git(format!("{} is not possible when only printing git config commands", operation))
+    }
+}
+
+impl GitWrapper for PrintOnlyGitWrapper {
+    fn get_config(&self, _key: &str, _scope: Option<GitConfigScope>) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn set_config(&self, key: &str, value: &str, scope: GitConfigScope) -> Result<()> {
+        self.commands.lock().unwrap().push(GitConfigCommand {
+            key: key.to_string(),
+            value: value.to_string(),
+            scope,
+        });
+        Ok(())
+    }
+
+    fn unset_config(&self, _key: &str, _scope: GitConfigScope) -> Result<()> {
+        // Unsets only clear what a previous profile left behind, and `git config
+        // --unset` fails for keys that are not set, so they are not printed
+        Ok(())
+    }
+
+    fn get_all_config(&self, _scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
+        Ok(HashMap::new())
+    }
+
+    fn get_config_with_origin(&self, _key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        Ok(None)
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        Ok(HashMap::new())
+    }
+
+    fn is_git_available(&self) -> Result<bool> {
+        // Git is run by whoever runs the printed commands
+        Ok(true)
+    }
+
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn test_sign(&self, _key_type: &KeyType, _signing_key: &str) -> Result<bool> {
+        Err(Self::unsupported("Test signing"))
+    }
+
+    fn push_config_to_remote(&self, _scope: GitConfigScope, _remote: &str, _keys_whitelist: &[&str]) -> Result<()> {
+        Err(Self::unsupported("Pushing config to a remote"))
+    }
+
+    fn pull_config_from_remote(&self, _remote: &str) -> Result<HashMap<String, String>> {
+        Err(Self::unsupported("Pulling config from a remote"))
+    }
+
+    fn get_last_commit_note(&self, _ref_name: &str) -> Result<Option<String>> {
+        Err(Self::unsupported("Reading commit notes"))
+    }
+
+    fn add_commit_note(&self, _message: &str, _ref_name: &str) -> Result<()> {
+        Err(Self::unsupported("Adding commit notes"))
+    }
+
+    fn set_allowed_signers(&self, _profile_name: &str, _content: &str) -> Result<PathBuf> {
+        Err(Self::unsupported("Writing an allowed signers file"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrapper() -> PrintOnlyGitWrapper {
+        let git = PrintOnlyGitWrapper::new();
+        git.set_config("user.email", "work@example.com", GitConfigScope::Global).unwrap();
+        git.unset_config("gpg.format", GitConfigScope::Global).unwrap();
+        git.set_config("user.name", "Jane \"JD\" $Doe", GitConfigScope::Local).unwrap();
+        git
+    }
+
+    #[test]
+    fn test_render_sh() {
+        assert_eq!(
+            wrapper().render(ScriptFormat::Sh),
+            "git config --global user.email \"work@example.com\"\ngit config --local user.name \"Jane \\\"JD\\\" \\$Doe\"\n"
+        );
+    }
+
+    #[test]
+    fn test_render_powershell_and_make() {
+        assert_eq!(
+            wrapper().render(ScriptFormat::Powershell),
+            "git config --global user.email 'work@example.com'\ngit config --local user.name 'Jane \"JD\" $Doe'\n"
+        );
+        assert_eq!(
+            wrapper().render(ScriptFormat::Make),
+            ".PHONY: git-config\ngit-config:\n\tgit config --global user.email \"work@example.com\"\n\tgit config --local user.name \"Jane \\\"JD\\\" \\$$Doe\"\n"
+        );
+    }
+}
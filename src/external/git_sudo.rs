@@ -0,0 +1,237 @@
+//! Git configuration wrapper that runs as another user.
+//!
+//! [`SudoGitWrapper`] runs every `git config` operation through
+//! `sudo -n -H -u <username>`, so a bootstrap script running as an admin can
+//! configure git for a service account on a headless server. `-H` sets `HOME`
+//! to the user's, so `--global` reaches their `~/.gitconfig` rather than the
+//! caller's. Operations that do not read or write the user's configuration
+//! are left to the wrapped [`GitWrapper`].
+
+use super::git::{origin_line, split_origin, ConfigOrigin, GitConfigScope, GitWrapper, ALLOWED_SIGNERS_DIR};
+use crate::config::types::KeyType;
+use crate::error::{GitSetupError, Result};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::sync::Arc;
+
+/// Runs git config operations as `username` via `sudo`.
+pub struct SudoGitWrapper {
+    username: String,
+    inner: Arc<dyn GitWrapper>,
+}
+
+impl SudoGitWrapper {
+    /// Create a wrapper running git config as `username`, delegating other operations to `inner`.
+    pub fn new(username: impl Into<String>, inner: Arc<dyn GitWrapper>) -> Self {
+        Self {
+            username: username.into(),
+            inner,
+        }
+    }
+
+    /// The user git config runs as.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Check that `sudo` is available and may run commands as the user without a password.
+    pub fn check_access(&self) -> Result<()> {
+        let output = Command::new("sudo")
+            .args(["-n", "-H", "-u", &self.username, "true"])
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("sudo -n -H -u {} true", self.username),
+                error: e.to_string(),
+            })?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GitSetupError::Git(format!(
+                "Cannot run commands as '{}' without a password (a NOPASSWD sudo rule is required): {}",
+                self.username,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    /// Run `git config` with `args` as the user.
+    fn git_config(&self, args: &[&str]) -> Result<Output> {
+        Command::new("sudo")
+            .args(["-n", "-H", "-u", &self.username, "git", "config"])
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("sudo -n -H -u {} git config {}", self.username, args.join(" ")),
+                error: e.to_string(),
+            })
+    }
+
+    /// Error for a failed `git config` run as the user.
+    fn config_error(&self, action: &str, output: &Output) -> GitSetupError {
+        GitSetupError::Git(format!(
+            "Failed to {} as '{}': {}",
+            action,
+            self.username,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+impl GitWrapper for SudoGitWrapper {
+    fn get_config(&self, key: &str, scope: Option<GitConfigScope>) -> Result<Option<String>> {
+        let mut args = Vec::new();
+        if let Some(scope) = &scope {
+            args.push(scope.to_git_arg());
+        }
+        args.extend(["--get", key]);
+
+        let output = self.git_config(&args)?;
+        if output.status.success() {
+            let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Ok((!value.is_empty()).then_some(value))
+        } else if output.stderr.is_empty() || output.status.code() == Some(1) {
+            // git config returns exit code 1 when key is not found
+            Ok(None)
+        } else {
+            Err(self.config_error(&format!("get config '{}'", key), &output))
+        }
+    }
+
+    fn set_config(&self, key: &str, value: &str, scope: GitConfigScope) -> Result<()> {
+        let output = self.git_config(&[scope.to_git_arg(), key, value])?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(self.config_error(&format!("set config '{}' to '{}'", key, value), &output))
+        }
+    }
+
+    fn unset_config(&self, key: &str, scope: GitConfigScope) -> Result<()> {
+        let output = self.git_config(&[scope.to_git_arg(), "--unset", key])?;
+        // git config --unset returns exit code 5 when key is not found
+        if output.status.success() || output.status.code() == Some(5) {
+            Ok(())
+        } else {
+            Err(self.config_error(&format!("unset config '{}'", key), &output))
+        }
+    }
+
+    fn get_all_config(&self, scope: Option<GitConfigScope>) -> Result<HashMap<String, String>> {
+        let mut args = vec!["--list"];
+        if let Some(scope) = &scope {
+            args.push(scope.to_git_arg());
+        }
+
+        let output = self.git_config(&args)?;
+        if !output.status.success() {
+            return Err(self.config_error("list git config", &output));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect())
+    }
+
+    fn get_config_with_origin(&self, key: &str) -> Result<Option<(String, ConfigOrigin)>> {
+        let output = self.git_config(&["--show-origin", "--get", key])?;
+        if !output.status.success() {
+            if output.stderr.is_empty() || output.status.code() == Some(1) {
+                return Ok(None);
+            }
+            return Err(self.config_error(&format!("get config '{}'", key), &output));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some((file, value)) = stdout.lines().next().and_then(split_origin) else {
+            return Ok(None);
+        };
+        // The user's files may not be readable by us, in which case the line is 0
+        let line = origin_line(&file, key, &mut HashMap::new());
+        Ok(Some((value.to_string(), ConfigOrigin { file, line })))
+    }
+
+    fn get_all_config_with_origin(&self) -> Result<HashMap<String, (String, ConfigOrigin)>> {
+        let output = self.git_config(&["--show-origin", "--list"])?;
+        if !output.status.success() {
+            return Err(self.config_error("list git config", &output));
+        }
+
+        let mut config = HashMap::new();
+        let mut files = HashMap::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some((file, entry)) = split_origin(line)
+                && let Some((key, value)) = entry.split_once('=')
+            {
+                let line = origin_line(&file, key, &mut files);
+                config.insert(key.to_string(), (value.to_string(), ConfigOrigin { file, line }));
+            }
+        }
+        Ok(config)
+    }
+
+    fn is_git_available(&self) -> Result<bool> {
+        self.inner.is_git_available()
+    }
+
+    fn is_inside_work_tree(&self) -> Result<bool> {
+        self.inner.is_inside_work_tree()
+    }
+
+    fn test_sign(&self, key_type: &KeyType, signing_key: &str) -> Result<bool> {
+        self.inner.test_sign(key_type, signing_key)
+    }
+
+    fn push_config_to_remote(&self, scope: GitConfigScope, remote: &str, keys_whitelist: &[&str]) -> Result<()> {
+        self.inner.push_config_to_remote(scope, remote, keys_whitelist)
+    }
+
+    fn pull_config_from_remote(&self, remote: &str) -> Result<HashMap<String, String>> {
+        self.inner.pull_config_from_remote(remote)
+    }
+
+    fn get_last_commit_note(&self, ref_name: &str) -> Result<Option<String>> {
+        self.inner.get_last_commit_note(ref_name)
+    }
+
+    fn add_commit_note(&self, message: &str, ref_name: &str) -> Result<()> {
+        self.inner.add_commit_note(message, ref_name)
+    }
+
+    fn set_allowed_signers(&self, profile_name: &str, content: &str) -> Result<PathBuf> {
+        // Written by the user into their own data directory, so it is theirs to read
+        let script = format!(
+            "dir=\"${{XDG_DATA_HOME:-$HOME/.local/share}}/git-setup/{}\" && mkdir -p \"$dir\" && cat > \"$dir/$1\" && echo \"$dir/$1\"",
+            ALLOWED_SIGNERS_DIR
+        );
+        let mut child = Command::new("sudo")
+            .args(["-n", "-H", "-u", &self.username, "sh", "-c", &script, "sh", profile_name])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("sudo -n -H -u {} sh -c ...", self.username),
+                error: e.to_string(),
+            })?;
+
+        let mut content = content.to_string();
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+        } else {
+            Err(self.config_error(&format!("write the allowed signers file of '{}'", profile_name), &output))
+        }
+    }
+}
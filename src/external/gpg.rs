@@ -5,8 +5,11 @@
 //! implementation that uses std::process::Command to execute gpg commands.
 
 use crate::error::{GitSetupError, Result};
+use chrono::{DateTime, NaiveDate, Utc};
 use std::collections::HashMap;
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Information about a GPG key.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,6 +32,25 @@ pub struct GpgKeyInfo {
     pub trust_level: String,
 }
 
+/// Parse a GPG expiration date, given in seconds since the epoch or as `YYYY-MM-DD`.
+pub fn parse_expiration(date: &str) -> Option<DateTime<Utc>> {
+    if let Ok(seconds) = date.parse::<i64>() {
+        return DateTime::from_timestamp(seconds, 0);
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc())
+}
+
+/// Whole days left until the GPG expiration date `expiration`, negative once it has passed.
+///
+/// Returns `None` if `expiration` cannot be parsed (see [`parse_expiration`]).
+pub fn days_until_expiry(expiration: &str) -> Option<i64> {
+    let remaining = parse_expiration(expiration)? - Utc::now();
+    Some(remaining.num_seconds().div_euclid(24 * 60 * 60))
+}
+
 /// Parameters for generating a new GPG key.
 #[derive(Debug, Clone)]
 pub struct GpgKeyGenParams {
@@ -48,6 +70,24 @@ pub struct GpgKeyGenParams {
     pub passphrase: Option<String>,
 }
 
+/// Digest algorithm used when creating a signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DigestAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// Name of the algorithm as accepted by `gpg --digest-algo`.
+    pub fn as_gpg_arg(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "SHA256",
+            DigestAlgorithm::Sha512 => "SHA512",
+        }
+    }
+}
+
 /// Trait for GPG operations.
 ///
 /// This trait allows for easy testing by providing a mock implementation
@@ -59,6 +99,9 @@ pub trait GpgWrapper {
     /// List only secret (private) keys in the keyring.
     fn list_secret_keys(&self) -> Result<Vec<GpgKeyInfo>>;
 
+    /// List the keys with a user ID containing `email`.
+    fn list_keys_by_email(&self, email: &str) -> Result<Vec<GpgKeyInfo>>;
+
     /// Get information about a specific key by ID or fingerprint.
     fn get_key_info(&self, key_id: &str) -> Result<Option<GpgKeyInfo>>;
 
@@ -92,10 +135,49 @@ pub trait GpgWrapper {
     /// Sign data with a GPG key.
     fn sign_data(&self, data: &str, key_id: &str, passphrase: Option<&str>) -> Result<String>;
 
+    /// Sign data with a GPG key using a specific digest algorithm.
+    ///
+    /// Implementations that cannot choose the digest fall back to `sign_data`.
+    fn sign_data_with_digest(
+        &self,
+        data: &str,
+        key_id: &str,
+        passphrase: Option<&str>,
+        _digest: DigestAlgorithm,
+    ) -> Result<String> {
+        self.sign_data(data, key_id, passphrase)
+    }
+
     /// Verify a GPG signature.
     fn verify_signature(&self, data: &str, signature: &str) -> Result<bool>;
+
+    /// Unlock the secret key `key_id` with `passphrase`, leaving the passphrase
+    /// cached in gpg-agent so later signatures do not prompt for it.
+    fn preset_passphrase(&self, key_id: &str, passphrase: &str) -> Result<()>;
+
+    /// Fingerprint of the primary key that made `signature` of `data`, or None
+    /// if the signature does not verify.
+    fn signature_signer(&self, data: &str, signature: &str) -> Result<Option<String>>;
+
+    /// Encrypt data to the public key `recipient`, returning an ASCII-armored message.
+    fn encrypt_data(&self, data: &str, recipient: &str) -> Result<String>;
+
+    /// Decrypt an ASCII-armored message with a secret key from the keyring.
+    fn decrypt_data(&self, message: &str) -> Result<String>;
+
+    /// Check whether the GPG agent is running and answers requests.
+    fn ping_agent(&self) -> Result<bool>;
+
+    /// Start the GPG agent if it is not running.
+    fn start_agent(&self) -> Result<()>;
+
+    /// Stop the GPG agent.
+    fn stop_agent(&self) -> Result<()>;
 }
 
+/// Status line gpg prints when no key matches a `--list-keys` user ID (`GPG_ERR_NO_PUBKEY`).
+const NO_PUBLIC_KEY_STATUS: &str = "[GNUPG:] ERROR keylist.getkey 9";
+
 /// Real GPG wrapper implementation using std::process::Command.
 pub struct SystemGpgWrapper {
     /// Path to the GPG binary
@@ -123,6 +205,61 @@ impl Default for SystemGpgWrapper {
 }
 
 impl SystemGpgWrapper {
+    /// Run `gpgconf` with `args` and return its trimmed output.
+    fn gpgconf(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("gpgconf")
+            .args(args)
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("gpgconf {}", args.join(" ")),
+                error: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Err(GitSetupError::ExternalCommand {
+                command: format!("gpgconf {}", args.join(" ")),
+                error: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Run gpg with `args`, writing `input` to its stdin, and return its output.
+    fn run_with_input(&self, args: &[&str], input: &str) -> Result<String> {
+        use std::io::Write;
+
+        let command = format!("{} {}", self.gpg_path, args.join(" "));
+        let mut child = Command::new(&self.gpg_path)
+            .args(args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: command.clone(),
+                error: e.to_string(),
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).map_err(|e| GitSetupError::ExternalCommand {
+                command: command.clone(),
+                error: format!("Failed to write data to stdin: {}", e),
+            })?;
+        }
+
+        let output = child.wait_with_output().map_err(|e| GitSetupError::ExternalCommand {
+            command: command.clone(),
+            error: e.to_string(),
+        })?;
+        if !output.status.success() {
+            return Err(GitSetupError::ExternalCommand {
+                command,
+                error: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
     /// Parse GPG key listing output in colon format.
     fn parse_key_listing(&self, output: &str) -> Result<Vec<GpgKeyInfo>> {
         let mut keys = Vec::new();
@@ -202,6 +339,68 @@ impl SystemGpgWrapper {
 
         Ok(keys)
     }
+
+    /// Create a detached signature, optionally forcing the digest algorithm.
+    fn sign(
+        &self,
+        data: &str,
+        key_id: &str,
+        passphrase: Option<&str>,
+        digest: Option<DigestAlgorithm>,
+    ) -> Result<String> {
+        let mut cmd = Command::new(&self.gpg_path);
+        cmd.args(["--armor", "--detach-sign", "--local-user", key_id]);
+
+        if let Some(digest) = digest {
+            cmd.args(["--digest-algo", digest.as_gpg_arg()]);
+        }
+
+        if passphrase.is_some() {
+            cmd.args(["--batch", "--yes", "--pinentry-mode", "loopback", "--passphrase-fd", "0"]);
+        }
+
+        let mut child = cmd
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("{} --armor --detach-sign --local-user {}", self.gpg_path, key_id),
+                error: e.to_string(),
+            })?;
+
+        if let Some(stdin) = child.stdin.as_mut() {
+            use std::io::Write;
+
+            if let Some(passphrase) = passphrase {
+                writeln!(stdin, "{}", passphrase).map_err(|e| GitSetupError::ExternalCommand {
+                    command: format!("{} --sign", self.gpg_path),
+                    error: format!("Failed to write passphrase to stdin: {}", e),
+                })?;
+            }
+
+            stdin.write_all(data.as_bytes()).map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("{} --sign", self.gpg_path),
+                error: format!("Failed to write data to stdin: {}", e),
+            })?;
+        }
+
+        let output = child.wait_with_output().map_err(|e| GitSetupError::ExternalCommand {
+            command: format!("{} --armor --detach-sign --local-user {}", self.gpg_path, key_id),
+            error: e.to_string(),
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GitSetupError::ExternalCommand {
+                command: format!("{} --sign", self.gpg_path),
+                error: stderr.to_string(),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.to_string())
+    }
 }
 
 impl GpgWrapper for SystemGpgWrapper {
@@ -247,6 +446,34 @@ impl GpgWrapper for SystemGpgWrapper {
         self.parse_key_listing(&stdout)
     }
 
+    fn list_keys_by_email(&self, email: &str) -> Result<Vec<GpgKeyInfo>> {
+        // Let gpg match the exact address instead of loading the whole keyring;
+        // `--` keeps an address starting with `-` from being read as an option
+        let user_id = format!("<{}>", email);
+        let output = Command::new(&self.gpg_path)
+            .args(["--status-fd", "2", "--list-keys", "--with-colons", "--fingerprint", "--", &user_id])
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("{} --list-keys --with-colons --fingerprint -- {}", self.gpg_path, user_id),
+                error: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // No matching key is not an error, just an empty listing
+            if stderr.lines().any(|line| line.trim() == NO_PUBLIC_KEY_STATUS) {
+                return Ok(Vec::new());
+            }
+            return Err(GitSetupError::ExternalCommand {
+                command: format!("{} --list-keys --with-colons --fingerprint -- {}", self.gpg_path, user_id),
+                error: stderr.trim().to_string(),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        self.parse_key_listing(&stdout)
+    }
+
     fn get_key_info(&self, key_id: &str) -> Result<Option<GpgKeyInfo>> {
         let output = Command::new(&self.gpg_path)
             .args(&["--list-keys", "--with-colons", "--fingerprint", key_id])
@@ -634,54 +861,17 @@ impl GpgWrapper for SystemGpgWrapper {
     }
 
     fn sign_data(&self, data: &str, key_id: &str, passphrase: Option<&str>) -> Result<String> {
-        let mut cmd = Command::new(&self.gpg_path);
-        cmd.args(&["--armor", "--detach-sign", "--local-user", key_id]);
-
-        if let Some(_) = passphrase {
-            cmd.args(&["--batch", "--yes", "--passphrase-fd", "0"]);
-        }
-
-        let mut child = cmd
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .spawn()
-            .map_err(|e| GitSetupError::ExternalCommand {
-                command: format!("{} --armor --detach-sign --local-user {}", self.gpg_path, key_id),
-                error: e.to_string(),
-            })?;
-
-        if let Some(stdin) = child.stdin.as_mut() {
-            use std::io::Write;
-
-            if let Some(passphrase) = passphrase {
-                writeln!(stdin, "{}", passphrase).map_err(|e| GitSetupError::ExternalCommand {
-                    command: format!("{} --sign", self.gpg_path),
-                    error: format!("Failed to write passphrase to stdin: {}", e),
-                })?;
-            }
-
-            stdin.write_all(data.as_bytes()).map_err(|e| GitSetupError::ExternalCommand {
-                command: format!("{} --sign", self.gpg_path),
-                error: format!("Failed to write data to stdin: {}", e),
-            })?;
-        }
-
-        let output = child.wait_with_output().map_err(|e| GitSetupError::ExternalCommand {
-            command: format!("{} --armor --detach-sign --local-user {}", self.gpg_path, key_id),
-            error: e.to_string(),
-        })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(GitSetupError::ExternalCommand {
-                command: format!("{} --sign", self.gpg_path),
-                error: stderr.to_string(),
-            });
-        }
+        self.sign(data, key_id, passphrase, None)
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(stdout.to_string())
+    fn sign_data_with_digest(
+        &self,
+        data: &str,
+        key_id: &str,
+        passphrase: Option<&str>,
+        digest: DigestAlgorithm,
+    ) -> Result<String> {
+        self.sign(data, key_id, passphrase, Some(digest))
     }
 
     fn verify_signature(&self, data: &str, signature: &str) -> Result<bool> {
@@ -703,8 +893,83 @@ impl GpgWrapper for SystemGpgWrapper {
 
         Ok(output.status.success())
     }
+
+    fn preset_passphrase(&self, key_id: &str, passphrase: &str) -> Result<()> {
+        // gpg-agent caches a passphrase given through the loopback pinentry
+        self.sign("", key_id, Some(passphrase), None).map(|_| ())
+    }
+
+    fn signature_signer(&self, data: &str, signature: &str) -> Result<Option<String>> {
+        use std::io::Write;
+        let mut data_file = tempfile::NamedTempFile::new()?;
+        let mut sig_file = tempfile::NamedTempFile::new()?;
+        data_file.write_all(data.as_bytes())?;
+        sig_file.write_all(signature.as_bytes())?;
+
+        let output = Command::new(&self.gpg_path)
+            .args(["--status-fd", "1", "--verify"])
+            .arg(sig_file.path())
+            .arg(data_file.path())
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: format!("{} --status-fd 1 --verify", self.gpg_path),
+                error: e.to_string(),
+            })?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        // VALIDSIG <fingerprint> <date> <timestamp> <expiry> <version> <reserved>
+        //          <pubkey-algo> <hash-algo> <class> <primary-key-fingerprint>
+        Ok(String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+            let fields: Vec<&str> = line.strip_prefix("[GNUPG:] VALIDSIG ")?.split_whitespace().collect();
+            fields.get(9).or_else(|| fields.first()).map(|fingerprint| fingerprint.to_string())
+        }))
+    }
+
+    fn encrypt_data(&self, data: &str, recipient: &str) -> Result<String> {
+        self.run_with_input(&["--batch", "--armor", "--encrypt", "--recipient", recipient], data)
+    }
+
+    fn decrypt_data(&self, message: &str) -> Result<String> {
+        self.run_with_input(&["--quiet", "--decrypt"], message)
+    }
+
+    fn ping_agent(&self) -> Result<bool> {
+        let socket = self.gpgconf(&["--list-dirs", "agent-socket"])?;
+        if !std::path::Path::new(&socket).exists() {
+            return Ok(false);
+        }
+
+        // --no-autostart so that pinging does not start the agent itself
+        let output = Command::new("gpg-connect-agent")
+            .args(["--no-autostart", "--quiet", "GETINFO version", "/bye"])
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: "gpg-connect-agent --quiet 'GETINFO version' /bye".to_string(),
+                error: e.to_string(),
+            })?;
+
+        Ok(output.status.success() && String::from_utf8_lossy(&output.stdout).contains("OK"))
+    }
+
+    fn start_agent(&self) -> Result<()> {
+        self.gpgconf(&["--launch", "gpg-agent"]).map(|_| ())
+    }
+
+    fn stop_agent(&self) -> Result<()> {
+        self.gpgconf(&["--kill", "gpg-agent"]).map(|_| ())
+    }
 }
 
+/// First line of the messages `MockGpgWrapper::encrypt_data` returns, followed by the recipient.
+const MOCK_MESSAGE_HEADER: &str = "-----BEGIN MOCK PGP MESSAGE-----\n";
+/// Last line of the messages `MockGpgWrapper::encrypt_data` returns.
+const MOCK_MESSAGE_FOOTER: &str = "\n-----END MOCK PGP MESSAGE-----";
+
+/// Line of a mock signature naming the key that made it.
+const MOCK_SIGNATURE_PREFIX: &str = "Mock signature by ";
+
 /// Mock GPG wrapper for testing.
 pub struct MockGpgWrapper {
     /// Mock key data
@@ -713,6 +978,12 @@ pub struct MockGpgWrapper {
     should_fail: HashMap<String, bool>,
     /// Mock return values
     return_values: HashMap<String, String>,
+    /// Key IDs passed to `sign_data`
+    sign_data_calls: Mutex<Vec<String>>,
+    /// Key IDs passed to `preset_passphrase`
+    preset_passphrase_calls: Mutex<Vec<String>>,
+    /// When the agent answers pings, or None while it is stopped
+    agent_ready_at: Mutex<Option<Instant>>,
 }
 
 impl MockGpgWrapper {
@@ -722,9 +993,18 @@ impl MockGpgWrapper {
             keys: Vec::new(),
             should_fail: HashMap::new(),
             return_values: HashMap::new(),
+            sign_data_calls: Mutex::new(Vec::new()),
+            preset_passphrase_calls: Mutex::new(Vec::new()),
+            agent_ready_at: Mutex::new(Some(Instant::now())),
         }
     }
 
+    /// Simulate an agent that only answers pings `millis` milliseconds from now.
+    pub fn with_agent_startup_delay(self, millis: u64) -> Self {
+        *self.agent_ready_at.lock().unwrap() = Some(Instant::now() + Duration::from_millis(millis));
+        self
+    }
+
     /// Add a mock key to the wrapper.
     pub fn add_key(&mut self, key: GpgKeyInfo) {
         self.keys.push(key);
@@ -740,6 +1020,16 @@ impl MockGpgWrapper {
         self.return_values.insert(operation.to_string(), value.to_string());
     }
 
+    /// Get the key IDs passed to `sign_data`.
+    pub fn sign_data_calls(&self) -> Vec<String> {
+        self.sign_data_calls.lock().unwrap().clone()
+    }
+
+    /// Get the key IDs passed to `preset_passphrase`.
+    pub fn preset_passphrase_calls(&self) -> Vec<String> {
+        self.preset_passphrase_calls.lock().unwrap().clone()
+    }
+
     /// Helper to check if an operation should fail.
     fn check_should_fail(&self, operation: &str) -> bool {
         self.should_fail.get(operation).copied().unwrap_or(false)
@@ -779,6 +1069,16 @@ impl GpgWrapper for MockGpgWrapper {
         Ok(self.keys.clone())
     }
 
+    fn list_keys_by_email(&self, email: &str) -> Result<Vec<GpgKeyInfo>> {
+        if self.check_should_fail("list_keys_by_email") {
+            return Err(GitSetupError::ExternalCommand {
+                command: format!("gpg --list-keys {}", email),
+                error: "Mock failure".to_string(),
+            });
+        }
+        Ok(self.keys.iter().filter(|k| k.user_id.contains(email)).cloned().collect())
+    }
+
     fn get_key_info(&self, key_id: &str) -> Result<Option<GpgKeyInfo>> {
         if self.check_should_fail("get_key_info") {
             return Err(GitSetupError::ExternalCommand {
@@ -889,7 +1189,8 @@ impl GpgWrapper for MockGpgWrapper {
         Ok(())
     }
 
-    fn sign_data(&self, _data: &str, _key_id: &str, _passphrase: Option<&str>) -> Result<String> {
+    fn sign_data(&self, _data: &str, key_id: &str, _passphrase: Option<&str>) -> Result<String> {
+        self.sign_data_calls.lock().unwrap().push(key_id.to_string());
         if self.check_should_fail("sign_data") {
             return Err(GitSetupError::ExternalCommand {
                 command: "gpg --sign".to_string(),
@@ -897,7 +1198,7 @@ impl GpgWrapper for MockGpgWrapper {
             });
         }
         Ok(self.get_return_value("sign_data")
-            .unwrap_or_else(|| "-----BEGIN PGP SIGNATURE-----\nMock signature\n-----END PGP SIGNATURE-----".to_string()))
+            .unwrap_or_else(|| format!("-----BEGIN PGP SIGNATURE-----\n{}{}\n-----END PGP SIGNATURE-----", MOCK_SIGNATURE_PREFIX, key_id)))
     }
 
     fn verify_signature(&self, _data: &str, _signature: &str) -> Result<bool> {
@@ -909,6 +1210,92 @@ impl GpgWrapper for MockGpgWrapper {
         }
         Ok(true)
     }
+
+    fn preset_passphrase(&self, key_id: &str, _passphrase: &str) -> Result<()> {
+        if self.check_should_fail("preset_passphrase") {
+            return Err(GitSetupError::ExternalCommand {
+                command: "gpg --pinentry-mode loopback --sign".to_string(),
+                error: "Mock failure".to_string(),
+            });
+        }
+        self.preset_passphrase_calls.lock().unwrap().push(key_id.to_string());
+        Ok(())
+    }
+
+    fn signature_signer(&self, _data: &str, signature: &str) -> Result<Option<String>> {
+        if self.check_should_fail("verify_signature") {
+            return Err(GitSetupError::ExternalCommand {
+                command: "gpg --verify".to_string(),
+                error: "Mock failure".to_string(),
+            });
+        }
+        Ok(signature
+            .lines()
+            .find_map(|line| line.strip_prefix(MOCK_SIGNATURE_PREFIX))
+            .map(str::to_string))
+    }
+
+    fn encrypt_data(&self, data: &str, recipient: &str) -> Result<String> {
+        if self.check_should_fail("encrypt_data") {
+            return Err(GitSetupError::ExternalCommand {
+                command: "gpg --encrypt".to_string(),
+                error: "Mock failure".to_string(),
+            });
+        }
+        Ok(format!("{}{}\n{}{}", MOCK_MESSAGE_HEADER, recipient, data, MOCK_MESSAGE_FOOTER))
+    }
+
+    fn decrypt_data(&self, message: &str) -> Result<String> {
+        let failure = || GitSetupError::ExternalCommand {
+            command: "gpg --decrypt".to_string(),
+            error: "Mock failure".to_string(),
+        };
+        if self.check_should_fail("decrypt_data") {
+            return Err(failure());
+        }
+        message
+            .strip_prefix(MOCK_MESSAGE_HEADER)
+            .and_then(|message| message.strip_suffix(MOCK_MESSAGE_FOOTER))
+            .and_then(|message| message.split_once('\n'))
+            .map(|(_recipient, data)| data.to_string())
+            .ok_or_else(failure)
+    }
+
+    fn ping_agent(&self) -> Result<bool> {
+        if self.check_should_fail("ping_agent") {
+            return Err(GitSetupError::ExternalCommand {
+                command: "gpg-connect-agent".to_string(),
+                error: "Mock failure".to_string(),
+            });
+        }
+        Ok(self
+            .agent_ready_at
+            .lock()
+            .unwrap()
+            .is_some_and(|ready_at| Instant::now() >= ready_at))
+    }
+
+    fn start_agent(&self) -> Result<()> {
+        if self.check_should_fail("start_agent") {
+            return Err(GitSetupError::ExternalCommand {
+                command: "gpgconf --launch gpg-agent".to_string(),
+                error: "Mock failure".to_string(),
+            });
+        }
+        self.agent_ready_at.lock().unwrap().get_or_insert_with(Instant::now);
+        Ok(())
+    }
+
+    fn stop_agent(&self) -> Result<()> {
+        if self.check_should_fail("stop_agent") {
+            return Err(GitSetupError::ExternalCommand {
+                command: "gpgconf --kill gpg-agent".to_string(),
+                error: "Mock failure".to_string(),
+            });
+        }
+        *self.agent_ready_at.lock().unwrap() = None;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -996,6 +1383,34 @@ mod tests {
         }
     }
 
+    /// Test that only "no public key" is an empty listing; other gpg failures are errors.
+    #[cfg(unix)]
+    #[test]
+    fn test_system_gpg_wrapper_list_keys_by_email_failures() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let fake_gpg = |name: &str, script: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            SystemGpgWrapper::with_path(path.to_string_lossy().into_owned())
+        };
+
+        let no_key = fake_gpg(
+            "no-key",
+            "echo 'gpg: error reading key: No public key' >&2; echo '[GNUPG:] ERROR keylist.getkey 9' >&2; exit 2",
+        );
+        assert!(no_key.list_keys_by_email("jane@example.com").unwrap().is_empty());
+
+        let broken = fake_gpg("broken", "echo 'gpg: keydb_search failed: Permission denied' >&2; exit 2");
+        let result = broken.list_keys_by_email("jane@example.com");
+        assert!(matches!(result, Err(GitSetupError::ExternalCommand { error, .. }) if error.contains("Permission denied")));
+
+        let args = fake_gpg("args", "[ \"$6\" = -- ] && [ \"$7\" = '<-jane@example.com>' ] || exit 3");
+        assert!(args.list_keys_by_email("-jane@example.com").unwrap().is_empty());
+    }
+
     #[test]
     fn test_system_gpg_wrapper_import_key_from_file_fails_with_nonexistent_file() {
         let wrapper = SystemGpgWrapper::new();
@@ -1083,6 +1498,28 @@ mod tests {
         assert_eq!(found_key.unwrap(), key);
     }
 
+    #[test]
+    fn test_mock_gpg_wrapper_list_keys_by_email() {
+        let mut wrapper = MockGpgWrapper::new();
+        let key = create_test_key();
+        let mut second = create_test_key();
+        second.key_id = "EF567890".to_string();
+        second.fingerprint = "EF567890EF567890EF567890EF567890EF567890".to_string();
+        let mut other = create_test_key();
+        other.key_id = "11112222".to_string();
+        other.user_id = "Other User <other@example.com>".to_string();
+        wrapper.add_key(key.clone());
+        wrapper.add_key(second.clone());
+        wrapper.add_key(other);
+
+        let keys = wrapper.list_keys_by_email("test@example.com").unwrap();
+        assert_eq!(keys, vec![key, second]);
+        assert!(wrapper.list_keys_by_email("nobody@example.com").unwrap().is_empty());
+
+        wrapper.set_should_fail("list_keys_by_email", true);
+        assert!(wrapper.list_keys_by_email("test@example.com").is_err());
+    }
+
     #[test]
     fn test_mock_gpg_wrapper_get_key_info_not_found() {
         let wrapper = MockGpgWrapper::new();
@@ -1179,6 +1616,43 @@ mod tests {
         assert!(result.unwrap());
     }
 
+    #[test]
+    fn test_mock_gpg_wrapper_agent_lifecycle() {
+        let mock = MockGpgWrapper::new();
+        assert!(mock.ping_agent().unwrap());
+        mock.stop_agent().unwrap();
+        assert!(!mock.ping_agent().unwrap());
+        mock.start_agent().unwrap();
+        assert!(mock.ping_agent().unwrap());
+
+        let slow = MockGpgWrapper::new().with_agent_startup_delay(60_000);
+        assert!(!slow.ping_agent().unwrap());
+    }
+
+    #[test]
+    fn test_parse_expiration() {
+        let at = |timestamp: &str| DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc);
+        assert_eq!(parse_expiration("1711929600"), Some(at("2024-04-01T00:00:00Z")));
+        assert_eq!(parse_expiration("2024-04-01"), Some(at("2024-04-01T00:00:00Z")));
+        assert_eq!(parse_expiration("never"), None);
+    }
+
+    #[test]
+    fn test_days_until_expiry() {
+        // An hour into the day, so the test does not race the day boundary
+        let in_days = |days: i64| {
+            (Utc::now() + chrono::Duration::days(days) + chrono::Duration::hours(1))
+                .timestamp()
+                .to_string()
+        };
+        assert_eq!(days_until_expiry(&in_days(10)), Some(10));
+        assert_eq!(days_until_expiry(&in_days(0)), Some(0));
+        // Expired 23 hours ago
+        assert_eq!(days_until_expiry(&in_days(-1)), Some(-1));
+        assert_eq!(days_until_expiry("2000-01-01").map(|days| days < -9000), Some(true));
+        assert_eq!(days_until_expiry("never"), None);
+    }
+
     #[test]
     fn test_parse_gpg_key_listing() {
         let wrapper = SystemGpgWrapper::new();
@@ -0,0 +1,74 @@
+//! Per-profile git hooks.
+//!
+//! A profile can carry its own directory of git hooks (e.g. a work profile
+//! enforcing lint checks). [`HooksManager`] points git at that directory
+//! through `core.hooksPath` and removes the setting again.
+
+use super::git::{GitConfigScope, GitWrapper};
+use crate::{
+    error::{GitSetupError, Result},
+    platform::{PlatformPaths, SystemPlatform},
+};
+use std::path::{Path, PathBuf};
+
+/// Git config key selecting the hooks directory.
+pub const HOOKS_PATH_KEY: &str = "core.hooksPath";
+
+/// Installs and uninstalls profile hook directories.
+pub struct HooksManager;
+
+impl HooksManager {
+    /// Set `core.hooksPath` at `scope` to `hooks_dir`, with `~` expanded.
+    ///
+    /// # Errors
+    /// Returns an error if `hooks_dir` is not a directory or git config fails
+    pub fn install(hooks_dir: &Path, scope: GitConfigScope, git: &dyn GitWrapper) -> Result<()> {
+        let hooks_dir = Self::expand(hooks_dir);
+        if !hooks_dir.is_dir() {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("git hooks directory '{}' does not exist", hooks_dir.display()),
+            });
+        }
+        git.set_config(HOOKS_PATH_KEY, &hooks_dir.to_string_lossy(), scope)
+    }
+
+    /// Unset `core.hooksPath` at `scope`, so git uses the repository's own hooks again.
+    pub fn uninstall(scope: GitConfigScope, git: &dyn GitWrapper) -> Result<()> {
+        git.unset_config(HOOKS_PATH_KEY, scope)
+    }
+
+    /// `hooks_dir` with a leading `~` replaced by the home directory
+    fn expand(hooks_dir: &Path) -> PathBuf {
+        PathBuf::from(SystemPlatform.expand_path(&hooks_dir.to_string_lossy()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::git::MockGitWrapper;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_and_uninstall() {
+        let dir = TempDir::new().unwrap();
+        let git = MockGitWrapper::new();
+
+        HooksManager::install(dir.path(), GitConfigScope::Local, &git).unwrap();
+        assert_eq!(
+            git.get_config(HOOKS_PATH_KEY, Some(GitConfigScope::Local)).unwrap(),
+            Some(dir.path().to_string_lossy().to_string())
+        );
+
+        HooksManager::uninstall(GitConfigScope::Local, &git).unwrap();
+        assert_eq!(git.get_config(HOOKS_PATH_KEY, Some(GitConfigScope::Local)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_install_missing_directory() {
+        let git = MockGitWrapper::new();
+        let result = HooksManager::install(Path::new("/nonexistent/hooks"), GitConfigScope::Global, &git);
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        assert!(git.config_writes().is_empty());
+    }
+}
@@ -1,10 +1,29 @@
+pub mod bitwarden;
 pub mod git;
+pub mod git_dry_run;
+pub mod git_file;
+pub mod git_inherit;
+pub mod git_print;
+pub mod git_sudo;
 pub mod gpg;
+pub mod hooks;
 pub mod onepassword;
+pub mod ssh;
 
-pub use git::{GitConfigScope, GitWrapper, MockGitWrapper, SystemGitWrapper};
+pub use bitwarden::{BitwardenSshKey, BitwardenWrapper, MockBitwardenWrapper, SystemBitwardenWrapper};
+pub use git::{ConfigOrigin, GitConfigCall, GitConfigScope, GitWrapper, MockGitWrapper, SigningInfo, SystemGitWrapper};
+pub use git_dry_run::DryRunGitWrapper;
+pub use git_file::FilesystemGitWrapper;
+pub use git_inherit::InheritingGitWrapper;
+pub use git_print::PrintOnlyGitWrapper;
+pub use git_sudo::SudoGitWrapper;
+pub use hooks::HooksManager;
 pub use gpg::{GpgKeyGenParams, GpgKeyInfo, GpgWrapper, MockGpgWrapper, SystemGpgWrapper};
 pub use onepassword::{
     GpgItemTemplate, GpgKeyItem, MockOnePasswordWrapper, OnePasswordWrapper, SshKeyItem,
     SystemOnePasswordWrapper, Vault,
 };
+pub use ssh::{
+    MockSshKeyGenerator, SshKeyAlgorithm, SshKeyGenParams, SshKeyGenerator, SshKeyPair, SshKeyType,
+    SystemSshKeyGenerator,
+};
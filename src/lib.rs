@@ -9,10 +9,11 @@ pub mod output;
 pub mod platform;
 pub mod profile;
 pub mod tui;
+pub mod util;
 
 pub use cli::{Args, OutputFormat};
 pub use detection::{AutoDetector, DetectionConfig, DetectionResult, ProfileDetector, RepositoryContext};
-pub use error::{GitSetupError, Result};
+pub use error::{ErrorContext, GitSetupError, Result, ResultExt};
 pub use matching::{FuzzyMatcher, ProfileFuzzyMatcher, MatchResult, MatchedField, FieldMatch, MatchingAlgorithm};
 pub use output::{JsonFormatter, OutputFormatter};
 pub use platform::{PlatformPaths, SystemPlatform};
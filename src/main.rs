@@ -1,29 +1,93 @@
 use clap::Parser;
-use git_setup_rs::{Args, Result};
+use git_setup_rs::config::loader::ConfigLoaderTrait;
+use git_setup_rs::external::onepassword::{
+    OnePasswordWrapper, RateLimitedOnePasswordWrapper, SystemOnePasswordWrapper,
+};
+use git_setup_rs::{Args, GitSetupError, Result};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let args = Args::parse();
 
+    if let Err(error) = run(&args).await {
+        print_error(&error);
+        std::process::exit(1);
+    }
+}
+
+/// Print `error` followed by the operations it interrupted, innermost first.
+fn print_error(error: &GitSetupError) {
+    eprintln!("Error: {}", error.root_cause());
+    if let GitSetupError::Context(context) = error {
+        for operation in context.chain().iter().rev() {
+            eprintln!("  {}", operation);
+        }
+    }
+}
+
+async fn run(args: &Args) -> Result<()> {
     // Create dependencies
-    let profile_manager = Arc::new(git_setup_rs::profile::manager::ProfileManagerImpl::new());
+    let tool_config = git_setup_rs::config::loader::ConfigLoader::from_platform_default()?
+        .load()?
+        .tool
+        .effective()?;
+    // Hooks that can only warn have nothing to say under --quiet
+    let validation_hooks = if args.skip_hooks {
+        Vec::new()
+    } else {
+        let mut hooks = tool_config.validation_hooks.clone();
+        hooks.retain(|hook| hook.fail_on_nonzero || !args.quiet);
+        hooks
+    };
+    let profile_manager = Arc::new(
+        git_setup_rs::profile::manager::ProfileManagerImpl::new()
+            .with_history_file(git_setup_rs::profile::undo::UndoRedoStack::default_path()?)?
+            .with_activation_file(git_setup_rs::profile::activation::ActivationHistory::default_path()?)?
+            .with_validation_hooks(validation_hooks),
+    );
     let git_wrapper = Arc::new(git_setup_rs::external::git::SystemGitWrapper::new());
-    let onepassword_wrapper = Arc::new(git_setup_rs::external::onepassword::SystemOnePasswordWrapper::new());
+    let onepassword_wrapper: Arc<dyn OnePasswordWrapper> = match tool_config.op_rate_limit_calls_per_minute {
+        Some(calls_per_minute) => Arc::new(RateLimitedOnePasswordWrapper::new(
+            SystemOnePasswordWrapper::new(),
+            calls_per_minute,
+        )),
+        None => Arc::new(SystemOnePasswordWrapper::new()),
+    };
+    let bitwarden_wrapper = Arc::new(git_setup_rs::external::bitwarden::SystemBitwardenWrapper::new());
     let gpg_wrapper = Arc::new(git_setup_rs::external::gpg::SystemGpgWrapper::new());
-    let profile_detector = Arc::new(git_setup_rs::detection::AutoDetector::new());
+    let profile_detector = Arc::new(git_setup_rs::detection::AutoDetector::new(
+        profile_manager.clone(),
+        git_wrapper.clone(),
+    )?);
     let fuzzy_matcher = Arc::new(git_setup_rs::matching::ProfileFuzzyMatcher::new());
 
+    // Let --watch and --import finish their current write on Ctrl-C; a second Ctrl-C exits at once
+    let cancellation_token = CancellationToken::new();
+    if args.watch || args.import {
+        let token = cancellation_token.clone();
+        ctrlc::set_handler(move || {
+            if token.is_cancelled() {
+                std::process::exit(130);
+            }
+            token.cancel();
+        })
+        .map_err(|e| GitSetupError::Git(format!("Cannot install the Ctrl-C handler: {}", e)))?;
+    }
+
     // Create command handler
     let handler = git_setup_rs::commands::handlers::CommandHandlerBuilder::new()
         .with_profile_manager(profile_manager)
         .with_git_wrapper(git_wrapper)
         .with_onepassword_wrapper(onepassword_wrapper)
+        .with_bitwarden_wrapper(bitwarden_wrapper)
         .with_gpg_wrapper(gpg_wrapper)
         .with_profile_detector(profile_detector)
         .with_fuzzy_matcher(fuzzy_matcher)
+        .with_cancellation_token(cancellation_token)
         .build()?;
 
     // Execute the command
-    handler.execute(&args).await
+    handler.execute(args).await
 }
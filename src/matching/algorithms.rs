@@ -1,10 +1,11 @@
 //! Matching algorithms for fuzzy string comparison.
 //!
 //! This module implements various string matching algorithms including
-//! Levenshtein distance, substring matching, and fuzzy matching with
-//! character skipping support.
+//! Levenshtein distance, substring matching, trigram similarity and fuzzy
+//! matching with character skipping support.
 
 use super::MatchingAlgorithm;
+use std::collections::HashSet;
 
 /// Levenshtein distance-based matching algorithm.
 ///
@@ -32,8 +33,9 @@ impl MatchingAlgorithm for LevenshteinMatcher {
         }
 
         // Calculate Levenshtein distance
+        // Lowercasing can change the number of characters (e.g. 'İ'), so measure the compared strings
         let distance = levenshtein_distance(&query_lower, &target_lower);
-        let max_len = query.chars().count().max(target.chars().count()) as f64;
+        let max_len = query_lower.chars().count().max(target_lower.chars().count()) as f64;
 
         // Convert distance to similarity score (0.0 to 1.0)
         if max_len == 0.0 {
@@ -145,6 +147,67 @@ impl MatchingAlgorithm for FuzzyAlgorithm {
     }
 }
 
+/// Trigram (3-gram) similarity matching algorithm.
+///
+/// Scores the Jaccard similarity of the sets of 3-character substrings of
+/// query and target. A typo only affects the few trigrams around it, so
+/// misspelled email addresses still share most of their trigrams with the
+/// correct one, however long the rest of the address is.
+#[derive(Debug, Default)]
+pub struct TrigramAlgorithm;
+
+impl MatchingAlgorithm for TrigramAlgorithm {
+    fn score(&self, query: &str, target: &str) -> f64 {
+        if query.is_empty() && target.is_empty() {
+            return 1.0;
+        }
+
+        if query.is_empty() || target.is_empty() {
+            return 0.0;
+        }
+
+        let query_lower = query.to_lowercase();
+        let target_lower = target.to_lowercase();
+
+        // Exact match gets perfect score
+        if query_lower == target_lower {
+            return 1.0;
+        }
+
+        let query_trigrams = trigrams(&query_lower);
+        let target_trigrams = trigrams(&target_lower);
+        let shared = query_trigrams.intersection(&target_trigrams).count();
+        let total = query_trigrams.union(&target_trigrams).count();
+        shared as f64 / total as f64
+    }
+
+    fn name(&self) -> &'static str {
+        "trigram"
+    }
+}
+
+/// One instance of every matching algorithm, e.g. to select one by name.
+pub fn available_algorithms() -> Vec<Box<dyn MatchingAlgorithm>> {
+    vec![
+        Box::new(FuzzyAlgorithm),
+        Box::new(SubstringMatcher),
+        Box::new(LevenshteinMatcher),
+        Box::new(TrigramAlgorithm),
+    ]
+}
+
+/// The set of 3-character substrings of `s`.
+///
+/// Strings shorter than three characters are their own only trigram, so they
+/// still match themselves.
+fn trigrams(s: &str) -> HashSet<String> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() < 3 {
+        return HashSet::from([s.to_string()]);
+    }
+    chars.windows(3).map(|window| window.iter().collect()).collect()
+}
+
 /// Calculate Levenshtein distance between two strings.
 ///
 /// Uses dynamic programming to compute the minimum number of single-character
@@ -348,6 +411,19 @@ mod tests {
             assert_eq!(matcher.name(), "levenshtein");
         }
 
+        #[test]
+        fn test_multi_byte_characters() {
+            let matcher = LevenshteinMatcher;
+            // One substitution in three characters, counted per character rather than per byte
+            let score = matcher.score("日本語", "日本人");
+            assert!((score - 2.0 / 3.0).abs() < f64::EPSILON, "got {}", score);
+            assert_eq!(matcher.score("🔑 work", "🔑 work"), 1.0);
+
+            // 'İ' lowercases to two characters; the score must stay within range
+            let score = matcher.score("İstanbul", "istanbul");
+            assert!((0.0..=1.0).contains(&score), "got {}", score);
+        }
+
         #[test]
         fn test_different_lengths() {
             let matcher = LevenshteinMatcher;
@@ -482,9 +558,66 @@ mod tests {
         }
     }
 
+    mod trigram_tests {
+        use super::*;
+
+        #[test]
+        fn test_exact_match() {
+            let matcher = TrigramAlgorithm;
+            assert_eq!(matcher.score("test", "test"), 1.0);
+            assert_eq!(matcher.score("Work", "work"), 1.0);
+            assert_eq!(matcher.score("ab", "AB"), 1.0);
+        }
+
+        #[test]
+        fn test_empty_strings() {
+            let matcher = TrigramAlgorithm;
+            assert_eq!(matcher.score("", ""), 1.0);
+            assert_eq!(matcher.score("", "test"), 0.0);
+            assert_eq!(matcher.score("test", ""), 0.0);
+        }
+
+        #[test]
+        fn test_jaccard_similarity() {
+            let matcher = TrigramAlgorithm;
+            // {wor, ork} shared out of {wor, ork, rks, ksp, spa, pac, ace}
+            assert!((matcher.score("work", "workspace") - 2.0 / 7.0).abs() < f64::EPSILON);
+            assert_eq!(matcher.score("abc", "xyz"), 0.0);
+            assert_eq!(matcher.score("ab", "abc"), 0.0);
+        }
+
+        #[test]
+        fn test_misspelled_email() {
+            let matcher = TrigramAlgorithm;
+            let score = matcher.score("jnae.doe@example.com", "jane.doe@example.com");
+            assert!(score > 0.7, "A transposition should keep most trigrams, got {}", score);
+            let score = matcher.score("jnae.doe@example.com", "john@personal.com");
+            assert!(score < 0.1, "Different addresses should share few trigrams, got {}", score);
+        }
+
+        #[test]
+        fn test_multi_byte_characters() {
+            let matcher = TrigramAlgorithm;
+            assert_eq!(matcher.score("日本語です", "日本語だよ"), 1.0 / 5.0);
+            assert_eq!(trigrams("café").len(), 2);
+        }
+
+        #[test]
+        fn test_algorithm_name() {
+            let matcher = TrigramAlgorithm;
+            assert_eq!(matcher.name(), "trigram");
+        }
+    }
+
     mod helper_function_tests {
         use super::*;
 
+        #[test]
+        fn test_available_algorithms() {
+            let names: Vec<&str> = available_algorithms().iter().map(|algorithm| algorithm.name()).collect();
+            assert_eq!(names, vec!["fuzzy", "substring", "levenshtein", "trigram"]);
+        }
+
         #[test]
         fn test_levenshtein_distance_basic() {
             assert_eq!(levenshtein_distance("", ""), 0);
@@ -5,7 +5,7 @@
 
 use super::{
     MatchingAlgorithm, FuzzyMatcher as FuzzyMatcherTrait, MatchResult, FieldMatch, MatchedField,
-    LevenshteinMatcher, SubstringMatcher, FuzzyAlgorithm,
+    LevenshteinMatcher, SubstringMatcher, FuzzyAlgorithm, available_algorithms,
 };
 use crate::config::types::Profile;
 use std::sync::Arc;
@@ -81,6 +81,21 @@ impl ProfileFuzzyMatcher {
         }
     }
 
+    /// Create a fuzzy matcher scoring only with the algorithm named `name`
+    /// (`fuzzy`, `substring`, `levenshtein` or `trigram`), without fallbacks.
+    ///
+    /// Returns `None` if there is no algorithm with that name.
+    pub fn with_algorithm(name: &str) -> Option<Self> {
+        let algorithm = available_algorithms()
+            .into_iter()
+            .find(|algorithm| algorithm.name() == name)?;
+        Some(Self {
+            config: MatchConfig::default(),
+            primary_algorithm: Arc::from(algorithm),
+            fallback_algorithms: Vec::new(),
+        })
+    }
+
     /// Set the primary matching algorithm.
     pub fn with_primary_algorithm(mut self, algorithm: Arc<dyn MatchingAlgorithm>) -> Self {
         self.primary_algorithm = algorithm;
@@ -277,6 +292,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "personal".to_string(),
@@ -295,6 +319,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "opensource".to_string(),
@@ -313,6 +346,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ]
     }
@@ -492,6 +534,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
         let results = matcher.find_matches("test", &[profile]);
@@ -535,6 +586,33 @@ mod tests {
         assert_eq!(results[0].algorithm, "levenshtein");
     }
 
+    #[test]
+    fn test_with_algorithm() {
+        let matcher = ProfileFuzzyMatcher::with_algorithm("levenshtein").unwrap();
+        let profiles = create_test_profiles();
+
+        let results = matcher.find_matches("persnal", &profiles);
+        assert!(!results.is_empty(), "Should match a misspelled name");
+        assert_eq!(results[0].algorithm, "levenshtein");
+        assert!(ProfileFuzzyMatcher::with_algorithm("soundex").is_none());
+    }
+
+    #[test]
+    fn test_with_trigram_algorithm() {
+        let matcher = ProfileFuzzyMatcher::with_algorithm("trigram")
+            .unwrap()
+            .with_updated_config(|config| {
+                config.match_name = false;
+                config.match_email = true;
+            });
+        let profiles = create_test_profiles();
+
+        let results = matcher.find_matches("jonh.doe@company.com", &profiles);
+        assert_eq!(results.len(), 1, "Only the misspelled address should match");
+        assert_eq!(results[0].profile.name, "work-project");
+        assert_eq!(results[0].algorithm, "trigram");
+    }
+
     #[test]
     fn test_config_builder_pattern() {
         let matcher = ProfileFuzzyMatcher::new()
@@ -49,6 +49,11 @@ pub trait FuzzyMatcher: Send + Sync {
     /// # Returns
     /// The best match if score is above high-confidence threshold, None otherwise
     fn find_best_match(&self, query: &str, profiles: &[Profile]) -> Option<MatchResult>;
+
+    /// Explain how `result` was scored, one line per matched field.
+    fn explain(&self, result: &MatchResult) -> String {
+        result.explain()
+    }
 }
 
 /// Mock implementation of FuzzyMatcher for testing
@@ -132,6 +137,17 @@ impl MatchedField {
         }
     }
 
+    /// Get the value of this field in `profile`, if set.
+    pub fn value<'a>(&self, profile: &'a Profile) -> Option<&'a str> {
+        match self {
+            MatchedField::Name => Some(&profile.name),
+            MatchedField::Email => Some(&profile.git_user_email),
+            MatchedField::UserName => profile.git_user_name.as_deref(),
+            MatchedField::VaultName => profile.vault_name.as_deref(),
+            MatchedField::SshKeyTitle => profile.ssh_key_title.as_deref(),
+        }
+    }
+
     /// Get the field weight for scoring (higher weight = more important).
     pub fn weight(&self) -> f64 {
         match self {
@@ -176,6 +192,41 @@ impl MatchResult {
     pub fn is_exact(&self) -> bool {
         self.score >= 0.99
     }
+
+    /// Describe how the score was reached, for debugging surprising rankings.
+    ///
+    /// The first line gives the overall score and algorithm, followed by one
+    /// line per matched field with its score, weight and weighted score.
+    pub fn explain(&self) -> String {
+        let mut lines = vec![format!("Score: {:.2} via '{}' algorithm", self.score, self.algorithm)];
+        for field_match in &self.field_matches {
+            let label = field_match.field.display_name();
+            let mut chars = label.chars();
+            let label: String = chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars).collect();
+            lines.push(format!(
+                "  {} '{}' matched with score {:.2} (weight {:.1}, weighted={:.2})",
+                label,
+                field_match.field.value(&self.profile).unwrap_or_default(),
+                field_match.score,
+                field_match.field.weight(),
+                field_match.weighted_score()
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Describe the match on one line, e.g. `personal (0.87, via name match)`.
+    pub fn explain_compact(&self) -> String {
+        match self.primary_field() {
+            Some(field_match) => format!(
+                "{} ({:.2}, via {} match)",
+                self.profile.name,
+                self.score,
+                field_match.field.display_name()
+            ),
+            None => format!("{} ({:.2})", self.profile.name, self.score),
+        }
+    }
 }
 
 impl FieldMatch {
@@ -218,6 +269,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -302,6 +362,33 @@ mod tests {
         assert_eq!(primary.score, 0.9);
     }
 
+    #[test]
+    fn test_match_result_explain() {
+        let profile = create_test_profile("personal");
+        let match_result = MatchResult::new(
+            profile,
+            0.87,
+            "JaroWinkler".to_string(),
+            vec![
+                FieldMatch::new(MatchedField::Name, 0.92, None),
+                FieldMatch::new(MatchedField::Email, 0.7, None),
+            ],
+        );
+
+        assert_eq!(
+            match_result.explain(),
+            "Score: 0.87 via 'JaroWinkler' algorithm\n  \
+             Name 'personal' matched with score 0.92 (weight 1.0, weighted=0.92)\n  \
+             Email 'personal@example.com' matched with score 0.70 (weight 0.6, weighted=0.42)"
+        );
+        assert_eq!(match_result.explain_compact(), "personal (0.87, via name match)");
+        assert_eq!(MockFuzzyMatcher::new().explain(&match_result), match_result.explain());
+
+        let no_fields = MatchResult::new(create_test_profile("work"), 0.5, "test".to_string(), vec![]);
+        assert_eq!(no_fields.explain(), "Score: 0.50 via 'test' algorithm");
+        assert_eq!(no_fields.explain_compact(), "work (0.50)");
+    }
+
     #[test]
     fn test_create_test_profile() {
         let profile = create_test_profile("work");
@@ -44,6 +44,7 @@
 use crate::config::types::Profile;
 use crate::error::{GitSetupError, Result};
 use crate::output::OutputFormatter;
+use crate::profile::activation::ActivationRecord;
 
 /// CSV formatter implementation that outputs comma-separated values.
 #[derive(Debug, Default)]
@@ -54,15 +55,33 @@ impl CsvFormatter {
     pub fn new() -> Self {
         Self
     }
-}
 
-impl OutputFormatter for CsvFormatter {
-    fn format_profiles(&self, profiles: &[Profile]) -> Result<String> {
+    /// Write a header row followed by `records` as CSV.
+    pub fn write_records<I>(header: &[&str], records: I) -> Result<String>
+    where
+        I: IntoIterator<Item = Vec<String>>,
+    {
         let mut output = Vec::new();
         let mut writer = csv::Writer::from_writer(&mut output);
 
-        // Write header row with all Profile fields
-        writer.write_record(&[
+        writer.write_record(header)?;
+        for record in records {
+            writer.write_record(&record)?;
+        }
+
+        writer.flush()?;
+        drop(writer);
+
+        String::from_utf8(output).map_err(|e| GitSetupError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("CSV output contains invalid UTF-8: {}", e)
+        )))
+    }
+
+    /// Write a header row and one record per profile, with optional extra columns.
+    fn write_csv(&self, profiles: &[&Profile], extra_headers: &[&str], extra_values: &[Vec<String>]) -> Result<String> {
+        // Header row with all Profile fields
+        let mut header = vec![
             "name",
             "git_user_name",
             "git_user_email",
@@ -79,11 +98,12 @@ impl OutputFormatter for CsvFormatter {
             "include_if_dirs",
             "host_patterns",
             "one_password"
-        ])?;
+        ];
+        header.extend_from_slice(extra_headers);
 
-        // Write data rows
-        for profile in profiles {
-            let record = vec![
+        // Data rows
+        let records = profiles.iter().enumerate().map(|(i, profile)| {
+            let mut record = vec![
                 profile.name.clone(),
                 profile.git_user_name.as_deref().unwrap_or("").to_string(),
                 profile.git_user_email.clone(),
@@ -101,16 +121,34 @@ impl OutputFormatter for CsvFormatter {
                 profile.host_patterns.join(";"),
                 profile.one_password.to_string(),
             ];
-            writer.write_record(&record)?;
-        }
+            if let Some(extra) = extra_values.get(i) {
+                record.extend(extra.iter().cloned());
+            }
+            record
+        });
 
-        writer.flush()?;
-        drop(writer);
+        Self::write_records(&header, records)
+    }
+}
 
-        String::from_utf8(output).map_err(|e| GitSetupError::Io(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("CSV output contains invalid UTF-8: {}", e)
-        )))
+impl OutputFormatter for CsvFormatter {
+    fn format_profiles(&self, profiles: &[Profile]) -> Result<String> {
+        let profiles: Vec<&Profile> = profiles.iter().collect();
+        self.write_csv(&profiles, &[], &[])
+    }
+
+    fn format_profiles_with_activations(&self, profiles: &[(Profile, Option<ActivationRecord>)]) -> Result<String> {
+        let (profiles, activations): (Vec<&Profile>, Vec<Vec<String>>) = profiles
+            .iter()
+            .map(|(profile, activation)| {
+                let last_activated = activation
+                    .as_ref()
+                    .map(|record| record.activated_at.to_rfc3339())
+                    .unwrap_or_default();
+                (profile, vec![last_activated])
+            })
+            .unzip();
+        self.write_csv(&profiles, &["last_activated"], &activations)
     }
 }
 
@@ -139,6 +177,15 @@ mod tests {
                 include_if_dirs: vec!["/work/projects".to_string()],
                 host_patterns: vec!["github.com".to_string(), "*.company.com".to_string()],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "personal".to_string(),
@@ -157,6 +204,15 @@ mod tests {
                 include_if_dirs: vec!["/home/user/personal".to_string()],
                 host_patterns: vec!["github.com".to_string()],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ]
     }
@@ -180,6 +236,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -202,6 +267,15 @@ mod tests {
             include_if_dirs: vec!["/path/with spaces".to_string()],
             host_patterns: vec!["*.example,com".to_string()],
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -388,6 +462,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gpg_profile".to_string(),
@@ -406,6 +489,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "x509_profile".to_string(),
@@ -424,6 +516,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gitsign_profile".to_string(),
@@ -442,6 +543,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -479,6 +589,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "global_scope".to_string(),
@@ -497,6 +616,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "system_scope".to_string(),
@@ -515,6 +643,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -551,6 +688,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "authorizedkeys_source".to_string(),
@@ -569,6 +715,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "file_source".to_string(),
@@ -587,6 +742,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -721,4 +885,18 @@ mod tests {
         assert_eq!(record.get(1).unwrap(), "User with \"quotes\"");
         assert!(record.get(4).unwrap().contains("newline"));
     }
+
+    /// Test that the activation date is written as an extra last column.
+    #[test]
+    fn test_format_profiles_with_activations() {
+        let record = ActivationRecord::now("minimal", None);
+        let rows = vec![(minimal_profile(), Some(record.clone())), (minimal_profile(), None)];
+
+        let csv = CsvFormatter::new().format_profiles_with_activations(&rows).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert!(lines[0].ends_with(",one_password,last_activated"));
+        assert!(lines[1].ends_with(&format!(",{}", record.activated_at.to_rfc3339())));
+        assert!(lines[2].ends_with(','));
+    }
 }
@@ -0,0 +1,108 @@
+//! Diff output formatting for profile comparisons.
+//!
+//! This module renders the [`FieldDiff`]s of two profiles as a side-by-side
+//! table with the old values in red and the new ones in green, or as a JSON
+//! array of `{field, old, new}` objects for scripts.
+
+use crate::error::Result;
+use crate::profile::diff::FieldDiff;
+use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color, ContentArrangement, Table};
+
+/// Formatter for the field differences between two profiles.
+#[derive(Debug, Default)]
+pub struct DiffFormatter;
+
+impl DiffFormatter {
+    /// Create a new DiffFormatter instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Table of the changed fields with the values of `old_label` and `new_label` side by side.
+    ///
+    /// Values are coloured when `color` is set, regardless of the terminal.
+    pub fn format_table(&self, diffs: &[FieldDiff], old_label: &str, new_label: &str, color: bool) -> String {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec![
+                Cell::new("Field").add_attribute(Attribute::Bold),
+                Cell::new(old_label).add_attribute(Attribute::Bold),
+                Cell::new(new_label).add_attribute(Attribute::Bold),
+            ]);
+        if color {
+            table.enforce_styling();
+        } else {
+            table.force_no_tty();
+        }
+
+        for diff in diffs {
+            table.add_row(vec![
+                Cell::new(&diff.field),
+                Cell::new(&diff.old).fg(Color::Red),
+                Cell::new(&diff.new).fg(Color::Green),
+            ]);
+        }
+        table.to_string()
+    }
+
+    /// The changed fields as a pretty-printed JSON array of `{field, old, new}` objects.
+    pub fn format_json(&self, diffs: &[FieldDiff]) -> Result<String> {
+        Ok(serde_json::to_string_pretty(diffs)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diffs() -> Vec<FieldDiff> {
+        vec![
+            FieldDiff {
+                field: "key_type".to_string(),
+                old: "ssh".to_string(),
+                new: "gpg".to_string(),
+            },
+            FieldDiff {
+                field: "signing_key".to_string(),
+                old: "~/.ssh/id_work".to_string(),
+                new: String::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_table() {
+        let output = DiffFormatter::new().format_table(&diffs(), "work", "work-eu", false);
+        assert_eq!(
+            output,
+            "\
+┌─────────────┬────────────────┬─────────┐
+│ Field       ┆ work           ┆ work-eu │
+╞═════════════╪════════════════╪═════════╡
+│ key_type    ┆ ssh            ┆ gpg     │
+├╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┤
+│ signing_key ┆ ~/.ssh/id_work ┆         │
+└─────────────┴────────────────┴─────────┘"
+        );
+
+        let colored = DiffFormatter::new().format_table(&diffs(), "work", "work-eu", true);
+        assert!(colored.contains("\x1b[38;5;9m ssh "));
+        assert!(colored.contains("\x1b[38;5;10m gpg "));
+    }
+
+    #[test]
+    fn test_format_json() {
+        let output = DiffFormatter::new().format_json(&diffs()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                {"field": "key_type", "old": "ssh", "new": "gpg"},
+                {"field": "signing_key", "old": "~/.ssh/id_work", "new": ""},
+            ])
+        );
+        assert_eq!(DiffFormatter::new().format_json(&[]).unwrap(), "[]");
+    }
+}
@@ -42,6 +42,7 @@
 
 use crate::config::types::Profile;
 use crate::error::{GitSetupError, Result};
+use crate::profile::activation::ActivationRecord;
 use serde_json;
 
 /// Trait for formatting profile data into different output formats.
@@ -55,6 +56,33 @@ pub trait OutputFormatter {
     /// * `Ok(String)` - The formatted output
     /// * `Err(GitSetupError)` - If serialization fails
     fn format_profiles(&self, profiles: &[Profile]) -> Result<String>;
+
+    /// Format profiles together with their most recent activation.
+    ///
+    /// Used by `list --since/--until`; a `last_activated` field is added to
+    /// every profile, empty for profiles that were never applied.
+    fn format_profiles_with_activations(&self, profiles: &[(Profile, Option<ActivationRecord>)]) -> Result<String>;
+}
+
+/// Serialize profiles as JSON objects with an added `last_activated` timestamp.
+pub(crate) fn profiles_with_last_activated(
+    profiles: &[(Profile, Option<ActivationRecord>)],
+) -> Result<Vec<serde_json::Value>> {
+    profiles
+        .iter()
+        .map(|(profile, activation)| {
+            let mut value = serde_json::to_value(profile)?;
+            if let serde_json::Value::Object(fields) = &mut value {
+                fields.insert(
+                    "last_activated".to_string(),
+                    activation
+                        .as_ref()
+                        .map_or(serde_json::Value::Null, |record| record.activated_at.to_rfc3339().into()),
+                );
+            }
+            Ok(value)
+        })
+        .collect()
 }
 
 /// JSON formatter implementation that outputs pretty-printed JSON.
@@ -73,6 +101,10 @@ impl OutputFormatter for JsonFormatter {
         // Use serde_json's pretty printing to format the profiles
         serde_json::to_string_pretty(profiles).map_err(GitSetupError::Json)
     }
+
+    fn format_profiles_with_activations(&self, profiles: &[(Profile, Option<ActivationRecord>)]) -> Result<String> {
+        serde_json::to_string_pretty(&profiles_with_last_activated(profiles)?).map_err(GitSetupError::Json)
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +132,15 @@ mod tests {
                 include_if_dirs: vec!["/work/projects".to_string()],
                 host_patterns: vec!["github.com".to_string(), "*.company.com".to_string()],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "personal".to_string(),
@@ -118,6 +159,15 @@ mod tests {
                 include_if_dirs: vec!["/home/user/personal".to_string()],
                 host_patterns: vec!["github.com".to_string()],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ]
     }
@@ -141,6 +191,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -323,6 +382,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gpg_profile".to_string(),
@@ -341,6 +409,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "x509_profile".to_string(),
@@ -359,6 +436,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gitsign_profile".to_string(),
@@ -377,6 +463,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -414,6 +509,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "global_scope".to_string(),
@@ -432,6 +536,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "system_scope".to_string(),
@@ -450,6 +563,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -486,6 +608,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "authorizedkeys_source".to_string(),
@@ -504,6 +635,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "file_source".to_string(),
@@ -522,6 +662,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -551,4 +700,18 @@ mod tests {
         assert!(git_err.to_string().contains("EOF") || git_err.to_string().contains("expected"));
     }
 
+    /// Test that the activation date is added to each profile.
+    #[test]
+    fn test_format_profiles_with_activations() {
+        let profiles = test_profiles();
+        let record = ActivationRecord::now("work", None);
+        let rows = vec![(profiles[0].clone(), Some(record.clone())), (profiles[1].clone(), None)];
+
+        let json = JsonFormatter::new().format_profiles_with_activations(&rows).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed[0]["name"], "work");
+        assert_eq!(parsed[0]["last_activated"], record.activated_at.to_rfc3339());
+        assert!(parsed[1]["last_activated"].is_null());
+    }
 }
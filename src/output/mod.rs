@@ -3,11 +3,15 @@
 //! This module provides different output formatters for profile data.
 
 pub mod csv;
+pub mod diff;
 pub mod json;
 pub mod table;
+pub mod toml;
 pub mod yaml;
 
 pub use csv::CsvFormatter;
+pub use diff::DiffFormatter;
 pub use json::{JsonFormatter, OutputFormatter};
-pub use table::TableFormatter;
+pub use table::{ColumnConfig, TableFormatOptions, TableFormatter};
+pub use toml::TomlFormatter;
 pub use yaml::YamlFormatter;
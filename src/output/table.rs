@@ -41,9 +41,14 @@
 //! ```
 
 use crate::config::types::Profile;
-use crate::error::Result;
+use crate::error::{GitSetupError, Result};
 use crate::output::OutputFormatter;
-use comfy_table::{presets::UTF8_FULL, Attribute, Cell, ContentArrangement, Table};
+use crate::profile::activation::ActivationRecord;
+use comfy_table::{
+    presets::{NOTHING, UTF8_FULL},
+    Attribute, Cell, ContentArrangement, Table,
+};
+use unicode_width::UnicodeWidthStr;
 
 /// Maximum width for truncating long field values to maintain readable table layout.
 const MAX_FIELD_WIDTH: usize = 30;
@@ -51,6 +56,118 @@ const MAX_FIELD_WIDTH: usize = 30;
 /// Message displayed when there are no profiles to show.
 const NO_PROFILES_MESSAGE: &str = "No profiles found.";
 
+/// Column keys and their header labels, in display order.
+const COLUMNS: &[(&str, &str)] = &[
+    ("name", "Name"),
+    ("email", "Email"),
+    ("key_type", "Key Type"),
+    ("signing_key", "Signing Key"),
+    ("vault", "Vault"),
+    ("scope", "Scope"),
+    ("1password", "1Password"),
+];
+
+/// Key and header label of the activation date column shown by `list --since/--until`.
+const LAST_ACTIVATED_COLUMN: (&str, &str) = ("last_activated", "Last Activated");
+
+/// Width and wrapping configuration for a single table column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnConfig {
+    /// Column key (`name`, `email`, `key_type`, `signing_key`, `vault`, `scope`, `1password`, `last_activated`)
+    pub name: String,
+    /// Minimum content width of the column
+    pub min_width: usize,
+    /// Maximum content width of the column
+    pub max_width: usize,
+    /// Wrap values longer than the column width instead of truncating them
+    pub wrap: bool,
+}
+
+impl ColumnConfig {
+    /// Create a column configuration that truncates values longer than `max_width`.
+    pub fn new(name: impl Into<String>, min_width: usize, max_width: usize) -> Self {
+        Self {
+            name: name.into(),
+            min_width,
+            max_width,
+            wrap: false,
+        }
+    }
+
+    /// Set whether long values are wrapped instead of truncated.
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Compute the column width for the longest value in the column.
+    ///
+    /// The width is `max(min_width, min(max_width, longest_value_width))`.
+    pub fn width_for(&self, longest_value_width: usize) -> usize {
+        self.min_width.max(self.max_width.min(longest_value_width))
+    }
+}
+
+/// Options controlling how [`TableFormatter::format`] lays out the table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableFormatOptions {
+    /// Columns to render, in display order
+    pub columns: Vec<ColumnConfig>,
+    /// Draw UTF8 borders around the table
+    pub border: bool,
+}
+
+impl Default for TableFormatOptions {
+    fn default() -> Self {
+        Self {
+            columns: COLUMNS
+                .iter()
+                .map(|(name, _)| ColumnConfig::new(*name, 0, MAX_FIELD_WIDTH))
+                .collect(),
+            border: true,
+        }
+    }
+}
+
+impl TableFormatOptions {
+    /// Add the `last_activated` column unless it is already shown.
+    pub fn with_last_activated(mut self) -> Self {
+        if !self.columns.iter().any(|c| c.name.eq_ignore_ascii_case(LAST_ACTIVATED_COLUMN.0)) {
+            self.columns.push(ColumnConfig::new(LAST_ACTIVATED_COLUMN.0, 0, MAX_FIELD_WIDTH));
+        }
+        self
+    }
+
+    /// Override the width of a column, pinning both its minimum and maximum width.
+    pub fn set_column_width(&mut self, name: &str, width: usize) -> Result<()> {
+        let column = self
+            .columns
+            .iter_mut()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| GitSetupError::Git(format!("Unknown table column: {}", name)))?;
+        column.min_width = width;
+        column.max_width = width;
+        Ok(())
+    }
+
+    /// Apply a `<name>=<width>` column width override as passed to `--column-width`.
+    pub fn apply_column_width(&mut self, spec: &str) -> Result<()> {
+        let (name, width) = spec.split_once('=').ok_or_else(|| {
+            GitSetupError::Git(format!(
+                "Invalid column width '{}': expected <name>=<width>",
+                spec
+            ))
+        })?;
+        let width = width.trim().parse::<usize>().ok().filter(|w| *w > 0).ok_or_else(|| {
+            GitSetupError::Git(format!(
+                "Invalid column width '{}': width must be a positive integer",
+                spec
+            ))
+        })?;
+        self.set_column_width(name.trim(), width)
+    }
+}
+
 /// Tabular formatter implementation that outputs professional tables with borders and alignment.
 #[derive(Debug, Default)]
 pub struct TableFormatter;
@@ -61,16 +178,159 @@ impl TableFormatter {
         Self
     }
 
+    /// Format profiles as a table using the given column and border options.
+    pub fn format(&self, profiles: &[Profile], options: &TableFormatOptions) -> Result<String> {
+        let rows: Vec<(&Profile, Option<&ActivationRecord>)> = profiles.iter().map(|p| (p, None)).collect();
+        self.format_rows(&rows, options)
+    }
+
+    /// Format profiles and their most recent activation as a table.
+    ///
+    /// The activation date is only shown if `options` include the `last_activated` column.
+    pub fn format_with_activations(
+        &self,
+        profiles: &[(Profile, Option<ActivationRecord>)],
+        options: &TableFormatOptions,
+    ) -> Result<String> {
+        let rows: Vec<(&Profile, Option<&ActivationRecord>)> =
+            profiles.iter().map(|(p, activation)| (p, activation.as_ref())).collect();
+        self.format_rows(&rows, options)
+    }
+
+    /// Format arbitrary rows under `headers`, e.g. the git config changes of a dry run.
+    pub fn format_table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(
+                headers
+                    .iter()
+                    .map(|header| Cell::new(header).add_attribute(Attribute::Bold))
+                    .collect::<Vec<_>>(),
+            );
+        for row in rows {
+            table.add_row(row.iter().map(Cell::new).collect::<Vec<_>>());
+        }
+        table.to_string()
+    }
+
+    fn format_rows(&self, rows: &[(&Profile, Option<&ActivationRecord>)], options: &TableFormatOptions) -> Result<String> {
+        // Handle empty profile list
+        if rows.is_empty() {
+            return Ok(NO_PROFILES_MESSAGE.to_string());
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(if options.border { UTF8_FULL } else { NOTHING })
+            .set_content_arrangement(ContentArrangement::Dynamic);
+
+        // Set header
+        table.set_header(
+            options
+                .columns
+                .iter()
+                .map(|column| Cell::new(Self::header(&column.name)).add_attribute(Attribute::Bold))
+                .collect::<Vec<_>>(),
+        );
+
+        // Size each column from its longest value, then fit every value into it
+        let values: Vec<Vec<String>> = rows
+            .iter()
+            .map(|(profile, activation)| {
+                options
+                    .columns
+                    .iter()
+                    .map(|column| Self::column_value(&column.name, profile, *activation))
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<usize> = options
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let longest = values.iter().map(|row| row[i].width()).max().unwrap_or(0);
+                column.width_for(longest)
+            })
+            .collect();
+
+        for row in &values {
+            table.add_row(
+                row.iter()
+                    .zip(&options.columns)
+                    .zip(&widths)
+                    .map(|((value, column), width)| {
+                        if column.wrap {
+                            Cell::new(textwrap::fill(value, (*width).max(1)))
+                        } else {
+                            Cell::new(Self::truncate_to(value, *width))
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        Ok(table.to_string())
+    }
+
+    /// Header label for a column key, falling back to the key itself.
+    fn header(name: &str) -> &str {
+        COLUMNS
+            .iter()
+            .chain(std::iter::once(&LAST_ACTIVATED_COLUMN))
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map_or(name, |(_, header)| header)
+    }
+
+    /// Untruncated display value of a column for a profile.
+    fn column_value(name: &str, profile: &Profile, activation: Option<&ActivationRecord>) -> String {
+        match name.to_ascii_lowercase().as_str() {
+            "name" => profile.name.clone(),
+            "email" => profile.git_user_email.clone(),
+            "key_type" => Self::format_key_type(&profile.key_type),
+            "signing_key" => profile.signing_key.clone().unwrap_or_else(|| "none".to_string()),
+            "vault" => profile.vault_name.clone().unwrap_or_else(|| "none".to_string()),
+            "scope" => Self::format_scope(&profile.scope),
+            "1password" => profile.one_password.to_string(),
+            "last_activated" => activation.map_or_else(
+                || "never".to_string(),
+                |record| record.activated_at.format("%Y-%m-%d %H:%M").to_string(),
+            ),
+            _ => String::new(),
+        }
+    }
+
     /// Truncate a string to the maximum field width with ellipsis if needed.
+    #[cfg(test)]
     fn truncate_field(value: &str) -> String {
-        if value.len() <= MAX_FIELD_WIDTH {
-            value.to_string()
-        } else {
-            format!("{}...", &value[..MAX_FIELD_WIDTH.saturating_sub(3)])
+        Self::truncate_to(value, MAX_FIELD_WIDTH)
+    }
+
+    /// Truncate a string to `width` display columns with ellipsis if needed.
+    fn truncate_to(value: &str, width: usize) -> String {
+        if value.width() <= width {
+            return value.to_string();
+        }
+
+        let budget = width.saturating_sub(3);
+        let mut used = 0;
+        let mut truncated = String::new();
+        for ch in value.chars() {
+            let ch_width = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+            if used + ch_width > budget {
+                break;
+            }
+            used += ch_width;
+            truncated.push(ch);
         }
+        format!("{}...", truncated)
     }
 
     /// Format an optional string field, showing "none" for None values.
+    #[cfg(test)]
     fn format_optional(value: &Option<String>) -> String {
         match value {
             Some(v) => Self::truncate_field(v),
@@ -103,42 +363,11 @@ impl TableFormatter {
 
 impl OutputFormatter for TableFormatter {
     fn format_profiles(&self, profiles: &[Profile]) -> Result<String> {
-        // Handle empty profile list
-        if profiles.is_empty() {
-            return Ok(NO_PROFILES_MESSAGE.to_string());
-        }
-
-        // Create table with UTF8 borders
-        let mut table = Table::new();
-        table
-            .load_preset(UTF8_FULL)
-            .set_content_arrangement(ContentArrangement::Dynamic);
-
-        // Set header
-        table.set_header(vec![
-            Cell::new("Name").add_attribute(Attribute::Bold),
-            Cell::new("Email").add_attribute(Attribute::Bold),
-            Cell::new("Key Type").add_attribute(Attribute::Bold),
-            Cell::new("Signing Key").add_attribute(Attribute::Bold),
-            Cell::new("Vault").add_attribute(Attribute::Bold),
-            Cell::new("Scope").add_attribute(Attribute::Bold),
-            Cell::new("1Password").add_attribute(Attribute::Bold),
-        ]);
-
-        // Add rows for each profile
-        for profile in profiles {
-            table.add_row(vec![
-                Cell::new(&Self::truncate_field(&profile.name)),
-                Cell::new(&Self::truncate_field(&profile.git_user_email)),
-                Cell::new(&Self::format_key_type(&profile.key_type)),
-                Cell::new(&Self::format_optional(&profile.signing_key)),
-                Cell::new(&Self::format_optional(&profile.vault_name)),
-                Cell::new(&Self::format_scope(&profile.scope)),
-                Cell::new(&profile.one_password.to_string()),
-            ]);
-        }
+        self.format(profiles, &TableFormatOptions::default())
+    }
 
-        Ok(table.to_string())
+    fn format_profiles_with_activations(&self, profiles: &[(Profile, Option<ActivationRecord>)]) -> Result<String> {
+        self.format_with_activations(profiles, &TableFormatOptions::default().with_last_activated())
     }
 }
 
@@ -167,6 +396,15 @@ mod tests {
                 include_if_dirs: vec!["/work/projects".to_string()],
                 host_patterns: vec!["github.com".to_string(), "*.company.com".to_string()],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "personal".to_string(),
@@ -185,6 +423,15 @@ mod tests {
                 include_if_dirs: vec!["/home/user/personal".to_string()],
                 host_patterns: vec!["github.com".to_string()],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ]
     }
@@ -208,6 +455,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -230,6 +486,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -411,6 +676,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gpg_profile".to_string(),
@@ -429,6 +703,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "x509_profile".to_string(),
@@ -447,6 +730,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gitsign_profile".to_string(),
@@ -465,6 +757,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -500,6 +801,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "global_scope".to_string(),
@@ -518,6 +828,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "system_scope".to_string(),
@@ -536,6 +855,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -570,6 +898,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "onepassword_false".to_string(),
@@ -588,6 +925,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -666,6 +1012,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -702,6 +1057,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -716,4 +1080,102 @@ mod tests {
         assert!(table_output.contains("none")); // For scope
         assert!(table_output.contains("false")); // For one_password
     }
+
+    #[test]
+    fn test_column_width_computation() {
+        let column = ColumnConfig::new("email", 10, 20);
+        assert_eq!(column.width_for(5), 10); // Clamped up to min_width
+        assert_eq!(column.width_for(15), 15); // Fits the longest value
+        assert_eq!(column.width_for(40), 20); // Clamped down to max_width
+    }
+
+    #[test]
+    fn test_default_options_match_format_profiles() {
+        let formatter = TableFormatter::new();
+        let profiles = vec![long_field_profile()];
+
+        let default_output = formatter.format_profiles(&profiles).unwrap();
+        let explicit_output = formatter
+            .format(&profiles, &TableFormatOptions::default())
+            .unwrap();
+
+        assert_eq!(default_output, explicit_output);
+    }
+
+    #[test]
+    fn test_wrap_keeps_full_value() {
+        let formatter = TableFormatter::new();
+        let profiles = vec![long_field_profile()];
+
+        let mut options = TableFormatOptions::default();
+        for column in &mut options.columns {
+            if column.name == "email" {
+                *column = ColumnConfig::new("email", 0, 20).with_wrap(true);
+            }
+        }
+
+        let table_output = formatter.format(&profiles, &options).unwrap();
+
+        // Wrapped values are split across lines rather than truncated
+        assert!(!table_output.contains("very_long_email_address_tha..."));
+        assert!(table_output.contains("@example.com"));
+    }
+
+    #[test]
+    fn test_column_width_override() {
+        let formatter = TableFormatter::new();
+        let profiles = vec![long_field_profile()];
+
+        let mut options = TableFormatOptions::default();
+        options.apply_column_width("name=10").unwrap();
+
+        let table_output = formatter.format(&profiles, &options).unwrap();
+        assert!(table_output.contains("very_lo..."));
+        assert!(!table_output.contains("very_long_profile_name_that..."));
+    }
+
+    #[test]
+    fn test_invalid_column_width_specs() {
+        let mut options = TableFormatOptions::default();
+        assert!(options.apply_column_width("email").is_err());
+        assert!(options.apply_column_width("email=wide").is_err());
+        assert!(options.apply_column_width("email=0").is_err());
+        assert!(options.apply_column_width("unknown=10").is_err());
+        assert!(options.apply_column_width("Email=40").is_ok());
+    }
+
+    #[test]
+    fn test_no_border() {
+        let formatter = TableFormatter::new();
+        let profiles = test_profiles();
+
+        let options = TableFormatOptions {
+            border: false,
+            ..TableFormatOptions::default()
+        };
+        let table_output = formatter.format(&profiles, &options).unwrap();
+
+        assert!(!table_output.contains("┌"));
+        assert!(!table_output.contains("│"));
+        assert!(table_output.contains("Name"));
+        assert!(table_output.contains("work@example.com"));
+    }
+
+    /// Test the activation date column.
+    #[test]
+    fn test_format_with_activations() {
+        let mut record = ActivationRecord::now("minimal", None);
+        record.activated_at = chrono::DateTime::parse_from_rfc3339("2024-01-15T09:30:00Z").unwrap().into();
+        let rows = vec![(minimal_profile(), Some(record)), (long_field_profile(), None)];
+        let formatter = TableFormatter::new();
+
+        let output = formatter.format_profiles_with_activations(&rows).unwrap();
+        assert!(output.contains("Last Activated"));
+        assert!(output.contains("2024-01-15 09:30"));
+        assert!(output.contains("never"));
+
+        // Without the column the table matches the plain format
+        let plain = formatter.format_with_activations(&rows, &TableFormatOptions::default()).unwrap();
+        assert!(!plain.contains("Last Activated"));
+    }
 }
@@ -0,0 +1,223 @@
+//! TOML output formatting for profile lists.
+//!
+//! This module serializes profiles with the `toml` crate as an array of
+//! `[[profile]]` tables. The output parses back with
+//! [`TomlFormatter::parse_profiles`], so `--import --from-toml` can read what
+//! `--list --output toml` writes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use git_setup_rs::output::{TomlFormatter, OutputFormatter};
+//! use git_setup_rs::config::types::Profile;
+//!
+//! let profiles = vec![Profile {
+//!     name: "work".to_string(),
+//!     git_user_email: "work@example.com".to_string(),
+//!     ..Default::default()
+//! }];
+//!
+//! let toml_output = TomlFormatter::new().format_profiles(&profiles).unwrap();
+//! assert!(toml_output.starts_with("[[profile]]"));
+//! assert_eq!(TomlFormatter::parse_profiles(&toml_output).unwrap(), profiles);
+//! ```
+
+use crate::config::types::Profile;
+use crate::error::Result;
+use crate::output::{json::profiles_with_last_activated, OutputFormatter};
+use crate::profile::activation::ActivationRecord;
+use serde::{Deserialize, Serialize};
+
+/// Document layout: the profiles as a `[[profile]]` array of tables.
+#[derive(Serialize, Deserialize)]
+struct ProfileDocument<T> {
+    #[serde(default = "Vec::new")]
+    profile: Vec<T>,
+}
+
+/// TOML formatter implementation writing `[[profile]]` tables.
+#[derive(Debug, Default)]
+pub struct TomlFormatter;
+
+impl TomlFormatter {
+    /// Create a new TomlFormatter instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse profiles written by [`TomlFormatter::format_profiles`].
+    ///
+    /// Any other fields of a table, such as `last_activated`, are ignored.
+    pub fn parse_profiles(content: &str) -> Result<Vec<Profile>> {
+        let document: ProfileDocument<Profile> = toml::from_str(content)?;
+        Ok(document.profile)
+    }
+}
+
+impl OutputFormatter for TomlFormatter {
+    fn format_profiles(&self, profiles: &[Profile]) -> Result<String> {
+        let document = ProfileDocument {
+            profile: profiles.to_vec(),
+        };
+        Ok(toml::to_string_pretty(&document)?)
+    }
+
+    fn format_profiles_with_activations(&self, profiles: &[(Profile, Option<ActivationRecord>)]) -> Result<String> {
+        // TOML has no null, so unset fields are left out like `format_profiles` does
+        let mut profiles = profiles_with_last_activated(profiles)?;
+        for profile in &mut profiles {
+            if let serde_json::Value::Object(fields) = profile {
+                fields.retain(|_, value| !value.is_null());
+            }
+        }
+        Ok(toml::to_string_pretty(&ProfileDocument { profile: profiles })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::{KeyType, Scope, SecretStoreKind, SshKeySource};
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn work_profile() -> Profile {
+        Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Work User".to_string()),
+            git_user_email: "work@example.com".to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("~/.ssh/work_ed25519.pub".to_string()),
+            scope: Some(Scope::Local),
+            match_patterns: vec!["work/*".to_string()],
+            per_field_scope: HashMap::from([("commit.gpgsign".to_string(), Scope::Global)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_profiles() {
+        let output = TomlFormatter::new().format_profiles(&[work_profile()]).unwrap();
+
+        assert!(output.starts_with("[[profile]]\n"));
+        assert!(output.contains("name = \"work\""));
+        assert!(output.contains("key_type = \"ssh\""));
+        assert!(output.contains("\"commit.gpgsign\" = \"global\""));
+        assert!(!output.contains("vault_name"));
+        assert_eq!(TomlFormatter::parse_profiles(&output).unwrap(), vec![work_profile()]);
+    }
+
+    #[test]
+    fn test_format_empty() {
+        let output = TomlFormatter::new().format_profiles(&[]).unwrap();
+        assert!(TomlFormatter::parse_profiles(&output).unwrap().is_empty());
+        assert!(TomlFormatter::parse_profiles("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_format_with_activations() {
+        let record = ActivationRecord::now("work", None);
+        let profiles = vec![(work_profile(), Some(record.clone())), (Profile::default(), None)];
+
+        let output = TomlFormatter::new().format_profiles_with_activations(&profiles).unwrap();
+
+        assert_eq!(output.matches("[[profile]]").count(), 2);
+        assert_eq!(output.matches("last_activated").count(), 1);
+        assert!(output.contains(&record.activated_at.to_rfc3339()));
+        assert_eq!(TomlFormatter::parse_profiles(&output).unwrap()[0], work_profile());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(TomlFormatter::parse_profiles("[[profile]]\nname = ").is_err());
+    }
+
+    fn key_type() -> impl Strategy<Value = KeyType> {
+        prop_oneof![Just(KeyType::Ssh), Just(KeyType::Gpg), Just(KeyType::X509), Just(KeyType::Gitsign)]
+    }
+
+    fn scope() -> impl Strategy<Value = Scope> {
+        prop_oneof![Just(Scope::Local), Just(Scope::Global), Just(Scope::System)]
+    }
+
+    fn ssh_key_source() -> impl Strategy<Value = SshKeySource> {
+        prop_oneof![Just(SshKeySource::OnePassword), Just(SshKeySource::AuthorizedKeys), Just(SshKeySource::File)]
+    }
+
+    fn secret_store() -> impl Strategy<Value = SecretStoreKind> {
+        prop_oneof![Just(SecretStoreKind::OnePassword), Just(SecretStoreKind::Bitwarden)]
+    }
+
+    fn text() -> impl Strategy<Value = String> {
+        // Quotes, backslashes, newlines and non-ASCII need escaping in TOML strings
+        "[a-zA-Z0-9 ._@/~*\"'\\\\\n\té-]{0,24}"
+    }
+
+    fn strings() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::vec(text(), 0..4)
+    }
+
+    prop_compose! {
+        fn profile()(
+            (name, git_user_name, git_user_email, key_type, signing_key, vault_name) in (
+                text(), prop::option::of(text()), text(), key_type(),
+                prop::option::of(text()), prop::option::of(text()),
+            ),
+            (ssh_key_title, scope, ssh_key_source, ssh_key_path, allowed_signers) in (
+                prop::option::of(text()), prop::option::of(scope()), prop::option::of(ssh_key_source()),
+                prop::option::of(text()), prop::option::of(text()),
+            ),
+            (match_patterns, repos, include_if_dirs, host_patterns, tags) in (
+                strings(), strings(), strings(), strings(), strings(),
+            ),
+            (one_password, require_passphrase, is_archived, description) in (
+                any::<bool>(), any::<bool>(), any::<bool>(), prop::option::of(text()),
+            ),
+            (per_field_scope, git_hooks_dir, allowed_signers_content, secret_store, rotated_key_item) in (
+                prop::collection::hash_map("[a-z]{1,8}\\.[a-zA-Z]{1,8}", scope(), 0..3),
+                prop::option::of("[a-z/~._-]{1,16}".prop_map(PathBuf::from)),
+                prop::option::of(text()),
+                prop::option::of(secret_store()),
+                prop::option::of(text()),
+            ),
+        ) -> Profile {
+            Profile {
+                name,
+                git_user_name,
+                git_user_email,
+                key_type,
+                signing_key,
+                vault_name,
+                ssh_key_title,
+                scope,
+                ssh_key_source,
+                ssh_key_path,
+                allowed_signers,
+                match_patterns,
+                repos,
+                include_if_dirs,
+                host_patterns,
+                one_password,
+                require_passphrase,
+                is_archived,
+                description,
+                per_field_scope,
+                git_hooks_dir,
+                allowed_signers_content,
+                secret_store,
+                rotated_key_item,
+                tags,
+            }
+        }
+    }
+
+    proptest! {
+        /// Every field of every profile survives a round-trip through TOML.
+        #[test]
+        fn prop_round_trip(profiles in prop::collection::vec(profile(), 0..4)) {
+            let output = TomlFormatter::new().format_profiles(&profiles).unwrap();
+            prop_assert_eq!(TomlFormatter::parse_profiles(&output).unwrap(), profiles);
+        }
+    }
+}
@@ -42,7 +42,8 @@
 
 use crate::config::types::Profile;
 use crate::error::Result;
-use crate::output::OutputFormatter;
+use crate::output::{json::profiles_with_last_activated, OutputFormatter};
+use crate::profile::activation::ActivationRecord;
 use serde_yaml;
 
 /// YAML formatter implementation that outputs well-formatted YAML.
@@ -54,19 +55,27 @@ impl YamlFormatter {
     pub fn new() -> Self {
         Self
     }
+
+    /// Ensure proper YAML document start
+    fn with_document_start(yaml_string: String) -> String {
+        if yaml_string.starts_with("---") {
+            yaml_string
+        } else {
+            format!("---\n{}", yaml_string)
+        }
+    }
 }
 
 impl OutputFormatter for YamlFormatter {
     fn format_profiles(&self, profiles: &[Profile]) -> Result<String> {
         // Use serde_yaml to serialize the profiles with the document marker
         let yaml_string = serde_yaml::to_string(profiles)?;
+        Ok(Self::with_document_start(yaml_string))
+    }
 
-        // Ensure proper YAML document start
-        if yaml_string.starts_with("---") {
-            Ok(yaml_string)
-        } else {
-            Ok(format!("---\n{}", yaml_string))
-        }
+    fn format_profiles_with_activations(&self, profiles: &[(Profile, Option<ActivationRecord>)]) -> Result<String> {
+        let yaml_string = serde_yaml::to_string(&profiles_with_last_activated(profiles)?)?;
+        Ok(Self::with_document_start(yaml_string))
     }
 }
 
@@ -96,6 +105,15 @@ mod tests {
                 include_if_dirs: vec!["/work/projects".to_string()],
                 host_patterns: vec!["github.com".to_string(), "*.company.com".to_string()],
                 one_password: true,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "personal".to_string(),
@@ -114,6 +132,15 @@ mod tests {
                 include_if_dirs: vec!["/home/user/personal".to_string()],
                 host_patterns: vec!["github.com".to_string()],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ]
     }
@@ -137,6 +164,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -340,6 +376,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gpg_profile".to_string(),
@@ -358,6 +403,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "x509_profile".to_string(),
@@ -376,6 +430,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "gitsign_profile".to_string(),
@@ -394,6 +457,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -431,6 +503,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "global_scope".to_string(),
@@ -449,6 +530,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "system_scope".to_string(),
@@ -467,6 +557,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -503,6 +602,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "authorizedkeys_source".to_string(),
@@ -521,6 +629,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
             Profile {
                 name: "file_source".to_string(),
@@ -539,6 +656,15 @@ mod tests {
                 include_if_dirs: vec![],
                 host_patterns: vec![],
                 one_password: false,
+                require_passphrase: false,
+                is_archived: false,
+                description: None,
+                per_field_scope: Default::default(),
+                git_hooks_dir: None,
+                allowed_signers_content: None,
+                secret_store: None,
+                rotated_key_item: None,
+                tags: vec![],
             },
         ];
 
@@ -564,4 +690,15 @@ mod tests {
         // Verify the error conversion works
         assert!(matches!(git_err, GitSetupError::Yaml(_)));
     }
+
+    /// Test that the activation date is added to each profile.
+    #[test]
+    fn test_format_profiles_with_activations() {
+        let rows = vec![(minimal_profile(), None)];
+
+        let yaml = YamlFormatter::new().format_profiles_with_activations(&rows).unwrap();
+
+        assert!(yaml.starts_with("---"));
+        assert!(yaml.contains("last_activated: null"));
+    }
 }
@@ -6,6 +6,26 @@ pub trait PlatformPaths: std::fmt::Debug + Send + Sync {
     fn default_ssh_program(&self) -> &'static str;
     fn default_gpg_program(&self) -> &'static str;
     fn expand_path(&self, path: &str) -> String;
+
+    /// Directory for git-setup's own data files
+    /// (`$XDG_DATA_HOME/git-setup`, falling back to `~/.local/share/git-setup`).
+    fn data_dir(&self) -> Result<PathBuf, std::io::Error> {
+        let data_home = match std::env::var("XDG_DATA_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => self.home_dir()?.join(".local").join("share"),
+        };
+        Ok(data_home.join("git-setup"))
+    }
+
+    /// Directory for git-setup's cached downloads
+    /// (`$XDG_CACHE_HOME/git-setup`, falling back to `~/.cache/git-setup`).
+    fn cache_dir(&self) -> Result<PathBuf, std::io::Error> {
+        let cache_home = match std::env::var("XDG_CACHE_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => self.home_dir()?.join(".cache"),
+        };
+        Ok(cache_home.join("git-setup"))
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,144 @@
+//! Activation history for profiles.
+//!
+//! Every successful `apply` is recorded as an [`ActivationRecord`] so that
+//! stale profiles can be found later. The history can be persisted to disk
+//! alongside the undo history.
+
+use crate::{
+    error::Result,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Maximum number of activation records kept; the oldest are dropped first.
+pub const MAX_ACTIVATION_RECORDS: usize = 1000;
+
+/// A single application of a profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivationRecord {
+    /// Name of the applied profile.
+    pub profile_name: String,
+    /// Repository the profile was applied to, if it was applied with local scope.
+    pub repo_path: Option<PathBuf>,
+    /// When the profile was applied.
+    pub activated_at: DateTime<Utc>,
+}
+
+impl ActivationRecord {
+    /// Create a record for an activation happening now.
+    pub fn now(profile_name: impl Into<String>, repo_path: Option<PathBuf>) -> Self {
+        Self {
+            profile_name: profile_name.into(),
+            repo_path,
+            activated_at: Utc::now(),
+        }
+    }
+}
+
+/// Bounded, chronologically ordered list of activation records.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivationHistory {
+    records: Vec<ActivationRecord>,
+}
+
+impl ActivationHistory {
+    /// Create an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default location of the persisted history
+    /// (`~/.local/share/git-setup/activations.json`, honouring `XDG_DATA_HOME`).
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(SystemPlatform.data_dir()?.join("activations.json"))
+    }
+
+    /// Load a history from `path`, returning an empty history if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the history to `path`, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Append a record, dropping the oldest records beyond the limit.
+    pub fn record(&mut self, record: ActivationRecord) {
+        self.records.push(record);
+        if self.records.len() > MAX_ACTIVATION_RECORDS {
+            let excess = self.records.len() - MAX_ACTIVATION_RECORDS;
+            self.records.drain(..excess);
+        }
+    }
+
+    /// Remove every record.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// All records, oldest first.
+    pub fn records(&self) -> &[ActivationRecord] {
+        &self.records
+    }
+
+    /// Most recent activation of the named profile.
+    pub fn last_activation(&self, profile_name: &str) -> Option<&ActivationRecord> {
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.profile_name == profile_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_last_activation() {
+        let mut history = ActivationHistory::new();
+        history.record(ActivationRecord::now("work", None));
+        history.record(ActivationRecord::now("personal", Some(PathBuf::from("/repo"))));
+        history.record(ActivationRecord::now("work", Some(PathBuf::from("/other"))));
+
+        let last = history.last_activation("work").unwrap();
+        assert_eq!(last.repo_path, Some(PathBuf::from("/other")));
+        assert!(history.last_activation("missing").is_none());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = ActivationHistory::new();
+        for i in 0..(MAX_ACTIVATION_RECORDS + 3) {
+            history.record(ActivationRecord::now(format!("p{}", i), None));
+        }
+        assert_eq!(history.records().len(), MAX_ACTIVATION_RECORDS);
+        assert_eq!(history.records()[0].profile_name, "p3");
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("activations.json");
+
+        let mut history = ActivationHistory::new();
+        history.record(ActivationRecord::now("work", Some(PathBuf::from("/repo"))));
+        history.save(&path).unwrap();
+
+        let loaded = ActivationHistory::load(&path).unwrap();
+        assert_eq!(loaded.records(), history.records());
+        assert!(ActivationHistory::load(&dir.path().join("missing.json")).unwrap().records().is_empty());
+    }
+}
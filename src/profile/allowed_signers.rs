@@ -0,0 +1,246 @@
+//! Allowed signers files of profiles.
+//!
+//! Git verifies SSH commit signatures against the keys listed in the file
+//! `gpg.ssh.allowedSignersFile` points at. A profile's
+//! `allowed_signers_content` is written to a file of its own through
+//! [`GitWrapper::set_allowed_signers`]; the entries can also be taken from
+//! another profile or from a list a team publishes at a URL, which
+//! [`AllowedSignersUpdater`] downloads with `curl` and caches for an hour.
+
+use super::ProfileManager;
+use crate::{
+    config::types::Profile,
+    error::{GitSetupError, Result},
+    external::git::GitWrapper,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Git config key pointing at the allowed signers file.
+pub const ALLOWED_SIGNERS_KEY: &str = "gpg.ssh.allowedSignersFile";
+
+/// How long a downloaded allowed signers list is used before it is downloaded again.
+pub const DOWNLOAD_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Where the entries of an allowed signers file come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignersSource<'a> {
+    /// The `allowed_signers_content` of the named profile
+    Profile(&'a str),
+    /// A list downloaded from a URL
+    Url(&'a str),
+}
+
+/// Regenerates the allowed signers files of profiles.
+pub struct AllowedSignersUpdater {
+    profile_manager: Arc<dyn ProfileManager>,
+    git: Arc<dyn GitWrapper>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl AllowedSignersUpdater {
+    /// Create an updater reading profiles from `profile_manager` and writing files through `git`.
+    pub fn new(profile_manager: Arc<dyn ProfileManager>, git: Arc<dyn GitWrapper>) -> Self {
+        Self {
+            profile_manager,
+            git,
+            cache_dir: None,
+        }
+    }
+
+    /// Cache downloaded lists in `dir` instead of `~/.cache/git-setup`.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Write the allowed signers file of `profile` and return its path.
+    ///
+    /// The entries come from `source`, or from the profile's own
+    /// `allowed_signers_content` without one.
+    ///
+    /// # Errors
+    /// Returns an error if there are no entries, the download fails or the file cannot be written
+    pub fn update(&self, profile: &Profile, source: Option<SignersSource<'_>>) -> Result<PathBuf> {
+        let content = match source {
+            Some(source) => self.content(source)?,
+            None => Self::profile_content(profile)?.to_string(),
+        };
+        self.git.set_allowed_signers(&profile.name, &content)
+    }
+
+    /// Rewrite the allowed signers file of every profile that has `allowed_signers_content`.
+    ///
+    /// Returns the name of each updated profile with the path of its file.
+    pub fn update_all(&self) -> Result<Vec<(String, PathBuf)>> {
+        self.profile_manager
+            .list()?
+            .into_iter()
+            .filter_map(|profile| {
+                let content = profile.allowed_signers_content?;
+                Some(
+                    self.git
+                        .set_allowed_signers(&profile.name, &content)
+                        .map(|path| (profile.name, path)),
+                )
+            })
+            .collect()
+    }
+
+    /// The allowed signers entries `source` provides.
+    pub fn content(&self, source: SignersSource<'_>) -> Result<String> {
+        match source {
+            SignersSource::Profile(name) => {
+                let profile = self
+                    .profile_manager
+                    .read(name)?
+                    .ok_or_else(|| GitSetupError::ProfileNotFound { name: name.to_string() })?;
+                Self::profile_content(&profile).map(str::to_string)
+            }
+            SignersSource::Url(url) => self.download(url),
+        }
+    }
+
+    /// Download the list at `url`, using a cached copy younger than [`DOWNLOAD_CACHE_TTL`].
+    pub fn download(&self, url: &str) -> Result<String> {
+        let cache_dir = match &self.cache_dir {
+            Some(dir) => dir.clone(),
+            None => SystemPlatform.cache_dir()?,
+        };
+        let cache_file = cache_dir.join(cache_file_name(url));
+
+        let fresh = std::fs::metadata(&cache_file)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < DOWNLOAD_CACHE_TTL);
+        if fresh {
+            return Ok(std::fs::read_to_string(&cache_file)?);
+        }
+
+        let content = Self::fetch(url)?;
+        if content.trim().is_empty() {
+            return Err(GitSetupError::Git(format!("The allowed signers list at '{}' is empty", url)));
+        }
+        std::fs::create_dir_all(&cache_dir)?;
+        std::fs::write(&cache_file, &content)?;
+        Ok(content)
+    }
+
+    /// Download `url` with `curl`.
+    fn fetch(url: &str) -> Result<String> {
+        let command = format!("curl -fsSL {}", url);
+        let output = Command::new("curl")
+            .args(["-fsSL", "--max-time", "30", url])
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| GitSetupError::ExternalCommand {
+                command: command.clone(),
+                error: e.to_string(),
+            })?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(GitSetupError::ExternalCommand {
+                command,
+                error: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            })
+        }
+    }
+
+    fn profile_content(profile: &Profile) -> Result<&str> {
+        profile
+            .allowed_signers_content
+            .as_deref()
+            .ok_or_else(|| GitSetupError::InvalidProfile {
+                reason: format!("profile '{}' has no allowed_signers_content", profile.name),
+            })
+    }
+}
+
+/// Name of the cache file of `url`, from its 64-bit FNV-1a hash.
+///
+/// The hash is stable across builds, unlike `std`'s `DefaultHasher`.
+fn cache_file_name(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("allowed-signers-{:016x}.txt", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::git::MockGitWrapper;
+    use crate::profile::mock::MockProfileManager;
+    use tempfile::TempDir;
+
+    const TEAM_SIGNERS: &str = "jane@work.com ssh-ed25519 AAAAC3Nza jane\n";
+
+    fn profile(name: &str, content: Option<&str>) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            allowed_signers_content: content.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn updater(git: &MockGitWrapper) -> AllowedSignersUpdater {
+        let manager = MockProfileManager::with_profiles(vec![
+            profile("work", Some(TEAM_SIGNERS)),
+            profile("personal", None),
+        ]);
+        AllowedSignersUpdater::new(Arc::new(manager), Arc::new(git.clone()))
+    }
+
+    #[test]
+    fn test_update_from_profiles() {
+        let git = MockGitWrapper::new();
+        let updater = updater(&git);
+
+        let path = updater.update(&profile("work", Some(TEAM_SIGNERS)), None).unwrap();
+        assert_eq!(path, PathBuf::from("/mock/allowed_signers/work"));
+        assert_eq!(git.allowed_signers("work").as_deref(), Some(TEAM_SIGNERS));
+
+        updater
+            .update(&profile("personal", None), Some(SignersSource::Profile("work")))
+            .unwrap();
+        assert_eq!(git.allowed_signers("personal").as_deref(), Some(TEAM_SIGNERS));
+
+        let result = updater.update(&profile("personal", None), None);
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        let result = updater.content(SignersSource::Profile("missing"));
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+    }
+
+    #[test]
+    fn test_update_all_skips_profiles_without_content() {
+        let git = MockGitWrapper::new();
+        let updated = updater(&git).update_all().unwrap();
+
+        assert_eq!(updated, vec![("work".to_string(), PathBuf::from("/mock/allowed_signers/work"))]);
+        assert_eq!(git.allowed_signers("personal"), None);
+    }
+
+    #[test]
+    fn test_download_uses_fresh_cache() {
+        let dir = TempDir::new().unwrap();
+        let url = "https://example.com/allowed_signers";
+        std::fs::write(dir.path().join(cache_file_name(url)), TEAM_SIGNERS).unwrap();
+
+        let git = MockGitWrapper::new();
+        let updater = updater(&git).with_cache_dir(dir.path());
+        assert_eq!(updater.content(SignersSource::Url(url)).unwrap(), TEAM_SIGNERS);
+    }
+
+    #[test]
+    fn test_cache_file_name() {
+        assert_eq!(cache_file_name(""), "allowed-signers-cbf29ce484222325.txt");
+        assert_ne!(cache_file_name("https://a.example"), cache_file_name("https://b.example"));
+    }
+}
@@ -0,0 +1,251 @@
+//! Usage analytics over the activation history.
+//!
+//! [`ActivationAnalyzer`] answers questions such as which profile is applied
+//! most often, which profiles have not been applied recently and at which
+//! hours of the day profiles tend to be applied. [`ProfileStats`] sums up
+//! how often, when last and where a single profile was applied.
+
+use super::activation::ActivationRecord;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// How often and when last a profile was applied in a repository.
+type RepoUsage = (usize, DateTime<Utc>);
+
+/// How often, when last and where a profile was applied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileStats {
+    pub name: String,
+    pub apply_count: usize,
+    pub last_used: DateTime<Utc>,
+    /// Repository the profile was applied in most often, if any activation recorded one
+    pub most_common_repo: Option<PathBuf>,
+}
+
+/// Statistics computed from a list of activation records.
+#[derive(Debug, Clone)]
+pub struct ActivationAnalyzer {
+    records: Vec<ActivationRecord>,
+    now: DateTime<Utc>,
+}
+
+impl ActivationAnalyzer {
+    /// Create an analyzer over `records`, measuring ages from the current time.
+    pub fn new(records: Vec<ActivationRecord>) -> Self {
+        Self {
+            records,
+            now: Utc::now(),
+        }
+    }
+
+    /// Measure ages from `now` instead of the current time.
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Number of analyzed records.
+    pub fn total(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Activation count of every profile in the history, sorted by name.
+    pub fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for record in &self.records {
+            *counts.entry(record.profile_name.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Profile applied most often; ties go to the alphabetically first name.
+    pub fn most_frequent(&self) -> Option<&str> {
+        self.counts()
+            .into_iter()
+            .fold(None, |best: Option<(&str, usize)>, (name, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((name, count)),
+            })
+            .map(|(name, _)| name)
+    }
+
+    /// Profile applied least often; ties go to the alphabetically first name.
+    pub fn least_frequent(&self) -> Option<&str> {
+        self.counts()
+            .into_iter()
+            .fold(None, |best: Option<(&str, usize)>, (name, count)| match best {
+                Some((_, best_count)) if best_count <= count => best,
+                _ => Some((name, count)),
+            })
+            .map(|(name, _)| name)
+    }
+
+    /// Number of activations in each hour of the day (UTC).
+    pub fn hourly_histogram(&self) -> [usize; 24] {
+        let mut histogram = [0; 24];
+        for record in &self.records {
+            histogram[record.activated_at.hour() as usize] += 1;
+        }
+        histogram
+    }
+
+    /// Profiles whose most recent activation is more than `days` days old, sorted by name.
+    pub fn inactive_since(&self, days: u32) -> Vec<String> {
+        let cutoff = self.now - Duration::days(i64::from(days));
+        let mut last: BTreeMap<&str, DateTime<Utc>> = BTreeMap::new();
+        for record in &self.records {
+            let entry = last.entry(record.profile_name.as_str()).or_insert(record.activated_at);
+            *entry = (*entry).max(record.activated_at);
+        }
+        last.into_iter()
+            .filter(|(_, at)| *at < cutoff)
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// Stats of every profile in the history, sorted by name.
+    ///
+    /// Ties for the most common repository go to the one used most recently.
+    pub fn profile_stats(&self) -> Vec<ProfileStats> {
+        let mut stats: BTreeMap<&str, (ProfileStats, BTreeMap<&Path, RepoUsage>)> = BTreeMap::new();
+        for record in &self.records {
+            let (profile, repos) = stats.entry(record.profile_name.as_str()).or_insert_with(|| {
+                let profile = ProfileStats {
+                    name: record.profile_name.clone(),
+                    apply_count: 0,
+                    last_used: record.activated_at,
+                    most_common_repo: None,
+                };
+                (profile, BTreeMap::new())
+            });
+            profile.apply_count += 1;
+            profile.last_used = profile.last_used.max(record.activated_at);
+            if let Some(repo) = &record.repo_path {
+                let entry = repos.entry(repo.as_path()).or_insert((0, record.activated_at));
+                entry.0 += 1;
+                entry.1 = entry.1.max(record.activated_at);
+            }
+        }
+        stats
+            .into_values()
+            .map(|(mut profile, repos)| {
+                profile.most_common_repo = repos
+                    .into_iter()
+                    .max_by_key(|(_, usage)| *usage)
+                    .map(|(repo, _)| repo.to_path_buf());
+                profile
+            })
+            .collect()
+    }
+
+    /// Average activations per day of each profile, sorted by name.
+    ///
+    /// The period runs from the oldest record to now and counts as at least one day.
+    pub fn daily_averages(&self) -> BTreeMap<&str, f64> {
+        let Some(first) = self.records.iter().map(|record| record.activated_at).min() else {
+            return BTreeMap::new();
+        };
+        let days = ((self.now - first).num_seconds() as f64 / 86_400.0).max(1.0);
+        self.counts()
+            .into_iter()
+            .map(|(name, count)| (name, count as f64 / days))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    fn record(name: &str, timestamp: &str) -> ActivationRecord {
+        ActivationRecord {
+            profile_name: name.to_string(),
+            repo_path: None,
+            activated_at: at(timestamp),
+        }
+    }
+
+    fn analyzer() -> ActivationAnalyzer {
+        ActivationAnalyzer::new(vec![
+            record("work", "2024-03-01T09:15:00Z"),
+            record("personal", "2024-03-05T20:00:00Z"),
+            record("work", "2024-03-10T09:45:00Z"),
+            record("oss", "2024-01-02T09:00:00Z"),
+            record("work", "2024-03-19T14:00:00Z"),
+        ])
+        .at(at("2024-03-20T12:00:00Z"))
+    }
+
+    #[test]
+    fn test_frequency() {
+        let analyzer = analyzer();
+        assert_eq!(analyzer.most_frequent(), Some("work"));
+        // "oss" and "personal" were both applied once
+        assert_eq!(analyzer.least_frequent(), Some("oss"));
+        assert_eq!(analyzer.counts()["work"], 3);
+
+        let empty = ActivationAnalyzer::new(Vec::new());
+        assert_eq!(empty.most_frequent(), None);
+        assert_eq!(empty.least_frequent(), None);
+        assert!(empty.daily_averages().is_empty());
+    }
+
+    #[test]
+    fn test_hourly_histogram() {
+        let histogram = analyzer().hourly_histogram();
+        assert_eq!(histogram[9], 3);
+        assert_eq!(histogram[14], 1);
+        assert_eq!(histogram[20], 1);
+        assert_eq!(histogram.iter().sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_inactive_since() {
+        let analyzer = analyzer();
+        assert_eq!(analyzer.inactive_since(10), vec!["oss", "personal"]);
+        assert_eq!(analyzer.inactive_since(30), vec!["oss"]);
+        assert!(analyzer.inactive_since(90).is_empty());
+    }
+
+    #[test]
+    fn test_profile_stats() {
+        let in_repo = |name: &str, repo: &str, timestamp: &str| ActivationRecord {
+            repo_path: Some(PathBuf::from(repo)),
+            ..record(name, timestamp)
+        };
+        let analyzer = ActivationAnalyzer::new(vec![
+            in_repo("work", "/src/api", "2024-03-01T09:00:00Z"),
+            in_repo("work", "/src/web", "2024-03-02T09:00:00Z"),
+            in_repo("work", "/src/api", "2024-03-03T09:00:00Z"),
+            in_repo("oss", "/src/lib", "2024-03-04T09:00:00Z"),
+            in_repo("oss", "/src/cli", "2024-03-05T09:00:00Z"),
+            record("personal", "2024-03-06T09:00:00Z"),
+        ]);
+
+        let stats = analyzer.profile_stats();
+        let names: Vec<&str> = stats.iter().map(|stats| stats.name.as_str()).collect();
+        assert_eq!(names, vec!["oss", "personal", "work"]);
+
+        assert_eq!(stats[2].apply_count, 3);
+        assert_eq!(stats[2].last_used, at("2024-03-03T09:00:00Z"));
+        assert_eq!(stats[2].most_common_repo, Some(PathBuf::from("/src/api")));
+        // Equally used repositories go to the most recent one
+        assert_eq!(stats[0].most_common_repo, Some(PathBuf::from("/src/cli")));
+        assert_eq!(stats[1].most_common_repo, None);
+    }
+
+    #[test]
+    fn test_daily_averages() {
+        let analyzer = analyzer();
+        let averages = analyzer.daily_averages();
+        // 78 days and 3 hours between 2024-01-02T09:00 and 2024-03-20T12:00
+        let days = 78.0 + 3.0 / 24.0;
+        assert!((averages["work"] - 3.0 / days).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,335 @@
+//! Batch edits across many profiles.
+//!
+//! A [`ProfileUpdate`] changes one field of every profile selected by a filter,
+//! for example moving all work profiles to a new email domain. The update runs
+//! as a transaction, so either every selected profile is changed or none is.
+//! [`BatchResult`] reports which profiles of a batch create were stored.
+
+use super::{transaction::run_transaction, ProfileManager};
+use crate::{
+    config::types::{KeyType, Profile},
+    error::{GitSetupError, Result},
+};
+use clap::ValueEnum;
+
+/// Profile field changed by a bulk update or printed by `--list --field`.
+///
+/// Only the fields in [`ProfileField::UPDATABLE`] can be changed by a bulk
+/// update. Each field also accepts its [`Profile::get_field`] name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProfileField {
+    /// The profile name
+    Name,
+    /// The whole git user email
+    #[value(alias = "git_user_email")]
+    Email,
+    /// The domain part of the git user email
+    #[value(alias = "email_domain")]
+    EmailDomain,
+    /// The git user name
+    #[value(alias = "git_user_name")]
+    UserName,
+    /// The signing key type (`ssh`, `gpg`, `x509`, `gitsign`)
+    #[value(alias = "key_type")]
+    KeyType,
+    /// The signing key
+    #[value(alias = "signing_key")]
+    SigningKey,
+    /// The 1Password vault
+    #[value(alias = "vault_name")]
+    Vault,
+    /// The 1Password item title of the SSH key
+    #[value(alias = "ssh_key_title")]
+    SshKeyTitle,
+    /// The scope the profile is applied at
+    Scope,
+    /// Where the SSH key is read from
+    #[value(alias = "ssh_key_source")]
+    SshKeySource,
+    /// The SSH key file
+    #[value(alias = "ssh_key_path")]
+    SshKeyPath,
+    /// The allowed signers file
+    #[value(alias = "allowed_signers")]
+    AllowedSigners,
+    /// Patterns selecting the profile by remote
+    #[value(alias = "match_patterns")]
+    MatchPatterns,
+    /// Repositories the profile is used in
+    Repos,
+    /// Directories the profile is included for
+    #[value(alias = "include_if_dirs")]
+    IncludeIfDirs,
+    /// Hosts the profile is used for
+    #[value(alias = "host_patterns")]
+    HostPatterns,
+    /// Whether the key is stored in 1Password
+    #[value(alias = "one_password")]
+    OnePassword,
+    /// Whether the key needs a passphrase
+    #[value(alias = "require_passphrase")]
+    RequirePassphrase,
+    /// Whether the profile is archived
+    #[value(alias = "is_archived")]
+    IsArchived,
+    /// The profile description
+    Description,
+    /// Scopes of single git config keys
+    #[value(alias = "per_field_scope")]
+    PerFieldScope,
+    /// The git hooks directory
+    #[value(alias = "git_hooks_dir")]
+    GitHooksDir,
+    /// Inline allowed signers entries
+    #[value(alias = "allowed_signers_content")]
+    AllowedSignersContent,
+    /// The secret store holding the key
+    #[value(alias = "secret_store")]
+    SecretStore,
+    /// The 1Password item of the previous key
+    #[value(alias = "rotated_key_item")]
+    RotatedKeyItem,
+    /// The profile tags
+    Tags,
+}
+
+impl ProfileField {
+    /// Fields a bulk update can change.
+    pub const UPDATABLE: &'static [ProfileField] = &[
+        ProfileField::Email,
+        ProfileField::EmailDomain,
+        ProfileField::UserName,
+        ProfileField::KeyType,
+        ProfileField::SigningKey,
+        ProfileField::Vault,
+    ];
+
+    /// Name of the field for [`Profile::get_field`].
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            ProfileField::Name => "name",
+            ProfileField::Email => "git_user_email",
+            ProfileField::EmailDomain => "email_domain",
+            ProfileField::UserName => "git_user_name",
+            ProfileField::KeyType => "key_type",
+            ProfileField::SigningKey => "signing_key",
+            ProfileField::Vault => "vault_name",
+            ProfileField::SshKeyTitle => "ssh_key_title",
+            ProfileField::Scope => "scope",
+            ProfileField::SshKeySource => "ssh_key_source",
+            ProfileField::SshKeyPath => "ssh_key_path",
+            ProfileField::AllowedSigners => "allowed_signers",
+            ProfileField::MatchPatterns => "match_patterns",
+            ProfileField::Repos => "repos",
+            ProfileField::IncludeIfDirs => "include_if_dirs",
+            ProfileField::HostPatterns => "host_patterns",
+            ProfileField::OnePassword => "one_password",
+            ProfileField::RequirePassphrase => "require_passphrase",
+            ProfileField::IsArchived => "is_archived",
+            ProfileField::Description => "description",
+            ProfileField::PerFieldScope => "per_field_scope",
+            ProfileField::GitHooksDir => "git_hooks_dir",
+            ProfileField::AllowedSignersContent => "allowed_signers_content",
+            ProfileField::SecretStore => "secret_store",
+            ProfileField::RotatedKeyItem => "rotated_key_item",
+            ProfileField::Tags => "tags",
+        }
+    }
+
+    /// Current value of the field in `profile`, formatted as it would be given on the command line.
+    pub fn value_of(&self, profile: &Profile) -> Option<String> {
+        match self {
+            ProfileField::Email => Some(profile.git_user_email.clone()),
+            ProfileField::EmailDomain => email_domain(&profile.git_user_email).map(str::to_string),
+            ProfileField::UserName => profile.git_user_name.clone(),
+            ProfileField::KeyType => Some(format!("{:?}", profile.key_type).to_lowercase()),
+            ProfileField::SigningKey => profile.signing_key.clone(),
+            ProfileField::Vault => profile.vault_name.clone(),
+            field => profile.get_field(field.field_name()),
+        }
+    }
+}
+
+/// A single field change applied to many profiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileUpdate {
+    pub field: ProfileField,
+    pub new_value: String,
+}
+
+impl ProfileUpdate {
+    /// Create an update setting `field` to `new_value`.
+    pub fn new(field: ProfileField, new_value: impl Into<String>) -> Self {
+        Self {
+            field,
+            new_value: new_value.into(),
+        }
+    }
+
+    /// Apply the update to `profile`, returning whether anything changed.
+    pub fn apply(&self, profile: &mut Profile) -> Result<bool> {
+        let value = self.new_value.as_str();
+        let changed = match self.field {
+            ProfileField::Email => replace(&mut profile.git_user_email, value.to_string()),
+            ProfileField::EmailDomain => {
+                let (local, _) = profile.git_user_email.rsplit_once('@').ok_or_else(|| {
+                    GitSetupError::InvalidProfile {
+                        reason: format!("profile '{}' has no email domain", profile.name),
+                    }
+                })?;
+                let email = format!("{}@{}", local, value);
+                replace(&mut profile.git_user_email, email)
+            }
+            ProfileField::UserName => replace(&mut profile.git_user_name, Some(value.to_string())),
+            ProfileField::KeyType => replace(&mut profile.key_type, value.parse::<KeyType>()?),
+            ProfileField::SigningKey => replace(&mut profile.signing_key, Some(value.to_string())),
+            ProfileField::Vault => replace(&mut profile.vault_name, Some(value.to_string())),
+            field => {
+                return Err(GitSetupError::InvalidProfile {
+                    reason: format!("field '{}' cannot be changed by a bulk update", field.field_name()),
+                });
+            }
+        };
+        Ok(changed)
+    }
+}
+
+/// Outcome of [`ProfileManager::batch_create`].
+#[derive(Debug, Default)]
+pub struct BatchResult {
+    /// Number of profiles created
+    pub created: usize,
+    /// Name and error of every profile that was not created
+    pub failed: Vec<(String, GitSetupError)>,
+}
+
+impl BatchResult {
+    /// Whether every profile of the batch was created.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Set `target` to `value`, returning whether it changed.
+fn replace<T: PartialEq>(target: &mut T, value: T) -> bool {
+    if *target == value {
+        return false;
+    }
+    *target = value;
+    true
+}
+
+/// Domain part of an email address.
+pub fn email_domain(email: &str) -> Option<&str> {
+    email.rsplit_once('@').map(|(_, domain)| domain)
+}
+
+/// Apply `update` to every profile matching `filter`, returning how many changed.
+///
+/// Nothing is changed if any profile fails to update.
+pub fn bulk_update<F>(manager: &dyn ProfileManager, filter: F, update: &ProfileUpdate) -> Result<usize>
+where
+    F: Fn(&Profile) -> bool,
+{
+    let mut updated = 0;
+    run_transaction(manager, |m| {
+        for mut profile in m.list()?.into_iter().filter(|p| filter(p)) {
+            if update.apply(&mut profile)? {
+                let name = profile.name.clone();
+                m.update(&name, profile)?;
+                updated += 1;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::mock::MockProfileManager;
+
+    fn profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: email.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn manager() -> MockProfileManager {
+        MockProfileManager::with_profiles(vec![
+            profile("work", "jane@old.com"),
+            profile("work-oss", "jane.oss@old.com"),
+            profile("personal", "jane@home.org"),
+        ])
+        .track_calls(true)
+    }
+
+    #[test]
+    fn test_bulk_update_email_domain() {
+        let manager = manager();
+        let update = ProfileUpdate::new(ProfileField::EmailDomain, "new.com");
+
+        let count = manager
+            .bulk_update(|p| email_domain(&p.git_user_email) == Some("old.com"), update)
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(manager.read("work").unwrap().unwrap().git_user_email, "jane@new.com");
+        assert_eq!(manager.read("work-oss").unwrap().unwrap().git_user_email, "jane.oss@new.com");
+        assert_eq!(manager.read("personal").unwrap().unwrap().git_user_email, "jane@home.org");
+        assert_eq!(manager.update_call_count(), 2);
+        assert!(!manager.was_called_with_update("personal"));
+    }
+
+    #[test]
+    fn test_bulk_update_key_type() {
+        let manager = manager();
+        let update = ProfileUpdate::new(ProfileField::KeyType, "gpg");
+
+        assert_eq!(manager.bulk_update(|p| p.name.starts_with("work"), update.clone()).unwrap(), 2);
+        // Profiles that already have the value are not counted
+        assert_eq!(manager.bulk_update(|_| true, update).unwrap(), 1);
+        assert_eq!(manager.read("personal").unwrap().unwrap().key_type, KeyType::Gpg);
+        assert_eq!(manager.update_call_count(), 3);
+    }
+
+    #[test]
+    fn test_bulk_update_is_all_or_nothing() {
+        let manager = MockProfileManager::with_profiles(vec![
+            profile("work", "jane@old.com"),
+            profile("broken", "no-domain"),
+        ]);
+        let update = ProfileUpdate::new(ProfileField::EmailDomain, "new.com");
+
+        assert!(manager.bulk_update(|_| true, update).is_err());
+        assert_eq!(manager.read("work").unwrap().unwrap().git_user_email, "jane@old.com");
+    }
+
+    #[test]
+    fn test_field_value_of() {
+        let profile = profile("work", "jane@old.com");
+        assert_eq!(ProfileField::EmailDomain.value_of(&profile).as_deref(), Some("old.com"));
+        assert_eq!(ProfileField::KeyType.value_of(&profile).as_deref(), Some("ssh"));
+        assert_eq!(ProfileField::Vault.value_of(&profile), None);
+    }
+
+    #[test]
+    fn test_every_field_is_readable() {
+        let profile = profile("work", "jane@old.com");
+        for field in ProfileField::value_variants() {
+            assert!(profile.get_field(field.field_name()).is_some(), "{:?}", field);
+        }
+
+        let update = ProfileUpdate::new(ProfileField::Tags, "oss");
+        assert!(update.apply(&mut profile.clone()).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_type() {
+        let update = ProfileUpdate::new(ProfileField::KeyType, "rsa");
+        assert!(update.apply(&mut profile("work", "jane@old.com")).is_err());
+    }
+}
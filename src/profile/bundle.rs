@@ -0,0 +1,215 @@
+//! Profile bundles for moving profiles between machines.
+//!
+//! A [`ProfileBundle`] is a JSON document holding profiles behind a format
+//! name and version header, so a reader can reject bundles it does not
+//! understand. The profiles can be encrypted to the recipient's GPG key and
+//! signed with the exporter's key; reading such a bundle verifies the
+//! signature and the signer before anything is decrypted or parsed.
+//!
+//! Unsigned bundles are plain JSON, no more trusted than any other profile
+//! file, and import without a check unless a trusted signer is required.
+
+use crate::{
+    config::types::Profile,
+    error::{GitSetupError, Result},
+    external::gpg::GpgWrapper,
+    output::{JsonFormatter, OutputFormatter},
+};
+use serde::{Deserialize, Serialize};
+
+/// Format name in the header of every bundle.
+pub const BUNDLE_FORMAT: &str = "git-setup-profiles";
+
+/// Version of the bundle layout written by this version of git-setup.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Profiles written by `--export` and read by `--import --from-bundle`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileBundle {
+    pub format: String,
+    pub version: u32,
+    /// GPG key the payload is encrypted to, if encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_for: Option<String>,
+    /// The profiles as a JSON array, or an armored GPG message holding it
+    pub payload: String,
+    /// Detached signature of `payload`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl ProfileBundle {
+    /// An unencrypted bundle of `profiles`.
+    pub fn new(profiles: &[Profile]) -> Result<Self> {
+        Ok(Self {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            encrypted_for: None,
+            payload: JsonFormatter::new().format_profiles(profiles)?,
+            signature: None,
+        })
+    }
+
+    /// A bundle of `profiles` encrypted to `recipient` and signed with the secret key `signer`.
+    pub fn encrypted(profiles: &[Profile], recipient: &str, signer: &str, gpg: &dyn GpgWrapper) -> Result<Self> {
+        let plain = Self::new(profiles)?;
+        let payload = gpg.encrypt_data(&plain.payload, recipient)?;
+        Ok(Self {
+            encrypted_for: Some(recipient.to_string()),
+            payload,
+            ..plain
+        }
+        .signed(signer, gpg)?)
+    }
+
+    /// The bundle with its payload signed by the secret key `signer`.
+    pub fn signed(self, signer: &str, gpg: &dyn GpgWrapper) -> Result<Self> {
+        let signature = gpg.sign_data(&self.payload, signer, None)?;
+        Ok(Self {
+            signature: Some(signature),
+            ..self
+        })
+    }
+
+    /// Parse a bundle, rejecting other formats and later versions.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(json)?;
+        if bundle.format != BUNDLE_FORMAT {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!("'{}' is not a git-setup profile bundle", bundle.format),
+            });
+        }
+        if bundle.version > BUNDLE_VERSION {
+            return Err(GitSetupError::InvalidProfile {
+                reason: format!(
+                    "profile bundle version {} is newer than the supported version {}",
+                    bundle.version, BUNDLE_VERSION
+                ),
+            });
+        }
+        Ok(bundle)
+    }
+
+    /// The bundle as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// The profiles of the bundle.
+    ///
+    /// A signature is verified before the payload is decrypted or parsed, and
+    /// must be made by `trusted_signer`. Encrypted bundles must be signed, and
+    /// so must every bundle when a `trusted_signer` is given.
+    pub fn profiles(&self, gpg: &dyn GpgWrapper, trusted_signer: Option<&str>) -> Result<Vec<Profile>> {
+        match (&self.signature, trusted_signer) {
+            (Some(signature), trusted_signer) => {
+                let signer = gpg.signature_signer(&self.payload, signature)?.ok_or_else(|| {
+                    GitSetupError::InvalidProfile {
+                        reason: "the signature of the profile bundle does not verify".to_string(),
+                    }
+                })?;
+                match trusted_signer {
+                    Some(trusted_signer) if is_same_key(&signer, trusted_signer) => {}
+                    Some(trusted_signer) => {
+                        return Err(GitSetupError::InvalidProfile {
+                            reason: format!(
+                                "the profile bundle is signed by {}, not by the trusted signer {}",
+                                signer, trusted_signer
+                            ),
+                        });
+                    }
+                    None => {
+                        return Err(GitSetupError::InvalidProfile {
+                            reason: format!(
+                                "the profile bundle is signed by {}; name the key you expect with --trusted-signer",
+                                signer
+                            ),
+                        });
+                    }
+                }
+            }
+            (None, _) if self.encrypted_for.is_some() => {
+                return Err(GitSetupError::InvalidProfile {
+                    reason: "the encrypted profile bundle is not signed".to_string(),
+                });
+            }
+            (None, Some(trusted_signer)) => {
+                return Err(GitSetupError::InvalidProfile {
+                    reason: format!("the profile bundle is not signed by the trusted signer {}", trusted_signer),
+                });
+            }
+            (None, None) => {}
+        }
+
+        let payload = match &self.encrypted_for {
+            Some(_) => gpg.decrypt_data(&self.payload)?,
+            None => self.payload.clone(),
+        };
+        Ok(serde_json::from_str(&payload)?)
+    }
+}
+
+/// Whether `key_id`, a fingerprint or key ID, names the key with `fingerprint`.
+fn is_same_key(fingerprint: &str, key_id: &str) -> bool {
+    let key_id = key_id.trim_start_matches("0x").to_uppercase();
+    !key_id.is_empty() && fingerprint.to_uppercase().ends_with(&key_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::gpg::MockGpgWrapper;
+
+    fn profiles() -> Vec<Profile> {
+        vec![Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn test_plain_round_trip() {
+        let gpg = MockGpgWrapper::new();
+        let json = ProfileBundle::new(&profiles()).unwrap().to_json().unwrap();
+
+        let bundle = ProfileBundle::from_json(&json).unwrap();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.signature, None);
+        assert_eq!(bundle.profiles(&gpg, None).unwrap(), profiles());
+
+        let result = bundle.profiles(&gpg, Some("EF561234"));
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+    }
+
+    #[test]
+    fn test_encrypted_round_trip() {
+        let gpg = MockGpgWrapper::new();
+        let bundle = ProfileBundle::encrypted(&profiles(), "ABCD1234", "0123EF561234", &gpg).unwrap();
+
+        assert_eq!(bundle.encrypted_for.as_deref(), Some("ABCD1234"));
+        assert_ne!(bundle.payload, ProfileBundle::new(&profiles()).unwrap().payload);
+        assert_eq!(gpg.sign_data_calls(), vec!["0123EF561234".to_string()]);
+        assert_eq!(bundle.profiles(&gpg, Some("0123EF561234")).unwrap(), profiles());
+        assert_eq!(bundle.profiles(&gpg, Some("0xef561234")).unwrap(), profiles());
+
+        for trusted_signer in [None, Some("ABCD1234")] {
+            let result = bundle.profiles(&gpg, trusted_signer);
+            assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        }
+
+        let unsigned = ProfileBundle { signature: None, ..bundle };
+        assert!(matches!(unsigned.profiles(&gpg, None), Err(GitSetupError::InvalidProfile { .. })));
+    }
+
+    #[test]
+    fn test_rejects_unknown_headers() {
+        let mut bundle = ProfileBundle::new(&profiles()).unwrap();
+        bundle.version = BUNDLE_VERSION + 1;
+        let result = ProfileBundle::from_json(&bundle.to_json().unwrap());
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+
+        let result = ProfileBundle::from_json(r#"{"format": "other", "version": 1, "payload": "[]"}"#);
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+    }
+}
@@ -0,0 +1,169 @@
+//! Profile counts grouped by key type, scope and 1Password use.
+//!
+//! These back `--list --count-by-key-type` (and its scope and 1Password
+//! variants) and the `--profiles-md` summary, e.g.
+//! `SSH: 12, GPG: 3, Gitsign: 5, X509: 1, Total: 21`.
+
+use crate::config::types::{KeyType, Profile, Scope};
+use serde::Serialize;
+use std::fmt;
+
+/// Number of profiles using each key type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileCountsByKeyType {
+    pub ssh: usize,
+    pub gpg: usize,
+    pub gitsign: usize,
+    pub x509: usize,
+}
+
+impl ProfileCountsByKeyType {
+    /// Count `profiles` by key type.
+    pub fn from_profiles(profiles: &[Profile]) -> Self {
+        let mut counts = Self::default();
+        for profile in profiles {
+            match profile.key_type {
+                KeyType::Ssh => counts.ssh += 1,
+                KeyType::Gpg => counts.gpg += 1,
+                KeyType::Gitsign => counts.gitsign += 1,
+                KeyType::X509 => counts.x509 += 1,
+            }
+        }
+        counts
+    }
+
+    /// Labels and counts, in display order.
+    pub fn rows(&self) -> Vec<(&'static str, usize)> {
+        vec![("SSH", self.ssh), ("GPG", self.gpg), ("Gitsign", self.gitsign), ("X509", self.x509)]
+    }
+
+    /// Number of profiles counted.
+    pub fn total(&self) -> usize {
+        self.ssh + self.gpg + self.gitsign + self.x509
+    }
+}
+
+/// Number of profiles applying to each git config scope.
+///
+/// Profiles without a scope are counted as `unset`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileCountsByScope {
+    pub local: usize,
+    pub global: usize,
+    pub system: usize,
+    pub unset: usize,
+}
+
+impl ProfileCountsByScope {
+    /// Count `profiles` by scope.
+    pub fn from_profiles(profiles: &[Profile]) -> Self {
+        let mut counts = Self::default();
+        for profile in profiles {
+            match profile.scope {
+                Some(Scope::Local) => counts.local += 1,
+                Some(Scope::Global) => counts.global += 1,
+                Some(Scope::System) => counts.system += 1,
+                None => counts.unset += 1,
+            }
+        }
+        counts
+    }
+
+    /// Labels and counts, in display order.
+    pub fn rows(&self) -> Vec<(&'static str, usize)> {
+        vec![("Local", self.local), ("Global", self.global), ("System", self.system), ("Unset", self.unset)]
+    }
+
+    /// Number of profiles counted.
+    pub fn total(&self) -> usize {
+        self.local + self.global + self.system + self.unset
+    }
+}
+
+/// Number of profiles with and without 1Password integration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileCountsByOnePassword {
+    pub yes: usize,
+    pub no: usize,
+}
+
+impl ProfileCountsByOnePassword {
+    /// Count `profiles` by whether they use 1Password.
+    pub fn from_profiles(profiles: &[Profile]) -> Self {
+        let yes = profiles.iter().filter(|profile| profile.one_password).count();
+        Self {
+            yes,
+            no: profiles.len() - yes,
+        }
+    }
+
+    /// Labels and counts, in display order.
+    pub fn rows(&self) -> Vec<(&'static str, usize)> {
+        vec![("Yes", self.yes), ("No", self.no)]
+    }
+
+    /// Number of profiles counted.
+    pub fn total(&self) -> usize {
+        self.yes + self.no
+    }
+}
+
+/// Render `rows` as `Label: n, ..., Total: n`.
+fn write_counts(f: &mut fmt::Formatter<'_>, rows: Vec<(&str, usize)>, total: usize) -> fmt::Result {
+    for (label, count) in rows {
+        write!(f, "{}: {}, ", label, count)?;
+    }
+    write!(f, "Total: {}", total)
+}
+
+impl fmt::Display for ProfileCountsByKeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_counts(f, self.rows(), self.total())
+    }
+}
+
+impl fmt::Display for ProfileCountsByScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_counts(f, self.rows(), self.total())
+    }
+}
+
+impl fmt::Display for ProfileCountsByOnePassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_counts(f, self.rows(), self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(key_type: KeyType, scope: Option<Scope>, one_password: bool) -> Profile {
+        Profile {
+            key_type,
+            scope,
+            one_password,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_counts() {
+        let profiles = vec![
+            profile(KeyType::Ssh, Some(Scope::Local), true),
+            profile(KeyType::Ssh, Some(Scope::Global), false),
+            profile(KeyType::Gpg, None, false),
+            profile(KeyType::Gitsign, Some(Scope::Local), true),
+        ];
+
+        let by_key_type = ProfileCountsByKeyType::from_profiles(&profiles);
+        assert_eq!(by_key_type.to_string(), "SSH: 2, GPG: 1, Gitsign: 1, X509: 0, Total: 4");
+
+        let by_scope = ProfileCountsByScope::from_profiles(&profiles);
+        assert_eq!(by_scope.to_string(), "Local: 2, Global: 1, System: 0, Unset: 1, Total: 4");
+
+        let by_one_password = ProfileCountsByOnePassword::from_profiles(&profiles);
+        assert_eq!(by_one_password, ProfileCountsByOnePassword { yes: 2, no: 2 });
+        assert_eq!(by_one_password.to_string(), "Yes: 2, No: 2, Total: 4");
+    }
+}
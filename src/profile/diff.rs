@@ -0,0 +1,265 @@
+//! Diff between a profile and the git config it would be applied to.
+//!
+//! [`ProfileDiff::compute`] lists the git config keys a profile manages with
+//! the value git has now and the value the profile sets, so a user can see
+//! what applying the profile would change before running it.
+//! [`ProfileDiff::compare`] lists the fields that differ between two profiles.
+
+use crate::{
+    config::types::{KeyType, Profile},
+    error::Result,
+    external::git::{GitConfigScope, GitWrapper},
+};
+use serde::Serialize;
+
+/// ANSI escape sequences used by [`ProfileDiff::render`] on terminals
+const GREY: &str = "\x1b[90m";
+const RESET: &str = "\x1b[0m";
+
+/// How a git config value changes when the profile is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    /// The key is not set in git; the profile sets it
+    Added,
+    /// The key is set in git but not part of the profile
+    Removed,
+    /// The key is set in git to a different value than the profile's
+    Changed,
+    /// The key already has the profile's value
+    Unchanged,
+}
+
+/// One git config key with its current and profile values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    /// Git config key (e.g. `user.email`)
+    pub field: String,
+    /// Value git has now at the compared scope
+    pub current: Option<String>,
+    /// Value the profile sets
+    pub new: Option<String>,
+    pub status: DiffStatus,
+}
+
+/// A profile field that differs between two profiles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDiff {
+    /// Field name as in [`Profile::FIELD_NAMES`]
+    pub field: String,
+    /// Value in the first profile, formatted like [`Profile::get_field`]
+    pub old: String,
+    /// Value in the second profile
+    pub new: String,
+}
+
+/// Computes and renders profile diffs.
+pub struct ProfileDiff;
+
+impl ProfileDiff {
+    /// Compare the config `profile` sets with the values `git` has at `scope`.
+    ///
+    /// Keys that neither git nor the profile sets are left out. A signing key
+    /// fetched from 1Password when the profile is applied is not compared.
+    pub fn compute(profile: &Profile, git: &dyn GitWrapper, scope: GitConfigScope) -> Result<Vec<DiffLine>> {
+        let mut lines = Vec::new();
+        for (field, new) in Self::expected_config(profile) {
+            let current = git.get_config(field, Some(scope.clone()))?;
+            let status = match (&current, &new) {
+                (None, None) => continue,
+                (None, Some(_)) => DiffStatus::Added,
+                (Some(_), None) => DiffStatus::Removed,
+                (Some(current), Some(new)) if current == new => DiffStatus::Unchanged,
+                (Some(_), Some(_)) => DiffStatus::Changed,
+            };
+            lines.push(DiffLine { field: field.to_string(), current, new, status });
+        }
+        Ok(lines)
+    }
+
+    /// The fields whose values differ between `old` and `new`, in declaration order.
+    ///
+    /// The names are not compared, as two profiles always differ in them.
+    pub fn compare(old: &Profile, new: &Profile) -> Vec<FieldDiff> {
+        Profile::FIELD_NAMES
+            .iter()
+            .filter(|field| **field != "name")
+            .filter_map(|field| {
+                let old = old.get_field(field).unwrap_or_default();
+                let new = new.get_field(field).unwrap_or_default();
+                (old != new).then(|| FieldDiff { field: field.to_string(), old, new })
+            })
+            .collect()
+    }
+
+    /// The git config keys the profile manages and the values it sets for them
+    fn expected_config(profile: &Profile) -> Vec<(&'static str, Option<String>)> {
+        let set = |value: &str| Some(value.to_string());
+        let (format, program) = match profile.key_type {
+            KeyType::Ssh => ("ssh", None),
+            KeyType::Gpg => ("openpgp", None),
+            KeyType::Gitsign => ("x509", set("gitsign")),
+            KeyType::X509 => ("x509", set("smimesign")),
+        };
+        let allowed_signers = match profile.key_type {
+            KeyType::Ssh => profile.allowed_signers.clone(),
+            _ => None,
+        };
+
+        let mut config = vec![("user.email", Some(profile.git_user_email.clone()))];
+        // Applying leaves user.name alone when the profile has none
+        if profile.git_user_name.is_some() {
+            config.push(("user.name", profile.git_user_name.clone()));
+        }
+        config.push(("gpg.format", set(format)));
+        match profile.key_type {
+            KeyType::Ssh | KeyType::Gpg if profile.key_store().is_some() && profile.signing_key.is_none() => {}
+            KeyType::Ssh | KeyType::Gpg => config.push(("user.signingkey", profile.signing_key.clone())),
+            KeyType::X509 | KeyType::Gitsign => config.push(("user.signingkey", None)),
+        }
+        config.push(("gpg.ssh.allowedSignersFile", allowed_signers));
+        config.push(("gpg.x509.program", program));
+        config.push(("commit.gpgsign", set("true")));
+        config
+    }
+
+    /// Render `lines` as `-`/`+` lines, with unchanged lines in grey when `color` is set.
+    pub fn render(lines: &[DiffLine], color: bool) -> String {
+        let mut output = String::new();
+        for line in lines {
+            let entry = |value: &Option<String>| format!("{} = {}", line.field, value.as_deref().unwrap_or_default());
+            match line.status {
+                DiffStatus::Unchanged if color => {
+                    output.push_str(&format!("{}  {}{}\n", GREY, entry(&line.current), RESET))
+                }
+                DiffStatus::Unchanged => output.push_str(&format!("  {}\n", entry(&line.current))),
+                DiffStatus::Added => output.push_str(&format!("+ {}\n", entry(&line.new))),
+                DiffStatus::Removed => output.push_str(&format!("- {}\n", entry(&line.current))),
+                DiffStatus::Changed => {
+                    output.push_str(&format!("- {}\n", entry(&line.current)));
+                    output.push_str(&format!("+ {}\n", entry(&line.new)));
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::git::MockGitWrapper;
+    use std::collections::HashMap;
+
+    fn test_git() -> MockGitWrapper {
+        MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Global,
+            HashMap::from([
+                ("user.email".to_string(), "old@example.com".to_string()),
+                ("user.name".to_string(), "Jane Doe".to_string()),
+                ("gpg.format".to_string(), "x509".to_string()),
+                ("gpg.x509.program".to_string(), "gitsign".to_string()),
+            ]),
+        )
+    }
+
+    fn status_of(lines: &[DiffLine], field: &str) -> Option<DiffStatus> {
+        lines.iter().find(|line| line.field == field).map(|line| line.status)
+    }
+
+    #[test]
+    fn test_compute_diff() {
+        let profile = Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Jane Doe".to_string()),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("ssh-ed25519 AAAAC3...".to_string()),
+            ..Default::default()
+        };
+        let lines = ProfileDiff::compute(&profile, &test_git(), GitConfigScope::Global).unwrap();
+
+        assert_eq!(status_of(&lines, "user.email"), Some(DiffStatus::Changed));
+        assert_eq!(status_of(&lines, "user.name"), Some(DiffStatus::Unchanged));
+        assert_eq!(status_of(&lines, "gpg.format"), Some(DiffStatus::Changed));
+        assert_eq!(status_of(&lines, "user.signingkey"), Some(DiffStatus::Added));
+        assert_eq!(status_of(&lines, "gpg.x509.program"), Some(DiffStatus::Removed));
+        assert_eq!(status_of(&lines, "commit.gpgsign"), Some(DiffStatus::Added));
+        assert_eq!(status_of(&lines, "gpg.ssh.allowedSignersFile"), None);
+
+        // Nothing is set at another scope
+        let lines = ProfileDiff::compute(&profile, &test_git(), GitConfigScope::Local).unwrap();
+        assert!(lines.iter().all(|line| line.status == DiffStatus::Added));
+    }
+
+    #[test]
+    fn test_compute_diff_skips_1password_key() {
+        let profile = Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Ssh,
+            one_password: true,
+            ..Default::default()
+        };
+        let lines = ProfileDiff::compute(&profile, &test_git(), GitConfigScope::Global).unwrap();
+
+        assert_eq!(status_of(&lines, "user.signingkey"), None);
+        assert_eq!(status_of(&lines, "user.name"), None);
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let line = |field: &str, current: Option<&str>, new: Option<&str>, status| DiffLine {
+            field: field.to_string(),
+            current: current.map(str::to_string),
+            new: new.map(str::to_string),
+            status,
+        };
+        let lines = vec![
+            line("user.email", Some("old@example.com"), Some("new@example.com"), DiffStatus::Changed),
+            line("user.name", Some("Jane"), Some("Jane"), DiffStatus::Unchanged),
+            line("commit.gpgsign", None, Some("true"), DiffStatus::Added),
+            line("gpg.x509.program", Some("gitsign"), None, DiffStatus::Removed),
+        ];
+
+        assert_eq!(
+            ProfileDiff::render(&lines, false),
+            "- user.email = old@example.com\n+ user.email = new@example.com\n  user.name = Jane\n\
+             + commit.gpgsign = true\n- gpg.x509.program = gitsign\n"
+        );
+        assert!(ProfileDiff::render(&lines, true).contains("\x1b[90m  user.name = Jane\x1b[0m"));
+    }
+
+    #[test]
+    fn test_compare_profiles() {
+        let work = Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Ssh,
+            signing_key: Some("~/.ssh/id_work".to_string()),
+            repos: vec!["git@github.com:acme/*".to_string()],
+            ..Default::default()
+        };
+        let work_eu = Profile {
+            name: "work-eu".to_string(),
+            key_type: KeyType::Gpg,
+            signing_key: None,
+            repos: vec!["git@github.com:acme/*".to_string(), "git@github.com:acme-eu/*".to_string()],
+            ..work.clone()
+        };
+
+        let field = |field: &str, old: &str, new: &str| FieldDiff {
+            field: field.to_string(),
+            old: old.to_string(),
+            new: new.to_string(),
+        };
+        assert_eq!(
+            ProfileDiff::compare(&work, &work_eu),
+            vec![
+                field("key_type", "ssh", "gpg"),
+                field("signing_key", "~/.ssh/id_work", ""),
+                field("repos", "git@github.com:acme/*", "git@github.com:acme/*,git@github.com:acme-eu/*"),
+            ]
+        );
+        assert!(ProfileDiff::compare(&work, &Profile { name: "copy".to_string(), ..work.clone() }).is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+//! Detection of profiles with conflicting settings.
+//!
+//! Profiles that share an email, a signing key or a 1Password SSH key are
+//! usually accidental copies of each other.
+
+use crate::config::types::Profile;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Setting that a group of profiles has in common.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    /// Same `git_user_email`
+    Email,
+    /// Same `signing_key`
+    SigningKey,
+    /// Same `vault_name` and `ssh_key_title`
+    OnePasswordKey,
+}
+
+impl fmt::Display for DuplicateReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplicateReason::Email => write!(f, "email"),
+            DuplicateReason::SigningKey => write!(f, "signing key"),
+            DuplicateReason::OnePasswordKey => write!(f, "1Password key"),
+        }
+    }
+}
+
+/// Profiles that share a setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateGroup {
+    /// Which setting the profiles share
+    pub reason: DuplicateReason,
+    /// The shared value
+    pub field_value: String,
+    /// Names of the profiles in the group, in list order
+    pub profiles: Vec<String>,
+}
+
+/// Group `profiles` by `key`, keeping only groups with more than one profile.
+fn group_by<F>(profiles: &[Profile], reason: DuplicateReason, key: F) -> Vec<DuplicateGroup>
+where
+    F: Fn(&Profile) -> Option<String>,
+{
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for profile in profiles {
+        if let Some(value) = key(profile).filter(|value| !value.is_empty()) {
+            groups.entry(value).or_default().push(profile.name.clone());
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(field_value, profiles)| DuplicateGroup {
+            reason,
+            field_value,
+            profiles,
+        })
+        .collect()
+}
+
+/// Find groups of profiles with the same email, signing key or 1Password SSH key.
+pub fn find_duplicates(profiles: &[Profile]) -> Vec<DuplicateGroup> {
+    let mut groups = group_by(profiles, DuplicateReason::Email, |p| Some(p.git_user_email.clone()));
+    groups.extend(group_by(profiles, DuplicateReason::SigningKey, |p| p.signing_key.clone()));
+    groups.extend(group_by(profiles, DuplicateReason::OnePasswordKey, |p| {
+        match (&p.vault_name, &p.ssh_key_title) {
+            (Some(vault), Some(title)) => Some(format!("{}/{}", vault, title)),
+            _ => None,
+        }
+    }));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: email.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let profiles = vec![
+            Profile {
+                signing_key: Some("ssh-ed25519 AAAA".to_string()),
+                vault_name: Some("Work".to_string()),
+                ssh_key_title: Some("laptop".to_string()),
+                ..profile("work", "jane@work.com")
+            },
+            Profile {
+                signing_key: Some("ssh-ed25519 AAAA".to_string()),
+                ..profile("personal", "jane@home.com")
+            },
+            Profile {
+                vault_name: Some("Work".to_string()),
+                ssh_key_title: Some("laptop".to_string()),
+                ..profile("work-copy", "jane@work.com")
+            },
+            profile("oss", "jane@oss.org"),
+        ];
+
+        let groups = find_duplicates(&profiles);
+        assert_eq!(
+            groups,
+            vec![
+                DuplicateGroup {
+                    reason: DuplicateReason::Email,
+                    field_value: "jane@work.com".to_string(),
+                    profiles: vec!["work".to_string(), "work-copy".to_string()],
+                },
+                DuplicateGroup {
+                    reason: DuplicateReason::SigningKey,
+                    field_value: "ssh-ed25519 AAAA".to_string(),
+                    profiles: vec!["work".to_string(), "personal".to_string()],
+                },
+                DuplicateGroup {
+                    reason: DuplicateReason::OnePasswordKey,
+                    field_value: "Work/laptop".to_string(),
+                    profiles: vec!["work".to_string(), "work-copy".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_duplicates_ignores_empty_values() {
+        let profiles = vec![profile("a", ""), profile("b", ""), profile("c", "c@example.com")];
+        assert!(find_duplicates(&profiles).is_empty());
+    }
+}
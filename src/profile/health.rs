@@ -0,0 +1,298 @@
+//! Composite health score of a profile.
+//!
+//! [`HealthScoreCalculator`] checks a profile's key, 1Password item, signing
+//! setup, key expiry, recent use, description and email uniqueness and sums
+//! the points of the passing checks into a score from 0 to 100.
+
+use super::{
+    activation::ActivationRecord,
+    duplicates::{find_duplicates, DuplicateReason},
+    validator::ProfileValidator,
+};
+use crate::{
+    commands::check_keys::is_literal_ssh_key,
+    config::types::{KeyType, Profile, SecretStoreKind},
+    error::Result,
+    external::{
+        git::GitWrapper,
+        gpg::{parse_expiration, GpgWrapper},
+        onepassword::OnePasswordWrapper,
+    },
+    platform::{PlatformPaths, SystemPlatform},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+/// GPG keys expiring within this many days count as expiring.
+const EXPIRY_WARNING_DAYS: i64 = 30;
+
+/// Profiles applied within this many days count as in use.
+const RECENT_ACTIVATION_DAYS: i64 = 7;
+
+/// Letter grade of a health score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    /// Grade of `score`: A from 90, B from 80, C from 70, D from 60, F below.
+    pub fn from_score(score: u8) -> Self {
+        match score {
+            90.. => Grade::A,
+            80..=89 => Grade::B,
+            70..=79 => Grade::C,
+            60..=69 => Grade::D,
+            _ => Grade::F,
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Health score of a profile and the points each check contributed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthScore {
+    pub score: u8,
+    pub components: Vec<(String, u8)>,
+    pub grade: Grade,
+}
+
+/// Computes health scores against the activation history and the other profiles.
+pub struct HealthScoreCalculator<'a> {
+    activations: &'a [ActivationRecord],
+    profiles: &'a [Profile],
+    now: DateTime<Utc>,
+}
+
+impl<'a> HealthScoreCalculator<'a> {
+    /// Create a calculator using `activations` for recent use and `profiles` for duplicate emails.
+    pub fn new(activations: &'a [ActivationRecord], profiles: &'a [Profile]) -> Self {
+        Self {
+            activations,
+            profiles,
+            now: Utc::now(),
+        }
+    }
+
+    /// Measure key expiry and recent use from `now` instead of the current time.
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Score `profile`.
+    ///
+    /// A check whose tool fails (e.g. 1Password is not signed in) scores no points.
+    pub fn calculate(
+        &self,
+        profile: &Profile,
+        git: &dyn GitWrapper,
+        gpg: &dyn GpgWrapper,
+        op: &dyn OnePasswordWrapper,
+    ) -> Result<HealthScore> {
+        let checks = [
+            ("Key exists locally", 20, Self::key_exists_locally(profile, gpg)),
+            ("1Password item exists", 20, Self::onepassword_item_exists(profile, op)),
+            ("Signing config valid", 20, Self::signing_config_valid(profile, git)),
+            ("No expiring keys", 20, !self.key_expiring(profile, gpg)),
+            ("Applied in last 7 days", 10, self.recently_applied(profile)),
+            ("Description set", 5, profile.description.as_deref().is_some_and(|d| !d.trim().is_empty())),
+            ("No duplicate email", 5, !self.email_duplicated(profile)),
+        ];
+
+        let components: Vec<(String, u8)> = checks
+            .into_iter()
+            .map(|(name, points, passed)| (name.to_string(), if passed { points } else { 0 }))
+            .collect();
+        let score = components.iter().map(|(_, points)| points).sum();
+
+        Ok(HealthScore {
+            score,
+            components,
+            grade: Grade::from_score(score),
+        })
+    }
+
+    /// The signing key is on disk (SSH) or in the keyring (GPG).
+    ///
+    /// x509 and gitsign keys are managed by their signing programs and always pass.
+    fn key_exists_locally(profile: &Profile, gpg: &dyn GpgWrapper) -> bool {
+        match profile.key_type {
+            KeyType::Ssh => profile
+                .ssh_key_path
+                .as_deref()
+                .or(profile.signing_key.as_deref().filter(|key| !is_literal_ssh_key(key)))
+                .is_some_and(|path| Path::new(&SystemPlatform.expand_path(path)).exists()),
+            KeyType::Gpg => profile
+                .signing_key
+                .as_deref()
+                .is_some_and(|key_id| matches!(gpg.get_key_info(key_id), Ok(Some(_)))),
+            KeyType::X509 | KeyType::Gitsign => true,
+        }
+    }
+
+    /// The profile's key item is found in its 1Password vault.
+    fn onepassword_item_exists(profile: &Profile, op: &dyn OnePasswordWrapper) -> bool {
+        let Some(title) = profile
+            .ssh_key_title
+            .as_deref()
+            .filter(|_| profile.key_store() == Some(SecretStoreKind::OnePassword))
+        else {
+            return false;
+        };
+        let vault = profile.vault_name.as_deref();
+        match profile.key_type {
+            KeyType::Ssh => op
+                .list_ssh_keys(vault)
+                .is_ok_and(|keys| keys.iter().any(|key| key.title == title)),
+            KeyType::Gpg => op
+                .list_gpg_keys(vault)
+                .is_ok_and(|keys| keys.iter().any(|key| key.title == title)),
+            KeyType::X509 | KeyType::Gitsign => false,
+        }
+    }
+
+    /// The profile is structurally valid and git can make a signed commit with its key.
+    fn signing_config_valid(profile: &Profile, git: &dyn GitWrapper) -> bool {
+        if !ProfileValidator::validate(profile).is_empty() {
+            return false;
+        }
+        let signing_key = match profile.key_type {
+            KeyType::Ssh => profile.ssh_key_path.as_deref().or(profile.signing_key.as_deref()),
+            KeyType::Gpg | KeyType::X509 => profile.signing_key.as_deref(),
+            // gitsign signs keylessly
+            KeyType::Gitsign => Some(profile.signing_key.as_deref().unwrap_or_default()),
+        };
+        signing_key.is_some_and(|key| git.test_sign(&profile.key_type, key).unwrap_or(false))
+    }
+
+    /// The profile's GPG key has expired or expires within [`EXPIRY_WARNING_DAYS`].
+    fn key_expiring(&self, profile: &Profile, gpg: &dyn GpgWrapper) -> bool {
+        if profile.key_type != KeyType::Gpg {
+            return false;
+        }
+        let expiration = profile
+            .signing_key
+            .as_deref()
+            .and_then(|key_id| gpg.get_key_info(key_id).ok().flatten())
+            .and_then(|info| info.expiration_date)
+            .and_then(|date| parse_expiration(&date));
+        expiration.is_some_and(|at| at <= self.now + Duration::days(EXPIRY_WARNING_DAYS))
+    }
+
+    /// The profile was applied within [`RECENT_ACTIVATION_DAYS`].
+    fn recently_applied(&self, profile: &Profile) -> bool {
+        let cutoff = self.now - Duration::days(RECENT_ACTIVATION_DAYS);
+        self.activations
+            .iter()
+            .any(|record| record.profile_name == profile.name && record.activated_at >= cutoff)
+    }
+
+    /// Another profile uses the same email.
+    fn email_duplicated(&self, profile: &Profile) -> bool {
+        find_duplicates(self.profiles).iter().any(|group| {
+            group.reason == DuplicateReason::Email && group.profiles.contains(&profile.name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::{
+        git::MockGitWrapper,
+        gpg::{GpgKeyInfo, MockGpgWrapper},
+        onepassword::MockOnePasswordWrapper,
+    };
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    fn gpg_profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: email.to_string(),
+            key_type: KeyType::Gpg,
+            signing_key: Some("ABCD1234".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn gpg_with_key(expiration_date: Option<&str>) -> MockGpgWrapper {
+        let mut gpg = MockGpgWrapper::new();
+        gpg.add_key(GpgKeyInfo {
+            key_id: "ABCD1234".to_string(),
+            fingerprint: "0123456789ABCDEF0123456789ABCDEFABCD1234".to_string(),
+            user_id: "Jane <jane@example.com>".to_string(),
+            key_type: "RSA".to_string(),
+            key_size: Some(4096),
+            creation_date: "2024-01-01".to_string(),
+            expiration_date: expiration_date.map(String::from),
+            trust_level: "ultimate".to_string(),
+        });
+        gpg
+    }
+
+    #[test]
+    fn test_grade_boundaries() {
+        assert_eq!(Grade::from_score(100), Grade::A);
+        assert_eq!(Grade::from_score(90), Grade::A);
+        assert_eq!(Grade::from_score(89), Grade::B);
+        assert_eq!(Grade::from_score(70), Grade::C);
+        assert_eq!(Grade::from_score(60), Grade::D);
+        assert_eq!(Grade::from_score(59), Grade::F);
+    }
+
+    #[test]
+    fn test_calculate_gpg_profile() {
+        let work = Profile {
+            description: Some("Day job".to_string()),
+            ..gpg_profile("work", "jane@work.com")
+        };
+        let profiles = vec![work.clone(), gpg_profile("personal", "jane@home.com")];
+        let activations = vec![ActivationRecord {
+            profile_name: "work".to_string(),
+            repo_path: None,
+            activated_at: at("2024-03-18T09:00:00Z"),
+        }];
+        let calculator = HealthScoreCalculator::new(&activations, &profiles).at(at("2024-03-20T12:00:00Z"));
+
+        // The key expires in 2025 and test signing succeeds; not stored in 1Password
+        let health = calculator
+            .calculate(&work, &MockGitWrapper::new(), &gpg_with_key(Some("2025-01-01")), &MockOnePasswordWrapper::new())
+            .unwrap();
+        assert_eq!(health.score, 80);
+        assert_eq!(health.grade, Grade::B);
+        assert_eq!(health.components[1], ("1Password item exists".to_string(), 0));
+        assert_eq!(health.components[4], ("Applied in last 7 days".to_string(), 10));
+    }
+
+    #[test]
+    fn test_calculate_penalizes_expiring_key_and_duplicate_email() {
+        let work = gpg_profile("work", "jane@work.com");
+        let profiles = vec![work.clone(), gpg_profile("work-copy", "jane@work.com")];
+        let calculator = HealthScoreCalculator::new(&[], &profiles).at(at("2024-03-20T12:00:00Z"));
+
+        // Expires in ten days, given as seconds since the epoch
+        let expires = at("2024-03-30T12:00:00Z").timestamp().to_string();
+        let health = calculator
+            .calculate(&work, &MockGitWrapper::new(), &gpg_with_key(Some(&expires)), &MockOnePasswordWrapper::new())
+            .unwrap();
+        assert_eq!(health.components[3], ("No expiring keys".to_string(), 0));
+        assert_eq!(health.components[6], ("No duplicate email".to_string(), 0));
+        assert_eq!(health.score, 40);
+        assert_eq!(health.grade, Grade::F);
+    }
+}
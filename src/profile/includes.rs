@@ -0,0 +1,176 @@
+//! Exporting profiles as gitconfig `[includeIf]` blocks.
+//!
+//! Every profile with `include_if_dirs` gets a snippet holding its `[user]`,
+//! `[commit]` and `[gpg]` settings, and the exported file includes that
+//! snippet for each of the profile's directories. Put in `~/.gitconfig`, the
+//! result switches identity by directory without git-setup being installed.
+
+use crate::{
+    config::{snippets::SnippetManager, types::{KeyType, Profile}},
+    error::Result,
+    external::{FilesystemGitWrapper, GitConfigScope, GitWrapper},
+};
+use std::path::Path;
+
+/// Write the `[includeIf]` blocks of `profiles` to `output_path` and their
+/// snippets to the same directory, replacing earlier content of those files.
+pub fn export_to_gitconfig_includes(profiles: &[Profile], output_path: &Path) -> Result<()> {
+    let dir = output_path.parent().unwrap_or_else(|| Path::new(""));
+    let snippets = SnippetManager::new(dir);
+    let output = FilesystemGitWrapper::new(output_path);
+    output.clear()?;
+
+    for profile in profiles.iter().filter(|profile| !profile.include_if_dirs.is_empty()) {
+        let snippet_path = snippets.snippet_path(&profile.name);
+        write_snippet(profile, &snippet_path)?;
+
+        // git resolves a relative include path against the including file
+        let include_path = snippet_path.file_name().unwrap_or_default().to_string_lossy();
+        for include_dir in &profile.include_if_dirs {
+            let key = format!("includeIf.gitdir:{}/.path", include_dir.trim_end_matches('/'));
+            output.add_config(&key, &include_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the identity and signing settings of `profile` to the snippet at `path`.
+fn write_snippet(profile: &Profile, path: &Path) -> Result<()> {
+    let snippet = FilesystemGitWrapper::new(path);
+    snippet.clear()?;
+
+    if let Some(name) = &profile.git_user_name {
+        snippet.set_config("user.name", name, GitConfigScope::Global)?;
+    }
+    snippet.set_config("user.email", &profile.git_user_email, GitConfigScope::Global)?;
+
+    match profile.key_type {
+        // Keys kept in a secret store are only known when the profile is applied;
+        // git can sign with the private key file instead, but not without any key
+        KeyType::Ssh if profile.signing_key.is_none() => match &profile.ssh_key_path {
+            Some(key_path) => {
+                snippet.configure_ssh_signing(key_path, profile.allowed_signers.as_deref(), GitConfigScope::Global)
+            }
+            None => {
+                eprintln!(
+                    "Warning: profile '{}' has no SSH signing key or key path, so its snippet does not sign commits",
+                    profile.name
+                );
+                Ok(())
+            }
+        },
+        // gpg picks the secret key matching user.email
+        KeyType::Gpg if profile.signing_key.is_none() => {
+            snippet.set_config("commit.gpgsign", "true", GitConfigScope::Global)?;
+            snippet.set_config("gpg.format", "openpgp", GitConfigScope::Global)
+        }
+        _ => snippet.configure_signing(profile, GitConfigScope::Global),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile(name: &str, key_type: KeyType, signing_key: Option<&str>, dirs: &[&str]) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_name: Some("Jane Doe".to_string()),
+            git_user_email: format!("jane@{}.com", name),
+            key_type,
+            signing_key: signing_key.map(str::to_string),
+            include_if_dirs: dirs.iter().map(|dir| dir.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_export_to_gitconfig_includes() {
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join("gitconfig");
+        let profiles = vec![
+            profile("work", KeyType::Ssh, Some("ssh-ed25519 AAAA jane@work.com"), &["~/work", "~/clients/"]),
+            profile("oss", KeyType::Gpg, Some("ABCD1234"), &["~/src/oss/"]),
+            profile("personal", KeyType::Ssh, Some("ssh-ed25519 BBBB"), &[]),
+        ];
+
+        export_to_gitconfig_includes(&profiles, &output_path).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&output_path).unwrap(),
+            "[includeIf \"gitdir:~/work/\"]\n\tpath = profile-work.gitconfig\n\
+             [includeIf \"gitdir:~/clients/\"]\n\tpath = profile-work.gitconfig\n\
+             [includeIf \"gitdir:~/src/oss/\"]\n\tpath = profile-oss.gitconfig\n"
+        );
+        assert!(!dir.path().join("profile-personal.gitconfig").exists());
+
+        // The snippets parse back to the profiles' settings
+        let work = FilesystemGitWrapper::new(dir.path().join("profile-work.gitconfig"));
+        assert_eq!(work.get_config("user.name", None).unwrap().as_deref(), Some("Jane Doe"));
+        assert_eq!(work.get_config("user.email", None).unwrap().as_deref(), Some("jane@work.com"));
+        assert_eq!(
+            work.get_config("user.signingkey", None).unwrap().as_deref(),
+            Some("ssh-ed25519 AAAA jane@work.com")
+        );
+        assert_eq!(work.get_config("gpg.format", None).unwrap().as_deref(), Some("ssh"));
+        assert_eq!(work.get_config("commit.gpgsign", None).unwrap().as_deref(), Some("true"));
+
+        let oss = FilesystemGitWrapper::new(dir.path().join("profile-oss.gitconfig"));
+        assert_eq!(oss.get_config("user.signingkey", None).unwrap().as_deref(), Some("ABCD1234"));
+        assert_eq!(oss.get_config("gpg.format", None).unwrap().as_deref(), Some("openpgp"));
+    }
+
+    #[test]
+    fn test_export_replaces_previous_output() {
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join("gitconfig");
+        std::fs::write(&output_path, "[includeIf \"gitdir:~/old/\"]\n\tpath = profile-old.gitconfig\n").unwrap();
+
+        let profiles = vec![profile("work", KeyType::Gitsign, None, &["~/work/"])];
+        export_to_gitconfig_includes(&profiles, &output_path).unwrap();
+
+        let output = FilesystemGitWrapper::new(&output_path);
+        assert_eq!(output.get_config("includeIf.gitdir:~/old/.path", None).unwrap(), None);
+        assert_eq!(
+            output.get_config("includeIf.gitdir:~/work/.path", None).unwrap().as_deref(),
+            Some("profile-work.gitconfig")
+        );
+
+        let work = FilesystemGitWrapper::new(dir.path().join("profile-work.gitconfig"));
+        assert_eq!(work.get_config("gpg.x509.program", None).unwrap().as_deref(), Some("gitsign"));
+    }
+
+    #[test]
+    fn test_export_without_stored_signing_key() {
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join("gitconfig");
+        let profiles = vec![
+            profile("work", KeyType::Ssh, None, &["~/work/"]),
+            Profile {
+                ssh_key_path: Some("~/.ssh/id_oss".to_string()),
+                ..profile("oss", KeyType::Ssh, None, &["~/src/oss/"])
+            },
+            profile("clients", KeyType::Gpg, None, &["~/clients/"]),
+        ];
+
+        export_to_gitconfig_includes(&profiles, &output_path).unwrap();
+
+        // Without a key, git could not sign, so signing is left off
+        let work = FilesystemGitWrapper::new(dir.path().join("profile-work.gitconfig"));
+        assert_eq!(work.get_config("user.email", None).unwrap().as_deref(), Some("jane@work.com"));
+        assert_eq!(work.get_config("user.signingkey", None).unwrap(), None);
+        assert_eq!(work.get_config("commit.gpgsign", None).unwrap(), None);
+
+        let oss = FilesystemGitWrapper::new(dir.path().join("profile-oss.gitconfig"));
+        assert_eq!(oss.get_config("user.signingkey", None).unwrap().as_deref(), Some("~/.ssh/id_oss"));
+        assert_eq!(oss.get_config("gpg.format", None).unwrap().as_deref(), Some("ssh"));
+        assert_eq!(oss.get_config("commit.gpgsign", None).unwrap().as_deref(), Some("true"));
+
+        let clients = FilesystemGitWrapper::new(dir.path().join("profile-clients.gitconfig"));
+        assert_eq!(clients.get_config("user.signingkey", None).unwrap(), None);
+        assert_eq!(clients.get_config("gpg.format", None).unwrap().as_deref(), Some("openpgp"));
+        assert_eq!(clients.get_config("commit.gpgsign", None).unwrap().as_deref(), Some("true"));
+    }
+}
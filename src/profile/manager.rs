@@ -1,10 +1,17 @@
 //! Profile manager implementation for git-setup-rs.
 
+use super::activation::{ActivationHistory, ActivationRecord};
+use super::bulk::BatchResult;
+use super::transaction::ProfileSnapshot;
+use super::undo::{ProfileOperation, UndoRedoStack};
+use super::validator::ProfileValidator;
+use super::watch::{ChangeCallback, WatchHandle};
 use crate::{
-    config::types::Profile,
+    config::types::{Profile, ValidationHookConfig},
     error::{GitSetupError, Result},
 };
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 /// In-memory implementation of ProfileManager.
@@ -15,7 +22,17 @@ use std::sync::{Arc, Mutex};
 #[derive(Debug, Clone)]
 pub struct ProfileManagerImpl {
     profiles: Arc<Mutex<HashMap<String, Profile>>>,
+    /// Profile names by lowercased `git_user_email`, rebuilt on every write.
+    email_index: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    /// Listing order set by `reorder`; empty lists profiles by name.
+    order: Arc<Mutex<Vec<String>>>,
     default_profile: Arc<Mutex<Option<String>>>,
+    history: Arc<Mutex<UndoRedoStack>>,
+    history_path: Option<PathBuf>,
+    activations: Arc<Mutex<ActivationHistory>>,
+    activations_path: Option<PathBuf>,
+    /// Hooks every created or updated profile must pass.
+    validation_hooks: Vec<ValidationHookConfig>,
 }
 
 impl ProfileManagerImpl {
@@ -23,10 +40,39 @@ impl ProfileManagerImpl {
     pub fn new() -> Self {
         Self {
             profiles: Arc::new(Mutex::new(HashMap::new())),
+            email_index: Arc::new(Mutex::new(HashMap::new())),
+            order: Arc::new(Mutex::new(Vec::new())),
             default_profile: Arc::new(Mutex::new(None)),
+            history: Arc::new(Mutex::new(UndoRedoStack::new())),
+            history_path: None,
+            activations: Arc::new(Mutex::new(ActivationHistory::new())),
+            activations_path: None,
+            validation_hooks: Vec::new(),
         }
     }
 
+    /// Persist the undo/redo history to `path`, loading any history already stored there.
+    pub fn with_history_file(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        *self.history.lock().unwrap() = UndoRedoStack::load(&path)?;
+        self.history_path = Some(path);
+        Ok(self)
+    }
+
+    /// Persist the activation history to `path`, loading any history already stored there.
+    pub fn with_activation_file(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        *self.activations.lock().unwrap() = ActivationHistory::load(&path)?;
+        self.activations_path = Some(path);
+        Ok(self)
+    }
+
+    /// Run `hooks` on every profile before it is created or updated.
+    pub fn with_validation_hooks(mut self, hooks: Vec<ValidationHookConfig>) -> Self {
+        self.validation_hooks = hooks;
+        self
+    }
+
     /// Get the default profile name.
     pub fn get_default(&self) -> Result<Option<String>> {
         Ok(self.default_profile.lock().unwrap().clone())
@@ -61,58 +107,153 @@ impl ProfileManagerImpl {
         Ok(matches)
     }
 
-    /// Validate a profile.
+    /// Rebuild the email index from `profiles`.
+    ///
+    /// Called with the profiles still locked, so the index never lags behind a write.
+    fn reindex(&self, profiles: &HashMap<String, Profile>) {
+        let mut index: HashMap<String, Vec<String>> = HashMap::new();
+        for profile in profiles.values() {
+            index
+                .entry(profile.git_user_email.to_lowercase())
+                .or_default()
+                .push(profile.name.clone());
+        }
+        for names in index.values_mut() {
+            names.sort();
+        }
+        *self.email_index.lock().unwrap() = index;
+    }
+
+    /// Validate a profile, rejecting it on the first structural problem or failing validation hook.
     fn validate(&self, profile: &Profile) -> Result<()> {
-        // Validate name
-        if profile.name.is_empty() {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Profile name cannot be empty".to_string(),
-            });
+        match ProfileValidator::validate_with_hooks(profile, &self.validation_hooks)?
+            .into_iter()
+            .next()
+        {
+            Some(error) => Err(GitSetupError::InvalidProfile {
+                reason: error.message,
+            }),
+            None => Ok(()),
         }
+    }
 
-        // Validate name doesn't contain invalid characters
-        if profile.name.contains('/') || profile.name.contains('\\') {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Profile name cannot contain path separators".to_string(),
-            });
+    /// Record an operation in the undo history.
+    fn record(&self, operation: ProfileOperation) -> Result<()> {
+        let mut history = self.history.lock().unwrap();
+        history.record(operation);
+        self.persist_history(&history)
+    }
+
+    /// Write the history to disk if a history file is configured.
+    fn persist_history(&self, history: &UndoRedoStack) -> Result<()> {
+        match &self.history_path {
+            Some(path) => history.save(path),
+            None => Ok(()),
         }
+    }
+
+    /// Remove and/or insert a profile without recording history.
+    fn replace(&self, remove: Option<&str>, insert: Option<Profile>) -> Result<()> {
+        let mut profiles = self.profiles.lock().unwrap();
 
-        // Validate profile name length
-        if profile.name.len() > 100 {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Profile name cannot exceed 100 characters".to_string(),
+        if let Some(name) = remove
+            && !profiles.contains_key(name)
+        {
+            return Err(GitSetupError::ProfileNotFound {
+                name: name.to_string(),
             });
         }
-
-        // Validate profile name format (alphanumeric + dash/underscore)
-        if !profile.name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Profile name can only contain alphanumeric characters, dashes, and underscores".to_string(),
+        if let Some(profile) = &insert
+            && remove != Some(profile.name.as_str())
+            && profiles.contains_key(&profile.name)
+        {
+            return Err(GitSetupError::DuplicateProfile {
+                name: profile.name.clone(),
             });
         }
 
-        // Validate email
-        if profile.git_user_email.is_empty() {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Email address cannot be empty".to_string(),
-            });
+        if let Some(name) = remove {
+            profiles.remove(name);
+
+            // Keep the default profile reference pointing at the same profile
+            let mut default = self.default_profile.lock().unwrap();
+            if default.as_deref() == Some(name) {
+                *default = insert.as_ref().map(|p| p.name.clone());
+            }
+        }
+        if let Some(profile) = insert {
+            profiles.insert(profile.name.clone(), profile);
         }
+        self.reindex(&profiles);
 
-        if !profile.git_user_email.contains('@') {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Invalid email address format".to_string(),
-            });
+        Ok(())
+    }
+
+    /// Set the archived flag of a profile without recording history.
+    ///
+    /// Returns the updated profile, or `None` if the flag already had that value.
+    fn set_archived(&self, name: &str, archived: bool) -> Result<Option<Profile>> {
+        let mut profiles = self.profiles.lock().unwrap();
+        let profile = profiles.get_mut(name).ok_or_else(|| GitSetupError::ProfileNotFound {
+            name: name.to_string(),
+        })?;
+        if profile.is_archived == archived {
+            return Ok(None);
         }
+        profile.is_archived = archived;
+        Ok(Some(profile.clone()))
+    }
 
-        // Basic email validation
-        let parts: Vec<&str> = profile.git_user_email.split('@').collect();
-        if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
-            return Err(GitSetupError::InvalidProfile {
-                reason: "Invalid email address format".to_string(),
-            });
+    /// Reverse an operation, returning the name of the restored profile.
+    fn revert(&self, operation: &ProfileOperation) -> Result<String> {
+        match operation {
+            ProfileOperation::Created(profile) => {
+                self.replace(Some(&profile.name), None)?;
+                Ok(profile.name.clone())
+            }
+            ProfileOperation::Updated { before, after } => {
+                self.replace(Some(&after.name), Some(before.as_ref().clone()))?;
+                Ok(before.name.clone())
+            }
+            ProfileOperation::Deleted(profile) => {
+                self.replace(None, Some(profile.clone()))?;
+                Ok(profile.name.clone())
+            }
+            ProfileOperation::Archived(profile) => {
+                self.set_archived(&profile.name, false)?;
+                Ok(profile.name.clone())
+            }
+            ProfileOperation::Unarchived(profile) => {
+                self.set_archived(&profile.name, true)?;
+                Ok(profile.name.clone())
+            }
         }
+    }
 
-        Ok(())
+    /// Re-apply a previously reverted operation, returning the affected profile name.
+    fn reapply(&self, operation: &ProfileOperation) -> Result<String> {
+        match operation {
+            ProfileOperation::Created(profile) => {
+                self.replace(None, Some(profile.clone()))?;
+                Ok(profile.name.clone())
+            }
+            ProfileOperation::Updated { before, after } => {
+                self.replace(Some(&before.name), Some(after.as_ref().clone()))?;
+                Ok(after.name.clone())
+            }
+            ProfileOperation::Deleted(profile) => {
+                self.replace(Some(&profile.name), None)?;
+                Ok(profile.name.clone())
+            }
+            ProfileOperation::Archived(profile) => {
+                self.set_archived(&profile.name, true)?;
+                Ok(profile.name.clone())
+            }
+            ProfileOperation::Unarchived(profile) => {
+                self.set_archived(&profile.name, false)?;
+                Ok(profile.name.clone())
+            }
+        }
     }
 }
 
@@ -133,8 +274,38 @@ impl super::ProfileManager for ProfileManagerImpl {
             });
         }
 
-        profiles.insert(profile.name.clone(), profile);
-        Ok(())
+        profiles.insert(profile.name.clone(), profile.clone());
+        self.reindex(&profiles);
+        drop(profiles);
+
+        self.record(ProfileOperation::Created(profile))
+    }
+
+    /// Create the whole batch under one lock, so no other writer sees part of it.
+    fn batch_create(&self, profiles: Vec<Profile>) -> Result<BatchResult> {
+        let mut result = BatchResult::default();
+        let mut created = Vec::new();
+
+        let mut stored = self.profiles.lock().unwrap();
+        for profile in profiles {
+            if let Err(error) = self.validate(&profile) {
+                result.failed.push((profile.name, error));
+            } else if stored.contains_key(&profile.name) {
+                let name = profile.name;
+                result.failed.push((name.clone(), GitSetupError::DuplicateProfile { name }));
+            } else {
+                stored.insert(profile.name.clone(), profile.clone());
+                created.push(profile);
+            }
+        }
+        self.reindex(&stored);
+        drop(stored);
+
+        result.created = created.len();
+        for profile in created {
+            self.record(ProfileOperation::Created(profile))?;
+        }
+        Ok(result)
     }
 
     fn read(&self, name: &str) -> Result<Option<Profile>> {
@@ -146,11 +317,11 @@ impl super::ProfileManager for ProfileManagerImpl {
         self.validate(&profile)?;
 
         let mut profiles = self.profiles.lock().unwrap();
-        if !profiles.contains_key(name) {
+        let Some(before) = profiles.get(name).cloned() else {
             return Err(GitSetupError::ProfileNotFound {
                 name: name.to_string(),
             });
-        }
+        };
 
         // If renaming, check new name doesn't exist
         if profile.name != name && profiles.contains_key(&profile.name) {
@@ -170,40 +341,186 @@ impl super::ProfileManager for ProfileManagerImpl {
             }
         }
 
-        profiles.insert(profile.name.clone(), profile);
-        Ok(())
+        profiles.insert(profile.name.clone(), profile.clone());
+        self.reindex(&profiles);
+        drop(profiles);
+
+        self.record(ProfileOperation::Updated {
+            before: Box::new(before),
+            after: Box::new(profile),
+        })
     }
 
     fn delete(&self, name: &str) -> Result<()> {
         let mut profiles = self.profiles.lock().unwrap();
-        if !profiles.contains_key(name) {
+        let Some(removed) = profiles.remove(name) else {
             return Err(GitSetupError::ProfileNotFound {
                 name: name.to_string(),
             });
-        }
-
-        profiles.remove(name);
+        };
+        self.reindex(&profiles);
+        drop(profiles);
 
         // Clear default if it was deleted
         let mut default = self.default_profile.lock().unwrap();
         if default.as_ref() == Some(&name.to_string()) {
             *default = None;
         }
+        drop(default);
 
-        Ok(())
+        self.record(ProfileOperation::Deleted(removed))
     }
 
     fn list(&self) -> Result<Vec<Profile>> {
+        Ok(self.list_all()?.into_iter().filter(|p| !p.is_archived).collect())
+    }
+
+    fn list_all(&self) -> Result<Vec<Profile>> {
         let profiles = self.profiles.lock().unwrap();
         let mut profile_list: Vec<Profile> = profiles.values().cloned().collect();
         profile_list.sort_by(|a, b| a.name.cmp(&b.name));
+        super::sort_by_order(&mut profile_list, &self.order.lock().unwrap());
         Ok(profile_list)
     }
 
+    fn reorder(&self, order: Vec<String>) -> Result<()> {
+        let profiles = self.profiles.lock().unwrap();
+        if let Some(name) = order.iter().find(|name| !profiles.contains_key(*name)) {
+            return Err(GitSetupError::ProfileNotFound { name: name.clone() });
+        }
+        *self.order.lock().unwrap() = order;
+        Ok(())
+    }
+
     fn exists(&self, name: &str) -> Result<bool> {
         let profiles = self.profiles.lock().unwrap();
         Ok(profiles.contains_key(name))
     }
+
+    fn search_by_email(&self, email: &str) -> Result<Vec<Profile>> {
+        // Copy the names before locking the profiles; writers take the locks the other way round
+        let names = self
+            .email_index
+            .lock()
+            .unwrap()
+            .get(&email.to_lowercase())
+            .cloned()
+            .unwrap_or_default();
+        let profiles = self.profiles.lock().unwrap();
+        Ok(names
+            .iter()
+            .filter_map(|name| profiles.get(name))
+            .filter(|profile| !profile.is_archived)
+            .cloned()
+            .collect())
+    }
+
+    fn archive(&self, name: &str) -> Result<()> {
+        match self.set_archived(name, true)? {
+            Some(profile) => self.record(ProfileOperation::Archived(profile)),
+            None => Ok(()),
+        }
+    }
+
+    fn unarchive(&self, name: &str) -> Result<()> {
+        match self.set_archived(name, false)? {
+            Some(profile) => self.record(ProfileOperation::Unarchived(profile)),
+            None => Ok(()),
+        }
+    }
+
+    fn undo(&self) -> Result<Option<String>> {
+        let mut history = self.history.lock().unwrap();
+        let Some(operation) = history.pop_undo() else {
+            return Ok(None);
+        };
+
+        match self.revert(&operation) {
+            Ok(name) => {
+                history.push_redo(operation);
+                self.persist_history(&history)?;
+                Ok(Some(name))
+            }
+            Err(e) => {
+                history.push_undo(operation);
+                Err(e)
+            }
+        }
+    }
+
+    fn record_activation(&self, record: ActivationRecord) -> Result<()> {
+        let mut activations = self.activations.lock().unwrap();
+        activations.record(record);
+        match &self.activations_path {
+            Some(path) => activations.save(path),
+            None => Ok(()),
+        }
+    }
+
+    fn activation_history(&self) -> Result<Vec<ActivationRecord>> {
+        Ok(self.activations.lock().unwrap().records().to_vec())
+    }
+
+    fn clear_activation_history(&self) -> Result<()> {
+        let mut activations = self.activations.lock().unwrap();
+        activations.clear();
+        match &self.activations_path {
+            Some(path) => activations.save(path),
+            None => Ok(()),
+        }
+    }
+
+    fn snapshot(&self) -> Result<ProfileSnapshot> {
+        Ok(ProfileSnapshot {
+            profiles: self.profiles.lock().unwrap().values().cloned().collect(),
+            default_profile: self.default_profile.lock().unwrap().clone(),
+            history: Some(self.history.lock().unwrap().clone()),
+        })
+    }
+
+    fn restore(&self, snapshot: ProfileSnapshot) -> Result<()> {
+        let mut profiles = self.profiles.lock().unwrap();
+        *profiles = snapshot
+            .profiles
+            .into_iter()
+            .map(|profile| (profile.name.clone(), profile))
+            .collect();
+        self.reindex(&profiles);
+        drop(profiles);
+        *self.default_profile.lock().unwrap() = snapshot.default_profile;
+
+        if let Some(restored) = snapshot.history {
+            let mut history = self.history.lock().unwrap();
+            *history = restored;
+            self.persist_history(&history)?;
+        }
+        Ok(())
+    }
+
+    fn watch(&self, callback: ChangeCallback) -> Result<WatchHandle> {
+        // Clones share the store, so changes through any handle are seen
+        let manager = self.clone();
+        WatchHandle::poll(move || super::ProfileManager::list_all(&manager), callback)
+    }
+
+    fn redo(&self) -> Result<Option<String>> {
+        let mut history = self.history.lock().unwrap();
+        let Some(operation) = history.pop_redo() else {
+            return Ok(None);
+        };
+
+        match self.reapply(&operation) {
+            Ok(name) => {
+                history.push_undo(operation);
+                self.persist_history(&history)?;
+                Ok(Some(name))
+            }
+            Err(e) => {
+                history.push_redo(operation);
+                Err(e)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +547,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -326,6 +652,24 @@ mod tests {
         assert_eq!(profiles[1].name, "test2");
     }
 
+    #[test]
+    fn test_reorder_profiles() {
+        let manager = ProfileManagerImpl::new();
+        for name in ["alpha", "beta", "gamma"] {
+            let mut profile = test_profile();
+            profile.name = name.to_string();
+            manager.create(profile).unwrap();
+        }
+
+        manager.reorder(vec!["gamma".to_string(), "alpha".to_string()]).unwrap();
+        let names: Vec<String> = manager.list().unwrap().into_iter().map(|p| p.name).collect();
+        // Profiles left out of the order follow it
+        assert_eq!(names, ["gamma", "alpha", "beta"]);
+
+        let result = manager.reorder(vec!["missing".to_string()]);
+        assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
+    }
+
     #[test]
     fn test_profile_validation_empty_name() {
         let manager = ProfileManagerImpl::new();
@@ -444,6 +788,74 @@ mod tests {
         assert_eq!(results[0].name, "WorkProfile");
     }
 
+    #[test]
+    fn test_search_by_email_case_insensitive() {
+        let manager = ProfileManagerImpl::new();
+        let mut profile = test_profile();
+        profile.git_user_email = "Jane.Doe@Example.com".to_string();
+        manager.create(profile).unwrap();
+
+        let results = manager.search_by_email("jane.doe@example.COM").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "test");
+        assert!(manager.search_by_email("jane@example.com").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_by_email_shared_address() {
+        let manager = ProfileManagerImpl::new();
+        for name in ["work-ssh", "work-gpg", "personal"] {
+            let mut profile = test_profile();
+            profile.name = name.to_string();
+            if name == "personal" {
+                profile.git_user_email = "me@personal.com".to_string();
+            }
+            manager.create(profile).unwrap();
+        }
+
+        let names = |email: &str| -> Vec<String> {
+            manager.search_by_email(email).unwrap().into_iter().map(|p| p.name).collect()
+        };
+        assert_eq!(names("test@example.com"), vec!["work-gpg", "work-ssh"]);
+
+        // The index follows updates, renames, deletes, archiving and undo
+        let mut moved = manager.read("work-gpg").unwrap().unwrap();
+        moved.git_user_email = "me@personal.com".to_string();
+        manager.update("work-gpg", moved).unwrap();
+        assert_eq!(names("test@example.com"), vec!["work-ssh"]);
+        assert_eq!(names("me@personal.com"), vec!["personal", "work-gpg"]);
+
+        let mut renamed = manager.read("work-ssh").unwrap().unwrap();
+        renamed.name = "acme".to_string();
+        manager.update("work-ssh", renamed).unwrap();
+        assert_eq!(names("test@example.com"), vec!["acme"]);
+
+        manager.delete("acme").unwrap();
+        assert!(names("test@example.com").is_empty());
+        manager.undo().unwrap();
+        assert_eq!(names("test@example.com"), vec!["acme"]);
+
+        manager.archive("personal").unwrap();
+        assert_eq!(names("me@personal.com"), vec!["work-gpg"]);
+    }
+
+    /// Test that the default implementation finds the same profiles as the index.
+    #[test]
+    fn test_search_by_email_default_impl() {
+        let mut shared = test_profile();
+        shared.name = "other".to_string();
+        shared.git_user_email = "TEST@example.com".to_string();
+        let manager = crate::profile::mock::MockProfileManager::with_profiles(vec![test_profile(), shared]);
+
+        let names: Vec<String> = manager
+            .search_by_email("Test@Example.com")
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["other", "test"]);
+    }
+
     #[test]
     fn test_default_profile() {
         let manager = ProfileManagerImpl::new();
@@ -526,6 +938,33 @@ mod tests {
         assert!(manager.get_default().unwrap().is_none());
     }
 
+    #[test]
+    fn test_watch_changes() {
+        use crate::profile::watch::{ChangeEventType, WATCH_POLL_INTERVAL};
+
+        let manager = ProfileManagerImpl::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = events.clone();
+        let handle = manager
+            .watch_changes(move |event| received.lock().unwrap().push((event.event_type, event.profile_name)))
+            .unwrap();
+
+        // Changes through a clone of the manager are seen too
+        manager.clone().create(test_profile()).unwrap();
+        std::thread::sleep(WATCH_POLL_INTERVAL * 3);
+        manager.delete("test").unwrap();
+        std::thread::sleep(WATCH_POLL_INTERVAL * 3);
+        drop(handle);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (ChangeEventType::Created, "test".to_string()),
+                (ChangeEventType::Deleted, "test".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_thread_safety() {
         use std::thread;
@@ -552,4 +991,197 @@ mod tests {
         let profiles = manager.list().unwrap();
         assert_eq!(profiles.len(), 10);
     }
+
+    #[test]
+    fn test_undo_redo_create() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+
+        assert_eq!(manager.undo().unwrap(), Some("test".to_string()));
+        assert!(!manager.exists("test").unwrap());
+
+        assert_eq!(manager.redo().unwrap(), Some("test".to_string()));
+        assert!(manager.exists("test").unwrap());
+    }
+
+    #[test]
+    fn test_undo_delete_restores_exact_profile() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+        manager.delete("test").unwrap();
+
+        assert_eq!(manager.undo().unwrap(), Some("test".to_string()));
+        let restored = manager.read("test").unwrap().unwrap();
+        let original = test_profile();
+        assert_eq!(restored.git_user_name, original.git_user_name);
+        assert_eq!(restored.signing_key, original.signing_key);
+        assert_eq!(restored.vault_name, original.vault_name);
+        assert_eq!(restored.one_password, original.one_password);
+
+        assert_eq!(manager.redo().unwrap(), Some("test".to_string()));
+        assert!(!manager.exists("test").unwrap());
+    }
+
+    #[test]
+    fn test_undo_rename() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+
+        let mut renamed = test_profile();
+        renamed.name = "renamed".to_string();
+        renamed.git_user_email = "renamed@example.com".to_string();
+        manager.update("test", renamed).unwrap();
+
+        assert_eq!(manager.undo().unwrap(), Some("test".to_string()));
+        assert!(!manager.exists("renamed").unwrap());
+        let restored = manager.read("test").unwrap().unwrap();
+        assert_eq!(restored.git_user_email, "test@example.com");
+    }
+
+    #[test]
+    fn test_archive_hides_profile_from_list() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+        manager.archive("test").unwrap();
+
+        assert!(manager.list().unwrap().is_empty());
+        assert_eq!(manager.list_all().unwrap().len(), 1);
+        assert_eq!(manager.list_archived().unwrap()[0].name, "test");
+        // Archived profiles can still be read by name
+        assert!(manager.read("test").unwrap().unwrap().is_archived);
+
+        manager.unarchive("test").unwrap();
+        assert_eq!(manager.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_undo_redo_archive() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+        manager.archive("test").unwrap();
+
+        assert_eq!(manager.undo().unwrap(), Some("test".to_string()));
+        assert!(!manager.read("test").unwrap().unwrap().is_archived);
+
+        assert_eq!(manager.redo().unwrap(), Some("test".to_string()));
+        assert!(manager.read("test").unwrap().unwrap().is_archived);
+
+        // Archiving an archived profile is not recorded again
+        manager.archive("test").unwrap();
+        manager.undo().unwrap();
+        assert!(!manager.read("test").unwrap().unwrap().is_archived);
+        assert!(matches!(manager.archive("missing"), Err(GitSetupError::ProfileNotFound { .. })));
+    }
+
+    #[test]
+    fn test_undo_with_empty_history() {
+        let manager = ProfileManagerImpl::new();
+        assert_eq!(manager.undo().unwrap(), None);
+        assert_eq!(manager.redo().unwrap(), None);
+    }
+
+    #[test]
+    fn test_new_operation_clears_redo() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+        manager.undo().unwrap();
+
+        let mut other = test_profile();
+        other.name = "other".to_string();
+        manager.create(other).unwrap();
+
+        assert_eq!(manager.redo().unwrap(), None);
+    }
+
+    #[test]
+    fn test_history_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("undo-stack.json");
+
+        let manager = ProfileManagerImpl::new().with_history_file(&path).unwrap();
+        manager.create(test_profile()).unwrap();
+        manager.delete("test").unwrap();
+
+        // A fresh manager picks up the persisted history
+        let manager = ProfileManagerImpl::new().with_history_file(&path).unwrap();
+        assert_eq!(manager.undo().unwrap(), Some("test".to_string()));
+        assert!(manager.exists("test").unwrap());
+    }
+
+    #[test]
+    fn test_invalid_glob_patterns_rejected() {
+        let manager = ProfileManagerImpl::new();
+
+        let mut profile = test_profile();
+        profile.match_patterns = vec!["work/[".to_string()];
+        let result = manager.create(profile);
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+        assert!(result.unwrap_err().to_string().contains("Invalid glob pattern 'work/['"));
+
+        manager.create(test_profile()).unwrap();
+        let mut profile = test_profile();
+        profile.host_patterns = vec!["[github.com".to_string()];
+        let result = manager.update("test", profile);
+        assert!(matches!(result, Err(GitSetupError::InvalidProfile { .. })));
+    }
+
+    #[test]
+    fn test_validation_hooks_run_on_every_write() {
+        let manager = ProfileManagerImpl::new().with_validation_hooks(vec![ValidationHookConfig {
+            command: "grep -q '\"key_type\":\"gpg\"' || { echo 'GPG required' >&2; exit 1; }".to_string(),
+            applies_to_profiles: "work*".to_string(),
+            fail_on_nonzero: true,
+        }]);
+
+        manager.create(test_profile()).unwrap();
+        let renamed = Profile {
+            name: "work".to_string(),
+            ..test_profile()
+        };
+        let result = manager.update("test", renamed.clone());
+        assert!(result.unwrap_err().to_string().contains("GPG required"));
+        assert!(manager.exists("test").unwrap());
+
+        let result = manager.batch_create(vec![Profile {
+            name: "work-2".to_string(),
+            ..test_profile()
+        }]);
+        assert_eq!(result.unwrap().failed.len(), 1);
+
+        manager
+            .update(
+                "test",
+                Profile {
+                    key_type: KeyType::Gpg,
+                    ..renamed
+                },
+            )
+            .unwrap();
+        assert!(manager.exists("work").unwrap());
+    }
+
+    #[test]
+    fn test_batch_create_collects_failures() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(test_profile()).unwrap();
+
+        let profile = |name: &str| Profile { name: name.to_string(), ..test_profile() };
+        let invalid = Profile {
+            match_patterns: vec!["work/[".to_string()],
+            ..profile("invalid")
+        };
+        let result = manager
+            .batch_create(vec![profile("work"), invalid, profile("test"), profile("personal"), profile("work")])
+            .unwrap();
+
+        assert_eq!(result.created, 2);
+        assert!(!result.is_complete());
+        let failed: Vec<&str> = result.failed.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(failed, vec!["invalid", "test", "work"]);
+        assert!(matches!(result.failed[1].1, GitSetupError::DuplicateProfile { .. }));
+        assert_eq!(manager.list().unwrap().len(), 3);
+
+        // Each created profile can be undone on its own
+        assert_eq!(manager.undo().unwrap(), Some("personal".to_string()));
+    }
 }
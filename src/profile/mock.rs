@@ -1,67 +1,233 @@
 //! Mock implementation of ProfileManager for testing.
 
+use super::activation::ActivationRecord;
+use super::bulk::BatchResult;
 use crate::{config::types::Profile, error::{GitSetupError, Result}};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+/// A call made to a [`MockProfileManager`], recorded when call tracking is enabled.
+#[derive(Debug, Clone)]
+pub enum ProfileManagerCall {
+    Create(Profile),
+    BatchCreate(Vec<String>),
+    Read(String),
+    Update(String, Profile),
+    Delete(String),
+    List,
+    ListAll,
+    Exists(String),
+    Reorder(Vec<String>),
+    RecordActivation(ActivationRecord),
+    ActivationHistory,
+    ClearActivationHistory,
+}
+
 /// Mock implementation of ProfileManager for testing.
 ///
 /// This implementation stores profiles in memory and provides
 /// configurable behavior for testing error conditions.
+///
+/// ```ignore
+/// let manager = MockProfileManager::with_profiles(vec![profile])
+///     .with_delete_error(GitSetupError::Git("disk full".to_string()))
+///     .track_calls(true);
+/// ```
 #[derive(Debug, Clone)]
 pub struct MockProfileManager {
     profiles: Arc<Mutex<HashMap<String, Profile>>>,
-    create_should_fail: Arc<Mutex<bool>>,
-    read_should_fail: Arc<Mutex<bool>>,
-    update_should_fail: Arc<Mutex<bool>>,
-    delete_should_fail: Arc<Mutex<bool>>,
+    order: Arc<Mutex<Vec<String>>>,
+    create_error: Arc<Mutex<Option<GitSetupError>>>,
+    read_error: Arc<Mutex<Option<GitSetupError>>>,
+    update_error: Arc<Mutex<Option<GitSetupError>>>,
+    delete_error: Arc<Mutex<Option<GitSetupError>>>,
+    list_error: Arc<Mutex<Option<GitSetupError>>>,
+    creates_before_failure: Arc<Mutex<Option<usize>>>,
+    activations: Arc<Mutex<Vec<ActivationRecord>>>,
+    track_calls: bool,
+    call_log: Arc<Mutex<Vec<ProfileManagerCall>>>,
 }
 
 impl MockProfileManager {
     /// Create a new mock profile manager.
     pub fn new() -> Self {
-        Self {
-            profiles: Arc::new(Mutex::new(HashMap::new())),
-            create_should_fail: Arc::new(Mutex::new(false)),
-            read_should_fail: Arc::new(Mutex::new(false)),
-            update_should_fail: Arc::new(Mutex::new(false)),
-            delete_should_fail: Arc::new(Mutex::new(false)),
-        }
+        Self::with_profiles(Vec::new())
     }
 
     /// Create a mock profile manager with pre-populated profiles.
     pub fn with_profiles(profiles: Vec<Profile>) -> Self {
-        let mut profile_map = HashMap::new();
-        for profile in profiles {
-            profile_map.insert(profile.name.clone(), profile);
-        }
+        let profile_map = profiles
+            .into_iter()
+            .map(|profile| (profile.name.clone(), profile))
+            .collect();
         Self {
             profiles: Arc::new(Mutex::new(profile_map)),
-            create_should_fail: Arc::new(Mutex::new(false)),
-            read_should_fail: Arc::new(Mutex::new(false)),
-            update_should_fail: Arc::new(Mutex::new(false)),
-            delete_should_fail: Arc::new(Mutex::new(false)),
+            order: Arc::new(Mutex::new(Vec::new())),
+            create_error: Arc::new(Mutex::new(None)),
+            read_error: Arc::new(Mutex::new(None)),
+            update_error: Arc::new(Mutex::new(None)),
+            delete_error: Arc::new(Mutex::new(None)),
+            list_error: Arc::new(Mutex::new(None)),
+            creates_before_failure: Arc::new(Mutex::new(None)),
+            activations: Arc::new(Mutex::new(Vec::new())),
+            track_calls: false,
+            call_log: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    /// Configure create operation to fail.
-    pub fn fail_on_create(&self, should_fail: bool) {
-        *self.create_should_fail.lock().unwrap() = should_fail;
+    /// Make every create operation fail with `error`.
+    pub fn with_create_error(self, error: GitSetupError) -> Self {
+        *self.create_error.lock().unwrap() = Some(error);
+        self
+    }
+
+    /// Make every read operation fail with `error`.
+    pub fn with_read_error(self, error: GitSetupError) -> Self {
+        *self.read_error.lock().unwrap() = Some(error);
+        self
+    }
+
+    /// Make every update operation fail with `error`.
+    pub fn with_update_error(self, error: GitSetupError) -> Self {
+        *self.update_error.lock().unwrap() = Some(error);
+        self
+    }
+
+    /// Make every delete operation fail with `error`.
+    pub fn with_delete_error(self, error: GitSetupError) -> Self {
+        *self.delete_error.lock().unwrap() = Some(error);
+        self
+    }
+
+    /// Make every list operation fail with `error`.
+    pub fn with_list_error(self, error: GitSetupError) -> Self {
+        *self.list_error.lock().unwrap() = Some(error);
+        self
+    }
+
+    /// Record every call in the call log.
+    pub fn track_calls(mut self, enabled: bool) -> Self {
+        self.track_calls = enabled;
+        self
+    }
+
+    /// Let the next `count` create operations succeed and fail every one after that.
+    pub fn fail_on_create_after(&self, count: usize) {
+        *self.creates_before_failure.lock().unwrap() = Some(count);
+    }
+
+    /// Pre-populate the activation history.
+    pub fn with_activations(self, activations: Vec<ActivationRecord>) -> Self {
+        *self.activations.lock().unwrap() = activations;
+        self
+    }
+
+    /// Calls recorded since call tracking was enabled, oldest first.
+    pub fn calls(&self) -> Vec<ProfileManagerCall> {
+        self.call_log.lock().unwrap().clone()
+    }
+
+    /// Whether a profile named `name` was created.
+    pub fn was_called_with_create(&self, name: &str) -> bool {
+        self.any_call(|call| matches!(call, ProfileManagerCall::Create(profile) if profile.name == name))
+    }
+
+    /// Whether the profile `name` was read.
+    pub fn was_called_with_read(&self, name: &str) -> bool {
+        self.any_call(|call| matches!(call, ProfileManagerCall::Read(read) if read == name))
+    }
+
+    /// Whether the profile `name` was updated.
+    pub fn was_called_with_update(&self, name: &str) -> bool {
+        self.any_call(|call| matches!(call, ProfileManagerCall::Update(updated, _) if updated == name))
     }
 
-    /// Configure read operation to fail.
-    pub fn fail_on_read(&self, should_fail: bool) {
-        *self.read_should_fail.lock().unwrap() = should_fail;
+    /// Whether the profile `name` was deleted.
+    pub fn was_called_with_delete(&self, name: &str) -> bool {
+        self.any_call(|call| matches!(call, ProfileManagerCall::Delete(deleted) if deleted == name))
     }
 
-    /// Configure update operation to fail.
-    pub fn fail_on_update(&self, should_fail: bool) {
-        *self.update_should_fail.lock().unwrap() = should_fail;
+    /// Number of create calls.
+    pub fn create_call_count(&self) -> usize {
+        self.count_calls(|call| matches!(call, ProfileManagerCall::Create(_)))
     }
 
-    /// Configure delete operation to fail.
-    pub fn fail_on_delete(&self, should_fail: bool) {
-        *self.delete_should_fail.lock().unwrap() = should_fail;
+    /// Number of batch create calls; the profiles of a batch also count as create calls.
+    pub fn batch_create_call_count(&self) -> usize {
+        self.count_calls(|call| matches!(call, ProfileManagerCall::BatchCreate(_)))
+    }
+
+    /// Number of read calls.
+    pub fn read_call_count(&self) -> usize {
+        self.count_calls(|call| matches!(call, ProfileManagerCall::Read(_)))
+    }
+
+    /// Number of update calls.
+    pub fn update_call_count(&self) -> usize {
+        self.count_calls(|call| matches!(call, ProfileManagerCall::Update(..)))
+    }
+
+    /// Number of delete calls.
+    pub fn delete_call_count(&self) -> usize {
+        self.count_calls(|call| matches!(call, ProfileManagerCall::Delete(_)))
+    }
+
+    /// Number of list calls, including calls listing archived profiles.
+    pub fn list_call_count(&self) -> usize {
+        self.count_calls(|call| matches!(call, ProfileManagerCall::List | ProfileManagerCall::ListAll))
+    }
+
+    fn any_call(&self, predicate: impl Fn(&ProfileManagerCall) -> bool) -> bool {
+        self.call_log.lock().unwrap().iter().any(predicate)
+    }
+
+    fn count_calls(&self, predicate: impl Fn(&ProfileManagerCall) -> bool) -> usize {
+        self.call_log.lock().unwrap().iter().filter(|call| predicate(call)).count()
+    }
+
+    fn record(&self, call: ProfileManagerCall) {
+        if self.track_calls {
+            self.call_log.lock().unwrap().push(call);
+        }
+    }
+
+    /// Fail with the error configured in `slot`, if any.
+    fn check(slot: &Mutex<Option<GitSetupError>>) -> Result<()> {
+        match slot.lock().unwrap().as_ref() {
+            Some(error) => Err(replay(error)),
+            None => Ok(()),
+        }
+    }
+
+    fn sorted_profiles(&self) -> Result<Vec<Profile>> {
+        Self::check(&self.list_error)?;
+        let profiles = self.profiles.lock().unwrap();
+        let mut profile_list: Vec<Profile> = profiles.values().cloned().collect();
+        profile_list.sort_by(|a, b| a.name.cmp(&b.name));
+        super::sort_by_order(&mut profile_list, &self.order.lock().unwrap());
+        Ok(profile_list)
+    }
+}
+
+/// Copy of a configured error, so it can be returned on every call.
+///
+/// Errors wrapping third-party errors are not `Clone` and come back as
+/// [`GitSetupError::Git`] with the same message.
+fn replay(error: &GitSetupError) -> GitSetupError {
+    match error {
+        GitSetupError::ConfigNotFound { path } => GitSetupError::ConfigNotFound { path: path.clone() },
+        GitSetupError::ProfileNotFound { name } => GitSetupError::ProfileNotFound { name: name.clone() },
+        GitSetupError::DuplicateProfile { name } => GitSetupError::DuplicateProfile { name: name.clone() },
+        GitSetupError::InvalidProfile { reason } => GitSetupError::InvalidProfile { reason: reason.clone() },
+        GitSetupError::ExternalCommand { command, error } => GitSetupError::ExternalCommand {
+            command: command.clone(),
+            error: error.clone(),
+        },
+        GitSetupError::OnePassword(message) => GitSetupError::OnePassword(message.clone()),
+        GitSetupError::Bitwarden(message) => GitSetupError::Bitwarden(message.clone()),
+        GitSetupError::Git(message) => GitSetupError::Git(message.clone()),
+        GitSetupError::Io(io) => GitSetupError::Io(std::io::Error::new(io.kind(), io.to_string())),
+        other => GitSetupError::Git(other.to_string()),
     }
 }
 
@@ -73,10 +239,16 @@ impl Default for MockProfileManager {
 
 impl super::ProfileManager for MockProfileManager {
     fn create(&self, profile: Profile) -> Result<()> {
-        if *self.create_should_fail.lock().unwrap() {
-            return Err(GitSetupError::DuplicateProfile {
-                name: profile.name.clone(),
-            });
+        self.record(ProfileManagerCall::Create(profile.clone()));
+        Self::check(&self.create_error)?;
+        if let Some(remaining) = self.creates_before_failure.lock().unwrap().as_mut() {
+            if *remaining == 0 {
+                return Err(GitSetupError::Git(format!(
+                    "Mock failure creating profile '{}'",
+                    profile.name
+                )));
+            }
+            *remaining -= 1;
         }
 
         let mut profiles = self.profiles.lock().unwrap();
@@ -90,23 +262,32 @@ impl super::ProfileManager for MockProfileManager {
         Ok(())
     }
 
-    fn read(&self, name: &str) -> Result<Option<Profile>> {
-        if *self.read_should_fail.lock().unwrap() {
-            return Err(GitSetupError::ProfileNotFound {
-                name: name.to_string(),
-            });
+    fn batch_create(&self, profiles: Vec<Profile>) -> Result<BatchResult> {
+        self.record(ProfileManagerCall::BatchCreate(
+            profiles.iter().map(|profile| profile.name.clone()).collect(),
+        ));
+        let mut result = BatchResult::default();
+        for profile in profiles {
+            let name = profile.name.clone();
+            match self.create(profile) {
+                Ok(()) => result.created += 1,
+                Err(error) => result.failed.push((name, error)),
+            }
         }
+        Ok(result)
+    }
+
+    fn read(&self, name: &str) -> Result<Option<Profile>> {
+        self.record(ProfileManagerCall::Read(name.to_string()));
+        Self::check(&self.read_error)?;
 
         let profiles = self.profiles.lock().unwrap();
         Ok(profiles.get(name).cloned())
     }
 
     fn update(&self, name: &str, profile: Profile) -> Result<()> {
-        if *self.update_should_fail.lock().unwrap() {
-            return Err(GitSetupError::ProfileNotFound {
-                name: name.to_string(),
-            });
-        }
+        self.record(ProfileManagerCall::Update(name.to_string(), profile.clone()));
+        Self::check(&self.update_error)?;
 
         let mut profiles = self.profiles.lock().unwrap();
         if !profiles.contains_key(name) {
@@ -130,11 +311,8 @@ impl super::ProfileManager for MockProfileManager {
     }
 
     fn delete(&self, name: &str) -> Result<()> {
-        if *self.delete_should_fail.lock().unwrap() {
-            return Err(GitSetupError::ProfileNotFound {
-                name: name.to_string(),
-            });
-        }
+        self.record(ProfileManagerCall::Delete(name.to_string()));
+        Self::check(&self.delete_error)?;
 
         let mut profiles = self.profiles.lock().unwrap();
         if !profiles.contains_key(name) {
@@ -148,16 +326,47 @@ impl super::ProfileManager for MockProfileManager {
     }
 
     fn list(&self) -> Result<Vec<Profile>> {
-        let profiles = self.profiles.lock().unwrap();
-        let mut profile_list: Vec<Profile> = profiles.values().cloned().collect();
-        profile_list.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(profile_list)
+        self.record(ProfileManagerCall::List);
+        Ok(self.sorted_profiles()?.into_iter().filter(|p| !p.is_archived).collect())
+    }
+
+    fn list_all(&self) -> Result<Vec<Profile>> {
+        self.record(ProfileManagerCall::ListAll);
+        self.sorted_profiles()
     }
 
     fn exists(&self, name: &str) -> Result<bool> {
+        self.record(ProfileManagerCall::Exists(name.to_string()));
         let profiles = self.profiles.lock().unwrap();
         Ok(profiles.contains_key(name))
     }
+
+    fn reorder(&self, order: Vec<String>) -> Result<()> {
+        self.record(ProfileManagerCall::Reorder(order.clone()));
+        let profiles = self.profiles.lock().unwrap();
+        if let Some(name) = order.iter().find(|name| !profiles.contains_key(*name)) {
+            return Err(GitSetupError::ProfileNotFound { name: name.clone() });
+        }
+        *self.order.lock().unwrap() = order;
+        Ok(())
+    }
+
+    fn record_activation(&self, record: ActivationRecord) -> Result<()> {
+        self.record(ProfileManagerCall::RecordActivation(record.clone()));
+        self.activations.lock().unwrap().push(record);
+        Ok(())
+    }
+
+    fn activation_history(&self) -> Result<Vec<ActivationRecord>> {
+        self.record(ProfileManagerCall::ActivationHistory);
+        Ok(self.activations.lock().unwrap().clone())
+    }
+
+    fn clear_activation_history(&self) -> Result<()> {
+        self.record(ProfileManagerCall::ClearActivationHistory);
+        self.activations.lock().unwrap().clear();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -184,6 +393,15 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
@@ -294,26 +512,66 @@ mod tests {
     }
 
     #[test]
-    fn test_mock_fail_on_create() {
-        let manager = MockProfileManager::new();
-        manager.fail_on_create(true);
+    fn test_mock_create_error() {
+        let manager = MockProfileManager::new()
+            .with_create_error(GitSetupError::DuplicateProfile { name: "test".to_string() });
 
-        let profile = test_profile();
-        let result = manager.create(profile);
+        let result = manager.create(test_profile());
         assert!(matches!(result, Err(GitSetupError::DuplicateProfile { .. })));
+        // The error is returned on every call
+        assert!(manager.create(test_profile()).is_err());
+        assert!(!manager.exists("test").unwrap());
     }
 
     #[test]
-    fn test_mock_fail_on_read() {
-        let manager = MockProfileManager::new();
-        let profile = test_profile();
-        manager.create(profile).unwrap();
+    fn test_mock_read_error() {
+        let manager = MockProfileManager::with_profiles(vec![test_profile()])
+            .with_read_error(GitSetupError::ProfileNotFound { name: "test".to_string() });
 
-        manager.fail_on_read(true);
         let result = manager.read("test");
         assert!(matches!(result, Err(GitSetupError::ProfileNotFound { .. })));
     }
 
+    #[test]
+    fn test_mock_update_delete_and_list_errors() {
+        let manager = MockProfileManager::with_profiles(vec![test_profile()])
+            .with_update_error(GitSetupError::Git("update failed".to_string()))
+            .with_delete_error(GitSetupError::Io(std::io::Error::other("disk full")))
+            .with_list_error(GitSetupError::InvalidProfile { reason: "corrupt".to_string() });
+
+        assert_eq!(manager.update("test", test_profile()).unwrap_err().to_string(), "Git error: update failed");
+        assert!(matches!(manager.delete("test"), Err(GitSetupError::Io(_))));
+        assert!(matches!(manager.list(), Err(GitSetupError::InvalidProfile { .. })));
+        assert!(manager.list_archived().is_err());
+        assert!(manager.exists("test").unwrap());
+    }
+
+    #[test]
+    fn test_mock_call_tracking() {
+        let manager = MockProfileManager::with_profiles(vec![test_profile()]).track_calls(true);
+        let mut renamed = test_profile();
+        renamed.name = "renamed".to_string();
+
+        manager.read("test").unwrap();
+        manager.update("test", renamed).unwrap();
+        manager.delete("renamed").unwrap();
+        let _ = manager.delete("renamed");
+        manager.list().unwrap();
+
+        assert!(manager.was_called_with_read("test"));
+        assert!(manager.was_called_with_update("test"));
+        assert!(manager.was_called_with_delete("renamed"));
+        assert!(!manager.was_called_with_create("test"));
+        assert_eq!(manager.delete_call_count(), 2);
+        assert_eq!(manager.list_call_count(), 1);
+        assert_eq!(manager.calls().len(), 5);
+
+        // Calls are only recorded when tracking is enabled
+        let untracked = MockProfileManager::new();
+        untracked.create(test_profile()).unwrap();
+        assert_eq!(untracked.create_call_count(), 0);
+    }
+
     #[test]
     fn test_mock_rename_profile() {
         let manager = MockProfileManager::new();
@@ -346,6 +604,26 @@ mod tests {
         assert!(matches!(result, Err(GitSetupError::DuplicateProfile { .. })));
     }
 
+    #[test]
+    fn test_mock_list_with_activation_dates() {
+        let mut other = test_profile();
+        other.name = "other".to_string();
+        let manager = MockProfileManager::with_profiles(vec![test_profile(), other]).with_activations(vec![
+            ActivationRecord::now("test", None),
+            ActivationRecord::now("test", Some(std::path::PathBuf::from("/repo"))),
+        ]);
+
+        let last = manager.last_activation("test").unwrap().unwrap();
+        assert_eq!(last.repo_path, Some(std::path::PathBuf::from("/repo")));
+        assert!(manager.last_activation("other").unwrap().is_none());
+
+        let listed = manager.list_with_activation_dates().unwrap();
+        assert_eq!(listed.len(), 2);
+        for (profile, record) in listed {
+            assert_eq!(record.is_some(), profile.name == "test");
+        }
+    }
+
     #[test]
     fn test_mock_thread_safety() {
         use std::thread;
@@ -372,4 +650,21 @@ mod tests {
         let profiles = manager.list().unwrap();
         assert_eq!(profiles.len(), 10);
     }
+
+    #[test]
+    fn test_mock_batch_create_call_counts() {
+        let manager = MockProfileManager::new().track_calls(true);
+        manager.fail_on_create_after(2);
+        let profiles = (1..=4)
+            .map(|i| Profile { name: format!("test{}", i), ..test_profile() })
+            .collect();
+
+        let result = manager.batch_create(profiles).unwrap();
+
+        assert_eq!(result.created, 2);
+        assert_eq!(result.failed.len(), 2);
+        assert_eq!(manager.batch_create_call_count(), 1);
+        // The batch does not stop at the first failure
+        assert_eq!(manager.create_call_count(), 4);
+    }
 }
@@ -3,10 +3,31 @@
 //! This module provides functionality for managing git configuration profiles,
 //! including CRUD operations, validation, and persistence.
 
+pub mod activation;
+pub mod allowed_signers;
+pub mod analytics;
+pub mod bundle;
+pub mod bulk;
+pub mod counts;
+pub mod diff;
+pub mod duplicates;
+pub mod health;
+pub mod includes;
 pub mod manager;
 pub mod mock;
-
-use crate::{config::types::Profile, error::Result, matching::{MatchResult, ProfileFuzzyMatcher, FuzzyMatcher}};
+pub mod schema;
+pub mod transaction;
+pub mod undo;
+pub mod validator;
+pub mod watch;
+
+use crate::{config::types::Profile, error::{GitSetupError, Result}, matching::{MatchResult, ProfileFuzzyMatcher, FuzzyMatcher}};
+use activation::ActivationRecord;
+use bulk::{BatchResult, ProfileUpdate};
+use counts::{ProfileCountsByKeyType, ProfileCountsByOnePassword, ProfileCountsByScope};
+use transaction::ProfileSnapshot;
+use watch::{ChangeCallback, ProfileChangeEvent, WatchHandle};
+use std::path::Path;
 
 /// Trait defining all profile management operations.
 ///
@@ -25,6 +46,28 @@ pub trait ProfileManager: Send + Sync {
     /// - The profile data is invalid
     fn create(&self, profile: Profile) -> Result<()>;
 
+    /// Create several profiles, e.g. when loading them from a dotfile repository.
+    ///
+    /// Every profile is attempted: one that fails is reported in the result
+    /// rather than stopping the batch. The default implementation calls
+    /// [`ProfileManager::create`] for each profile; backends that can hold
+    /// their store locked for the whole batch should override it.
+    ///
+    /// # Errors
+    /// Only returns an error if the store itself fails; failures of single
+    /// profiles are listed in [`BatchResult::failed`]
+    fn batch_create(&self, profiles: Vec<Profile>) -> Result<BatchResult> {
+        let mut result = BatchResult::default();
+        for profile in profiles {
+            let name = profile.name.clone();
+            match self.create(profile) {
+                Ok(()) => result.created += 1,
+                Err(error) => result.failed.push((name, error)),
+            }
+        }
+        Ok(result)
+    }
+
     /// Read a profile by name.
     ///
     /// # Arguments
@@ -58,12 +101,41 @@ pub trait ProfileManager: Send + Sync {
     /// Returns an error if the profile does not exist
     fn delete(&self, name: &str) -> Result<()>;
 
-    /// List all profiles.
+    /// List all profiles that are not archived.
     ///
     /// # Returns
-    /// A vector of all profiles, sorted by name
+    /// A vector of the active profiles, sorted by name
     fn list(&self) -> Result<Vec<Profile>>;
 
+    /// List all profiles, including archived ones.
+    ///
+    /// Managers that cannot store archived profiles return the same as [`ProfileManager::list`].
+    fn list_all(&self) -> Result<Vec<Profile>> {
+        self.list()
+    }
+
+    /// List only the archived profiles, sorted by name.
+    fn list_archived(&self) -> Result<Vec<Profile>> {
+        Ok(self.list_all()?.into_iter().filter(|p| p.is_archived).collect())
+    }
+
+    /// Count the active profiles by key type.
+    ///
+    /// Managers backed by a database can count without loading every profile.
+    fn count_by_key_type(&self) -> Result<ProfileCountsByKeyType> {
+        Ok(ProfileCountsByKeyType::from_profiles(&self.list()?))
+    }
+
+    /// Count the active profiles by git config scope.
+    fn count_by_scope(&self) -> Result<ProfileCountsByScope> {
+        Ok(ProfileCountsByScope::from_profiles(&self.list()?))
+    }
+
+    /// Count the active profiles with and without 1Password integration.
+    fn count_by_one_password(&self) -> Result<ProfileCountsByOnePassword> {
+        Ok(ProfileCountsByOnePassword::from_profiles(&self.list()?))
+    }
+
     /// Check if a profile exists.
     ///
     /// # Arguments
@@ -73,15 +145,196 @@ pub trait ProfileManager: Send + Sync {
     /// `true` if the profile exists, `false` otherwise
     fn exists(&self, name: &str) -> Result<bool>;
 
+    /// Archive a profile, hiding it from [`ProfileManager::list`] without deleting it.
+    ///
+    /// Archived profiles can still be read by name and restored with
+    /// [`ProfileManager::unarchive`].
+    ///
+    /// # Errors
+    /// Returns an error if the profile does not exist
+    fn archive(&self, name: &str) -> Result<()> {
+        set_archived(self, name, true)
+    }
+
+    /// Restore an archived profile.
+    ///
+    /// # Errors
+    /// Returns an error if the profile does not exist
+    fn unarchive(&self, name: &str) -> Result<()> {
+        set_archived(self, name, false)
+    }
+
+    /// Store a new listing order for the profiles.
+    ///
+    /// `order` lists profile names in the order [`ProfileManager::list`] should
+    /// return them; profiles missing from it are listed after it by name.
+    ///
+    /// # Errors
+    /// Returns an error if a name does not exist or the manager cannot store an order
+    fn reorder(&self, _order: Vec<String>) -> Result<()> {
+        Err(GitSetupError::Git("This profile store cannot reorder profiles".to_string()))
+    }
+
+    /// Reverse the most recent create, update, or delete.
+    ///
+    /// # Returns
+    /// - `Ok(Some(name))` with the affected profile name if an operation was undone
+    /// - `Ok(None)` if there is nothing to undo or the manager keeps no history
+    fn undo(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Re-apply the most recently undone operation.
+    ///
+    /// # Returns
+    /// - `Ok(Some(name))` with the affected profile name if an operation was redone
+    /// - `Ok(None)` if there is nothing to redo or the manager keeps no history
+    fn redo(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Record that a profile was applied.
+    ///
+    /// Managers that keep no activation history ignore the record.
+    fn record_activation(&self, _record: ActivationRecord) -> Result<()> {
+        Ok(())
+    }
+
+    /// Get all recorded profile activations, oldest first.
+    fn activation_history(&self) -> Result<Vec<ActivationRecord>> {
+        Ok(Vec::new())
+    }
+
+    /// Remove every recorded profile activation.
+    ///
+    /// Managers that keep no activation history have nothing to clear.
+    fn clear_activation_history(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Get the most recent activation of a profile, if it was ever applied.
+    fn last_activation(&self, profile_name: &str) -> Result<Option<ActivationRecord>> {
+        Ok(self
+            .activation_history()?
+            .into_iter()
+            .rev()
+            .find(|record| record.profile_name == profile_name))
+    }
+
+    /// List all profiles together with their most recent activation.
+    fn list_with_activation_dates(&self) -> Result<Vec<(Profile, Option<ActivationRecord>)>> {
+        let history = self.activation_history()?;
+        Ok(self
+            .list()?
+            .into_iter()
+            .map(|profile| {
+                let last = history.iter().rev().find(|record| record.profile_name == profile.name).cloned();
+                (profile, last)
+            })
+            .collect())
+    }
+
+    /// Capture the stored profiles so a failed transaction can be rolled back.
+    fn snapshot(&self) -> Result<ProfileSnapshot> {
+        Ok(ProfileSnapshot::new(self.list_all()?))
+    }
+
+    /// Roll the store back to a snapshot taken with [`ProfileManager::snapshot`].
+    ///
+    /// The default implementation replays creates, updates and deletes; backends
+    /// that can swap their whole state at once should override it.
+    fn restore(&self, snapshot: ProfileSnapshot) -> Result<()> {
+        transaction::restore_profiles(self, snapshot.profiles())
+    }
+
+    /// Report profile changes made through this or any other handle on the store.
+    ///
+    /// `callback` runs on a background thread until the returned handle is
+    /// dropped. The default implementation cannot see changes and returns
+    /// [`WatchHandle::noop`].
+    fn watch(&self, callback: ChangeCallback) -> Result<WatchHandle> {
+        let _ = callback;
+        Ok(WatchHandle::noop())
+    }
+
+    /// Call `callback` for every profile created, updated or deleted until the handle is dropped.
+    ///
+    /// See [`ProfileManager::watch`], which trait objects call with a boxed callback.
+    fn watch_changes<F>(&self, callback: F) -> Result<WatchHandle>
+    where
+        Self: Sized,
+        F: Fn(ProfileChangeEvent) + Send + 'static,
+    {
+        self.watch(Box::new(callback))
+    }
+
+    /// Run several operations as one all-or-nothing update.
+    ///
+    /// If `f` fails, every change it made is rolled back and its error returned.
+    fn transaction<F>(&self, f: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnOnce(&mut dyn ProfileManager) -> Result<()>,
+    {
+        transaction::run_transaction(self, f)
+    }
+
+    /// Apply `update` to every profile matching `filter`, returning how many changed.
+    ///
+    /// The profiles are updated in one transaction: if any update fails, none are kept.
+    fn bulk_update<F>(&self, filter: F, update: ProfileUpdate) -> Result<usize>
+    where
+        Self: Sized,
+        F: Fn(&Profile) -> bool,
+    {
+        bulk::bulk_update(self, filter, &update)
+    }
+
+    /// Find the active profiles whose `git_user_email` is `email`, ignoring case.
+    ///
+    /// Unlike [`ProfileManager::fuzzy_find`] this is an exact lookup, e.g. for
+    /// the email set in an existing git config. The default implementation
+    /// scans [`ProfileManager::list`]; backends that index profiles by email
+    /// should override it.
+    fn search_by_email(&self, email: &str) -> Result<Vec<Profile>> {
+        let email = email.to_lowercase();
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|profile| profile.git_user_email.to_lowercase() == email)
+            .collect())
+    }
+
+    /// List the active profiles tagged with `tag`.
+    ///
+    /// The default implementation scans [`ProfileManager::list`].
+    fn list_by_tag(&self, tag: &str) -> Result<Vec<Profile>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|profile| profile.tags.iter().any(|t| t == tag))
+            .collect())
+    }
+
+    /// Write `[includeIf "gitdir:<dir>/"]` blocks for the active profiles to `output_path`.
+    ///
+    /// Each profile with `include_if_dirs` gets a per-profile include file,
+    /// written next to `output_path`, holding its `[user]`, `[commit]` and
+    /// `[gpg]` settings. The result works without git-setup installed.
+    fn export_to_gitconfig_includes(&self, output_path: &Path) -> Result<()> {
+        includes::export_to_gitconfig_includes(&self.list()?, output_path)
+    }
+
     /// Find profiles using fuzzy matching.
     ///
     /// # Arguments
     /// * `query` - The search query string
+    /// * `include_archived` - Whether archived profiles can match
     ///
     /// # Returns
     /// A vector of match results sorted by score (highest first)
-    fn fuzzy_find(&self, query: &str) -> Result<Vec<MatchResult>> {
-        let profiles = self.list()?;
+    fn fuzzy_find(&self, query: &str, include_archived: bool) -> Result<Vec<MatchResult>> {
+        let profiles = if include_archived { self.list_all()? } else { self.list()? };
         let matcher = ProfileFuzzyMatcher::new();
         Ok(matcher.find_matches(query, &profiles))
     }
@@ -101,11 +354,32 @@ pub trait ProfileManager: Send + Sync {
     }
 }
 
+/// Set the archived flag of a profile through a regular update.
+/// Stable-sort `profiles` so the names in `order` come first, in that order.
+pub(crate) fn sort_by_order(profiles: &mut [Profile], order: &[String]) {
+    if order.is_empty() {
+        return;
+    }
+    profiles.sort_by_key(|profile| order.iter().position(|name| *name == profile.name).unwrap_or(order.len()));
+}
+
+fn set_archived<M: ProfileManager + ?Sized>(manager: &M, name: &str, archived: bool) -> Result<()> {
+    let mut profile = manager.read(name)?.ok_or_else(|| GitSetupError::ProfileNotFound {
+        name: name.to_string(),
+    })?;
+    if profile.is_archived == archived {
+        return Ok(());
+    }
+    profile.is_archived = archived;
+    manager.update(name, profile)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::Arc;
     use crate::config::types::{KeyType, Scope};
+    use crate::profile::mock::MockProfileManager;
 
     /// Test that the ProfileManager trait is object-safe
     #[test]
@@ -149,76 +423,50 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
-    /// Mock implementation of ProfileManager for testing
-    struct MockProfileManager {
-        profiles: Vec<Profile>,
-    }
-
-    impl MockProfileManager {
-        fn new() -> Self {
-            Self {
-                profiles: vec![
-                    create_test_profile("work-project"),
-                    create_test_profile("personal"),
-                    create_test_profile("opensource"),
-                ],
-            }
-        }
-    }
-
-    impl ProfileManager for MockProfileManager {
-        fn create(&self, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn read(&self, name: &str) -> Result<Option<Profile>> {
-            Ok(self.profiles.iter().find(|p| p.name == name).cloned())
-        }
-
-        fn update(&self, _name: &str, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn delete(&self, _name: &str) -> Result<()> {
-            Ok(())
-        }
-
-        fn list(&self) -> Result<Vec<Profile>> {
-            Ok(self.profiles.clone())
-        }
-
-        fn exists(&self, name: &str) -> Result<bool> {
-            Ok(self.profiles.iter().any(|p| p.name == name))
-        }
+    fn test_manager() -> MockProfileManager {
+        MockProfileManager::with_profiles(vec![
+            create_test_profile("work-project"),
+            create_test_profile("personal"),
+            create_test_profile("opensource"),
+        ])
     }
 
     #[test]
     fn test_fuzzy_find_integration() {
-        let manager = MockProfileManager::new();
+        let manager = test_manager();
 
         // Test exact match
-        let results = manager.fuzzy_find("personal").unwrap();
+        let results = manager.fuzzy_find("personal", false).unwrap();
         assert!(!results.is_empty());
         assert_eq!(results[0].profile.name, "personal");
         assert!(results[0].score > 0.9);
 
         // Test fuzzy match
-        let results = manager.fuzzy_find("wrk").unwrap();
+        let results = manager.fuzzy_find("wrk", false).unwrap();
         assert!(!results.is_empty());
         let work_match = results.iter().find(|r| r.profile.name == "work-project");
         assert!(work_match.is_some());
 
         // Test no match
-        let results = manager.fuzzy_find("xyzzyx").unwrap();
+        let results = manager.fuzzy_find("xyzzyx", false).unwrap();
         assert!(results.is_empty());
     }
 
     #[test]
     fn test_fuzzy_best_integration() {
-        let manager = MockProfileManager::new();
+        let manager = test_manager();
 
         // Test exact match returns best
         let best = manager.fuzzy_best("personal").unwrap();
@@ -236,11 +484,12 @@ mod tests {
 
     #[test]
     fn test_fuzzy_methods_use_profile_list() {
-        let manager = MockProfileManager::new();
+        let manager = test_manager().track_calls(true);
 
         // Verify the fuzzy methods use the same profiles as list()
         let all_profiles = manager.list().unwrap();
-        let fuzzy_results = manager.fuzzy_find("o").unwrap(); // Should match multiple profiles
+        let fuzzy_results = manager.fuzzy_find("o", false).unwrap(); // Should match multiple profiles
+        assert_eq!(manager.list_call_count(), 2);
 
         // All fuzzy results should be from the profile list
         for result in fuzzy_results {
@@ -250,9 +499,9 @@ mod tests {
 
     #[test]
     fn test_fuzzy_find_sorted_by_score() {
-        let manager = MockProfileManager::new();
+        let manager = test_manager();
 
-        let results = manager.fuzzy_find("o").unwrap();
+        let results = manager.fuzzy_find("o", false).unwrap();
 
         // Results should be sorted by score (highest first)
         if results.len() > 1 {
@@ -266,21 +515,23 @@ mod tests {
     }
 
     #[test]
-    fn test_fuzzy_methods_handle_empty_profile_list() {
-        struct EmptyProfileManager;
-
-        impl ProfileManager for EmptyProfileManager {
-            fn create(&self, _profile: Profile) -> Result<()> { Ok(()) }
-            fn read(&self, _name: &str) -> Result<Option<Profile>> { Ok(None) }
-            fn update(&self, _name: &str, _profile: Profile) -> Result<()> { Ok(()) }
-            fn delete(&self, _name: &str) -> Result<()> { Ok(()) }
-            fn list(&self) -> Result<Vec<Profile>> { Ok(vec![]) }
-            fn exists(&self, _name: &str) -> Result<bool> { Ok(false) }
-        }
+    fn test_fuzzy_find_excludes_archived_by_default() {
+        let manager = MockProfileManager::with_profiles(vec![Profile {
+            is_archived: true,
+            description: None,
+            ..create_test_profile("personal")
+        }]);
+
+        assert!(manager.fuzzy_find("personal", false).unwrap().is_empty());
+        assert_eq!(manager.fuzzy_find("personal", true).unwrap().len(), 1);
+        assert!(manager.fuzzy_best("personal").unwrap().is_none());
+    }
 
-        let manager = EmptyProfileManager;
+    #[test]
+    fn test_fuzzy_methods_handle_empty_profile_list() {
+        let manager = MockProfileManager::new();
 
-        let results = manager.fuzzy_find("anything").unwrap();
+        let results = manager.fuzzy_find("anything", false).unwrap();
         assert!(results.is_empty());
 
         let best = manager.fuzzy_best("anything").unwrap();
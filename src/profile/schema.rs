@@ -0,0 +1,313 @@
+//! JSON Schema for serialized profiles.
+//!
+//! [`ProfileJsonSchema::generate`] describes the JSON serialization of
+//! [`Profile`](crate::config::types::Profile) as a JSON Schema draft-7 document, so IDE plugins and CI scripts
+//! can check profile files before importing them. The schema is versioned by
+//! its `$id` and only changes incompatibly under a new version.
+//! [`ProfileJsonSchema::validate`] checks a JSON value against it.
+
+use crate::config::types::{KeyType, Scope, SecretStoreKind, SshKeySource};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Identifier of the current version of the profile schema.
+pub const PROFILE_SCHEMA_ID: &str = "https://git-setup.rs/schema/profile/v1";
+
+/// JSON type of a profile field.
+enum FieldKind {
+    String,
+    Boolean,
+    StringList,
+    KeyType,
+    Scope,
+    SshKeySource,
+    SecretStore,
+    /// Git config keys mapped to scopes
+    ScopeMap,
+}
+
+/// Schema of one serialized profile field.
+struct FieldSchema {
+    name: &'static str,
+    kind: FieldKind,
+    /// Whether deserializing fails without the field
+    required: bool,
+    /// Whether the field serializes as `null` when unset
+    nullable: bool,
+    description: &'static str,
+}
+
+impl FieldSchema {
+    const fn required(name: &'static str, kind: FieldKind, description: &'static str) -> Self {
+        Self { name, kind, required: true, nullable: false, description }
+    }
+
+    const fn optional(name: &'static str, kind: FieldKind, description: &'static str) -> Self {
+        Self { name, kind, required: false, nullable: true, description }
+    }
+
+    const fn defaulted(name: &'static str, kind: FieldKind, description: &'static str) -> Self {
+        Self { name, kind, required: false, nullable: false, description }
+    }
+}
+
+/// Fields of a profile in serialization order.
+const PROFILE_FIELDS: &[FieldSchema] = &[
+    FieldSchema::required("name", FieldKind::String, "Unique name of the profile"),
+    FieldSchema::optional("git_user_name", FieldKind::String, "Name recorded in commits (user.name)"),
+    FieldSchema::required("git_user_email", FieldKind::String, "Email recorded in commits (user.email)"),
+    FieldSchema::required("key_type", FieldKind::KeyType, "Type of the key commits are signed with"),
+    FieldSchema::optional("signing_key", FieldKind::String, "Public key or key ID used to sign commits"),
+    FieldSchema::optional("vault_name", FieldKind::String, "1Password vault holding the key"),
+    FieldSchema::optional("ssh_key_title", FieldKind::String, "Title of the SSH key item in 1Password"),
+    FieldSchema::optional("scope", FieldKind::Scope, "Git config the profile is written to"),
+    FieldSchema::optional("ssh_key_source", FieldKind::SshKeySource, "Where the SSH key comes from"),
+    FieldSchema::optional("ssh_key_path", FieldKind::String, "Path to the private SSH key"),
+    FieldSchema::optional("allowed_signers", FieldKind::String, "File of trusted SSH signing keys"),
+    FieldSchema::defaulted("match_patterns", FieldKind::StringList, "Remote URL patterns selecting the profile"),
+    FieldSchema::defaulted("repos", FieldKind::StringList, "Repositories the profile is used for"),
+    FieldSchema::defaulted("include_if_dirs", FieldKind::StringList, "Directories covered by an includeIf section"),
+    FieldSchema::defaulted("host_patterns", FieldKind::StringList, "Hostname patterns selecting the profile"),
+    FieldSchema::defaulted("one_password", FieldKind::Boolean, "Load the key from 1Password"),
+    FieldSchema::defaulted("require_passphrase", FieldKind::Boolean, "Prompt for the key's passphrase when applying"),
+    FieldSchema::defaulted("is_archived", FieldKind::Boolean, "Hide the profile from listings and detection"),
+    FieldSchema::optional("description", FieldKind::String, "What the profile is for"),
+    FieldSchema::defaulted("per_field_scope", FieldKind::ScopeMap, "Scopes overriding `scope` for single git config keys"),
+    FieldSchema::optional("git_hooks_dir", FieldKind::String, "Directory of git hooks set as core.hooksPath"),
+    FieldSchema::optional("allowed_signers_content", FieldKind::String, "Entries of the allowed signers file"),
+    FieldSchema::optional("secret_store", FieldKind::SecretStore, "Secret manager the key is loaded from (default: onepassword)"),
+    FieldSchema::optional("rotated_key_item", FieldKind::String, "1Password item of the key replaced by a rotation"),
+    FieldSchema::defaulted("tags", FieldKind::StringList, "Groups the profile belongs to"),
+];
+
+/// Builds and checks against the JSON Schema of a profile.
+pub struct ProfileJsonSchema;
+
+impl ProfileJsonSchema {
+    /// The JSON Schema draft-7 document describing a serialized profile.
+    pub fn generate() -> Value {
+        let properties: Map<String, Value> = PROFILE_FIELDS
+            .iter()
+            .map(|field| (field.name.to_string(), Self::property(field)))
+            .collect();
+        let required: Vec<&str> = PROFILE_FIELDS
+            .iter()
+            .filter(|field| field.required)
+            .map(|field| field.name)
+            .collect();
+
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "$id": PROFILE_SCHEMA_ID,
+            "title": "git-setup profile",
+            "description": "A git identity and signing configuration managed by git-setup",
+            "type": "object",
+            "required": required,
+            "properties": properties,
+            "additionalProperties": false,
+        })
+    }
+
+    /// Schema of a single field.
+    fn property(field: &FieldSchema) -> Value {
+        let mut property = match &field.kind {
+            FieldKind::String => json!({ "type": "string" }),
+            FieldKind::Boolean => json!({ "type": "boolean" }),
+            FieldKind::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+            FieldKind::KeyType => Self::string_enum(&[KeyType::Ssh, KeyType::Gpg, KeyType::X509, KeyType::Gitsign]),
+            FieldKind::Scope => Self::string_enum(&Self::scopes()),
+            FieldKind::SshKeySource => Self::string_enum(&[
+                SshKeySource::OnePassword,
+                SshKeySource::AuthorizedKeys,
+                SshKeySource::File,
+            ]),
+            FieldKind::SecretStore => Self::string_enum(&[SecretStoreKind::OnePassword, SecretStoreKind::Bitwarden]),
+            FieldKind::ScopeMap => json!({
+                "type": "object",
+                "additionalProperties": Self::string_enum(&Self::scopes()),
+            }),
+        };
+
+        if field.nullable {
+            // Unset optional fields serialize as null
+            let kind = property["type"].clone();
+            property["type"] = json!([kind, "null"]);
+            if let Some(values) = property.get_mut("enum").and_then(Value::as_array_mut) {
+                values.push(Value::Null);
+            }
+        }
+        property["description"] = json!(field.description);
+        property
+    }
+
+    fn scopes() -> [Scope; 3] {
+        [Scope::Local, Scope::Global, Scope::System]
+    }
+
+    /// A string schema allowing the serialized names of `variants`.
+    fn string_enum<T: Serialize>(variants: &[T]) -> Value {
+        let names: Vec<Value> = variants
+            .iter()
+            .map(|variant| serde_json::to_value(variant).unwrap_or(Value::Null))
+            .collect();
+        json!({ "type": "string", "enum": names })
+    }
+
+    /// Check `value` against the profile schema, returning each violation.
+    ///
+    /// Only the keywords the profile schema uses are supported.
+    pub fn validate(value: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        Self::validate_against(&Self::generate(), value, "$", &mut errors);
+        errors
+    }
+
+    fn validate_against(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+        if let Some(expected) = schema.get("type") {
+            let types: Vec<&str> = match expected {
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                other => other.as_str().into_iter().collect(),
+            };
+            if !types.iter().any(|kind| Self::has_type(value, kind)) {
+                errors.push(format!("{}: expected {}, found {}", path, types.join(" or "), Self::type_name(value)));
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+            && !allowed.contains(value)
+        {
+            let names: Vec<String> = allowed.iter().filter(|v| !v.is_null()).map(Value::to_string).collect();
+            errors.push(format!("{}: {} is not one of {}", path, value, names.join(", ")));
+        }
+
+        if let Value::Object(object) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                if let Some(name) = name.as_str().filter(|name| !object.contains_key(*name)) {
+                    errors.push(format!("{}: missing required field '{}'", path, name));
+                }
+            }
+            for (name, field) in object {
+                let field_path = format!("{}.{}", path, name);
+                match (properties.and_then(|properties| properties.get(name)), schema.get("additionalProperties")) {
+                    (Some(property), _) => Self::validate_against(property, field, &field_path, errors),
+                    (None, Some(Value::Bool(false))) => errors.push(format!("{}: unknown field", field_path)),
+                    (None, Some(additional @ Value::Object(_))) => {
+                        Self::validate_against(additional, field, &field_path, errors)
+                    }
+                    (None, _) => {}
+                }
+            }
+        }
+
+        if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+            for (index, item) in items.iter().enumerate() {
+                Self::validate_against(item_schema, item, &format!("{}[{}]", path, index), errors);
+            }
+        }
+    }
+
+    fn has_type(value: &Value, kind: &str) -> bool {
+        match kind {
+            "string" => value.is_string(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            "null" => value.is_null(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            _ => false,
+        }
+    }
+
+    fn type_name(value: &Value) -> &'static str {
+        match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::Profile;
+
+    /// Serialized field names of `Profile`, for checking the schema covers them.
+    fn serialized_fields() -> Vec<String> {
+        let profile = Profile {
+            per_field_scope: [("commit.gpgsign".to_string(), Scope::Local)].into(),
+            ..Default::default()
+        };
+        match serde_json::to_value(profile).unwrap() {
+            Value::Object(object) => object.keys().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_schema_covers_every_profile_field() {
+        let mut schema_fields: Vec<String> = PROFILE_FIELDS.iter().map(|field| field.name.to_string()).collect();
+        let mut profile_fields = serialized_fields();
+        schema_fields.sort();
+        profile_fields.sort();
+        assert_eq!(schema_fields, profile_fields);
+    }
+
+    #[test]
+    fn test_generate_schema() {
+        let schema = ProfileJsonSchema::generate();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert_eq!(schema["$id"], PROFILE_SCHEMA_ID);
+        assert_eq!(schema["required"], json!(["name", "git_user_email", "key_type"]));
+        assert_eq!(schema["properties"]["key_type"]["enum"], json!(["ssh", "gpg", "x509", "gitsign"]));
+        assert_eq!(schema["properties"]["scope"]["type"], json!(["string", "null"]));
+        assert_eq!(schema["properties"]["scope"]["enum"], json!(["local", "global", "system", null]));
+        assert!(schema["properties"]["git_user_name"]["description"].is_string());
+    }
+
+    #[test]
+    fn test_serialized_profiles_are_valid() {
+        let profile = Profile {
+            name: "work".to_string(),
+            git_user_email: "jane@work.com".to_string(),
+            git_user_name: Some("Jane".to_string()),
+            scope: Some(Scope::Global),
+            per_field_scope: [("commit.gpgsign".to_string(), Scope::Local)].into(),
+            match_patterns: vec!["github.com/acme/*".to_string()],
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&profile).unwrap();
+        assert_eq!(ProfileJsonSchema::validate(&value), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validate_reports_violations() {
+        let value = json!({
+            "name": "work",
+            "key_type": "rsa",
+            "scope": "repo",
+            "repos": ["a", 1],
+            "per_field_scope": { "user.email": "everywhere" },
+            "colour": "blue",
+        });
+        assert_eq!(
+            ProfileJsonSchema::validate(&value),
+            vec![
+                "$: missing required field 'git_user_email'".to_string(),
+                "$.colour: unknown field".to_string(),
+                "$.key_type: \"rsa\" is not one of \"ssh\", \"gpg\", \"x509\", \"gitsign\"".to_string(),
+                "$.per_field_scope.user.email: \"everywhere\" is not one of \"local\", \"global\", \"system\"".to_string(),
+                "$.repos[1]: expected string, found number".to_string(),
+                "$.scope: \"repo\" is not one of \"local\", \"global\", \"system\"".to_string(),
+            ]
+        );
+
+        assert_eq!(ProfileJsonSchema::validate(&json!([])), vec!["$: expected object, found array".to_string()]);
+    }
+}
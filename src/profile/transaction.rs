@@ -0,0 +1,348 @@
+//! All-or-nothing profile updates.
+//!
+//! A transaction snapshots the profile store, runs a closure against it and
+//! restores the snapshot if the closure fails, so a batch of operations such as
+//! an import either fully succeeds or leaves the store untouched.
+
+use super::{
+    activation::ActivationRecord,
+    counts::{ProfileCountsByKeyType, ProfileCountsByOnePassword, ProfileCountsByScope},
+    undo::UndoRedoStack,
+    watch::{ChangeCallback, WatchHandle},
+    ProfileManager,
+};
+use crate::{
+    config::types::Profile,
+    error::{Result, ResultExt},
+};
+use std::sync::Arc;
+
+/// Saved state of a profile store, used to roll back a failed transaction.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileSnapshot {
+    pub(crate) profiles: Vec<Profile>,
+    pub(crate) default_profile: Option<String>,
+    pub(crate) history: Option<UndoRedoStack>,
+}
+
+impl ProfileSnapshot {
+    /// Snapshot of a store holding `profiles`.
+    pub fn new(profiles: Vec<Profile>) -> Self {
+        Self {
+            profiles,
+            ..Default::default()
+        }
+    }
+
+    /// Profiles stored when the snapshot was taken.
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+}
+
+/// Bring `manager` back to `profiles` using only its create, update and delete operations.
+pub fn restore_profiles<M: ProfileManager + ?Sized>(manager: &M, profiles: &[Profile]) -> Result<()> {
+    for current in manager.list_all()? {
+        if !profiles.iter().any(|profile| profile.name == current.name) {
+            manager.delete(&current.name)?;
+        }
+    }
+
+    for profile in profiles {
+        if manager.exists(&profile.name)? {
+            manager.update(&profile.name, profile.clone())?;
+        } else {
+            manager.create(profile.clone())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `f` against `manager`, undoing all of its changes if it fails.
+pub fn run_transaction<F>(manager: &dyn ProfileManager, f: F) -> Result<()>
+where
+    F: FnOnce(&mut dyn ProfileManager) -> Result<()>,
+{
+    let snapshot = manager.snapshot()?;
+
+    match f(&mut Borrowed(manager)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            manager
+                .restore(snapshot)
+                .with_context("while rolling back a failed transaction")?;
+            Err(e)
+        }
+    }
+}
+
+/// Wrapper that adds transactions to any shared profile manager.
+pub struct TransactionalProfileManager {
+    inner: Arc<dyn ProfileManager>,
+}
+
+impl TransactionalProfileManager {
+    /// Wrap `inner`.
+    pub fn new(inner: Arc<dyn ProfileManager>) -> Self {
+        Self { inner }
+    }
+
+    /// Run `f` as a transaction: either all of its changes are kept or none are.
+    pub fn transaction<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn ProfileManager) -> Result<()>,
+    {
+        run_transaction(self.inner.as_ref(), f)
+    }
+}
+
+impl ProfileManager for TransactionalProfileManager {
+    fn create(&self, profile: Profile) -> Result<()> {
+        self.inner.create(profile)
+    }
+
+    fn read(&self, name: &str) -> Result<Option<Profile>> {
+        self.inner.read(name)
+    }
+
+    fn update(&self, name: &str, profile: Profile) -> Result<()> {
+        self.inner.update(name, profile)
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        self.inner.delete(name)
+    }
+
+    fn list(&self) -> Result<Vec<Profile>> {
+        self.inner.list()
+    }
+
+    fn list_all(&self) -> Result<Vec<Profile>> {
+        self.inner.list_all()
+    }
+
+    fn count_by_key_type(&self) -> Result<ProfileCountsByKeyType> {
+        self.inner.count_by_key_type()
+    }
+
+    fn count_by_scope(&self) -> Result<ProfileCountsByScope> {
+        self.inner.count_by_scope()
+    }
+
+    fn count_by_one_password(&self) -> Result<ProfileCountsByOnePassword> {
+        self.inner.count_by_one_password()
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        self.inner.exists(name)
+    }
+
+    fn archive(&self, name: &str) -> Result<()> {
+        self.inner.archive(name)
+    }
+
+    fn unarchive(&self, name: &str) -> Result<()> {
+        self.inner.unarchive(name)
+    }
+
+    fn reorder(&self, order: Vec<String>) -> Result<()> {
+        self.inner.reorder(order)
+    }
+
+    fn undo(&self) -> Result<Option<String>> {
+        self.inner.undo()
+    }
+
+    fn redo(&self) -> Result<Option<String>> {
+        self.inner.redo()
+    }
+
+    fn record_activation(&self, record: ActivationRecord) -> Result<()> {
+        self.inner.record_activation(record)
+    }
+
+    fn activation_history(&self) -> Result<Vec<ActivationRecord>> {
+        self.inner.activation_history()
+    }
+
+    fn clear_activation_history(&self) -> Result<()> {
+        self.inner.clear_activation_history()
+    }
+
+    fn snapshot(&self) -> Result<ProfileSnapshot> {
+        self.inner.snapshot()
+    }
+
+    fn restore(&self, snapshot: ProfileSnapshot) -> Result<()> {
+        self.inner.restore(snapshot)
+    }
+
+    fn watch(&self, callback: ChangeCallback) -> Result<WatchHandle> {
+        self.inner.watch(callback)
+    }
+}
+
+/// Profile manager handed to a transaction closure.
+struct Borrowed<'a>(&'a dyn ProfileManager);
+
+impl ProfileManager for Borrowed<'_> {
+    fn create(&self, profile: Profile) -> Result<()> {
+        self.0.create(profile)
+    }
+
+    fn read(&self, name: &str) -> Result<Option<Profile>> {
+        self.0.read(name)
+    }
+
+    fn update(&self, name: &str, profile: Profile) -> Result<()> {
+        self.0.update(name, profile)
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        self.0.delete(name)
+    }
+
+    fn list(&self) -> Result<Vec<Profile>> {
+        self.0.list()
+    }
+
+    fn list_all(&self) -> Result<Vec<Profile>> {
+        self.0.list_all()
+    }
+
+    fn count_by_key_type(&self) -> Result<ProfileCountsByKeyType> {
+        self.0.count_by_key_type()
+    }
+
+    fn count_by_scope(&self) -> Result<ProfileCountsByScope> {
+        self.0.count_by_scope()
+    }
+
+    fn count_by_one_password(&self) -> Result<ProfileCountsByOnePassword> {
+        self.0.count_by_one_password()
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        self.0.exists(name)
+    }
+
+    fn archive(&self, name: &str) -> Result<()> {
+        self.0.archive(name)
+    }
+
+    fn unarchive(&self, name: &str) -> Result<()> {
+        self.0.unarchive(name)
+    }
+
+    fn reorder(&self, order: Vec<String>) -> Result<()> {
+        self.0.reorder(order)
+    }
+
+    fn undo(&self) -> Result<Option<String>> {
+        self.0.undo()
+    }
+
+    fn redo(&self) -> Result<Option<String>> {
+        self.0.redo()
+    }
+
+    fn record_activation(&self, record: ActivationRecord) -> Result<()> {
+        self.0.record_activation(record)
+    }
+
+    fn activation_history(&self) -> Result<Vec<ActivationRecord>> {
+        self.0.activation_history()
+    }
+
+    fn clear_activation_history(&self) -> Result<()> {
+        self.0.clear_activation_history()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::GitSetupError,
+        profile::{manager::ProfileManagerImpl, mock::MockProfileManager},
+    };
+
+    fn profile(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            ..Default::default()
+        }
+    }
+
+    fn create_five(manager: &mut dyn ProfileManager) -> Result<()> {
+        for name in ["one", "two", "three", "four", "five"] {
+            manager.create(profile(name))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_transaction_rolls_back_earlier_operations() {
+        let mock = Arc::new(MockProfileManager::with_profiles(vec![profile("existing")]).track_calls(true));
+        mock.fail_on_create_after(2);
+        let manager = TransactionalProfileManager::new(mock.clone());
+
+        let result = manager.transaction(create_five);
+        assert!(matches!(result.unwrap_err(), GitSetupError::Git(msg) if msg.contains("three")));
+
+        let names: Vec<String> = mock.list().unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["existing"]);
+        assert!(mock.was_called_with_delete("one"));
+        assert!(mock.was_called_with_delete("two"));
+        assert!(!mock.was_called_with_delete("existing"));
+    }
+
+    #[test]
+    fn test_successful_transaction_keeps_changes() {
+        let mock = Arc::new(MockProfileManager::new().track_calls(true));
+        let manager = TransactionalProfileManager::new(mock.clone());
+
+        manager.transaction(create_five).unwrap();
+        assert_eq!(mock.list().unwrap().len(), 5);
+        assert_eq!(mock.create_call_count(), 5);
+        assert_eq!(mock.delete_call_count(), 0);
+    }
+
+    #[test]
+    fn test_rollback_restores_updated_and_deleted_profiles() {
+        let mock = Arc::new(MockProfileManager::with_profiles(vec![profile("a"), profile("b")]).track_calls(true));
+        let manager = TransactionalProfileManager::new(mock.clone());
+
+        let result = manager.transaction(|m| {
+            m.update("a", Profile { git_user_email: "changed@example.com".to_string(), ..profile("a") })?;
+            m.delete("b")?;
+            Err(GitSetupError::Git("abort".to_string()))
+        });
+        assert!(result.is_err());
+
+        assert_eq!(mock.read("a").unwrap().unwrap().git_user_email, "a@example.com");
+        assert!(mock.exists("b").unwrap());
+        // The rollback restores the update and recreates the deleted profile
+        assert_eq!(mock.update_call_count(), 2);
+        assert!(mock.was_called_with_create("b"));
+    }
+
+    #[test]
+    fn test_transaction_on_profile_manager_impl() {
+        let manager = ProfileManagerImpl::new();
+        manager.create(profile("existing")).unwrap();
+
+        let result = manager.transaction(|m| {
+            m.create(profile("new"))?;
+            m.create(profile("existing"))
+        });
+        assert!(matches!(result.unwrap_err(), GitSetupError::DuplicateProfile { .. }));
+
+        assert!(!manager.exists("new").unwrap());
+        // The rolled back operations are not left in the undo history
+        assert_eq!(manager.undo().unwrap(), Some("existing".to_string()));
+        assert_eq!(manager.undo().unwrap(), None);
+    }
+}
@@ -0,0 +1,193 @@
+//! Undo/redo history for profile operations.
+//!
+//! Every write made through a [`ProfileManager`](super::ProfileManager) that keeps
+//! history is recorded as a [`ProfileOperation`]. The history can be persisted to
+//! disk so that `git-setup --undo` works across process restarts.
+
+use crate::{
+    config::types::Profile,
+    error::Result,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of operations kept in the undo history.
+pub const MAX_UNDO_ENTRIES: usize = 20;
+
+/// A reversible profile operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProfileOperation {
+    /// A profile was created.
+    Created(Profile),
+    /// A profile was updated (possibly renamed).
+    Updated {
+        before: Box<Profile>,
+        after: Box<Profile>,
+    },
+    /// A profile was deleted.
+    Deleted(Profile),
+    /// A profile was archived.
+    Archived(Profile),
+    /// An archived profile was restored.
+    Unarchived(Profile),
+}
+
+impl ProfileOperation {
+    /// Name of the profile affected by the operation.
+    ///
+    /// For renames this is the name the profile had after the update.
+    pub fn profile_name(&self) -> &str {
+        match self {
+            ProfileOperation::Created(profile)
+            | ProfileOperation::Deleted(profile)
+            | ProfileOperation::Archived(profile)
+            | ProfileOperation::Unarchived(profile) => &profile.name,
+            ProfileOperation::Updated { after, .. } => &after.name,
+        }
+    }
+}
+
+/// Bounded undo/redo stacks of profile operations.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UndoRedoStack {
+    undo: VecDeque<ProfileOperation>,
+    redo: Vec<ProfileOperation>,
+}
+
+impl UndoRedoStack {
+    /// Create an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default location of the persisted history
+    /// (`~/.local/share/git-setup/undo-stack.json`, honouring `XDG_DATA_HOME`).
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(SystemPlatform.data_dir()?.join("undo-stack.json"))
+    }
+
+    /// Load a history from `path`, returning an empty history if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the history to `path`, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record a new operation, discarding any redo history.
+    pub fn record(&mut self, operation: ProfileOperation) {
+        self.redo.clear();
+        self.push_undo(operation);
+    }
+
+    /// Take the most recent operation to undo.
+    pub fn pop_undo(&mut self) -> Option<ProfileOperation> {
+        self.undo.pop_back()
+    }
+
+    /// Take the most recently undone operation to redo.
+    pub fn pop_redo(&mut self) -> Option<ProfileOperation> {
+        self.redo.pop()
+    }
+
+    /// Push an operation onto the undo stack without touching the redo stack.
+    pub fn push_undo(&mut self, operation: ProfileOperation) {
+        self.undo.push_back(operation);
+        while self.undo.len() > MAX_UNDO_ENTRIES {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Push an undone operation onto the redo stack.
+    pub fn push_redo(&mut self, operation: ProfileOperation) {
+        self.redo.push(operation);
+    }
+
+    /// Number of operations that can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Number of operations that can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: format!("{}@example.com", name),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_record_clears_redo() {
+        let mut stack = UndoRedoStack::new();
+        stack.record(ProfileOperation::Created(profile("work")));
+        let op = stack.pop_undo().unwrap();
+        stack.push_redo(op);
+        assert_eq!(stack.redo_len(), 1);
+
+        stack.record(ProfileOperation::Deleted(profile("personal")));
+        assert_eq!(stack.redo_len(), 0);
+        assert_eq!(stack.undo_len(), 1);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut stack = UndoRedoStack::new();
+        for i in 0..(MAX_UNDO_ENTRIES + 5) {
+            stack.record(ProfileOperation::Created(profile(&format!("p{}", i))));
+        }
+        assert_eq!(stack.undo_len(), MAX_UNDO_ENTRIES);
+        // The oldest entries are dropped; the newest is still on top
+        assert_eq!(
+            stack.pop_undo().unwrap().profile_name(),
+            format!("p{}", MAX_UNDO_ENTRIES + 4)
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("undo-stack.json");
+
+        let mut stack = UndoRedoStack::new();
+        stack.record(ProfileOperation::Updated {
+            before: Box::new(profile("old")),
+            after: Box::new(profile("new")),
+        });
+        stack.save(&path).unwrap();
+
+        let mut loaded = UndoRedoStack::load(&path).unwrap();
+        assert_eq!(loaded.undo_len(), 1);
+        assert_eq!(loaded.pop_undo().unwrap().profile_name(), "new");
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let stack = UndoRedoStack::load(&dir.path().join("missing.json")).unwrap();
+        assert_eq!(stack.undo_len(), 0);
+        assert_eq!(stack.redo_len(), 0);
+    }
+}
@@ -0,0 +1,566 @@
+//! Structural validation for profiles.
+//!
+//! These checks only look at the profile data itself; checks that need external
+//! tools (1Password, GPG, git) are performed separately. Organizations can add
+//! their own policies as validation hooks: scripts that receive the profile as
+//! JSON on stdin and reject it by exiting non-zero.
+//! [`ProfileValidator::report`] runs a chain of consistency checks used by
+//! `--validate`, grading each as passed, warning or failed.
+
+use crate::{
+    config::types::{infer_key_type, KeyType, Profile, SecretStoreKind, ValidationHookConfig},
+    error::{GitSetupError, Result},
+};
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Maximum length of a profile name.
+const MAX_NAME_LENGTH: usize = 100;
+
+/// A single structural problem with a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileValidationError {
+    /// Name of the offending `Profile` field (e.g. `git_user_email`)
+    pub field: String,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl ProfileValidationError {
+    /// Create a new validation error for a field.
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ProfileValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Outcome of one check of a [`ValidationReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// The profile may work, but probably not as intended
+    Warn,
+    /// Applying the profile will fail or misconfigure git
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "pass"),
+            CheckStatus::Warn => write!(f, "warn"),
+            CheckStatus::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// One check of a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationCheck {
+    /// What was checked, usually the name of a `Profile` field
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl ValidationCheck {
+    fn new(name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Results of the checks [`ProfileValidator::report`] ran on a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub profile: String,
+    pub checks: Vec<ValidationCheck>,
+}
+
+impl ValidationReport {
+    /// Number of checks with `status`.
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+
+    /// Whether the profile passes: no failed checks, and with `strict` no warnings either.
+    pub fn passed(&self, strict: bool) -> bool {
+        self.count(CheckStatus::Fail) == 0 && (!strict || self.count(CheckStatus::Warn) == 0)
+    }
+}
+
+/// Result of a validation hook that exited non-zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationFinding {
+    /// Command of the hook
+    pub hook: String,
+    /// The hook's stderr, or its exit status if it printed nothing
+    pub message: String,
+    /// Whether the finding rejects the profile (`fail_on_nonzero`) or is only a warning
+    pub is_failure: bool,
+}
+
+impl fmt::Display for ValidationFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.hook, self.message)
+    }
+}
+
+/// Runs validation hooks on a profile.
+#[derive(Debug, Default)]
+pub struct ValidationHookRunner;
+
+impl ValidationHookRunner {
+    /// Run the hooks applying to `profile`, returning a finding for each hook that exited non-zero.
+    pub fn run(profile: &Profile, hooks: &[ValidationHookConfig]) -> Result<Vec<ValidationFinding>> {
+        let input = serde_json::to_string(profile)?;
+        let mut findings = Vec::new();
+
+        for hook in hooks {
+            let pattern = glob::Pattern::new(&hook.applies_to_profiles).map_err(|e| {
+                GitSetupError::InvalidProfile {
+                    reason: format!("invalid applies_to_profiles '{}': {}", hook.applies_to_profiles, e),
+                }
+            })?;
+            if !pattern.matches(&profile.name) {
+                continue;
+            }
+
+            let output = Self::run_hook(&hook.command, &input)?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                findings.push(ValidationFinding {
+                    hook: hook.command.clone(),
+                    message: if stderr.is_empty() {
+                        format!("exited with {}", output.status)
+                    } else {
+                        stderr
+                    },
+                    is_failure: hook.fail_on_nonzero,
+                });
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Run `command` with `input` on stdin.
+    fn run_hook(command: &str, input: &str) -> Result<std::process::Output> {
+        let spawn_error = |e: std::io::Error| GitSetupError::ExternalCommand {
+            command: command.to_string(),
+            error: e.to_string(),
+        };
+        let mut child = Command::new("sh")
+            .args(["-c", command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(spawn_error)?;
+
+        // A hook may exit without reading its input
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+        child.wait_with_output().map_err(spawn_error)
+    }
+}
+
+/// Runs all structural validations on a profile.
+#[derive(Debug, Default)]
+pub struct ProfileValidator;
+
+impl ProfileValidator {
+    /// Validate a profile, returning every problem found (empty if valid).
+    pub fn validate(profile: &Profile) -> Vec<ProfileValidationError> {
+        let mut errors = Vec::new();
+
+        Self::validate_name(&profile.name, &mut errors);
+        Self::validate_email(&profile.git_user_email, &mut errors);
+        Self::validate_patterns("match_patterns", &profile.match_patterns, &mut errors);
+        Self::validate_patterns("host_patterns", &profile.host_patterns, &mut errors);
+
+        errors
+    }
+
+    /// Validate a profile, then run the validation hooks applying to it as the last step.
+    ///
+    /// Hooks with `fail_on_nonzero` that exit non-zero are reported as errors
+    /// of the `validation_hooks` field; other findings are printed as warnings.
+    pub fn validate_with_hooks(
+        profile: &Profile,
+        hooks: &[ValidationHookConfig],
+    ) -> Result<Vec<ProfileValidationError>> {
+        let mut errors = Self::validate(profile);
+        for finding in ValidationHookRunner::run(profile, hooks)? {
+            if finding.is_failure {
+                errors.push(ProfileValidationError::new(
+                    "validation_hooks",
+                    format!("rejected by validation hook {}", finding),
+                ));
+            } else {
+                eprintln!("Warning: validation hook {}", finding);
+            }
+        }
+        Ok(errors)
+    }
+
+    /// Check that `profile` is consistent enough to be applied.
+    ///
+    /// Checks that do not apply to the profile, e.g. the GPG key ID of an SSH
+    /// profile, are left out of the report.
+    pub fn report(profile: &Profile) -> ValidationReport {
+        let mut errors = Vec::new();
+        Self::validate_name(&profile.name, &mut errors);
+        Self::validate_email(&profile.git_user_email, &mut errors);
+        let mut checks: Vec<ValidationCheck> = ["name", "git_user_email"]
+            .into_iter()
+            .map(|field| match errors.iter().find(|error| error.field == field) {
+                Some(error) => ValidationCheck::new(field, CheckStatus::Fail, error.message.clone()),
+                None => ValidationCheck::new(field, CheckStatus::Pass, "valid"),
+            })
+            .collect();
+
+        checks.extend(Self::check_signing_key(profile));
+        checks.extend(Self::check_vault_name(profile));
+        checks.extend(profile.allowed_signers.as_deref().map(Self::check_allowed_signers));
+
+        let mut errors = Vec::new();
+        Self::validate_patterns("match_patterns", &profile.match_patterns, &mut errors);
+        checks.push(match errors.as_slice() {
+            [] => ValidationCheck::new(
+                "match_patterns",
+                CheckStatus::Pass,
+                format!("{} valid glob pattern(s)", profile.match_patterns.len()),
+            ),
+            errors => ValidationCheck::new(
+                "match_patterns",
+                CheckStatus::Fail,
+                errors.iter().map(|error| error.message.as_str()).collect::<Vec<_>>().join("; "),
+            ),
+        });
+
+        ValidationReport {
+            profile: profile.name.clone(),
+            checks,
+        }
+    }
+
+    /// SSH profiles need a key or, with a secret store, a key title; GPG keys should be key IDs.
+    /// A key shaped like another key type than `key_type` is a warning.
+    fn check_signing_key(profile: &Profile) -> Option<ValidationCheck> {
+        let check = |status, message: String| Some(ValidationCheck::new("signing_key", status, message));
+        let inferred = profile.signing_key.as_deref().and_then(infer_key_type);
+        let mismatch = |key: &str| {
+            let name = |key_type: &KeyType| format!("{:?}", key_type).to_lowercase();
+            let inferred = inferred.as_ref().map(name).unwrap_or_default();
+            check(
+                CheckStatus::Warn,
+                format!("'{}' is shaped like key type {}, not {}", key, inferred, name(&profile.key_type)),
+            )
+        };
+        match (&profile.key_type, profile.signing_key.as_deref()) {
+            (KeyType::X509 | KeyType::Gitsign, Some(key)) if inferred.is_some() => mismatch(key),
+            (KeyType::X509 | KeyType::Gitsign, _) => None,
+            (KeyType::Ssh, None) if profile.key_store().is_some() => match &profile.ssh_key_title {
+                Some(title) => check(CheckStatus::Pass, format!("loaded from the secret store item '{}'", title)),
+                None => check(CheckStatus::Fail, "ssh_key_title is needed to load the key".to_string()),
+            },
+            (KeyType::Gpg, None) => match profile.key_store() {
+                Some(SecretStoreKind::OnePassword) => check(CheckStatus::Pass, "loaded from 1Password".to_string()),
+                Some(SecretStoreKind::Bitwarden) => {
+                    check(CheckStatus::Fail, "Bitwarden cannot store GPG keys".to_string())
+                }
+                None => check(CheckStatus::Fail, "GPG profiles need a signing_key".to_string()),
+            },
+            (KeyType::Ssh, None) => check(CheckStatus::Fail, "SSH profiles need a signing_key".to_string()),
+            (KeyType::Ssh, Some(key)) if inferred == Some(KeyType::Gpg) => mismatch(key),
+            (KeyType::Ssh, Some(_)) => check(CheckStatus::Pass, "set".to_string()),
+            (KeyType::Gpg, Some(_)) if inferred == Some(KeyType::Gpg) => check(CheckStatus::Pass, "valid key ID".to_string()),
+            (KeyType::Gpg, Some(key)) if inferred == Some(KeyType::Ssh) => mismatch(key),
+            (KeyType::Gpg, Some(key)) => check(
+                CheckStatus::Warn,
+                format!("'{}' is not a key ID or fingerprint; GPG will look it up as a user ID", key),
+            ),
+        }
+    }
+
+    /// 1Password profiles should name the vault holding their key.
+    fn check_vault_name(profile: &Profile) -> Option<ValidationCheck> {
+        if profile.key_store() != Some(SecretStoreKind::OnePassword) {
+            return None;
+        }
+        Some(match &profile.vault_name {
+            Some(vault) => ValidationCheck::new("vault_name", CheckStatus::Pass, format!("vault '{}'", vault)),
+            None => ValidationCheck::new(
+                "vault_name",
+                CheckStatus::Warn,
+                "not set; every vault is searched for the key",
+            ),
+        })
+    }
+
+    /// The allowed signers file must be a usable path; relative paths depend on the working directory.
+    fn check_allowed_signers(path: &str) -> ValidationCheck {
+        let (status, message) = if path.trim().is_empty() {
+            (CheckStatus::Fail, "path is empty".to_string())
+        } else if path.contains(['\0', '\n']) {
+            (CheckStatus::Fail, "path contains a NUL or newline character".to_string())
+        } else if !(path.starts_with('/') || path.starts_with('~') || std::path::Path::new(path).is_absolute()) {
+            (CheckStatus::Warn, format!("'{}' is relative to the repository git runs in", path))
+        } else {
+            (CheckStatus::Pass, format!("'{}'", path))
+        };
+        ValidationCheck::new("allowed_signers", status, message)
+    }
+
+    fn validate_name(name: &str, errors: &mut Vec<ProfileValidationError>) {
+        let message = if name.is_empty() {
+            "Profile name cannot be empty"
+        } else if name.contains('/') || name.contains('\\') {
+            "Profile name cannot contain path separators"
+        } else if name.len() > MAX_NAME_LENGTH {
+            "Profile name cannot exceed 100 characters"
+        } else if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            "Profile name can only contain alphanumeric characters, dashes, and underscores"
+        } else {
+            return;
+        };
+        errors.push(ProfileValidationError::new("name", message));
+    }
+
+    fn validate_email(email: &str, errors: &mut Vec<ProfileValidationError>) {
+        let message = if email.is_empty() {
+            "Email address cannot be empty"
+        } else {
+            let parts: Vec<&str> = email.split('@').collect();
+            if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
+                "Invalid email address format"
+            } else {
+                return;
+            }
+        };
+        errors.push(ProfileValidationError::new("git_user_email", message));
+    }
+
+    fn validate_patterns(
+        field: &str,
+        patterns: &[String],
+        errors: &mut Vec<ProfileValidationError>,
+    ) {
+        for pattern in patterns {
+            if let Err(err) = glob::Pattern::new(pattern) {
+                errors.push(ProfileValidationError::new(
+                    field,
+                    format!("Invalid glob pattern '{}': {}", pattern, err),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_profile() -> Profile {
+        Profile {
+            name: "work".to_string(),
+            git_user_email: "work@example.com".to_string(),
+            match_patterns: vec!["*/work/*".to_string()],
+            host_patterns: vec!["*.company.com".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_valid_profile_has_no_errors() {
+        assert!(ProfileValidator::validate(&valid_profile()).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_glob_patterns() {
+        let mut profile = valid_profile();
+        profile.match_patterns.push("work/[".to_string());
+        profile.host_patterns.push("***[".to_string());
+
+        let errors = ProfileValidator::validate(&profile);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "match_patterns");
+        assert!(errors[0].message.starts_with("Invalid glob pattern 'work/['"));
+        assert_eq!(errors[1].field, "host_patterns");
+    }
+
+    fn hook(command: &str, applies_to_profiles: &str, fail_on_nonzero: bool) -> ValidationHookConfig {
+        ValidationHookConfig {
+            command: command.to_string(),
+            applies_to_profiles: applies_to_profiles.to_string(),
+            fail_on_nonzero,
+        }
+    }
+
+    #[test]
+    fn test_validation_hooks_receive_profile_json() {
+        let hooks = vec![
+            hook("grep -q '\"key_type\":\"gpg\"' || { echo 'work profiles must use GPG' >&2; exit 1; }", "work*", true),
+            hook("exit 3", "personal", true),
+            hook("exit 1", "*", false),
+        ];
+
+        let findings = ValidationHookRunner::run(&valid_profile(), &hooks).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].message, "work profiles must use GPG");
+        assert!(findings[0].is_failure);
+        assert!(findings[1].message.starts_with("exited with"));
+        assert!(!findings[1].is_failure);
+
+        let errors = ProfileValidator::validate_with_hooks(&valid_profile(), &hooks).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "validation_hooks");
+        assert!(errors[0].message.ends_with(": work profiles must use GPG"));
+
+        let gpg_profile = Profile {
+            key_type: crate::config::types::KeyType::Gpg,
+            ..valid_profile()
+        };
+        assert!(ProfileValidator::validate_with_hooks(&gpg_profile, &hooks).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_collects_all_errors() {
+        let profile = Profile {
+            name: "bad/name".to_string(),
+            git_user_email: "not-an-email".to_string(),
+            ..Default::default()
+        };
+
+        let errors = ProfileValidator::validate(&profile);
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["name", "git_user_email"]);
+        assert_eq!(
+            errors[0].to_string(),
+            "name: Profile name cannot contain path separators"
+        );
+    }
+
+    fn statuses(report: &ValidationReport) -> Vec<(&str, CheckStatus)> {
+        report.checks.iter().map(|check| (check.name.as_str(), check.status)).collect()
+    }
+
+    #[test]
+    fn test_report_ssh_profile() {
+        let profile = Profile {
+            key_type: KeyType::Ssh,
+            signing_key: Some("~/.ssh/id_work.pub".to_string()),
+            allowed_signers: Some("~/.ssh/allowed_signers".to_string()),
+            ..valid_profile()
+        };
+        let report = ProfileValidator::report(&profile);
+        assert_eq!(
+            statuses(&report),
+            vec![
+                ("name", CheckStatus::Pass),
+                ("git_user_email", CheckStatus::Pass),
+                ("signing_key", CheckStatus::Pass),
+                ("allowed_signers", CheckStatus::Pass),
+                ("match_patterns", CheckStatus::Pass),
+            ]
+        );
+        assert!(report.passed(true));
+
+        let profile = Profile {
+            signing_key: None,
+            allowed_signers: Some("signers".to_string()),
+            match_patterns: vec!["work/[".to_string()],
+            ..profile
+        };
+        let report = ProfileValidator::report(&profile);
+        assert_eq!(
+            statuses(&report)[2..],
+            [
+                ("signing_key", CheckStatus::Fail),
+                ("allowed_signers", CheckStatus::Warn),
+                ("match_patterns", CheckStatus::Fail),
+            ]
+        );
+        assert_eq!(report.count(CheckStatus::Fail), 2);
+        assert!(!report.passed(false));
+    }
+
+    #[test]
+    fn test_report_key_type_mismatch() {
+        let profile = |key_type: KeyType, key: &str| Profile {
+            key_type,
+            signing_key: Some(key.to_string()),
+            ..valid_profile()
+        };
+        let signing_key_check = |profile: &Profile| {
+            ProfileValidator::report(profile)
+                .checks
+                .into_iter()
+                .find(|check| check.name == "signing_key")
+        };
+
+        let check = signing_key_check(&profile(KeyType::Ssh, "0123456789ABCDEF0123456789ABCDEF01234567")).unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.message.ends_with("is shaped like key type gpg, not ssh"));
+
+        let check = signing_key_check(&profile(KeyType::Gpg, "sk-ssh-ed25519@openssh.com AAAAGnNr")).unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.message.ends_with("is shaped like key type ssh, not gpg"));
+
+        let check = signing_key_check(&profile(KeyType::Gitsign, "ssh-ed25519 AAAAC3")).unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(signing_key_check(&profile(KeyType::X509, "~/certs/work.pem")).is_none());
+
+        // Key file paths have no recognizable shape
+        let check = signing_key_check(&profile(KeyType::Ssh, "~/.ssh/id_work.pub")).unwrap();
+        assert_eq!(check.status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_report_gpg_and_1password_profiles() {
+        let gpg = |key: &str| Profile {
+            key_type: KeyType::Gpg,
+            signing_key: Some(key.to_string()),
+            ..valid_profile()
+        };
+        for key in ["ABCD1234", "0x1234567890ABCDEF", "1234 5678 90AB CDEF 1234  5678 90AB CDEF 1234 5678"] {
+            assert!(ProfileValidator::report(&gpg(key)).passed(true), "{}", key);
+        }
+        let report = ProfileValidator::report(&gpg("jane@work.com"));
+        assert_eq!(statuses(&report)[2], ("signing_key", CheckStatus::Warn));
+        assert!(report.passed(false));
+        assert!(!report.passed(true));
+
+        let one_password = Profile {
+            one_password: true,
+            ssh_key_title: Some("Work Key".to_string()),
+            ..valid_profile()
+        };
+        let report = ProfileValidator::report(&one_password);
+        assert_eq!(statuses(&report)[2..4], [("signing_key", CheckStatus::Pass), ("vault_name", CheckStatus::Warn)]);
+
+        let bitwarden = Profile {
+            secret_store: Some(SecretStoreKind::Bitwarden),
+            rotated_key_item: None,
+            tags: vec![],
+            ssh_key_title: None,
+            ..one_password
+        };
+        let report = ProfileValidator::report(&bitwarden);
+        assert_eq!(statuses(&report)[2..], [("signing_key", CheckStatus::Fail), ("match_patterns", CheckStatus::Pass)]);
+    }
+}
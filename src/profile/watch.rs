@@ -0,0 +1,183 @@
+//! Change notifications for profiles.
+//!
+//! Applications embedding git-setup-rs can subscribe to profile changes with
+//! [`ProfileManager::watch_changes`](super::ProfileManager::watch_changes)
+//! instead of re-listing the profiles themselves. Managers without a native
+//! change feed use [`WatchHandle::poll`], which lists the profiles every
+//! [`WATCH_POLL_INTERVAL`] and reports what differs from the previous listing.
+
+use crate::{config::types::Profile, error::Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How often [`WatchHandle::poll`] lists the profiles.
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Kind of change made to a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeEventType {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A change to a single profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileChangeEvent {
+    pub event_type: ChangeEventType,
+    pub profile_name: String,
+    /// When the change was noticed.
+    pub timestamp: Instant,
+}
+
+/// Callback invoked for every profile change.
+pub type ChangeCallback = Box<dyn Fn(ProfileChangeEvent) + Send + 'static>;
+
+/// A running profile watch, cancelled when dropped.
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WatchHandle {
+    /// A handle for a manager that cannot report changes; no callback is ever invoked.
+    pub fn noop() -> Self {
+        Self {
+            stop: Arc::new(AtomicBool::new(true)),
+            thread: None,
+        }
+    }
+
+    /// Watch by listing the profiles with `list` every [`WATCH_POLL_INTERVAL`].
+    ///
+    /// The first listing is the baseline; only later changes are reported. A
+    /// failed listing is skipped and compared again on the next poll.
+    pub fn poll<L>(list: L, callback: ChangeCallback) -> Result<Self>
+    where
+        L: Fn() -> Result<Vec<Profile>> + Send + 'static,
+    {
+        let mut previous = list()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+
+        let thread = std::thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                std::thread::sleep(WATCH_POLL_INTERVAL);
+                if stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(current) = list() else { continue };
+                let timestamp = Instant::now();
+                for (event_type, profile_name) in changes_between(&previous, &current) {
+                    callback(ProfileChangeEvent { event_type, profile_name, timestamp });
+                }
+                previous = current;
+            }
+        });
+
+        Ok(Self {
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Whether the watch can still report changes.
+    pub fn is_active(&self) -> bool {
+        !self.stop.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            // A panicking callback has already ended the watch
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Changes turning the profiles `old` into `new`, ordered by profile name.
+pub fn changes_between(old: &[Profile], new: &[Profile]) -> Vec<(ChangeEventType, String)> {
+    let old: HashMap<&str, &Profile> = old.iter().map(|profile| (profile.name.as_str(), profile)).collect();
+    let new: HashMap<&str, &Profile> = new.iter().map(|profile| (profile.name.as_str(), profile)).collect();
+
+    let mut changes: Vec<(ChangeEventType, String)> = new
+        .iter()
+        .filter_map(|(name, profile)| match old.get(name) {
+            None => Some((ChangeEventType::Created, name.to_string())),
+            Some(previous) if previous != profile => Some((ChangeEventType::Updated, name.to_string())),
+            Some(_) => None,
+        })
+        .chain(
+            old.keys()
+                .filter(|name| !new.contains_key(*name))
+                .map(|name| (ChangeEventType::Deleted, name.to_string())),
+        )
+        .collect();
+    changes.sort_by(|a, b| a.1.cmp(&b.1));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn profile(name: &str, email: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            git_user_email: email.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_changes_between() {
+        let old = vec![profile("work", "jane@work.com"), profile("oss", "jane@oss.dev")];
+        let new = vec![profile("work", "jane@corp.com"), profile("personal", "jane@home.net")];
+
+        assert_eq!(
+            changes_between(&old, &new),
+            vec![
+                (ChangeEventType::Deleted, "oss".to_string()),
+                (ChangeEventType::Created, "personal".to_string()),
+                (ChangeEventType::Updated, "work".to_string()),
+            ]
+        );
+        assert!(changes_between(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_poll_reports_changes_until_dropped() {
+        let profiles = Arc::new(Mutex::new(vec![profile("work", "jane@work.com")]));
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let listed = profiles.clone();
+        let received = events.clone();
+        let handle = WatchHandle::poll(
+            move || Ok(listed.lock().unwrap().clone()),
+            Box::new(move |event| received.lock().unwrap().push((event.event_type, event.profile_name))),
+        )
+        .unwrap();
+        assert!(handle.is_active());
+
+        profiles.lock().unwrap().push(profile("oss", "jane@oss.dev"));
+        std::thread::sleep(WATCH_POLL_INTERVAL * 3);
+        drop(handle);
+        assert_eq!(*events.lock().unwrap(), vec![(ChangeEventType::Created, "oss".to_string())]);
+
+        // Changes after the handle is dropped are not reported
+        profiles.lock().unwrap().clear();
+        std::thread::sleep(WATCH_POLL_INTERVAL * 2);
+        assert_eq!(events.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_noop_handle() {
+        assert!(!WatchHandle::noop().is_active());
+    }
+}
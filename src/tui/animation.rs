@@ -0,0 +1,201 @@
+//! Screen transition animations for the TUI.
+//!
+//! Terminals cannot blend colours, so transitions are approximated: the departing
+//! screen is dimmed and the arriving screen takes over a growing part of the area.
+
+use super::screens::ScreenType;
+use crate::error::Result;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    widgets::Clear,
+};
+use std::time::Duration;
+
+/// Default length of a screen transition.
+pub const DEFAULT_ANIMATION_DURATION: Duration = Duration::from_millis(200);
+
+/// Interval between redraws while a transition is running
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How a screen transition is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationType {
+    /// The arriving screen grows from the centre over the dimmed departing screen
+    #[default]
+    Fade,
+    /// The arriving screen pushes in from the right
+    SlideLeft,
+    /// The arriving screen pushes in from the left
+    SlideRight,
+}
+
+/// Progress of a transition between two screens
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationState {
+    /// Completion from 0.0 to 1.0
+    pub progress: f64,
+    pub from_screen: ScreenType,
+    pub to_screen: ScreenType,
+    pub animation_type: AnimationType,
+    duration: Duration,
+}
+
+impl AnimationState {
+    /// Start a transition that completes after `duration`
+    pub fn new(
+        from_screen: ScreenType,
+        to_screen: ScreenType,
+        animation_type: AnimationType,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            progress: 0.0,
+            from_screen,
+            to_screen,
+            animation_type,
+            duration,
+        }
+    }
+
+    /// Advance the animation by `dt`
+    pub fn tick(&mut self, dt: Duration) {
+        if self.duration.is_zero() {
+            self.progress = 1.0;
+        } else {
+            self.progress = (self.progress + dt.as_secs_f64() / self.duration.as_secs_f64()).min(1.0);
+        }
+    }
+
+    /// Check if the transition has completed
+    pub fn is_finished(&self) -> bool {
+        self.progress >= 1.0
+    }
+
+    /// Split `area` into the areas of the departing and arriving screens
+    pub fn layout(&self, area: Rect) -> (Rect, Rect) {
+        let progress = self.progress.clamp(0.0, 1.0);
+        match self.animation_type {
+            AnimationType::Fade => {
+                let width = (f64::from(area.width) * progress).round() as u16;
+                let height = (f64::from(area.height) * progress).round() as u16;
+                let arriving = Rect::new(
+                    area.x + (area.width - width) / 2,
+                    area.y + (area.height - height) / 2,
+                    width,
+                    height,
+                );
+                (area, arriving)
+            }
+            AnimationType::SlideLeft => {
+                let offset = (f64::from(area.width) * progress).round() as u16;
+                let departing = Rect::new(area.x, area.y, area.width - offset, area.height);
+                let arriving = Rect::new(area.x + area.width - offset, area.y, offset, area.height);
+                (departing, arriving)
+            }
+            AnimationType::SlideRight => {
+                let offset = (f64::from(area.width) * progress).round() as u16;
+                let arriving = Rect::new(area.x, area.y, offset, area.height);
+                let departing = Rect::new(area.x + offset, area.y, area.width - offset, area.height);
+                (departing, arriving)
+            }
+        }
+    }
+
+    /// Draw the transition in `area`
+    ///
+    /// `draw(frame, area, arriving)` draws the departing screen, which is then
+    /// dimmed, or the arriving screen over a cleared area.
+    pub fn render(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        mut draw: impl FnMut(&mut Frame, Rect, bool) -> Result<()>,
+    ) -> Result<()> {
+        let (departing_area, arriving_area) = self.layout(area);
+
+        if departing_area.area() > 0 {
+            draw(frame, departing_area, false)?;
+            frame.buffer_mut().set_style(departing_area, Style::new().dim());
+        }
+        if arriving_area.area() > 0 {
+            frame.render_widget(Clear, arriving_area);
+            draw(frame, arriving_area, true)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation(animation_type: AnimationType) -> AnimationState {
+        AnimationState::new(
+            ScreenType::Main,
+            ScreenType::Help,
+            animation_type,
+            Duration::from_millis(200),
+        )
+    }
+
+    #[test]
+    fn test_tick_advances_progress() {
+        let mut state = animation(AnimationType::Fade);
+        state.tick(Duration::from_millis(50));
+        assert!((state.progress - 0.25).abs() < f64::EPSILON);
+        assert!(!state.is_finished());
+
+        state.tick(Duration::from_millis(500));
+        assert_eq!(state.progress, 1.0);
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_zero_duration_finishes_immediately() {
+        let mut state = AnimationState::new(
+            ScreenType::Main,
+            ScreenType::Help,
+            AnimationType::Fade,
+            Duration::ZERO,
+        );
+        state.tick(Duration::ZERO);
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_slide_layouts() {
+        let area = Rect::new(0, 0, 100, 20);
+
+        let mut state = animation(AnimationType::SlideLeft);
+        state.progress = 0.25;
+        let (departing, arriving) = state.layout(area);
+        assert_eq!(departing, Rect::new(0, 0, 75, 20));
+        assert_eq!(arriving, Rect::new(75, 0, 25, 20));
+
+        state.animation_type = AnimationType::SlideRight;
+        let (departing, arriving) = state.layout(area);
+        assert_eq!(arriving, Rect::new(0, 0, 25, 20));
+        assert_eq!(departing, Rect::new(25, 0, 75, 20));
+    }
+
+    #[test]
+    fn test_fade_layout_grows_from_centre() {
+        let area = Rect::new(0, 0, 100, 20);
+        let mut state = animation(AnimationType::Fade);
+
+        let (departing, arriving) = state.layout(area);
+        assert_eq!(departing, area);
+        assert_eq!(arriving.area(), 0);
+
+        state.progress = 0.5;
+        let (_, arriving) = state.layout(area);
+        assert_eq!(arriving, Rect::new(25, 5, 50, 10));
+
+        state.progress = 1.0;
+        let (_, arriving) = state.layout(area);
+        assert_eq!(arriving, area);
+    }
+}
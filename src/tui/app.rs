@@ -1,17 +1,31 @@
 use super::{
-    ComponentAction, EventHandler, Event, TerminalManager, Theme, UIHelpers, UI,
+    ClipboardHelper, Component, ComponentAction, EventHandler, Event, Exporter, TerminalManager, Theme,
+    UIHelpers, UI,
 };
+use super::animation::{AnimationState, AnimationType, DEFAULT_ANIMATION_DURATION, FRAME_INTERVAL};
 use super::events::{KeyBindings, KeyAction};
+use super::screens::{ScreenType, profile_list::ProfileListScreen};
 use crate::{
     error::{Result, GitSetupError},
     cli::Args,
+    config::{
+        loader::{ConfigLoader, ConfigLoaderTrait},
+        types::ToolConfig,
+    },
+    platform::{PlatformPaths, SystemPlatform},
 };
 use ratatui::{
     Frame,
     layout::{Rect, Layout, Direction, Constraint},
     widgets::{Block, Borders, Paragraph, List, ListItem},
 };
-use std::time::Duration;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Interval between tick events
+const TICK_RATE: Duration = Duration::from_millis(250);
 
 /// Available screens in the application
 #[derive(Debug, Clone, PartialEq)]
@@ -24,6 +38,22 @@ pub enum Screen {
     Help,
 }
 
+impl From<&Screen> for ScreenType {
+    fn from(screen: &Screen) -> Self {
+        match screen {
+            Screen::Main => ScreenType::Main,
+            Screen::ProfileList => ScreenType::ProfileList,
+            Screen::ProfileEdit(name) => ScreenType::ProfileEdit(name.clone()),
+            Screen::ProfileCreate => ScreenType::ProfileCreate,
+            Screen::Settings => ScreenType::Settings,
+            Screen::Help => ScreenType::Help,
+        }
+    }
+}
+
+/// A running transition together with the screen being left
+type Transition = (Screen, AnimationState);
+
 /// Application state
 pub struct AppState {
     pub current_screen: Screen,
@@ -55,14 +85,28 @@ pub struct App {
     args: Args,
     should_exit: bool,
     return_value: Option<String>,
+    animation_duration: Duration,
+    transition: Option<Transition>,
+    /// Handles data exported by screens
+    exporter: Exporter,
+    /// Profile list shown on [`Screen::ProfileList`], a placeholder without one
+    profile_list: Option<ProfileListScreen>,
 }
 
 impl App {
     pub fn new(args: Args) -> Result<Self> {
         let terminal_manager = TerminalManager::new()?;
-        let event_handler = EventHandler::new(Duration::from_millis(250))?;
+        let event_handler = EventHandler::new(TICK_RATE)?;
         let key_bindings = KeyBindings::default();
-        let theme = Theme::default();
+        let theme = match &args.theme {
+            Some(path) => Theme::from_toml(Path::new(&SystemPlatform.expand_path(path)))?,
+            None => Theme::default(),
+        };
+        let loader = match &args.file {
+            Some(path) => ConfigLoader::new(PathBuf::from(path)),
+            None => ConfigLoader::from_platform_default()?,
+        };
+        let tool_config = loader.load()?.tool.effective()?;
 
         Ok(Self {
             state: AppState::default(),
@@ -73,20 +117,58 @@ impl App {
             args,
             should_exit: false,
             return_value: None,
-        })
+            animation_duration: DEFAULT_ANIMATION_DURATION,
+            transition: None,
+            exporter: Exporter::new(ClipboardHelper::new()),
+            profile_list: None,
+        }
+        .with_tool_config(&tool_config))
+    }
+
+    /// Apply tool settings such as the screen transition length
+    pub fn with_tool_config(mut self, config: &ToolConfig) -> Self {
+        if let Some(duration) = config.tui_animation_duration() {
+            self.animation_duration = duration;
+        }
+        self
+    }
+
+    /// Show `screen` as the profile list, routing its actions through [`App::handle_component_action`]
+    pub fn with_profile_list(mut self, screen: ProfileListScreen) -> Self {
+        self.profile_list = Some(screen);
+        self
+    }
+
+    /// Copy exported data to `clipboard` instead of the system clipboard
+    pub fn with_clipboard(mut self, clipboard: ClipboardHelper) -> Self {
+        self.exporter = Exporter::new(clipboard);
+        self
     }
 
     pub fn run(&mut self) -> Result<Option<String>> {
+        let mut last_frame = Instant::now();
+
         // Main event loop
         loop {
+            // Advance transitions by the time since the last frame rather than on ticks,
+            // which come less often than a transition lasts
+            let now = Instant::now();
+            self.advance_transition(now - last_frame);
+            last_frame = now;
+
             // Draw UI - capture the state we need for rendering
             let current_screen = self.state.current_screen.clone();
             let status_message = self.state.status_message.clone();
             let theme = self.theme.clone();
             let key_bindings = self.key_bindings.clone();
+            let transition = self.transition.clone();
+            let profile_list = self.profile_list.as_ref();
+            let exporter = &self.exporter;
 
             self.terminal_manager.terminal().draw(|f| {
-                if let Err(e) = Self::render_frame_static(f, &current_screen, &status_message, &theme, &key_bindings) {
+                let result = Self::render_frame_static(f, &current_screen, &transition, &status_message, &theme, &key_bindings, profile_list)
+                    .and_then(|()| exporter.render_prompt(f, f.area(), &theme));
+                if let Err(e) = result {
                     // Draw error screen
                     UI::draw_error(f, f.area(), &e.to_string(), &theme);
                 }
@@ -97,6 +179,9 @@ impl App {
                 if let Err(e) = self.handle_event(event) {
                     self.state.status_message = Some(format!("Error: {}", e));
                 }
+            } else if self.transition.is_some() {
+                // Redraw at the frame rate until the transition finishes
+                std::thread::sleep(FRAME_INTERVAL);
             }
 
             // Check exit condition
@@ -114,17 +199,26 @@ impl App {
     fn render_frame_static(
         f: &mut Frame,
         current_screen: &Screen,
+        transition: &Option<Transition>,
         status_message: &Option<String>,
         theme: &Theme,
-        key_bindings: &KeyBindings
+        key_bindings: &KeyBindings,
+        profile_list: Option<&ProfileListScreen>,
     ) -> Result<()> {
         let (header, content, footer) = UI::standard_layout(f.area());
 
         // Draw header
         Self::draw_header(f, header, current_screen, theme)?;
 
-        // Draw current screen
-        Self::draw_screen(f, content, current_screen, theme, key_bindings)?;
+        // Draw current screen, or both screens while a transition is running
+        if let Some((departing, animation)) = transition {
+            animation.render(f, content, |f, area, arriving| {
+                let screen = if arriving { current_screen } else { departing };
+                Self::draw_screen(f, area, screen, theme, key_bindings, profile_list)
+            })?;
+        } else {
+            Self::draw_screen(f, content, current_screen, theme, key_bindings, profile_list)?;
+        }
 
         // Draw status bar
         Self::draw_status_bar(f, footer, status_message, theme)?;
@@ -137,10 +231,20 @@ impl App {
         Ok(())
     }
 
-    fn draw_screen(f: &mut Frame, area: Rect, current_screen: &Screen, theme: &Theme, key_bindings: &KeyBindings) -> Result<()> {
+    fn draw_screen(
+        f: &mut Frame,
+        area: Rect,
+        current_screen: &Screen,
+        theme: &Theme,
+        key_bindings: &KeyBindings,
+        profile_list: Option<&ProfileListScreen>,
+    ) -> Result<()> {
         match current_screen {
             Screen::Main => Self::draw_main_menu(f, area, theme),
-            Screen::ProfileList => Self::draw_profile_list(f, area, theme),
+            Screen::ProfileList => match profile_list {
+                Some(screen) => screen.render(f, area, theme),
+                None => Self::draw_profile_list(f, area, theme),
+            },
             Screen::ProfileEdit(name) => Self::draw_profile_edit(f, area, name, theme),
             Screen::ProfileCreate => Self::draw_profile_create(f, area, theme),
             Screen::Settings => Self::draw_settings(f, area, theme),
@@ -192,6 +296,24 @@ impl App {
     }
 
     fn handle_event(&mut self, event: Event) -> Result<()> {
+        // The export path prompt takes all input while it is open
+        if self.exporter.is_prompting() && matches!(event, Event::Key(_)) {
+            let action = self.exporter.handle_prompt_event(event)?;
+            return self.handle_component_action(action);
+        }
+
+        if self.state.current_screen == Screen::ProfileList
+            && let Some(screen) = &mut self.profile_list
+            && matches!(event, Event::Key(_) | Event::Tick)
+        {
+            let is_key = matches!(event, Event::Key(_));
+            let action = screen.handle_event(event.clone())?;
+            self.handle_component_action(action)?;
+            if is_key {
+                return Ok(());
+            }
+        }
+
         match event {
             Event::Key(key_event) => {
                 if let Some(action) = self.key_bindings.get_action(&key_event) {
@@ -246,22 +368,73 @@ impl App {
         Ok(())
     }
 
+    /// Handle an action raised by a screen or component
+    pub fn handle_component_action(&mut self, action: ComponentAction) -> Result<()> {
+        match action {
+            ComponentAction::Exit => self.should_exit = true,
+            ComponentAction::NavigateBack => self.navigate_back()?,
+            ComponentAction::Return(value) => {
+                self.return_value = Some(value);
+                self.should_exit = true;
+            }
+            ComponentAction::ShowError(message) => {
+                self.state.status_message = Some(format!("Error: {}", message));
+            }
+            ComponentAction::ShowPopup(message) => self.state.status_message = Some(message),
+            ComponentAction::ExportData(data) => {
+                let action = self.exporter.export(data);
+                self.handle_component_action(action)?;
+            }
+            ComponentAction::None | ComponentAction::NavigateTo(_) | ComponentAction::Refresh => {}
+        }
+        Ok(())
+    }
+
     fn navigate_to(&mut self, screen: Screen) -> Result<()> {
-        self.state.previous_screen = Some(self.state.current_screen.clone());
-        self.state.current_screen = screen;
+        let departing = std::mem::replace(&mut self.state.current_screen, screen);
+        self.state.previous_screen = Some(departing.clone());
+        self.start_transition(departing, AnimationType::SlideLeft);
         Ok(())
     }
 
     fn navigate_back(&mut self) -> Result<()> {
+        let departing = self.state.current_screen.clone();
         if let Some(prev) = self.state.previous_screen.take() {
             self.state.current_screen = prev;
         } else {
             // If no previous screen, go to main
             self.state.current_screen = Screen::Main;
         }
+        self.start_transition(departing, AnimationType::SlideRight);
         Ok(())
     }
 
+    /// Start animating from `departing` to the current screen
+    fn start_transition(&mut self, departing: Screen, animation_type: AnimationType) {
+        if self.animation_duration.is_zero() || departing == self.state.current_screen {
+            self.transition = None;
+            return;
+        }
+
+        let animation = AnimationState::new(
+            ScreenType::from(&departing),
+            ScreenType::from(&self.state.current_screen),
+            animation_type,
+            self.animation_duration,
+        );
+        self.transition = Some((departing, animation));
+    }
+
+    /// Advance the running transition, dropping it once finished
+    fn advance_transition(&mut self, dt: Duration) {
+        if let Some((_, animation)) = &mut self.transition {
+            animation.tick(dt);
+            if animation.is_finished() {
+                self.transition = None;
+            }
+        }
+    }
+
     fn get_screen_title(current_screen: &Screen) -> &str {
         match current_screen {
             Screen::Main => "Main Menu",
@@ -403,4 +576,13 @@ mod tests {
             Screen::ProfileEdit("test2".to_string())
         );
     }
+
+    #[test]
+    fn test_screen_type_from_screen() {
+        assert_eq!(ScreenType::from(&Screen::Main), ScreenType::Main);
+        assert_eq!(
+            ScreenType::from(&Screen::ProfileEdit("work".to_string())),
+            ScreenType::ProfileEdit("work".to_string())
+        );
+    }
 }
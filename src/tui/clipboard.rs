@@ -0,0 +1,122 @@
+//! Clipboard access for the TUI application.
+//!
+//! Exported data is copied to the system clipboard through [`ClipboardHelper`].
+//! The clipboard itself sits behind the [`Clipboard`] trait, so screens can be
+//! tested with [`MockClipboard`] without touching the user's clipboard. On
+//! headless terminals there is no clipboard and copying fails; callers offer
+//! another way out, such as writing a file.
+
+use crate::error::Result;
+use is_terminal::IsTerminal;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+/// A clipboard text can be copied to
+pub trait Clipboard: Send + Sync {
+    /// Replace the clipboard content with `content`
+    fn set_text(&self, content: &str) -> Result<()>;
+}
+
+/// The system clipboard, through `arboard`
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn set_text(&self, content: &str) -> Result<()> {
+        if ClipboardHelper::is_headless() {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "no clipboard on a headless terminal").into());
+        }
+        arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_text(content))
+            .map_err(|e| io::Error::other(format!("cannot copy to the clipboard: {}", e)).into())
+    }
+}
+
+/// Clipboard recording what is copied to it, for testing
+#[derive(Default)]
+pub struct MockClipboard {
+    contents: Mutex<Vec<String>>,
+    unavailable: bool,
+}
+
+impl MockClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail every copy, like a headless terminal
+    pub fn unavailable() -> Self {
+        Self {
+            unavailable: true,
+            ..Self::default()
+        }
+    }
+
+    /// Everything copied so far, oldest first
+    pub fn contents(&self) -> Vec<String> {
+        self.contents.lock().unwrap().clone()
+    }
+}
+
+impl Clipboard for MockClipboard {
+    fn set_text(&self, content: &str) -> Result<()> {
+        if self.unavailable {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "no clipboard on a headless terminal").into());
+        }
+        self.contents.lock().unwrap().push(content.to_string());
+        Ok(())
+    }
+}
+
+/// Copies text to a clipboard, the system clipboard unless another is given
+#[derive(Clone)]
+pub struct ClipboardHelper {
+    clipboard: Arc<dyn Clipboard>,
+}
+
+impl ClipboardHelper {
+    /// Copy to the system clipboard
+    pub fn new() -> Self {
+        Self::with_clipboard(Arc::new(SystemClipboard))
+    }
+
+    /// Copy to `clipboard` instead of the system clipboard
+    pub fn with_clipboard(clipboard: Arc<dyn Clipboard>) -> Self {
+        Self { clipboard }
+    }
+
+    /// Copy `content` to the clipboard
+    ///
+    /// # Errors
+    /// Fails on headless terminals or when no clipboard can be opened
+    pub fn set(&self, content: &str) -> Result<()> {
+        self.clipboard.set_text(content)
+    }
+
+    /// Check if there is no interactive terminal, and so likely no clipboard
+    pub fn is_headless() -> bool {
+        !std::io::stdout().is_terminal()
+    }
+}
+
+impl Default for ClipboardHelper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_copies_to_the_given_clipboard() {
+        let clipboard = Arc::new(MockClipboard::new());
+        let helper = ClipboardHelper::with_clipboard(clipboard.clone());
+
+        helper.set("name,email\n").unwrap();
+        assert_eq!(clipboard.contents(), vec!["name,email\n".to_string()]);
+
+        let helper = ClipboardHelper::with_clipboard(Arc::new(MockClipboard::unavailable()));
+        assert!(helper.set("name,email\n").is_err());
+    }
+}
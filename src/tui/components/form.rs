@@ -16,6 +16,8 @@ use ratatui::{
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 use regex::Regex;
 
 /// Types of form fields
@@ -56,6 +58,55 @@ pub enum ValidationRuleType {
     Custom(fn(&str) -> bool),
 }
 
+/// Condition deciding whether a field is shown, evaluated against the whole form
+#[derive(Clone)]
+pub struct VisibilityCondition(Arc<dyn Fn(&FormState) -> bool + Send + Sync>);
+
+impl VisibilityCondition {
+    /// Check whether the field is shown for the current form state
+    pub fn is_met(&self, state: &FormState) -> bool {
+        (self.0)(state)
+    }
+}
+
+impl fmt::Debug for VisibilityCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VisibilityCondition")
+    }
+}
+
+/// Source of auto-complete suggestions for a form field
+pub trait SuggestionsProvider: Send + Sync {
+    /// Suggestions for the partially typed value
+    fn suggest(&self, partial: &str) -> Vec<String>;
+}
+
+/// Suggestions from a fixed list, matched case-insensitively anywhere in the item
+pub struct StaticSuggestionsProvider(pub Vec<String>);
+
+impl SuggestionsProvider for StaticSuggestionsProvider {
+    fn suggest(&self, partial: &str) -> Vec<String> {
+        let partial = partial.to_lowercase();
+        self.0
+            .iter()
+            .filter(|item| item.to_lowercase().contains(&partial))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Function computing suggestions for a partially typed value
+pub type SuggestFn = Box<dyn Fn(&str) -> Vec<String> + Send + Sync>;
+
+/// Suggestions computed by a function of the typed value
+pub struct DynamicSuggestionsProvider(pub SuggestFn);
+
+impl SuggestionsProvider for DynamicSuggestionsProvider {
+    fn suggest(&self, partial: &str) -> Vec<String> {
+        (self.0)(partial)
+    }
+}
+
 /// Form field configuration
 #[derive(Debug, Clone)]
 pub struct FormField {
@@ -70,6 +121,7 @@ pub struct FormField {
     pub is_required: bool,
     pub is_readonly: bool,
     pub is_hidden: bool,
+    pub visibility_condition: Option<VisibilityCondition>,
     pub tab_index: usize,
 }
 
@@ -88,10 +140,29 @@ impl FormField {
             is_required: false,
             is_readonly: false,
             is_hidden: false,
+            visibility_condition: None,
             tab_index: 0,
         }
     }
 
+    /// Only show the field while `condition` holds for the form state
+    pub fn with_conditional_visibility(
+        mut self,
+        condition: Box<dyn Fn(&FormState) -> bool + Send + Sync>,
+    ) -> Self {
+        self.visibility_condition = Some(VisibilityCondition(Arc::from(condition)));
+        self
+    }
+
+    /// Only show the field while the field `field_name` has the value `value`
+    pub fn hide_unless_field_equals(self, field_name: &str, value: &str) -> Self {
+        let field_name = field_name.to_string();
+        let value = value.to_string();
+        self.with_conditional_visibility(Box::new(move |state| {
+            state.fields.get(&field_name).is_some_and(|field| field.value == value)
+        }))
+    }
+
     /// Set the field value
     pub fn set_value(&mut self, value: &str) -> &mut Self {
         self.value = value.to_string();
@@ -259,23 +330,50 @@ impl FormState {
         }
     }
 
-    /// Navigate to next field
+    /// Check if a field is shown: it exists, is not hidden and its visibility condition holds
+    pub fn is_field_visible(&self, name: &str) -> bool {
+        self.fields.get(name).is_some_and(|field| {
+            !field.is_hidden
+                && field
+                    .visibility_condition
+                    .as_ref()
+                    .is_none_or(|condition| condition.is_met(self))
+        })
+    }
+
+    /// Get the names of the fields currently shown, in tab order
+    pub fn get_visible_fields(&self) -> Vec<&str> {
+        self.field_order
+            .iter()
+            .map(|name| name.as_str())
+            .filter(|name| self.is_field_visible(name))
+            .collect()
+    }
+
+    /// Navigate to the next visible field
     pub fn next_field(&mut self) -> bool {
-        if self.current_field < self.field_order.len().saturating_sub(1) {
-            self.current_field += 1;
-            true
-        } else {
-            false
+        let next = (self.current_field + 1..self.field_order.len())
+            .find(|&i| self.is_field_visible(&self.field_order[i]));
+        match next {
+            Some(i) => {
+                self.current_field = i;
+                true
+            }
+            None => false,
         }
     }
 
-    /// Navigate to previous field
+    /// Navigate to the previous visible field
     pub fn prev_field(&mut self) -> bool {
-        if self.current_field > 0 {
-            self.current_field -= 1;
-            true
-        } else {
-            false
+        let prev = (0..self.current_field)
+            .rev()
+            .find(|&i| self.is_field_visible(&self.field_order[i]));
+        match prev {
+            Some(i) => {
+                self.current_field = i;
+                true
+            }
+            None => false,
         }
     }
 
@@ -297,22 +395,42 @@ impl FormState {
         }
     }
 
-    /// Validate all fields
+    /// Validate all visible fields
     pub fn validate_all(&mut self) -> bool {
         self.show_validation = true;
         let mut all_valid = true;
         
-        for (name, field) in &self.fields {
-            let result = field.validate();
-            if matches!(result, ValidationResult::Invalid(_)) {
-                all_valid = false;
+        let results: Vec<(String, Option<ValidationResult>)> = self
+            .fields
+            .iter()
+            .map(|(name, field)| (name.clone(), self.is_field_visible(name).then(|| field.validate())))
+            .collect();
+
+        for (name, result) in results {
+            match result {
+                Some(result) => {
+                    if matches!(result, ValidationResult::Invalid(_)) {
+                        all_valid = false;
+                    }
+                    self.validation_results.insert(name, result);
+                }
+                // Hidden fields never block submission
+                None => {
+                    self.validation_results.remove(&name);
+                }
             }
-            self.validation_results.insert(name.clone(), result);
         }
         
         all_valid
     }
 
+    /// Mark a field as invalid with an externally computed error message
+    pub fn set_validation_error(&mut self, name: &str, message: &str) {
+        self.show_validation = true;
+        self.validation_results
+            .insert(name.to_string(), ValidationResult::Invalid(message.to_string()));
+    }
+
     /// Get validation result for a field
     pub fn get_validation_result(&self, name: &str) -> Option<&ValidationResult> {
         self.validation_results.get(name)
@@ -347,12 +465,68 @@ impl FormState {
     }
 }
 
+/// Maximum number of suggestions shown at once in an auto-complete dropdown
+pub const MAX_DROPDOWN_ITEMS: usize = 8;
+
+/// Inline list of suggestions shown below the focused field
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoCompleteDropdown {
+    items: Vec<String>,
+    selected: usize,
+    offset: usize,
+}
+
+impl AutoCompleteDropdown {
+    /// Create a dropdown with the first item highlighted
+    pub fn new(items: Vec<String>) -> Self {
+        Self { items, selected: 0, offset: 0 }
+    }
+
+    /// Highlighted suggestion
+    pub fn selected_item(&self) -> Option<&str> {
+        self.items.get(self.selected).map(|s| s.as_str())
+    }
+
+    /// Suggestions currently scrolled into view
+    pub fn visible_items(&self) -> &[String] {
+        let end = (self.offset + MAX_DROPDOWN_ITEMS).min(self.items.len());
+        &self.items[self.offset..end]
+    }
+
+    /// Index of the highlighted suggestion within [`visible_items`](Self::visible_items)
+    pub fn visible_selected(&self) -> usize {
+        self.selected - self.offset
+    }
+
+    /// Highlight the next suggestion, scrolling if needed
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.selected += 1;
+            if self.selected >= self.offset + MAX_DROPDOWN_ITEMS {
+                self.offset = self.selected + 1 - MAX_DROPDOWN_ITEMS;
+            }
+        }
+    }
+
+    /// Highlight the previous suggestion, scrolling if needed
+    pub fn previous(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+            if self.selected < self.offset {
+                self.offset = self.selected;
+            }
+        }
+    }
+}
+
 /// Form component for rendering and handling forms
 pub struct FormComponent {
     state: FormState,
     title: String,
     show_help: bool,
     cursor_position: usize,
+    suggestions: HashMap<String, Box<dyn SuggestionsProvider>>,
+    dropdown: Option<AutoCompleteDropdown>,
 }
 
 impl FormComponent {
@@ -363,6 +537,8 @@ impl FormComponent {
             title: title.to_string(),
             show_help: false,
             cursor_position: 0,
+            suggestions: HashMap::new(),
+            dropdown: None,
         }
     }
 
@@ -372,6 +548,95 @@ impl FormComponent {
         self
     }
 
+    /// Offer auto-complete suggestions from `provider` while typing in the field `field_name`
+    pub fn with_suggestions(mut self, field_name: &str, provider: Box<dyn SuggestionsProvider>) -> Self {
+        self.suggestions.insert(field_name.to_string(), provider);
+        self
+    }
+
+    /// Get the open auto-complete dropdown, if any
+    pub fn dropdown(&self) -> Option<&AutoCompleteDropdown> {
+        self.dropdown.as_ref()
+    }
+
+    /// Check if an auto-complete dropdown is open
+    pub fn is_dropdown_open(&self) -> bool {
+        self.dropdown.is_some()
+    }
+
+    /// Recompute the suggestions for the current field's value
+    fn update_dropdown(&mut self) {
+        self.dropdown = self
+            .state
+            .current_field()
+            .and_then(|field| {
+                let provider = self.suggestions.get(&field.name)?;
+                let items = provider.suggest(&field.value);
+                // Nothing to offer once the value is exactly the only suggestion
+                let complete = items.len() == 1 && items[0] == field.value;
+                (!items.is_empty() && !complete).then(|| AutoCompleteDropdown::new(items))
+            });
+    }
+
+    /// Replace the current field's value with the highlighted suggestion
+    fn accept_suggestion(&mut self) {
+        let Some(dropdown) = self.dropdown.take() else {
+            return;
+        };
+        if let (Some(value), Some(field)) = (dropdown.selected_item(), self.state.current_field_mut()) {
+            field.set_value(value);
+            self.cursor_position = value.len();
+            self.state.is_dirty = true;
+        }
+    }
+
+    /// Handle a key while the auto-complete dropdown is open, returning `None` for keys it ignores
+    fn handle_dropdown_event(&mut self, key_event: &KeyEvent) -> Option<ComponentAction> {
+        let dropdown = self.dropdown.as_mut()?;
+        match key_event.code {
+            KeyCode::Down => dropdown.next(),
+            KeyCode::Up => dropdown.previous(),
+            KeyCode::Tab | KeyCode::Enter => self.accept_suggestion(),
+            KeyCode::Esc => self.dropdown = None,
+            _ => return None,
+        }
+        Some(ComponentAction::None)
+    }
+
+    /// Render the auto-complete dropdown below the field occupying `field_area`
+    fn render_dropdown(&self, frame: &mut Frame, field_area: Rect, theme: &Theme) {
+        let Some(dropdown) = &self.dropdown else {
+            return;
+        };
+        let screen = frame.area();
+        let y = field_area.y + field_area.height;
+        let height = (dropdown.visible_items().len() as u16 + 2).min(screen.bottom().saturating_sub(y));
+        if height < 3 {
+            return;
+        }
+        let area = Rect::new(field_area.x, y, field_area.width, height);
+
+        let items: Vec<ListItem> = dropdown
+            .visible_items()
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let style = if i == dropdown.visible_selected() {
+                    theme.styles.selected
+                } else {
+                    theme.styles.base
+                };
+                ListItem::new(item.as_str()).style(style)
+            })
+            .collect();
+        let title = format!("Suggestions ({}/{})", dropdown.selected + 1, dropdown.items.len());
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).border_style(theme.styles.border).title(title));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(list, area);
+    }
+
     /// Get form state
     pub fn state(&self) -> &FormState {
         &self.state
@@ -557,8 +822,9 @@ impl Component for FormComponent {
         let form_area = chunks[0];
         let help_area = chunks[1];
 
-        // Create layout for form fields
-        let field_count = self.state.field_order.len();
+        // Create layout for the visible form fields
+        let visible_fields = self.state.get_visible_fields();
+        let field_count = visible_fields.len();
         if field_count == 0 {
             return Ok(());
         }
@@ -573,10 +839,15 @@ impl Component for FormComponent {
             .split(form_area);
 
         // Render each field
-        for (i, field_name) in self.state.field_order.iter().enumerate() {
+        let current_field = self.state.current_field_name();
+        let mut focused_area = None;
+        for (i, field_name) in visible_fields.into_iter().enumerate() {
             if let Some(field) = self.state.fields.get(field_name) {
-                if !field.is_hidden && i < field_chunks.len() {
-                    let is_focused = i == self.state.current_field;
+                if i < field_chunks.len() {
+                    let is_focused = Some(field_name) == current_field;
+                    if is_focused {
+                        focused_area = Some(field_chunks[i]);
+                    }
                     self.render_field(frame, field_chunks[i], field, is_focused, theme)?;
 
                     // Show validation error if any
@@ -597,9 +868,14 @@ impl Component for FormComponent {
             }
         }
 
+        // The dropdown is drawn last so it covers the fields below the focused one
+        if let Some(area) = focused_area {
+            self.render_dropdown(frame, area, theme);
+        }
+
         // Render help text if enabled
         if self.show_help && help_area.height > 0 {
-            let help_text = "Tab/Shift+Tab: Navigate • Enter: Next field • Ctrl+S: Save • Esc: Cancel • F1: Toggle help";
+            let help_text = "Tab/Shift+Tab, ↑/↓: Navigate • Enter: Next field • Ctrl+S: Save • Esc: Cancel • F1: Toggle help";
             let help = Paragraph::new(help_text)
                 .block(Block::default().borders(Borders::ALL).title("Help"))
                 .style(theme.styles.help);
@@ -612,6 +888,10 @@ impl Component for FormComponent {
     fn handle_event(&mut self, event: Event) -> Result<ComponentAction> {
         match event {
             Event::Key(key_event) => {
+                if let Some(action) = self.handle_dropdown_event(&key_event) {
+                    return Ok(action);
+                }
+
                 match key_event.code {
                     KeyCode::Tab => {
                         if key_event.modifiers.contains(KeyModifiers::SHIFT) {
@@ -627,6 +907,19 @@ impl Component for FormComponent {
                         };
                         Ok(ComponentAction::None)
                     }
+                    KeyCode::Up | KeyCode::Down => {
+                        if key_event.code == KeyCode::Up {
+                            self.state.prev_field();
+                        } else {
+                            self.state.next_field();
+                        }
+                        self.cursor_position = if let Some(field) = self.state.current_field() {
+                            field.value.len()
+                        } else {
+                            0
+                        };
+                        Ok(ComponentAction::None)
+                    }
                     KeyCode::Enter => {
                         if self.state.next_field() {
                             self.cursor_position = if let Some(field) = self.state.current_field() {
@@ -650,13 +943,19 @@ impl Component for FormComponent {
                         }
                     }
                     KeyCode::Char(ch) => {
-                        self.handle_char_input(ch)
+                        let action = self.handle_char_input(ch)?;
+                        self.update_dropdown();
+                        Ok(action)
                     }
                     KeyCode::Backspace => {
-                        self.handle_backspace()
+                        let action = self.handle_backspace()?;
+                        self.update_dropdown();
+                        Ok(action)
                     }
                     KeyCode::Delete => {
-                        self.handle_delete()
+                        let action = self.handle_delete()?;
+                        self.update_dropdown();
+                        Ok(action)
                     }
                     KeyCode::Left => {
                         self.handle_cursor_movement(CursorDirection::Left)
@@ -786,6 +1085,22 @@ mod tests {
         assert!(state.validate_all());
     }
 
+    #[test]
+    fn test_form_state_external_validation_error() {
+        let mut state = FormState::new();
+        state.add_field(FormField::new("name", "Name", FieldType::Text));
+
+        state.set_validation_error("name", "Profile name cannot contain path separators");
+
+        assert!(state.show_validation);
+        assert_eq!(
+            state.get_validation_result("name"),
+            Some(&ValidationResult::Invalid(
+                "Profile name cannot contain path separators".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn test_form_state_dirty_tracking() {
         let mut state = FormState::new();
@@ -858,4 +1173,110 @@ mod tests {
         assert_eq!(form.state.field_order.len(), 1);
         assert_eq!(form.state.current_field_name(), Some("test"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_conditional_visibility() {
+        let mut state = FormState::new();
+        let mut key_type = FormField::new("key_type", "Key Type", FieldType::Select(vec![
+            "Ssh".to_string(),
+            "Gpg".to_string(),
+        ]));
+        key_type.set_value("Ssh").set_tab_index(0);
+        let mut signers = FormField::new("allowed_signers", "Allowed Signers", FieldType::Path)
+            .hide_unless_field_equals("key_type", "Ssh");
+        signers.set_required(true).set_tab_index(1);
+        let mut email = FormField::new("email", "Email", FieldType::Email);
+        email.set_tab_index(2);
+        state.add_field(key_type).add_field(signers).add_field(email);
+
+        assert_eq!(state.get_visible_fields(), vec!["key_type", "allowed_signers", "email"]);
+        assert!(!state.validate_all());
+
+        state.set_field_value("key_type", "Gpg").unwrap();
+        assert_eq!(state.get_visible_fields(), vec!["key_type", "email"]);
+        // Hidden required fields do not block validation
+        assert!(state.validate_all());
+        assert_eq!(state.get_validation_result("allowed_signers"), None);
+
+        // Navigation skips the hidden field in both directions
+        assert!(state.next_field());
+        assert_eq!(state.current_field_name(), Some("email"));
+        assert!(state.prev_field());
+        assert_eq!(state.current_field_name(), Some("key_type"));
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn vault_form(vaults: &[&str]) -> FormComponent {
+        let mut form = FormComponent::new("Test Form")
+            .with_suggestions(
+                "vault",
+                Box::new(StaticSuggestionsProvider(vaults.iter().map(|v| v.to_string()).collect())),
+            );
+        form.add_field(FormField::new("vault", "Vault", FieldType::Text));
+        form
+    }
+
+    #[test]
+    fn test_static_suggestions_provider() {
+        let provider = StaticSuggestionsProvider(vec!["Personal".to_string(), "Work".to_string()]);
+        assert_eq!(provider.suggest("or"), vec!["Work"]);
+        assert_eq!(provider.suggest("P"), vec!["Personal"]);
+        assert_eq!(provider.suggest("").len(), 2);
+
+        let dynamic = DynamicSuggestionsProvider(Box::new(|partial| vec![format!("{}-1", partial)]));
+        assert_eq!(dynamic.suggest("a"), vec!["a-1"]);
+    }
+
+    #[test]
+    fn test_dropdown_accepts_highlighted_suggestion() {
+        let mut form = vault_form(&["Personal", "Private", "Work"]);
+
+        form.handle_event(key(KeyCode::Char('p'))).unwrap();
+        assert_eq!(form.dropdown().unwrap().visible_items(), ["Personal", "Private"]);
+
+        form.handle_event(key(KeyCode::Down)).unwrap();
+        assert_eq!(form.dropdown().unwrap().selected_item(), Some("Private"));
+        form.handle_event(key(KeyCode::Tab)).unwrap();
+
+        assert!(!form.is_dropdown_open());
+        assert_eq!(form.state().current_field().unwrap().value, "Private");
+        assert!(form.state().is_dirty());
+    }
+
+    #[test]
+    fn test_dropdown_escape_closes_without_accepting() {
+        let mut form = vault_form(&["Work"]);
+
+        form.handle_event(key(KeyCode::Char('w'))).unwrap();
+        assert!(form.is_dropdown_open());
+        assert_eq!(form.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::None);
+
+        assert!(!form.is_dropdown_open());
+        assert_eq!(form.state().current_field().unwrap().value, "w");
+
+        // No suggestions, no dropdown
+        form.handle_event(key(KeyCode::Char('x'))).unwrap();
+        assert!(!form.is_dropdown_open());
+    }
+
+    #[test]
+    fn test_dropdown_scrolls_past_max_items() {
+        let mut dropdown = AutoCompleteDropdown::new((0..12).map(|i| format!("vault-{}", i)).collect());
+        assert_eq!(dropdown.visible_items().len(), MAX_DROPDOWN_ITEMS);
+
+        for _ in 0..9 {
+            dropdown.next();
+        }
+        assert_eq!(dropdown.selected_item(), Some("vault-9"));
+        assert_eq!(dropdown.visible_items()[0], "vault-2");
+        assert_eq!(dropdown.visible_selected(), 7);
+
+        for _ in 0..9 {
+            dropdown.previous();
+        }
+        assert_eq!(dropdown.visible_items()[0], "vault-0");
+    }
+}
@@ -1,7 +1,12 @@
 pub mod form;
 pub mod dialog;
 pub mod table;
+pub mod preview;
 
-pub use form::{FormComponent, FormField, FormState, FieldType, ValidationRule, ValidationRuleType, ValidationResult};
+pub use form::{
+    FormComponent, FormField, FormState, FieldType, ValidationRule, ValidationRuleType, ValidationResult,
+    AutoCompleteDropdown, SuggestionsProvider, StaticSuggestionsProvider, DynamicSuggestionsProvider,
+};
 pub use dialog::{DialogComponent, DialogType, DialogResult};
-pub use table::{TableComponent, TableState, TableColumn, TableRow};
\ No newline at end of file
+pub use table::{TableComponent, TableState, TableColumn, TableRow};
+pub use preview::ProfilePreviewComponent;
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! Profile preview component for the TUI application.
+//!
+//! This module provides a read-only panel showing the git configuration a
+//! profile will produce when it is applied.
+
+use crate::{
+    config::types::{KeyType, Profile},
+    error::Result,
+    tui::{Component, ComponentAction, Event, Theme, focus::focus_border_style},
+};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+/// Read-only preview of a profile
+pub struct ProfilePreviewComponent {
+    profile: Profile,
+    focused: bool,
+}
+
+impl ProfilePreviewComponent {
+    /// Create a preview of `profile`
+    pub fn new(profile: Profile) -> Self {
+        Self {
+            profile,
+            focused: false,
+        }
+    }
+
+    /// Replace the previewed profile
+    pub fn set_profile(&mut self, profile: Profile) {
+        self.profile = profile;
+    }
+
+    /// Get the previewed profile
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Check if the preview has keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Git configuration lines the profile will produce
+    pub fn lines(&self) -> Vec<String> {
+        let profile = &self.profile;
+        let mut lines = vec!["[user]".to_string()];
+        if let Some(name) = &profile.git_user_name {
+            lines.push(format!("    name = {}", name));
+        }
+        lines.push(format!("    email = {}", profile.git_user_email));
+        if let Some(key) = &profile.signing_key {
+            lines.push(format!("    signingkey = {}", key));
+        }
+
+        let format = match profile.key_type {
+            KeyType::Ssh => "ssh",
+            KeyType::Gpg => "openpgp",
+            KeyType::X509 | KeyType::Gitsign => "x509",
+        };
+        lines.push("[gpg]".to_string());
+        lines.push(format!("    format = {}", format));
+
+        if profile.signing_key.is_some() {
+            lines.push("[commit]".to_string());
+            lines.push("    gpgsign = true".to_string());
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "Scope: {}",
+            profile.scope.as_ref().map(|s| format!("{:?}", s)).unwrap_or("Not set".to_string())
+        ));
+        lines.push(format!("1Password: {}", if profile.one_password { "Yes" } else { "No" }));
+        lines
+    }
+}
+
+impl Component for ProfilePreviewComponent {
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) -> Result<()> {
+        let title = if self.profile.name.is_empty() {
+            "Preview".to_string()
+        } else {
+            format!("Preview: {}", self.profile.name)
+        };
+
+        let lines: Vec<Line> = self
+            .lines()
+            .into_iter()
+            .map(|line| {
+                if line.starts_with('[') {
+                    Line::from(Span::styled(line, theme.styles.highlight))
+                } else {
+                    Line::from(Span::styled(line, theme.styles.base))
+                }
+            })
+            .collect();
+
+        let preview = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(focus_border_style(self.focused, theme))
+                    .title(title),
+            );
+        frame.render_widget(preview, area);
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: Event) -> Result<ComponentAction> {
+        Ok(ComponentAction::None)
+    }
+
+    fn focus_order(&self) -> u8 {
+        1
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_lines() {
+        let mut preview = ProfilePreviewComponent::new(Profile::default());
+        preview.set_profile(Profile {
+            name: "work".to_string(),
+            git_user_name: Some("Jane Doe".to_string()),
+            git_user_email: "jane@work.com".to_string(),
+            key_type: KeyType::Gpg,
+            signing_key: Some("ABCD1234".to_string()),
+            ..Default::default()
+        });
+
+        let lines = preview.lines();
+        assert!(lines.contains(&"    name = Jane Doe".to_string()));
+        assert!(lines.contains(&"    email = jane@work.com".to_string()));
+        assert!(lines.contains(&"    signingkey = ABCD1234".to_string()));
+        assert!(lines.contains(&"    format = openpgp".to_string()));
+        assert!(lines.contains(&"    gpgsign = true".to_string()));
+    }
+
+    #[test]
+    fn test_preview_focus() {
+        let mut preview = ProfilePreviewComponent::new(Profile::default());
+        assert_eq!(preview.focus_order(), 1);
+        assert!(!preview.is_focused());
+        preview.set_focused(true);
+        assert!(preview.is_focused());
+    }
+}
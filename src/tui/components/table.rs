@@ -5,16 +5,17 @@
 
 use crate::{
     error::Result,
+    output::CsvFormatter,
     tui::{Component, ComponentAction, Event, Theme, UIHelpers},
 };
 use ratatui::{
     Frame,
-    layout::{Rect, Layout, Direction, Constraint, Alignment},
+    layout::{Rect, Layout, Direction, Constraint, Alignment, Flex},
     style::{Style, Color, Modifier},
     widgets::{Block, Borders, Row, Table, TableState as RatatuiTableState, Cell, Paragraph},
     text::{Line, Span},
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use std::cmp::Ordering;
 
 /// Column definition for the table
@@ -55,6 +56,8 @@ impl TableColumn {
 pub struct TableRow {
     pub cells: Vec<String>,
     pub metadata: Option<String>,
+    /// Draw the row in the theme's warning style
+    pub highlighted: bool,
 }
 
 impl TableRow {
@@ -63,6 +66,7 @@ impl TableRow {
         Self {
             cells,
             metadata: None,
+            highlighted: false,
         }
     }
 
@@ -71,6 +75,12 @@ impl TableRow {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Set whether the row is highlighted as a warning
+    pub fn with_highlight(mut self, highlighted: bool) -> Self {
+        self.highlighted = highlighted;
+        self
+    }
 }
 
 /// Sort direction
@@ -374,6 +384,8 @@ pub struct TableComponent {
     show_row_numbers: bool,
     show_status: bool,
     filter_cursor: usize,
+    /// Area the table was last drawn in, used to map mouse clicks to cells
+    last_rendered_area: std::cell::Cell<Option<Rect>>,
 }
 
 impl TableComponent {
@@ -386,6 +398,7 @@ impl TableComponent {
             show_row_numbers: false,
             show_status: true,
             filter_cursor: 0,
+            last_rendered_area: std::cell::Cell::new(None),
         }
     }
 
@@ -423,6 +436,18 @@ impl TableComponent {
         &mut self.state
     }
 
+    /// Export the filtered rows, in their current sort order, as CSV
+    pub fn export_to_csv(&self) -> String {
+        let header: Vec<&str> = self.state.columns.iter().map(|column| column.title.as_str()).collect();
+        let records = self
+            .state
+            .filtered_rows
+            .iter()
+            .map(|&index| self.state.rows[index].cells.clone());
+        // Writing to memory only fails on invalid UTF-8, which `String` cells rule out
+        CsvFormatter::write_records(&header, records).unwrap_or_default()
+    }
+
     /// Handle filter input
     fn handle_filter_input(&mut self, ch: char) -> Result<ComponentAction> {
         if self.filter_cursor <= self.state.filter_text.len() {
@@ -443,6 +468,79 @@ impl TableComponent {
         Ok(ComponentAction::None)
     }
 
+    /// Column width constraints, including the row number column when shown
+    fn column_constraints(&self) -> Vec<Constraint> {
+        let mut constraints = self.state.columns.iter().map(|col| col.width).collect::<Vec<_>>();
+        if self.show_row_numbers {
+            constraints.insert(0, Constraint::Length(4));
+        }
+        constraints
+    }
+
+    /// Handle a mouse event: a left click on a header sorts by that column,
+    /// a left click on a row selects it and the wheel moves the selection.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<ComponentAction> {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(area) = self.last_rendered_area.get() else {
+                    return Ok(ComponentAction::None);
+                };
+                // Inside the border, the header takes the first line and rows follow
+                let inner = Rect::new(
+                    area.x + 1,
+                    area.y + 1,
+                    area.width.saturating_sub(2),
+                    area.height.saturating_sub(2),
+                );
+                if mouse.column < inner.left()
+                    || mouse.column >= inner.right()
+                    || mouse.row < inner.top()
+                    || mouse.row >= inner.bottom()
+                {
+                    return Ok(ComponentAction::None);
+                }
+
+                if mouse.row == inner.top() {
+                    if self.show_header
+                        && let Some(column_index) = self.column_at(inner, mouse.column)
+                    {
+                        self.state.sort_by_column(column_index);
+                    }
+                } else {
+                    let row_index = self.state.scroll_offset + (mouse.row - inner.top() - 1) as usize;
+                    if row_index < self.state.total_rows() {
+                        self.state.selected = Some(row_index);
+                        self.state.ensure_selected_visible();
+                    }
+                }
+            }
+            MouseEventKind::ScrollDown => self.state.select_next(),
+            MouseEventKind::ScrollUp => self.state.select_previous(),
+            _ => {}
+        }
+        Ok(ComponentAction::None)
+    }
+
+    /// Index of the data column drawn at screen column `x` within `inner`
+    ///
+    /// Mirrors the layout ratatui gives the columns: a highlight symbol gutter
+    /// while a row is selected, then the columns one cell apart.
+    fn column_at(&self, inner: Rect, x: u16) -> Option<usize> {
+        let selection_width = if self.state.selected.is_some() { 2 } else { 0 };
+        let [_, columns_area] =
+            Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)]).areas(inner);
+        let columns = Layout::horizontal(self.column_constraints())
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(columns_area);
+        let index = columns.iter().position(|column| x >= column.left() && x < column.right())?;
+        if self.show_row_numbers {
+            index.checked_sub(1)
+        } else {
+            Some(index)
+        }
+    }
+
     /// Render the table
     fn render_table(&self, frame: &mut Frame, area: Rect, theme: &Theme) -> Result<()> {
         let visible_rows = self.state.visible_rows();
@@ -462,6 +560,8 @@ impl TableComponent {
                 
                 let style = if self.state.multi_select && self.state.is_row_selected(actual_row_index) {
                     theme.styles.selected
+                } else if row.highlighted {
+                    theme.styles.warning
                 } else {
                     theme.styles.base
                 };
@@ -471,10 +571,7 @@ impl TableComponent {
             .collect();
 
         // Prepare column constraints
-        let mut constraints = self.state.columns.iter().map(|col| col.width).collect::<Vec<_>>();
-        if self.show_row_numbers {
-            constraints.insert(0, Constraint::Length(4));
-        }
+        let constraints = self.column_constraints();
 
         // Prepare header
         let mut header_cells: Vec<Cell> = self.state.columns
@@ -517,6 +614,7 @@ impl TableComponent {
         }
 
         frame.render_stateful_widget(table, area, &mut table_state);
+        self.last_rendered_area.set(Some(area));
 
         Ok(())
     }
@@ -676,6 +774,7 @@ impl Component for TableComponent {
                 }
                 Ok(ComponentAction::None)
             }
+            Event::Mouse(mouse_event) if !self.state.show_filter => self.handle_mouse_event(mouse_event),
             _ => Ok(ComponentAction::None),
         }
     }
@@ -704,6 +803,8 @@ mod tests {
         assert_eq!(row.cells.len(), 2);
         assert_eq!(row.cells[0], "John");
         assert_eq!(row.metadata, Some("user_id:123".to_string()));
+        assert!(!row.highlighted);
+        assert!(row.with_highlight(true).highlighted);
     }
 
     #[test]
@@ -855,4 +956,66 @@ mod tests {
         direction = direction.toggle();
         assert_eq!(direction, SortDirection::Ascending);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_export_to_csv() {
+        let mut table = TableComponent::new("Profiles");
+        table.set_columns(vec![
+            TableColumn::new("Name", Constraint::Length(20)).sortable(),
+            TableColumn::new("Email", Constraint::Length(30)),
+        ]);
+        table.set_rows(vec![
+            TableRow::new(vec!["work".to_string(), "jane@work.com".to_string()]),
+            TableRow::new(vec!["personal".to_string(), "Jane, Doe <jane@home.org>".to_string()]),
+            TableRow::new(vec!["oss".to_string(), "jane@oss.dev".to_string()]),
+        ]);
+        table.state_mut().sort_by_column(0);
+        table.state_mut().set_filter("jane@");
+
+        assert_eq!(
+            table.export_to_csv(),
+            "Name,Email\noss,jane@oss.dev\npersonal,\"Jane, Doe <jane@home.org>\"\nwork,jane@work.com\n"
+        );
+    }
+
+    #[test]
+    fn test_mouse_sorts_and_selects() {
+        let click = |column, row| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: crossterm::event::KeyModifiers::NONE,
+            })
+        };
+        let mut table = TableComponent::new("People");
+        table.set_columns(vec![
+            TableColumn::new("Name", Constraint::Length(10)).sortable(),
+            TableColumn::new("Age", Constraint::Length(5)).sortable(),
+        ]);
+        table.set_rows(vec![
+            TableRow::new(vec!["John".to_string(), "30".to_string()]),
+            TableRow::new(vec!["Alice".to_string(), "35".to_string()]),
+            TableRow::new(vec!["Bob".to_string(), "25".to_string()]),
+        ]);
+
+        // Clicks are ignored until the table has been drawn
+        table.handle_event(click(13, 1)).unwrap();
+        assert_eq!(table.state().sort_column, None);
+
+        table.last_rendered_area.set(Some(Rect::new(0, 0, 40, 10)));
+
+        // The Age header sits after the 10 cell Name column and a spacer
+        table.handle_event(click(13, 1)).unwrap();
+        assert_eq!(table.state().sort_column, Some(1));
+        assert_eq!(table.state().visible_rows()[0].cells[0], "Bob");
+
+        // The second row is on the line below the first
+        table.handle_event(click(3, 3)).unwrap();
+        assert_eq!(table.state().selected_row().unwrap().cells[0], "John");
+
+        // With a row selected the columns shift right by the highlight symbol
+        table.handle_event(click(4, 1)).unwrap();
+        assert_eq!(table.state().sort_column, Some(0));
+    }
+}
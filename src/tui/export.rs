@@ -0,0 +1,154 @@
+//! Export of data raised by screens through [`ComponentAction::ExportData`].
+//!
+//! The data is copied to the clipboard. When that fails, typically on a
+//! headless terminal, a popup form asks for a file to write it to instead.
+
+use super::{
+    clipboard::ClipboardHelper,
+    components::form::{FieldType, FormField},
+    Component, ComponentAction, Event, FormComponent, Theme, UIHelpers,
+};
+use crate::error::{Result, ResultExt};
+use crossterm::event::KeyCode;
+use ratatui::{Frame, layout::Rect, widgets::Clear};
+use std::path::PathBuf;
+
+/// File written when the path prompt is confirmed empty
+pub const DEFAULT_EXPORT_FILE: &str = "profiles.csv";
+
+/// Name of the path field of the prompt
+const PATH_FIELD: &str = "path";
+
+/// Copies exported CSV to the clipboard, or prompts for a file to write it to
+pub struct Exporter {
+    clipboard: ClipboardHelper,
+    /// Path prompt with the data waiting to be written, while it is open
+    prompt: Option<(FormComponent, String)>,
+}
+
+impl Exporter {
+    pub fn new(clipboard: ClipboardHelper) -> Self {
+        Self { clipboard, prompt: None }
+    }
+
+    /// Whether the path prompt is open and takes the input
+    pub fn is_prompting(&self) -> bool {
+        self.prompt.is_some()
+    }
+
+    /// Copy `data` to the clipboard, opening the path prompt if that fails
+    pub fn export(&mut self, data: String) -> ComponentAction {
+        if self.clipboard.set(&data).is_ok() {
+            return ComponentAction::ShowPopup(format!("Copied {} to clipboard", Self::describe(&data)));
+        }
+
+        let mut form = FormComponent::new("Export to File");
+        let mut field = FormField::new(PATH_FIELD, "No clipboard available, save CSV to", FieldType::Path);
+        field.set_placeholder(DEFAULT_EXPORT_FILE);
+        form.add_field(field);
+        self.prompt = Some((form, data));
+        ComponentAction::None
+    }
+
+    /// Handle input for the open path prompt: Enter writes the file, Esc cancels
+    pub fn handle_prompt_event(&mut self, event: Event) -> Result<ComponentAction> {
+        let Some((form, _)) = self.prompt.as_mut() else {
+            return Ok(ComponentAction::None);
+        };
+        if let Event::Key(key_event) = &event
+            && key_event.code == KeyCode::Esc
+        {
+            self.prompt = None;
+            return Ok(ComponentAction::ShowPopup("Export cancelled".to_string()));
+        }
+
+        match form.handle_event(event)? {
+            ComponentAction::Return(_) => {
+                let Some((form, data)) = self.prompt.take() else {
+                    return Ok(ComponentAction::None);
+                };
+                let value = form.state().get_data().remove(PATH_FIELD).unwrap_or_default();
+                let path = PathBuf::from(match value.trim() {
+                    "" => DEFAULT_EXPORT_FILE,
+                    path => path,
+                });
+                std::fs::write(&path, &data)
+                    .with_context(format!("while exporting to {}", path.display()))?;
+                Ok(ComponentAction::ShowPopup(format!(
+                    "Saved {} to {}",
+                    Self::describe(&data),
+                    path.display()
+                )))
+            }
+            _ => Ok(ComponentAction::None),
+        }
+    }
+
+    /// Draw the path prompt over `area`, if it is open
+    pub fn render_prompt(&self, frame: &mut Frame, area: Rect, theme: &Theme) -> Result<()> {
+        if let Some((form, _)) = &self.prompt {
+            let popup = UIHelpers::centered_rect(60, 30, area);
+            frame.render_widget(Clear, popup);
+            form.render(frame, popup, theme)?;
+        }
+        Ok(())
+    }
+
+    /// Row count of CSV `data`, not counting the header
+    fn describe(data: &str) -> String {
+        format!("{} row(s) as CSV", data.lines().count().saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::clipboard::MockClipboard;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const CSV: &str = "name,email\nwork,work@example.com\n";
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_export_copies_to_clipboard() {
+        let clipboard = Arc::new(MockClipboard::new());
+        let mut exporter = Exporter::new(ClipboardHelper::with_clipboard(clipboard.clone()));
+
+        let action = exporter.export(CSV.to_string());
+        assert_eq!(action, ComponentAction::ShowPopup("Copied 1 row(s) as CSV to clipboard".to_string()));
+        assert_eq!(clipboard.contents(), vec![CSV.to_string()]);
+        assert!(!exporter.is_prompting());
+    }
+
+    #[test]
+    fn test_export_without_clipboard_prompts_for_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("out.csv");
+        let mut exporter = Exporter::new(ClipboardHelper::with_clipboard(Arc::new(MockClipboard::unavailable())));
+
+        assert_eq!(exporter.export(CSV.to_string()), ComponentAction::None);
+        assert!(exporter.is_prompting());
+
+        for ch in path.to_str().unwrap().chars() {
+            exporter.handle_prompt_event(key(KeyCode::Char(ch))).unwrap();
+        }
+        let action = exporter.handle_prompt_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(
+            action,
+            ComponentAction::ShowPopup(format!("Saved 1 row(s) as CSV to {}", path.display()))
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), CSV);
+        assert!(!exporter.is_prompting());
+
+        // Esc closes the prompt without writing anything
+        exporter.export(CSV.to_string());
+        let action = exporter.handle_prompt_event(key(KeyCode::Esc)).unwrap();
+        assert_eq!(action, ComponentAction::ShowPopup("Export cancelled".to_string()));
+        assert!(!exporter.is_prompting());
+    }
+}
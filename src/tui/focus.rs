@@ -0,0 +1,235 @@
+//! Keyboard focus handling for screens made of several components.
+//!
+//! Components are ordered by [`Component::focus_order`]. `Tab` moves focus to the
+//! next component and `Shift+Tab` to the previous one, wrapping around at the ends.
+//! All other events are routed to the focused component.
+
+use super::{Component, ComponentAction, Event, Theme};
+use crate::error::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::style::Style;
+
+/// Border style for a component, highlighted when it has focus
+pub fn focus_border_style(focused: bool, theme: &Theme) -> Style {
+    if focused {
+        theme.styles.selected
+    } else {
+        theme.styles.border
+    }
+}
+
+/// Tracks which component has keyboard focus
+#[derive(Debug, Clone, Default)]
+pub struct FocusManager {
+    focused: Option<u8>,
+}
+
+impl FocusManager {
+    /// Create a focus manager with nothing focused
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Focus order of the focused component
+    pub fn focused(&self) -> Option<u8> {
+        self.focused
+    }
+
+    /// Check if `component` has focus
+    pub fn is_focused(&self, component: &dyn Component) -> bool {
+        self.focused == Some(component.focus_order())
+    }
+
+    /// Check if `event` moves focus
+    pub fn is_focus_key(event: &Event) -> bool {
+        matches!(event, Event::Key(key) if matches!(key.code, KeyCode::Tab | KeyCode::BackTab))
+    }
+
+    /// Focus the component with the lowest focus order
+    pub fn focus_first(&mut self, components: &mut [&mut dyn Component]) {
+        self.focused = Self::orders(components).first().copied();
+        self.sync(components);
+    }
+
+    /// Move focus to the next component, wrapping to the first
+    pub fn focus_next(&mut self, components: &mut [&mut dyn Component]) {
+        let orders = Self::orders(components);
+        self.focused = match self.focused {
+            Some(current) => orders
+                .iter()
+                .find(|&&order| order > current)
+                .or(orders.first())
+                .copied(),
+            None => orders.first().copied(),
+        };
+        self.sync(components);
+    }
+
+    /// Move focus to the previous component, wrapping to the last
+    pub fn focus_prev(&mut self, components: &mut [&mut dyn Component]) {
+        let orders = Self::orders(components);
+        self.focused = match self.focused {
+            Some(current) => orders
+                .iter()
+                .rev()
+                .find(|&&order| order < current)
+                .or(orders.last())
+                .copied(),
+            None => orders.last().copied(),
+        };
+        self.sync(components);
+    }
+
+    /// Move focus on `Tab`/`Shift+Tab` and route other events to the focused component
+    pub fn handle_event(
+        &mut self,
+        event: Event,
+        components: &mut [&mut dyn Component],
+    ) -> Result<ComponentAction> {
+        if let Event::Key(key) = &event {
+            match key.code {
+                KeyCode::BackTab => {
+                    self.focus_prev(components);
+                    return Ok(ComponentAction::None);
+                }
+                KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                    self.focus_prev(components);
+                    return Ok(ComponentAction::None);
+                }
+                KeyCode::Tab => {
+                    self.focus_next(components);
+                    return Ok(ComponentAction::None);
+                }
+                _ => {}
+            }
+        }
+
+        if self.focused.is_none() {
+            self.focus_first(components);
+        }
+        match components
+            .iter_mut()
+            .find(|component| Some(component.focus_order()) == self.focused)
+        {
+            Some(component) => component.handle_event(event),
+            None => Ok(ComponentAction::None),
+        }
+    }
+
+    /// Distinct focus orders of `components`, ascending
+    fn orders(components: &[&mut dyn Component]) -> Vec<u8> {
+        let mut orders: Vec<u8> = components.iter().map(|c| c.focus_order()).collect();
+        orders.sort_unstable();
+        orders.dedup();
+        orders
+    }
+
+    /// Tell every component whether it has focus
+    fn sync(&self, components: &mut [&mut dyn Component]) {
+        for component in components.iter_mut() {
+            let focused = Some(component.focus_order()) == self.focused;
+            component.set_focused(focused);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+    use ratatui::{Frame, layout::Rect};
+
+    struct TestComponent {
+        order: u8,
+        focused: bool,
+        events: usize,
+    }
+
+    impl TestComponent {
+        fn new(order: u8) -> Self {
+            Self { order, focused: false, events: 0 }
+        }
+    }
+
+    impl Component for TestComponent {
+        fn render(&self, _frame: &mut Frame, _area: Rect, _theme: &Theme) -> Result<()> {
+            Ok(())
+        }
+
+        fn handle_event(&mut self, _event: Event) -> Result<ComponentAction> {
+            self.events += 1;
+            Ok(ComponentAction::Refresh)
+        }
+
+        fn focus_order(&self) -> u8 {
+            self.order
+        }
+
+        fn set_focused(&mut self, focused: bool) {
+            self.focused = focused;
+        }
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_tab_cycles_by_focus_order() {
+        let mut second = TestComponent::new(5);
+        let mut first = TestComponent::new(1);
+        let mut focus = FocusManager::new();
+
+        focus.focus_first(&mut [&mut second, &mut first]);
+        assert_eq!(focus.focused(), Some(1));
+        assert!(first.focused && !second.focused);
+
+        let tab = key(KeyCode::Tab, KeyModifiers::NONE);
+        focus.handle_event(tab.clone(), &mut [&mut second, &mut first]).unwrap();
+        assert_eq!(focus.focused(), Some(5));
+        assert!(second.focused && !first.focused);
+
+        focus.handle_event(tab, &mut [&mut second, &mut first]).unwrap();
+        assert_eq!(focus.focused(), Some(1));
+    }
+
+    #[test]
+    fn test_shift_tab_moves_backward() {
+        let mut a = TestComponent::new(0);
+        let mut b = TestComponent::new(1);
+        let mut c = TestComponent::new(2);
+        let mut focus = FocusManager::new();
+        focus.focus_first(&mut [&mut a, &mut b, &mut c]);
+
+        focus.handle_event(key(KeyCode::BackTab, KeyModifiers::SHIFT), &mut [&mut a, &mut b, &mut c]).unwrap();
+        assert_eq!(focus.focused(), Some(2));
+
+        focus.handle_event(key(KeyCode::Tab, KeyModifiers::SHIFT), &mut [&mut a, &mut b, &mut c]).unwrap();
+        assert_eq!(focus.focused(), Some(1));
+        assert!(focus.is_focused(&b));
+    }
+
+    #[test]
+    fn test_events_routed_to_focused_component() {
+        let mut a = TestComponent::new(0);
+        let mut b = TestComponent::new(1);
+        let mut focus = FocusManager::new();
+
+        let action = focus
+            .handle_event(key(KeyCode::Char('x'), KeyModifiers::NONE), &mut [&mut a, &mut b])
+            .unwrap();
+        assert_eq!(action, ComponentAction::Refresh);
+        assert_eq!((a.events, b.events), (1, 0));
+
+        focus.focus_next(&mut [&mut a, &mut b]);
+        focus.handle_event(key(KeyCode::Enter, KeyModifiers::NONE), &mut [&mut a, &mut b]).unwrap();
+        assert_eq!((a.events, b.events), (1, 1));
+    }
+
+    #[test]
+    fn test_focus_border_style() {
+        let theme = Theme::default();
+        assert_eq!(focus_border_style(true, &theme), theme.styles.selected);
+        assert_eq!(focus_border_style(false, &theme), theme.styles.border);
+    }
+}
@@ -1,15 +1,23 @@
+pub mod animation;
 pub mod app;
+pub mod clipboard;
+pub mod export;
 pub mod terminal;
 pub mod events;
+pub mod focus;
 pub mod ui;
 pub mod widgets;
 pub mod theme;
 pub mod screens;
 pub mod components;
 
+pub use animation::{AnimationState, AnimationType};
 pub use app::{App, AppState, Screen};
+pub use clipboard::{Clipboard, ClipboardHelper};
+pub use export::Exporter;
 pub use terminal::{Terminal, TerminalManager};
 pub use events::{Event, EventHandler, KeyBinding};
+pub use focus::FocusManager;
 pub use ui::UI;
 pub use theme::Theme;
 pub use screens::{Screen as ScreenTrait, ScreenType, ScreenManager};
@@ -47,6 +55,14 @@ pub trait Component: Send {
     fn on_exit(&mut self) -> Result<()> {
         Ok(())
     }
+
+    /// Position of this component in the Tab order of its screen
+    fn focus_order(&self) -> u8 {
+        0
+    }
+
+    /// Called when the component gains or loses keyboard focus
+    fn set_focused(&mut self, _focused: bool) {}
 }
 
 /// Actions that components can trigger
@@ -68,6 +84,8 @@ pub enum ComponentAction {
     ShowPopup(String),
     /// Return a value and exit
     Return(String),
+    /// Export data (CSV) for the application to copy to the clipboard
+    ExportData(String),
 }
 
 /// Main trait for the TUI application
@@ -37,6 +37,7 @@ pub enum MenuAction {
     ViewProfiles,
     CreateProfile,
     ImportProfiles,
+    ImportFromOnePassword,
     Settings,
     Help,
     Exit,
@@ -76,6 +77,13 @@ impl MainMenuScreen {
                 key_hint: Some('3'),
                 enabled: true,
             },
+            MenuItem {
+                label: "Import from 1Password".to_string(),
+                description: "Create a profile from an SSH key stored in 1Password".to_string(),
+                action: MenuAction::ImportFromOnePassword,
+                key_hint: Some('4'),
+                enabled: true,
+            },
             MenuItem {
                 label: "Settings".to_string(),
                 description: "Configure application settings and preferences".to_string(),
@@ -165,6 +173,7 @@ impl MainMenuScreen {
                 MenuAction::ImportProfiles => Ok(ComponentAction::ShowPopup(
                     "Import functionality will be implemented in a future version".to_string()
                 )),
+                MenuAction::ImportFromOnePassword => Ok(ComponentAction::NavigateTo(ScreenType::ProfileImport)),
                 MenuAction::Settings => Ok(ComponentAction::NavigateTo(ScreenType::Settings)),
                 MenuAction::Help => Ok(ComponentAction::NavigateTo(ScreenType::Help)),
                 MenuAction::Exit => Ok(ComponentAction::Exit),
@@ -398,7 +407,7 @@ mod tests {
         
         assert_eq!(menu.title, "Git Profile Manager");
         assert_eq!(menu.selected_index, 0);
-        assert_eq!(menu.menu_items.len(), 6);
+        assert_eq!(menu.menu_items.len(), 7);
         assert_eq!(menu.selected_action(), Some(MenuAction::ViewProfiles));
     }
 
@@ -418,7 +427,7 @@ mod tests {
         
         // Test wrap around - previous from first item
         menu.previous_item();
-        assert_eq!(menu.selected_index, 5);
+        assert_eq!(menu.selected_index, 6);
         assert_eq!(menu.selected_action(), Some(MenuAction::Exit));
         
         // Test wrap around - next from last item
@@ -456,9 +465,10 @@ mod tests {
         let test_cases = vec![
             (0, MenuAction::ViewProfiles, ComponentAction::NavigateTo(ScreenType::ProfileList)),
             (1, MenuAction::CreateProfile, ComponentAction::NavigateTo(ScreenType::ProfileCreate)),
-            (3, MenuAction::Settings, ComponentAction::NavigateTo(ScreenType::Settings)),
-            (4, MenuAction::Help, ComponentAction::NavigateTo(ScreenType::Help)),
-            (5, MenuAction::Exit, ComponentAction::Exit),
+            (3, MenuAction::ImportFromOnePassword, ComponentAction::NavigateTo(ScreenType::ProfileImport)),
+            (4, MenuAction::Settings, ComponentAction::NavigateTo(ScreenType::Settings)),
+            (5, MenuAction::Help, ComponentAction::NavigateTo(ScreenType::Help)),
+            (6, MenuAction::Exit, ComponentAction::Exit),
         ];
         
         for (index, expected_action, expected_component_action) in test_cases {
@@ -500,7 +510,7 @@ mod tests {
         let up_key = KeyEvent::new(KeyCode::Up, KeyModifiers::empty());
         let result = menu.handle_event(Event::Key(up_key)).unwrap();
         assert_eq!(result, ComponentAction::None);
-        assert_eq!(menu.selected_index, 5); // Wrapped to last item
+        assert_eq!(menu.selected_index, 6); // Wrapped to last item
         
         let down_key = KeyEvent::new(KeyCode::Down, KeyModifiers::empty());
         let result = menu.handle_event(Event::Key(down_key)).unwrap();
@@ -565,9 +575,10 @@ mod tests {
         assert_eq!(menu.menu_items[0].key_hint, Some('1'));
         assert_eq!(menu.menu_items[1].key_hint, Some('2'));
         assert_eq!(menu.menu_items[2].key_hint, Some('3'));
-        assert_eq!(menu.menu_items[3].key_hint, Some('s'));
-        assert_eq!(menu.menu_items[4].key_hint, Some('?'));
-        assert_eq!(menu.menu_items[5].key_hint, Some('q'));
+        assert_eq!(menu.menu_items[3].key_hint, Some('4'));
+        assert_eq!(menu.menu_items[4].key_hint, Some('s'));
+        assert_eq!(menu.menu_items[5].key_hint, Some('?'));
+        assert_eq!(menu.menu_items[6].key_hint, Some('q'));
     }
 
     #[test]
@@ -7,14 +7,21 @@ pub mod main_menu;
 pub mod profile_list;
 pub mod profile_view;
 pub mod profile_create;
+pub mod profile_import;
+pub mod settings;
 
 use crate::{
     error::Result,
-    tui::{Component, ComponentAction, Event, Theme},
+    tui::{
+        animation::{AnimationState, AnimationType, DEFAULT_ANIMATION_DURATION},
+        focus::FocusManager,
+        Component, ComponentAction, Event, Theme,
+    },
 };
 use ratatui::{Frame, layout::Rect};
 use std::collections::HashMap;
 use std::any::Any;
+use std::time::Duration;
 
 /// Screen types available in the application
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -23,6 +30,7 @@ pub enum ScreenType {
     ProfileList,
     ProfileEdit(String),
     ProfileCreate,
+    ProfileImport,
     ProfileView(String),
     Settings,
     Help,
@@ -56,6 +64,11 @@ pub trait Screen: Component {
         vec![]
     }
     
+    /// Components that take part in Tab focus navigation, if any
+    fn focus_components(&mut self) -> Vec<&mut dyn Component> {
+        Vec::new()
+    }
+
     /// Handle screen-specific navigation
     fn handle_navigation(&mut self, event: Event) -> Result<ComponentAction> {
         self.handle_event(event)
@@ -71,6 +84,10 @@ pub struct ScreenManager {
     current_screen: Option<ScreenType>,
     screen_stack: Vec<ScreenType>,
     theme: Theme,
+    animations_enabled: bool,
+    animation_duration: Duration,
+    animation: Option<AnimationState>,
+    focus: FocusManager,
 }
 
 impl ScreenManager {
@@ -81,8 +98,24 @@ impl ScreenManager {
             current_screen: None,
             screen_stack: Vec::new(),
             theme,
+            animations_enabled: false,
+            animation_duration: DEFAULT_ANIMATION_DURATION,
+            animation: None,
+            focus: FocusManager::new(),
         }
     }
+
+    /// Enable or disable animated screen transitions
+    pub fn with_animations(mut self, enabled: bool) -> Self {
+        self.animations_enabled = enabled;
+        self
+    }
+
+    /// Set how long screen transitions take
+    pub fn with_animation_duration(mut self, duration: Duration) -> Self {
+        self.animation_duration = duration;
+        self
+    }
     
     /// Register a screen with the manager
     pub fn register_screen(&mut self, screen: Box<dyn Screen>) {
@@ -112,7 +145,12 @@ impl ScreenManager {
         // Enter new screen
         if let Some(screen) = self.screens.get_mut(&screen_type) {
             screen.on_screen_enter()?;
-            self.current_screen = Some(screen_type);
+            self.focus.focus_first(&mut screen.focus_components());
+            if let Some(previous) = self.current_screen.replace(screen_type.clone())
+                && previous != screen_type
+            {
+                self.start_animation(previous, screen_type, AnimationType::SlideLeft);
+            }
         }
         
         Ok(())
@@ -136,7 +174,10 @@ impl ScreenManager {
             // Enter previous screen
             if let Some(screen) = self.screens.get_mut(&previous) {
                 screen.on_screen_enter()?;
-                self.current_screen = Some(previous);
+                self.focus.focus_first(&mut screen.focus_components());
+                if let Some(departing) = self.current_screen.replace(previous.clone()) {
+                    self.start_animation(departing, previous, AnimationType::SlideRight);
+                }
             }
         }
         
@@ -157,8 +198,40 @@ impl ScreenManager {
         }
     }
     
-    /// Render the current screen
+    /// Animate a transition between two screens with a fade
+    ///
+    /// Does nothing when animations are disabled.
+    pub fn animate_transition(&mut self, from: ScreenType, to: ScreenType) {
+        self.start_animation(from, to, AnimationType::Fade);
+    }
+
+    fn start_animation(&mut self, from: ScreenType, to: ScreenType, animation_type: AnimationType) {
+        if self.animations_enabled {
+            self.animation = Some(AnimationState::new(from, to, animation_type, self.animation_duration));
+        }
+    }
+
+    /// Advance the running transition, if any
+    pub fn tick(&mut self, dt: Duration) {
+        if let Some(animation) = &mut self.animation {
+            animation.tick(dt);
+            if animation.is_finished() {
+                self.animation = None;
+            }
+        }
+    }
+
+    /// Get the running transition
+    pub fn animation(&self) -> Option<&AnimationState> {
+        self.animation.as_ref()
+    }
+
+    /// Render the current screen, or both screens while a transition is running
     pub fn render(&self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if let Some(animation) = &self.animation {
+            return self.render_transition(frame, area, animation);
+        }
+
         if let Some(current) = &self.current_screen {
             if let Some(screen) = self.screens.get(current) {
                 screen.render(frame, area, &self.theme)?;
@@ -167,10 +240,34 @@ impl ScreenManager {
         Ok(())
     }
     
+    fn render_transition(&self, frame: &mut Frame, area: Rect, animation: &AnimationState) -> Result<()> {
+        animation.render(frame, area, |frame, area, arriving| {
+            let screen_type = if arriving { &animation.to_screen } else { &animation.from_screen };
+            match self.screens.get(screen_type) {
+                Some(screen) => screen.render(frame, area, &self.theme),
+                None => Ok(()),
+            }
+        })
+    }
+    
+    /// Get the focus of the current screen's components
+    pub fn focus(&self) -> &FocusManager {
+        &self.focus
+    }
+
     /// Handle event for current screen
+    ///
+    /// `Tab`/`Shift+Tab` move focus between the screen's components when it has
+    /// more than one; every other event goes to the screen.
     pub fn handle_event(&mut self, event: Event) -> Result<ComponentAction> {
         if let Some(current) = &self.current_screen {
             if let Some(screen) = self.screens.get_mut(current) {
+                if FocusManager::is_focus_key(&event) {
+                    let mut components = screen.focus_components();
+                    if components.len() > 1 {
+                        return self.focus.handle_event(event, &mut components);
+                    }
+                }
                 return screen.handle_navigation(event);
             }
         }
@@ -192,6 +289,8 @@ impl ScreenManager {
 mod tests {
     use super::*;
     use crate::tui::Theme;
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::sync::{Arc, Mutex};
 
     /// Mock screen for testing
     struct MockScreen {
@@ -200,6 +299,7 @@ mod tests {
         can_exit: bool,
         enter_called: bool,
         exit_called: bool,
+        renders: Arc<Mutex<Vec<(ScreenType, Rect)>>>,
     }
 
     impl MockScreen {
@@ -210,8 +310,15 @@ mod tests {
                 can_exit: true,
                 enter_called: false,
                 exit_called: false,
+                renders: Arc::new(Mutex::new(Vec::new())),
             }
         }
+
+        /// Share a render log with other screens
+        fn with_render_log(mut self, renders: Arc<Mutex<Vec<(ScreenType, Rect)>>>) -> Self {
+            self.renders = renders;
+            self
+        }
         
         fn set_can_exit(&mut self, can_exit: bool) {
             self.can_exit = can_exit;
@@ -227,7 +334,8 @@ mod tests {
     }
 
     impl Component for MockScreen {
-        fn render(&self, _frame: &mut Frame, _area: Rect, _theme: &Theme) -> Result<()> {
+        fn render(&self, _frame: &mut Frame, area: Rect, _theme: &Theme) -> Result<()> {
+            self.renders.lock().unwrap().push((self.screen_type.clone(), area));
             Ok(())
         }
 
@@ -375,4 +483,74 @@ mod tests {
         assert!(manager.handle_event(Event::Tick).is_ok());
         assert!(manager.navigate_back().is_ok());
     }
-}
\ No newline at end of file
+
+    fn animated_manager(renders: &Arc<Mutex<Vec<(ScreenType, Rect)>>>) -> ScreenManager {
+        let mut manager = ScreenManager::new(Theme::default())
+            .with_animations(true)
+            .with_animation_duration(Duration::from_millis(200));
+        manager.register_screen(Box::new(
+            MockScreen::new("Main".to_string(), ScreenType::Main).with_render_log(renders.clone()),
+        ));
+        manager.register_screen(Box::new(
+            MockScreen::new("Help".to_string(), ScreenType::Help).with_render_log(renders.clone()),
+        ));
+        manager
+    }
+
+    #[test]
+    fn test_screen_manager_renders_both_screens_during_animation() {
+        let renders = Arc::new(Mutex::new(Vec::new()));
+        let mut manager = animated_manager(&renders);
+        assert!(manager.navigate_to(ScreenType::Main).is_ok());
+        assert!(manager.navigate_to(ScreenType::Help).is_ok());
+
+        let animation = manager.animation().unwrap();
+        assert_eq!(animation.from_screen, ScreenType::Main);
+        assert_eq!(animation.to_screen, ScreenType::Help);
+        assert_eq!(animation.animation_type, AnimationType::SlideLeft);
+
+        manager.tick(Duration::from_millis(100));
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal.draw(|f| manager.render(f, f.area()).unwrap()).unwrap();
+
+        let rendered = renders.lock().unwrap().clone();
+        assert_eq!(
+            rendered,
+            vec![
+                (ScreenType::Main, Rect::new(0, 0, 20, 10)),
+                (ScreenType::Help, Rect::new(20, 0, 20, 10)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_screen_manager_animation_finishes() {
+        let renders = Arc::new(Mutex::new(Vec::new()));
+        let mut manager = animated_manager(&renders);
+        assert!(manager.navigate_to(ScreenType::Main).is_ok());
+        assert!(manager.navigate_to(ScreenType::Help).is_ok());
+        assert!(manager.navigate_back().is_ok());
+        assert_eq!(manager.animation().unwrap().animation_type, AnimationType::SlideRight);
+
+        manager.tick(Duration::from_millis(250));
+        assert!(manager.animation().is_none());
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal.draw(|f| manager.render(f, f.area()).unwrap()).unwrap();
+        assert_eq!(
+            renders.lock().unwrap().clone(),
+            vec![(ScreenType::Main, Rect::new(0, 0, 40, 10))]
+        );
+    }
+
+    #[test]
+    fn test_screen_manager_animations_disabled_by_default() {
+        let mut manager = ScreenManager::new(Theme::default());
+        manager.register_screen(Box::new(MockScreen::new("Main".to_string(), ScreenType::Main)));
+        manager.register_screen(Box::new(MockScreen::new("Help".to_string(), ScreenType::Help)));
+        assert!(manager.navigate_to(ScreenType::Main).is_ok());
+        assert!(manager.navigate_to(ScreenType::Help).is_ok());
+        manager.animate_transition(ScreenType::Help, ScreenType::Main);
+        assert!(manager.animation().is_none());
+    }
+}
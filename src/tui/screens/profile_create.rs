@@ -1,34 +1,57 @@
 //! Profile creation screen for the TUI application.
 //!
-//! This module provides a wizard-style interface for creating new profiles.
+//! This module provides a wizard-style interface for creating new profiles:
+//! the identity, the key type, the key-specific settings, the 1Password
+//! settings of profiles that use 1Password and finally a summary of the
+//! profile that will be created. Steps without fields to fill in are skipped.
 
 use crate::{
     error::Result,
     tui::{
         Component, ComponentAction, Event, Theme,
         screens::{Screen, ScreenType},
-        components::{FormComponent, FormField, FieldType, ValidationRule, ValidationRuleType},
+        components::{
+            DialogComponent, DynamicSuggestionsProvider, FormComponent, FormField, FieldType, ProfilePreviewComponent, ValidationRule,
+            ValidationRuleType,
+        },
+        focus::focus_border_style,
     },
     config::types::{Profile, KeyType, Scope},
-    profile::ProfileManager,
+    external::onepassword::OnePasswordWrapper,
+    profile::{validator::ProfileValidator, ProfileManager},
 };
 use ratatui::{
     Frame,
     layout::{Rect, Layout, Direction, Constraint, Alignment},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 use crossterm::event::{KeyCode, KeyEvent};
 use std::any::Any;
 use std::sync::Arc;
 use regex::Regex;
 
+/// Form fields shown on each step; the last step is the summary and has none
+const STEP_FIELDS: [&[&str]; 5] = [
+    &["name", "email", "user_name"],
+    &["key_type"],
+    &["signing_key", "allowed_signers", "scope", "one_password"],
+    &["vault_name", "ssh_key_title"],
+    &[],
+];
+
+/// Names of the steps shown in the progress indicator
+const STEP_NAMES: [&str; 5] = ["Identity", "Key type", "Signing", "1Password", "Summary"];
+
 /// Profile creation screen
 pub struct ProfileCreateScreen {
     form: FormComponent,
+    preview: ProfilePreviewComponent,
     theme: Theme,
     profile_manager: Arc<dyn ProfileManager>,
     current_step: usize,
     total_steps: usize,
+    /// Shown from saving until the profile is created on the next tick
+    progress: Option<DialogComponent>,
 }
 
 impl ProfileCreateScreen {
@@ -70,8 +93,9 @@ impl ProfileCreateScreen {
             });
 
         let mut user_name_field = FormField::new("user_name", "Git User Name", FieldType::Text);
-        user_name_field.set_placeholder("Your Full Name")
-            .set_help("The name to use for Git commits (optional)")
+        user_name_field.set_required(true)
+            .set_placeholder("Your Full Name")
+            .set_help("The name to use for Git commits")
             .set_tab_index(2);
 
         let mut key_type_field = FormField::new("key_type", "Key Type", FieldType::Select(vec![
@@ -85,17 +109,37 @@ impl ProfileCreateScreen {
             .set_help("The type of key to use for signing")
             .set_tab_index(3);
 
-        let mut signing_key_field = FormField::new("signing_key", "Signing Key", FieldType::Text);
+        // X509 and gitsign sign without a configured key
+        let mut signing_key_field = FormField::new("signing_key", "Signing Key", FieldType::Text)
+            .with_conditional_visibility(Box::new(|state| {
+                state
+                    .fields
+                    .get("key_type")
+                    .is_some_and(|field| field.value == "Ssh" || field.value == "Gpg")
+            }));
         signing_key_field.set_placeholder("ssh-ed25519 AAAAC3... or GPG key ID")
             .set_help("The key to use for signing commits (optional)")
             .set_tab_index(4);
 
-        let mut vault_name_field = FormField::new("vault_name", "1Password Vault", FieldType::Text);
+        let mut allowed_signers_field = FormField::new("allowed_signers", "Allowed Signers File", FieldType::Path)
+            .hide_unless_field_equals("key_type", "Ssh");
+        allowed_signers_field.set_placeholder("~/.ssh/allowed_signers")
+            .set_help("File of trusted SSH signing keys for verifying signatures (optional)")
+            .set_tab_index(4);
+
+        // The 1Password step is only shown for SSH profiles that use 1Password
+        let uses_one_password = |state: &crate::tui::components::FormState| {
+            let value = |name: &str| state.fields.get(name).map(|field| field.value.as_str());
+            value("key_type") == Some("Ssh") && value("one_password") == Some("true")
+        };
+        let mut vault_name_field = FormField::new("vault_name", "1Password Vault", FieldType::Text)
+            .with_conditional_visibility(Box::new(uses_one_password));
         vault_name_field.set_placeholder("Vault Name")
             .set_help("1Password vault containing SSH keys (optional)")
             .set_tab_index(5);
 
-        let mut ssh_key_title_field = FormField::new("ssh_key_title", "SSH Key Title", FieldType::Text);
+        let mut ssh_key_title_field = FormField::new("ssh_key_title", "SSH Key Title", FieldType::Text)
+            .with_conditional_visibility(Box::new(uses_one_password));
         ssh_key_title_field.set_placeholder("SSH Key Title")
             .set_help("Title of SSH key in 1Password (optional)")
             .set_tab_index(6);
@@ -120,18 +164,45 @@ impl ProfileCreateScreen {
             .add_field(user_name_field)
             .add_field(key_type_field)
             .add_field(signing_key_field)
+            .add_field(allowed_signers_field)
             .add_field(vault_name_field)
             .add_field(ssh_key_title_field)
             .add_field(scope_field)
             .add_field(one_password_field);
 
-        Self {
+        let mut screen = Self {
             form,
+            preview: ProfilePreviewComponent::new(Profile::default()),
             theme,
             profile_manager,
             current_step: 1,
-            total_steps: 3,
-        }
+            total_steps: STEP_FIELDS.len(),
+            progress: None,
+        };
+        screen.show_step(1);
+        screen.preview.set_profile(screen.profile_from_form());
+        screen
+    }
+
+    /// Suggest the 1Password vaults while typing the vault name
+    ///
+    /// The vaults are listed once, when the screen is set up; without access to
+    /// 1Password the field simply has no suggestions.
+    pub fn with_vault_suggestions(mut self, onepassword: &dyn OnePasswordWrapper) -> Self {
+        let vaults: Vec<String> = onepassword
+            .list_vaults()
+            .map(|vaults| vaults.into_iter().map(|vault| vault.name).collect())
+            .unwrap_or_default();
+        let provider = DynamicSuggestionsProvider(Box::new(move |partial: &str| {
+            let partial = partial.to_lowercase();
+            vaults
+                .iter()
+                .filter(|vault| vault.to_lowercase().starts_with(&partial))
+                .cloned()
+                .collect()
+        }));
+        self.form = self.form.with_suggestions("vault_name", Box::new(provider));
+        self
     }
 
     /// Get the current step
@@ -144,9 +215,142 @@ impl ProfileCreateScreen {
         self.total_steps
     }
 
+    /// Show the fields of `step` and focus the first one
+    fn show_step(&mut self, step: usize) {
+        self.current_step = step.clamp(1, self.total_steps);
+        let shown = STEP_FIELDS[self.current_step - 1];
+        let state = self.form.state_mut();
+        for (name, field) in state.fields.iter_mut() {
+            field.is_hidden = !shown.contains(&name.as_str());
+        }
+        state.current_field = state
+            .field_order
+            .iter()
+            .position(|name| state.is_field_visible(name))
+            .unwrap_or(0);
+    }
+
+    /// Whether `step` has anything to show: the summary, or a field whose
+    /// condition (e.g. the chosen key type) is met
+    fn step_applies(&self, step: usize) -> bool {
+        let state = self.form.state();
+        let fields = STEP_FIELDS[step - 1];
+        fields.is_empty()
+            || fields.iter().any(|name| {
+                state.fields.get(*name).is_some_and(|field| {
+                    field
+                        .visibility_condition
+                        .as_ref()
+                        .is_none_or(|condition| condition.is_met(state))
+                })
+            })
+    }
+
+    /// Move to the next step that applies once the fields of this one are valid
+    fn next_step(&mut self) {
+        if self.form.state_mut().validate_all()
+            && let Some(step) = (self.current_step + 1..=self.total_steps).find(|&step| self.step_applies(step))
+        {
+            self.show_step(step);
+        }
+    }
+
+    /// Go back to the previous step that applies
+    fn previous_step(&mut self) {
+        if let Some(step) = (1..self.current_step).rev().find(|&step| self.step_applies(step)) {
+            self.show_step(step);
+        }
+    }
+
+    /// Progress indicator: done steps are checked, the current one is marked
+    /// and steps skipped for this profile are crossed out
+    fn progress_line(&self) -> String {
+        STEP_NAMES
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let step = index + 1;
+                let marker = if step == self.current_step {
+                    "●"
+                } else if !self.step_applies(step) {
+                    "–"
+                } else if step < self.current_step {
+                    "✓"
+                } else {
+                    "○"
+                };
+                format!("{} {}", marker, name)
+            })
+            .collect::<Vec<_>>()
+            .join(" › ")
+    }
+
+    /// Validate the fields of every step up to the current one
+    ///
+    /// When a field is invalid the first step with an error is shown.
+    fn validate_steps(&mut self) -> bool {
+        let step = self.current_step;
+        let reached: Vec<&str> = STEP_FIELDS[..step].concat();
+        let state = self.form.state_mut();
+        for name in &reached {
+            if let Some(field) = state.fields.get_mut(*name) {
+                field.is_hidden = false;
+            }
+        }
+        let form_valid = state.validate_all();
+
+        // Run the structural profile checks and show each error next to its field
+        let errors = ProfileValidator::validate(&self.profile_from_form());
+        for error in &errors {
+            if let Some(field) = Self::form_field_for(&error.field).filter(|field| reached.contains(field)) {
+                self.form.state_mut().set_validation_error(field, &error.message);
+            }
+        }
+
+        let state = self.form.state();
+        let invalid_step = STEP_FIELDS[..step].iter().position(|fields| {
+            fields.iter().any(|name| {
+                matches!(
+                    state.get_validation_result(name),
+                    Some(crate::tui::components::ValidationResult::Invalid(_))
+                )
+            })
+        });
+        self.show_step(invalid_step.map_or(step, |index| index + 1));
+        form_valid && invalid_step.is_none()
+    }
+
     /// Create profile from form data
     pub fn create_profile(&mut self) -> Result<Profile> {
-        let form_data = self.form.state().get_data();
+        let profile = self.profile_from_form();
+
+        // Validate that profile name doesn't already exist
+        if self.profile_manager.exists(&profile.name)? {
+            return Err(crate::error::GitSetupError::DuplicateProfile { 
+                name: profile.name.clone() 
+            });
+        }
+
+        // Create the profile
+        self.profile_manager.create(profile.clone())?;
+
+        Ok(profile)
+    }
+
+    /// Build a profile from the current form data
+    fn profile_from_form(&self) -> Profile {
+        let state = self.form.state();
+        let mut form_data = state.get_data();
+        // Values typed into fields that are hidden for the chosen key type are ignored;
+        // fields of the other steps are only hidden while their step is not shown
+        form_data.retain(|name, _| {
+            state.fields.get(name).is_some_and(|field| {
+                field
+                    .visibility_condition
+                    .as_ref()
+                    .is_none_or(|condition| condition.is_met(state))
+            })
+        });
         
         let name = form_data.get("name").unwrap_or(&String::new()).clone();
         let email = form_data.get("email").unwrap_or(&String::new()).clone();
@@ -155,6 +359,7 @@ impl ProfileCreateScreen {
         let signing_key = form_data.get("signing_key").unwrap_or(&String::new()).clone();
         let vault_name = form_data.get("vault_name").unwrap_or(&String::new()).clone();
         let ssh_key_title = form_data.get("ssh_key_title").unwrap_or(&String::new()).clone();
+        let allowed_signers = form_data.get("allowed_signers").unwrap_or(&String::new()).clone();
         let scope_str = form_data.get("scope").unwrap_or(&"Local".to_string()).clone();
         let one_password_str = form_data.get("one_password").unwrap_or(&"false".to_string()).clone();
 
@@ -178,7 +383,7 @@ impl ProfileCreateScreen {
         // Parse one password
         let one_password = one_password_str == "true";
 
-        let profile = Profile {
+        Profile {
             name,
             git_user_name: if user_name.is_empty() { None } else { Some(user_name) },
             git_user_email: email,
@@ -189,25 +394,39 @@ impl ProfileCreateScreen {
             scope,
             ssh_key_source: None,
             ssh_key_path: None,
-            allowed_signers: None,
+            allowed_signers: if allowed_signers.is_empty() { None } else { Some(allowed_signers) },
             match_patterns: vec![],
             repos: vec![],
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password,
-        };
-
-        // Validate that profile name doesn't already exist
-        if self.profile_manager.exists(&profile.name)? {
-            return Err(crate::error::GitSetupError::DuplicateProfile { 
-                name: profile.name.clone() 
-            });
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
+    }
 
-        // Create the profile
-        self.profile_manager.create(profile.clone())?;
-
-        Ok(profile)
+    /// Map a `Profile` field name to the form field that edits it
+    fn form_field_for(profile_field: &str) -> Option<&'static str> {
+        match profile_field {
+            "name" => Some("name"),
+            "git_user_email" => Some("email"),
+            "git_user_name" => Some("user_name"),
+            "key_type" => Some("key_type"),
+            "signing_key" => Some("signing_key"),
+            "vault_name" => Some("vault_name"),
+            "ssh_key_title" => Some("ssh_key_title"),
+            "allowed_signers" => Some("allowed_signers"),
+            "scope" => Some("scope"),
+            "one_password" => Some("one_password"),
+            _ => None,
+        }
     }
 
     /// Render the profile creation screen
@@ -221,23 +440,43 @@ impl ProfileCreateScreen {
             ])
             .split(area);
 
-        // Render title
+        // Render title with the progress through the steps
         let title_text = format!("Create New Profile - Step {} of {}", self.current_step, self.total_steps);
-        let title = Paragraph::new(title_text)
+        let title = Paragraph::new(self.progress_line())
             .style(self.theme.styles.title)
             .alignment(Alignment::Center)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(self.theme.styles.border)
+                    .title(title_text)
             );
         frame.render_widget(title, chunks[0]);
 
-        // Render form
-        self.form.render(frame, chunks[1], &self.theme)?;
+        // Render form and preview side by side, highlighting the focused one
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+
+        let form_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(focus_border_style(!self.preview.is_focused(), &self.theme))
+            .title("Profile");
+        let form_area = form_block.inner(body[0]);
+        frame.render_widget(form_block, body[0]);
+        if self.current_step == self.total_steps {
+            let summary = Paragraph::new(self.summary())
+                .style(self.theme.styles.base)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(summary, form_area);
+        } else {
+            self.form.render(frame, form_area, &self.theme)?;
+        }
+        self.preview.render(frame, body[1], &self.theme)?;
 
         // Render progress/help
-        let help_text = "Tab/Shift+Tab: Navigate • Ctrl+S: Save • Esc: Cancel • ? for help";
+        let help_text = "Tab/Shift+Tab: Switch panel • ↑/↓: Navigate • Enter: Next step • Ctrl+S: Save • Esc: Back • ? for help";
         let help = Paragraph::new(help_text)
             .style(self.theme.styles.help)
             .alignment(Alignment::Center)
@@ -249,24 +488,69 @@ impl ProfileCreateScreen {
             );
         frame.render_widget(help, chunks[2]);
 
+        if let Some(progress) = &self.progress {
+            progress.render(frame, area, &self.theme)?;
+        }
+
         Ok(())
     }
 
+    /// Summary of the profile that will be created, shown on the last step
+    fn summary(&self) -> String {
+        let profile = self.profile_from_form();
+        let rows = [
+            ("Name", Some(profile.name)),
+            ("Email", Some(profile.git_user_email)),
+            ("User name", profile.git_user_name),
+            ("Key type", Some(format!("{:?}", profile.key_type))),
+            ("Signing key", profile.signing_key),
+            ("Allowed signers", profile.allowed_signers),
+            ("1Password vault", profile.vault_name),
+            ("SSH key title", profile.ssh_key_title),
+            ("Scope", profile.scope.map(|scope| format!("{:?}", scope))),
+            ("Use 1Password", Some(if profile.one_password { "yes" } else { "no" }.to_string())),
+        ];
+
+        let mut lines = vec!["The following profile will be created:".to_string(), String::new()];
+        lines.extend(
+            rows.into_iter()
+                .filter_map(|(label, value)| value.map(|value| format!("{:<16} {}", format!("{}:", label), value))),
+        );
+        lines.push(String::new());
+        lines.push("Press Enter or Ctrl+S to create the profile.".to_string());
+        lines.join("\n")
+    }
+
     /// Handle form submission
+    ///
+    /// The profile is created on the next tick so the progress overlay is drawn
+    /// first; creating keys in 1Password can take a while.
     fn handle_form_submit(&mut self) -> Result<ComponentAction> {
-        if self.form.state().validate_all() {
-            match self.create_profile() {
-                Ok(profile) => {
-                    Ok(ComponentAction::ShowPopup(format!("Profile '{}' created successfully!", profile.name)))
-                }
-                Err(e) => {
-                    Ok(ComponentAction::ShowPopup(format!("Error creating profile: {}", e)))
-                }
+        if !self.validate_steps() {
+            return Ok(ComponentAction::ShowPopup("Please fix validation errors before saving".to_string()));
+        }
+
+        self.progress = Some(DialogComponent::progress("Creating Profile", "Creating the profile...", 0.0, false));
+        Ok(ComponentAction::Refresh)
+    }
+
+    /// Create the profile after saving and open it
+    fn finish_create(&mut self) -> Result<ComponentAction> {
+        self.progress = None;
+        match self.create_profile() {
+            Ok(profile) => {
+                self.form.state_mut().mark_clean();
+                Ok(ComponentAction::NavigateTo(ScreenType::ProfileView(profile.name)))
             }
-        } else {
-            Ok(ComponentAction::ShowPopup("Please fix validation errors before saving".to_string()))
+            Err(e) => Ok(ComponentAction::ShowError(format!("Error creating profile: {}", e))),
         }
     }
+
+    /// Check if the focused field is the last one shown on this step
+    fn on_last_field(&self) -> bool {
+        let state = self.form.state();
+        state.current_field_name().is_none_or(|current| state.get_visible_fields().last() == Some(&current))
+    }
 }
 
 impl Component for ProfileCreateScreen {
@@ -275,22 +559,42 @@ impl Component for ProfileCreateScreen {
     }
 
     fn handle_event(&mut self, event: Event) -> Result<ComponentAction> {
+        if self.progress.is_some() {
+            return match event {
+                Event::Tick => self.finish_create(),
+                _ => Ok(ComponentAction::None),
+            };
+        }
+
         match event {
             Event::Key(key_event) => {
                 match key_event.code {
                     KeyCode::Char('s') if key_event.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
                         self.handle_form_submit()
                     }
-                    KeyCode::Esc => {
+                    KeyCode::Enter if self.current_step == self.total_steps => self.handle_form_submit(),
+                    KeyCode::Enter if !self.preview.is_focused() && !self.form.is_dropdown_open() && self.on_last_field() => {
+                        self.next_step();
+                        Ok(ComponentAction::None)
+                    }
+                    // Esc first closes an open suggestions dropdown
+                    KeyCode::Esc if !self.form.is_dropdown_open() && self.current_step > 1 => {
+                        self.previous_step();
+                        Ok(ComponentAction::None)
+                    }
+                    KeyCode::Esc if !self.form.is_dropdown_open() => {
                         if self.form.state().is_dirty() {
                             Ok(ComponentAction::ShowPopup("Unsaved changes will be lost. Are you sure?".to_string()))
                         } else {
                             Ok(ComponentAction::NavigateBack)
                         }
                     }
+                    _ if self.preview.is_focused() => self.preview.handle_event(event),
                     _ => {
-                        // Forward event to form
-                        self.form.handle_event(event)
+                        // Forward event to form and keep the preview in sync
+                        let action = self.form.handle_event(event)?;
+                        self.preview.set_profile(self.profile_from_form());
+                        Ok(action)
                     }
                 }
             }
@@ -310,9 +614,11 @@ impl Screen for ProfileCreateScreen {
 
     fn screen_help(&self) -> Vec<(&str, &str)> {
         vec![
-            ("Tab/Shift+Tab", "Navigate between fields"),
+            ("Tab/Shift+Tab", "Switch between form and preview"),
+            ("↑/↓", "Navigate between fields"),
+            ("Enter", "Next field or step"),
             ("Ctrl+S", "Save profile"),
-            ("Esc", "Cancel creation"),
+            ("Esc", "Cancel creation, or go back a step"),
             ("?", "Toggle help"),
         ]
     }
@@ -321,6 +627,10 @@ impl Screen for ProfileCreateScreen {
         !self.form.state().is_dirty()
     }
 
+    fn focus_components(&mut self) -> Vec<&mut dyn Component> {
+        vec![&mut self.form, &mut self.preview]
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -329,49 +639,9 @@ impl Screen for ProfileCreateScreen {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::profile::ProfileManager;
+    use crate::profile::mock::MockProfileManager;
     use std::sync::Arc;
 
-    struct MockProfileManager {
-        profiles: Vec<Profile>,
-    }
-
-    impl MockProfileManager {
-        fn new() -> Self {
-            Self { profiles: vec![] }
-        }
-
-        fn with_profile(profile: Profile) -> Self {
-            Self { profiles: vec![profile] }
-        }
-    }
-
-    impl ProfileManager for MockProfileManager {
-        fn create(&self, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn read(&self, name: &str) -> Result<Option<Profile>> {
-            Ok(self.profiles.iter().find(|p| p.name == name).cloned())
-        }
-
-        fn update(&self, _name: &str, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn delete(&self, _name: &str) -> Result<()> {
-            Ok(())
-        }
-
-        fn list(&self) -> Result<Vec<Profile>> {
-            Ok(self.profiles.clone())
-        }
-
-        fn exists(&self, name: &str) -> Result<bool> {
-            Ok(self.profiles.iter().any(|p| p.name == name))
-        }
-    }
-
     fn create_test_screen() -> ProfileCreateScreen {
         let manager = Arc::new(MockProfileManager::new());
         ProfileCreateScreen::new(Theme::default(), manager)
@@ -384,7 +654,7 @@ mod tests {
         assert_eq!(screen.title(), "Create Profile");
         assert_eq!(screen.screen_type(), ScreenType::ProfileCreate);
         assert_eq!(screen.current_step(), 1);
-        assert_eq!(screen.total_steps(), 3);
+        assert_eq!(screen.total_steps(), 5);
         assert!(screen.can_exit());
     }
 
@@ -399,6 +669,26 @@ mod tests {
         assert!(help.iter().any(|(_, desc)| desc.contains("Cancel")));
     }
 
+    #[test]
+    fn test_vault_name_suggestions() {
+        use crate::external::onepassword::MockOnePasswordWrapper;
+        use crossterm::event::KeyModifiers;
+
+        let mut screen = create_test_screen().with_vault_suggestions(&MockOnePasswordWrapper::new());
+        screen.form.state_mut().set_field_value("one_password", "true").unwrap();
+        let state = screen.form.state_mut();
+        state.current_field = state.field_order.iter().position(|name| name == "vault_name").unwrap();
+
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::empty()));
+        screen.handle_event(key(KeyCode::Char('w'))).unwrap();
+        assert_eq!(screen.form.dropdown().unwrap().visible_items(), ["Work"]);
+
+        // The first Esc only closes the dropdown
+        assert_eq!(screen.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::None);
+        assert!(!screen.form.is_dropdown_open());
+        assert!(matches!(screen.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::ShowPopup(_)));
+    }
+
     #[test]
     fn test_profile_create_form_validation() {
         let mut screen = create_test_screen();
@@ -409,6 +699,7 @@ mod tests {
         // Set required fields
         screen.form.state_mut().set_field_value("name", "test").unwrap();
         screen.form.state_mut().set_field_value("email", "test@example.com").unwrap();
+        screen.form.state_mut().set_field_value("user_name", "Test User").unwrap();
         
         // Should now be valid
         assert!(screen.form.state().validate_all());
@@ -433,10 +724,19 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         };
 
-        let manager = Arc::new(MockProfileManager::with_profile(existing_profile));
-        let mut screen = ProfileCreateScreen::new(Theme::default(), manager);
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![existing_profile]).track_calls(true));
+        let mut screen = ProfileCreateScreen::new(Theme::default(), manager.clone());
         
         // Set form data to duplicate name
         screen.form.state_mut().set_field_value("name", "existing").unwrap();
@@ -446,11 +746,13 @@ mod tests {
         let result = screen.create_profile();
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), crate::error::GitSetupError::DuplicateProfile { .. }));
+        assert_eq!(manager.create_call_count(), 0);
     }
 
     #[test]
     fn test_profile_create_success() {
-        let mut screen = create_test_screen();
+        let manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut screen = ProfileCreateScreen::new(Theme::default(), manager.clone());
         
         // Set valid form data
         screen.form.state_mut().set_field_value("name", "test").unwrap();
@@ -471,6 +773,8 @@ mod tests {
         assert_eq!(profile.key_type, KeyType::Ssh);
         assert_eq!(profile.scope, Some(Scope::Local));
         assert!(profile.one_password);
+        assert!(manager.was_called_with_create("test"));
+        assert_eq!(manager.create_call_count(), 1);
     }
 
     #[test]
@@ -509,14 +813,87 @@ mod tests {
         // Set valid form data
         screen.form.state_mut().set_field_value("name", "test").unwrap();
         screen.form.state_mut().set_field_value("email", "test@example.com").unwrap();
+        screen.form.state_mut().set_field_value("user_name", "Test User").unwrap();
         
-        // Test save with valid data
+        // Saving shows the progress overlay and creates the profile on the next tick
+        let save_event = KeyEvent::new(KeyCode::Char('s'), crossterm::event::KeyModifiers::CONTROL);
+        let result = screen.handle_event(Event::Key(save_event)).unwrap();
+        assert_eq!(result, ComponentAction::Refresh);
+        assert!(screen.progress.is_some());
+
+        let result = screen.handle_event(Event::Tick).unwrap();
+        assert_eq!(result, ComponentAction::NavigateTo(ScreenType::ProfileView("test".to_string())));
+        assert!(screen.progress.is_none());
+        assert!(screen.can_exit());
+    }
+
+    #[test]
+    fn test_profile_create_steps() {
+        let mut screen = create_test_screen();
+        let key = |code| Event::Key(KeyEvent::new(code, crossterm::event::KeyModifiers::empty()));
+        let visible = |screen: &ProfileCreateScreen| -> Vec<String> {
+            screen.form.state().get_visible_fields().into_iter().map(String::from).collect()
+        };
+        assert_eq!(visible(&screen), ["name", "email", "user_name"]);
+
+        // Enter on the last field only moves on once the step is valid
+        screen.form.state_mut().set_field_value("name", "test").unwrap();
+        screen.form.state_mut().current_field = 2;
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.current_step(), 1);
+
+        screen.form.state_mut().set_field_value("email", "test@example.com").unwrap();
+        screen.form.state_mut().set_field_value("user_name", "Test User").unwrap();
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.current_step(), 2);
+        assert_eq!(visible(&screen), ["key_type"]);
+
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.current_step(), 3);
+        assert_eq!(visible(&screen), ["signing_key", "allowed_signers", "scope", "one_password"]);
+
+        // Esc goes back a step
+        screen.handle_event(key(KeyCode::Esc)).unwrap();
+        assert_eq!(screen.current_step(), 2);
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.current_step(), 3);
+
+        // Saving from a later step shows the first step with an error
+        screen.form.state_mut().set_field_value("email", "").unwrap();
+        let save_event = KeyEvent::new(KeyCode::Char('s'), crossterm::event::KeyModifiers::CONTROL);
+        assert!(matches!(screen.handle_event(Event::Key(save_event)).unwrap(), ComponentAction::ShowPopup(_)));
+        assert_eq!(screen.current_step(), 1);
+
+        // The summary lists what will be created
+        screen.form.state_mut().set_field_value("email", "test@example.com").unwrap();
+        screen.show_step(5);
+        assert!(visible(&screen).is_empty());
+        let summary = screen.summary();
+        assert!(summary.contains("test@example.com"));
+        assert!(summary.contains("Key type:        Ssh"));
+        assert_eq!(screen.handle_event(key(KeyCode::Enter)).unwrap(), ComponentAction::Refresh);
+    }
+
+    #[test]
+    fn test_profile_create_shows_profile_validation_errors_inline() {
+        let mut screen = create_test_screen();
+
+        // Passes the form's own rules but fails profile validation
+        screen.form.state_mut().set_field_value("name", "test").unwrap();
+        screen.form.state_mut().set_field_value("email", "test@@example.com").unwrap();
+
         let save_event = KeyEvent::new(KeyCode::Char('s'), crossterm::event::KeyModifiers::CONTROL);
         let result = screen.handle_event(Event::Key(save_event)).unwrap();
         match result {
-            ComponentAction::ShowPopup(msg) => assert!(msg.contains("created successfully")),
+            ComponentAction::ShowPopup(msg) => assert!(msg.contains("validation errors")),
             _ => panic!("Expected ShowPopup action"),
         }
+
+        assert!(matches!(
+            screen.form.state().get_validation_result("email"),
+            Some(crate::tui::components::ValidationResult::Invalid(msg))
+                if msg == "Invalid email address format"
+        ));
     }
 
     #[test]
@@ -600,4 +977,95 @@ mod tests {
         assert_eq!(profile.ssh_key_title, None);
         assert!(!profile.one_password);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_profile_create_tab_switches_focus() {
+        let mut manager = crate::tui::ScreenManager::new(Theme::default());
+        manager.register_screen(Box::new(create_test_screen()));
+        manager.navigate_to(ScreenType::ProfileCreate).unwrap();
+        assert_eq!(manager.focus().focused(), Some(0));
+
+        let key = |code| Event::Key(KeyEvent::new(code, crossterm::event::KeyModifiers::empty()));
+
+        // Typing goes to the form and updates the preview
+        manager.handle_event(key(KeyCode::Char('w'))).unwrap();
+        manager.handle_event(key(KeyCode::Tab)).unwrap();
+        assert_eq!(manager.focus().focused(), Some(1));
+
+        // Typing while the preview has focus leaves the form untouched
+        manager.handle_event(key(KeyCode::Char('x'))).unwrap();
+        manager.handle_event(key(KeyCode::BackTab)).unwrap();
+        assert_eq!(manager.focus().focused(), Some(0));
+
+        let screen = manager.current_screen_mut().unwrap();
+        let screen = screen.as_any().downcast_ref::<ProfileCreateScreen>().unwrap();
+        assert_eq!(screen.form.state().get_data().get("name"), Some(&"w".to_string()));
+        assert_eq!(screen.preview.profile().name, "w");
+        assert!(!screen.preview.is_focused());
+    }
+
+    #[test]
+    fn test_profile_create_key_type_toggles_fields() {
+        let mut screen = create_test_screen();
+        screen.show_step(3);
+        let visible = |screen: &ProfileCreateScreen| -> Vec<String> {
+            screen.form.state().get_visible_fields().into_iter().map(String::from).collect()
+        };
+
+        let fields = visible(&screen);
+        assert!(fields.contains(&"signing_key".to_string()));
+        assert!(fields.contains(&"allowed_signers".to_string()));
+
+        screen.form.state_mut().set_field_value("one_password", "true").unwrap();
+        screen.show_step(4);
+        assert_eq!(visible(&screen), ["vault_name", "ssh_key_title"]);
+        screen.show_step(3);
+
+        screen.form.state_mut().set_field_value("allowed_signers", "~/.ssh/allowed_signers").unwrap();
+        screen.form.state_mut().set_field_value("key_type", "Gpg").unwrap();
+        let fields = visible(&screen);
+        assert!(fields.contains(&"signing_key".to_string()));
+        assert!(!fields.contains(&"allowed_signers".to_string()));
+        assert!(!screen.step_applies(4));
+        assert_eq!(screen.profile_from_form().allowed_signers, None);
+
+        screen.form.state_mut().set_field_value("key_type", "Gitsign").unwrap();
+        assert!(!visible(&screen).contains(&"signing_key".to_string()));
+
+        screen.form.state_mut().set_field_value("key_type", "Ssh").unwrap();
+        assert_eq!(
+            screen.profile_from_form().allowed_signers,
+            Some("~/.ssh/allowed_signers".to_string())
+        );
+    }
+
+    #[test]
+    fn test_profile_create_one_password_step() {
+        let mut screen = create_test_screen();
+        let key = |code| Event::Key(KeyEvent::new(code, crossterm::event::KeyModifiers::empty()));
+        screen.form.state_mut().set_field_value("name", "work").unwrap();
+        screen.form.state_mut().set_field_value("email", "jane@work.com").unwrap();
+        screen.form.state_mut().set_field_value("user_name", "Jane").unwrap();
+        screen.form.state_mut().set_field_value("vault_name", "Work").unwrap();
+        screen.show_step(3);
+        assert_eq!(screen.progress_line(), "✓ Identity › ✓ Key type › ● Signing › – 1Password › ○ Summary");
+
+        // Without 1Password the 1Password step is skipped both ways, and its values are dropped
+        screen.next_step();
+        assert_eq!(screen.current_step(), 5);
+        assert_eq!(screen.profile_from_form().vault_name, None);
+        screen.handle_event(key(KeyCode::Esc)).unwrap();
+        assert_eq!(screen.current_step(), 3);
+
+        screen.form.state_mut().set_field_value("one_password", "true").unwrap();
+        assert_eq!(screen.progress_line(), "✓ Identity › ✓ Key type › ● Signing › ○ 1Password › ○ Summary");
+        screen.next_step();
+        assert_eq!(screen.current_step(), 4);
+        assert_eq!(screen.profile_from_form().vault_name, Some("Work".to_string()));
+        screen.next_step();
+        assert_eq!(screen.current_step(), 5);
+        assert!(screen.summary().contains("1Password vault: Work"));
+        screen.handle_event(key(KeyCode::Esc)).unwrap();
+        assert_eq!(screen.current_step(), 4);
+    }
+}
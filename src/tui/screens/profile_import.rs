@@ -0,0 +1,643 @@
+//! 1Password import screen for the TUI application.
+//!
+//! This module provides a wizard that turns an SSH key stored in 1Password into
+//! a profile: it checks that the 1Password CLI is signed in, lets the user pick
+//! a vault and an SSH key from it, edit the identity of the new profile and
+//! finally confirm its creation.
+
+use crate::{
+    error::{GitSetupError, Result},
+    tui::{
+        Component, ComponentAction, Event, Theme,
+        screens::{Screen, ScreenType},
+        components::{DialogComponent, FieldType, FormComponent, FormField, ValidationRule, ValidationRuleType},
+        widgets::{ListState, SelectableList},
+    },
+    config::types::{KeyType, Profile, Scope},
+    external::onepassword::{OnePasswordWrapper, SshKeyItem, Vault},
+    profile::ProfileManager,
+};
+use ratatui::{
+    Frame,
+    layout::{Rect, Layout, Direction, Constraint, Alignment},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use crossterm::event::KeyCode;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use regex::Regex;
+
+/// Help shown when the 1Password CLI is not signed in
+const SIGN_IN_HELP: &str = "Sign in with `op signin`, or enable the 1Password app integration \
+    under Settings → Developer, then press r to try again.";
+
+/// Steps of the import wizard, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStep {
+    Authenticate,
+    SelectVault,
+    SelectKey,
+    Details,
+    Confirm,
+}
+
+impl ImportStep {
+    /// One-based position of the step in the wizard
+    pub fn number(self) -> usize {
+        match self {
+            ImportStep::Authenticate => 1,
+            ImportStep::SelectVault => 2,
+            ImportStep::SelectKey => 3,
+            ImportStep::Details => 4,
+            ImportStep::Confirm => 5,
+        }
+    }
+
+    /// The step before this one, if any
+    fn previous(self) -> Option<ImportStep> {
+        match self {
+            ImportStep::Authenticate => None,
+            ImportStep::SelectVault => Some(ImportStep::Authenticate),
+            ImportStep::SelectKey => Some(ImportStep::SelectVault),
+            ImportStep::Details => Some(ImportStep::SelectKey),
+            ImportStep::Confirm => Some(ImportStep::Details),
+        }
+    }
+}
+
+/// Calls run on the tick after their progress overlay was drawn
+#[derive(Debug, Clone, PartialEq)]
+enum PendingCall {
+    CheckAuthentication,
+    ListVaults,
+    ListKeys(String),
+    CreateProfile,
+}
+
+/// 1Password import screen
+pub struct ProfileImportScreen {
+    theme: Theme,
+    profile_manager: Arc<dyn ProfileManager>,
+    onepassword: Arc<dyn OnePasswordWrapper + Send + Sync>,
+    step: ImportStep,
+    vaults: Vec<Vault>,
+    vault_list: ListState,
+    keys: Vec<SshKeyItem>,
+    key_list: ListState,
+    form: FormComponent,
+    /// Why the current step cannot continue, e.g. 1Password is not signed in
+    error: Option<String>,
+    pending: Option<PendingCall>,
+    /// Shown while `pending` waits for the next tick
+    progress: Option<DialogComponent>,
+}
+
+impl ProfileImportScreen {
+    /// Create a new 1Password import screen
+    pub fn new(
+        theme: Theme,
+        profile_manager: Arc<dyn ProfileManager>,
+        onepassword: Arc<dyn OnePasswordWrapper + Send + Sync>,
+    ) -> Self {
+        let mut form = FormComponent::new("Profile Details");
+
+        let mut name_field = FormField::new("name", "Profile Name", FieldType::Text);
+        name_field.set_required(true)
+            .set_help("A descriptive name for this profile (e.g., 'work', 'personal')")
+            .set_tab_index(0)
+            .add_validation_rule(ValidationRule {
+                name: "no_special_chars".to_string(),
+                rule_type: ValidationRuleType::Regex(Regex::new(r"^[a-zA-Z0-9_-]+$").unwrap()),
+                message: "Name can only contain letters, numbers, hyphens, and underscores".to_string(),
+            });
+
+        let mut email_field = FormField::new("email", "Git User Email", FieldType::Email);
+        email_field.set_required(true)
+            .set_placeholder("your.email@example.com")
+            .set_help("The email address to use for Git commits")
+            .set_tab_index(1)
+            .add_validation_rule(ValidationRule {
+                name: "email_format".to_string(),
+                rule_type: ValidationRuleType::EmailFormat,
+                message: "Please enter a valid email address".to_string(),
+            });
+
+        let mut user_name_field = FormField::new("user_name", "Git User Name", FieldType::Text);
+        user_name_field.set_placeholder("Your Full Name")
+            .set_help("The name to use for Git commits (optional)")
+            .set_tab_index(2);
+
+        form.add_field(name_field)
+            .add_field(email_field)
+            .add_field(user_name_field);
+
+        Self {
+            theme,
+            profile_manager,
+            onepassword,
+            step: ImportStep::Authenticate,
+            vaults: Vec::new(),
+            vault_list: ListState::default(),
+            keys: Vec::new(),
+            key_list: ListState::default(),
+            form,
+            error: None,
+            pending: None,
+            progress: None,
+        }
+    }
+
+    /// Get the current step
+    pub fn step(&self) -> ImportStep {
+        self.step
+    }
+
+    /// Get the total steps
+    pub fn total_steps(&self) -> usize {
+        ImportStep::Confirm.number()
+    }
+
+    /// Show a progress overlay and run `call` on the next tick
+    fn start(&mut self, call: PendingCall) {
+        let message = match &call {
+            PendingCall::CheckAuthentication => "Checking 1Password sign-in...".to_string(),
+            PendingCall::ListVaults => "Listing 1Password vaults...".to_string(),
+            PendingCall::ListKeys(vault) => format!("Listing SSH keys in '{}'...", vault),
+            PendingCall::CreateProfile => "Creating the profile...".to_string(),
+        };
+        self.error = None;
+        self.progress = Some(DialogComponent::progress("1Password Import", &message, 0.0, false));
+        self.pending = Some(call);
+    }
+
+    /// Run the pending call and move on to the step it leads to
+    fn finish_pending(&mut self) -> Result<ComponentAction> {
+        self.progress = None;
+        let Some(call) = self.pending.take() else {
+            return Ok(ComponentAction::None);
+        };
+
+        match call {
+            PendingCall::CheckAuthentication => {
+                match self.onepassword.is_authenticated() {
+                    Ok(true) => self.start(PendingCall::ListVaults),
+                    Ok(false) => self.error = Some(format!("1Password CLI is not signed in.\n\n{}", SIGN_IN_HELP)),
+                    Err(e) => self.error = Some(format!("Could not reach the 1Password CLI: {}\n\n{}", e, SIGN_IN_HELP)),
+                }
+            }
+            PendingCall::ListVaults => match self.onepassword.list_vaults() {
+                Ok(vaults) => {
+                    self.vault_list = ListState::new(vaults.iter().map(|vault| vault.name.clone()).collect());
+                    self.vaults = vaults;
+                    self.step = ImportStep::SelectVault;
+                    if self.vaults.is_empty() {
+                        self.error = Some("No vaults are available to this 1Password account.".to_string());
+                    }
+                }
+                Err(e) => self.error = Some(format!("Error listing vaults: {}", e)),
+            },
+            PendingCall::ListKeys(vault) => match self.onepassword.list_ssh_keys(Some(&vault)) {
+                Ok(keys) => {
+                    self.key_list = ListState::new(keys.iter().map(|key| key.title.clone()).collect());
+                    self.keys = keys;
+                    self.step = ImportStep::SelectKey;
+                    if self.keys.is_empty() {
+                        self.error = Some(format!("Vault '{}' has no SSH keys.", vault));
+                    }
+                }
+                Err(e) => self.error = Some(format!("Error listing SSH keys: {}", e)),
+            },
+            PendingCall::CreateProfile => return self.finish_create(),
+        }
+        Ok(ComponentAction::None)
+    }
+
+    /// The selected vault
+    fn selected_vault(&self) -> Option<&Vault> {
+        self.vault_list.selected.and_then(|index| self.vaults.get(index))
+    }
+
+    /// The selected SSH key
+    fn selected_key(&self) -> Option<&SshKeyItem> {
+        self.key_list.selected.and_then(|index| self.keys.get(index))
+    }
+
+    /// Fill the details form from the title of the chosen key
+    fn prefill_details(&mut self) {
+        let Some(key) = self.selected_key() else {
+            return;
+        };
+        let data = HashMap::from([("name".to_string(), Self::name_from_title(&key.title))]);
+        self.form.state_mut().load_data(data);
+        self.form.state_mut().current_field = 0;
+    }
+
+    /// Profile name derived from a key title, e.g. "Work SSH Key" becomes "work-ssh-key"
+    pub fn name_from_title(title: &str) -> String {
+        title
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Build the profile from the chosen key and the details form
+    pub fn profile(&self) -> Profile {
+        let data = self.form.state().get_data();
+        let field = |name: &str| data.get(name).cloned().unwrap_or_default();
+        let user_name = field("user_name");
+        let key = self.selected_key();
+
+        Profile {
+            name: field("name"),
+            git_user_email: field("email"),
+            git_user_name: if user_name.is_empty() { None } else { Some(user_name) },
+            key_type: KeyType::Ssh,
+            signing_key: key.and_then(|key| key.public_key.clone()),
+            vault_name: self.selected_vault().map(|vault| vault.name.clone()),
+            ssh_key_title: key.map(|key| key.title.clone()),
+            scope: Some(Scope::Local),
+            one_password: true,
+            ..Default::default()
+        }
+    }
+
+    /// Create the profile after confirming and open it
+    fn finish_create(&mut self) -> Result<ComponentAction> {
+        let profile = self.profile();
+        let result = match self.profile_manager.exists(&profile.name) {
+            Ok(true) => Err(GitSetupError::DuplicateProfile { name: profile.name.clone() }),
+            Ok(false) => self.profile_manager.create(profile.clone()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                self.form.state_mut().mark_clean();
+                Ok(ComponentAction::NavigateTo(ScreenType::ProfileView(profile.name)))
+            }
+            Err(e) => Ok(ComponentAction::ShowError(format!("Error creating profile: {}", e))),
+        }
+    }
+
+    /// Go back a step, leaving the screen from the first two
+    fn back(&mut self) -> ComponentAction {
+        self.error = None;
+        match self.step.previous() {
+            // Going back to the sign-in check would only repeat it
+            None | Some(ImportStep::Authenticate) => ComponentAction::NavigateBack,
+            Some(step) => {
+                self.step = step;
+                ComponentAction::None
+            }
+        }
+    }
+
+    /// Handle a key press on the current step
+    fn handle_step_key(&mut self, event: Event) -> Result<ComponentAction> {
+        let Event::Key(key_event) = &event else {
+            return Ok(ComponentAction::None);
+        };
+
+        match (self.step, key_event.code) {
+            (_, KeyCode::Esc) => Ok(self.back()),
+            (ImportStep::Authenticate, KeyCode::Char('r')) => {
+                self.start(PendingCall::CheckAuthentication);
+                Ok(ComponentAction::Refresh)
+            }
+            (ImportStep::SelectVault, code) => {
+                match code {
+                    KeyCode::Up | KeyCode::Char('k') => self.vault_list.select_previous(),
+                    KeyCode::Down | KeyCode::Char('j') => self.vault_list.select_next(),
+                    KeyCode::Enter => {
+                        if let Some(vault) = self.selected_vault() {
+                            let name = vault.name.clone();
+                            self.start(PendingCall::ListKeys(name));
+                            return Ok(ComponentAction::Refresh);
+                        }
+                    }
+                    _ => {}
+                }
+                Ok(ComponentAction::None)
+            }
+            (ImportStep::SelectKey, code) => {
+                match code {
+                    KeyCode::Up | KeyCode::Char('k') => self.key_list.select_previous(),
+                    KeyCode::Down | KeyCode::Char('j') => self.key_list.select_next(),
+                    KeyCode::Enter if self.selected_key().is_some() => {
+                        self.prefill_details();
+                        self.step = ImportStep::Details;
+                    }
+                    _ => {}
+                }
+                Ok(ComponentAction::None)
+            }
+            (ImportStep::Details, KeyCode::Enter) if !self.form.is_dropdown_open() => {
+                if self.form.state_mut().validate_all() {
+                    self.step = ImportStep::Confirm;
+                }
+                Ok(ComponentAction::None)
+            }
+            (ImportStep::Details, _) => self.form.handle_event(event),
+            (ImportStep::Confirm, KeyCode::Enter) => {
+                self.start(PendingCall::CreateProfile);
+                Ok(ComponentAction::Refresh)
+            }
+            _ => Ok(ComponentAction::None),
+        }
+    }
+
+    /// Summary of the profile that will be created, shown on the last step
+    fn summary(&self) -> String {
+        let profile = self.profile();
+        let rows = [
+            ("Name", Some(profile.name)),
+            ("Email", Some(profile.git_user_email)),
+            ("User name", profile.git_user_name),
+            ("1Password vault", profile.vault_name),
+            ("SSH key title", profile.ssh_key_title),
+            ("Signing key", profile.signing_key),
+        ];
+
+        let mut lines = vec!["The following profile will be created:".to_string(), String::new()];
+        lines.extend(
+            rows.into_iter()
+                .filter_map(|(label, value)| value.map(|value| format!("{:<16} {}", format!("{}:", label), value))),
+        );
+        lines.push(String::new());
+        lines.push("Press Enter to create the profile.".to_string());
+        lines.join("\n")
+    }
+
+    /// Render the body of the current step
+    fn render_step(&self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let (title, list) = match self.step {
+            ImportStep::SelectVault => ("Vaults", Some(&self.vault_list)),
+            ImportStep::SelectKey => ("SSH Keys", Some(&self.key_list)),
+            ImportStep::Authenticate => ("1Password", None),
+            ImportStep::Details => ("Profile", None),
+            ImportStep::Confirm => ("Summary", None),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.theme.styles.border)
+            .title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str())
+                .style(self.theme.styles.error)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(error, inner);
+            return Ok(());
+        }
+
+        if let Some(list) = list {
+            // The widget only scrolls the copy; selection lives in the screen state
+            let mut state = list.clone();
+            state.calculate_offset(inner.height as usize);
+            let widget = SelectableList::new()
+                .show_indicators(false)
+                .style(self.theme.styles.base)
+                .highlight_style(self.theme.styles.selected);
+            frame.render_stateful_widget(widget, inner, &mut state);
+            return Ok(());
+        }
+
+        match self.step {
+            ImportStep::Details => self.form.render(frame, inner, &self.theme)?,
+            ImportStep::Confirm => {
+                let summary = Paragraph::new(self.summary())
+                    .style(self.theme.styles.base)
+                    .wrap(Wrap { trim: false });
+                frame.render_widget(summary, inner);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Render the 1Password import screen
+    fn render_profile_import(&self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Title
+                Constraint::Min(0),     // Step
+                Constraint::Length(3),  // Help
+            ])
+            .split(area);
+
+        let title_text = format!(
+            "Import from 1Password - Step {} of {}",
+            self.step.number(),
+            self.total_steps()
+        );
+        let title = Paragraph::new(title_text)
+            .style(self.theme.styles.title)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.styles.border)
+            );
+        frame.render_widget(title, chunks[0]);
+
+        self.render_step(frame, chunks[1])?;
+
+        let help_text = match self.step {
+            ImportStep::Authenticate => "r: Retry • Esc: Back",
+            ImportStep::SelectVault | ImportStep::SelectKey => "↑/↓: Navigate • Enter: Select • Esc: Back",
+            ImportStep::Details => "↑/↓: Navigate • Enter: Next step • Esc: Back",
+            ImportStep::Confirm => "Enter: Create profile • Esc: Back",
+        };
+        let help = Paragraph::new(help_text)
+            .style(self.theme.styles.help)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.styles.border)
+                    .title("Controls")
+            );
+        frame.render_widget(help, chunks[2]);
+
+        if let Some(progress) = &self.progress {
+            progress.render(frame, area, &self.theme)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ProfileImportScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, _theme: &Theme) -> Result<()> {
+        self.render_profile_import(frame, area)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<ComponentAction> {
+        // 1Password calls run on the tick after their progress overlay was drawn
+        if self.progress.is_some() {
+            return match event {
+                Event::Tick => self.finish_pending(),
+                _ => Ok(ComponentAction::None),
+            };
+        }
+
+        self.handle_step_key(event)
+    }
+}
+
+impl Screen for ProfileImportScreen {
+    fn title(&self) -> &str {
+        "Import from 1Password"
+    }
+
+    fn screen_type(&self) -> ScreenType {
+        ScreenType::ProfileImport
+    }
+
+    fn on_screen_enter(&mut self) -> Result<()> {
+        self.step = ImportStep::Authenticate;
+        self.start(PendingCall::CheckAuthentication);
+        Ok(())
+    }
+
+    fn screen_help(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("↑/↓", "Navigate vaults, keys and fields"),
+            ("Enter", "Select or continue to the next step"),
+            ("r", "Retry the 1Password sign-in check"),
+            ("Esc", "Go back a step, or cancel the import"),
+        ]
+    }
+
+    fn can_exit(&self) -> bool {
+        !self.form.state().is_dirty()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::onepassword::MockOnePasswordWrapper;
+    use crate::profile::mock::MockProfileManager;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn work_key() -> SshKeyItem {
+        SshKeyItem {
+            id: "key1".to_string(),
+            title: "Work SSH Key".to_string(),
+            vault: Vault {
+                id: "vault2".to_string(),
+                name: "Work".to_string(),
+            },
+            category: "SSH_KEY".to_string(),
+            public_key: Some("ssh-ed25519 AAAAC3Nza work".to_string()),
+            private_key: None,
+        }
+    }
+
+    fn create_test_screen(onepassword: MockOnePasswordWrapper) -> (ProfileImportScreen, Arc<MockProfileManager>) {
+        let manager = Arc::new(MockProfileManager::new().track_calls(true));
+        let mut screen = ProfileImportScreen::new(Theme::default(), manager.clone(), Arc::new(onepassword));
+        screen.on_screen_enter().unwrap();
+        (screen, manager)
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    /// Run pending 1Password calls until none is left
+    fn settle(screen: &mut ProfileImportScreen) -> ComponentAction {
+        let mut action = ComponentAction::None;
+        while screen.progress.is_some() {
+            action = screen.handle_event(Event::Tick).unwrap();
+        }
+        action
+    }
+
+    #[test]
+    fn test_profile_import_screen_creation() {
+        let (screen, _) = create_test_screen(MockOnePasswordWrapper::new());
+
+        assert_eq!(screen.title(), "Import from 1Password");
+        assert_eq!(screen.screen_type(), ScreenType::ProfileImport);
+        assert_eq!(screen.step(), ImportStep::Authenticate);
+        assert_eq!(screen.total_steps(), 5);
+        assert!(screen.progress.is_some());
+        assert!(screen.can_exit());
+    }
+
+    #[test]
+    fn test_import_wizard_creates_profile() {
+        let (mut screen, manager) = create_test_screen(MockOnePasswordWrapper::new().with_ssh_keys(vec![work_key()]));
+
+        settle(&mut screen);
+        assert_eq!(screen.step(), ImportStep::SelectVault);
+        assert_eq!(screen.vault_list.items, vec!["Personal".to_string(), "Work".to_string()]);
+
+        screen.handle_event(key(KeyCode::Down)).unwrap();
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        settle(&mut screen);
+        assert_eq!(screen.step(), ImportStep::SelectKey);
+        assert_eq!(screen.key_list.items, vec!["Work SSH Key".to_string()]);
+
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.step(), ImportStep::Details);
+        assert_eq!(screen.form.state().get_data()["name"], "work-ssh-key");
+
+        // The email is required before the profile can be confirmed
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.step(), ImportStep::Details);
+        screen.form.state_mut().set_field_value("email", "jane@work.com").unwrap();
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.step(), ImportStep::Confirm);
+        assert_eq!(manager.create_call_count(), 0);
+
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(
+            settle(&mut screen),
+            ComponentAction::NavigateTo(ScreenType::ProfileView("work-ssh-key".to_string()))
+        );
+        assert!(manager.was_called_with_create("work-ssh-key"));
+        let profile = manager.read("work-ssh-key").unwrap().unwrap();
+        assert_eq!(profile.git_user_email, "jane@work.com");
+        assert_eq!(profile.vault_name.as_deref(), Some("Work"));
+        assert_eq!(profile.ssh_key_title.as_deref(), Some("Work SSH Key"));
+        assert_eq!(profile.signing_key.as_deref(), Some("ssh-ed25519 AAAAC3Nza work"));
+        assert!(profile.one_password);
+    }
+
+    #[test]
+    fn test_import_requires_sign_in() {
+        let (mut screen, _) = create_test_screen(MockOnePasswordWrapper::new().with_authenticated(false));
+
+        settle(&mut screen);
+        assert_eq!(screen.step(), ImportStep::Authenticate);
+        assert!(screen.error.as_deref().is_some_and(|error| error.contains("op signin")));
+
+        // Lists stay unreachable until the check passes
+        screen.handle_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(screen.step(), ImportStep::Authenticate);
+        assert_eq!(screen.handle_event(key(KeyCode::Char('r'))).unwrap(), ComponentAction::Refresh);
+        assert!(screen.progress.is_some());
+        assert_eq!(screen.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::None);
+        settle(&mut screen);
+        assert_eq!(screen.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::NavigateBack);
+    }
+
+    #[test]
+    fn test_name_from_title() {
+        assert_eq!(ProfileImportScreen::name_from_title("Work SSH Key"), "work-ssh-key");
+        assert_eq!(ProfileImportScreen::name_from_title("  GitHub (personal) "), "github-personal");
+        assert_eq!(ProfileImportScreen::name_from_title("ci_deploy"), "ci_deploy");
+    }
+}
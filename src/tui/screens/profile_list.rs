@@ -1,17 +1,22 @@
 //! Profile list screen for the TUI application.
 //!
 //! This module provides a comprehensive profile management interface
-//! with search, filtering, and profile operations.
+//! with search, filtering, and profile operations. While the screen is shown,
+//! changes made to the profiles elsewhere refresh the list automatically.
 
 use crate::{
     error::Result,
     tui::{
         Component, ComponentAction, Event, Theme,
+        components::table::{TableColumn, TableComponent, TableRow},
         screens::{Screen, ScreenType},
         widgets::input::{InputWidget, InputState},
+        widgets::list::{IndicatorType, ListItem as IndicatorItem},
     },
     config::types::Profile,
-    profile::ProfileManager,
+    detection::TwoPhaseSetup,
+    external::git::{GitConfigScope, GitWrapper},
+    profile::{validator::ProfileValidator, watch::WatchHandle, ProfileManager},
     matching::{MatchResult, ProfileFuzzyMatcher, FuzzyMatcher},
 };
 use ratatui::{
@@ -23,7 +28,10 @@ use ratatui::{
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::any::Any;
+use std::collections::VecDeque;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Sorting fields for profile list
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -68,6 +76,22 @@ pub struct ProfileListScreen {
     fuzzy_matcher: ProfileFuzzyMatcher,
     /// Whether to show help
     show_help: bool,
+    /// Detected global-then-local setup of the current repository
+    two_phase: Option<TwoPhaseSetup>,
+    /// `user.email` of the current repository's local config
+    applied_email: Option<String>,
+    /// Name of the default profile
+    default_profile: Option<String>,
+    /// Whether archived profiles are listed
+    show_archived: bool,
+    /// Only profiles with this tag are listed
+    tag_filter: Option<String>,
+    /// File the search history is persisted to
+    search_history_path: Option<PathBuf>,
+    /// Set by the profile watch when the profiles changed since the last refresh
+    profiles_changed: Arc<AtomicBool>,
+    /// Profile watch running while the screen is shown
+    watch: Option<WatchHandle>,
 }
 
 impl ProfileListScreen {
@@ -89,9 +113,76 @@ impl ProfileListScreen {
             profile_manager,
             fuzzy_matcher: ProfileFuzzyMatcher::new(),
             show_help: false,
+            two_phase: None,
+            applied_email: None,
+            default_profile: None,
+            show_archived: false,
+            tag_filter: None,
+            search_history_path: None,
+            profiles_changed: Arc::new(AtomicBool::new(false)),
+            watch: None,
         })
     }
 
+    /// Recall and persist search queries in `path`.
+    ///
+    /// Without it, the history only lasts for the session. A missing or
+    /// unreadable history file starts an empty history.
+    pub fn with_search_history(mut self, path: PathBuf) -> Self {
+        let _ = self.search_input.load_history(&path);
+        self.search_history_path = Some(path);
+        self
+    }
+
+    /// Mark the profile whose email is set in the local git config as applied
+    pub fn with_applied_profile_from(mut self, git: &dyn GitWrapper) -> Self {
+        // Outside a repository there is simply no applied profile
+        self.applied_email = git
+            .get_config("user.email", Some(GitConfigScope::Local))
+            .ok()
+            .flatten();
+        self
+    }
+
+    /// Mark the named profile as the default
+    pub fn with_default_profile(mut self, name: Option<String>) -> Self {
+        self.default_profile = name;
+        self
+    }
+
+    /// Status indicator of a profile.
+    ///
+    /// Validation problems take precedence over the applied and default markers.
+    pub fn indicator(&self, profile: &Profile) -> Option<IndicatorType> {
+        if !ProfileValidator::validate(profile).is_empty() {
+            Some(IndicatorType::Warning)
+        } else if self.applied_email.as_deref() == Some(profile.git_user_email.as_str()) {
+            Some(IndicatorType::Check)
+        } else if self.default_profile.as_deref() == Some(profile.name.as_str()) {
+            Some(IndicatorType::Star)
+        } else {
+            None
+        }
+    }
+
+    /// Mark the profiles of a detected global-then-local setup as partially applied
+    pub fn with_two_phase_setup(mut self, setup: Option<TwoPhaseSetup>) -> Self {
+        self.two_phase = setup;
+        self
+    }
+
+    /// Scope a profile is partially applied with, if it is part of a global-then-local setup
+    pub fn partial_scope(&self, profile_name: &str) -> Option<&'static str> {
+        let setup = self.two_phase.as_ref()?;
+        if setup.global_profile == profile_name {
+            Some("global")
+        } else if setup.local_profile == profile_name {
+            Some("local")
+        } else {
+            None
+        }
+    }
+
     /// Get the currently selected profile
     pub fn selected_profile(&self) -> Option<&Profile> {
         self.filtered_profiles.get(self.selected_index)
@@ -119,11 +210,55 @@ impl ProfileListScreen {
 
     /// Refresh the profile list
     pub fn refresh(&mut self) -> Result<()> {
-        self.profiles = self.profile_manager.list()?;
+        self.profiles = if self.show_archived {
+            self.profile_manager.list_all()?
+        } else {
+            self.profile_manager.list()?
+        };
         self.apply_filter_and_sort();
         Ok(())
     }
 
+    /// Check if archived profiles are listed
+    pub fn is_showing_archived(&self) -> bool {
+        self.show_archived
+    }
+
+    /// Show or hide archived profiles
+    pub fn toggle_show_archived(&mut self) -> Result<()> {
+        self.show_archived = !self.show_archived;
+        self.refresh()
+    }
+
+    /// Get the tag profiles are filtered by
+    pub fn tag_filter(&self) -> Option<&str> {
+        self.tag_filter.as_deref()
+    }
+
+    /// List only the profiles tagged `tag`, or all profiles for `None`
+    pub fn set_tag_filter(&mut self, tag: Option<String>) {
+        self.tag_filter = tag;
+        self.apply_filter_and_sort();
+    }
+
+    /// Tags of the loaded profiles, sorted and without duplicates
+    pub fn available_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.profiles.iter().flat_map(|profile| profile.tags.clone()).collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Filter by the next tag, going back to all profiles after the last one
+    pub fn cycle_tag_filter(&mut self) {
+        let tags = self.available_tags();
+        let next = match &self.tag_filter {
+            None => tags.first().cloned(),
+            Some(current) => tags.iter().find(|tag| *tag > current).cloned(),
+        };
+        self.set_tag_filter(next);
+    }
+
     /// Navigate to the previous profile
     pub fn previous_profile(&mut self) {
         if self.selected_index > 0 {
@@ -145,14 +280,35 @@ impl ProfileListScreen {
     /// Start search mode
     pub fn start_search(&mut self) {
         self.search_active = true;
-        self.search_input = InputState::new();
-        self.search_input.content = self.search_query.clone();
-        self.search_input.cursor_position = self.search_query.len();
+        self.search_input.history_cursor = None;
+        self.search_input.set_content(self.search_query.clone());
     }
 
-    /// Stop search mode
+    /// Stop search mode, recording the query in the search history
     pub fn stop_search(&mut self) {
         self.search_active = false;
+        if self.search_input.push_history()
+            && let Some(path) = &self.search_history_path
+        {
+            // Losing the history is not worth interrupting the search for
+            let _ = self.search_input.save_history(path);
+        }
+    }
+
+    /// Search history, oldest first
+    pub fn search_history(&self) -> &VecDeque<String> {
+        &self.search_input.history
+    }
+
+    /// Replace the search query with the previous (Up) or next (Down) history entry
+    fn browse_search_history(&mut self, previous: bool) {
+        if previous {
+            self.search_input.history_previous();
+        } else {
+            self.search_input.history_next();
+        }
+        self.search_query = self.search_input.content.clone();
+        self.apply_filter_and_sort();
     }
 
     /// Handle search input
@@ -197,11 +353,23 @@ impl ProfileListScreen {
 
     /// Apply filter and sort to profiles
     fn apply_filter_and_sort(&mut self) {
+        // Apply tag filter, then search the tagged profiles so the search
+        // result limit only counts profiles that can be shown
+        let tagged: Vec<Profile> = match &self.tag_filter {
+            Some(tag) => self
+                .profiles
+                .iter()
+                .filter(|profile| profile.tags.contains(tag))
+                .cloned()
+                .collect(),
+            None => self.profiles.clone(),
+        };
+
         // Apply search filter
         if self.search_query.is_empty() {
-            self.filtered_profiles = self.profiles.clone();
+            self.filtered_profiles = tagged;
         } else {
-            let matches = self.fuzzy_matcher.find_matches(&self.search_query, &self.profiles);
+            let matches = self.fuzzy_matcher.find_matches(&self.search_query, &tagged);
             self.filtered_profiles = matches.into_iter().map(|m| m.profile).collect();
         }
 
@@ -349,7 +517,7 @@ impl ProfileListScreen {
 
     /// Render sort info
     fn render_sort_info(&self, frame: &mut Frame, area: Rect) -> Result<()> {
-        let sort_text = format!(
+        let mut sort_text = format!(
             "Sort by: {:?} {} | {} profile(s)",
             self.sort_field,
             match self.sort_direction {
@@ -358,6 +526,9 @@ impl ProfileListScreen {
             },
             self.filtered_profiles.len()
         );
+        if let Some(tag) = &self.tag_filter {
+            sort_text.push_str(&format!(" | Tag: {}", tag));
+        }
 
         let sort_widget = Paragraph::new(sort_text)
             .style(self.theme.styles.info)
@@ -404,19 +575,35 @@ impl ProfileListScreen {
             .height(1)
             .bottom_margin(1);
 
+        let indicators: Vec<Option<IndicatorType>> =
+            self.filtered_profiles.iter().map(|profile| self.indicator(profile)).collect();
+        let show_indicators = indicators.iter().any(Option::is_some);
+
         // Create rows
         let rows: Vec<Row> = self.filtered_profiles
             .iter()
+            .zip(indicators)
             .enumerate()
-            .map(|(index, profile)| {
+            .map(|(index, (profile, indicator))| {
                 let style = if index == self.selected_index {
                     self.theme.styles.selected
+                } else if profile.is_archived {
+                    self.theme.styles.base.fg(self.theme.colors.muted)
                 } else {
                     self.theme.styles.base
                 };
 
+                let mut name = IndicatorItem::new(match self.partial_scope(&profile.name) {
+                    Some(scope) => format!("{} (partial: {})", profile.name, scope),
+                    None if profile.is_archived => format!("{} (archived)", profile.name),
+                    None => profile.name.clone(),
+                });
+                if let Some(indicator) = indicator {
+                    name = name.with_indicator(indicator);
+                }
+
                 let cells = vec![
-                    Cell::from(profile.name.as_str()),
+                    Cell::from(name.content(show_indicators)),
                     Cell::from(profile.git_user_email.as_str()),
                     Cell::from(format!("{:?}", profile.key_type)),
                     Cell::from(profile.scope.as_ref().map(|s| format!("{:?}", s)).unwrap_or_default()),
@@ -449,6 +636,32 @@ impl ProfileListScreen {
         Ok(())
     }
 
+    /// Table of the listed profiles, in their current order, for exporting
+    pub fn export_table(&self) -> TableComponent {
+        let mut table = TableComponent::new("Profiles");
+        table.set_columns(
+            ["Name", "Email", "Key Type", "Scope", "1Password"]
+                .iter()
+                .map(|title| TableColumn::new(title, Constraint::Percentage(20)))
+                .collect(),
+        );
+        table.set_rows(
+            self.filtered_profiles
+                .iter()
+                .map(|profile| {
+                    TableRow::new(vec![
+                        profile.name.clone(),
+                        profile.git_user_email.clone(),
+                        format!("{:?}", profile.key_type),
+                        profile.scope.as_ref().map(|s| format!("{:?}", s)).unwrap_or_default(),
+                        if profile.one_password { "Yes" } else { "No" }.to_string(),
+                    ])
+                })
+                .collect(),
+        );
+        table
+    }
+
     /// Render status bar
     fn render_status_bar(&self, frame: &mut Frame, area: Rect) -> Result<()> {
         let status_text = if self.filtered_profiles.is_empty() {
@@ -480,9 +693,11 @@ impl ProfileListScreen {
     fn render_help(&self, frame: &mut Frame, area: Rect) -> Result<()> {
         let help_text = vec![
             "Navigation: ↑/↓ or j/k to move, Enter/v to view, e to edit",
-            "Actions: n to create, d to delete, a to apply, r to refresh",
-            "Search: / to search, Esc to clear/exit search",
-            "Sort: s to cycle sort field, S to reverse direction",
+            "Actions: n to create, d to delete, a to apply, R to refresh",
+            "History: u to undo, r to redo",
+            "Export: Ctrl+E to copy the list as CSV, Ctrl+H to show/hide archived",
+            "Search: / to search, ↑/↓ to recall previous searches, Esc to clear/exit search",
+            "Sort: s to cycle sort field, S to reverse direction, t to filter by tag",
             "Other: ? to toggle help, q to quit",
         ].join("\n");
 
@@ -525,6 +740,10 @@ impl Component for ProfileListScreen {
                     self.handle_normal_event(key_event)
                 }
             }
+            Event::Tick if self.profiles_changed.swap(false, Ordering::Relaxed) => {
+                self.refresh()?;
+                Ok(ComponentAction::Refresh)
+            }
             _ => Ok(ComponentAction::None),
         }
     }
@@ -566,12 +785,28 @@ impl ProfileListScreen {
                 self.search_input.move_cursor_to_end();
                 Ok(ComponentAction::None)
             }
+            KeyCode::Up => {
+                self.browse_search_history(true);
+                Ok(ComponentAction::None)
+            }
+            KeyCode::Down => {
+                self.browse_search_history(false);
+                Ok(ComponentAction::None)
+            }
             _ => Ok(ComponentAction::None),
         }
     }
 
     /// Handle events in normal mode
     fn handle_normal_event(&mut self, key_event: KeyEvent) -> Result<ComponentAction> {
+        if key_event.code == KeyCode::Char('e') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return Ok(ComponentAction::ExportData(self.export_table().export_to_csv()));
+        }
+        if key_event.code == KeyCode::Char('h') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            self.toggle_show_archived()?;
+            return Ok(ComponentAction::Refresh);
+        }
+
         match key_event.code {
             KeyCode::Up | KeyCode::Char('k') => {
                 self.previous_profile();
@@ -621,7 +856,21 @@ impl ProfileListScreen {
                 self.apply_filter_and_sort();
                 Ok(ComponentAction::None)
             }
+            KeyCode::Char('t') => {
+                self.cycle_tag_filter();
+                Ok(ComponentAction::None)
+            }
+            KeyCode::Char('u') => {
+                self.profile_manager.undo()?;
+                self.refresh()?;
+                Ok(ComponentAction::Refresh)
+            }
             KeyCode::Char('r') => {
+                self.profile_manager.redo()?;
+                self.refresh()?;
+                Ok(ComponentAction::Refresh)
+            }
+            KeyCode::Char('R') => {
                 self.refresh()?;
                 Ok(ComponentAction::Refresh)
             }
@@ -655,9 +904,22 @@ impl Screen for ProfileListScreen {
     }
 
     fn on_screen_enter(&mut self) -> Result<()> {
+        if self.watch.is_none() {
+            let changed = self.profiles_changed.clone();
+            self.watch = Some(
+                self.profile_manager
+                    .watch(Box::new(move |_| changed.store(true, Ordering::Relaxed)))?,
+            );
+        }
         self.refresh()
     }
 
+    fn on_screen_exit(&mut self) -> Result<()> {
+        // Dropping the handle stops the watch
+        self.watch = None;
+        Ok(())
+    }
+
     fn screen_help(&self) -> Vec<(&str, &str)> {
         vec![
             ("↑/↓ or j/k", "Navigate profiles"),
@@ -669,7 +931,11 @@ impl Screen for ProfileListScreen {
             ("/", "Search profiles"),
             ("s", "Change sort field"),
             ("S", "Reverse sort direction"),
-            ("r", "Refresh"),
+            ("t", "Filter by the next tag"),
+            ("u", "Undo last change"),
+            ("r", "Redo last undone change"),
+            ("R", "Refresh"),
+            ("Ctrl+E", "Export list as CSV"),
             ("?", "Toggle help"),
             ("Esc", "Clear search or go back"),
             ("q", "Quit"),
@@ -686,51 +952,10 @@ mod tests {
     use super::*;
     use crate::{
         config::types::{KeyType, Scope},
-        profile::ProfileManager,
+        profile::{mock::MockProfileManager, ProfileManager},
     };
     use std::sync::Arc;
 
-    /// Mock profile manager for testing
-    struct MockProfileManager {
-        profiles: Vec<Profile>,
-    }
-
-    impl MockProfileManager {
-        fn new(profiles: Vec<Profile>) -> Self {
-            Self { profiles }
-        }
-
-        fn empty() -> Self {
-            Self { profiles: vec![] }
-        }
-    }
-
-    impl ProfileManager for MockProfileManager {
-        fn create(&self, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn read(&self, name: &str) -> Result<Option<Profile>> {
-            Ok(self.profiles.iter().find(|p| p.name == name).cloned())
-        }
-
-        fn update(&self, _name: &str, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn delete(&self, _name: &str) -> Result<()> {
-            Ok(())
-        }
-
-        fn list(&self) -> Result<Vec<Profile>> {
-            Ok(self.profiles.clone())
-        }
-
-        fn exists(&self, name: &str) -> Result<bool> {
-            Ok(self.profiles.iter().any(|p| p.name == name))
-        }
-    }
-
     fn create_test_profile(name: &str, email: &str) -> Profile {
         Profile {
             name: name.to_string(),
@@ -749,17 +974,32 @@ mod tests {
             include_if_dirs: vec![],
             host_patterns: vec![],
             one_password: false,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: Default::default(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
-    fn create_test_screen() -> ProfileListScreen {
+    fn create_test_manager() -> Arc<MockProfileManager> {
         let profiles = vec![
             create_test_profile("work", "work@example.com"),
             create_test_profile("personal", "personal@example.com"),
             create_test_profile("opensource", "opensource@example.com"),
         ];
-        let manager = Arc::new(MockProfileManager::new(profiles));
-        ProfileListScreen::new(Theme::default(), manager).unwrap()
+        let order = profiles.iter().map(|p| p.name.clone()).collect();
+        let manager = Arc::new(MockProfileManager::with_profiles(profiles).track_calls(true));
+        manager.reorder(order).unwrap();
+        manager
+    }
+
+    fn create_test_screen() -> ProfileListScreen {
+        ProfileListScreen::new(Theme::default(), create_test_manager()).unwrap()
     }
 
     #[test]
@@ -774,9 +1014,44 @@ mod tests {
         assert!(!screen.is_search_active());
     }
 
+    #[test]
+    fn test_profile_list_partial_scope() {
+        let screen = create_test_screen().with_two_phase_setup(Some(TwoPhaseSetup {
+            global_profile: "personal".to_string(),
+            local_profile: "work".to_string(),
+            local_keys: vec!["user.signingkey".to_string()],
+        }));
+
+        assert_eq!(screen.partial_scope("personal"), Some("global"));
+        assert_eq!(screen.partial_scope("work"), Some("local"));
+        assert_eq!(screen.partial_scope("opensource"), None);
+        assert_eq!(create_test_screen().partial_scope("work"), None);
+    }
+
+    #[test]
+    fn test_profile_list_indicators() {
+        let mut local = std::collections::HashMap::new();
+        local.insert("user.email".to_string(), "work@example.com".to_string());
+        let git = crate::external::git::MockGitWrapper::new()
+            .with_scoped_config(GitConfigScope::Local, local);
+
+        let screen = create_test_screen()
+            .with_applied_profile_from(&git)
+            .with_default_profile(Some("personal".to_string()));
+        let profiles = screen.profiles.clone();
+        let indicator = |name: &str| screen.indicator(profiles.iter().find(|p| p.name == name).unwrap());
+
+        assert_eq!(indicator("work"), Some(IndicatorType::Check));
+        assert_eq!(indicator("personal"), Some(IndicatorType::Star));
+        assert_eq!(indicator("opensource"), None);
+
+        let invalid = create_test_profile("bad name", "not-an-email");
+        assert_eq!(screen.indicator(&invalid), Some(IndicatorType::Warning));
+    }
+
     #[test]
     fn test_profile_list_screen_empty() {
-        let manager = Arc::new(MockProfileManager::empty());
+        let manager = Arc::new(MockProfileManager::new());
         let screen = ProfileListScreen::new(Theme::default(), manager).unwrap();
         
         assert_eq!(screen.profile_count(), 0);
@@ -842,6 +1117,45 @@ mod tests {
         assert!(!screen.is_search_active());
     }
 
+    #[test]
+    fn test_tag_filter() {
+        let tagged = |name: &str, tags: &[&str]| Profile {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..create_test_profile(name, &format!("{}@example.com", name))
+        };
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![
+            tagged("work", &["client-a", "office"]),
+            tagged("client", &["client-a"]),
+            tagged("personal", &[]),
+        ]));
+        let mut screen = ProfileListScreen::new(Theme::default(), manager).unwrap();
+        assert_eq!(screen.available_tags(), vec!["client-a".to_string(), "office".to_string()]);
+
+        let key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::empty());
+        screen.handle_event(Event::Key(key)).unwrap();
+        assert_eq!(screen.tag_filter(), Some("client-a"));
+        assert_eq!(screen.profile_count(), 2);
+
+        screen.handle_event(Event::Key(key)).unwrap();
+        assert_eq!(screen.tag_filter(), Some("office"));
+        assert_eq!(screen.selected_profile().unwrap().name, "work");
+
+        // The search narrows the tagged profiles further
+        screen.set_tag_filter(Some("client-a".to_string()));
+        screen.start_search();
+        screen.handle_search_input('c');
+        screen.handle_search_input('l');
+        assert_eq!(screen.profile_count(), 1);
+        assert_eq!(screen.selected_profile().unwrap().name, "client");
+        screen.stop_search();
+        screen.clear_search();
+
+        screen.handle_event(Event::Key(key)).unwrap();
+        screen.handle_event(Event::Key(key)).unwrap();
+        assert_eq!(screen.tag_filter(), None);
+        assert_eq!(screen.profile_count(), 3);
+    }
+
     #[test]
     fn test_sorting() {
         let mut screen = create_test_screen();
@@ -925,6 +1239,76 @@ mod tests {
         assert!(!screen.is_search_active());
     }
 
+    #[test]
+    fn test_ctrl_e_exports_filtered_profiles() {
+        let mut screen = create_test_screen();
+        for ch in "o".chars() {
+            screen.handle_search_input(ch);
+        }
+        screen.set_sort_field(SortField::Name);
+
+        let export = KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL);
+        let ComponentAction::ExportData(csv) = screen.handle_normal_event(export).unwrap() else {
+            panic!("Expected ExportData action");
+        };
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Name,Email,Key Type,Scope,1Password");
+        assert_eq!(lines.len(), 1 + screen.profile_count());
+        assert!(csv.contains("work,work@example.com,Ssh,Local,No"));
+    }
+
+    #[test]
+    fn test_search_history_recall() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("search-history.json");
+        let mut screen = create_test_screen().with_search_history(path.clone());
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::empty());
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::empty());
+
+        screen.start_search();
+        for ch in "work".chars() {
+            screen.handle_search_input(ch);
+        }
+        screen.handle_search_event(enter).unwrap();
+        assert_eq!(screen.search_history(), &VecDeque::from(["work".to_string()]));
+
+        screen.clear_search();
+        screen.start_search();
+        screen.handle_search_input('p');
+        screen.handle_search_event(up).unwrap();
+        assert_eq!(screen.search_query(), "work");
+        screen.handle_search_event(down).unwrap();
+        assert_eq!(screen.search_query(), "p");
+
+        // The history is restored by a new screen
+        let restored = create_test_screen().with_search_history(path);
+        assert_eq!(restored.search_history().len(), 1);
+    }
+
+    #[test]
+    fn test_ctrl_h_toggles_archived_profiles() {
+        let profiles = vec![
+            create_test_profile("work", "work@example.com"),
+            Profile {
+                is_archived: true,
+                description: None,
+                ..create_test_profile("old", "old@example.com")
+            },
+        ];
+        let mut screen = ProfileListScreen::new(Theme::default(), Arc::new(MockProfileManager::with_profiles(profiles))).unwrap();
+        assert_eq!(screen.profile_count(), 1);
+
+        let toggle = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL);
+        assert_eq!(screen.handle_normal_event(toggle).unwrap(), ComponentAction::Refresh);
+        assert!(screen.is_showing_archived());
+        assert_eq!(screen.profile_count(), 2);
+
+        screen.handle_normal_event(toggle).unwrap();
+        assert_eq!(screen.profile_count(), 1);
+    }
+
     #[test]
     fn test_normal_event_handling() {
         let mut screen = create_test_screen();
@@ -1000,7 +1384,7 @@ mod tests {
 
     #[test]
     fn test_empty_list_actions() {
-        let manager = Arc::new(MockProfileManager::empty());
+        let manager = Arc::new(MockProfileManager::new());
         let screen = ProfileListScreen::new(Theme::default(), manager).unwrap();
         
         // Test actions on empty list
@@ -1037,15 +1421,51 @@ mod tests {
 
     #[test]
     fn test_refresh_functionality() {
-        let mut screen = create_test_screen();
+        let manager = create_test_manager();
+        let mut screen = ProfileListScreen::new(Theme::default(), manager.clone()).unwrap();
         
         // Initial state
         assert_eq!(screen.profile_count(), 3);
+        assert_eq!(manager.list_call_count(), 1);
         
         // Refresh should work
         let result = screen.refresh();
         assert!(result.is_ok());
         assert_eq!(screen.profile_count(), 3);
+        assert_eq!(manager.list_call_count(), 2);
+    }
+
+    #[test]
+    fn test_refreshes_on_watched_change() {
+        use crate::profile::{manager::ProfileManagerImpl, watch::WATCH_POLL_INTERVAL};
+
+        let manager = ProfileManagerImpl::new();
+        let mut screen = ProfileListScreen::new(Theme::default(), Arc::new(manager.clone())).unwrap();
+        screen.on_screen_enter().unwrap();
+        assert_eq!(screen.handle_event(Event::Tick).unwrap(), ComponentAction::None);
+
+        manager.create(create_test_profile("work", "work@example.com")).unwrap();
+        std::thread::sleep(WATCH_POLL_INTERVAL * 3);
+        assert_eq!(screen.handle_event(Event::Tick).unwrap(), ComponentAction::Refresh);
+        assert_eq!(screen.profile_count(), 1);
+
+        screen.on_screen_exit().unwrap();
+        assert!(screen.watch.is_none());
+    }
+
+    #[test]
+    fn test_undo_redo_keys() {
+        let mut screen = create_test_screen();
+
+        for key in ['u', 'r', 'R'] {
+            let event = Event::Key(KeyEvent::new(KeyCode::Char(key), KeyModifiers::empty()));
+            let action = screen.handle_event(event).unwrap();
+            assert!(matches!(action, ComponentAction::Refresh));
+        }
+
+        let help = screen.screen_help();
+        assert!(help.iter().any(|(key, desc)| *key == "u" && desc.contains("Undo")));
+        assert!(help.iter().any(|(key, desc)| *key == "r" && desc.contains("Redo")));
     }
 
     #[test]
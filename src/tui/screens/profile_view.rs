@@ -1,25 +1,55 @@
 //! Profile view screen for the TUI application.
 //!
-//! This module provides a read-only view of a profile's details.
+//! This module provides a read-only view of a profile's details next to the
+//! values git currently has for them, highlighting fields that differ. Below
+//! the details, the changes applying the profile would make are shown as a diff.
 
 use crate::{
     error::Result,
     tui::{
         Component, ComponentAction, Event, Theme,
+        clipboard::ClipboardHelper,
+        components::{TableColumn, TableComponent, TableRow},
         screens::{Screen, ScreenType},
     },
-    config::types::Profile,
-    profile::ProfileManager,
+    config::types::{KeyType, Profile},
+    external::git::{GitConfigScope, GitWrapper},
+    profile::{
+        ProfileManager,
+        diff::{DiffLine, DiffStatus, ProfileDiff},
+    },
 };
 use ratatui::{
     Frame,
     layout::{Rect, Layout, Direction, Constraint, Alignment},
-    widgets::{Block, Borders, Paragraph, List, ListItem},
     text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the git config is re-read to pick up changes made outside the TUI
+const GIT_CONFIG_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// One row of the profile view: a profile field and the matching git config value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileFieldRow {
+    pub field: &'static str,
+    pub profile_value: Option<String>,
+    /// Git config key the field is applied to, if any
+    pub git_key: Option<&'static str>,
+    pub git_value: Option<String>,
+}
+
+impl ProfileFieldRow {
+    /// Whether git has a different value than the profile for this field
+    pub fn differs(&self) -> bool {
+        self.git_key.is_some() && self.profile_value != self.git_value
+    }
+}
 
 /// Profile view screen
 pub struct ProfileViewScreen {
@@ -27,19 +57,68 @@ pub struct ProfileViewScreen {
     profile: Option<Profile>,
     theme: Theme,
     profile_manager: Arc<dyn ProfileManager>,
+    git_wrapper: Option<Arc<dyn GitWrapper + Send + Sync>>,
+    /// Git config at the profile's effective scope, once fetched
+    git_config: Option<HashMap<String, String>>,
+    /// Changes applying the profile would make to the git config
+    diff: Vec<DiffLine>,
+    table: TableComponent,
+    last_refresh: Instant,
+    /// Whether the profile would be applied inside a git work tree
+    in_repo: bool,
+    /// Clipboard field values are copied to
+    clipboard: ClipboardHelper,
 }
 
 impl ProfileViewScreen {
     /// Create a new profile view screen
     pub fn new(profile_name: String, theme: Theme, profile_manager: Arc<dyn ProfileManager>) -> Result<Self> {
         let profile = profile_manager.read(&profile_name)?;
-        
-        Ok(Self {
+
+        let mut table = TableComponent::new("Profile Details");
+        table.set_columns(vec![
+            TableColumn::new("Field", Constraint::Length(18)),
+            TableColumn::new("Profile", Constraint::Percentage(40)),
+            TableColumn::new("Git Config", Constraint::Percentage(40)),
+        ]);
+
+        let mut screen = Self {
             profile_name,
             profile,
             theme,
             profile_manager,
-        })
+            git_wrapper: None,
+            git_config: None,
+            diff: Vec::new(),
+            table,
+            last_refresh: Instant::now(),
+            in_repo: false,
+            clipboard: ClipboardHelper::new(),
+        };
+        screen.update_table();
+        Ok(screen)
+    }
+
+    /// Compare the profile with the git config read through `git`
+    ///
+    /// The git config is fetched by [`ProfileViewScreen::refresh`], which runs
+    /// when the screen is entered.
+    pub fn with_git_wrapper(mut self, git: Arc<dyn GitWrapper + Send + Sync>) -> Self {
+        self.git_wrapper = Some(git);
+        self
+    }
+
+    /// Resolve the effective scope as if applying inside (or outside) a git work tree
+    pub fn with_in_repo(mut self, in_repo: bool) -> Self {
+        self.in_repo = in_repo;
+        self.update_table();
+        self
+    }
+
+    /// Copy field values to `clipboard` instead of the system clipboard
+    pub fn with_clipboard(mut self, clipboard: ClipboardHelper) -> Self {
+        self.clipboard = clipboard;
+        self
     }
 
     /// Get the profile name
@@ -52,21 +131,152 @@ impl ProfileViewScreen {
         self.profile.as_ref()
     }
 
-    /// Refresh the profile data
+    /// Refresh the profile data and the git config it is compared with
     pub fn refresh(&mut self) -> Result<()> {
         self.profile = self.profile_manager.read(&self.profile_name)?;
+        (self.git_config, self.diff) = match (&self.profile, &self.git_wrapper) {
+            (Some(profile), Some(git)) => {
+                let scope = GitConfigScope::from(profile.effective_scope(self.in_repo));
+                let diff = ProfileDiff::compute(profile, git.as_ref(), scope.clone())?;
+                (Some(git.get_all_config(Some(scope))?), diff)
+            }
+            _ => (None, Vec::new()),
+        };
+        self.last_refresh = Instant::now();
+        self.update_table();
         Ok(())
     }
 
+    /// The rows shown for `profile`, with git values once the config is fetched
+    pub fn field_rows(&self, profile: &Profile) -> Vec<ProfileFieldRow> {
+        let list = |values: &[String]| Some(if values.is_empty() { "None".to_string() } else { values.join(", ") });
+        let signing_format = match profile.key_type {
+            KeyType::Ssh => "ssh",
+            KeyType::Gpg => "openpgp",
+            KeyType::X509 | KeyType::Gitsign => "x509",
+        };
+
+        let rows = [
+            ("Name", Some(profile.name.clone()), None),
+            ("Git User Name", profile.git_user_name.clone(), Some("user.name")),
+            ("Git User Email", Some(profile.git_user_email.clone()), Some("user.email")),
+            ("Key Type", Some(format!("{:?}", profile.key_type)), None),
+            ("Signing Format", Some(signing_format.to_string()), Some("gpg.format")),
+            ("Signing Key", profile.signing_key.clone(), Some("user.signingkey")),
+            ("Vault Name", profile.vault_name.clone(), None),
+            ("SSH Key Title", profile.ssh_key_title.clone(), None),
+            ("Scope", profile.scope.as_ref().map(|s| format!("{:?}", s)), None),
+            ("Effective Scope", Some(self.effective_scope_label(profile)), None),
+            ("Field-level Scopes", Some(self.field_scopes_label(profile)), None),
+            ("1Password", Some(if profile.one_password { "Yes" } else { "No" }.to_string()), None),
+            ("Match Patterns", list(&profile.match_patterns), None),
+            ("Repositories", list(&profile.repos), None),
+            ("Include If Dirs", list(&profile.include_if_dirs), None),
+            ("Host Patterns", list(&profile.host_patterns), None),
+        ];
+
+        rows.into_iter()
+            .map(|(field, profile_value, git_key)| {
+                // Keys without a git config to compare with are never highlighted
+                let git_key = git_key.filter(|_| self.git_config.is_some());
+                let git_value = git_key.and_then(|key| self.git_value(key));
+                ProfileFieldRow { field, profile_value, git_key, git_value }
+            })
+            .collect()
+    }
+
+    /// Value of `key` in the fetched git config; `git config --list` lowercases key names
+    fn git_value(&self, key: &str) -> Option<String> {
+        let config = self.git_config.as_ref()?;
+        config
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Rebuild the table rows from the profile and git config
+    fn update_table(&mut self) {
+        let rows = match &self.profile {
+            Some(profile) => self.field_rows(profile),
+            None => Vec::new(),
+        };
+        let not_set = |value: &Option<String>| value.clone().unwrap_or_else(|| "Not set".to_string());
+        let table_rows = rows
+            .iter()
+            .map(|row| {
+                let git_cell = if row.git_key.is_some() { not_set(&row.git_value) } else { String::new() };
+                TableRow::new(vec![row.field.to_string(), not_set(&row.profile_value), git_cell])
+                    .with_highlight(row.differs())
+            })
+            .collect();
+
+        self.table.set_rows(table_rows);
+        if self.table.state().selected.is_none() {
+            self.table.state_mut().select_first();
+        }
+    }
+
+    /// Changes applying the profile would make, once the git config is fetched
+    pub fn diff_lines(&self) -> &[DiffLine] {
+        &self.diff
+    }
+
+    /// The diff as `-`/`+` lines, with unchanged values dimmed
+    fn diff_text(&self) -> Vec<Line<'static>> {
+        let entry = |field: &str, value: &Option<String>| format!("{} = {}", field, value.as_deref().unwrap_or_default());
+        let mut lines = Vec::new();
+        for line in &self.diff {
+            let removed = Span::styled(format!("- {}", entry(&line.field, &line.current)), self.theme.styles.error);
+            let added = Span::styled(format!("+ {}", entry(&line.field, &line.new)), self.theme.styles.success);
+            match line.status {
+                DiffStatus::Unchanged => lines.push(Line::from(Span::styled(
+                    format!("  {}", entry(&line.field, &line.current)),
+                    self.theme.styles.help,
+                ))),
+                DiffStatus::Added => lines.push(Line::from(added)),
+                DiffStatus::Removed => lines.push(Line::from(removed)),
+                DiffStatus::Changed => {
+                    lines.push(Line::from(removed));
+                    lines.push(Line::from(added));
+                }
+            }
+        }
+        lines
+    }
+
+    /// The field row currently selected in the table
+    pub fn selected_field(&self) -> Option<ProfileFieldRow> {
+        let profile = self.profile.as_ref()?;
+        let index = self.table.state().selected_row_index()?;
+        self.field_rows(profile).into_iter().nth(index)
+    }
+
+    /// Copy the profile value of the selected field to the clipboard
+    fn copy_selected_field(&self) -> Result<ComponentAction> {
+        let Some(row) = self.selected_field() else {
+            return Ok(ComponentAction::None);
+        };
+        match &row.profile_value {
+            Some(value) => {
+                self.clipboard.set(value)?;
+                Ok(ComponentAction::ShowPopup(format!("Copied {} to clipboard", row.field)))
+            }
+            None => Ok(ComponentAction::ShowPopup(format!("{} is not set", row.field))),
+        }
+    }
+
     /// Render the profile view
     fn render_profile_view(&self, frame: &mut Frame, area: Rect) -> Result<()> {
         if let Some(profile) = &self.profile {
+            let diff_text = self.diff_text();
+            let diff_height = if diff_text.is_empty() { 0 } else { diff_text.len() as u16 + 2 };
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(3),  // Title
-                    Constraint::Min(0),     // Profile details
-                    Constraint::Length(3),  // Help
+                    Constraint::Length(3),            // Title
+                    Constraint::Min(0),               // Profile details
+                    Constraint::Length(diff_height),  // Diff from the applied config
+                    Constraint::Length(3),            // Help
                 ])
                 .split(area);
 
@@ -81,11 +291,22 @@ impl ProfileViewScreen {
                 );
             frame.render_widget(title, chunks[0]);
 
-            // Render profile details
-            self.render_profile_details(frame, chunks[1], profile)?;
+            // Render profile details next to the git config
+            self.table.render(frame, chunks[1], &self.theme)?;
+
+            // Render what applying the profile would change
+            if !diff_text.is_empty() {
+                let diff = Paragraph::new(diff_text).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(self.theme.styles.border)
+                        .title("Changes on Apply")
+                );
+                frame.render_widget(diff, chunks[2]);
+            }
 
             // Render help
-            let help_text = "e: Edit • d: Delete • Esc: Back • q: Quit";
+            let help_text = "e: Edit • a: Apply • d: Delete • Ctrl+C: Copy value • Esc: Back • q: Quit";
             let help = Paragraph::new(help_text)
                 .style(self.theme.styles.help)
                 .alignment(Alignment::Center)
@@ -95,7 +316,7 @@ impl ProfileViewScreen {
                         .border_style(self.theme.styles.border)
                         .title("Controls")
                 );
-            frame.render_widget(help, chunks[2]);
+            frame.render_widget(help, chunks[3]);
         } else {
             let error_msg = format!("Profile '{}' not found", self.profile_name);
             let error = Paragraph::new(error_msg)
@@ -113,51 +334,43 @@ impl ProfileViewScreen {
         Ok(())
     }
 
-    /// Render profile details
-    fn render_profile_details(&self, frame: &mut Frame, area: Rect, profile: &Profile) -> Result<()> {
-        let details = vec![
-            format!("Name: {}", profile.name),
-            format!("Git User Name: {}", profile.git_user_name.as_deref().unwrap_or("Not set")),
-            format!("Git User Email: {}", profile.git_user_email),
-            format!("Key Type: {:?}", profile.key_type),
-            format!("Signing Key: {}", profile.signing_key.as_deref().unwrap_or("Not set")),
-            format!("Vault Name: {}", profile.vault_name.as_deref().unwrap_or("Not set")),
-            format!("SSH Key Title: {}", profile.ssh_key_title.as_deref().unwrap_or("Not set")),
-            format!("Scope: {}", profile.scope.as_ref().map(|s| format!("{:?}", s)).unwrap_or("Not set".to_string())),
-            format!("1Password: {}", if profile.one_password { "Yes" } else { "No" }),
-            format!("Match Patterns: {}", if profile.match_patterns.is_empty() { "None" } else { &profile.match_patterns.join(", ") }),
-            format!("Repositories: {}", if profile.repos.is_empty() { "None" } else { &profile.repos.join(", ") }),
-            format!("Include If Dirs: {}", if profile.include_if_dirs.is_empty() { "None" } else { &profile.include_if_dirs.join(", ") }),
-            format!("Host Patterns: {}", if profile.host_patterns.is_empty() { "None" } else { &profile.host_patterns.join(", ") }),
-        ];
-
-        let items: Vec<ListItem> = details
-            .into_iter()
-            .map(|detail| ListItem::new(detail))
+    /// Scopes set for individual git config keys, e.g. `commit.gpgsign: Local`
+    pub fn field_scopes_label(&self, profile: &Profile) -> String {
+        let mut scopes: Vec<String> = profile
+            .per_field_scope
+            .iter()
+            .map(|(key, scope)| format!("{}: {:?}", key, scope))
             .collect();
+        if scopes.is_empty() {
+            return "None".to_string();
+        }
+        scopes.sort();
+        scopes.join(", ")
+    }
 
-        let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(self.theme.styles.border)
-                    .title("Profile Details")
-            )
-            .style(self.theme.styles.base);
-
-        frame.render_widget(list, area);
-        Ok(())
+    /// Scope the profile would be applied with, marking inferred scopes
+    pub fn effective_scope_label(&self, profile: &Profile) -> String {
+        let scope = profile.effective_scope(self.in_repo);
+        if profile.scope.is_some() {
+            format!("{:?}", scope)
+        } else {
+            format!("{:?} (inferred)", scope)
+        }
     }
 }
 
 impl Component for ProfileViewScreen {
-    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) -> Result<()> {
+    fn render(&self, frame: &mut Frame, area: Rect, _theme: &Theme) -> Result<()> {
         self.render_profile_view(frame, area)
     }
 
     fn handle_event(&mut self, event: Event) -> Result<ComponentAction> {
         match event {
             Event::Key(key_event) => {
+                if key_event.code == KeyCode::Char('c') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    return self.copy_selected_field();
+                }
+
                 match key_event.code {
                     KeyCode::Char('e') => {
                         if self.profile.is_some() {
@@ -166,6 +379,13 @@ impl Component for ProfileViewScreen {
                             Ok(ComponentAction::None)
                         }
                     }
+                    KeyCode::Char('a') => {
+                        if self.profile.is_some() {
+                            Ok(ComponentAction::Return(self.profile_name.clone()))
+                        } else {
+                            Ok(ComponentAction::None)
+                        }
+                    }
                     KeyCode::Char('d') => {
                         if self.profile.is_some() {
                             Ok(ComponentAction::ShowPopup(format!("Delete profile '{}'?", self.profile_name)))
@@ -179,9 +399,18 @@ impl Component for ProfileViewScreen {
                     KeyCode::Esc => {
                         Ok(ComponentAction::NavigateBack)
                     }
+                    KeyCode::Up | KeyCode::Down | KeyCode::Home | KeyCode::End
+                    | KeyCode::PageUp | KeyCode::PageDown => {
+                        self.table.handle_event(Event::Key(key_event))
+                    }
                     _ => Ok(ComponentAction::None),
                 }
             }
+            Event::Mouse(_) => self.table.handle_event(event),
+            Event::Tick if self.last_refresh.elapsed() >= GIT_CONFIG_REFRESH_INTERVAL => {
+                self.refresh()?;
+                Ok(ComponentAction::Refresh)
+            }
             _ => Ok(ComponentAction::None),
         }
     }
@@ -203,7 +432,9 @@ impl Screen for ProfileViewScreen {
     fn screen_help(&self) -> Vec<(&str, &str)> {
         vec![
             ("e", "Edit profile"),
+            ("a", "Apply profile"),
             ("d", "Delete profile"),
+            ("Ctrl+C", "Copy selected value"),
             ("Esc", "Go back"),
             ("q", "Quit"),
         ]
@@ -219,46 +450,12 @@ mod tests {
     use super::*;
     use crate::{
         config::types::{KeyType, Scope},
-        profile::ProfileManager,
+        external::git::MockGitWrapper,
+        tui::clipboard::MockClipboard,
+        profile::mock::MockProfileManager,
     };
     use std::sync::Arc;
 
-    struct MockProfileManager {
-        profiles: Vec<Profile>,
-    }
-
-    impl MockProfileManager {
-        fn new(profiles: Vec<Profile>) -> Self {
-            Self { profiles }
-        }
-    }
-
-    impl ProfileManager for MockProfileManager {
-        fn create(&self, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn read(&self, name: &str) -> Result<Option<Profile>> {
-            Ok(self.profiles.iter().find(|p| p.name == name).cloned())
-        }
-
-        fn update(&self, _name: &str, _profile: Profile) -> Result<()> {
-            Ok(())
-        }
-
-        fn delete(&self, _name: &str) -> Result<()> {
-            Ok(())
-        }
-
-        fn list(&self) -> Result<Vec<Profile>> {
-            Ok(self.profiles.clone())
-        }
-
-        fn exists(&self, name: &str) -> Result<bool> {
-            Ok(self.profiles.iter().any(|p| p.name == name))
-        }
-    }
-
     fn create_test_profile(name: &str) -> Profile {
         Profile {
             name: name.to_string(),
@@ -277,13 +474,22 @@ mod tests {
             include_if_dirs: vec!["/home/user/work".to_string()],
             host_patterns: vec!["*.work.com".to_string()],
             one_password: true,
+            require_passphrase: false,
+            is_archived: false,
+            description: None,
+            per_field_scope: HashMap::new(),
+            git_hooks_dir: None,
+            allowed_signers_content: None,
+            secret_store: None,
+            rotated_key_item: None,
+            tags: vec![],
         }
     }
 
     #[test]
     fn test_profile_view_screen_creation() {
         let profile = create_test_profile("test");
-        let manager = Arc::new(MockProfileManager::new(vec![profile]));
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
         let screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager).unwrap();
         
         assert_eq!(screen.profile_name(), "test");
@@ -292,9 +498,26 @@ mod tests {
         assert_eq!(screen.screen_type(), ScreenType::ProfileView("test".to_string()));
     }
 
+    #[test]
+    fn test_profile_view_effective_scope() {
+        let mut profile = create_test_profile("test");
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile.clone()]));
+        let screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager).unwrap();
+        assert_eq!(screen.effective_scope_label(&profile), "Local");
+
+        assert_eq!(screen.field_scopes_label(&profile), "None");
+        profile.per_field_scope.insert("commit.gpgsign".to_string(), Scope::Local);
+        assert_eq!(screen.field_scopes_label(&profile), "commit.gpgsign: Local");
+
+        profile.scope = None;
+        assert_eq!(screen.effective_scope_label(&profile), "Global (inferred)");
+        let screen = screen.with_in_repo(true);
+        assert_eq!(screen.effective_scope_label(&profile), "Local (inferred)");
+    }
+
     #[test]
     fn test_profile_view_screen_not_found() {
-        let manager = Arc::new(MockProfileManager::new(vec![]));
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![]));
         let screen = ProfileViewScreen::new("missing".to_string(), Theme::default(), manager).unwrap();
         
         assert_eq!(screen.profile_name(), "missing");
@@ -304,7 +527,7 @@ mod tests {
     #[test]
     fn test_profile_view_event_handling() {
         let profile = create_test_profile("test");
-        let manager = Arc::new(MockProfileManager::new(vec![profile]));
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
         let mut screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager).unwrap();
         
         // Test edit event
@@ -334,7 +557,7 @@ mod tests {
     #[test]
     fn test_profile_view_screen_help() {
         let profile = create_test_profile("test");
-        let manager = Arc::new(MockProfileManager::new(vec![profile]));
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
         let screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager).unwrap();
         
         let help = screen.screen_help();
@@ -345,7 +568,7 @@ mod tests {
 
     #[test]
     fn test_profile_view_events_with_missing_profile() {
-        let manager = Arc::new(MockProfileManager::new(vec![]));
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![]));
         let mut screen = ProfileViewScreen::new("missing".to_string(), Theme::default(), manager).unwrap();
         
         // Test edit event with missing profile
@@ -362,12 +585,134 @@ mod tests {
     #[test]
     fn test_profile_view_refresh() {
         let profile = create_test_profile("test");
-        let manager = Arc::new(MockProfileManager::new(vec![profile]));
-        let mut screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager).unwrap();
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile]).track_calls(true));
+        let mut screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager.clone()).unwrap();
         
         // Refresh should work
         let result = screen.refresh();
         assert!(result.is_ok());
         assert!(screen.profile().is_some());
+        assert_eq!(manager.read_call_count(), 2);
+        assert!(manager.was_called_with_read("test"));
+    }
+
+    fn create_git_screen(git: &MockGitWrapper) -> ProfileViewScreen {
+        let profile = create_test_profile("test");
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let mut screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager)
+            .unwrap()
+            .with_git_wrapper(Arc::new(git.clone()));
+        screen.refresh().unwrap();
+        screen
+    }
+
+    #[test]
+    fn test_profile_view_compares_git_config() {
+        let git = MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Local,
+            HashMap::from([
+                ("user.name".to_string(), "Test User".to_string()),
+                ("user.email".to_string(), "other@example.com".to_string()),
+                ("gpg.format".to_string(), "ssh".to_string()),
+            ]),
+        );
+        let screen = create_git_screen(&git);
+        let rows = screen.field_rows(screen.profile().unwrap());
+        let row = |field: &str| rows.iter().find(|row| row.field == field).unwrap().clone();
+
+        assert!(!row("Git User Name").differs());
+        assert!(row("Git User Email").differs());
+        assert_eq!(row("Git User Email").git_value.as_deref(), Some("other@example.com"));
+        assert!(!row("Signing Format").differs());
+        assert!(row("Signing Key").differs());
+        assert_eq!(row("Signing Key").git_value, None);
+        assert!(!row("Vault Name").differs());
+
+        let highlighted: Vec<&str> = screen
+            .table
+            .state()
+            .rows
+            .iter()
+            .filter(|row| row.highlighted)
+            .map(|row| row.cells[0].as_str())
+            .collect();
+        assert_eq!(highlighted, ["Git User Email", "Signing Key"]);
+    }
+
+    #[test]
+    fn test_profile_view_shows_diff() {
+        let git = MockGitWrapper::new().with_scoped_config(
+            GitConfigScope::Local,
+            HashMap::from([
+                ("user.name".to_string(), "Test User".to_string()),
+                ("user.email".to_string(), "other@example.com".to_string()),
+            ]),
+        );
+        let screen = create_git_screen(&git);
+        let status = |field: &str| {
+            screen.diff_lines().iter().find(|line| line.field == field).map(|line| line.status)
+        };
+
+        assert_eq!(status("user.name"), Some(DiffStatus::Unchanged));
+        assert_eq!(status("user.email"), Some(DiffStatus::Changed));
+        assert_eq!(status("commit.gpgsign"), Some(DiffStatus::Added));
+
+        let text: Vec<String> = screen.diff_text().iter().map(|line| line.to_string()).collect();
+        assert!(text.contains(&"- user.email = other@example.com".to_string()));
+        assert!(text.contains(&"+ user.email = test@example.com".to_string()));
+        assert!(text.contains(&"  user.name = Test User".to_string()));
+    }
+
+    #[test]
+    fn test_profile_view_without_git_config() {
+        let profile = create_test_profile("test");
+        let manager = Arc::new(MockProfileManager::with_profiles(vec![profile]));
+        let screen = ProfileViewScreen::new("test".to_string(), Theme::default(), manager).unwrap();
+
+        assert!(screen.table.state().rows.iter().all(|row| !row.highlighted));
+        assert!(screen.table.state().rows.iter().all(|row| row.cells[2].is_empty()));
+    }
+
+    #[test]
+    fn test_profile_view_refetches_on_tick() {
+        let git = MockGitWrapper::new();
+        let mut screen = create_git_screen(&git);
+        git.set_config("user.email", "test@example.com", GitConfigScope::Local).unwrap();
+
+        // Ticks before the interval has passed keep the fetched config
+        assert_eq!(screen.handle_event(Event::Tick).unwrap(), ComponentAction::None);
+        assert!(screen.selected_field().is_some());
+        let email = |screen: &ProfileViewScreen| {
+            screen
+                .field_rows(screen.profile().unwrap())
+                .into_iter()
+                .find(|row| row.field == "Git User Email")
+                .unwrap()
+        };
+        assert!(email(&screen).differs());
+
+        screen.last_refresh = Instant::now() - GIT_CONFIG_REFRESH_INTERVAL;
+        assert_eq!(screen.handle_event(Event::Tick).unwrap(), ComponentAction::Refresh);
+        assert!(!email(&screen).differs());
+    }
+
+    #[test]
+    fn test_profile_view_apply_and_copy() {
+        let git = MockGitWrapper::new();
+        let clipboard = Arc::new(MockClipboard::new());
+        let mut screen = create_git_screen(&git).with_clipboard(ClipboardHelper::with_clipboard(clipboard.clone()));
+
+        let apply = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty());
+        let result = screen.handle_event(Event::Key(apply)).unwrap();
+        assert_eq!(result, ComponentAction::Return("test".to_string()));
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::empty());
+        screen.handle_event(Event::Key(down)).unwrap();
+        assert_eq!(screen.selected_field().unwrap().field, "Git User Name");
+
+        let copy = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let result = screen.handle_event(Event::Key(copy)).unwrap();
+        assert_eq!(result, ComponentAction::ShowPopup("Copied Git User Name to clipboard".to_string()));
+        assert_eq!(clipboard.contents(), vec!["Test User".to_string()]);
     }
 }
\ No newline at end of file
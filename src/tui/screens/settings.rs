@@ -0,0 +1,376 @@
+//! Settings screen for the TUI application.
+//!
+//! This module provides a form for the auto-detection settings: a checkbox for
+//! each detection source and the minimum confidence a match needs. Saving
+//! writes them to `detection.toml` in the config directory.
+
+use crate::{
+    detection::DetectionConfig,
+    error::Result,
+    tui::{
+        Component, ComponentAction, Event, Theme,
+        screens::{Screen, ScreenType},
+        components::{FormComponent, FormField, FieldType, ValidationRule, ValidationRuleType},
+    },
+};
+use ratatui::{
+    Frame,
+    layout::{Rect, Layout, Direction, Constraint, Alignment},
+    widgets::{Block, Borders, Paragraph},
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::any::Any;
+use std::path::{Path, PathBuf};
+
+/// The boolean `DetectionConfig` fields with their label and help text
+const CHECKBOXES: [(&str, &str, &str); 8] = [
+    ("check_remote_url", "Match remote URLs", "Match profiles against the repository's remote URLs"),
+    ("check_directory", "Match directories", "Match profiles against the repository path"),
+    ("check_include_if", "Match includeIf directories", "Match the directories of profiles' includeIf sections"),
+    ("check_hostname", "Match hostname", "Match profiles against this machine's hostname"),
+    ("check_git_config", "Match git config", "Match the identity already set in the git config"),
+    ("check_git_notes", "Match git notes", "Match the profile named in the git-setup note of HEAD"),
+    ("check_profile_hints", "Match profile hints", "Match the profiles listed in git-setup.profile-hints"),
+    ("enable_cache", "Cache results", "Cache detection results"),
+];
+
+/// Form field holding `DetectionConfig::min_confidence`
+const MIN_CONFIDENCE_FIELD: &str = "min_confidence";
+
+/// The boolean setting of `config` edited by the checkbox `name`
+fn flag_mut<'a>(config: &'a mut DetectionConfig, name: &str) -> Option<&'a mut bool> {
+    match name {
+        "check_remote_url" => Some(&mut config.check_remote_url),
+        "check_directory" => Some(&mut config.check_directory),
+        "check_include_if" => Some(&mut config.check_include_if),
+        "check_hostname" => Some(&mut config.check_hostname),
+        "check_git_config" => Some(&mut config.check_git_config),
+        "check_git_notes" => Some(&mut config.check_git_notes),
+        "check_profile_hints" => Some(&mut config.check_profile_hints),
+        "enable_cache" => Some(&mut config.enable_cache),
+        _ => None,
+    }
+}
+
+/// Check that `value` is a confidence between 0 and 1
+fn is_confidence(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(|confidence| (0.0..=1.0).contains(&confidence))
+}
+
+/// Detection settings screen
+pub struct SettingsScreen {
+    form: FormComponent,
+    theme: Theme,
+    config: DetectionConfig,
+    path: PathBuf,
+}
+
+impl SettingsScreen {
+    /// Create a settings screen editing `config`, saving it to `path`
+    pub fn new(theme: Theme, config: DetectionConfig, path: impl Into<PathBuf>) -> Self {
+        let mut form = FormComponent::new("Detection Settings");
+
+        for (index, (name, label, help)) in CHECKBOXES.iter().enumerate() {
+            let mut field = FormField::new(name, label, FieldType::Checkbox);
+            field.set_help(help).set_tab_index(index);
+            form.add_field(field);
+        }
+
+        let mut min_confidence_field = FormField::new(MIN_CONFIDENCE_FIELD, "Minimum Confidence", FieldType::Number);
+        min_confidence_field.set_required(true)
+            .set_placeholder("0.6")
+            .set_help("Confidence a detected profile needs to be used, between 0.0 and 1.0")
+            .set_tab_index(CHECKBOXES.len())
+            .add_validation_rule(ValidationRule {
+                name: "confidence_range".to_string(),
+                rule_type: ValidationRuleType::Custom(is_confidence),
+                message: "Confidence must be a number between 0.0 and 1.0".to_string(),
+            });
+        form.add_field(min_confidence_field);
+
+        let mut screen = Self {
+            form,
+            theme,
+            config,
+            path: path.into(),
+        };
+        screen.load_form();
+        screen
+    }
+
+    /// Create a settings screen for the settings saved in the platform config directory
+    pub fn from_platform_default(theme: Theme) -> Result<Self> {
+        let path = DetectionConfig::default_path()?;
+        let config = DetectionConfig::load(&path)?;
+        Ok(Self::new(theme, config, path))
+    }
+
+    /// Get the saved settings
+    pub fn config(&self) -> &DetectionConfig {
+        &self.config
+    }
+
+    /// Get the path the settings are saved to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Fill the form with the saved settings
+    fn load_form(&mut self) {
+        let mut config = self.config.clone();
+        let state = self.form.state_mut();
+        for (name, _, _) in CHECKBOXES {
+            if let (Some(field), Some(value)) = (state.fields.get_mut(name), flag_mut(&mut config, name)) {
+                field.set_value(&value.to_string());
+            }
+        }
+        if let Some(field) = state.fields.get_mut(MIN_CONFIDENCE_FIELD) {
+            field.set_value(&config.min_confidence.to_string());
+        }
+        state.mark_clean();
+    }
+
+    /// Build the settings from the current form data
+    ///
+    /// Settings the form does not show, such as the custom extractors, are kept.
+    fn config_from_form(&self) -> DetectionConfig {
+        let form_data = self.form.state().get_data();
+        let mut config = self.config.clone();
+        for (name, _, _) in CHECKBOXES {
+            if let Some(value) = flag_mut(&mut config, name) {
+                *value = form_data.get(name).is_some_and(|value| value == "true");
+            }
+        }
+        if let Some(min_confidence) = form_data
+            .get(MIN_CONFIDENCE_FIELD)
+            .filter(|value| is_confidence(value))
+            .and_then(|value| value.parse().ok())
+        {
+            config.min_confidence = min_confidence;
+        }
+        config
+    }
+
+    /// Write the settings in the form to disk
+    pub fn save(&mut self) -> Result<()> {
+        let config = self.config_from_form();
+        config.save(&self.path)?;
+        self.config = config;
+        self.form.state_mut().mark_clean();
+        Ok(())
+    }
+
+    /// Handle form submission
+    fn handle_save(&mut self) -> Result<ComponentAction> {
+        if !self.form.state_mut().validate_all() {
+            return Ok(ComponentAction::ShowPopup("Please fix validation errors before saving".to_string()));
+        }
+
+        match self.save() {
+            Ok(()) => Ok(ComponentAction::ShowPopup(format!("Settings saved to {}", self.path.display()))),
+            Err(e) => Ok(ComponentAction::ShowError(format!("Error saving settings: {}", e))),
+        }
+    }
+
+    /// Flip the focused field if it is a checkbox, returning whether it was
+    fn toggle_checkbox(&mut self) -> Result<bool> {
+        let state = self.form.state_mut();
+        let Some(field) = state.current_field().filter(|field| field.field_type == FieldType::Checkbox) else {
+            return Ok(false);
+        };
+        let (name, checked) = (field.name.clone(), field.value == "true");
+        state.set_field_value(&name, &(!checked).to_string())?;
+        Ok(true)
+    }
+
+    /// Render the settings screen
+    fn render_settings(&self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Title
+                Constraint::Min(0),     // Form
+                Constraint::Length(3),  // Help
+            ])
+            .split(area);
+
+        let title = Paragraph::new("Detection Settings")
+            .style(self.theme.styles.title)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.styles.border)
+            );
+        frame.render_widget(title, chunks[0]);
+
+        self.form.render(frame, chunks[1], &self.theme)?;
+
+        let help_text = "↑/↓: Navigate • Space: Toggle • Ctrl+S: Save • Esc: Back • F1: Field help";
+        let help = Paragraph::new(help_text)
+            .style(self.theme.styles.help)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.styles.border)
+                    .title("Controls")
+            );
+        frame.render_widget(help, chunks[2]);
+
+        Ok(())
+    }
+}
+
+impl Component for SettingsScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, _theme: &Theme) -> Result<()> {
+        self.render_settings(frame, area)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<ComponentAction> {
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Char('s') if key_event.modifiers.contains(KeyModifiers::CONTROL) => self.handle_save(),
+                KeyCode::Char(' ') if self.toggle_checkbox()? => Ok(ComponentAction::None),
+                _ => match self.form.handle_event(event)? {
+                    // Enter on the last field submits the form
+                    ComponentAction::Return(_) => self.handle_save(),
+                    action => Ok(action),
+                },
+            },
+            _ => Ok(ComponentAction::None),
+        }
+    }
+}
+
+impl Screen for SettingsScreen {
+    fn title(&self) -> &str {
+        "Settings"
+    }
+
+    fn screen_type(&self) -> ScreenType {
+        ScreenType::Settings
+    }
+
+    fn screen_help(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("↑/↓", "Navigate between settings"),
+            ("Space", "Toggle a checkbox"),
+            ("Ctrl+S", "Save settings"),
+            ("Esc", "Back to the main menu"),
+        ]
+    }
+
+    fn can_exit(&self) -> bool {
+        !self.form.state().is_dirty()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::{ScreenManager, screens::main_menu::MainMenuScreen};
+    use crossterm::event::KeyEvent;
+    use tempfile::TempDir;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn ctrl_s() -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+    }
+
+    fn create_test_screen(dir: &TempDir) -> SettingsScreen {
+        SettingsScreen::new(Theme::default(), DetectionConfig::default(), dir.path().join("detection.toml"))
+    }
+
+    #[test]
+    fn test_settings_screen_creation() {
+        let dir = TempDir::new().unwrap();
+        let screen = create_test_screen(&dir);
+
+        assert_eq!(screen.title(), "Settings");
+        assert_eq!(screen.screen_type(), ScreenType::Settings);
+        assert!(screen.can_exit());
+
+        let state = screen.form.state();
+        assert_eq!(state.field_order.len(), CHECKBOXES.len() + 1);
+        for (name, _, _) in CHECKBOXES {
+            assert_eq!(state.fields[name].field_type, FieldType::Checkbox);
+            assert_eq!(state.fields[name].value, "true");
+        }
+        assert_eq!(state.fields[MIN_CONFIDENCE_FIELD].field_type, FieldType::Number);
+        assert_eq!(state.fields[MIN_CONFIDENCE_FIELD].value, "0.6");
+    }
+
+    #[test]
+    fn test_settings_toggle_and_save() {
+        let dir = TempDir::new().unwrap();
+        let mut screen = create_test_screen(&dir);
+        screen.config.custom_extractors = vec!["jira_project".to_string()];
+
+        // The first field is the remote URL checkbox
+        assert_eq!(screen.handle_event(key(KeyCode::Char(' '))).unwrap(), ComponentAction::None);
+        assert!(!screen.can_exit());
+        screen.form.state_mut().set_field_value(MIN_CONFIDENCE_FIELD, "0.8").unwrap();
+
+        assert!(matches!(screen.handle_event(ctrl_s()).unwrap(), ComponentAction::ShowPopup(_)));
+        assert!(screen.can_exit());
+
+        let saved = DetectionConfig::load(screen.path()).unwrap();
+        assert!(!saved.check_remote_url);
+        assert!(saved.check_directory);
+        assert_eq!(saved.min_confidence, 0.8);
+        assert_eq!(saved.custom_extractors, vec!["jira_project".to_string()]);
+        assert_eq!(&saved, screen.config());
+    }
+
+    #[test]
+    fn test_settings_invalid_confidence_is_not_saved() {
+        let dir = TempDir::new().unwrap();
+        let mut screen = create_test_screen(&dir);
+
+        screen.form.state_mut().set_field_value(MIN_CONFIDENCE_FIELD, "1.5").unwrap();
+        assert!(matches!(screen.handle_event(ctrl_s()).unwrap(), ComponentAction::ShowPopup(_)));
+        assert!(!screen.path().exists());
+        assert!(!screen.can_exit());
+
+        // Esc asks before discarding unsaved changes
+        assert!(matches!(screen.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::ShowPopup(_)));
+        screen.load_form();
+        assert_eq!(screen.handle_event(key(KeyCode::Esc)).unwrap(), ComponentAction::NavigateBack);
+    }
+
+    #[test]
+    fn test_settings_space_ignored_outside_checkboxes() {
+        let dir = TempDir::new().unwrap();
+        let mut screen = create_test_screen(&dir);
+        let state = screen.form.state_mut();
+        state.current_field = state.field_order.iter().position(|name| name == MIN_CONFIDENCE_FIELD).unwrap();
+
+        screen.handle_event(key(KeyCode::Char(' '))).unwrap();
+        assert_eq!(screen.form.state().fields[MIN_CONFIDENCE_FIELD].value, "0.6");
+    }
+
+    #[test]
+    fn test_settings_reached_from_main_menu() {
+        let dir = TempDir::new().unwrap();
+        let mut manager = ScreenManager::new(Theme::default());
+        manager.register_screen(Box::new(MainMenuScreen::new(Theme::default())));
+        manager.register_screen(Box::new(create_test_screen(&dir)));
+        manager.navigate_to(ScreenType::Main).unwrap();
+
+        let action = manager.handle_event(key(KeyCode::Char('s'))).unwrap();
+        assert_eq!(action, ComponentAction::NavigateTo(ScreenType::Settings));
+        manager.navigate_to(ScreenType::Settings).unwrap();
+        assert_eq!(manager.current_screen(), Some(&ScreenType::Settings));
+
+        let screen = manager.current_screen_mut().unwrap();
+        assert!(screen.as_any().downcast_ref::<SettingsScreen>().is_some());
+    }
+}
@@ -1,4 +1,5 @@
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{
         disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
@@ -28,7 +29,7 @@ impl TerminalManager {
         std::panic::set_hook(Box::new(move |panic_info| {
             // Restore terminal before printing panic
             let _ = disable_raw_mode();
-            let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+            let _ = execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen, Show);
 
             // Call original panic hook
             original_hook(panic_info);
@@ -38,7 +39,7 @@ impl TerminalManager {
         enable_raw_mode()?;
 
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, Hide, SetTitle("Git Setup"))?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture, Hide, SetTitle("Git Setup"))?;
 
         let backend = CrosstermBackend::new(stdout);
         let terminal = RatatuiTerminal::new(backend)?;
@@ -59,6 +60,7 @@ impl TerminalManager {
 
         execute!(
             self.terminal.backend_mut(),
+            DisableMouseCapture,
             LeaveAlternateScreen,
             Show
         )?;
@@ -1,5 +1,7 @@
+use crate::error::{GitSetupError, Result};
 use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path, str::FromStr};
 
 /// Theme configuration
 #[derive(Debug, Clone)]
@@ -9,7 +11,7 @@ pub struct Theme {
     pub styles: ThemeStyles,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ThemeColors {
     pub background: Color,
     pub foreground: Color,
@@ -39,79 +41,116 @@ pub struct ThemeStyles {
     pub key: Style,
 }
 
+/// Built-in dark theme.
+const DARK_THEME: &str = include_str!("themes/dark.toml");
+
+/// Built-in light theme.
+const LIGHT_THEME: &str = include_str!("themes/light.toml");
+
+/// A theme file: an optional name and colours by semantic name.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: Option<String>,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+impl ThemeColors {
+    /// Semantic names a theme file can set.
+    pub const NAMES: &'static [&'static str] = &[
+        "background", "foreground", "primary", "secondary", "success", "warning",
+        "error", "info", "border", "highlight", "muted",
+    ];
+
+    /// Style names a theme file can set, with the colour each one derives from.
+    ///
+    /// Setting one sets that colour, so `selected` also recolours titles.
+    pub const STYLE_NAMES: &'static [(&'static str, &'static str)] = &[
+        ("selected", "primary"), ("title", "primary"), ("key", "secondary"), ("help", "muted"),
+    ];
+
+    /// The colour named `name`, or the one the style named `name` derives from.
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        let name = Self::STYLE_NAMES
+            .iter()
+            .find(|(style, _)| *style == name)
+            .map_or(name, |(_, color)| color);
+        Some(match name {
+            "background" => &mut self.background,
+            "foreground" => &mut self.foreground,
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "info" => &mut self.info,
+            "border" => &mut self.border,
+            "highlight" => &mut self.highlight,
+            "muted" => &mut self.muted,
+            _ => return None,
+        })
+    }
+}
+
 impl Theme {
     /// Default dark theme
     pub fn dark() -> Self {
-        let colors = ThemeColors {
-            background: Color::Black,
-            foreground: Color::White,
-            primary: Color::Cyan,
-            secondary: Color::Magenta,
-            success: Color::Green,
-            warning: Color::Yellow,
-            error: Color::Red,
-            info: Color::Blue,
-            border: Color::Gray,
-            highlight: Color::LightCyan,
-            muted: Color::DarkGray,
-        };
-
-        let styles = ThemeStyles {
-            base: Style::default()
-                .fg(colors.foreground)
-                .bg(colors.background),
-            title: Style::default()
-                .fg(colors.primary)
-                .add_modifier(Modifier::BOLD),
-            border: Style::default()
-                .fg(colors.border),
-            highlight: Style::default()
-                .fg(colors.background)
-                .bg(colors.highlight),
-            selected: Style::default()
-                .fg(colors.background)
-                .bg(colors.primary)
-                .add_modifier(Modifier::BOLD),
-            success: Style::default()
-                .fg(colors.success),
-            warning: Style::default()
-                .fg(colors.warning),
-            error: Style::default()
-                .fg(colors.error)
-                .add_modifier(Modifier::BOLD),
-            info: Style::default()
-                .fg(colors.info),
-            help: Style::default()
-                .fg(colors.muted)
-                .add_modifier(Modifier::ITALIC),
-            key: Style::default()
-                .fg(colors.secondary)
-                .add_modifier(Modifier::BOLD),
-        };
-
-        Self {
-            name: "Dark".to_string(),
-            colors,
-            styles,
-        }
+        Self::parse(DARK_THEME, "the built-in dark theme", ThemeColors::default())
+            .expect("the built-in dark theme is valid")
     }
 
     /// Light theme
     pub fn light() -> Self {
-        let colors = ThemeColors {
-            background: Color::White,
-            foreground: Color::Black,
-            primary: Color::Blue,
-            secondary: Color::Magenta,
-            success: Color::Green,
-            warning: Color::Yellow,
-            error: Color::Red,
-            info: Color::Cyan,
-            border: Color::Gray,
-            highlight: Color::LightBlue,
-            muted: Color::DarkGray,
-        };
+        Self::parse(LIGHT_THEME, "the built-in light theme", ThemeColors::default())
+            .expect("the built-in light theme is valid")
+    }
+
+    /// Load a theme from a TOML file mapping semantic names to colours.
+    ///
+    /// Style names such as `selected` set the colour the style derives from,
+    /// see [`ThemeColors::STYLE_NAMES`].
+    /// Colours are names (`red`, `light-cyan`), hex values (`#1e90ff`) or
+    /// palette indexes (`208`); those the file leaves out are taken from the
+    /// dark theme. Unknown names and colours are reported with the file path.
+    pub fn from_toml(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content, &format!("'{}'", path.display()), Self::dark().colors)
+    }
+
+    /// Theme described by the TOML `content`, starting from the `base` colours.
+    ///
+    /// `source` names the content in errors.
+    fn parse(content: &str, source: &str, mut colors: ThemeColors) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(content)?;
+        for (name, value) in &file.colors {
+            let color = colors.color_mut(name).ok_or_else(|| {
+                GitSetupError::Git(format!(
+                    "Unknown theme colour '{}' in {}; expected one of {}",
+                    name,
+                    source,
+                    ThemeColors::NAMES
+                        .iter()
+                        .chain(ThemeColors::STYLE_NAMES.iter().map(|(style, _)| style))
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?;
+            *color = Color::from_str(value).map_err(|_| {
+                GitSetupError::Git(format!(
+                    "Invalid colour '{}' for '{}' in {}; use a colour name such as red or light-blue, \
+                     a hex value such as #1e90ff or a palette index from 0 to 255",
+                    value, name, source
+                ))
+            })?;
+        }
+
+        Ok(Self::with_colors(file.name.unwrap_or_else(|| "Custom".to_string()), colors))
+    }
 
+    /// Theme with the standard styles for `colors`.
+    fn with_colors(name: String, colors: ThemeColors) -> Self {
         let styles = ThemeStyles {
             base: Style::default()
                 .fg(colors.foreground)
@@ -146,7 +185,7 @@ impl Theme {
         };
 
         Self {
-            name: "Light".to_string(),
+            name,
             colors,
             styles,
         }
@@ -247,4 +286,62 @@ mod tests {
         assert_eq!(default_theme.name, "Dark");
     }
 
+    #[test]
+    fn test_builtin_themes_set_every_colour() {
+        let light = Theme::light();
+        assert_eq!(light.colors.primary, Color::Blue);
+        assert_eq!(light.colors.highlight, Color::LightBlue);
+        assert_eq!(light.colors.muted, Color::DarkGray);
+        assert_eq!(light.styles.selected.bg, Some(Color::Blue));
+        assert_eq!(Theme::dark().colors.highlight, Color::LightCyan);
+    }
+
+    #[test]
+    fn test_theme_from_toml() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("solarized.toml");
+        std::fs::write(
+            &path,
+            "name = \"Solarized\"\n[colors]\nbackground = \"#002b36\"\nerror = \"light red\"\nborder = \"240\"\n",
+        )
+        .unwrap();
+
+        let theme = Theme::from_toml(&path).unwrap();
+        assert_eq!(theme.name, "Solarized");
+        assert_eq!(theme.colors.background, Color::Rgb(0x00, 0x2b, 0x36));
+        assert_eq!(theme.colors.error, Color::LightRed);
+        assert_eq!(theme.colors.border, Color::Indexed(240));
+        assert_eq!(theme.styles.base.bg, Some(Color::Rgb(0x00, 0x2b, 0x36)));
+        // Colours the file leaves out come from the dark theme
+        assert_eq!(theme.colors.primary, Color::Cyan);
+    }
+
+    #[test]
+    fn test_theme_from_toml_style_names() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("theme.toml");
+        std::fs::write(&path, "[colors]\nselected = \"#ff8800\"\nhelp = \"gray\"\n").unwrap();
+
+        let theme = Theme::from_toml(&path).unwrap();
+        assert_eq!(theme.colors.primary, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.styles.selected.bg, Some(Color::Rgb(0xff, 0x88, 0x00)));
+        assert_eq!(theme.styles.help.fg, Some(Color::Gray));
+    }
+
+    #[test]
+    fn test_theme_from_toml_unknown_colour() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("theme.toml");
+
+        std::fs::write(&path, "[colors]\nerror = \"crimson-ish\"\n").unwrap();
+        let message = Theme::from_toml(&path).unwrap_err().to_string();
+        assert!(message.contains("Invalid colour 'crimson-ish' for 'error'"), "{}", message);
+        assert!(message.contains("theme.toml"), "{}", message);
+
+        std::fs::write(&path, "[colors]\nselectd = \"red\"\n").unwrap();
+        let message = Theme::from_toml(&path).unwrap_err().to_string();
+        assert!(message.contains("Unknown theme colour 'selectd'"), "{}", message);
+        assert!(message.contains("expected one of background, foreground"), "{}", message);
+    }
+
 }
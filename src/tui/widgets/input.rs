@@ -4,8 +4,23 @@ use ratatui::{
     style::Style,
     widgets::{Block, Widget, StatefulWidget},
 };
+use crate::{
+    error::Result,
+    platform::{PlatformPaths, SystemPlatform},
+};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use unicode_width::UnicodeWidthStr;
 
+/// Maximum number of previous inputs kept in the history.
+pub const MAX_HISTORY_ENTRIES: usize = 20;
+
+/// Default location of the persisted search history
+/// (`~/.local/share/git-setup/search-history.json`, honouring `XDG_DATA_HOME`).
+pub fn search_history_path() -> Result<PathBuf> {
+    Ok(SystemPlatform.data_dir()?.join("search-history.json"))
+}
+
 /// State for input widget
 #[derive(Debug, Default, Clone)]
 pub struct InputState {
@@ -13,6 +28,12 @@ pub struct InputState {
     pub cursor_position: usize,
     pub is_focused: bool,
     pub is_password: bool,
+    /// Previously submitted inputs, oldest first
+    pub history: VecDeque<String>,
+    /// Entry of `history` shown while browsing it with Up/Down
+    pub history_cursor: Option<usize>,
+    /// Input that was being typed when browsing started
+    saved_input: String,
 }
 
 impl InputState {
@@ -71,6 +92,79 @@ impl InputState {
         self.content.clear();
         self.cursor_position = 0;
     }
+
+    /// Replace the content, moving the cursor to the end.
+    pub fn set_content(&mut self, content: String) {
+        self.cursor_position = content.len();
+        self.content = content;
+    }
+
+    /// Show the previous history entry (Up), saving the typed input on the first press.
+    pub fn history_previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let cursor = match self.history_cursor {
+            None => {
+                self.saved_input = self.content.clone();
+                self.history.len() - 1
+            }
+            Some(cursor) => cursor.saturating_sub(1),
+        };
+        self.history_cursor = Some(cursor);
+        self.set_content(self.history[cursor].clone());
+    }
+
+    /// Show the next history entry (Down), or the saved input after the newest entry.
+    pub fn history_next(&mut self) {
+        let Some(cursor) = self.history_cursor else {
+            return;
+        };
+        if cursor + 1 < self.history.len() {
+            self.history_cursor = Some(cursor + 1);
+            self.set_content(self.history[cursor + 1].clone());
+        } else {
+            self.history_cursor = None;
+            let saved = std::mem::take(&mut self.saved_input);
+            self.set_content(saved);
+        }
+    }
+
+    /// Record the content in the history unless it is empty or repeats the last entry.
+    ///
+    /// Returns whether the history changed.
+    pub fn push_history(&mut self) -> bool {
+        self.history_cursor = None;
+        if self.content.is_empty() || self.history.back() == Some(&self.content) {
+            return false;
+        }
+        self.history.push_back(self.content.clone());
+        while self.history.len() > MAX_HISTORY_ENTRIES {
+            self.history.pop_front();
+        }
+        true
+    }
+
+    /// Load the history from a JSON array of strings at `path`; a missing file leaves it empty.
+    pub fn load_history(&mut self, path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let entries: Vec<String> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let skip = entries.len().saturating_sub(MAX_HISTORY_ENTRIES);
+        self.history = entries.into_iter().skip(skip).collect();
+        self.history_cursor = None;
+        Ok(())
+    }
+
+    /// Save the history to `path` as a JSON array of strings, creating parent directories as needed.
+    pub fn save_history(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.history)?)?;
+        Ok(())
+    }
 }
 
 /// Input widget for text entry
@@ -322,4 +416,64 @@ mod tests {
         assert_eq!(state.content, "");
         assert_eq!(state.cursor_position, 0);
     }
+
+    #[test]
+    fn test_history_navigation() {
+        let mut state = InputState::new();
+        for query in ["work", "personal"] {
+            state.set_content(query.to_string());
+            assert!(state.push_history());
+        }
+        state.set_content("wo".to_string());
+
+        state.history_previous();
+        assert_eq!(state.content, "personal");
+        assert_eq!(state.history_cursor, Some(1));
+        state.history_previous();
+        assert_eq!(state.content, "work");
+        // The oldest entry stays selected
+        state.history_previous();
+        assert_eq!(state.content, "work");
+
+        state.history_next();
+        assert_eq!(state.content, "personal");
+        state.history_next();
+        assert_eq!(state.content, "wo");
+        assert_eq!(state.cursor_position, 2);
+        assert_eq!(state.history_cursor, None);
+    }
+
+    #[test]
+    fn test_push_history_skips_empty_and_repeated_input() {
+        let mut state = InputState::with_content("work".to_string());
+        assert!(state.push_history());
+        assert!(!state.push_history());
+        state.clear();
+        assert!(!state.push_history());
+
+        for i in 0..MAX_HISTORY_ENTRIES {
+            state.set_content(format!("query-{}", i));
+            state.push_history();
+        }
+        assert_eq!(state.history.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(state.history.front().map(String::as_str), Some("query-0"));
+    }
+
+    #[test]
+    fn test_history_persistence() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("git-setup").join("search-history.json");
+
+        let mut state = InputState::new();
+        state.load_history(&path).unwrap();
+        assert!(state.history.is_empty());
+
+        state.set_content("work".to_string());
+        state.push_history();
+        state.save_history(&path).unwrap();
+
+        let mut restored = InputState::new();
+        restored.load_history(&path).unwrap();
+        assert_eq!(restored.history, VecDeque::from(["work".to_string()]));
+    }
 }
@@ -1,28 +1,109 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Style, Modifier},
-    widgets::{Block, Widget, StatefulWidget, List, ListItem},
+    widgets::{Block, Widget, StatefulWidget, List, ListItem as RatatuiListItem},
 };
 
+/// Status badge shown at the left margin of a list item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndicatorType {
+    /// ✓, e.g. the applied profile
+    Check,
+    /// ✗, e.g. a failed operation
+    Cross,
+    /// ★, e.g. the default profile
+    Star,
+    /// ⚠, e.g. a profile failing validation
+    Warning,
+    /// Any other single-width symbol
+    Custom(String),
+}
+
+impl IndicatorType {
+    /// Symbol rendered for this indicator
+    pub fn symbol(&self) -> &str {
+        match self {
+            IndicatorType::Check => "✓",
+            IndicatorType::Cross => "✗",
+            IndicatorType::Star => "★",
+            IndicatorType::Warning => "⚠",
+            IndicatorType::Custom(symbol) => symbol,
+        }
+    }
+}
+
+/// Text of a list entry with an optional status indicator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListItem {
+    pub text: String,
+    pub indicator: Option<IndicatorType>,
+}
+
+impl ListItem {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            indicator: None,
+        }
+    }
+
+    /// Show `indicator` in front of the item
+    pub fn with_indicator(mut self, indicator: IndicatorType) -> Self {
+        self.indicator = Some(indicator);
+        self
+    }
+
+    /// Text prefixed with a two column indicator gutter, so items with and
+    /// without an indicator stay aligned
+    pub fn content(&self, show_indicator: bool) -> String {
+        if !show_indicator {
+            return self.text.clone();
+        }
+        match &self.indicator {
+            Some(indicator) => format!("{} {}", indicator.symbol(), self.text),
+            None => format!("  {}", self.text),
+        }
+    }
+}
+
 /// State for selectable list widget
 #[derive(Debug, Default, Clone)]
 pub struct ListState {
     pub items: Vec<String>,
+    /// Indicator of each item, parallel to `items`
+    pub indicators: Vec<Option<IndicatorType>>,
     pub selected: Option<usize>,
     pub offset: usize,
+    /// Area the items were last drawn in, used to map mouse clicks to items
+    pub last_rendered_area: Option<Rect>,
 }
 
 impl ListState {
     pub fn new(items: Vec<String>) -> Self {
         let selected = if items.is_empty() { None } else { Some(0) };
         Self {
+            indicators: vec![None; items.len()],
             items,
             selected,
             offset: 0,
+            last_rendered_area: None,
         }
     }
 
+    /// Create a state from items carrying indicators
+    pub fn with_list_items(items: Vec<ListItem>) -> Self {
+        let mut state = Self::new(Vec::new());
+        state.update_list_items(items);
+        state
+    }
+
+    /// Indicator of the item at `index`
+    pub fn indicator(&self, index: usize) -> Option<&IndicatorType> {
+        self.indicators.get(index).and_then(Option::as_ref)
+    }
+
     pub fn select_next(&mut self) {
         if self.items.is_empty() {
             return;
@@ -70,7 +151,15 @@ impl ListState {
         self.selected.and_then(|i| self.items.get(i))
     }
 
+    /// Replace the items, keeping their indicators
+    pub fn update_list_items(&mut self, items: Vec<ListItem>) {
+        let (texts, indicators) = items.into_iter().map(|item| (item.text, item.indicator)).unzip();
+        self.update_items(texts);
+        self.indicators = indicators;
+    }
+
     pub fn update_items(&mut self, items: Vec<String>) {
+        self.indicators = vec![None; items.len()];
         self.items = items;
         // Reset selection if out of bounds
         if let Some(selected) = self.selected {
@@ -86,6 +175,35 @@ impl ListState {
         }
     }
 
+    /// Handle a mouse event: a left click selects the item under the cursor
+    /// and the wheel moves the selection.
+    ///
+    /// Returns whether the event changed the selection.
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) -> bool {
+        let previous = self.selected;
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(index) = self.item_at(mouse.column, mouse.row) {
+                    self.selected = Some(index);
+                }
+            }
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::ScrollUp => self.select_previous(),
+            _ => {}
+        }
+        self.selected != previous
+    }
+
+    /// Index of the item drawn at the given screen position
+    fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.last_rendered_area?;
+        if column < area.left() || column >= area.right() || row < area.top() || row >= area.bottom() {
+            return None;
+        }
+        let index = self.offset + (row - area.top()) as usize;
+        (index < self.items.len()).then_some(index)
+    }
+
     /// Calculate offset for scrolling
     pub fn calculate_offset(&mut self, visible_height: usize) {
         if let Some(selected) = self.selected {
@@ -105,6 +223,7 @@ pub struct SelectableList<'a> {
     style: Style,
     highlight_style: Style,
     highlight_symbol: &'a str,
+    show_indicators: bool,
 }
 
 impl<'a> SelectableList<'a> {
@@ -114,9 +233,16 @@ impl<'a> SelectableList<'a> {
             style: Style::default(),
             highlight_style: Style::default().add_modifier(Modifier::REVERSED),
             highlight_symbol: "> ",
+            show_indicators: true,
         }
     }
 
+    /// Show or hide item indicators
+    pub fn show_indicators(mut self, show: bool) -> Self {
+        self.show_indicators = show;
+        self
+    }
+
     pub fn block(mut self, block: Block<'a>) -> Self {
         self.block = Some(block);
         self
@@ -152,6 +278,8 @@ impl<'a> StatefulWidget for SelectableList<'a> {
             None => area,
         };
 
+        state.last_rendered_area = Some(list_area);
+
         if list_area.height == 0 || state.items.is_empty() {
             return;
         }
@@ -159,15 +287,23 @@ impl<'a> StatefulWidget for SelectableList<'a> {
         // Update offset for scrolling
         state.calculate_offset(list_area.height as usize);
 
+        // Only reserve the indicator gutter when some item has an indicator
+        let show_indicators = self.show_indicators && state.indicators.iter().any(Option::is_some);
+
         // Create list items
-        let items: Vec<ListItem> = state.items
+        let items: Vec<RatatuiListItem> = state.items
             .iter()
             .skip(state.offset)
             .take(list_area.height as usize)
             .enumerate()
-            .map(|(i, item)| {
+            .map(|(i, text)| {
                 let global_index = i + state.offset;
                 let is_selected = state.selected == Some(global_index);
+                let item = ListItem {
+                    text: text.clone(),
+                    indicator: state.indicator(global_index).cloned(),
+                }
+                .content(show_indicators);
 
                 let content = if is_selected {
                     format!("{}{}", self.highlight_symbol, item)
@@ -181,7 +317,7 @@ impl<'a> StatefulWidget for SelectableList<'a> {
                     self.style
                 };
 
-                ListItem::new(content).style(style)
+                RatatuiListItem::new(content).style(style)
             })
             .collect();
 
@@ -309,6 +445,89 @@ mod tests {
         assert_eq!(state.selected, None);
     }
 
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_list_state_mouse_events() {
+        let items = (1..=5).map(|i| format!("Item {}", i)).collect();
+        let mut state = ListState::new(items);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 5));
+        SelectableList::new()
+            .block(Block::bordered())
+            .render(Rect::new(0, 0, 20, 5), &mut buf, &mut state);
+        assert_eq!(state.last_rendered_area, Some(Rect::new(1, 1, 18, 3)));
+
+        // Clicking the third visible row selects the third item
+        assert!(state.handle_mouse_event(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3)));
+        assert_eq!(state.selected, Some(2));
+
+        // Clicks on the border are ignored
+        assert!(!state.handle_mouse_event(mouse(MouseEventKind::Down(MouseButton::Left), 5, 4)));
+        assert_eq!(state.selected, Some(2));
+
+        assert!(state.handle_mouse_event(mouse(MouseEventKind::ScrollDown, 5, 2)));
+        assert_eq!(state.selected, Some(3));
+        assert!(state.handle_mouse_event(mouse(MouseEventKind::ScrollUp, 5, 2)));
+        assert_eq!(state.selected, Some(2));
+    }
+
+    #[test]
+    fn test_list_item_indicators() {
+        let item = ListItem::new("work").with_indicator(IndicatorType::Check);
+        assert_eq!(item.content(true), "✓ work");
+        assert_eq!(item.content(false), "work");
+        assert_eq!(ListItem::new("plain").content(true), "  plain");
+        assert_eq!(
+            ListItem::new("x").with_indicator(IndicatorType::Custom("!".to_string())).content(true),
+            "! x"
+        );
+    }
+
+    #[test]
+    fn test_list_state_with_list_items() {
+        let mut state = ListState::with_list_items(vec![
+            ListItem::new("work").with_indicator(IndicatorType::Star),
+            ListItem::new("personal"),
+        ]);
+        assert_eq!(state.items, vec!["work".to_string(), "personal".to_string()]);
+        assert_eq!(state.indicator(0), Some(&IndicatorType::Star));
+        assert_eq!(state.indicator(1), None);
+        assert_eq!(state.selected, Some(0));
+
+        // Plain updates drop stale indicators
+        state.update_items(vec!["other".to_string()]);
+        assert_eq!(state.indicator(0), None);
+    }
+
+    #[test]
+    fn test_render_indicators_at_left_margin() {
+        let mut state = ListState::with_list_items(vec![
+            ListItem::new("work").with_indicator(IndicatorType::Check),
+            ListItem::new("personal"),
+        ]);
+        let area = Rect::new(0, 0, 20, 2);
+        let mut buf = Buffer::empty(area);
+        SelectableList::new().render(area, &mut buf, &mut state);
+
+        assert!(line_of(&buf, 0, area.width).starts_with("> ✓ work"));
+        assert!(line_of(&buf, 1, area.width).starts_with("    personal"));
+
+        let mut buf = Buffer::empty(area);
+        SelectableList::new().show_indicators(false).render(area, &mut buf, &mut state);
+        assert!(line_of(&buf, 0, area.width).starts_with("> work"));
+    }
+
+    fn line_of(buf: &Buffer, y: u16, width: u16) -> String {
+        (0..width).map(|x| buf[(x, y)].symbol().to_string()).collect()
+    }
+
     #[test]
     fn test_list_state_offset_calculation() {
         let items: Vec<String> = (0..10).map(|i| format!("Item {}", i)).collect();
@@ -3,5 +3,5 @@ pub mod list;
 pub mod popup;
 
 pub use input::{InputWidget, InputState};
-pub use list::{SelectableList, ListState};
+pub use list::{IndicatorType, ListItem, ListState, SelectableList};
 pub use popup::{PopupWidget, PopupType};
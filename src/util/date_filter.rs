@@ -0,0 +1,198 @@
+//! Date filters for profile activation history.
+//!
+//! `--since` and `--until` accept ISO-8601 dates (`2024-01-15`), RFC 3339
+//! timestamps, relative expressions (`7 days ago`, `yesterday`, `last week`)
+//! or `never` to select profiles that have never been applied.
+
+use crate::error::{GitSetupError, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+
+/// Expression selecting profiles without any recorded activation.
+pub const NEVER: &str = "never";
+
+/// Parse a relative date expression such as `3 days ago` or `last week`.
+pub fn parse_relative(expr: &str) -> Result<DateTime<Utc>> {
+    parse_relative_from(expr, Utc::now())
+}
+
+/// Parse a relative date expression against `now`.
+pub fn parse_relative_from(expr: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let normalized = expr.trim().to_ascii_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["now"] => Ok(now),
+        ["today"] => Ok(start_of_day(now)),
+        ["yesterday"] => Ok(start_of_day(now) - Duration::days(1)),
+        ["last", unit] => Ok(now - unit_duration(unit, 1).ok_or_else(|| invalid(expr))?),
+        [count, unit, "ago"] => {
+            let count = count.parse::<i64>().map_err(|_| invalid(expr))?;
+            Ok(now - unit_duration(unit, count).ok_or_else(|| invalid(expr))?)
+        }
+        _ => Err(invalid(expr)),
+    }
+}
+
+/// Parse an absolute or relative date, returning the instant and whether it named a whole day.
+fn parse_date(expr: &str, now: DateTime<Utc>) -> Result<(DateTime<Utc>, bool)> {
+    let trimmed = expr.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        return Ok((date.and_time(NaiveTime::MIN).and_utc(), true));
+    }
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok((timestamp.with_timezone(&Utc), false));
+    }
+
+    let whole_day = matches!(trimmed.to_ascii_lowercase().as_str(), "today" | "yesterday");
+    Ok((parse_relative_from(trimmed, now)?, whole_day))
+}
+
+/// Length of `count` units, accepting singular and plural unit names.
+fn unit_duration(unit: &str, count: i64) -> Option<Duration> {
+    let days = match unit.trim_end_matches('s') {
+        "minute" | "min" => return Some(Duration::minutes(count)),
+        "hour" => return Some(Duration::hours(count)),
+        "day" => 1,
+        "week" => 7,
+        "month" => 30,
+        "year" => 365,
+        _ => return None,
+    };
+    Some(Duration::days(days * count))
+}
+
+fn start_of_day(instant: DateTime<Utc>) -> DateTime<Utc> {
+    instant.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+fn invalid(expr: &str) -> GitSetupError {
+    GitSetupError::Git(format!(
+        "Invalid date '{}': expected YYYY-MM-DD, '<n> days ago', 'yesterday', 'last week' or 'never'",
+        expr
+    ))
+}
+
+/// Range of activation dates a profile must fall in, as given by `--since`/`--until`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivationDateFilter {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    never: bool,
+}
+
+impl ActivationDateFilter {
+    /// Build a filter from `--since` and `--until` expressions.
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<Self> {
+        Self::parse_from(since, until, Utc::now())
+    }
+
+    /// Build a filter, resolving relative expressions against `now`.
+    ///
+    /// `never` selects profiles without activations, so it cannot be combined
+    /// with a second bound.
+    pub fn parse_from(since: Option<&str>, until: Option<&str>, now: DateTime<Utc>) -> Result<Self> {
+        let is_never = |expr: &str| expr.trim().eq_ignore_ascii_case(NEVER);
+        let mut filter = Self {
+            never: since.is_some_and(is_never) || until.is_some_and(is_never),
+            ..Self::default()
+        };
+        if filter.never {
+            if since.is_some() && until.is_some() {
+                return Err(GitSetupError::Git(
+                    "'never' cannot be combined with another --since/--until bound".to_string(),
+                ));
+            }
+            return Ok(filter);
+        }
+
+        if let Some(expr) = since {
+            filter.since = Some(parse_date(expr, now)?.0);
+        }
+        if let Some(expr) = until {
+            // A date without a time includes the whole of that day
+            let (instant, whole_day) = parse_date(expr, now)?;
+            filter.until = Some(if whole_day { instant + Duration::days(1) } else { instant });
+        }
+        Ok(filter)
+    }
+
+    /// Whether the filter only selects profiles that were never applied.
+    pub fn is_never(&self) -> bool {
+        self.never
+    }
+
+    /// Check whether a profile last applied at `last_activated` passes the filter.
+    pub fn matches(&self, last_activated: Option<&DateTime<Utc>>) -> bool {
+        match last_activated {
+            None => self.never,
+            Some(_) if self.never => false,
+            Some(at) => {
+                self.since.is_none_or(|since| *at >= since) && self.until.is_none_or(|until| *at < until)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-03-20T15:30:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        assert_eq!(parse_relative_from("7 days ago", now()).unwrap(), at("2024-03-13T15:30:00Z"));
+        assert_eq!(parse_relative_from("1 week ago", now()).unwrap(), at("2024-03-13T15:30:00Z"));
+        assert_eq!(parse_relative_from("Last Week", now()).unwrap(), at("2024-03-13T15:30:00Z"));
+        assert_eq!(parse_relative_from("2 hours ago", now()).unwrap(), at("2024-03-20T13:30:00Z"));
+        assert_eq!(parse_relative_from("yesterday", now()).unwrap(), at("2024-03-19T00:00:00Z"));
+        assert_eq!(parse_relative_from("today", now()).unwrap(), at("2024-03-20T00:00:00Z"));
+
+        assert!(parse_relative_from("fortnight", now()).is_err());
+        assert!(parse_relative_from("many days ago", now()).is_err());
+        assert!(parse_relative_from("last century", now()).is_err());
+    }
+
+    #[test]
+    fn test_filter_date_range() {
+        let filter = ActivationDateFilter::parse_from(Some("2024-01-15"), Some("2024-01-31"), now()).unwrap();
+
+        assert!(filter.matches(Some(&at("2024-01-15T00:00:00Z"))));
+        assert!(filter.matches(Some(&at("2024-01-31T23:59:00Z"))));
+        assert!(!filter.matches(Some(&at("2024-02-01T00:00:00Z"))));
+        assert!(!filter.matches(Some(&at("2024-01-14T23:59:00Z"))));
+        assert!(!filter.matches(None));
+    }
+
+    #[test]
+    fn test_filter_relative_since() {
+        let filter = ActivationDateFilter::parse_from(Some("7 days ago"), None, now()).unwrap();
+
+        assert!(filter.matches(Some(&at("2024-03-19T09:00:00Z"))));
+        assert!(!filter.matches(Some(&at("2024-03-01T09:00:00Z"))));
+    }
+
+    #[test]
+    fn test_filter_never() {
+        let filter = ActivationDateFilter::parse_from(Some("never"), None, now()).unwrap();
+
+        assert!(filter.is_never());
+        assert!(filter.matches(None));
+        assert!(!filter.matches(Some(&at("2024-03-19T09:00:00Z"))));
+
+        assert!(ActivationDateFilter::parse_from(Some("never"), Some("yesterday"), now()).is_err());
+        assert!(ActivationDateFilter::parse_from(Some("7 days ago"), Some("never"), now()).is_err());
+    }
+
+    #[test]
+    fn test_filter_rejects_invalid_dates() {
+        assert!(ActivationDateFilter::parse_from(Some("2024-13-45"), None, now()).is_err());
+        assert!(ActivationDateFilter::parse_from(None, Some("soon"), now()).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+//! Small helpers shared across git-setup-rs modules.
+
+pub mod date_filter;